@@ -0,0 +1,152 @@
+//! Derive macros for [`bevy_replicon`](https://docs.rs/bevy_replicon).
+//!
+//! Re-exported from the main crate, don't depend on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, Ident, Index};
+
+/// Maximum number of groups that fit into the change mask.
+const MAX_GROUPS: usize = 64;
+
+/// Derives `FieldGroups` for a struct.
+///
+/// See the trait documentation for details.
+#[proc_macro_derive(FieldGroups, attributes(field_group))]
+pub fn derive_field_groups(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    field_groups(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn field_groups(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "`FieldGroups` can be derived only for structs",
+        ));
+    };
+
+    // Groups in order of their first appearance, each with accessors to its fields.
+    let mut groups: Vec<(Option<Ident>, Vec<TokenStream2>)> = Vec::new();
+    match &data.fields {
+        Fields::Named(fields) => {
+            for field in &fields.named {
+                let ident = field
+                    .ident
+                    .as_ref()
+                    .expect("named fields should have idents");
+                add_field(&mut groups, group_name(field)?, quote! { #ident });
+            }
+        }
+        Fields::Unnamed(fields) => {
+            for (index, field) in fields.unnamed.iter().enumerate() {
+                let index = Index::from(index);
+                add_field(&mut groups, group_name(field)?, quote! { #index });
+            }
+        }
+        Fields::Unit => (),
+    }
+
+    if groups.is_empty() {
+        return Err(Error::new(
+            input.span(),
+            "`FieldGroups` requires at least one field",
+        ));
+    }
+    if groups.len() > MAX_GROUPS {
+        return Err(Error::new(
+            input.span(),
+            format!("number of field groups shouldn't exceed {MAX_GROUPS}"),
+        ));
+    }
+
+    let group_count = groups.len() as u8;
+    let group_indices: Vec<_> = (0..group_count).collect();
+    let serialize_arms = groups.iter().map(|(_, fields)| {
+        quote! {
+            #(::bevy_replicon::core::postcard_utils::to_extend_mut(&self.#fields, message)?;)*
+        }
+    });
+    let deserialize_arms = groups.iter().map(|(_, fields)| {
+        quote! {
+            #(self.#fields = ::bevy_replicon::core::postcard_utils::from_buf(message)?;)*
+        }
+    });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::bevy_replicon::core::replication::field_groups::FieldGroups for #ident #ty_generics #where_clause {
+            const GROUPS: u8 = #group_count;
+
+            fn serialize_group(
+                &self,
+                group: u8,
+                message: &mut ::std::vec::Vec<u8>,
+            ) -> ::bevy_replicon::postcard::Result<()> {
+                match group {
+                    #(#group_indices => { #serialize_arms })*
+                    _ => return ::std::result::Result::Err(::bevy_replicon::postcard::Error::SerdeSerCustom),
+                }
+
+                ::std::result::Result::Ok(())
+            }
+
+            fn deserialize_group(
+                &mut self,
+                group: u8,
+                message: &mut ::bevy_replicon::bytes::Bytes,
+            ) -> ::bevy_replicon::postcard::Result<()> {
+                match group {
+                    #(#group_indices => { #deserialize_arms })*
+                    _ => return ::std::result::Result::Err(::bevy_replicon::postcard::Error::SerdeDeCustom),
+                }
+
+                ::std::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+/// Returns the group name from the `field_group` attribute if present.
+fn group_name(field: &syn::Field) -> syn::Result<Option<Ident>> {
+    let mut name = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("field_group"))
+    {
+        if name.is_some() {
+            return Err(Error::new(
+                attr.span(),
+                "field can belong only to a single group",
+            ));
+        }
+        name = Some(attr.parse_args::<Ident>()?);
+    }
+
+    Ok(name)
+}
+
+/// Adds a field to its named group or creates a new single-field group for it.
+fn add_field(
+    groups: &mut Vec<(Option<Ident>, Vec<TokenStream2>)>,
+    name: Option<Ident>,
+    accessor: TokenStream2,
+) {
+    if let Some(name) = &name {
+        if let Some((_, fields)) = groups
+            .iter_mut()
+            .find(|(group, _)| group.as_ref() == Some(name))
+        {
+            fields.push(accessor);
+            return;
+        }
+    }
+
+    groups.push((name, vec![accessor]));
+}
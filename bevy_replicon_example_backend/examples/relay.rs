@@ -0,0 +1,50 @@
+//! A relay that forwards clients to a server without running a Bevy world.
+//!
+//! Start a server from another example, then run the relay and connect clients to its port.
+//! Prints statistics of forwarded messages every second.
+
+use std::{
+    io,
+    net::{Ipv4Addr, SocketAddr},
+    thread,
+    time::{Duration, Instant},
+};
+
+use bevy_replicon_example_backend::ExampleRelay;
+use clap::Parser;
+
+fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    let server_addr = SocketAddr::from((Ipv4Addr::LOCALHOST, cli.server_port));
+    let mut relay = ExampleRelay::new(cli.port, server_addr)?;
+    println!("relaying port {} to {server_addr}", cli.port);
+
+    let mut last_print = Instant::now();
+    loop {
+        relay.update()?;
+        if last_print.elapsed() >= Duration::from_secs(1) {
+            last_print = Instant::now();
+            println!(
+                "clients: {}, {:?}",
+                relay.connected_clients(),
+                relay.stats()
+            );
+        }
+
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+const PORT: u16 = 5001;
+const SERVER_PORT: u16 = 5000;
+
+#[derive(Parser)]
+struct Cli {
+    /// Port to accept clients on.
+    #[arg(short, long, default_value_t = PORT)]
+    port: u16,
+
+    /// Port of the server to forward clients to.
+    #[arg(short, long, default_value_t = SERVER_PORT)]
+    server_port: u16,
+}
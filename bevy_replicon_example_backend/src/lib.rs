@@ -5,12 +5,14 @@
 
 #[cfg(feature = "client")]
 mod client;
+mod relay;
 #[cfg(feature = "server")]
 mod server;
 mod tcp;
 
 #[cfg(feature = "client")]
 pub use client::*;
+pub use relay::*;
 #[cfg(feature = "server")]
 pub use server::*;
 
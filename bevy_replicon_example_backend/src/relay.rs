@@ -0,0 +1,184 @@
+use std::{
+    io,
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+};
+
+use bevy_replicon::{
+    bytes::Bytes,
+    core::{
+        channels::ReplicationChannel,
+        protocol::{
+            compression_kind::CompressionKind, postcard_utils, protocol_version::ProtocolVersion,
+            replication_epoch::ReplicationEpoch, replicon_tick::RepliconTick,
+            update_message_flags::UpdateMessageFlags,
+        },
+    },
+};
+
+use super::tcp;
+
+/// A relay that forwards messages between clients and a server made for examples.
+///
+/// Doesn't require a Bevy world. Each accepted client gets its own connection to the server,
+/// so the server sees the relay as multiple clients. Messages are forwarded as is and
+/// replication updates from the server are inspected using the wire format from
+/// [`protocol`](bevy_replicon::core::protocol) to collect [`RelayStats`].
+///
+/// Call [`Self::update`] periodically to accept new clients and forward messages.
+pub struct ExampleRelay {
+    listener: TcpListener,
+    server_addr: SocketAddr,
+    peers: Vec<RelayPeer>,
+    stats: RelayStats,
+}
+
+impl ExampleRelay {
+    /// Opens a relay socket on the specified port that forwards clients to the server address.
+    pub fn new(port: u16, server_addr: SocketAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            server_addr,
+            peers: Default::default(),
+            stats: Default::default(),
+        })
+    }
+
+    /// Returns local address if the relay is running.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Returns the number of connected clients.
+    pub fn connected_clients(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Returns statistics of forwarded messages.
+    pub fn stats(&self) -> &RelayStats {
+        &self.stats
+    }
+
+    /// Accepts new clients and forwards all available messages in both directions.
+    ///
+    /// Peers whose connection was closed on any side are dropped.
+    /// Returns an error only if the listener fails.
+    pub fn update(&mut self) -> io::Result<()> {
+        loop {
+            match self.listener.accept() {
+                Ok((client, _)) => {
+                    let server = TcpStream::connect(self.server_addr)?;
+                    for stream in [&client, &server] {
+                        stream.set_nodelay(true)?;
+                        stream.set_nonblocking(true)?;
+                    }
+                    self.peers.push(RelayPeer { client, server });
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let stats = &mut self.stats;
+        self.peers.retain_mut(|peer| {
+            let forwarded = forward(&mut peer.client, &mut peer.server, |_, message| {
+                stats.client_messages += 1;
+                stats.bytes += message.len();
+            })
+            .and_then(|_| {
+                forward(&mut peer.server, &mut peer.client, |channel_id, message| {
+                    stats.server_messages += 1;
+                    stats.bytes += message.len();
+                    if channel_id == ReplicationChannel::Updates as u8 {
+                        stats.inspect_update(message.clone());
+                    }
+                })
+            });
+
+            forwarded.is_ok()
+        });
+
+        Ok(())
+    }
+}
+
+/// Forwards all available messages from one stream to another.
+fn forward(
+    from: &mut TcpStream,
+    to: &mut TcpStream,
+    mut inspect: impl FnMut(u8, &Bytes),
+) -> io::Result<()> {
+    loop {
+        match tcp::read_message(from) {
+            Ok((channel_id, message)) => {
+                (inspect)(channel_id, &message);
+                tcp::send_message(to, channel_id, &message).map_err(io::Error::other)?;
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+struct RelayPeer {
+    client: TcpStream,
+    server: TcpStream,
+}
+
+/// Statistics of messages forwarded by [`ExampleRelay`].
+#[derive(Default, Debug, Clone, Copy)]
+pub struct RelayStats {
+    /// Number of messages forwarded from clients to the server.
+    pub client_messages: usize,
+
+    /// Number of messages forwarded from the server to clients.
+    pub server_messages: usize,
+
+    /// Total size of forwarded messages.
+    pub bytes: usize,
+
+    /// Number of forwarded replication updates.
+    pub updates: usize,
+
+    /// Tick of the last forwarded replication update.
+    pub last_update_tick: Option<RepliconTick>,
+
+    /// Number of replication updates that couldn't be decoded, are compressed or have a different protocol version.
+    pub invalid_updates: usize,
+}
+
+impl RelayStats {
+    /// Reads the header of an update message.
+    fn inspect_update(&mut self, mut message: Bytes) {
+        match read_update_tick(&mut message) {
+            Ok(Some(tick)) => {
+                self.updates += 1;
+                self.last_update_tick = Some(tick);
+            }
+            Ok(None) | Err(_) => self.invalid_updates += 1,
+        }
+    }
+}
+
+/// Returns tick of an update message or [`None`] if its version doesn't match or it's compressed.
+fn read_update_tick(message: &mut Bytes) -> bevy_replicon::postcard::Result<Option<RepliconTick>> {
+    let version: ProtocolVersion = postcard_utils::from_buf(message)?;
+    if version != ProtocolVersion::CURRENT {
+        return Ok(None);
+    }
+
+    // Relay doesn't decompress messages.
+    let compression: CompressionKind = postcard_utils::from_buf(message)?;
+    if compression != CompressionKind::None {
+        return Ok(None);
+    }
+
+    let flags: UpdateMessageFlags = postcard_utils::from_buf(message)?;
+    if flags.contains(UpdateMessageFlags::EPOCH) {
+        let _epoch: ReplicationEpoch = postcard_utils::from_buf(message)?;
+    }
+    let tick = postcard_utils::from_buf(message)?;
+
+    Ok(Some(tick))
+}
@@ -1,8 +1,10 @@
 use std::io;
 
 use bevy::prelude::*;
-use bevy_replicon::prelude::*;
-use bevy_replicon_example_backend::{ExampleClient, ExampleServer, RepliconExampleBackendPlugins};
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick};
+use bevy_replicon_example_backend::{
+    ExampleClient, ExampleRelay, ExampleServer, RepliconExampleBackendPlugins,
+};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -146,6 +148,66 @@ fn client_event() {
     assert_eq!(client_events.len(), 1);
 }
 
+#[test]
+fn relay() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            RepliconExampleBackendPlugins,
+        ));
+    }
+
+    let server_socket = ExampleServer::new(0).unwrap();
+    let server_addr = server_socket.local_addr().unwrap();
+    let mut relay = ExampleRelay::new(0, server_addr).unwrap();
+    let relay_addr = relay.local_addr().unwrap();
+    let client_socket = ExampleClient::new(relay_addr.port()).unwrap();
+
+    server_app.insert_resource(server_socket);
+    client_app.insert_resource(client_socket);
+
+    relay.update().unwrap();
+    server_app.update();
+    client_app.update();
+
+    assert_eq!(relay.connected_clients(), 1);
+    let connected_clients = server_app.world().resource::<ConnectedClients>();
+    assert_eq!(connected_clients.len(), 1);
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    relay.update().unwrap();
+    client_app.update();
+
+    client_app
+        .world_mut()
+        .query::<&Replicated>()
+        .single(client_app.world());
+
+    let stats = relay.stats();
+    assert_eq!(stats.updates, 1);
+    assert_eq!(stats.invalid_updates, 0);
+    let tick = **server_app.world().resource::<ServerTick>();
+    assert_eq!(stats.last_update_tick, Some(tick));
+
+    client_app.world_mut().remove_resource::<ExampleClient>();
+
+    relay.update().unwrap();
+    assert_eq!(relay.connected_clients(), 0);
+
+    server_app.update();
+
+    let connected_clients = server_app.world().resource::<ConnectedClients>();
+    assert_eq!(connected_clients.len(), 0);
+}
+
 fn setup(server_app: &mut App, client_app: &mut App) -> io::Result<()> {
     let server_socket = ExampleServer::new(0)?;
     let server_addr = server_socket.local_addr()?;
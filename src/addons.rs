@@ -0,0 +1,24 @@
+//! Optional features built on top of the core API.
+//!
+//! Their plugins are not part of [`RepliconPlugins`](crate::RepliconPlugins)
+//! and their types are not re-exported from [`prelude`](crate::prelude).
+
+pub mod anchored_event;
+#[cfg(feature = "chat")]
+pub mod chat;
+pub mod collection;
+pub mod dead_reckoning;
+#[cfg(feature = "zstd")]
+pub mod dictionary_compression;
+pub mod health_report;
+pub mod heartbeat;
+pub mod orphan_detection;
+pub mod pending_despawn;
+pub mod physics;
+pub mod preload_hints;
+pub mod replicated_timer;
+pub mod resimulation;
+pub mod roster;
+pub mod static_baseline;
+pub mod tick_aligned_event;
+pub mod vote;
@@ -0,0 +1,293 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::core::{
+    channels::RepliconChannel, event::server_event::ServerEventAppExt, replicon_tick::RepliconTick,
+};
+#[cfg(feature = "client")]
+use crate::{
+    client::{server_mutate_ticks::ServerMutateTicks, ClientSet, ServerUpdateTick},
+    core::common_conditions::*,
+};
+
+/// Registration of tick-anchored server events.
+///
+/// Useful for short-lived state transitions, such as "started attack at tick T",
+/// that should be applied on clients at the same point of the replicated timeline.
+pub trait AnchoredEventAppExt {
+    /**
+    Registers [`Anchored<E>`] as a server event and emits [`AnchorReached<E>`] on clients.
+
+    Anchored events are buffered on client until the local interpolated tick, which is
+    the last received server tick minus [`AnchorSettings::interpolation_delay`], reaches
+    the anchor tick. Events that are received after their anchor tick are emitted
+    immediately with [`AnchorReached::late_ticks`] set, so animations can snap forward.
+    Events that are late by more than [`AnchorSettings::max_late_ticks`] are dropped.
+    Duplicates with the same tick and value are ignored.
+
+    On server or in singleplayer events are emitted immediately without buffering.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        addons::anchored_event::{Anchored, AnchoredEventAppExt, AnchorReached},
+        prelude::*,
+        server::server_tick::ServerTick,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.add_anchored_event::<Attack>(ChannelKind::Ordered)
+        .add_systems(Update, (start_attack, play_attack));
+
+    fn start_attack(mut attacks: EventWriter<ToClients<Anchored<Attack>>>, server_tick: Res<ServerTick>) {
+        attacks.send(ToClients {
+            mode: SendMode::Broadcast,
+            event: Anchored::new(**server_tick, Attack),
+        });
+    }
+
+    fn play_attack(mut attacks: EventReader<AnchorReached<Attack>>) {
+        for reached in attacks.read() {
+            info!("playing attack skipping {} ticks", reached.late_ticks);
+        }
+    }
+
+    #[derive(Clone, PartialEq, Deserialize, Serialize)]
+    struct Attack;
+    ```
+    **/
+    fn add_anchored_event<E>(&mut self, channel: impl Into<RepliconChannel>) -> &mut Self
+    where
+        E: Clone + PartialEq + Serialize + DeserializeOwned + Send + Sync + 'static;
+}
+
+impl AnchoredEventAppExt for App {
+    fn add_anchored_event<E>(&mut self, channel: impl Into<RepliconChannel>) -> &mut Self
+    where
+        E: Clone + PartialEq + Serialize + DeserializeOwned + Send + Sync + 'static,
+    {
+        self.add_server_event::<Anchored<E>>(channel)
+            .add_event::<AnchorReached<E>>()
+            .init_resource::<AnchorSettings>();
+
+        #[cfg(feature = "client")]
+        self.init_resource::<AnchorBuffer<E>>().add_systems(
+            PreUpdate,
+            (
+                reset::<E>.in_set(ClientSet::Reset),
+                (
+                    receive::<E>.run_if(client_connected),
+                    receive_locally::<E>.run_if(server_or_singleplayer),
+                )
+                    .after(ClientSet::Receive),
+            ),
+        );
+
+        self
+    }
+}
+
+#[cfg(feature = "client")]
+fn receive<E: Clone + PartialEq + Send + Sync + 'static>(
+    mut anchored_events: ResMut<Events<Anchored<E>>>,
+    mut reached_events: EventWriter<AnchorReached<E>>,
+    mut buffer: ResMut<AnchorBuffer<E>>,
+    settings: Res<AnchorSettings>,
+    update_tick: Option<Res<ServerUpdateTick>>,
+    mutate_ticks: Option<Res<ServerMutateTicks>>,
+) {
+    for anchored in anchored_events.drain() {
+        buffer.insert(anchored);
+    }
+
+    if let Some(update_tick) = update_tick {
+        buffer.observe_tick(**update_tick);
+    }
+    if let Some(mutate_ticks) = mutate_ticks {
+        buffer.observe_tick(mutate_ticks.last_tick());
+    }
+
+    let interpolated_tick = buffer.latest_tick - settings.interpolation_delay;
+    for anchored in buffer.take_reached(interpolated_tick) {
+        let late_ticks = interpolated_tick - anchored.tick;
+        if late_ticks > settings.max_late_ticks {
+            debug!(
+                "dropping anchored event with {:?} that is {late_ticks} ticks late",
+                anchored.tick
+            );
+            continue;
+        }
+
+        reached_events.send(AnchorReached {
+            tick: anchored.tick,
+            event: anchored.event,
+            late_ticks,
+        });
+    }
+
+    buffer.forget_older(interpolated_tick - settings.max_late_ticks);
+}
+
+#[cfg(feature = "client")]
+fn receive_locally<E: Send + Sync + 'static>(
+    mut anchored_events: ResMut<Events<Anchored<E>>>,
+    mut reached_events: EventWriter<AnchorReached<E>>,
+) {
+    for anchored in anchored_events.drain() {
+        reached_events.send(AnchorReached {
+            tick: anchored.tick,
+            event: anchored.event,
+            late_ticks: 0,
+        });
+    }
+}
+
+#[cfg(feature = "client")]
+fn reset<E: Send + Sync + 'static>(mut buffer: ResMut<AnchorBuffer<E>>) {
+    buffer.clear();
+}
+
+/// Configuration for anchored events on client.
+///
+/// See [`AnchoredEventAppExt::add_anchored_event`] for details.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct AnchorSettings {
+    /// Number of ticks the local interpolated tick lags behind the last received server tick.
+    ///
+    /// Should match the delay used for interpolation of replicated components.
+    pub interpolation_delay: u32,
+
+    /// Maximum number of ticks an event can be late before it's dropped.
+    pub max_late_ticks: u32,
+}
+
+impl Default for AnchorSettings {
+    fn default() -> Self {
+        Self {
+            interpolation_delay: 0,
+            max_late_ticks: 16,
+        }
+    }
+}
+
+/// A server event anchored to a server tick.
+///
+/// See [`AnchoredEventAppExt::add_anchored_event`] for details.
+#[derive(Event, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Anchored<E> {
+    /// Server tick on which the event happened.
+    pub tick: RepliconTick,
+
+    /// Event data.
+    pub event: E,
+}
+
+impl<E> Anchored<E> {
+    /// Creates a new event anchored to the given tick.
+    pub fn new(tick: RepliconTick, event: E) -> Self {
+        Self { tick, event }
+    }
+}
+
+/// An event emitted on client when the local interpolated tick reaches the anchor of [`Anchored<E>`].
+///
+/// See [`AnchoredEventAppExt::add_anchored_event`] for details.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct AnchorReached<E> {
+    /// Server tick on which the event happened.
+    pub tick: RepliconTick,
+
+    /// Event data.
+    pub event: E,
+
+    /// Number of ticks the interpolated tick passed the anchor at the moment of emitting.
+    ///
+    /// Non-zero if the event arrived late.
+    pub late_ticks: u32,
+}
+
+/// Received anchored events that aren't reached yet.
+#[cfg(feature = "client")]
+#[derive(Resource)]
+struct AnchorBuffer<E> {
+    /// Events waiting for their tick, sorted by tick.
+    pending: Vec<Anchored<E>>,
+
+    /// Recently emitted or dropped events for deduplication.
+    processed: Vec<Anchored<E>>,
+
+    /// The latest known server tick.
+    latest_tick: RepliconTick,
+}
+
+#[cfg(feature = "client")]
+impl<E: PartialEq> AnchorBuffer<E> {
+    /// Inserts an event, ignoring duplicates.
+    fn insert(&mut self, anchored: Anchored<E>) {
+        let is_duplicate =
+            |other: &Anchored<E>| other.tick == anchored.tick && other.event == anchored.event;
+        if self.pending.iter().any(is_duplicate) || self.processed.iter().any(is_duplicate) {
+            trace!("ignoring duplicate anchored event with {:?}", anchored.tick);
+            return;
+        }
+
+        self.observe_tick(anchored.tick);
+        let index = self
+            .pending
+            .iter()
+            .position(|other| other.tick > anchored.tick)
+            .unwrap_or(self.pending.len());
+        self.pending.insert(index, anchored);
+    }
+}
+
+#[cfg(feature = "client")]
+impl<E: Clone> AnchorBuffer<E> {
+    /// Returns events whose tick is less than or equal to the given tick.
+    fn take_reached(&mut self, tick: RepliconTick) -> Vec<Anchored<E>> {
+        let reached = self
+            .pending
+            .iter()
+            .take_while(|anchored| anchored.tick <= tick)
+            .count();
+        let reached: Vec<_> = self.pending.drain(..reached).collect();
+        self.processed.extend(reached.iter().cloned());
+        reached
+    }
+}
+
+#[cfg(feature = "client")]
+impl<E> AnchorBuffer<E> {
+    /// Updates the latest known server tick.
+    fn observe_tick(&mut self, tick: RepliconTick) {
+        if tick > self.latest_tick {
+            self.latest_tick = tick;
+        }
+    }
+
+    /// Forgets processed events older than the given tick.
+    fn forget_older(&mut self, tick: RepliconTick) {
+        self.processed.retain(|anchored| anchored.tick >= tick);
+    }
+
+    fn clear(&mut self) {
+        self.pending.clear();
+        self.processed.clear();
+        self.latest_tick = Default::default();
+    }
+}
+
+#[cfg(feature = "client")]
+impl<E> Default for AnchorBuffer<E> {
+    fn default() -> Self {
+        Self {
+            pending: Default::default(),
+            processed: Default::default(),
+            latest_tick: Default::default(),
+        }
+    }
+}
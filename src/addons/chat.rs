@@ -0,0 +1,382 @@
+#[cfg(feature = "server")]
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+#[cfg(feature = "server")]
+use bevy::utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    channels::ChannelKind,
+    event::{
+        client_event::ClientEventAppExt, rate_limit::ClientEventRateLimit,
+        server_event::ServerEventAppExt,
+    },
+    ClientId,
+};
+#[cfg(feature = "server")]
+use crate::{
+    core::{
+        common_conditions::*,
+        event::{
+            client_event::FromClient,
+            server_event::{SendMode, ToClients},
+        },
+    },
+    server::{ClientConnected, ClientDisconnected, ServerSet},
+};
+
+/**
+Text chat with rooms, moderation and history.
+
+Clients send [`SendChat`] and receive [`ChatMessage`] over dedicated ordered channels.
+Messages are emitted on clients right after receiving, without waiting for replication.
+
+All clients are members of [`ChatRoom::GLOBAL`]. Other rooms can be joined and left on server via [`ChatRooms`].
+Messages from clients that aren't members of the room are ignored.
+
+On server each message passes [`ChatSettings::max_len`] and [`ChatSettings::filter`] checks,
+and is rate limited by [`Self::rate_limit`].
+Accepted messages are stored in [`ChatHistory`] and sent to clients that connect or join the room later.
+
+Should be added to both client and server after [`RepliconPlugins`](crate::RepliconPlugins).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::chat::{ChatMessage, ChatPlugin, ChatRoom, SendChat},
+    prelude::*,
+};
+
+# let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    ChatPlugin {
+        filter: Some(censor),
+        ..Default::default()
+    },
+))
+.add_systems(Update, (greet.run_if(client_just_connected), show_messages));
+
+fn censor(message: &mut ChatMessage) -> bool {
+    message.text = message.text.replace("cheese", "******");
+    true
+}
+
+fn greet(mut chat_events: EventWriter<SendChat>) {
+    chat_events.send(SendChat {
+        room: ChatRoom::GLOBAL,
+        text: "Hello!".into(),
+    });
+}
+
+fn show_messages(mut chat_events: EventReader<ChatMessage>) {
+    for message in chat_events.read() {
+        info!("`{:?}`: {}", message.sender, message.text);
+    }
+}
+```
+**/
+pub struct ChatPlugin {
+    /// Maximum number of stored messages per room.
+    ///
+    /// Set to 0 to disable history.
+    pub history_len: usize,
+
+    /// Maximum number of characters in a message.
+    pub max_len: usize,
+
+    /// Limit for sent messages from each client.
+    pub rate_limit: ClientEventRateLimit,
+
+    /// Function to moderate messages on server.
+    pub filter: Option<ChatFilterFn>,
+}
+
+impl Default for ChatPlugin {
+    fn default() -> Self {
+        Self {
+            history_len: 50,
+            max_len: 256,
+            rate_limit: ClientEventRateLimit::per_second(5),
+            filter: None,
+        }
+    }
+}
+
+impl Plugin for ChatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_client_event::<SendChat>(ChannelKind::Ordered)
+            .set_client_event_rate_limit::<SendChat>(self.rate_limit)
+            .add_server_event::<ChatMessage>(ChannelKind::Ordered)
+            .make_independent::<ChatMessage>();
+
+        #[cfg(feature = "server")]
+        app.insert_resource(ChatSettings {
+            history_len: self.history_len,
+            max_len: self.max_len,
+            filter: self.filter,
+        })
+        .init_resource::<ChatRooms>()
+        .init_resource::<ChatHistory>()
+        .add_observer(join_global)
+        .add_observer(remove_client)
+        .add_systems(
+            PreUpdate,
+            (
+                reset.run_if(server_just_stopped),
+                (send_history, receive_messages)
+                    .chain()
+                    .after(ServerSet::Receive)
+                    .run_if(server_running),
+            ),
+        );
+    }
+}
+
+#[cfg(feature = "server")]
+fn join_global(trigger: Trigger<ClientConnected>, mut rooms: ResMut<ChatRooms>) {
+    rooms.join(trigger.client_id, ChatRoom::GLOBAL);
+}
+
+#[cfg(feature = "server")]
+fn remove_client(trigger: Trigger<ClientDisconnected>, mut rooms: ResMut<ChatRooms>) {
+    rooms.remove_client(trigger.client_id);
+}
+
+#[cfg(feature = "server")]
+fn reset(mut rooms: ResMut<ChatRooms>, mut history: ResMut<ChatHistory>) {
+    rooms.clear();
+    history.clear();
+}
+
+/// Sends stored messages to clients that joined rooms since the last run.
+#[cfg(feature = "server")]
+fn send_history(
+    mut chat_events: EventWriter<ToClients<ChatMessage>>,
+    mut rooms: ResMut<ChatRooms>,
+    history: Res<ChatHistory>,
+) {
+    for (client_id, room) in rooms.joined.drain(..) {
+        for message in history.messages(room) {
+            chat_events.send(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: message.clone(),
+            });
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn receive_messages(
+    mut send_events: EventReader<FromClient<SendChat>>,
+    mut chat_events: EventWriter<ToClients<ChatMessage>>,
+    settings: Res<ChatSettings>,
+    rooms: Res<ChatRooms>,
+    mut history: ResMut<ChatHistory>,
+) {
+    for FromClient { client_id, event } in send_events.read() {
+        if !rooms.is_member(*client_id, event.room) {
+            debug!(
+                "ignoring message from `{client_id:?}` to `{:?}` without membership",
+                event.room
+            );
+            continue;
+        }
+
+        if event.text.chars().count() > settings.max_len {
+            debug!("ignoring too long message from `{client_id:?}`");
+            continue;
+        }
+
+        let mut message = ChatMessage {
+            sender: *client_id,
+            room: event.room,
+            text: event.text.clone(),
+        };
+        if let Some(filter) = settings.filter {
+            if !(filter)(&mut message) {
+                debug!("message from `{client_id:?}` rejected by filter");
+                continue;
+            }
+        }
+
+        history.push(message.clone(), settings.history_len);
+        if message.room == ChatRoom::GLOBAL {
+            chat_events.send(ToClients {
+                mode: SendMode::Broadcast,
+                event: message,
+            });
+        } else {
+            for member in rooms.members(message.room) {
+                chat_events.send(ToClients {
+                    mode: SendMode::Direct(member),
+                    event: message.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Moderates a chat message on server.
+///
+/// Can modify the message, for example, to censor words.
+/// Returns `false` to discard the message.
+pub type ChatFilterFn = fn(&mut ChatMessage) -> bool;
+
+/// Chat configuration on server.
+///
+/// Initialized from [`ChatPlugin`], can be changed at runtime.
+#[cfg(feature = "server")]
+#[derive(Resource, Clone, Copy)]
+pub struct ChatSettings {
+    /// See [`ChatPlugin::history_len`].
+    pub history_len: usize,
+
+    /// See [`ChatPlugin::max_len`].
+    pub max_len: usize,
+
+    /// See [`ChatPlugin::filter`].
+    pub filter: Option<ChatFilterFn>,
+}
+
+/// Memberships of clients in chat rooms.
+///
+/// See [`ChatPlugin`] for details.
+#[cfg(feature = "server")]
+#[derive(Resource, Default)]
+pub struct ChatRooms {
+    members: HashMap<ChatRoom, HashSet<ClientId>>,
+
+    /// Memberships to send the history for.
+    joined: Vec<(ClientId, ChatRoom)>,
+}
+
+#[cfg(feature = "server")]
+impl ChatRooms {
+    /// Adds a client to a room.
+    ///
+    /// The client will receive the room history on the next update.
+    pub fn join(&mut self, client_id: ClientId, room: ChatRoom) {
+        if self.members.entry(room).or_default().insert(client_id) {
+            self.joined.push((client_id, room));
+        }
+    }
+
+    /// Removes a client from a room.
+    pub fn leave(&mut self, client_id: ClientId, room: ChatRoom) {
+        if let Some(members) = self.members.get_mut(&room) {
+            members.remove(&client_id);
+        }
+        self.joined
+            .retain(|&(joined_id, joined_room)| joined_id != client_id || joined_room != room);
+    }
+
+    /// Returns `true` if the client is a member of the room.
+    ///
+    /// Always `true` for [`ChatRoom::GLOBAL`].
+    pub fn is_member(&self, client_id: ClientId, room: ChatRoom) -> bool {
+        room == ChatRoom::GLOBAL
+            || self
+                .members
+                .get(&room)
+                .is_some_and(|members| members.contains(&client_id))
+    }
+
+    /// Returns an iterator over clients that joined the room.
+    pub fn members(&self, room: ChatRoom) -> impl Iterator<Item = ClientId> + '_ {
+        self.members.get(&room).into_iter().flatten().copied()
+    }
+
+    fn remove_client(&mut self, client_id: ClientId) {
+        for members in self.members.values_mut() {
+            members.remove(&client_id);
+        }
+        self.joined.retain(|&(joined_id, _)| joined_id != client_id);
+    }
+
+    fn clear(&mut self) {
+        self.members.clear();
+        self.joined.clear();
+    }
+}
+
+/// Last accepted messages for each room.
+///
+/// See [`ChatPlugin`] for details.
+#[cfg(feature = "server")]
+#[derive(Resource, Default)]
+pub struct ChatHistory(HashMap<ChatRoom, VecDeque<ChatMessage>>);
+
+#[cfg(feature = "server")]
+impl ChatHistory {
+    /// Returns an iterator over stored messages of a room from the oldest to the newest.
+    pub fn messages(&self, room: ChatRoom) -> impl Iterator<Item = &ChatMessage> {
+        self.0.get(&room).into_iter().flatten()
+    }
+
+    fn push(&mut self, message: ChatMessage, max_len: usize) {
+        if max_len == 0 {
+            return;
+        }
+
+        let messages = self.0.entry(message.room).or_default();
+        if messages.len() >= max_len {
+            messages.pop_front();
+        }
+        messages.push_back(message);
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// A client event to send a message to a chat room.
+///
+/// See [`ChatPlugin`] for details.
+#[derive(Event, Clone, Debug, Deserialize, Serialize)]
+pub struct SendChat {
+    /// Room to send the message to.
+    pub room: ChatRoom,
+
+    /// Message content.
+    pub text: String,
+}
+
+/// A server event with a chat message accepted by the server.
+///
+/// See [`ChatPlugin`] for details.
+#[derive(Event, Clone, Debug, Deserialize, Serialize)]
+pub struct ChatMessage {
+    /// Author of the message.
+    pub sender: ClientId,
+
+    /// Room to which the message was sent.
+    pub room: ChatRoom,
+
+    /// Message content.
+    pub text: String,
+}
+
+/// Unique chat room ID.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Ord, PartialOrd, Deserialize, Serialize)]
+pub struct ChatRoom(u64);
+
+impl ChatRoom {
+    /// Room with all connected clients.
+    pub const GLOBAL: Self = Self(0);
+
+    /// Creates a new ID wrapping the given value.
+    pub const fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Gets the value of this ID.
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,314 @@
+use std::{hash::Hash, ops::DerefMut};
+
+use bevy::{
+    prelude::*,
+    utils::{hashbrown::hash_map, HashMap, HashSet},
+};
+use bytes::Bytes;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::core::{
+    postcard_utils,
+    replication::{
+        replication_registry::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::{DeserializeFn, RuleFns},
+        },
+        replication_rules::AppRuleExt,
+    },
+};
+#[cfg(feature = "server")]
+use crate::{
+    core::common_conditions::server_running,
+    server::{tick_batch_sent, ServerSet},
+};
+
+/// Collection replication functions for [`App`].
+pub trait AppCollectionExt {
+    /**
+    Creates a replication rule for a collection component that replicates per-element diffs.
+
+    Instead of sending the whole collection on every change, only inserted, changed
+    and removed elements are sent. Clients that receive the component for the first time
+    get the full collection. See [`RuleFns::with_diffs`] for details.
+
+    The component should dereference into [`ReplicatedCollection`] and be modified only through it,
+    since it tracks changes. Changes are cleared after each replication send.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        addons::collection::{AppCollectionExt, CollectionElement, ReplicatedCollection},
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_collection::<Inventory>();
+
+    #[derive(Component, Default, Deref, DerefMut)]
+    struct Inventory(ReplicatedCollection<Item>);
+
+    #[derive(Deserialize, Serialize)]
+    struct Item {
+        id: u32,
+        count: u16,
+    }
+
+    impl CollectionElement for Item {
+        type Key = u32;
+
+        fn key(&self) -> Self::Key {
+            self.id
+        }
+    }
+    ```
+    **/
+    fn replicate_collection<C: CollectionComponent>(&mut self) -> &mut Self;
+}
+
+impl AppCollectionExt for App {
+    fn replicate_collection<C: CollectionComponent>(&mut self) -> &mut Self {
+        self.replicate_with(
+            RuleFns::new(serialize::<C>, deserialize::<C>)
+                .with_in_place(deserialize_in_place::<C>)
+                .with_diffs(),
+        );
+
+        #[cfg(feature = "server")]
+        self.add_systems(
+            PostUpdate,
+            clear_changes::<C>
+                .after(ServerSet::Send)
+                .run_if(not(server_running).or(tick_batch_sent)),
+        );
+        #[cfg(not(feature = "server"))]
+        self.add_systems(PostUpdate, clear_changes::<C>);
+
+        self
+    }
+}
+
+/// Clears tracked changes after they were sent or if there is no server to send them.
+fn clear_changes<C: CollectionComponent>(mut collections: Query<&mut C>) {
+    for mut collection in &mut collections {
+        if collection.has_changes() {
+            collection.bypass_change_detection().clear_changes();
+        }
+    }
+}
+
+/// Serializes the full collection or only its changes.
+fn serialize<C: CollectionComponent>(
+    ctx: &SerializeCtx,
+    component: &C,
+    message: &mut Vec<u8>,
+) -> postcard::Result<()> {
+    postcard_utils::to_extend_mut(&ctx.diff, message)?;
+    if ctx.diff {
+        postcard_utils::to_extend_mut(&component.removed.len(), message)?;
+        for key in &component.removed {
+            postcard_utils::to_extend_mut(key, message)?;
+        }
+        postcard_utils::to_extend_mut(&component.changed.len(), message)?;
+        for key in &component.changed {
+            postcard_utils::to_extend_mut(&component.elements[key], message)?;
+        }
+    } else {
+        postcard_utils::to_extend_mut(&component.elements.len(), message)?;
+        for element in component.elements.values() {
+            postcard_utils::to_extend_mut(element, message)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates a new collection from the full collection or applies changes to an empty collection.
+fn deserialize<C: CollectionComponent>(
+    _ctx: &mut WriteCtx,
+    message: &mut Bytes,
+) -> postcard::Result<C> {
+    let mut component = C::default();
+    component.apply(message)?;
+    Ok(component)
+}
+
+/// Replaces the collection or applies changes to it.
+fn deserialize_in_place<C: CollectionComponent>(
+    _deserialize: DeserializeFn<C>,
+    _ctx: &mut WriteCtx,
+    component: &mut C,
+    message: &mut Bytes,
+) -> postcard::Result<()> {
+    component.apply(message)
+}
+
+/// A component that dereferences into [`ReplicatedCollection`].
+///
+/// Implemented automatically for all suitable types.
+/// See [`AppCollectionExt::replicate_collection`].
+pub trait CollectionComponent:
+    Component + Default + DerefMut<Target = ReplicatedCollection<Self::Element>>
+{
+    type Element: CollectionElement;
+}
+
+impl<C, E> CollectionComponent for C
+where
+    C: Component + Default + DerefMut<Target = ReplicatedCollection<E>>,
+    E: CollectionElement,
+{
+    type Element = E;
+}
+
+/// An element of [`ReplicatedCollection`].
+pub trait CollectionElement: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Unique identifier of the element inside the collection.
+    type Key: Eq + Hash + Clone + Serialize + DeserializeOwned + Send + Sync + 'static;
+
+    /// Extracts the key from the element.
+    ///
+    /// Should stay the same for the lifetime of the element.
+    fn key(&self) -> Self::Key;
+}
+
+/// Unordered collection of elements that tracks per-element changes for replication.
+///
+/// Elements are stored by keys obtained from [`CollectionElement::key`].
+/// Entities inside elements are not mapped.
+///
+/// See [`AppCollectionExt::replicate_collection`].
+#[derive(Clone)]
+pub struct ReplicatedCollection<E: CollectionElement> {
+    elements: HashMap<E::Key, E>,
+
+    /// Keys of inserted or modified elements since the last send.
+    changed: HashSet<E::Key>,
+
+    /// Keys of removed elements since the last send.
+    removed: HashSet<E::Key>,
+}
+
+impl<E: CollectionElement> ReplicatedCollection<E> {
+    /// Inserts an element, replacing an element with the same key.
+    ///
+    /// Returns the replaced element.
+    pub fn insert(&mut self, element: E) -> Option<E> {
+        let key = element.key();
+        self.changed.insert(key.clone());
+        self.elements.insert(key, element)
+    }
+
+    /// Removes an element by its key and returns it.
+    pub fn remove(&mut self, key: &E::Key) -> Option<E> {
+        let element = self.elements.remove(key)?;
+        self.changed.remove(key);
+        self.removed.insert(key.clone());
+        Some(element)
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.changed.clear();
+        self.removed
+            .extend(self.elements.drain().map(|(key, _)| key));
+    }
+
+    /// Returns a reference to an element by its key.
+    pub fn get(&self, key: &E::Key) -> Option<&E> {
+        self.elements.get(key)
+    }
+
+    /// Returns a mutable reference to an element by its key and marks it as changed.
+    ///
+    /// The key of the element shouldn't be modified.
+    pub fn get_mut(&mut self, key: &E::Key) -> Option<&mut E> {
+        let element = self.elements.get_mut(key)?;
+        self.changed.insert(key.clone());
+        Some(element)
+    }
+
+    /// Returns `true` if the collection contains an element with the key.
+    pub fn contains_key(&self, key: &E::Key) -> bool {
+        self.elements.contains_key(key)
+    }
+
+    /// Returns an iterator over all elements in arbitrary order.
+    pub fn iter(&self) -> hash_map::Values<'_, E::Key, E> {
+        self.elements.values()
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns `true` if the collection has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    fn has_changes(&self) -> bool {
+        !self.changed.is_empty() || !self.removed.is_empty()
+    }
+
+    fn clear_changes(&mut self) {
+        self.changed.clear();
+        self.removed.clear();
+    }
+
+    /// Applies the full collection or its changes from a message without tracking them.
+    fn apply(&mut self, message: &mut Bytes) -> postcard::Result<()> {
+        let diff: bool = postcard_utils::from_buf(message)?;
+        if diff {
+            let removed_len: usize = postcard_utils::from_buf(message)?;
+            for _ in 0..removed_len {
+                let key: E::Key = postcard_utils::from_buf(message)?;
+                self.elements.remove(&key);
+            }
+        } else {
+            self.elements.clear();
+        }
+
+        let len: usize = postcard_utils::from_buf(message)?;
+        for _ in 0..len {
+            let element: E = postcard_utils::from_buf(message)?;
+            self.elements.insert(element.key(), element);
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: CollectionElement> Default for ReplicatedCollection<E> {
+    fn default() -> Self {
+        Self {
+            elements: Default::default(),
+            changed: Default::default(),
+            removed: Default::default(),
+        }
+    }
+}
+
+impl<E: CollectionElement> FromIterator<E> for ReplicatedCollection<E> {
+    fn from_iter<T: IntoIterator<Item = E>>(iter: T) -> Self {
+        let mut collection = Self::default();
+        for element in iter {
+            collection.insert(element);
+        }
+        collection
+    }
+}
+
+impl<'a, E: CollectionElement> IntoIterator for &'a ReplicatedCollection<E> {
+    type Item = &'a E;
+    type IntoIter = hash_map::Values<'a, E::Key, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
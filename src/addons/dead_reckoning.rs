@@ -0,0 +1,233 @@
+use std::marker::PhantomData;
+#[cfg(feature = "server")]
+use std::time::Duration;
+
+#[cfg(feature = "server")]
+use bevy::ecs::component::Tick;
+use bevy::prelude::*;
+
+#[cfg(feature = "client")]
+use crate::client::{confirm_history::ConfirmHistory, ClientSet};
+#[cfg(any(feature = "client", feature = "server"))]
+use crate::core::common_conditions::*;
+#[cfg(feature = "server")]
+use crate::{
+    core::replication::Replicated,
+    server::{increment_tick, server_tick::ServerTick, ServerSet},
+};
+
+/**
+Advances component `P` (such as position) using component `V` (such as velocity)
+between received snapshots on client.
+
+Each frame the client writes `P` computed by the model from the last received `P`,
+the current `V` and the number of seconds since the snapshot was received.
+A new snapshot is taken when `P` or `V` is received.
+
+On server the model can be mirrored using [`Self::with_error_threshold`]. In this case
+mutations of `P` are not sent while the error between the actual value and the value predicted
+from the last sent snapshot stays under the threshold. `P` is always sent together with `V`.
+This is done by reverting the change tick of `P`, so change detection for `P` doesn't trigger
+for suppressed mutations after [`ServerSet::Send`].
+
+Because the mirrored snapshot is shared between clients, clients that haven't acknowledged
+the last sent snapshot may receive a slightly different value, so the error is only
+approximately bounded by the threshold.
+
+Components should be registered for replication separately.
+Should be added to both client and server after [`RepliconPlugins`](crate::RepliconPlugins).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{addons::dead_reckoning::DeadReckoningPlugin, prelude::*};
+use serde::{Deserialize, Serialize};
+
+let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    DeadReckoningPlugin::new(move_position).with_error_threshold(position_error, 0.5),
+))
+.replicate::<Position>()
+.replicate::<Velocity>();
+
+fn move_position(position: &Position, velocity: &Velocity, secs: f32) -> Position {
+    Position(position.0 + velocity.0 * secs)
+}
+
+fn position_error(actual: &Position, predicted: &Position) -> f32 {
+    actual.0.distance(predicted.0)
+}
+
+#[derive(Component, Clone, Deserialize, Serialize)]
+struct Position(Vec2);
+
+#[derive(Component, Deserialize, Serialize)]
+struct Velocity(Vec2);
+```
+**/
+pub struct DeadReckoningPlugin<P, V> {
+    model: DeadReckoningFn<P, V>,
+    error_threshold: Option<(ErrorFn<P>, f32)>,
+    marker: PhantomData<V>,
+}
+
+impl<P, V> DeadReckoningPlugin<P, V> {
+    /// Creates a new plugin with the given model.
+    pub fn new(model: DeadReckoningFn<P, V>) -> Self {
+        Self {
+            model,
+            error_threshold: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Enables suppression of server mutations for `P` while the predicted error stays under `threshold`.
+    ///
+    /// See the struct-level documentation for details.
+    pub fn with_error_threshold(mut self, error: ErrorFn<P>, threshold: f32) -> Self {
+        self.error_threshold = Some((error, threshold));
+        self
+    }
+}
+
+impl<P: Component + Clone, V: Component> Plugin for DeadReckoningPlugin<P, V> {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DeadReckoningFns::<P, V> {
+            model: self.model,
+            #[cfg(feature = "server")]
+            error_threshold: self.error_threshold,
+        });
+
+        #[cfg(feature = "client")]
+        app.add_systems(
+            PreUpdate,
+            advance::<P, V>
+                .after(ClientSet::Receive)
+                .run_if(client_connected),
+        );
+
+        #[cfg(feature = "server")]
+        if self.error_threshold.is_some() {
+            app.add_systems(
+                PostUpdate,
+                suppress_mutations::<P, V>
+                    .after(increment_tick)
+                    .before(ServerSet::Send)
+                    .run_if(server_running)
+                    .run_if(resource_changed::<ServerTick>),
+            );
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+fn advance<P: Component + Clone, V: Component>(
+    mut commands: Commands,
+    time: Res<Time>,
+    fns: Res<DeadReckoningFns<P, V>>,
+    mut bodies: Query<
+        (Entity, Mut<P>, Ref<V>, Option<&mut ReceivedSnapshot<P>>),
+        With<ConfirmHistory>,
+    >,
+) {
+    for (entity, mut value, velocity, snapshot) in &mut bodies {
+        let Some(mut snapshot) = snapshot else {
+            commands.entity(entity).insert(ReceivedSnapshot {
+                value: value.clone(),
+                elapsed: 0.0,
+            });
+            continue;
+        };
+
+        // The system doesn't observe its own writes, so it's a received value.
+        if value.is_changed() || velocity.is_changed() {
+            snapshot.value = value.clone();
+            snapshot.elapsed = 0.0;
+            continue;
+        }
+
+        snapshot.elapsed += time.delta_secs();
+        *value = (fns.model)(&snapshot.value, &velocity, snapshot.elapsed);
+    }
+}
+
+#[cfg(feature = "server")]
+fn suppress_mutations<P: Component + Clone, V: Component>(
+    mut commands: Commands,
+    time: Res<Time>,
+    fns: Res<DeadReckoningFns<P, V>>,
+    mut bodies: Query<(Entity, Mut<P>, Ref<V>, Option<&mut SentSnapshot<P>>), With<Replicated>>,
+) {
+    let Some((error, threshold)) = fns.error_threshold else {
+        return;
+    };
+
+    for (entity, mut value, velocity, snapshot) in &mut bodies {
+        let Some(mut snapshot) = snapshot else {
+            commands.entity(entity).insert(SentSnapshot {
+                value: value.clone(),
+                sent_at: time.elapsed(),
+                change_tick: value.last_changed(),
+            });
+            continue;
+        };
+
+        if velocity.is_changed() {
+            // Send both components, so the client takes a snapshot from the same value.
+            value.set_changed();
+        } else if value.is_changed() {
+            let secs = (time.elapsed() - snapshot.sent_at).as_secs_f32();
+            let predicted = (fns.model)(&snapshot.value, &velocity, secs);
+            if error(&value, &predicted) < threshold {
+                trace!("suppressing mutation for `{entity}`");
+                value.set_last_changed(snapshot.change_tick);
+                continue;
+            }
+        } else {
+            continue;
+        }
+
+        snapshot.value = value.clone();
+        snapshot.sent_at = time.elapsed();
+        snapshot.change_tick = value.last_changed();
+    }
+}
+
+/// Computes a value after the given number of seconds from a snapshot using its rate of change.
+pub type DeadReckoningFn<P, V> = fn(&P, &V, f32) -> P;
+
+/// Computes an error between the actual and predicted values.
+pub type ErrorFn<P> = fn(&P, &P) -> f32;
+
+#[derive(Resource)]
+struct DeadReckoningFns<P, V> {
+    model: DeadReckoningFn<P, V>,
+    #[cfg(feature = "server")]
+    error_threshold: Option<(ErrorFn<P>, f32)>,
+}
+
+/// The last received value of `P` on client.
+#[cfg(feature = "client")]
+#[derive(Component)]
+struct ReceivedSnapshot<P> {
+    value: P,
+
+    /// Seconds since the snapshot was received.
+    elapsed: f32,
+}
+
+/// The last sent value of `P` on server.
+#[cfg(feature = "server")]
+#[derive(Component)]
+struct SentSnapshot<P> {
+    value: P,
+
+    /// Time when the snapshot was sent.
+    sent_at: Duration,
+
+    /// Change tick of the component when the snapshot was sent.
+    change_tick: Tick,
+}
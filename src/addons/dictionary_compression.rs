@@ -0,0 +1,480 @@
+use std::io::{self, ErrorKind};
+
+use bevy::prelude::*;
+#[cfg(feature = "server")]
+use bevy::utils::HashSet;
+#[cfg(feature = "client")]
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "server")]
+use zstd::bulk::Compressor;
+use zstd::dict::{DecoderDictionary, EncoderDictionary};
+#[cfg(feature = "client")]
+use zstd::{bulk::Decompressor, zstd_safe};
+
+#[cfg(feature = "client")]
+use crate::client::ClientSet;
+#[cfg(feature = "client")]
+use crate::core::replicon_client::RepliconClient;
+use crate::core::{
+    channels::ChannelKind,
+    event::{client_event::ClientEventAppExt, server_event::ServerEventAppExt},
+    postcard_utils,
+    protocol::{compression_kind::CompressionKind, protocol_version::ProtocolVersion},
+};
+#[cfg(any(feature = "client", feature = "server"))]
+use crate::core::{channels::ReplicationChannel, common_conditions::*};
+#[cfg(feature = "server")]
+use crate::{
+    core::{
+        event::{
+            client_event::FromClient,
+            server_event::{SendMode, ToClients},
+        },
+        replicon_server::RepliconServer,
+        ClientId,
+    },
+    server::{ClientConnected, ClientDisconnected, ServerSet},
+};
+
+/// Compresses mutate messages with a zstd dictionary shared between client and server.
+///
+/// Mutate messages are small, so regular compression barely reduces their size.
+/// A dictionary trained on the game's own payloads captures their common structure
+/// and significantly improves compression ratios.
+///
+/// To use it:
+/// 1. Insert [`DictionarySamples`] on server to collect mutate messages during a play session.
+/// 2. Train a dictionary with [`DictionarySamples::train`] or [`CompressionDictionary::train`]
+///    and ship it with the game.
+/// 3. Load it with [`CompressionDictionary::new`] and insert as resource on both client and server.
+///
+/// On connection the server sends [`DictionaryOffer`] with the ID of its dictionary.
+/// If the client has a dictionary with the same ID, it replies with [`DictionaryAccepted`]
+/// and the server starts compressing mutate messages for it. Clients without
+/// a matching dictionary continue receiving uncompressed messages.
+///
+/// Compressed messages are marked with [`CompressionKind::Dictionary`] and decompressed
+/// before replication receives them, so the plugin needs to be added to both client and server
+/// after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct DictionaryCompressionPlugin;
+
+impl Plugin for DictionaryCompressionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_server_event::<DictionaryOffer>(ChannelKind::Ordered)
+            .make_independent::<DictionaryOffer>()
+            .add_client_event::<DictionaryAccepted>(ChannelKind::Ordered);
+
+        #[cfg(feature = "client")]
+        app.add_systems(
+            PreUpdate,
+            (
+                decompress_mutations
+                    .after(ClientSet::ReceivePackets)
+                    .before(ClientSet::Receive),
+                accept_dictionary.after(ClientSet::Receive),
+            )
+                .run_if(client_connected),
+        );
+
+        #[cfg(feature = "server")]
+        app.init_resource::<DictionaryClients>()
+            .add_observer(offer_dictionary)
+            .add_observer(remove_dictionary_client)
+            .add_systems(
+                PreUpdate,
+                receive_acceptances
+                    .after(ServerSet::Receive)
+                    .run_if(server_running),
+            )
+            .add_systems(
+                PostUpdate,
+                compress_mutations
+                    .after(ServerSet::Send)
+                    .before(ServerSet::SendPackets)
+                    .run_if(server_running),
+            );
+    }
+}
+
+/// Size of the message header that is left uncompressed.
+///
+/// The version stays uncompressed to let clients reject messages from incompatible servers.
+const HEADER_SIZE: usize = size_of::<ProtocolVersion>() + size_of::<CompressionKind>();
+
+/// Maximum size of a decompressed mutate message.
+///
+/// Protects against malicious frames that declare a huge content size.
+const MAX_DECOMPRESSED_SIZE: usize = 64 * 1024;
+
+#[cfg(feature = "server")]
+fn offer_dictionary(
+    trigger: Trigger<ClientConnected>,
+    dictionary: Option<Res<CompressionDictionary>>,
+    mut offers: EventWriter<ToClients<DictionaryOffer>>,
+) {
+    if let Some(dictionary) = dictionary {
+        debug!(
+            "offering dictionary {} to `{:?}`",
+            dictionary.id(),
+            trigger.client_id
+        );
+        offers.send(ToClients {
+            mode: SendMode::Direct(trigger.client_id),
+            event: DictionaryOffer {
+                id: dictionary.id(),
+            },
+        });
+    }
+}
+
+#[cfg(feature = "server")]
+fn remove_dictionary_client(
+    trigger: Trigger<ClientDisconnected>,
+    mut dictionary_clients: ResMut<DictionaryClients>,
+) {
+    dictionary_clients.0.remove(&trigger.client_id);
+}
+
+#[cfg(feature = "server")]
+fn receive_acceptances(
+    mut accepted_events: EventReader<FromClient<DictionaryAccepted>>,
+    dictionary: Option<Res<CompressionDictionary>>,
+    mut dictionary_clients: ResMut<DictionaryClients>,
+) {
+    for &FromClient { client_id, event } in accepted_events.read() {
+        if dictionary
+            .as_ref()
+            .is_some_and(|dictionary| dictionary.id() == event.id)
+        {
+            debug!("`{client_id:?}` accepted dictionary {}", event.id);
+            dictionary_clients.0.insert(client_id);
+        } else {
+            debug!(
+                "ignoring acceptance of unknown dictionary {} from `{client_id:?}`",
+                event.id
+            );
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn compress_mutations(
+    mut server: ResMut<RepliconServer>,
+    dictionary: Option<Res<CompressionDictionary>>,
+    mut samples: Option<ResMut<DictionarySamples>>,
+    dictionary_clients: Res<DictionaryClients>,
+) {
+    let mut compressor = dictionary
+        .as_ref()
+        .filter(|_| !dictionary_clients.0.is_empty())
+        .map(|dictionary| Compressor::with_prepared_dictionary(&dictionary.encoder))
+        .transpose()
+        .unwrap_or_else(|e| {
+            error!("unable to create compressor: {e}");
+            None
+        });
+
+    let mutations_id: u8 = ReplicationChannel::Mutations.into();
+    for (client_id, channel_id, message) in server.iter_sent_mut() {
+        if *channel_id != mutations_id {
+            continue;
+        }
+
+        // Messages could be already compressed by `MessageCompression`
+        // or not drained by the backend since the last tick.
+        let Some((version, data)) = message.split_at_checked(size_of::<ProtocolVersion>()) else {
+            continue;
+        };
+        let Ok((CompressionKind::None, data)) = postcard::take_from_bytes(data) else {
+            continue;
+        };
+
+        if let Some(samples) = samples.as_mut().filter(|samples| !samples.is_full()) {
+            samples.messages.push(data.to_vec());
+        }
+
+        let Some(compressor) = compressor
+            .as_mut()
+            .filter(|_| dictionary_clients.0.contains(client_id))
+        else {
+            continue;
+        };
+
+        let compressed = match compressor.compress(data) {
+            Ok(compressed) => compressed,
+            Err(e) => {
+                error!("unable to compress mutate message for `{client_id:?}`: {e}");
+                continue;
+            }
+        };
+
+        let mut compressed_message = Vec::with_capacity(compressed.len() + HEADER_SIZE);
+        compressed_message.extend_from_slice(version);
+        postcard_utils::to_extend_mut(&CompressionKind::Dictionary, &mut compressed_message)
+            .expect("compression kind should always be serializable");
+        compressed_message.extend_from_slice(&compressed);
+        *message = compressed_message.into();
+    }
+}
+
+#[cfg(feature = "client")]
+fn accept_dictionary(
+    mut offers: EventReader<DictionaryOffer>,
+    mut accepted_events: EventWriter<DictionaryAccepted>,
+    dictionary: Option<Res<CompressionDictionary>>,
+) {
+    for &DictionaryOffer { id } in offers.read() {
+        if dictionary
+            .as_ref()
+            .is_some_and(|dictionary| dictionary.id() == id)
+        {
+            debug!("accepting dictionary {id}");
+            accepted_events.send(DictionaryAccepted { id });
+        } else {
+            warn!("server offered unknown dictionary {id}, mutations will be uncompressed");
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+fn decompress_mutations(
+    mut client: ResMut<RepliconClient>,
+    dictionary: Option<Res<CompressionDictionary>>,
+) {
+    let Some(messages) = client.received_mut(ReplicationChannel::Mutations) else {
+        return;
+    };
+
+    let mut decompressor = None;
+    messages.retain_mut(|message| {
+        let Some((version, data)) = message.split_at_checked(size_of::<ProtocolVersion>()) else {
+            error!("ignoring mutate message without protocol version");
+            return false;
+        };
+        let Ok((CompressionKind::Dictionary, data)) = postcard::take_from_bytes(data) else {
+            // Not compressed with the dictionary, will be processed by replication.
+            return true;
+        };
+
+        let Some(dictionary) = &dictionary else {
+            error!("ignoring compressed mutate message without a dictionary");
+            return false;
+        };
+        if decompressor.is_none() {
+            match Decompressor::with_prepared_dictionary(&dictionary.decoder) {
+                Ok(new_decompressor) => decompressor = Some(new_decompressor),
+                Err(e) => {
+                    error!("unable to create decompressor: {e}");
+                    return false;
+                }
+            }
+        }
+        let decompressor = decompressor.as_mut().unwrap();
+        match decompress(decompressor, data) {
+            Ok(decompressed) => {
+                let mut decompressed_message = Vec::with_capacity(HEADER_SIZE + decompressed.len());
+                decompressed_message.extend_from_slice(version);
+                postcard_utils::to_extend_mut(&CompressionKind::None, &mut decompressed_message)
+                    .expect("compression kind should always be serializable");
+                decompressed_message.extend_from_slice(&decompressed);
+                *message = decompressed_message.into();
+                true
+            }
+            Err(e) => {
+                error!("ignoring mutate message that failed to decompress: {e}");
+                false
+            }
+        }
+    });
+}
+
+#[cfg(feature = "client")]
+fn decompress(decompressor: &mut Decompressor, data: &[u8]) -> io::Result<Bytes> {
+    let capacity = match zstd_safe::get_frame_content_size(data) {
+        Ok(Some(size)) if size <= MAX_DECOMPRESSED_SIZE as u64 => size as usize,
+        _ => {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "frame should have a valid content size",
+            ))
+        }
+    };
+
+    decompressor.decompress(data, capacity).map(Into::into)
+}
+
+/// A zstd dictionary shared between client and server.
+///
+/// See [`DictionaryCompressionPlugin`] for details.
+#[derive(Resource)]
+pub struct CompressionDictionary {
+    id: u32,
+    data: Vec<u8>,
+    encoder: EncoderDictionary<'static>,
+    decoder: DecoderDictionary<'static>,
+}
+
+impl CompressionDictionary {
+    /// Magic number at the beginning of zstd dictionaries.
+    const MAGIC: u32 = 0xEC30A437;
+
+    /// Loads a dictionary from bytes produced by [`Self::train`] or the `zstd --train` command.
+    ///
+    /// Returns an error if the data doesn't have a zstd dictionary header.
+    pub fn new(data: Vec<u8>) -> io::Result<Self> {
+        let header = |range: std::ops::Range<usize>| {
+            data.get(range)
+                .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        };
+        if header(0..4) != Some(Self::MAGIC) {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "data should start with zstd dictionary magic number",
+            ));
+        }
+        let id = header(4..8).ok_or_else(|| {
+            io::Error::new(ErrorKind::InvalidData, "data should contain dictionary ID")
+        })?;
+
+        Ok(Self {
+            id,
+            encoder: EncoderDictionary::copy(&data, zstd::DEFAULT_COMPRESSION_LEVEL),
+            decoder: DecoderDictionary::copy(&data),
+            data,
+        })
+    }
+
+    /// Trains a dictionary from message samples.
+    ///
+    /// Intended to be used offline, the resulting [`Self::as_bytes`] should be shipped with the game.
+    /// `max_size` limits the dictionary size in bytes, a few kilobytes is usually enough.
+    pub fn train<S: AsRef<[u8]>>(samples: &[S], max_size: usize) -> io::Result<Self> {
+        let data = zstd::dict::from_samples(samples, max_size)?;
+        Self::new(data)
+    }
+
+    /// Returns the dictionary ID that is sent to clients on connection.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the raw dictionary data.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Collects mutate messages on server to train a [`CompressionDictionary`].
+///
+/// Messages are collected until the configured limit is reached.
+/// The resource is not added by default.
+#[cfg(feature = "server")]
+#[derive(Resource)]
+pub struct DictionarySamples {
+    messages: Vec<Vec<u8>>,
+    max_count: usize,
+}
+
+#[cfg(feature = "server")]
+impl DictionarySamples {
+    /// Creates a new instance that collects up to `max_count` messages.
+    pub fn new(max_count: usize) -> Self {
+        Self {
+            messages: Vec::new(),
+            max_count,
+        }
+    }
+
+    /// Returns collected messages.
+    ///
+    /// Can be saved to train a dictionary later with [`CompressionDictionary::train`].
+    pub fn messages(&self) -> &[Vec<u8>] {
+        &self.messages
+    }
+
+    /// Returns `true` if the limit of collected messages is reached.
+    pub fn is_full(&self) -> bool {
+        self.messages.len() >= self.max_count
+    }
+
+    /// Trains a dictionary from collected messages.
+    ///
+    /// See also [`CompressionDictionary::train`].
+    pub fn train(&self, max_size: usize) -> io::Result<CompressionDictionary> {
+        CompressionDictionary::train(&self.messages, max_size)
+    }
+}
+
+/// Clients that accepted the server's [`CompressionDictionary`].
+#[cfg(feature = "server")]
+#[derive(Resource, Default)]
+pub struct DictionaryClients(HashSet<ClientId>);
+
+#[cfg(feature = "server")]
+impl DictionaryClients {
+    /// Returns `true` if mutate messages are compressed for the client.
+    pub fn contains(&self, client_id: ClientId) -> bool {
+        self.0.contains(&client_id)
+    }
+}
+
+/// A server event with the ID of the server's [`CompressionDictionary`].
+///
+/// Sent to each client on connection if the dictionary is present.
+/// See [`DictionaryCompressionPlugin`] for details.
+#[derive(Event, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct DictionaryOffer {
+    /// ID of the dictionary.
+    pub id: u32,
+}
+
+/// A client event sent in response to [`DictionaryOffer`] if the client has a matching dictionary.
+///
+/// See [`DictionaryCompressionPlugin`] for details.
+#[derive(Event, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct DictionaryAccepted {
+    /// ID of the accepted dictionary.
+    pub id: u32,
+}
+
+#[cfg(all(test, feature = "client", feature = "server"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let dictionary = CompressionDictionary::train(&samples(), 1024).unwrap();
+        let loaded = CompressionDictionary::new(dictionary.as_bytes().to_vec()).unwrap();
+        assert_eq!(loaded.id(), dictionary.id());
+
+        let message = b"entity 42 position 1.0 2.0 3.0 velocity 0.5 0.5 0.5";
+        let mut compressor = Compressor::with_prepared_dictionary(&dictionary.encoder).unwrap();
+        let compressed = compressor.compress(message).unwrap();
+
+        let mut decompressor = Decompressor::with_prepared_dictionary(&loaded.decoder).unwrap();
+        let decompressed = decompress(&mut decompressor, &compressed).unwrap();
+        assert_eq!(&decompressed[..], message);
+    }
+
+    #[test]
+    fn invalid_dictionary() {
+        assert!(CompressionDictionary::new(vec![1, 2, 3]).is_err());
+        assert!(CompressionDictionary::new(b"not a dictionary".to_vec()).is_err());
+    }
+
+    fn samples() -> Vec<Vec<u8>> {
+        (0..1000)
+            .map(|index| {
+                format!(
+                    "entity {} position {}.0 {}.0 {}.0 velocity 0.5 0.5 0.5",
+                    index,
+                    index % 7,
+                    index % 11,
+                    index % 13
+                )
+                .into_bytes()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+#[cfg(feature = "server")]
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(feature = "client", feature = "server"))]
+use crate::core::common_conditions::*;
+use crate::core::{channels::ChannelKind, event::client_event::ClientEventAppExt};
+#[cfg(feature = "client")]
+use crate::{
+    client::{BufferedMutations, ClientSet, ServerUpdateTick},
+    core::replicon_client::RepliconClient,
+};
+#[cfg(feature = "server")]
+use crate::{
+    core::{event::client_event::FromClient, ClientId},
+    server::{ClientDisconnected, ServerSet},
+};
+
+/// Reports of the client-perceived replication quality to the server.
+///
+/// Clients send [`ClientHealth`] every [`Self::interval`] over an unreliable channel.
+/// The server stores the last received report for each client in [`ClientHealthReports`],
+/// so operators can see the applied state quality, not just transport statistics.
+///
+/// Should be added to both client and server after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct HealthReportPlugin {
+    /// How often clients send reports.
+    pub interval: Duration,
+}
+
+impl Default for HealthReportPlugin {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for HealthReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_client_event::<ClientHealth>(ChannelKind::Unreliable);
+
+        #[cfg(feature = "client")]
+        app.add_systems(
+            PostUpdate,
+            send_report(self.interval)
+                .before(ClientSet::Send)
+                .run_if(client_connected),
+        );
+
+        #[cfg(feature = "server")]
+        app.init_resource::<ClientHealthReports>()
+            .add_observer(untrack_client)
+            .add_systems(
+                PreUpdate,
+                receive_reports
+                    .after(ServerSet::Receive)
+                    .run_if(server_running),
+            );
+    }
+}
+
+#[cfg(feature = "client")]
+fn send_report(
+    interval: Duration,
+) -> impl FnMut(
+    Local<Option<Duration>>,
+    EventWriter<ClientHealth>,
+    Res<Time>,
+    Res<RepliconClient>,
+    Option<Res<ServerUpdateTick>>,
+    Option<Res<BufferedMutations>>,
+) {
+    move |mut last_sent: Local<Option<Duration>>,
+          mut reports: EventWriter<ClientHealth>,
+          time: Res<Time>,
+          client: Res<RepliconClient>,
+          update_tick: Option<Res<ServerUpdateTick>>,
+          buffered_mutations: Option<Res<BufferedMutations>>| {
+        if last_sent.is_some_and(|last_sent| time.elapsed() - last_sent < interval) {
+            return;
+        }
+
+        let mut health = ClientHealth {
+            decode_errors: client.decode_errors().try_into().unwrap_or(u32::MAX),
+            ..Default::default()
+        };
+        if let Some(buffered_mutations) = buffered_mutations {
+            health.buffered_messages = buffered_mutations.len().try_into().unwrap_or(u32::MAX);
+            if let Some((update_tick, newest_tick)) =
+                update_tick.zip(buffered_mutations.newest_tick())
+            {
+                if newest_tick > **update_tick {
+                    health.tick_lag = newest_tick - **update_tick;
+                }
+            }
+        }
+
+        trace!("sending `{health:?}`");
+        *last_sent = Some(time.elapsed());
+        reports.send(health);
+    }
+}
+
+#[cfg(feature = "server")]
+fn untrack_client(trigger: Trigger<ClientDisconnected>, mut reports: ResMut<ClientHealthReports>) {
+    reports.0.remove(&trigger.client_id);
+}
+
+#[cfg(feature = "server")]
+fn receive_reports(
+    mut health_events: EventReader<FromClient<ClientHealth>>,
+    time: Res<Time>,
+    mut reports: ResMut<ClientHealthReports>,
+) {
+    for &FromClient { client_id, event } in health_events.read() {
+        reports.0.insert(
+            client_id,
+            HealthReport {
+                health: event,
+                received_at: time.elapsed(),
+            },
+        );
+    }
+}
+
+/// A client event with a summary of the applied replication state.
+///
+/// See [`HealthReportPlugin`] for details.
+#[derive(Event, Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ClientHealth {
+    /// Number of ticks between the last applied update tick and the newest
+    /// buffered mutate message.
+    ///
+    /// Grows when update messages are delayed or lost.
+    pub tick_lag: u32,
+
+    /// Number of mutate messages waiting for their update messages.
+    pub buffered_messages: u32,
+
+    /// Number of received messages that failed to decode since the connection.
+    ///
+    /// See [`RepliconClient::decode_errors`](crate::core::replicon_client::RepliconClient::decode_errors).
+    pub decode_errors: u32,
+}
+
+/// The last received [`ClientHealth`] for each connected client.
+///
+/// See [`HealthReportPlugin`] for details.
+#[cfg(feature = "server")]
+#[derive(Resource, Default)]
+pub struct ClientHealthReports(HashMap<ClientId, HealthReport>);
+
+#[cfg(feature = "server")]
+impl ClientHealthReports {
+    /// Returns the last report from the client.
+    ///
+    /// Returns [`None`] if the client hasn't sent any reports yet.
+    pub fn get(&self, client_id: ClientId) -> Option<&HealthReport> {
+        self.0.get(&client_id)
+    }
+
+    /// Returns an iterator over clients and their last reports.
+    pub fn iter(&self) -> impl Iterator<Item = (ClientId, &HealthReport)> {
+        self.0
+            .iter()
+            .map(|(&client_id, report)| (client_id, report))
+    }
+}
+
+/// A received [`ClientHealth`].
+///
+/// See [`ClientHealthReports`].
+#[cfg(feature = "server")]
+#[derive(Clone, Copy, Debug)]
+pub struct HealthReport {
+    /// Reported health.
+    pub health: ClientHealth,
+
+    /// Time when the report was received.
+    pub received_at: Duration,
+}
@@ -0,0 +1,215 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+#[cfg(feature = "server")]
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "client")]
+use crate::client::ClientSet;
+#[cfg(any(feature = "client", feature = "server"))]
+use crate::core::common_conditions::*;
+use crate::core::{channels::ChannelKind, event::client_event::ClientEventAppExt};
+#[cfg(feature = "server")]
+use crate::{
+    core::{event::client_event::FromClient, ClientId},
+    server::{ClientConnected, ClientDisconnected, ServerSet},
+};
+
+/// Transport-agnostic keepalive to detect lagging clients.
+///
+/// Clients send [`Heartbeat`] every [`Self::interval`] over an unreliable channel.
+/// If the server doesn't receive it from a client for longer than [`Self::timeout`],
+/// the client is marked as unresponsive in [`ClientHeartbeats`] and [`ClientUnresponsive`] is emitted.
+/// When heartbeats resume, [`ClientResponsive`] is emitted.
+///
+/// Use a timeout shorter than the one configured in the messaging backend
+/// to freeze or hide lagging players before they are disconnected.
+///
+/// Should be added to both client and server after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct HeartbeatPlugin {
+    /// How often clients send heartbeats.
+    pub interval: Duration,
+
+    /// Time without heartbeats after which a client is considered unresponsive.
+    pub timeout: Duration,
+}
+
+impl Default for HeartbeatPlugin {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(250),
+            timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+impl Plugin for HeartbeatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_client_event::<Heartbeat>(ChannelKind::Unreliable);
+
+        #[cfg(feature = "client")]
+        app.add_systems(
+            PostUpdate,
+            send_heartbeat(self.interval)
+                .before(ClientSet::Send)
+                .run_if(client_connected),
+        );
+
+        #[cfg(feature = "server")]
+        app.init_resource::<ClientHeartbeats>()
+            .add_event::<ClientUnresponsive>()
+            .add_event::<ClientResponsive>()
+            .add_observer(track_client)
+            .add_observer(untrack_client)
+            .add_systems(
+                PreUpdate,
+                (receive_heartbeats, detect_unresponsive(self.timeout))
+                    .chain()
+                    .after(ServerSet::Receive)
+                    .run_if(server_running),
+            );
+    }
+}
+
+#[cfg(feature = "client")]
+fn send_heartbeat(
+    interval: Duration,
+) -> impl FnMut(Local<Option<Duration>>, EventWriter<Heartbeat>, Res<Time>) {
+    move |mut last_sent: Local<Option<Duration>>,
+          mut heartbeats: EventWriter<Heartbeat>,
+          time: Res<Time>| {
+        if last_sent.is_some_and(|last_sent| time.elapsed() - last_sent < interval) {
+            return;
+        }
+
+        trace!("sending heartbeat");
+        *last_sent = Some(time.elapsed());
+        heartbeats.send(Heartbeat);
+    }
+}
+
+#[cfg(feature = "server")]
+fn track_client(
+    trigger: Trigger<ClientConnected>,
+    time: Res<Time>,
+    mut heartbeats: ResMut<ClientHeartbeats>,
+) {
+    heartbeats.0.insert(
+        trigger.client_id,
+        HeartbeatState {
+            last_received: time.elapsed(),
+            unresponsive: false,
+        },
+    );
+}
+
+#[cfg(feature = "server")]
+fn untrack_client(trigger: Trigger<ClientDisconnected>, mut heartbeats: ResMut<ClientHeartbeats>) {
+    heartbeats.0.remove(&trigger.client_id);
+}
+
+#[cfg(feature = "server")]
+fn receive_heartbeats(
+    mut heartbeat_events: EventReader<FromClient<Heartbeat>>,
+    mut responsive_events: EventWriter<ClientResponsive>,
+    time: Res<Time>,
+    mut heartbeats: ResMut<ClientHeartbeats>,
+) {
+    for &FromClient { client_id, .. } in heartbeat_events.read() {
+        let Some(state) = heartbeats.0.get_mut(&client_id) else {
+            continue;
+        };
+
+        state.last_received = time.elapsed();
+        if state.unresponsive {
+            debug!("`{client_id:?}` became responsive");
+            state.unresponsive = false;
+            responsive_events.send(ClientResponsive { client_id });
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn detect_unresponsive(
+    timeout: Duration,
+) -> impl FnMut(EventWriter<ClientUnresponsive>, Res<Time>, ResMut<ClientHeartbeats>) {
+    move |mut unresponsive_events: EventWriter<ClientUnresponsive>,
+          time: Res<Time>,
+          mut heartbeats: ResMut<ClientHeartbeats>| {
+        for (&client_id, state) in &mut heartbeats.0 {
+            if !state.unresponsive && time.elapsed() - state.last_received > timeout {
+                debug!("`{client_id:?}` became unresponsive");
+                state.unresponsive = true;
+                unresponsive_events.send(ClientUnresponsive { client_id });
+            }
+        }
+    }
+}
+
+/// A client event sent periodically to signal that the client is alive.
+///
+/// See [`HeartbeatPlugin`] for details.
+#[derive(Event, Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct Heartbeat;
+
+/// Heartbeat state of connected clients.
+///
+/// See [`HeartbeatPlugin`] for details.
+#[cfg(feature = "server")]
+#[derive(Resource, Default)]
+pub struct ClientHeartbeats(HashMap<ClientId, HeartbeatState>);
+
+#[cfg(feature = "server")]
+impl ClientHeartbeats {
+    /// Returns `true` if the client didn't send heartbeats for longer than the configured timeout.
+    pub fn is_unresponsive(&self, client_id: ClientId) -> bool {
+        self.0
+            .get(&client_id)
+            .is_some_and(|state| state.unresponsive)
+    }
+
+    /// Returns the time when the last heartbeat was received from the client.
+    ///
+    /// Initialized with the connection time.
+    pub fn last_received(&self, client_id: ClientId) -> Option<Duration> {
+        self.0.get(&client_id).map(|state| state.last_received)
+    }
+
+    /// Returns an iterator over unresponsive clients.
+    pub fn iter_unresponsive(&self) -> impl Iterator<Item = ClientId> + '_ {
+        self.0
+            .iter()
+            .filter(|(_, state)| state.unresponsive)
+            .map(|(&client_id, _)| client_id)
+    }
+}
+
+#[cfg(feature = "server")]
+struct HeartbeatState {
+    /// Time when the last heartbeat was received.
+    last_received: Duration,
+
+    /// Whether [`ClientUnresponsive`] was emitted without the following [`ClientResponsive`].
+    unresponsive: bool,
+}
+
+/// An event that emitted on server when a client stops sending heartbeats.
+///
+/// See [`HeartbeatPlugin`] for details.
+#[cfg(feature = "server")]
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientUnresponsive {
+    /// The unresponsive client.
+    pub client_id: ClientId,
+}
+
+/// An event that emitted on server when an unresponsive client resumes sending heartbeats.
+///
+/// See [`HeartbeatPlugin`] for details.
+#[cfg(feature = "server")]
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientResponsive {
+    /// The client that resumed sending heartbeats.
+    pub client_id: ClientId,
+}
@@ -0,0 +1,248 @@
+#[cfg(feature = "client")]
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(feature = "client", feature = "server"))]
+use crate::core::common_conditions::*;
+use crate::core::{
+    channels::ChannelKind,
+    event::{client_event::ClientEventAppExt, server_event::ServerEventAppExt},
+};
+#[cfg(feature = "client")]
+use crate::{
+    client::{confirm_history::ConfirmHistory, ClientSet, ServerUpdateTick},
+    core::{
+        replication::replication_registry::{ctx::DespawnCtx, ReplicationRegistry},
+        replicon_tick::RepliconTick,
+        server_entity_map::ServerEntityMap,
+    },
+};
+#[cfg(feature = "server")]
+use crate::{
+    core::{
+        event::{
+            client_event::FromClient,
+            server_event::{SendMode, ToClients},
+        },
+        replication::{replicated_clients::ReplicatedClients, Replicated},
+    },
+    server::ServerSet,
+};
+
+/// Detection of client entities that possibly missed their despawn.
+///
+/// Entities are tracked on client via [`ConfirmHistory`].
+/// If a mapped entity wasn't confirmed for more than [`Self::max_ticks`] ticks while other entities
+/// received newer ticks, [`PossiblyOrphaned`] is emitted for it once.
+///
+/// Entities that are rarely mutated will also be reported, so pick a number of ticks
+/// larger than the expected mutation interval.
+///
+/// If [`Self::resync`] is enabled, the client also sends [`ResyncRequest`] with the reported entities.
+/// The server sends such entities again if they are still replicated to the client.
+/// Otherwise the server replies with [`OrphanedEntities`] and the client despawns them.
+///
+/// Should be added to both client and server after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct OrphanDetectionPlugin {
+    /// Number of ticks without confirmation after which an entity is considered possibly orphaned.
+    pub max_ticks: u32,
+
+    /// Request resync for possibly orphaned entities.
+    pub resync: bool,
+}
+
+impl Default for OrphanDetectionPlugin {
+    fn default() -> Self {
+        Self {
+            max_ticks: 300,
+            resync: false,
+        }
+    }
+}
+
+impl Plugin for OrphanDetectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_client_event::<ResyncRequest>(ChannelKind::Ordered)
+            .add_server_event::<OrphanedEntities>(ChannelKind::Ordered);
+
+        #[cfg(feature = "client")]
+        app.add_event::<PossiblyOrphaned>().add_systems(
+            PreUpdate,
+            (detect_orphans(self.max_ticks, self.resync), despawn_orphans)
+                .chain()
+                .after(ClientSet::Receive)
+                .run_if(client_connected),
+        );
+
+        #[cfg(feature = "server")]
+        app.add_systems(
+            PreUpdate,
+            receive_resync_requests
+                .after(ServerSet::Receive)
+                .run_if(server_running),
+        );
+    }
+}
+
+#[cfg(feature = "client")]
+fn detect_orphans(
+    max_ticks: u32,
+    resync: bool,
+) -> impl FnMut(
+    Local<EntityHashSet>,
+    EventWriter<PossiblyOrphaned>,
+    EventWriter<ResyncRequest>,
+    Query<(Entity, &ConfirmHistory)>,
+    Res<ServerEntityMap>,
+    Res<ServerUpdateTick>,
+) {
+    move |mut reported: Local<EntityHashSet>,
+          mut orphaned_events: EventWriter<PossiblyOrphaned>,
+          mut resync_events: EventWriter<ResyncRequest>,
+          entities: Query<(Entity, &ConfirmHistory)>,
+          entity_map: Res<ServerEntityMap>,
+          update_tick: Res<ServerUpdateTick>| {
+        let latest_tick = entities
+            .iter()
+            .map(|(_, history)| history.last_tick())
+            .fold(
+                **update_tick,
+                |latest, tick| {
+                    if tick > latest {
+                        tick
+                    } else {
+                        latest
+                    }
+                },
+            );
+
+        reported.retain(|&entity| {
+            entities
+                .get(entity)
+                .is_ok_and(|(_, history)| is_stale(latest_tick, history, max_ticks))
+        });
+
+        let mut request = ResyncRequest::default();
+        for (entity, history) in &entities {
+            if !is_stale(latest_tick, history, max_ticks) || !reported.insert(entity) {
+                continue;
+            }
+            let Some(server_entity) = entity_map.get_by_client(entity) else {
+                continue;
+            };
+
+            debug!(
+                "`{entity}` wasn't confirmed since {:?}, latest tick is {latest_tick:?}",
+                history.last_tick()
+            );
+            orphaned_events.send(PossiblyOrphaned { entity });
+            if resync {
+                request.entities.push(server_entity);
+            }
+        }
+
+        if !request.entities.is_empty() {
+            debug!("requesting resync for {} entities", request.entities.len());
+            resync_events.send(request);
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+fn is_stale(latest_tick: RepliconTick, history: &ConfirmHistory, max_ticks: u32) -> bool {
+    latest_tick - history.last_tick() > max_ticks
+}
+
+/// Despawns entities that the server no longer replicates to this client.
+#[cfg(feature = "client")]
+fn despawn_orphans(
+    mut commands: Commands,
+    mut orphaned_events: EventReader<OrphanedEntities>,
+    mut entity_map: ResMut<ServerEntityMap>,
+    registry: Res<ReplicationRegistry>,
+    update_tick: Res<ServerUpdateTick>,
+) {
+    for event in orphaned_events.read() {
+        for &server_entity in &event.entities {
+            let Some(client_entity) = entity_map.remove_by_server(server_entity) else {
+                continue;
+            };
+
+            debug!("despawning orphaned `{client_entity}`");
+            let despawn = registry.despawn;
+            let ctx = DespawnCtx {
+                message_tick: **update_tick,
+            };
+            commands.queue(move |world: &mut World| {
+                if let Ok(entity) = world.get_entity_mut(client_entity) {
+                    (despawn)(&ctx, entity);
+                }
+            });
+        }
+    }
+}
+
+/// Resends requested entities or reports them as orphaned if they are no longer replicated to the client.
+#[cfg(feature = "server")]
+fn receive_resync_requests(
+    mut request_events: EventReader<FromClient<ResyncRequest>>,
+    mut orphaned_events: EventWriter<ToClients<OrphanedEntities>>,
+    mut replicated_clients: ResMut<ReplicatedClients>,
+    replicated: Query<(), With<Replicated>>,
+) {
+    for FromClient { client_id, event } in request_events.read() {
+        let Some(client) = replicated_clients.get_client_mut(*client_id) else {
+            continue;
+        };
+
+        let mut orphaned = OrphanedEntities::default();
+        for &entity in &event.entities {
+            if replicated.contains(entity) && client.visibility().is_visible(entity) {
+                debug!("resending `{entity}` to `{client_id:?}`");
+                client.remove_mutation_tick(entity);
+            } else {
+                orphaned.entities.push(entity);
+            }
+        }
+
+        if !orphaned.entities.is_empty() {
+            debug!(
+                "reporting {} orphaned entities to `{client_id:?}`",
+                orphaned.entities.len()
+            );
+            orphaned_events.send(ToClients {
+                mode: SendMode::Direct(*client_id),
+                event: orphaned,
+            });
+        }
+    }
+}
+
+/// An event that emitted on client for a mapped entity that wasn't confirmed for too long.
+///
+/// See [`OrphanDetectionPlugin`] for details.
+#[cfg(feature = "client")]
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PossiblyOrphaned {
+    /// Client entity.
+    pub entity: Entity,
+}
+
+/// A client event to request full state of entities.
+///
+/// See [`OrphanDetectionPlugin`] for details.
+#[derive(Event, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ResyncRequest {
+    /// Server entities.
+    pub entities: Vec<Entity>,
+}
+
+/// A server event with requested entities that are no longer replicated to the client.
+///
+/// See [`OrphanDetectionPlugin`] for details.
+#[derive(Event, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct OrphanedEntities {
+    /// Server entities.
+    pub entities: Vec<Entity>,
+}
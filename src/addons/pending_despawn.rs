@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+#[cfg(feature = "server")]
+use bevy::ecs::entity::EntityHashMap;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    channels::ChannelKind, event::client_event::ClientEventAppExt,
+    replication::replication_rules::AppRuleExt,
+};
+#[cfg(feature = "server")]
+use crate::{
+    core::{
+        common_conditions::*, event::client_event::FromClient,
+        replication::replicated_clients::ReplicatedClients, ClientId,
+    },
+    server::ServerSet,
+};
+
+/// Two-phase despawn protocol.
+///
+/// Instead of despawning an entity on server, insert [`PendingDespawn`] into it.
+/// The component will be replicated, so clients can run their exit logic (like death animations)
+/// while the entity data is still available. Once a client is done, it should send [`ConfirmDespawn`].
+/// The server despawns the entity after all clients that can see it confirmed the despawn
+/// or after [`Self::timeout`], whichever comes first.
+///
+/// Should be added to both client and server after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct PendingDespawnPlugin {
+    /// Maximum time to wait for confirmations from clients.
+    pub timeout: Duration,
+}
+
+impl Default for PendingDespawnPlugin {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl Plugin for PendingDespawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.replicate::<PendingDespawn>()
+            .add_mapped_client_event::<ConfirmDespawn>(ChannelKind::Ordered);
+
+        #[cfg(feature = "server")]
+        app.init_resource::<PendingDespawns>()
+            .add_observer(start_pending)
+            .add_observer(stop_pending)
+            .add_systems(
+                PreUpdate,
+                (receive_confirmations, despawn_confirmed(self.timeout))
+                    .chain()
+                    .after(ServerSet::Receive)
+                    .run_if(server_or_singleplayer),
+            );
+    }
+}
+
+#[cfg(feature = "server")]
+fn start_pending(
+    trigger: Trigger<OnAdd, PendingDespawn>,
+    time: Res<Time>,
+    mut pending_despawns: ResMut<PendingDespawns>,
+) {
+    pending_despawns.insert(
+        trigger.entity(),
+        PendingState {
+            since: time.elapsed(),
+            confirmed: Default::default(),
+        },
+    );
+}
+
+#[cfg(feature = "server")]
+fn stop_pending(
+    trigger: Trigger<OnRemove, PendingDespawn>,
+    mut pending_despawns: ResMut<PendingDespawns>,
+) {
+    pending_despawns.remove(&trigger.entity());
+}
+
+#[cfg(feature = "server")]
+fn receive_confirmations(
+    mut confirm_events: EventReader<FromClient<ConfirmDespawn>>,
+    mut pending_despawns: ResMut<PendingDespawns>,
+) {
+    for FromClient { client_id, event } in confirm_events.read() {
+        match pending_despawns.get_mut(&event.entity) {
+            Some(state) => {
+                debug!("`{client_id:?}` confirmed despawn for `{}`", event.entity);
+                state.confirmed.push(*client_id);
+            }
+            None => debug!(
+                "ignoring despawn confirmation from `{client_id:?}` for `{}` that isn't pending",
+                event.entity
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn despawn_confirmed(
+    timeout: Duration,
+) -> impl FnMut(Commands, Res<Time>, Res<PendingDespawns>, Res<ReplicatedClients>) {
+    move |mut commands: Commands,
+          time: Res<Time>,
+          pending_despawns: Res<PendingDespawns>,
+          replicated_clients: Res<ReplicatedClients>| {
+        for (&entity, state) in pending_despawns.iter() {
+            let timed_out = time.elapsed() - state.since >= timeout;
+            if timed_out
+                || replicated_clients
+                    .iter()
+                    .filter(|client| client.visibility().is_visible(entity))
+                    .all(|client| state.confirmed.contains(&client.id()))
+            {
+                if timed_out {
+                    debug!("despawning `{entity}` after confirmation timeout");
+                } else {
+                    debug!("despawning `{entity}` after confirmation from all clients");
+                }
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Marks an entity for despawn on server.
+///
+/// See [`PendingDespawnPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct PendingDespawn;
+
+/// A client event to confirm that the client finished its exit logic for an entity with [`PendingDespawn`].
+///
+/// Entity will be mapped to server automatically.
+#[derive(Event, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct ConfirmDespawn {
+    pub entity: Entity,
+}
+
+impl MapEntities for ConfirmDespawn {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity = entity_mapper.map_entity(self.entity);
+    }
+}
+
+/// Entities with [`PendingDespawn`] and their confirmation state.
+#[cfg(feature = "server")]
+#[derive(Resource, Default, Deref, DerefMut)]
+struct PendingDespawns(EntityHashMap<PendingState>);
+
+#[cfg(feature = "server")]
+struct PendingState {
+    /// Time when [`PendingDespawn`] was inserted.
+    since: Duration,
+
+    /// Clients that confirmed the despawn.
+    confirmed: Vec<ClientId>,
+}
@@ -0,0 +1,202 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(feature = "client")]
+use crate::client::ClientSet;
+#[cfg(any(feature = "client", feature = "server"))]
+use crate::core::common_conditions::*;
+use crate::core::replication::{command_markers::AppMarkerExt, replication_rules::AppRuleExt};
+#[cfg(feature = "server")]
+use crate::{core::replication::Replicated, server::ServerSet};
+
+/// Integration point for physics engines.
+///
+/// Implemented by physics networking crates to bridge the engine with replication.
+/// See [`PhysicsPlugin`] for details.
+pub trait PhysicsBackend: Send + Sync + 'static {
+    /// Replicated kinematic target of a body, such as position and rotation.
+    type Target: Component + Clone + PartialEq + Serialize + DeserializeOwned;
+
+    /// Returns `true` if the entity is simulated by the physics engine on server.
+    fn is_authoritative(entity: &EntityRef) -> bool;
+
+    /// Reads the current kinematic target from an authoritative entity.
+    fn read_target(entity: &EntityRef) -> Self::Target;
+
+    /// Applies a received target to a client entity.
+    ///
+    /// For example, moves a kinematic body.
+    fn apply_target(target: &Self::Target, entity: &mut EntityMut);
+
+    /// Prepares a client entity after receiving its first target.
+    ///
+    /// For example, inserts a kinematic body to avoid simulating it locally.
+    fn prepare_client_entity(_entity: &mut EntityCommands) {}
+}
+
+/**
+Replicates physics-authoritative entities using a [`PhysicsBackend`].
+
+On server, each tick entities with [`Replicated`]
+for which [`PhysicsBackend::is_authoritative`] returns `true` get [`PhysicsAuthority`]
+and an updated [`PhysicsBackend::Target`] that replicates to clients.
+
+On client, entities that received a target are marked with [`PhysicsInterpolated`]
+and prepared with [`PhysicsBackend::prepare_client_entity`]. Each changed target is
+passed to [`PhysicsBackend::apply_target`].
+
+[`PhysicsInterpolated`] is registered as a marker, so interpolation crates can buffer
+targets using [`AppMarkerExt::set_marker_fns`] and write them into the component over time.
+The marker is inserted after the first target is received, so only the first target
+is written directly.
+
+Should be added to both client and server after [`RepliconPlugins`](crate::RepliconPlugins).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::physics::{PhysicsBackend, PhysicsPlugin},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    PhysicsPlugin::<MyPhysics>::default(),
+));
+
+struct MyPhysics;
+
+impl PhysicsBackend for MyPhysics {
+    type Target = BodyTarget;
+
+    fn is_authoritative(entity: &EntityRef) -> bool {
+        entity.contains::<DynamicBody>()
+    }
+
+    fn read_target(entity: &EntityRef) -> Self::Target {
+        let transform = entity.get::<Transform>().unwrap();
+        BodyTarget(transform.translation)
+    }
+
+    fn apply_target(target: &Self::Target, entity: &mut EntityMut) {
+        if let Some(mut transform) = entity.get_mut::<Transform>() {
+            transform.translation = target.0;
+        }
+    }
+
+    fn prepare_client_entity(entity: &mut EntityCommands) {
+        entity.insert(Transform::default());
+    }
+}
+
+#[derive(Component)]
+struct DynamicBody;
+
+#[derive(Component, Clone, PartialEq, Deserialize, Serialize)]
+struct BodyTarget(Vec3);
+```
+**/
+pub struct PhysicsPlugin<B>(PhantomData<B>);
+
+impl<B> Default for PhysicsPlugin<B> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<B: PhysicsBackend> Plugin for PhysicsPlugin<B> {
+    fn build(&self, app: &mut App) {
+        app.register_marker::<PhysicsInterpolated>()
+            .replicate::<B::Target>();
+
+        #[cfg(feature = "client")]
+        app.add_observer(prepare_client_entity::<B>).add_systems(
+            PreUpdate,
+            apply_targets::<B>
+                .after(ClientSet::Receive)
+                .run_if(client_connected),
+        );
+
+        #[cfg(feature = "server")]
+        app.add_systems(
+            PostUpdate,
+            update_targets::<B>
+                .before(ServerSet::Send)
+                .run_if(server_running),
+        );
+    }
+}
+
+#[cfg(feature = "client")]
+fn prepare_client_entity<B: PhysicsBackend>(
+    trigger: Trigger<OnAdd, B::Target>,
+    mut commands: Commands,
+    authorities: Query<(), With<PhysicsAuthority>>,
+) {
+    // Skip targets inserted on server.
+    if authorities.contains(trigger.entity()) {
+        return;
+    }
+
+    let mut entity = commands.entity(trigger.entity());
+    entity.insert(PhysicsInterpolated);
+    B::prepare_client_entity(&mut entity);
+}
+
+#[cfg(feature = "client")]
+fn apply_targets<B: PhysicsBackend>(mut bodies: Query<EntityMut, Changed<B::Target>>) {
+    for mut entity in &mut bodies {
+        let target = entity
+            .get::<B::Target>()
+            .expect("query should filter by target")
+            .clone();
+        B::apply_target(&target, &mut entity);
+    }
+}
+
+#[cfg(feature = "server")]
+fn update_targets<B: PhysicsBackend>(world: &mut World) {
+    let mut query = world.query_filtered::<EntityRef, With<Replicated>>();
+    let mut targets = Vec::new();
+    let mut lost = Vec::new();
+    for entity in query.iter(world) {
+        if B::is_authoritative(&entity) {
+            let target = B::read_target(&entity);
+            if entity.get::<B::Target>() != Some(&target) {
+                targets.push((entity.id(), target));
+            }
+        } else if entity.contains::<PhysicsAuthority>() {
+            lost.push(entity.id());
+        }
+    }
+
+    for (entity, target) in targets {
+        world.entity_mut(entity).insert((PhysicsAuthority, target));
+    }
+
+    for entity in lost {
+        debug!("removing physics authority from `{entity}`");
+        world
+            .entity_mut(entity)
+            .remove::<(PhysicsAuthority, B::Target)>();
+    }
+}
+
+/// Marks entities simulated by the physics engine on server.
+///
+/// See [`PhysicsPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct PhysicsAuthority;
+
+/// Marks entities that receive physics targets from server.
+///
+/// See [`PhysicsPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct PhysicsInterpolated;
@@ -0,0 +1,193 @@
+#[cfg(feature = "server")]
+use std::{cmp::Reverse, mem};
+
+use bevy::prelude::*;
+#[cfg(feature = "server")]
+use bevy::utils::HashSet;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::core::{channels::ChannelKind, event::server_event::ServerEventAppExt};
+#[cfg(feature = "server")]
+use crate::{
+    core::{
+        common_conditions::server_running,
+        event::server_event::{SendMode, ToClients},
+        replication::replicated_clients::ReplicatedClients,
+        ClientId,
+    },
+    server::{relevance::InterestAnchor, ServerSet},
+};
+
+/// Server-driven hints for clients about content to load ahead of replication.
+pub trait AppPreloadExt {
+    /**
+    Registers [`PreloadHint<K>`] to tell clients which content to preload.
+
+    `K` identifies a group of assets, such as a scene or a world chunk.
+
+    The server can send hints manually via [`ToClients`]
+    or derive them from upcoming visibility with [`PreloadArea<K>`].
+    On clients hints are emitted as regular events right after receiving, without waiting for replication.
+
+    Should be registered on both client and server.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        addons::preload_hints::{AppPreloadExt, PreloadArea, PreloadHint},
+        prelude::*,
+        server::relevance::RelevancePlugin,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    app.add_plugins((
+        RepliconPlugins.set(ServerPlugin {
+            visibility_policy: VisibilityPolicy::Whitelist,
+            ..Default::default()
+        }),
+        RelevancePlugin {
+            radius: 50.0,
+            ..Default::default()
+        },
+    ))
+    .add_preload_hints::<ChunkId>()
+    .add_systems(Startup, spawn_chunks.run_if(server_running))
+    .add_systems(PreUpdate, preload_chunks.after(ClientSet::Receive));
+
+    fn spawn_chunks(mut commands: Commands) {
+        commands.spawn((
+            // Hint clients when their anchors come closer than the relevance radius.
+            PreloadArea {
+                key: ChunkId(0),
+                priority: 1,
+                radius: 80.0,
+            },
+            Transform::default(),
+        ));
+    }
+
+    fn preload_chunks(mut hints: EventReader<PreloadHint<ChunkId>>) {
+        for hint in hints.read() {
+            info!("start loading chunk {:?}", hint.key);
+        }
+    }
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    struct ChunkId(u32);
+    ```
+    **/
+    fn add_preload_hints<K>(&mut self) -> &mut Self
+    where
+        K: Serialize + DeserializeOwned + Clone + Send + Sync + 'static;
+}
+
+impl AppPreloadExt for App {
+    fn add_preload_hints<K>(&mut self) -> &mut Self
+    where
+        K: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        self.add_server_event::<PreloadHint<K>>(ChannelKind::Ordered)
+            .make_independent::<PreloadHint<K>>();
+
+        #[cfg(feature = "server")]
+        self.add_systems(
+            PostUpdate,
+            send_area_hints::<K>
+                .before(ServerSet::Send)
+                .run_if(server_running),
+        );
+
+        self
+    }
+}
+
+/// Asks a client to start loading content ahead of its replication.
+///
+/// See [`AppPreloadExt::add_preload_hints`].
+#[derive(Event, Clone, Debug, Deserialize, Serialize)]
+pub struct PreloadHint<K: Send + Sync + 'static> {
+    /// Content to load.
+    pub key: K,
+
+    /// Importance of the hint, higher values should be loaded first.
+    ///
+    /// Hints derived from [`PreloadArea`] in the same tick are sent in descending order.
+    pub priority: u8,
+}
+
+/// A server area that sends [`PreloadHint<K>`] to clients whose anchors come within [`Self::radius`].
+///
+/// Anchors are entities with [`InterestAnchor`]
+/// and [`Transform`]. Use a radius larger than the one from
+/// [`RelevancePlugin`](crate::server::relevance::RelevancePlugin) to hint clients
+/// before the area content becomes visible.
+///
+/// A hint is sent once when a client approaches the area and again only after
+/// all its anchors leave the radius and come back.
+#[derive(Component, Clone, Debug)]
+#[require(Transform)]
+pub struct PreloadArea<K: Send + Sync + 'static> {
+    /// Content associated with the area.
+    pub key: K,
+
+    /// Priority for the sent hint.
+    pub priority: u8,
+
+    /// Distance from the area at which the hint is sent.
+    pub radius: f32,
+}
+
+/// Sends hints for areas that replicated clients approached since the last run.
+#[cfg(feature = "server")]
+fn send_area_hints<K: Serialize + Clone + Send + Sync + 'static>(
+    mut hinted: Local<HashSet<(ClientId, Entity)>>,
+    mut inside: Local<HashSet<(ClientId, Entity)>>,
+    mut hint_events: EventWriter<ToClients<PreloadHint<K>>>,
+    replicated_clients: Res<ReplicatedClients>,
+    anchors: Query<(&InterestAnchor, &Transform)>,
+    areas: Query<(Entity, &PreloadArea<K>, &Transform)>,
+) {
+    for (anchor, anchor_transform) in &anchors {
+        if replicated_clients.get_client(**anchor).is_none() {
+            continue;
+        }
+
+        for (entity, area, transform) in &areas {
+            let distance = anchor_transform
+                .translation
+                .distance_squared(transform.translation);
+            if distance <= area.radius * area.radius {
+                inside.insert((**anchor, entity));
+            }
+        }
+    }
+
+    let mut new_hints: Vec<_> = inside
+        .difference(&hinted)
+        .filter_map(|&(client_id, entity)| {
+            let (_, area, _) = areas.get(entity).ok()?;
+            Some((client_id, area))
+        })
+        .collect();
+    new_hints.sort_by_key(|&(_, area)| Reverse(area.priority));
+    for (client_id, area) in new_hints {
+        debug!(
+            "sending preload hint with priority {} to `{client_id:?}`",
+            area.priority
+        );
+        hint_events.send(ToClients {
+            mode: SendMode::Direct(client_id),
+            event: PreloadHint {
+                key: area.key.clone(),
+                priority: area.priority,
+            },
+        });
+    }
+
+    // Remember only areas that are still approached to hint them again after re-entering.
+    mem::swap(&mut *hinted, &mut *inside);
+    inside.clear();
+}
@@ -0,0 +1,381 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "client")]
+use crate::client::{server_mutate_ticks::ServerMutateTicks, ClientSet, ServerUpdateTick};
+#[cfg(any(feature = "client", feature = "server"))]
+use crate::core::common_conditions::*;
+use crate::core::{
+    replication::{replication_rules::AppRuleExt, track_mutate_messages::TrackAppExt, Replicated},
+    replicon_tick::RepliconTick,
+};
+#[cfg(feature = "server")]
+use crate::server::{increment_tick, server_tick::ServerTick, ServerSet};
+
+/**
+Countdowns that finish on the same tick for server and clients.
+
+The server inserts [`ReplicatedTimer`] with the tick at which it should finish.
+When the server reaches this tick, the timer is marked as finished and [`TimerFinished`] is emitted.
+Clients emit [`TimerFinished`] after receiving the replication for the same tick,
+so the event can't be emitted earlier or later than the server state it belongs to.
+
+To display the remaining time, use [`ReplicatedTimer::remaining`] with [`TickClock`],
+which maps ticks to time on both sides.
+The plugin enables [`TrackAppExt::track_mutate_messages`] to let clients receive every server tick.
+
+Should be added to both client and server after [`RepliconPlugins`](crate::RepliconPlugins).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::replicated_timer::{ReplicatedTimer, ReplicatedTimerPlugin, TickClock, TimerFinished},
+    prelude::*,
+    server::server_tick::ServerTick,
+};
+
+let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, ReplicatedTimerPlugin::default()))
+    .add_systems(Update, (start_round.run_if(server_running), show_countdown, end_round));
+
+fn start_round(mut commands: Commands, server_tick: Res<ServerTick>, rounds: Query<(), With<ReplicatedTimer>>) {
+    if rounds.is_empty() {
+        commands.spawn(ReplicatedTimer::new(**server_tick + 300));
+    }
+}
+
+fn show_countdown(clock: Res<TickClock>, timers: Query<&ReplicatedTimer>) {
+    for timer in &timers {
+        info!("round ends in {:.1} seconds", timer.remaining(&clock).as_secs_f32());
+    }
+}
+
+fn end_round(mut finished_events: EventReader<TimerFinished>) {
+    for event in finished_events.read() {
+        info!("round finished on tick {:?}", event.tick);
+    }
+}
+```
+**/
+pub struct ReplicatedTimerPlugin {
+    /// Tick duration that [`TickClock`] assumes until it measures the actual one.
+    ///
+    /// By default it matches the default tick rate of 30 ticks per second.
+    pub initial_tick_duration: Duration,
+
+    /// Fraction of the clock error corrected on each received tick.
+    ///
+    /// Lower values make the displayed time smoother, but slower to adapt to drift.
+    pub drift_correction: f64,
+
+    /// Clock error in ticks after which the clock jumps to the received tick instead of correcting gradually.
+    pub snap_threshold: f64,
+}
+
+impl Default for ReplicatedTimerPlugin {
+    fn default() -> Self {
+        Self {
+            initial_tick_duration: Duration::from_secs(1) / 30,
+            drift_correction: 0.1,
+            snap_threshold: 4.0,
+        }
+    }
+}
+
+impl Plugin for ReplicatedTimerPlugin {
+    fn build(&self, app: &mut App) {
+        app.replicate::<ReplicatedTimer>()
+            .track_mutate_messages()
+            .add_event::<TimerFinished>()
+            .insert_resource(TickClock::new(
+                self.initial_tick_duration,
+                self.drift_correction,
+                self.snap_threshold,
+            ));
+
+        #[cfg(feature = "client")]
+        app.add_systems(
+            PreUpdate,
+            (
+                reset_clock.in_set(ClientSet::Reset),
+                (sample_received_tick, emit_received_finishes)
+                    .chain()
+                    .after(ClientSet::Receive)
+                    .run_if(client_connected),
+            ),
+        );
+
+        #[cfg(feature = "server")]
+        app.add_systems(
+            PostUpdate,
+            (sample_server_tick, finish_timers)
+                .chain()
+                .after(increment_tick)
+                .before(ServerSet::Send)
+                .run_if(server_running),
+        );
+    }
+}
+
+#[cfg(feature = "client")]
+fn reset_clock(mut clock: ResMut<TickClock>) {
+    clock.reset();
+}
+
+/// Samples the last tick received from the server.
+#[cfg(feature = "client")]
+fn sample_received_tick(
+    mut clock: ResMut<TickClock>,
+    time: Res<Time>,
+    update_tick: Res<ServerUpdateTick>,
+    mutate_ticks: Res<ServerMutateTicks>,
+) {
+    let mut tick = **update_tick;
+    if mutate_ticks.last_tick() > tick {
+        tick = mutate_ticks.last_tick();
+    }
+
+    clock.sample(tick, time.elapsed());
+}
+
+/// Emits [`TimerFinished`] for timers that the server finished in the received tick.
+#[cfg(feature = "client")]
+fn emit_received_finishes(
+    mut finished_events: EventWriter<TimerFinished>,
+    update_tick: Res<ServerUpdateTick>,
+    timers: Query<(Entity, Ref<ReplicatedTimer>)>,
+) {
+    for (entity, timer) in &timers {
+        if !timer.is_changed() || !timer.finished {
+            continue;
+        }
+
+        // Timers inserted as finished at earlier ticks, for example after connecting, were already finished on server.
+        if timer.is_added() && timer.end_tick != **update_tick {
+            continue;
+        }
+
+        debug!("`{entity}` finished its timer on `{:?}`", timer.end_tick);
+        finished_events.send(TimerFinished {
+            entity,
+            tick: timer.end_tick,
+        });
+    }
+}
+
+#[cfg(feature = "server")]
+fn sample_server_tick(mut clock: ResMut<TickClock>, time: Res<Time>, server_tick: Res<ServerTick>) {
+    clock.sample(**server_tick, time.elapsed());
+}
+
+/// Finishes timers that reached their end tick.
+#[cfg(feature = "server")]
+fn finish_timers(
+    mut finished_events: EventWriter<TimerFinished>,
+    server_tick: Res<ServerTick>,
+    mut timers: Query<(Entity, &mut ReplicatedTimer)>,
+) {
+    for (entity, mut timer) in &mut timers {
+        if timer.finished || timer.end_tick > **server_tick {
+            continue;
+        }
+
+        debug!("`{entity}` finished its timer on `{:?}`", timer.end_tick);
+        timer.finished = true;
+        finished_events.send(TimerFinished {
+            entity,
+            tick: timer.end_tick,
+        });
+    }
+}
+
+/// A countdown to a server tick.
+///
+/// See [`ReplicatedTimerPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Deserialize, Serialize)]
+#[require(Replicated)]
+pub struct ReplicatedTimer {
+    end_tick: RepliconTick,
+    finished: bool,
+}
+
+impl ReplicatedTimer {
+    /// Creates a timer that finishes at the given server tick.
+    ///
+    /// The tick should be ahead of the current [`ServerTick`],
+    /// otherwise the timer finishes immediately, but clients won't emit [`TimerFinished`] for it.
+    pub fn new(end_tick: RepliconTick) -> Self {
+        Self {
+            end_tick,
+            finished: false,
+        }
+    }
+
+    /// Returns the tick at which the timer finishes.
+    pub fn end_tick(&self) -> RepliconTick {
+        self.end_tick
+    }
+
+    /// Restarts the timer with a new end tick.
+    pub fn set_end_tick(&mut self, end_tick: RepliconTick) {
+        self.end_tick = end_tick;
+        self.finished = false;
+    }
+
+    /// Returns `true` if the server reached the end tick.
+    ///
+    /// On clients it's updated with the replication of the end tick.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the estimated time until the timer finishes.
+    pub fn remaining(&self, clock: &TickClock) -> Duration {
+        if self.finished {
+            Duration::ZERO
+        } else {
+            clock.duration_until(self.end_tick)
+        }
+    }
+}
+
+/// An event that emitted on both server and clients when a [`ReplicatedTimer`] finishes.
+///
+/// See [`ReplicatedTimerPlugin`] for details.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimerFinished {
+    /// Entity with the timer.
+    ///
+    /// On clients it's the client entity.
+    pub entity: Entity,
+
+    /// Server tick at which the timer finished.
+    pub tick: RepliconTick,
+}
+
+/**
+Maps server ticks to local time.
+
+On server it samples [`ServerTick`].
+On clients it samples the last received tick, so it estimates the tick at which
+replication arrives rather than the tick the server is currently simulating.
+
+Between samples the current tick is extrapolated with the measured tick duration.
+When a new tick is sampled, the estimation error is corrected gradually by
+[`ReplicatedTimerPlugin::drift_correction`] to avoid jumps caused by network jitter.
+If the error exceeds [`ReplicatedTimerPlugin::snap_threshold`], the clock jumps to the sampled tick.
+
+See [`ReplicatedTimerPlugin`] for details.
+**/
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct TickClock {
+    tick_duration: Duration,
+    drift_correction: f64,
+    snap_threshold: f64,
+
+    /// Last sampled tick or [`None`] if nothing was sampled yet.
+    last_tick: Option<RepliconTick>,
+
+    /// Local time of the [`Self::last_tick`] sampling.
+    last_sampled: Duration,
+
+    /// Estimated ticks passed since [`Self::last_tick`] at the time of sampling.
+    base_offset: f64,
+
+    /// Estimated ticks passed since [`Self::last_tick`] at the current time.
+    offset: f64,
+}
+
+impl TickClock {
+    fn new(tick_duration: Duration, drift_correction: f64, snap_threshold: f64) -> Self {
+        Self {
+            tick_duration,
+            drift_correction,
+            snap_threshold,
+            last_tick: None,
+            last_sampled: Duration::ZERO,
+            base_offset: 0.0,
+            offset: 0.0,
+        }
+    }
+
+    /// Returns the measured duration of a single tick.
+    pub fn tick_duration(&self) -> Duration {
+        self.tick_duration
+    }
+
+    /// Returns the last sampled tick or [`None`] if nothing was sampled yet.
+    pub fn last_tick(&self) -> Option<RepliconTick> {
+        self.last_tick
+    }
+
+    /// Returns the estimated number of ticks until the given tick.
+    ///
+    /// Returns 0 for already reached ticks.
+    pub fn ticks_until(&self, tick: RepliconTick) -> f64 {
+        let Some(last_tick) = self.last_tick else {
+            return 0.0;
+        };
+        if tick <= last_tick {
+            return 0.0;
+        }
+
+        let ticks = (tick - last_tick) as f64 - self.offset;
+        ticks.max(0.0)
+    }
+
+    /// Returns the estimated time until the given tick.
+    pub fn duration_until(&self, tick: RepliconTick) -> Duration {
+        self.tick_duration.mul_f64(self.ticks_until(tick))
+    }
+
+    fn sample(&mut self, tick: RepliconTick, elapsed: Duration) {
+        let Some(last_tick) = self.last_tick.filter(|&last_tick| last_tick <= tick) else {
+            self.snap(tick, elapsed);
+            return;
+        };
+
+        let ticks_per_duration = 1.0 / self.tick_duration.as_secs_f64();
+        let since_sampled = (elapsed - self.last_sampled).as_secs_f64();
+        self.offset = self.base_offset + since_sampled * ticks_per_duration;
+        if tick == last_tick {
+            return;
+        }
+
+        let received_ticks = tick - last_tick;
+        let error = received_ticks as f64 - self.offset;
+        if error.abs() > self.snap_threshold {
+            self.snap(tick, elapsed);
+            return;
+        }
+
+        let interval = since_sampled / received_ticks as f64;
+        let duration = self.tick_duration.as_secs_f64();
+        self.tick_duration =
+            Duration::from_secs_f64(duration + (interval - duration) * self.drift_correction);
+
+        self.base_offset = -error * (1.0 - self.drift_correction);
+        self.offset = self.base_offset;
+        self.last_tick = Some(tick);
+        self.last_sampled = elapsed;
+    }
+
+    fn snap(&mut self, tick: RepliconTick, elapsed: Duration) {
+        trace!("snapping clock to `{tick:?}`");
+        self.last_tick = Some(tick);
+        self.last_sampled = elapsed;
+        self.base_offset = 0.0;
+        self.offset = 0.0;
+    }
+
+    #[cfg_attr(not(feature = "client"), allow(dead_code))]
+    fn reset(&mut self) {
+        self.last_tick = None;
+        self.base_offset = 0.0;
+        self.offset = 0.0;
+    }
+}
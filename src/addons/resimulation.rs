@@ -0,0 +1,348 @@
+use std::marker::PhantomData;
+
+#[cfg(feature = "server")]
+use bevy::utils::HashMap;
+use bevy::{
+    ecs::schedule::{InternedScheduleLabel, ScheduleLabel},
+    prelude::*,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::core::{
+    channels::ChannelKind, event::client_event::ClientEventAppExt, replicon_tick::RepliconTick,
+    ClientId,
+};
+#[cfg(feature = "server")]
+use crate::{
+    core::{common_conditions::*, event::client_event::FromClient},
+    server::{
+        change_journal::{self, ChangeJournal},
+        increment_tick,
+        server_tick::ServerTick,
+        ServerSet,
+    },
+};
+
+/**
+Server-side resimulation for client inputs that arrive late.
+
+Clients send inputs as [`TickedInput`] stamped with the server tick they are intended for.
+The server runs the provided schedule once per tick with [`TickInputs`] containing
+inputs for the simulated tick. If an input arrives for an already simulated tick,
+but no more than [`Self::max_late_ticks`] late, the server rewinds entities marked with
+[`Resimulated`] using [`ChangeJournal`], and runs the schedule again for all ticks
+starting from the input tick. Resimulated states are recorded into the journal as
+keyframes for the corresponding ticks, so subsequent late inputs rewind to corrected states.
+The final corrections are replicated to clients as regular changes.
+
+Only changes of [`Resimulated`] entities are rewound, so systems in the schedule
+should modify only them. During resimulation [`ServerTick`]
+is not changed, use [`TickInputs::tick`] instead.
+
+Inserts [`ChangeJournal`] if it's missing. The journal should keep at least
+[`Self::max_late_ticks`] ticks.
+
+Only one instance of this plugin should be added. Use an enum for multiple input kinds.
+
+Should be added to both client and server after [`RepliconPlugins`](crate::RepliconPlugins).
+
+# Examples
+
+```
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+use bevy_replicon::{
+    addons::resimulation::{Resimulated, ResimulationPlugin, TickInputs},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+let mut app = App::new();
+app.add_plugins((
+    MinimalPlugins,
+    RepliconPlugins,
+    ResimulationPlugin::<Move>::new(Simulation, 8),
+))
+.replicate::<Position>()
+.add_systems(Simulation, apply_movement);
+
+fn apply_movement(inputs: Res<TickInputs<Move>>, mut players: Query<(&Player, &mut Position)>) {
+    for (client_id, input) in inputs.iter() {
+        for (player, mut position) in &mut players {
+            if player.0 == client_id {
+                position.0 += input.0;
+            }
+        }
+    }
+}
+
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct Simulation;
+
+#[derive(Clone, Deserialize, Serialize)]
+struct Move(Vec2);
+
+#[derive(Component)]
+#[require(Resimulated)]
+struct Player(ClientId);
+
+#[derive(Component, Deserialize, Serialize)]
+struct Position(Vec2);
+```
+**/
+pub struct ResimulationPlugin<I> {
+    schedule: InternedScheduleLabel,
+    max_late_ticks: u32,
+    channel: ChannelKind,
+    marker: PhantomData<I>,
+}
+
+impl<I> ResimulationPlugin<I> {
+    /// Creates a new plugin that runs `schedule` for each tick and accepts inputs
+    /// up to `max_late_ticks` late.
+    ///
+    /// Inputs are sent over [`ChannelKind::Ordered`].
+    pub fn new(schedule: impl ScheduleLabel, max_late_ticks: u32) -> Self {
+        Self {
+            schedule: schedule.intern(),
+            max_late_ticks,
+            channel: ChannelKind::Ordered,
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the channel over which inputs are sent.
+    pub fn with_channel(mut self, channel: ChannelKind) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Returns the schedule that runs for each tick.
+    pub fn schedule(&self) -> InternedScheduleLabel {
+        self.schedule
+    }
+
+    /// Returns the maximum number of ticks an input can be late.
+    pub fn max_late_ticks(&self) -> u32 {
+        self.max_late_ticks
+    }
+}
+
+impl<I: Clone + Serialize + DeserializeOwned + Send + Sync + 'static> Plugin
+    for ResimulationPlugin<I>
+{
+    fn build(&self, app: &mut App) {
+        app.add_client_event::<TickedInput<I>>(self.channel);
+
+        #[cfg(feature = "server")]
+        {
+            if !app.world().contains_resource::<ChangeJournal>() {
+                app.insert_resource(ChangeJournal::new(self.max_late_ticks + 1));
+            }
+
+            app.init_schedule(self.schedule)
+                .insert_resource(TickInputs::<I>::default())
+                .insert_resource(InputHistory::<I> {
+                    inputs: Default::default(),
+                    late_tick: None,
+                    max_late_ticks: self.max_late_ticks,
+                })
+                .add_systems(
+                    PreUpdate,
+                    (receive_inputs::<I>, resimulate::<I>(self.schedule))
+                        .chain()
+                        .after(ServerSet::Receive)
+                        .run_if(server_running),
+                )
+                .add_systems(
+                    PostUpdate,
+                    (
+                        simulate::<I>(self.schedule)
+                            .after(increment_tick)
+                            .before(ServerSet::Send)
+                            .run_if(server_running)
+                            .run_if(resource_changed::<ServerTick>),
+                        reset::<I>.run_if(server_just_stopped),
+                    ),
+                );
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn receive_inputs<I: Clone + Send + Sync + 'static>(
+    mut input_events: EventReader<FromClient<TickedInput<I>>>,
+    server_tick: Res<ServerTick>,
+    mut history: ResMut<InputHistory<I>>,
+) {
+    let next_tick = **server_tick + 1;
+    for FromClient { client_id, event } in input_events.read() {
+        let lateness = next_tick - event.tick;
+        if event.tick < next_tick && lateness > history.max_late_ticks {
+            debug!(
+                "ignoring input from `{client_id:?}` for {:?} that is {lateness} ticks late",
+                event.tick
+            );
+            continue;
+        }
+
+        if event.tick < next_tick
+            && history
+                .late_tick
+                .is_none_or(|late_tick| event.tick < late_tick)
+        {
+            history.late_tick = Some(event.tick);
+        }
+
+        history
+            .inputs
+            .entry(event.tick)
+            .or_default()
+            .push((*client_id, event.input.clone()));
+    }
+
+    let max_late_ticks = history.max_late_ticks;
+    history
+        .inputs
+        .retain(|&tick, _| tick >= next_tick || next_tick - tick <= max_late_ticks);
+}
+
+#[cfg(feature = "server")]
+fn resimulate<I: Clone + Send + Sync + 'static>(
+    schedule: InternedScheduleLabel,
+) -> impl FnMut(&mut World) {
+    move |world: &mut World| {
+        let mut history = world.resource_mut::<InputHistory<I>>();
+        let Some(late_tick) = history.late_tick.take() else {
+            return;
+        };
+
+        let server_tick = **world.resource::<ServerTick>();
+        debug!("resimulating from {late_tick:?} to {server_tick:?}");
+
+        change_journal::rollback(world, server_tick - late_tick + 1, |world, change| {
+            world
+                .get_entity(change.entity)
+                .is_ok_and(|entity| entity.contains::<Resimulated>())
+        });
+
+        let mut tick = late_tick;
+        while tick <= server_tick {
+            run_tick::<I>(world, schedule, tick);
+            tick += 1;
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn simulate<I: Clone + Send + Sync + 'static>(
+    schedule: InternedScheduleLabel,
+) -> impl FnMut(&mut World) {
+    move |world: &mut World| {
+        let server_tick = **world.resource::<ServerTick>();
+        let inputs = world
+            .resource::<InputHistory<I>>()
+            .inputs
+            .get(&server_tick)
+            .cloned()
+            .unwrap_or_default();
+        *world.resource_mut::<TickInputs<I>>() = TickInputs {
+            tick: server_tick,
+            inputs,
+        };
+        world.run_schedule(schedule);
+    }
+}
+
+/// Runs the schedule for an already simulated tick and records the result as a keyframe.
+#[cfg(feature = "server")]
+fn run_tick<I: Clone + Send + Sync + 'static>(
+    world: &mut World,
+    schedule: InternedScheduleLabel,
+    tick: RepliconTick,
+) {
+    let inputs = world
+        .resource::<InputHistory<I>>()
+        .inputs
+        .get(&tick)
+        .cloned()
+        .unwrap_or_default();
+    *world.resource_mut::<TickInputs<I>>() = TickInputs { tick, inputs };
+
+    let last_run = world.change_tick();
+    world.run_schedule(schedule);
+    let this_run = world.change_tick();
+
+    change_journal::record(world, tick, last_run, this_run);
+}
+
+#[cfg(feature = "server")]
+fn reset<I: Send + Sync + 'static>(mut history: ResMut<InputHistory<I>>) {
+    history.inputs.clear();
+    history.late_tick = None;
+}
+
+/// Marker for entities that are rewound for resimulation.
+///
+/// See [`ResimulationPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Resimulated;
+
+/// A client event with an input for a specific server tick.
+///
+/// See [`ResimulationPlugin`] for details.
+#[derive(Event, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct TickedInput<I> {
+    /// Server tick for which the input is intended.
+    ///
+    /// Usually the last received [`ServerUpdateTick`](crate::client::ServerUpdateTick)
+    /// plus the estimated number of ticks until the input reaches the server.
+    pub tick: RepliconTick,
+
+    /// Input data.
+    pub input: I,
+}
+
+/// Inputs for the tick that is currently simulated.
+///
+/// Available inside the schedule from [`ResimulationPlugin`].
+#[derive(Resource)]
+pub struct TickInputs<I> {
+    tick: RepliconTick,
+    inputs: Vec<(ClientId, I)>,
+}
+
+impl<I> TickInputs<I> {
+    /// Returns the currently simulated tick.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns an iterator over inputs for the tick in the order they were received.
+    pub fn iter(&self) -> impl Iterator<Item = (ClientId, &I)> {
+        self.inputs
+            .iter()
+            .map(|(client_id, input)| (*client_id, input))
+    }
+}
+
+impl<I> Default for TickInputs<I> {
+    fn default() -> Self {
+        Self {
+            tick: Default::default(),
+            inputs: Default::default(),
+        }
+    }
+}
+
+/// Received inputs for recent and future ticks.
+#[cfg(feature = "server")]
+#[derive(Resource)]
+struct InputHistory<I> {
+    inputs: HashMap<RepliconTick, Vec<(ClientId, I)>>,
+
+    /// The earliest tick of inputs that arrived after the tick was simulated.
+    late_tick: Option<RepliconTick>,
+
+    /// See [`ResimulationPlugin::max_late_ticks`].
+    max_late_ticks: u32,
+}
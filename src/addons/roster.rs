@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    replication::replication_rules::AppRuleExt, replicon_tick::RepliconTick, ClientId,
+};
+#[cfg(feature = "server")]
+use crate::{
+    core::{connected_clients::ConnectedClient, replication::Replicated},
+    server::server_tick::ServerTick,
+};
+
+/**
+Replicates the list of connected clients to all clients.
+
+On server each connected client is represented by an entity with
+[`ConnectedClient`].
+This plugin marks these entities as [`Replicated`] and inserts [`RosterEntry`] into them,
+so clients can query the roster for lobbies or scoreboards like any other replicated entity.
+
+To attach a display payload, such as a nickname, register a replication rule for your
+component and insert it into the client entity obtained from
+[`ConnectedClients::entity`](crate::core::connected_clients::ConnectedClients::entity).
+
+Roster entities follow the regular visibility rules, so with
+[`VisibilityPolicy::Whitelist`](crate::core::replication::replicated_clients::VisibilityPolicy::Whitelist) you need to
+make them visible explicitly.
+
+Should be added to both client and server after [`RepliconPlugins`](crate::RepliconPlugins).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{addons::roster::{ClientRosterPlugin, RosterEntry}, prelude::*};
+use serde::{Deserialize, Serialize};
+
+let mut app = App::new();
+app.add_plugins((MinimalPlugins, RepliconPlugins, ClientRosterPlugin))
+    .replicate::<Nickname>()
+    .add_observer(set_nickname)
+    .add_systems(Update, show_scoreboard);
+
+fn set_nickname(
+    trigger: Trigger<ClientConnected>,
+    mut commands: Commands,
+    connected_clients: Res<ConnectedClients>,
+) {
+    if let Some(entity) = connected_clients.entity(trigger.client_id) {
+        commands.entity(entity).insert(Nickname(format!("Player {:?}", trigger.client_id)));
+    }
+}
+
+fn show_scoreboard(roster: Query<(&RosterEntry, Option<&Nickname>)>) {
+    for (entry, nickname) in &roster {
+        info!("`{:?}` joined on tick {:?} as {nickname:?}", entry.client_id, entry.join_tick);
+    }
+}
+
+#[derive(Component, Debug, Deserialize, Serialize)]
+struct Nickname(String);
+```
+**/
+pub struct ClientRosterPlugin;
+
+impl Plugin for ClientRosterPlugin {
+    fn build(&self, app: &mut App) {
+        app.replicate::<RosterEntry>();
+
+        #[cfg(feature = "server")]
+        app.add_observer(insert_entry);
+    }
+}
+
+#[cfg(feature = "server")]
+fn insert_entry(
+    trigger: Trigger<OnAdd, ConnectedClient>,
+    mut commands: Commands,
+    clients: Query<&ConnectedClient>,
+    server_tick: Res<ServerTick>,
+) {
+    let Ok(client) = clients.get(trigger.entity()) else {
+        return;
+    };
+
+    debug!("adding `{:?}` to roster", client.id());
+    commands.entity(trigger.entity()).insert((
+        Replicated,
+        RosterEntry {
+            client_id: client.id(),
+            join_tick: **server_tick,
+        },
+    ));
+}
+
+/// A replicated component with information about a connected client.
+///
+/// See [`ClientRosterPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RosterEntry {
+    /// ID of the client.
+    pub client_id: ClientId,
+
+    /// Server tick on which the client connected.
+    pub join_tick: RepliconTick,
+}
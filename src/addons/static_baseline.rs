@@ -0,0 +1,308 @@
+use bevy::prelude::*;
+#[cfg(feature = "server")]
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(feature = "client", feature = "server"))]
+use crate::core::common_conditions::*;
+use crate::core::{
+    channels::ChannelKind,
+    event::{client_event::ClientEventAppExt, server_event::ServerEventAppExt},
+};
+#[cfg(feature = "client")]
+use crate::{client::ClientSet, core::server_entity_map::ServerEntityMap};
+#[cfg(feature = "server")]
+use crate::{
+    core::{
+        event::{
+            client_event::FromClient,
+            server_event::{SendMode, ToClients},
+        },
+        replication::replicated_clients::ReplicatedClients,
+    },
+    server::{
+        client_entity_map::{ClientEntityMap, ClientMapping},
+        ServerSet, StartReplication,
+    },
+};
+
+/**
+Replication of differences from a static world shared between server and clients.
+
+Games with large static content (level geometry, props, spawn points) can load the same scene
+on both server and clients at startup. Instead of sending all these entities to every
+connecting client, the server sends only what differs from the shared baseline:
+
+- Entities spawned after the baseline.
+- Components inserted or mutated after the baseline.
+- Despawns of baseline entities.
+
+Baseline entities are matched by [`BaselineEntity`] IDs, which should be assigned deterministically
+during loading on both sides. After loading, insert [`StaticBaseline`] with a hash of the content.
+On connection the client sends its hash and its baseline entities. If the hash matches the server's,
+the baseline entities are mapped to the client's entities via [`ClientEntityMap`] and
+only changes made after [`StaticBaseline`] insertion on server are replicated for them.
+Otherwise the client despawns its baseline entities and receives them from the server as usual.
+The result is available on client as [`BaselineResponse`].
+
+Requires [`ServerPlugin::replicate_after_connect`](crate::server::ServerPlugin::replicate_after_connect)
+to be disabled: replication starts automatically after the handshake. Should be added to both client
+and server after [`RepliconPlugins`](crate::RepliconPlugins).
+
+Removals of baseline components aren't tracked before the client connects, so such
+components shouldn't be removed. Entities that become visible to the client after being
+hidden are sent in full.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::static_baseline::{BaselineEntity, StaticBaseline, StaticBaselinePlugin},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+app.add_plugins((
+    RepliconPlugins.set(ServerPlugin {
+        replicate_after_connect: false,
+        ..Default::default()
+    }),
+    StaticBaselinePlugin,
+))
+.replicate::<Tree>()
+.add_systems(Startup, load_level);
+
+fn load_level(mut commands: Commands) {
+    for (index, position) in [Vec3::ZERO, Vec3::X].into_iter().enumerate() {
+        commands.spawn((
+            Replicated,
+            BaselineEntity(index as u64),
+            Tree,
+            Transform::from_translation(position),
+        ));
+    }
+
+    // Usually a hash of the loaded scene asset.
+    commands.insert_resource(StaticBaseline::new(42));
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct Tree;
+```
+**/
+pub struct StaticBaselinePlugin;
+
+impl Plugin for StaticBaselinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_client_event::<BaselineHandshake>(ChannelKind::Ordered)
+            .add_server_event::<BaselineResponse>(ChannelKind::Ordered)
+            .make_independent::<BaselineResponse>();
+
+        #[cfg(feature = "client")]
+        app.add_systems(
+            PreUpdate,
+            (
+                send_handshake.run_if(client_just_connected),
+                receive_response.run_if(client_connected),
+            )
+                .after(ClientSet::Receive),
+        );
+
+        #[cfg(feature = "server")]
+        app.add_systems(
+            PreUpdate,
+            receive_handshakes
+                .after(ServerSet::Receive)
+                .run_if(server_running),
+        );
+    }
+}
+
+/// Identifies an entity from the static content shared between server and clients.
+///
+/// See [`StaticBaselinePlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct BaselineEntity(pub u64);
+
+/// Marks the static content as loaded.
+///
+/// Should be inserted on both server and clients after all [`BaselineEntity`]s are spawned.
+/// On server, changes to baseline entities after the insertion are replicated as overrides.
+///
+/// See [`StaticBaselinePlugin`] for details.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct StaticBaseline {
+    hash: u64,
+}
+
+impl StaticBaseline {
+    /// Creates a new instance with a hash of the static content.
+    ///
+    /// Server and client baselines are considered the same only if their hashes match.
+    pub fn new(hash: u64) -> Self {
+        Self { hash }
+    }
+
+    /// Returns the hash of the static content.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// A client event with the loaded baseline.
+#[derive(Event, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BaselineHandshake {
+    /// Hash from [`StaticBaseline`] or [`None`] if it's not loaded.
+    pub hash: Option<u64>,
+
+    /// Baseline IDs with the corresponding client entities.
+    pub entities: Vec<(BaselineEntity, Entity)>,
+}
+
+/// A server event with the baseline handshake result.
+#[derive(Event, Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum BaselineResponse {
+    /// The baseline matches the server's.
+    ///
+    /// Contains baseline entities that no longer exist on server.
+    Accepted(Vec<BaselineEntity>),
+
+    /// The baseline doesn't match the server's, all baseline entities will be sent in full.
+    Rejected,
+}
+
+/// Sends the loaded baseline to the server.
+#[cfg(feature = "client")]
+fn send_handshake(
+    mut handshake_events: EventWriter<BaselineHandshake>,
+    baseline: Option<Res<StaticBaseline>>,
+    entities: Query<(Entity, &BaselineEntity)>,
+) {
+    let handshake = BaselineHandshake {
+        hash: baseline.map(|baseline| baseline.hash),
+        entities: entities
+            .iter()
+            .map(|(entity, &baseline_entity)| (baseline_entity, entity))
+            .collect(),
+    };
+
+    debug!(
+        "sending baseline {:?} with {} entities",
+        handshake.hash,
+        handshake.entities.len()
+    );
+    handshake_events.send(handshake);
+}
+
+/// Despawns baseline entities that won't be replicated.
+#[cfg(feature = "client")]
+fn receive_response(
+    mut commands: Commands,
+    mut response_events: EventReader<BaselineResponse>,
+    entity_map: Res<ServerEntityMap>,
+    entities: Query<(Entity, &BaselineEntity)>,
+) {
+    for response in response_events.read() {
+        for (entity, baseline_entity) in &entities {
+            let despawn = match response {
+                BaselineResponse::Accepted(despawned) => despawned.contains(baseline_entity),
+                BaselineResponse::Rejected => true,
+            };
+            if despawn && entity_map.get_by_client(entity).is_none() {
+                debug!("despawning `{entity}` with {baseline_entity:?}");
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+/// Maps baseline entities for clients with a matching baseline and starts replication.
+#[cfg(feature = "server")]
+fn receive_handshakes(
+    mut commands: Commands,
+    mut handshake_events: EventReader<FromClient<BaselineHandshake>>,
+    mut response_events: EventWriter<ToClients<BaselineResponse>>,
+    mut entity_map: ResMut<ClientEntityMap>,
+    replicated_clients: Res<ReplicatedClients>,
+    baseline: Option<Res<StaticBaseline>>,
+    entities: Query<(Entity, &BaselineEntity)>,
+) {
+    let mut server_entities = HashMap::new();
+    for FromClient { client_id, event } in handshake_events.read() {
+        let client_id = *client_id;
+        if replicated_clients.get_client(client_id).is_some() {
+            warn!("rejecting baseline from `{client_id:?}` that already has replication enabled");
+            response_events.send(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: BaselineResponse::Rejected,
+            });
+            continue;
+        }
+
+        let baseline = baseline
+            .as_ref()
+            .filter(|baseline| event.hash == Some(baseline.hash));
+        let Some(baseline) = baseline else {
+            debug!("rejecting baseline {:?} from `{client_id:?}`", event.hash);
+            response_events.send(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: BaselineResponse::Rejected,
+            });
+            commands.trigger(StartReplication(client_id));
+            continue;
+        };
+
+        if server_entities.is_empty() {
+            server_entities.extend(
+                entities
+                    .iter()
+                    .map(|(entity, &baseline_entity)| (baseline_entity, entity)),
+            );
+        }
+
+        let mut despawned = Vec::new();
+        let mut mapped = Vec::new();
+        for &(baseline_entity, client_entity) in &event.entities {
+            let Some(&server_entity) = server_entities.get(&baseline_entity) else {
+                despawned.push(baseline_entity);
+                continue;
+            };
+
+            if entity_map.insert_baseline(
+                client_id,
+                ClientMapping {
+                    server_entity,
+                    client_entity,
+                },
+            ) {
+                mapped.push(server_entity);
+            }
+        }
+
+        debug!(
+            "accepting baseline from `{client_id:?}` with {} mapped and {} despawned entities",
+            mapped.len(),
+            despawned.len()
+        );
+        response_events.send(ToClients {
+            mode: SendMode::Direct(client_id),
+            event: BaselineResponse::Accepted(despawned),
+        });
+
+        // Start replication with mutation ticks at the baseline
+        // to send only changes made after its insertion.
+        let tick = baseline.last_changed();
+        commands.trigger(StartReplication(client_id));
+        commands.queue(move |world: &mut World| {
+            let mut replicated_clients = world.resource_mut::<ReplicatedClients>();
+            let Some(client) = replicated_clients.get_client_mut(client_id) else {
+                return;
+            };
+            for entity in mapped {
+                client.set_mutation_tick(entity, tick);
+            }
+        });
+    }
+}
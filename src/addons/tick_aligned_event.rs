@@ -0,0 +1,240 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::core::{
+    channels::RepliconChannel,
+    event::client_event::{ClientEventAppExt, FromClient},
+    replicon_tick::RepliconTick,
+};
+#[cfg(feature = "server")]
+use crate::{
+    core::{common_conditions::*, ClientId},
+    server::{server_tick::ServerTick, ClientDisconnected, ServerSet},
+};
+
+/// Registration of client events aligned to server ticks.
+///
+/// Useful for inputs and actions in prediction-based games that should be applied
+/// on server at the same tick the client predicted them for.
+pub trait TickAlignedEventAppExt {
+    /**
+    Registers [`TickAligned<E>`] as a client event and emits [`FromClient<E>`] on server.
+
+    Clients send events stamped with the server tick at which they should be applied.
+    Server holds received events sorted by tick and emits them right after
+    [`ServerSet::Receive`] of the frame that simulates their tick,
+    i.e. when the tick is the next after the current [`ServerTick`].
+    Events for already simulated ticks are emitted immediately. Events that are ahead
+    of the server by more than [`TickAlignmentSettings::max_early_ticks`] are dropped.
+
+    In singleplayer events are emitted immediately without holding.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        addons::tick_aligned_event::{TickAligned, TickAlignedEventAppExt},
+        client::ServerUpdateTick,
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.add_tick_aligned_client_event::<Jump>(ChannelKind::Ordered)
+        .add_systems(Update, (send_jump.run_if(client_connected), apply_jump));
+
+    fn send_jump(mut jumps: EventWriter<TickAligned<Jump>>, update_tick: Res<ServerUpdateTick>) {
+        // Estimate the tick at which the event will reach the server.
+        let tick = **update_tick + 3;
+        jumps.send(TickAligned::new(tick, Jump));
+    }
+
+    fn apply_jump(mut jumps: EventReader<FromClient<Jump>>) {
+        for FromClient { client_id, .. } in jumps.read() {
+            info!("`{client_id:?}` jumped");
+        }
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct Jump;
+    ```
+    **/
+    fn add_tick_aligned_client_event<E>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self
+    where
+        E: Serialize + DeserializeOwned + Send + Sync + 'static;
+}
+
+impl TickAlignedEventAppExt for App {
+    fn add_tick_aligned_client_event<E>(&mut self, channel: impl Into<RepliconChannel>) -> &mut Self
+    where
+        E: Serialize + DeserializeOwned + Send + Sync + 'static,
+    {
+        self.add_client_event::<TickAligned<E>>(channel)
+            .add_event::<FromClient<E>>()
+            .init_resource::<TickAlignmentSettings>();
+
+        #[cfg(feature = "server")]
+        self.init_resource::<HeldClientEvents<E>>()
+            .add_observer(remove_client::<E>)
+            .add_systems(
+                PreUpdate,
+                (
+                    receive::<E>.run_if(server_running),
+                    receive_locally::<E>.run_if(not(server_running)),
+                )
+                    .after(ServerSet::Receive),
+            )
+            .add_systems(PostUpdate, reset::<E>.run_if(server_just_stopped));
+
+        self
+    }
+}
+
+#[cfg(feature = "server")]
+fn receive<E: Send + Sync + 'static>(
+    mut aligned_events: ResMut<Events<FromClient<TickAligned<E>>>>,
+    mut client_events: EventWriter<FromClient<E>>,
+    mut held_events: ResMut<HeldClientEvents<E>>,
+    settings: Res<TickAlignmentSettings>,
+    server_tick: Res<ServerTick>,
+) {
+    let next_tick = **server_tick + 1;
+    for FromClient { client_id, event } in aligned_events.drain() {
+        if event.tick > next_tick && event.tick - next_tick > settings.max_early_ticks {
+            debug!(
+                "ignoring event from `{client_id:?}` for {:?} that is {} ticks early",
+                event.tick,
+                event.tick - next_tick
+            );
+            continue;
+        }
+
+        held_events.insert(client_id, event);
+    }
+
+    for (client_id, event) in held_events.take_reached(next_tick) {
+        client_events.send(FromClient { client_id, event });
+    }
+}
+
+#[cfg(feature = "server")]
+fn receive_locally<E: Send + Sync + 'static>(
+    mut aligned_events: ResMut<Events<FromClient<TickAligned<E>>>>,
+    mut client_events: EventWriter<FromClient<E>>,
+) {
+    for FromClient { client_id, event } in aligned_events.drain() {
+        client_events.send(FromClient {
+            client_id,
+            event: event.event,
+        });
+    }
+}
+
+#[cfg(feature = "server")]
+fn remove_client<E: Send + Sync + 'static>(
+    trigger: Trigger<ClientDisconnected>,
+    mut held_events: ResMut<HeldClientEvents<E>>,
+) {
+    held_events.remove_client(trigger.client_id);
+}
+
+#[cfg(feature = "server")]
+fn reset<E: Send + Sync + 'static>(mut held_events: ResMut<HeldClientEvents<E>>) {
+    held_events.clear();
+}
+
+/// Configuration for tick-aligned client events on server.
+///
+/// See [`TickAlignedEventAppExt::add_tick_aligned_client_event`] for details.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct TickAlignmentSettings {
+    /// Maximum number of ticks an event can be ahead of the server before it's dropped.
+    ///
+    /// Limits the memory that a client can occupy with held events.
+    pub max_early_ticks: u32,
+}
+
+impl Default for TickAlignmentSettings {
+    fn default() -> Self {
+        Self {
+            max_early_ticks: 64,
+        }
+    }
+}
+
+/// A client event stamped with the server tick at which it should be applied.
+///
+/// See [`TickAlignedEventAppExt::add_tick_aligned_client_event`] for details.
+#[derive(Event, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct TickAligned<E> {
+    /// Server tick at which the event should be applied.
+    ///
+    /// Usually the last received [`ServerUpdateTick`](crate::client::ServerUpdateTick)
+    /// plus the estimated number of ticks until the event reaches the server.
+    pub tick: RepliconTick,
+
+    /// Event data.
+    pub event: E,
+}
+
+impl<E> TickAligned<E> {
+    /// Creates a new event aligned to the given tick.
+    pub fn new(tick: RepliconTick, event: E) -> Self {
+        Self { tick, event }
+    }
+}
+
+/// Received tick-aligned events that aren't reached yet.
+#[cfg(feature = "server")]
+#[derive(Resource)]
+struct HeldClientEvents<E> {
+    /// Events waiting for their tick, sorted by tick and then by arrival.
+    pending: Vec<(ClientId, TickAligned<E>)>,
+}
+
+#[cfg(feature = "server")]
+impl<E> HeldClientEvents<E> {
+    /// Inserts an event after all events with the same or earlier tick.
+    fn insert(&mut self, client_id: ClientId, aligned: TickAligned<E>) {
+        let index = self
+            .pending
+            .iter()
+            .position(|(_, other)| other.tick > aligned.tick)
+            .unwrap_or(self.pending.len());
+        self.pending.insert(index, (client_id, aligned));
+    }
+
+    /// Returns events whose tick is less than or equal to the given tick.
+    fn take_reached(&mut self, tick: RepliconTick) -> impl Iterator<Item = (ClientId, E)> + '_ {
+        let reached = self
+            .pending
+            .iter()
+            .take_while(|(_, aligned)| aligned.tick <= tick)
+            .count();
+        self.pending
+            .drain(..reached)
+            .map(|(client_id, aligned)| (client_id, aligned.event))
+    }
+
+    fn remove_client(&mut self, client_id: ClientId) {
+        self.pending.retain(|&(other, _)| other != client_id);
+    }
+
+    fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+#[cfg(feature = "server")]
+impl<E> Default for HeldClientEvents<E> {
+    fn default() -> Self {
+        Self {
+            pending: Default::default(),
+        }
+    }
+}
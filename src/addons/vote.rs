@@ -0,0 +1,348 @@
+use std::marker::PhantomData;
+
+#[cfg(feature = "server")]
+use bevy::utils::HashMap;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::core::{
+    channels::ChannelKind,
+    event::{client_event::ClientEventAppExt, server_event::ServerEventAppExt},
+    replication::{replication_rules::AppRuleExt, Replicated},
+    replicon_tick::RepliconTick,
+};
+#[cfg(feature = "server")]
+use crate::{
+    core::{
+        common_conditions::server_running,
+        event::{
+            client_event::FromClient,
+            server_event::{SendMode, ToClients},
+        },
+        replication::replicated_clients::ReplicatedClients,
+        ClientId,
+    },
+    server::{increment_tick, server_tick::ServerTick, ServerSet},
+};
+
+/// Collect-and-resolve flows, such as map votes or ready-checks.
+pub trait AppVoteExt {
+    /**
+    Registers [`Vote<T>`] with options of type `T`.
+
+    The server opens a vote by spawning an entity with [`Vote<T>`].
+    Clients submit their choices with [`VoteCast<T>`] and may change them until the vote is resolved.
+    The vote is resolved when the server reaches [`Vote::end_tick`] or when all replicated clients voted.
+    Choices of clients that are no longer replicated, for example after a disconnect, are dropped.
+    After that [`VoteResult<T>`] is broadcasted to clients and emitted on server.
+
+    The vote entity is kept after the resolution to let clients map the result,
+    despawn it when it's no longer needed. Vote entities follow the regular visibility rules.
+
+    Should be registered on both client and server.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        addons::vote::{AppVoteExt, Vote, VoteCast, VoteResult},
+        prelude::*,
+        server::server_tick::ServerTick,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.add_vote::<MapName>().add_systems(
+        Update,
+        (
+            open_vote.run_if(server_running),
+            pick_first.run_if(client_connected),
+            change_map,
+        ),
+    );
+
+    fn open_vote(mut commands: Commands, server_tick: Res<ServerTick>, votes: Query<(), With<Vote<MapName>>>) {
+        if votes.is_empty() {
+            let options = vec![MapName("forest".into()), MapName("desert".into())];
+            commands.spawn(Vote::new(options, **server_tick + 600));
+        }
+    }
+
+    fn pick_first(mut casts: EventWriter<VoteCast<MapName>>, votes: Query<Entity, Added<Vote<MapName>>>) {
+        for vote in &votes {
+            casts.send(VoteCast::new(vote, 0));
+        }
+    }
+
+    fn change_map(mut results: EventReader<VoteResult<MapName>>) {
+        for result in results.read() {
+            info!("selected map: {:?}", result.winner);
+        }
+    }
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    struct MapName(String);
+    ```
+    **/
+    fn add_vote<T>(&mut self) -> &mut Self
+    where
+        T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static;
+}
+
+impl AppVoteExt for App {
+    fn add_vote<T>(&mut self) -> &mut Self
+    where
+        T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        self.replicate::<Vote<T>>()
+            .add_mapped_client_event::<VoteCast<T>>(ChannelKind::Ordered)
+            .add_mapped_server_event::<VoteResult<T>>(ChannelKind::Ordered);
+
+        #[cfg(feature = "server")]
+        self.add_observer(insert_ballots::<T>)
+            .add_systems(
+                PreUpdate,
+                receive_casts::<T>
+                    .after(ServerSet::Receive)
+                    .run_if(server_running),
+            )
+            .add_systems(
+                PostUpdate,
+                resolve_votes::<T>
+                    .after(increment_tick)
+                    .before(ServerSet::Send)
+                    .run_if(server_running),
+            );
+
+        self
+    }
+}
+
+#[cfg(feature = "server")]
+fn insert_ballots<T: Send + Sync + 'static>(
+    trigger: Trigger<OnAdd, Vote<T>>,
+    mut commands: Commands,
+) {
+    commands
+        .entity(trigger.entity())
+        .insert(VoteBallots::default());
+}
+
+#[cfg(feature = "server")]
+fn receive_casts<T: Send + Sync + 'static>(
+    mut cast_events: EventReader<FromClient<VoteCast<T>>>,
+    mut votes: Query<(&Vote<T>, &mut VoteBallots)>,
+) {
+    for &FromClient {
+        client_id,
+        ref event,
+    } in cast_events.read()
+    {
+        let Ok((vote, mut ballots)) = votes.get_mut(event.vote) else {
+            debug!(
+                "ignoring vote from `{client_id:?}` for unknown `{}`",
+                event.vote
+            );
+            continue;
+        };
+
+        if ballots.resolved {
+            debug!(
+                "ignoring vote from `{client_id:?}` for resolved `{}`",
+                event.vote
+            );
+            continue;
+        }
+
+        if event.option >= vote.options.len() {
+            debug!(
+                "ignoring vote from `{client_id:?}` for invalid option {} of `{}`",
+                event.option, event.vote
+            );
+            continue;
+        }
+
+        ballots.choices.insert(client_id, event.option);
+    }
+}
+
+#[cfg(feature = "server")]
+fn resolve_votes<T: Clone + Send + Sync + 'static>(
+    mut result_events: EventWriter<ToClients<VoteResult<T>>>,
+    server_tick: Res<ServerTick>,
+    replicated_clients: Res<ReplicatedClients>,
+    mut votes: Query<(Entity, &Vote<T>, &mut VoteBallots)>,
+) {
+    for (entity, vote, mut ballots) in &mut votes {
+        if ballots.resolved {
+            continue;
+        }
+
+        if ballots
+            .choices
+            .keys()
+            .any(|&client_id| replicated_clients.get_client(client_id).is_none())
+        {
+            ballots
+                .choices
+                .retain(|&client_id, _| replicated_clients.get_client(client_id).is_some());
+        }
+
+        let all_voted = !replicated_clients.is_empty()
+            && replicated_clients
+                .iter()
+                .all(|client| ballots.choices.contains_key(&client.id()));
+        if vote.end_tick > **server_tick && !all_voted {
+            continue;
+        }
+
+        ballots.resolved = true;
+        let counts = ballots.counts(vote.options.len());
+        let max_count = counts.iter().copied().max().unwrap_or_default();
+        let mut winners = counts
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count == max_count);
+        let winner = match (winners.next(), winners.next()) {
+            (Some((index, _)), None) if max_count != 0 => Some(vote.options[index].clone()),
+            _ => None,
+        };
+
+        debug!("resolved `{entity}` with counts {counts:?}");
+        result_events.send(ToClients {
+            mode: SendMode::Broadcast,
+            event: VoteResult {
+                vote: entity,
+                winner,
+                counts,
+            },
+        });
+    }
+}
+
+/// A vote between options of type `T`.
+///
+/// See [`AppVoteExt::add_vote`].
+#[derive(Component, Clone, Debug, Deserialize, Serialize)]
+#[require(Replicated)]
+pub struct Vote<T: Send + Sync + 'static> {
+    /// Options to choose from.
+    ///
+    /// Clients refer to them by index.
+    pub options: Vec<T>,
+
+    /// Tick at which the vote is resolved.
+    pub end_tick: RepliconTick,
+}
+
+impl<T: Send + Sync + 'static> Vote<T> {
+    /// Creates a vote between options that ends at the given server tick.
+    pub fn new(options: Vec<T>, end_tick: RepliconTick) -> Self {
+        Self { options, end_tick }
+    }
+}
+
+/// Choices submitted to a [`Vote<T>`].
+///
+/// Inserted on server automatically.
+#[cfg(feature = "server")]
+#[derive(Component, Default)]
+pub struct VoteBallots {
+    /// Option indices chosen by clients.
+    choices: HashMap<ClientId, usize>,
+
+    /// Whether [`VoteResult`] was sent for the vote.
+    resolved: bool,
+}
+
+#[cfg(feature = "server")]
+impl VoteBallots {
+    /// Returns the option index chosen by the client.
+    pub fn choice(&self, client_id: ClientId) -> Option<usize> {
+        self.choices.get(&client_id).copied()
+    }
+
+    /// Returns the number of clients that voted.
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+
+    /// Returns `true` if nobody voted.
+    pub fn is_empty(&self) -> bool {
+        self.choices.is_empty()
+    }
+
+    /// Returns `true` if the vote was resolved.
+    pub fn is_resolved(&self) -> bool {
+        self.resolved
+    }
+
+    /// Returns the number of votes for each option.
+    pub fn counts(&self, options_count: usize) -> Vec<u32> {
+        let mut counts = vec![0; options_count];
+        for &option in self.choices.values() {
+            counts[option] += 1;
+        }
+        counts
+    }
+}
+
+/// A client event to choose an option of [`Vote<T>`].
+///
+/// See [`AppVoteExt::add_vote`].
+#[derive(Event, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct VoteCast<T> {
+    /// Entity with the vote.
+    pub vote: Entity,
+
+    /// Index of the chosen option in [`Vote::options`].
+    pub option: usize,
+
+    #[serde(skip)]
+    marker: PhantomData<T>,
+}
+
+impl<T> VoteCast<T> {
+    /// Creates a choice of the option with the given index.
+    pub fn new(vote: Entity, option: usize) -> Self {
+        Self {
+            vote,
+            option,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> MapEntities for VoteCast<T> {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.vote = entity_mapper.map_entity(self.vote);
+    }
+}
+
+/// A server event with the outcome of [`Vote<T>`].
+///
+/// See [`AppVoteExt::add_vote`].
+#[derive(Event, Clone, Debug, Deserialize, Serialize)]
+pub struct VoteResult<T> {
+    /// Entity with the vote.
+    pub vote: Entity,
+
+    /// Option with the most votes.
+    ///
+    /// [`None`] if nobody voted or the most voted options have the same count.
+    pub winner: Option<T>,
+
+    /// Number of votes for each option.
+    pub counts: Vec<u32>,
+}
+
+impl<T> MapEntities for VoteResult<T> {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.vote = entity_mapper.map_entity(self.vote);
+    }
+}
@@ -1,49 +1,93 @@
+pub mod catch_up;
 pub mod confirm_history;
+pub mod connection_quality;
+pub(super) mod delta_baselines;
 #[cfg(feature = "client_diagnostics")]
 pub mod diagnostics;
 pub mod event;
+pub mod extrapolation;
+pub mod mutation_buffer;
+pub mod segment_playback;
 pub mod server_mutate_ticks;
+pub mod sessions;
+pub mod world_reset;
 
+#[cfg(feature = "zstd")]
+use std::io::{self, ErrorKind};
+use std::mem;
+
+#[cfg(feature = "client_diagnostics")]
+use bevy::utils::Instant;
 use bevy::{ecs::world::CommandQueue, prelude::*};
 use bytes::{Buf, Bytes};
 use postcard::experimental::max_size::MaxSize;
+#[cfg(feature = "zstd")]
+use zstd::zstd_safe;
 
 use crate::core::{
     channels::{ReplicationChannel, RepliconChannels},
     common_conditions::{client_connected, client_just_connected, client_just_disconnected},
-    entity_serde, postcard_utils,
+    entity_serde,
+    network_fault::{FaultPolicy, NetworkFault},
+    protocol::{
+        array::{read_array, ArrayKind},
+        compression_kind::CompressionKind,
+        postcard_utils,
+        protocol_version::ProtocolVersion,
+        replication_epoch::ReplicationEpoch,
+        update_message_flags::UpdateMessageFlags,
+    },
     replication::{
         command_markers::{CommandMarkers, EntityMarkers},
         deferred_entity::DeferredEntity,
+        entity_batch,
         mutate_index::MutateIndex,
+        replicated_resources::ReplicatedResources,
         replication_registry::{
             ctx::{DespawnCtx, RemoveCtx, WriteCtx},
-            ReplicationRegistry,
+            FnsId, ReplicationRegistry,
         },
         track_mutate_messages::TrackMutateMessages,
-        update_message_flags::UpdateMessageFlags,
         Replicated,
     },
     replicon_client::RepliconClient,
     replicon_tick::RepliconTick,
-    server_entity_map::ServerEntityMap,
+    server_entity_map::{MappingConflict, ServerEntityMap},
+    server_pause::ServerPauseChanged,
 };
+use catch_up::{CatchUpPerformed, ClientCatchUp};
 use confirm_history::{ConfirmHistory, EntityReplicated};
+use connection_quality::{ConnectionQuality, ConnectionQualityChanged};
+use delta_baselines::DeltaBaselines;
+#[cfg(feature = "client_diagnostics")]
+use diagnostics::ClientApplyCosts;
+use mutation_buffer::MutationBufferPolicy;
 use server_mutate_ticks::{MutateTickReceived, ServerMutateTicks};
+use world_reset::{ReceivedEpoch, WorldReset};
 
 /// Client functionality and replication receiving.
 ///
+/// Combination of [`ClientSessionPlugin`] and [`ClientReplicationPlugin`].
 /// Can be disabled for server-only apps.
 pub struct ClientPlugin;
 
 impl Plugin for ClientPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((ClientSessionPlugin, ClientReplicationPlugin));
+    }
+}
+
+/// Core client functionality: channels, server pause state and connection quality.
+///
+/// Added by [`ClientPlugin`]. Can be used directly together with
+/// [`ClientEventPlugin`](event::ClientEventPlugin) for apps that need only the messaging layer.
+pub struct ClientSessionPlugin;
+
+impl Plugin for ClientSessionPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<RepliconClient>()
-            .init_resource::<ServerEntityMap>()
-            .init_resource::<ServerUpdateTick>()
-            .init_resource::<BufferedMutations>()
-            .add_event::<EntityReplicated>()
-            .add_event::<MutateTickReceived>()
+            .init_resource::<ServerPaused>()
+            .add_event::<ConnectionQualityChanged>()
             .configure_sets(
                 PreUpdate,
                 (
@@ -64,13 +108,57 @@ impl Plugin for ClientPlugin {
             .add_systems(Startup, setup_channels)
             .add_systems(
                 PreUpdate,
-                receive_replication
-                    .map(Result::unwrap)
-                    .in_set(ClientSet::Receive)
+                sample_stats
+                    .after(ClientSet::ReceivePackets)
+                    .before(ClientSet::Receive)
                     .run_if(client_connected),
             )
+            .add_systems(
+                PreUpdate,
+                (update_pause, send_faults).after(ClientSet::Receive),
+            )
+            .add_systems(
+                PreUpdate,
+                connection_quality::update_quality
+                    .in_set(ClientSet::Diagnostics)
+                    .run_if(resource_exists::<ConnectionQuality>.and(client_connected)),
+            )
             .add_systems(PreUpdate, reset.in_set(ClientSet::Reset));
     }
+}
+
+/// Replication receiving.
+///
+/// Added by [`ClientPlugin`]. Requires [`ClientSessionPlugin`].
+pub struct ClientReplicationPlugin;
+
+impl Plugin for ClientReplicationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServerEntityMap>()
+            .init_resource::<ServerUpdateTick>()
+            .init_resource::<ServerTickRange>()
+            .init_resource::<ReceivedEpoch>()
+            .init_resource::<BufferedMutations>()
+            .init_resource::<DeltaBaselines>()
+            .init_resource::<MutationBufferPolicy>()
+            .add_event::<EntityReplicated>()
+            .add_event::<MutateTickReceived>()
+            .add_event::<CatchUpPerformed>()
+            .add_event::<WorldReset>()
+            .add_event::<MappingConflict>()
+            .add_systems(
+                PreUpdate,
+                (
+                    receive_replication
+                        .pipe(report_replication_error)
+                        .run_if(client_connected),
+                    send_mapping_conflicts,
+                )
+                    .chain()
+                    .in_set(ClientSet::Receive),
+            )
+            .add_systems(PreUpdate, reset_replication.in_set(ClientSet::Reset));
+    }
 
     fn finish(&self, app: &mut App) {
         if **app.world().resource::<TrackMutateMessages>() {
@@ -79,8 +167,34 @@ impl Plugin for ClientPlugin {
     }
 }
 
+/// Records a fault if a replication message couldn't be applied.
+fn report_replication_error(
+    In(result): In<postcard::Result<()>>,
+    mut client: ResMut<RepliconClient>,
+) {
+    if let Err(e) = result {
+        client.add_decode_errors(1);
+        client.report_fault(NetworkFault::Replication(e));
+    }
+}
+
+/// Handles faults recorded by [`RepliconClient`] according to [`FaultPolicy`].
+fn send_faults(
+    mut client: ResMut<RepliconClient>,
+    policy: Res<FaultPolicy>,
+    mut fault_events: EventWriter<NetworkFault>,
+) {
+    for fault in client.bypass_change_detection().drain_faults() {
+        policy.handle(fault, &mut fault_events);
+    }
+}
+
+fn sample_stats(mut client: ResMut<RepliconClient>, time: Res<Time>) {
+    client.sample_stats(time.elapsed());
+}
+
 fn setup_channels(mut client: ResMut<RepliconClient>, channels: Res<RepliconChannels>) {
-    client.setup_server_channels(channels.server_channels().len());
+    client.setup_server_channels(channels.server_receive_len());
 }
 
 /// Receives and applies replication messages from the server.
@@ -109,38 +223,55 @@ pub(super) fn receive_replication(
             world.resource_scope(|world, mut buffered_mutations: Mut<BufferedMutations>| {
                 world.resource_scope(|world, command_markers: Mut<CommandMarkers>| {
                     world.resource_scope(|world, registry: Mut<ReplicationRegistry>| {
-                        world.resource_scope(
-                            |world, mut replicated_events: Mut<Events<EntityReplicated>>| {
-                                let mut stats = world.remove_resource::<ClientReplicationStats>();
-                                let mut mutate_ticks = world.remove_resource::<ServerMutateTicks>();
-                                let mut params = ReceiveParams {
-                                    queue: &mut queue,
-                                    entity_markers: &mut entity_markers,
-                                    entity_map: &mut entity_map,
-                                    replicated_events: &mut replicated_events,
-                                    mutate_ticks: mutate_ticks.as_mut(),
-                                    stats: stats.as_mut(),
-                                    command_markers: &command_markers,
-                                    registry: &registry,
-                                };
-
-                                apply_replication(
-                                    world,
-                                    &mut params,
-                                    &mut client,
-                                    &mut buffered_mutations,
-                                )?;
-
-                                if let Some(stats) = stats {
-                                    world.insert_resource(stats);
-                                }
-                                if let Some(mutate_ticks) = mutate_ticks {
-                                    world.insert_resource(mutate_ticks);
-                                }
-
-                                Ok(())
-                            },
-                        )
+                        world.resource_scope(|world, mut delta_baselines: Mut<DeltaBaselines>| {
+                            world.resource_scope(
+                                |world, mut replicated_events: Mut<Events<EntityReplicated>>| {
+                                    let mut stats =
+                                        world.remove_resource::<ClientReplicationStats>();
+                                    let mut mutate_ticks =
+                                        world.remove_resource::<ServerMutateTicks>();
+                                    #[cfg(feature = "client_diagnostics")]
+                                    let mut apply_costs =
+                                        world.remove_resource::<ClientApplyCosts>();
+                                    let mut params = ReceiveParams {
+                                        queue: &mut queue,
+                                        entity_markers: &mut entity_markers,
+                                        entity_map: &mut entity_map,
+                                        replicated_events: &mut replicated_events,
+                                        mutate_ticks: mutate_ticks.as_mut(),
+                                        stats: stats.as_mut(),
+                                        #[cfg(feature = "client_diagnostics")]
+                                        apply_costs: apply_costs.as_mut(),
+                                        delta_baselines: &mut delta_baselines,
+                                        command_markers: &command_markers,
+                                        registry: &registry,
+                                        catching_up: false,
+                                    };
+
+                                    // Put resources back even on error since it's recoverable.
+                                    let result = apply_replication(
+                                        world,
+                                        &mut params,
+                                        &mut client,
+                                        &mut buffered_mutations,
+                                    );
+
+                                    if let Some(stats) = stats {
+                                        world.insert_resource(stats);
+                                    }
+                                    if let Some(mutate_ticks) = mutate_ticks {
+                                        world.insert_resource(mutate_ticks);
+                                    }
+                                    #[cfg(feature = "client_diagnostics")]
+                                    if let Some(mut apply_costs) = apply_costs {
+                                        apply_costs.resolve_names(world.components());
+                                        world.insert_resource(apply_costs);
+                                    }
+
+                                    result
+                                },
+                            )
+                        })
                     })
                 })
             })
@@ -148,15 +279,50 @@ pub(super) fn receive_replication(
     })
 }
 
-fn reset(
+/// Emits conflicts rejected by [`ServerEntityMap`].
+fn send_mapping_conflicts(
+    mut entity_map: ResMut<ServerEntityMap>,
+    mut conflict_events: EventWriter<MappingConflict>,
+) {
+    conflict_events.send_batch(entity_map.bypass_change_detection().drain_conflicts());
+}
+
+fn update_pause(
+    mut pause_events: EventReader<ServerPauseChanged>,
+    mut server_paused: ResMut<ServerPaused>,
+) {
+    if let Some(event) = pause_events.read().last() {
+        debug!("changing server pause status to `{}`", event.paused);
+        server_paused.0 = event.paused;
+    }
+}
+
+fn reset(mut server_paused: ResMut<ServerPaused>, quality: Option<ResMut<ConnectionQuality>>) {
+    server_paused.0 = false;
+    if let Some(mut quality) = quality {
+        quality.reset();
+    }
+}
+
+fn reset_replication(
     mut update_tick: ResMut<ServerUpdateTick>,
+    mut tick_range: ResMut<ServerTickRange>,
+    mut received_epoch: ResMut<ReceivedEpoch>,
     mut entity_map: ResMut<ServerEntityMap>,
     mut buffered_mutations: ResMut<BufferedMutations>,
+    mut delta_baselines: ResMut<DeltaBaselines>,
     stats: Option<ResMut<ClientReplicationStats>>,
+    catch_up: Option<ResMut<ClientCatchUp>>,
 ) {
     *update_tick = Default::default();
+    if let Some(mut catch_up) = catch_up {
+        catch_up.last_tick = None;
+    }
+    *tick_range = Default::default();
+    *received_epoch = Default::default();
     entity_map.clear();
     buffered_mutations.clear();
+    delta_baselines.clear();
     if let Some(mut stats) = stats {
         *stats = Default::default();
     }
@@ -172,7 +338,7 @@ fn apply_replication(
     buffered_mutations: &mut BufferedMutations,
 ) -> postcard::Result<()> {
     for mut message in client.receive(ReplicationChannel::Updates) {
-        apply_update_message(world, params, &mut message)?;
+        apply_update_message(world, params, buffered_mutations, &mut message)?;
     }
 
     // Unlike update messages, we read all mutate messages first, sort them by tick
@@ -183,16 +349,101 @@ fn apply_replication(
     let update_tick = *world.resource::<ServerUpdateTick>();
     let acks_size =
         MutateIndex::POSTCARD_MAX_SIZE * client.received_count(ReplicationChannel::Mutations);
-    if acks_size != 0 {
-        let mut acks = Vec::with_capacity(acks_size);
-        for message in client.receive(ReplicationChannel::Mutations) {
-            let mutate_index = buffer_mutate_message(params, buffered_mutations, message)?;
+    let mut acks = Vec::with_capacity(acks_size);
+    for message in client.receive(ReplicationChannel::Mutations) {
+        if let Some(mutate_index) =
+            buffer_mutate_message(params, buffered_mutations, update_tick, message)?
+        {
             postcard_utils::to_extend_mut(&mutate_index, &mut acks)?;
         }
+    }
+    let epoch = **world.resource::<ReceivedEpoch>();
+    restore_pending_deltas(params, buffered_mutations, update_tick, epoch, &mut acks)?;
+    if !acks.is_empty() {
         client.send(ReplicationChannel::Updates, acks);
     }
 
-    apply_mutate_messages(world, params, buffered_mutations, update_tick)
+    if let Some(mut catch_up) = world.get_resource_mut::<ClientCatchUp>() {
+        // Messages are sorted by tick in descending order.
+        let latest_tick = buffered_mutations
+            .0
+            .iter()
+            .find(|mutate| mutate.update_tick <= *update_tick)
+            .map(|mutate| mutate.message_tick)
+            .filter(|&tick| tick > *update_tick)
+            .unwrap_or(*update_tick);
+
+        let max_tick_gap = catch_up.max_tick_gap;
+        match catch_up.last_tick {
+            // Nothing received yet.
+            _ if latest_tick == RepliconTick::default() => (),
+            None => catch_up.last_tick = Some(latest_tick),
+            Some(previous_tick) if latest_tick > previous_tick => {
+                catch_up.last_tick = Some(latest_tick);
+                if latest_tick - previous_tick > max_tick_gap {
+                    catch_up_to(
+                        world,
+                        params,
+                        buffered_mutations,
+                        update_tick,
+                        previous_tick,
+                        latest_tick,
+                        max_tick_gap,
+                    );
+                }
+            }
+            Some(_) => (),
+        }
+    }
+
+    let policy = *world.resource::<MutationBufferPolicy>();
+    apply_mutate_messages(world, params, buffered_mutations, update_tick, policy)?;
+    trim_mutate_messages(world, params, buffered_mutations, update_tick, policy);
+
+    Ok(())
+}
+
+/// Enables catch-up mode for this frame.
+///
+/// Drops buffered mutate messages that are older than the allowed gap.
+///
+/// See [`ClientCatchUp`].
+fn catch_up_to(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    buffered_mutations: &mut BufferedMutations,
+    update_tick: ServerUpdateTick,
+    previous_tick: RepliconTick,
+    latest_tick: RepliconTick,
+    max_tick_gap: u32,
+) {
+    debug!("catching up from {previous_tick:?} to {latest_tick:?}");
+    params.catching_up = true;
+    let min_tick = latest_tick - max_tick_gap;
+    buffered_mutations.0.retain(|mutate| {
+        if mutate.update_tick > *update_tick || mutate.message_tick >= min_tick {
+            return true;
+        }
+
+        trace!(
+            "dropping stale mutate message for {:?}",
+            mutate.message_tick
+        );
+        if let Some(mutate_ticks) = &mut params.mutate_ticks {
+            if mutate_ticks.confirm(mutate.message_tick, mutate.messages_count) {
+                world.send_event(MutateTickReceived {
+                    tick: mutate.message_tick,
+                });
+            }
+        }
+
+        false
+    });
+
+    world.send_event(CatchUpPerformed {
+        from: previous_tick,
+        to: latest_tick,
+    });
 }
 
 /// Reads and applies an update message.
@@ -201,6 +452,7 @@ fn apply_replication(
 fn apply_update_message(
     world: &mut World,
     params: &mut ReceiveParams,
+    buffered_mutations: &mut BufferedMutations,
     message: &mut Bytes,
 ) -> postcard::Result<()> {
     if let Some(stats) = &mut params.stats {
@@ -208,15 +460,31 @@ fn apply_update_message(
         stats.bytes += message.len();
     }
 
+    if !read_version(message)? || !read_compression(message)? {
+        return Ok(());
+    }
+
+    // Sections could be empty if the message only switches the epoch.
     let flags: UpdateMessageFlags = postcard_utils::from_buf(message)?;
-    debug_assert!(!flags.is_empty(), "message can't be empty");
+    let epoch = if flags.contains(UpdateMessageFlags::EPOCH) {
+        postcard_utils::from_buf(message)?
+    } else {
+        Default::default()
+    };
 
     let message_tick = postcard_utils::from_buf(message)?;
+    let ticks_covered = if flags.contains(UpdateMessageFlags::TICKS_COVERED) {
+        postcard_utils::from_buf(message)?
+    } else {
+        1
+    };
+    update_epoch(world, params, buffered_mutations, epoch, message_tick);
     trace!("applying update message for {message_tick:?}");
     world.resource_mut::<ServerUpdateTick>().0 = message_tick;
+    *world.resource_mut::<ServerTickRange>() = ServerTickRange::new(message_tick, ticks_covered);
 
-    let last_flag = flags.last();
-    for (_, flag) in flags.iter_names() {
+    let last_flag = flags.sections().last();
+    for (_, flag) in flags.sections().iter_names() {
         let array_kind = if flag != last_flag {
             ArrayKind::Sized
         } else {
@@ -225,33 +493,52 @@ fn apply_update_message(
 
         match flag {
             UpdateMessageFlags::MAPPINGS => {
-                debug_assert_eq!(array_kind, ArrayKind::Sized);
-                let len = apply_array(array_kind, message, |message| {
-                    apply_entity_mapping(world, params, message)
+                let len = read_array(array_kind, message, |message| {
+                    apply_entity_mapping(world, params, message, message_tick)
                 })?;
                 if let Some(stats) = &mut params.stats {
                     stats.mappings += len;
                 }
             }
             UpdateMessageFlags::DESPAWNS => {
-                let len = apply_array(array_kind, message, |message| {
-                    apply_despawn(world, params, message, message_tick)
+                let len = read_array(array_kind, message, |message| {
+                    let server_entity = entity_serde::deserialize_entity(message)?;
+                    apply_despawn(world, params, server_entity, message_tick);
+                    Ok(())
+                })?;
+                if let Some(stats) = &mut params.stats {
+                    stats.despawns += len;
+                }
+            }
+            UpdateMessageFlags::DESPAWN_BATCHES => {
+                let mut len = 0;
+                read_array(array_kind, message, |message| {
+                    entity_batch::deserialize(message, |server_entity| {
+                        len += 1;
+                        apply_despawn(world, params, server_entity, message_tick);
+                        Ok(())
+                    })
                 })?;
                 if let Some(stats) = &mut params.stats {
                     stats.despawns += len;
                 }
             }
             UpdateMessageFlags::REMOVALS => {
-                let len = apply_array(array_kind, message, |message| {
+                let len = read_array(array_kind, message, |message| {
                     apply_removals(world, params, message, message_tick)
                 })?;
                 if let Some(stats) = &mut params.stats {
                     stats.entities_changed += len;
                 }
             }
+            UpdateMessageFlags::RESOURCES => {
+                read_array(array_kind, message, |message| {
+                    apply_resource(world, message)
+                })?;
+            }
             UpdateMessageFlags::CHANGES => {
                 debug_assert_eq!(array_kind, ArrayKind::Dynamic);
-                let len = apply_array(array_kind, message, |message| {
+                let len = read_array(array_kind, message, |message| {
                     apply_changes(world, params, message, message_tick)
                 })?;
                 if let Some(stats) = &mut params.stats {
@@ -265,21 +552,61 @@ fn apply_update_message(
     Ok(())
 }
 
+/// Switches to the received epoch.
+///
+/// If it differs from the current one, discards all state from the previous epoch
+/// and emits [`WorldReset`]. The first received epoch is adopted without a reset.
+fn update_epoch(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    buffered_mutations: &mut BufferedMutations,
+    epoch: ReplicationEpoch,
+    message_tick: RepliconTick,
+) {
+    let mut received_epoch = world.resource_mut::<ReceivedEpoch>();
+    let previous_epoch = received_epoch.0.replace(epoch);
+    if previous_epoch.is_none_or(|previous_epoch| previous_epoch == epoch) {
+        return;
+    }
+
+    debug!("resetting world for {epoch:?}");
+    let ctx = DespawnCtx { message_tick };
+    let client_entities: Vec<_> = params.entity_map.to_client().values().copied().collect();
+    params.entity_map.clear();
+    params.delta_baselines.clear();
+    for client_entity in client_entities {
+        if let Ok(client_entity) = world.get_entity_mut(client_entity) {
+            (params.registry.despawn)(&ctx, client_entity);
+        }
+    }
+
+    buffered_mutations.0.retain(|mutate| mutate.epoch == epoch);
+
+    world.send_event(WorldReset { epoch });
+}
+
 /// Reads and buffers mutate message.
 ///
 /// For details see [`replication_messages`](crate::server::replication_messages).
 ///
-/// Returns mutate index to be used for acknowledgment.
+/// Returns mutate index to be used for acknowledgment or `None` if the message was ignored
+/// or its acknowledgment was postponed until [`restore_pending_deltas`].
 fn buffer_mutate_message(
     params: &mut ReceiveParams,
     buffered_mutations: &mut BufferedMutations,
+    last_update_tick: ServerUpdateTick,
     mut message: Bytes,
-) -> postcard::Result<MutateIndex> {
+) -> postcard::Result<Option<MutateIndex>> {
     if let Some(stats) = &mut params.stats {
         stats.messages += 1;
         stats.bytes += message.len();
     }
 
+    if !read_version(&mut message)? || !read_compression(&mut message)? {
+        return Ok(None);
+    }
+
+    let epoch = postcard_utils::from_buf(&mut message)?;
     let update_tick = postcard_utils::from_buf(&mut message)?;
     let message_tick = postcard_utils::from_buf(&mut message)?;
     let messages_count = if params.mutate_ticks.is_some() {
@@ -289,44 +616,223 @@ fn buffer_mutate_message(
     };
     let mutate_index = postcard_utils::from_buf(&mut message)?;
     trace!("received mutate message for {message_tick:?}");
+
+    let mut pending_ack = None;
+    if params.registry.has_delta() {
+        if update_tick > *last_update_tick {
+            // Deltas could be based on values from the update message that hasn't arrived yet.
+            // Until they are restored, the message can't be used as a baseline by the server.
+            pending_ack = Some(mutate_index);
+        } else {
+            message = restore_deltas(params, message, message_tick)?;
+        }
+    }
+
     buffered_mutations.insert(BufferedMutate {
+        epoch,
         update_tick,
         message_tick,
         messages_count,
+        pending_ack,
         message,
     });
 
-    Ok(mutate_index)
+    Ok(pending_ack.is_none().then_some(mutate_index))
+}
+
+/// Restores deltas for buffered mutate messages whose update message has arrived.
+///
+/// Messages are processed from the oldest since newer deltas could be based on their values.
+/// Writes mutate indices of the restored messages into `acks`.
+fn restore_pending_deltas(
+    params: &mut ReceiveParams,
+    buffered_mutations: &mut BufferedMutations,
+    update_tick: ServerUpdateTick,
+    epoch: Option<ReplicationEpoch>,
+    acks: &mut Vec<u8>,
+) -> postcard::Result<()> {
+    for mutate in buffered_mutations.0.iter_mut().rev() {
+        if mutate.update_tick > *update_tick || epoch.is_some_and(|epoch| mutate.epoch != epoch) {
+            continue;
+        }
+        let Some(mutate_index) = mutate.pending_ack.take() else {
+            continue;
+        };
+
+        let message = mem::take(&mut mutate.message);
+        mutate.message = restore_deltas(params, message, mutate.message_tick)?;
+        postcard_utils::to_extend_mut(&mutate_index, acks)?;
+    }
+
+    Ok(())
+}
+
+/// Replaces components encoded relative to acknowledged values with their full values.
+///
+/// Components with delta functions are placed first for each entity,
+/// so other components are copied without deserialization.
+/// Restored values are stored in [`DeltaBaselines`].
+fn restore_deltas(
+    params: &mut ReceiveParams,
+    mut message: Bytes,
+    message_tick: RepliconTick,
+) -> postcard::Result<Bytes> {
+    let mut restored = Vec::with_capacity(message.len());
+    let mut data_buffer = Vec::new();
+    while message.has_remaining() {
+        let entity_start = message.clone();
+        let server_entity = entity_serde::deserialize_entity(&mut message)?;
+        restored.extend_from_slice(&entity_start[..entity_start.len() - message.len()]);
+
+        let data_size: usize = postcard_utils::from_buf(&mut message)?;
+        if data_size > message.len() {
+            return Err(postcard::Error::DeserializeUnexpectedEnd);
+        }
+        let mut data = message.split_to(data_size);
+        data_buffer.clear();
+        while data.has_remaining() {
+            let mut component = data.clone();
+            let fns_id: FnsId = postcard_utils::from_buf(&mut component)?;
+            let (_, _, rule_fns) = params
+                .registry
+                .try_get(fns_id)
+                .ok_or(postcard::Error::SerdeDeCustom)?;
+            let Some((_, apply_diff)) = rule_fns.delta() else {
+                break;
+            };
+
+            let value = params.delta_baselines.read(
+                (server_entity, fns_id),
+                apply_diff,
+                message_tick,
+                &mut component,
+            )?;
+            postcard_utils::to_extend_mut(&fns_id, &mut data_buffer)?;
+            postcard_utils::to_extend_mut(&None::<RepliconTick>, &mut data_buffer)?;
+            postcard_utils::to_extend_mut(&value.len(), &mut data_buffer)?;
+            data_buffer.extend_from_slice(&value);
+            data = component;
+        }
+        data_buffer.extend_from_slice(&data);
+
+        postcard_utils::to_extend_mut(&data_buffer.len(), &mut restored)?;
+        restored.extend_from_slice(&data_buffer);
+    }
+
+    Ok(restored.into())
+}
+
+/// Reads [`ProtocolVersion`] from a replication message.
+///
+/// Returns `false` if it doesn't match [`ProtocolVersion::CURRENT`].
+fn read_version(message: &mut Bytes) -> postcard::Result<bool> {
+    let version: ProtocolVersion = postcard_utils::from_buf(message)?;
+    if version != ProtocolVersion::CURRENT {
+        error!(
+            "ignoring replication message with {version:?}, expected {:?}",
+            ProtocolVersion::CURRENT
+        );
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// Reads [`CompressionKind`] from a replication message and decompresses the rest of it.
+///
+/// Returns `false` if the message can't be decompressed.
+fn read_compression(message: &mut Bytes) -> postcard::Result<bool> {
+    let compression: CompressionKind = postcard_utils::from_buf(message)?;
+    match compression {
+        CompressionKind::None => Ok(true),
+        #[cfg(feature = "zstd")]
+        CompressionKind::Zstd => match decompress_zstd(message) {
+            Ok(decompressed) => {
+                *message = decompressed;
+                Ok(true)
+            }
+            Err(e) => {
+                error!("ignoring replication message that failed to decompress: {e}");
+                Ok(false)
+            }
+        },
+        #[cfg(not(feature = "zstd"))]
+        CompressionKind::Zstd => {
+            error!("ignoring replication message compressed with zstd, which requires the `zstd` feature");
+            Ok(false)
+        }
+        CompressionKind::Dictionary => {
+            error!("ignoring replication message compressed with a dictionary, which requires `DictionaryCompressionPlugin`");
+            Ok(false)
+        }
+    }
+}
+
+/// Maximum size of a decompressed replication message.
+///
+/// Protects against malicious frames that declare a huge content size.
+#[cfg(feature = "zstd")]
+const MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+#[cfg(feature = "zstd")]
+fn decompress_zstd(data: &[u8]) -> io::Result<Bytes> {
+    let capacity = match zstd_safe::get_frame_content_size(data) {
+        Ok(Some(size)) if size <= MAX_DECOMPRESSED_SIZE as u64 => size as usize,
+        _ => {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "frame should have a valid content size",
+            ))
+        }
+    };
+
+    zstd::bulk::decompress(data, capacity).map(Into::into)
 }
 
 /// Applies mutations from [`BufferedMutations`].
 ///
 /// If the mutate message can't be applied yet (because the update message with the
-/// corresponding tick hasn't arrived), it will be kept in the buffer.
+/// corresponding tick hasn't arrived), it will be kept in the buffer
+/// unless [`MutationBufferPolicy::optimistic`] is set.
 fn apply_mutate_messages(
     world: &mut World,
     params: &mut ReceiveParams,
     buffered_mutations: &mut BufferedMutations,
     update_tick: ServerUpdateTick,
+    policy: MutationBufferPolicy,
 ) -> postcard::Result<()> {
     let mut result = Ok(());
+    let epoch = **world.resource::<ReceivedEpoch>();
     buffered_mutations.0.retain_mut(|mutate| {
-        if mutate.update_tick > *update_tick {
-            return true;
-        }
+        if epoch.is_some_and(|epoch| mutate.epoch != epoch) {
+            // Messages from the next epoch wait for its update message.
+            if mutate.update_tick > *update_tick {
+                return true;
+            }
 
-        trace!("applying mutate message for {:?}", mutate.message_tick);
-        let len = apply_array(ArrayKind::Dynamic, &mut mutate.message, |message| {
-            apply_mutations(world, params, message, mutate.message_tick)
-        });
+            trace!(
+                "dropping mutate message for {:?} from {:?}",
+                mutate.message_tick,
+                mutate.epoch
+            );
+        } else {
+            if !policy.optimistic && mutate.update_tick > *update_tick {
+                return true;
+            }
 
-        match len {
-            Ok(len) => {
-                if let Some(stats) = &mut params.stats {
-                    stats.entities_changed += len;
+            trace!("applying mutate message for {:?}", mutate.message_tick);
+            let len = read_array(ArrayKind::Dynamic, &mut mutate.message, |message| {
+                apply_mutations(world, params, message, mutate.message_tick)
+            });
+
+            match len {
+                Ok(len) => {
+                    if let Some(stats) = &mut params.stats {
+                        stats.entities_changed += len;
+                    }
                 }
+                Err(e) => result = Err(e),
             }
-            Err(e) => result = Err(e),
         }
 
         if let Some(mutate_ticks) = &mut params.mutate_ticks {
@@ -343,19 +849,80 @@ fn apply_mutate_messages(
     result
 }
 
+/// Drops buffered mutate messages that exceed limits from [`MutationBufferPolicy`].
+///
+/// Should be called after [`apply_mutate_messages`], so only waiting messages are left.
+fn trim_mutate_messages(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    buffered_mutations: &mut BufferedMutations,
+    update_tick: ServerUpdateTick,
+    policy: MutationBufferPolicy,
+) {
+    let mut drop_mutate = |mutate: BufferedMutate| {
+        trace!(
+            "dropping buffered mutate message for {:?}",
+            mutate.message_tick
+        );
+        if let Some(mutate_ticks) = &mut params.mutate_ticks {
+            if mutate_ticks.confirm(mutate.message_tick, mutate.messages_count) {
+                world.send_event(MutateTickReceived {
+                    tick: mutate.message_tick,
+                });
+            }
+        }
+    };
+
+    if let Some(max_age) = policy.max_age {
+        // Messages are sorted by tick in descending order.
+        let latest_tick = buffered_mutations
+            .0
+            .first()
+            .map(|mutate| mutate.message_tick)
+            .filter(|&tick| tick > *update_tick)
+            .unwrap_or(*update_tick);
+        let index = buffered_mutations
+            .0
+            .partition_point(|mutate| latest_tick - mutate.message_tick <= max_age);
+        for mutate in buffered_mutations.0.drain(index..) {
+            drop_mutate(mutate);
+        }
+    }
+
+    if let Some(max_messages) = policy.max_messages {
+        if buffered_mutations.0.len() > max_messages {
+            debug!(
+                "dropping {} buffered mutate messages over the limit",
+                buffered_mutations.0.len() - max_messages
+            );
+            for mutate in buffered_mutations.0.drain(max_messages..) {
+                drop_mutate(mutate);
+            }
+        }
+    }
+}
+
 /// Deserializes and applies server mapping from client's pre-spawned entities.
 fn apply_entity_mapping(
     world: &mut World,
     params: &mut ReceiveParams,
     message: &mut Bytes,
+    message_tick: RepliconTick,
 ) -> postcard::Result<()> {
     let server_entity = entity_serde::deserialize_entity(message)?;
     let client_entity = entity_serde::deserialize_entity(message)?;
 
     if let Ok(mut entity) = world.get_entity_mut(client_entity) {
         debug!("received mapping from {server_entity:?} to {client_entity:?}");
-        entity.insert(Replicated);
-        params.entity_map.insert(server_entity, client_entity);
+        if params.entity_map.insert(server_entity, client_entity) {
+            entity.insert(Replicated);
+            // The server may skip sending components if they already match,
+            // so the mapping itself confirms the entity. Confirm only the previous tick
+            // to accept mutations for the mapping tick.
+            if !entity.contains::<ConfirmHistory>() {
+                entity.insert(ConfirmHistory::new(message_tick - 1));
+            }
+        }
     } else {
         // Entity could be despawned on client already.
         debug!("received mapping from {server_entity:?} to {client_entity:?}, but the entity doesn't exists");
@@ -364,17 +931,33 @@ fn apply_entity_mapping(
     Ok(())
 }
 
-/// Deserializes and applies entity despawn from update message.
+/// Deserializes and applies resource insertion, mutation or removal.
+fn apply_resource(world: &mut World, message: &mut Bytes) -> postcard::Result<()> {
+    let id = postcard_utils::from_buf(message)?;
+    let fns = world
+        .resource::<ReplicatedResources>()
+        .get(id)
+        .ok_or(postcard::Error::SerdeDeCustom)?;
+
+    if postcard_utils::from_buf(message)? {
+        (fns.write)(world, message)
+    } else {
+        (fns.remove)(world);
+        Ok(())
+    }
+}
+
+/// Applies entity despawn from update message.
 fn apply_despawn(
     world: &mut World,
     params: &mut ReceiveParams,
-    message: &mut Bytes,
+    server_entity: Entity,
     message_tick: RepliconTick,
-) -> postcard::Result<()> {
+) {
     // The entity might have already been despawned because of hierarchy or
     // with the last replication message, but the server might not yet have received confirmation
     // from the client and could include the deletion in the this message.
-    let server_entity = entity_serde::deserialize_entity(message)?;
+    params.delta_baselines.remove_entity(server_entity);
     if let Some(client_entity) = params
         .entity_map
         .remove_by_server(server_entity)
@@ -383,8 +966,6 @@ fn apply_despawn(
         let ctx = DespawnCtx { message_tick };
         (params.registry.despawn)(&ctx, client_entity);
     }
-
-    Ok(())
 }
 
 /// Deserializes and applies component removals for an entity.
@@ -413,13 +994,42 @@ fn apply_removals(
         message_tick,
     );
 
-    let len = apply_array(ArrayKind::Sized, message, |message| {
+    let len = read_array(ArrayKind::Sized, message, |message| {
         let fns_id = postcard_utils::from_buf(message)?;
-        let (component_id, component_fns, _) = params.registry.get(fns_id);
+        let (component_id, component_fns, rule_fns) = params
+            .registry
+            .try_get(fns_id)
+            .ok_or(postcard::Error::SerdeDeCustom)?;
+        let final_value = rule_fns.final_value() && postcard_utils::from_buf(message)?;
+        if final_value {
+            let mut ctx =
+                WriteCtx::new(&mut commands, params.entity_map, component_id, message_tick);
+
+            #[cfg(feature = "client_diagnostics")]
+            let start = params.apply_costs.is_some().then(Instant::now);
+
+            // SAFETY: `rule_fns` and `component_fns` were created for the same type.
+            unsafe {
+                component_fns.write(
+                    &mut ctx,
+                    rule_fns,
+                    params.entity_markers,
+                    &mut client_entity,
+                    message,
+                )?;
+            }
+
+            #[cfg(feature = "client_diagnostics")]
+            if let Some((apply_costs, start)) = params.apply_costs.as_mut().zip(start) {
+                apply_costs.record(fns_id, component_id, start.elapsed());
+            }
+        }
+
         let mut ctx = RemoveCtx {
             commands: &mut commands,
             message_tick,
             component_id,
+            final_value,
         };
         component_fns.remove(&mut ctx, params.entity_markers, &mut client_entity);
 
@@ -461,11 +1071,29 @@ fn apply_changes(
         message_tick,
     );
 
-    let len = apply_array(ArrayKind::Sized, message, |message| {
-        let fns_id = postcard_utils::from_buf(message)?;
-        let (component_id, component_fns, rule_fns) = params.registry.get(fns_id);
+    let mut write_component = |fns_id, message: &mut Bytes| {
+        let (component_id, component_fns, rule_fns) = params
+            .registry
+            .try_get(fns_id)
+            .ok_or(postcard::Error::SerdeDeCustom)?;
+        let mut value;
+        let message = match rule_fns.delta() {
+            Some((_, apply_diff)) => {
+                value = params.delta_baselines.read(
+                    (server_entity, fns_id),
+                    apply_diff,
+                    message_tick,
+                    message,
+                )?;
+                &mut value
+            }
+            None => message,
+        };
         let mut ctx = WriteCtx::new(&mut commands, params.entity_map, component_id, message_tick);
 
+        #[cfg(feature = "client_diagnostics")]
+        let start = params.apply_costs.is_some().then(Instant::now);
+
         // SAFETY: `rule_fns` and `component_fns` were created for the same type.
         unsafe {
             component_fns.write(
@@ -477,11 +1105,42 @@ fn apply_changes(
             )?;
         }
 
+        #[cfg(feature = "client_diagnostics")]
+        if let Some((apply_costs, start)) = params.apply_costs.as_mut().zip(start) {
+            apply_costs.record(fns_id, component_id, start.elapsed());
+        }
+
         Ok(())
-    })?;
+    };
+
+    // The lowest bit indicates presence of packed zero-sized components.
+    let header: usize = postcard_utils::from_buf(message)?;
+    let mut flags: u64 = if header & 1 != 0 {
+        postcard_utils::from_buf(message)?
+    } else {
+        0
+    };
+    let flags_len = flags.count_ones() as usize;
+    while flags != 0 {
+        let bit = flags.trailing_zeros();
+        flags &= flags - 1;
+
+        // Zero-sized components don't have any data.
+        let fns_id = params
+            .registry
+            .flag_fns_id(bit)
+            .ok_or(postcard::Error::SerdeDeCustom)?;
+        write_component(fns_id, &mut Bytes::new())?;
+    }
+
+    let len = header >> 1;
+    for _ in 0..len {
+        let fns_id = postcard_utils::from_buf(message)?;
+        write_component(fns_id, message)?;
+    }
 
     if let Some(stats) = &mut params.stats {
-        stats.components_changed += len;
+        stats.components_changed += len + flags_len;
     }
 
     params.queue.apply(world);
@@ -489,41 +1148,6 @@ fn apply_changes(
     Ok(())
 }
 
-fn apply_array(
-    kind: ArrayKind,
-    message: &mut Bytes,
-    mut f: impl FnMut(&mut Bytes) -> postcard::Result<()>,
-) -> postcard::Result<usize> {
-    match kind {
-        ArrayKind::Sized => {
-            let len = postcard_utils::from_buf(message)?;
-            for _ in 0..len {
-                (f)(message)?;
-            }
-
-            Ok(len)
-        }
-        ArrayKind::Dynamic => {
-            let mut len = 0;
-            while message.has_remaining() {
-                (f)(message)?;
-                len += 1;
-            }
-
-            Ok(len)
-        }
-    }
-}
-
-/// Type of serialized array.
-#[derive(PartialEq, Eq, Debug)]
-enum ArrayKind {
-    /// Size is serialized before the array.
-    Sized,
-    /// Size is unknown, means that all bytes needs to be consumed.
-    Dynamic,
-}
-
 fn confirm_tick(
     commands: &mut Commands,
     entity: &mut DeferredEntity,
@@ -554,6 +1178,9 @@ fn apply_mutations(
 ) -> postcard::Result<()> {
     let server_entity = entity_serde::deserialize_entity(message)?;
     let data_size: usize = postcard_utils::from_buf(message)?;
+    if data_size > message.len() {
+        return Err(postcard::Error::DeserializeUnexpectedEnd);
+    }
 
     let Some(client_entity) = params.entity_map.get_by_server(server_entity) else {
         // Mutation could arrive after a despawn from update message.
@@ -568,14 +1195,15 @@ fn apply_mutations(
         .entity_markers
         .read(params.command_markers, &*client_entity);
 
+    // All replicated entities have confirmed ticks, so it's possible only with malformed messages.
     let mut history = client_entity
         .get_mut::<ConfirmHistory>()
-        .expect("all entities from mutate message should have confirmed ticks");
+        .ok_or(postcard::Error::SerdeDeCustom)?;
     let new_tick = message_tick > history.last_tick();
     if new_tick {
         history.set_last_tick(message_tick);
     } else {
-        if !params.entity_markers.need_history() {
+        if params.catching_up || !params.entity_markers.need_history() {
             trace!(
                 "ignoring outdated mutations for client's {:?}",
                 client_entity.id()
@@ -605,9 +1233,28 @@ fn apply_mutations(
     let mut components_count = 0;
     while data.has_remaining() {
         let fns_id = postcard_utils::from_buf(&mut data)?;
-        let (component_id, component_fns, rule_fns) = params.registry.get(fns_id);
+        let (component_id, component_fns, rule_fns) = params
+            .registry
+            .try_get(fns_id)
+            .ok_or(postcard::Error::SerdeDeCustom)?;
+        let mut value;
+        let bytes = match rule_fns.delta() {
+            Some((_, apply_diff)) => {
+                value = params.delta_baselines.read(
+                    (server_entity, fns_id),
+                    apply_diff,
+                    message_tick,
+                    &mut data,
+                )?;
+                &mut value
+            }
+            None => &mut data,
+        };
         let mut ctx = WriteCtx::new(&mut commands, params.entity_map, component_id, message_tick);
 
+        #[cfg(feature = "client_diagnostics")]
+        let start = params.apply_costs.is_some().then(Instant::now);
+
         // SAFETY: `rule_fns` and `component_fns` were created for the same type.
         unsafe {
             if new_tick {
@@ -616,7 +1263,7 @@ fn apply_mutations(
                     rule_fns,
                     params.entity_markers,
                     &mut client_entity,
-                    &mut data,
+                    bytes,
                 )?;
             } else {
                 component_fns.consume_or_write(
@@ -625,11 +1272,16 @@ fn apply_mutations(
                     params.entity_markers,
                     params.command_markers,
                     &mut client_entity,
-                    &mut data,
+                    bytes,
                 )?;
             }
         }
 
+        #[cfg(feature = "client_diagnostics")]
+        if let Some((apply_costs, start)) = params.apply_costs.as_mut().zip(start) {
+            apply_costs.record(fns_id, component_id, start.elapsed());
+        }
+
         components_count += 1;
     }
 
@@ -652,8 +1304,16 @@ struct ReceiveParams<'a> {
     replicated_events: &'a mut Events<EntityReplicated>,
     mutate_ticks: Option<&'a mut ServerMutateTicks>,
     stats: Option<&'a mut ClientReplicationStats>,
+    #[cfg(feature = "client_diagnostics")]
+    apply_costs: Option<&'a mut ClientApplyCosts>,
+    delta_baselines: &'a mut DeltaBaselines,
     command_markers: &'a CommandMarkers,
     registry: &'a ReplicationRegistry,
+
+    /// Skip history for outdated mutations.
+    ///
+    /// See [`ClientCatchUp`].
+    catching_up: bool,
 }
 
 /// Set with replication and event systems related to client.
@@ -726,6 +1386,49 @@ pub enum ClientSet {
 #[derive(Clone, Copy, Debug, Default, Deref, Resource)]
 pub struct ServerUpdateTick(RepliconTick);
 
+/// Indicates if the server is paused.
+///
+/// Updated from [`ServerPauseChanged`] and reset on disconnect.
+/// See [`RepliconServer::pause`](crate::core::replicon_server::RepliconServer::pause).
+#[derive(Clone, Copy, Debug, Default, Deref, Resource)]
+pub struct ServerPaused(bool);
+
+/// Range of server ticks covered by the last received update message.
+///
+/// Contains multiple ticks if the server runs several simulation ticks per send.
+/// See `ServerPlugin::ticks_per_send`.
+#[derive(Clone, Copy, Debug, Default, Resource)]
+pub struct ServerTickRange {
+    first: RepliconTick,
+    last: RepliconTick,
+}
+
+impl ServerTickRange {
+    fn new(last: RepliconTick, ticks_covered: u32) -> Self {
+        Self {
+            first: last - ticks_covered.saturating_sub(1),
+            last,
+        }
+    }
+
+    /// Returns the first tick covered by the message.
+    pub fn first(&self) -> RepliconTick {
+        self.first
+    }
+
+    /// Returns the last tick covered by the message.
+    ///
+    /// Equal to [`ServerUpdateTick`].
+    pub fn last(&self) -> RepliconTick {
+        self.last
+    }
+
+    /// Returns the number of covered ticks.
+    pub fn ticks_count(&self) -> u32 {
+        self.last - self.first + 1
+    }
+}
+
 /// Cached buffered mutate messages, used to synchronize mutations with update messages.
 ///
 /// If [`ClientSet::Reset`] is disabled, then this needs to be cleaned up manually with [`Self::clear`].
@@ -737,6 +1440,31 @@ impl BufferedMutations {
         self.0.clear();
     }
 
+    /// Returns the number of buffered messages.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no buffered messages.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the total size of buffered messages in bytes.
+    pub fn bytes(&self) -> usize {
+        self.0.iter().map(|mutate| mutate.message.len()).sum()
+    }
+
+    /// Returns the oldest tick among buffered messages.
+    pub fn oldest_tick(&self) -> Option<RepliconTick> {
+        self.0.last().map(|mutate| mutate.message_tick)
+    }
+
+    /// Returns the newest tick among buffered messages.
+    pub fn newest_tick(&self) -> Option<RepliconTick> {
+        self.0.first().map(|mutate| mutate.message_tick)
+    }
+
     /// Inserts a new buffered message, maintaining sorting by their message tick in descending order.
     fn insert(&mut self, mutation: BufferedMutate) {
         let index = self
@@ -750,6 +1478,9 @@ impl BufferedMutations {
 ///
 /// See also [`crate::server::replication_messages`].
 pub(super) struct BufferedMutate {
+    /// Epoch in which the message was sent.
+    epoch: ReplicationEpoch,
+
     /// Required tick to wait for.
     update_tick: RepliconTick,
 
@@ -761,6 +1492,11 @@ pub(super) struct BufferedMutate {
     /// May not be equal to the number of received messages.
     messages_count: usize,
 
+    /// Index to acknowledge after restoring deltas.
+    ///
+    /// See [`restore_pending_deltas`].
+    pending_ack: Option<MutateIndex>,
+
     /// Mutations data.
     message: Bytes,
 }
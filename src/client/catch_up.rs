@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use crate::core::replicon_tick::RepliconTick;
+
+/// Enables catch-up mode on client after long stalls.
+///
+/// When the received ticks are more than [`Self::max_tick_gap`] ahead of the last applied
+/// tick (e.g. after the app was in background), the client fast-forwards:
+/// buffered mutate messages older than the gap are dropped, only the latest mutations
+/// are applied per entity (history requested by markers is skipped)
+/// and [`CatchUpPerformed`] is emitted so the game can hide the transition.
+///
+/// The resource is not added by default.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{client::catch_up::ClientCatchUp, prelude::*};
+///
+/// # let mut app = App::new();
+/// app.insert_resource(ClientCatchUp::new(60));
+/// ```
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct ClientCatchUp {
+    /// Maximum number of ticks the client can lag behind before catching up.
+    pub max_tick_gap: u32,
+
+    /// The latest tick received from update or mutate messages.
+    pub(super) last_tick: Option<RepliconTick>,
+}
+
+impl ClientCatchUp {
+    /// Creates a new instance with the specified maximum gap in ticks.
+    pub fn new(max_tick_gap: u32) -> Self {
+        Self {
+            max_tick_gap,
+            last_tick: None,
+        }
+    }
+}
+
+impl Default for ClientCatchUp {
+    fn default() -> Self {
+        Self::new(60)
+    }
+}
+
+/// An event that emitted after the client fast-forwarded its replication state.
+///
+/// See [`ClientCatchUp`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CatchUpPerformed {
+    /// The last applied update tick before catching up.
+    pub from: RepliconTick,
+
+    /// The latest received tick.
+    pub to: RepliconTick,
+}
@@ -0,0 +1,228 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::core::replicon_client::RepliconClient;
+
+/// Classifies the connection based on [`RepliconClient::rtt`] and [`RepliconClient::packet_loss`].
+///
+/// Stats are evaluated every [`Self::interval`], and [`ConnectionQualityChanged`] is emitted
+/// when any of the buckets changes. To avoid flickering when a value oscillates around a threshold,
+/// a bucket changes only when the value crosses the threshold by more than [`Self::hysteresis`].
+///
+/// The resource is not added by default.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     client::connection_quality::{ConnectionQuality, ConnectionQualityChanged},
+///     prelude::*,
+/// };
+///
+/// # let mut app = App::new();
+/// app.insert_resource(ConnectionQuality::default())
+///     .add_systems(Update, update_indicator);
+///
+/// fn update_indicator(mut quality_events: EventReader<ConnectionQualityChanged>) {
+///     for event in quality_events.read() {
+///         info!("connection quality changed to {event:?}");
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct ConnectionQuality {
+    /// Round-trip times in seconds above which the connection becomes
+    /// [`QualityBucket::Fair`] and [`QualityBucket::Poor`].
+    pub rtt_thresholds: [f64; 2],
+
+    /// Packet loss percentages above which the connection becomes
+    /// [`QualityBucket::Fair`] and [`QualityBucket::Poor`].
+    pub loss_thresholds: [f64; 2],
+
+    /// Fraction of a threshold by which a value needs to cross it to change the bucket.
+    pub hysteresis: f64,
+
+    /// How often the stats are evaluated.
+    pub interval: Duration,
+
+    rtt_bucket: QualityBucket,
+    loss_bucket: QualityBucket,
+
+    /// Time of the last evaluation.
+    last_update: Duration,
+}
+
+impl ConnectionQuality {
+    /// Returns the current bucket for the round-trip time.
+    pub fn rtt_bucket(&self) -> QualityBucket {
+        self.rtt_bucket
+    }
+
+    /// Returns the current bucket for the packet loss.
+    pub fn loss_bucket(&self) -> QualityBucket {
+        self.loss_bucket
+    }
+
+    /// Returns the worst of the two buckets.
+    pub fn overall(&self) -> QualityBucket {
+        self.rtt_bucket.max(self.loss_bucket)
+    }
+
+    /// Re-evaluates buckets and returns `true` if any of them changed.
+    fn update(&mut self, rtt: f64, packet_loss: f64) -> bool {
+        let rtt_bucket =
+            QualityBucket::classify(self.rtt_bucket, rtt, self.rtt_thresholds, self.hysteresis);
+        let loss_bucket = QualityBucket::classify(
+            self.loss_bucket,
+            packet_loss,
+            self.loss_thresholds,
+            self.hysteresis,
+        );
+
+        let changed = rtt_bucket != self.rtt_bucket || loss_bucket != self.loss_bucket;
+        self.rtt_bucket = rtt_bucket;
+        self.loss_bucket = loss_bucket;
+
+        changed
+    }
+
+    pub(super) fn reset(&mut self) {
+        self.rtt_bucket = Default::default();
+        self.loss_bucket = Default::default();
+        self.last_update = Duration::ZERO;
+    }
+}
+
+impl Default for ConnectionQuality {
+    fn default() -> Self {
+        Self {
+            rtt_thresholds: [0.1, 0.25],
+            loss_thresholds: [2.0, 10.0],
+            hysteresis: 0.1,
+            interval: Duration::from_secs(1),
+            rtt_bucket: Default::default(),
+            loss_bucket: Default::default(),
+            last_update: Duration::ZERO,
+        }
+    }
+}
+
+/// Standardized level of a connection metric.
+///
+/// See [`ConnectionQuality`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QualityBucket {
+    /// Below the first threshold.
+    #[default]
+    Good,
+    /// Between the thresholds.
+    Fair,
+    /// Above the second threshold.
+    Poor,
+}
+
+impl QualityBucket {
+    const ALL: [Self; 3] = [Self::Good, Self::Fair, Self::Poor];
+
+    /// Returns the bucket for the value, staying in the current one unless a threshold
+    /// is crossed by more than `hysteresis`.
+    fn classify(current: Self, value: f64, thresholds: [f64; 2], hysteresis: f64) -> Self {
+        let mut index = current as usize;
+        while index < thresholds.len() && value > thresholds[index] * (1.0 + hysteresis) {
+            index += 1;
+        }
+        while index > 0 && value < thresholds[index - 1] * (1.0 - hysteresis) {
+            index -= 1;
+        }
+
+        Self::ALL[index]
+    }
+}
+
+/// An event that emitted when [`ConnectionQuality`] buckets change.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConnectionQualityChanged {
+    /// The new bucket for the round-trip time.
+    pub rtt_bucket: QualityBucket,
+
+    /// The new bucket for the packet loss.
+    pub loss_bucket: QualityBucket,
+}
+
+pub(super) fn update_quality(
+    mut quality_events: EventWriter<ConnectionQualityChanged>,
+    mut quality: ResMut<ConnectionQuality>,
+    client: Res<RepliconClient>,
+    time: Res<Time>,
+) {
+    if time.elapsed() - quality.last_update < quality.interval {
+        return;
+    }
+    quality.last_update = time.elapsed();
+
+    if quality.update(client.rtt(), client.packet_loss()) {
+        debug!(
+            "changing connection quality to RTT `{:?}` and loss `{:?}`",
+            quality.rtt_bucket, quality.loss_bucket
+        );
+        quality_events.send(ConnectionQualityChanged {
+            rtt_bucket: quality.rtt_bucket,
+            loss_bucket: quality.loss_bucket,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classification() {
+        let thresholds = [10.0, 20.0];
+        let hysteresis = 0.1;
+
+        let bucket = QualityBucket::classify(QualityBucket::Good, 5.0, thresholds, hysteresis);
+        assert_eq!(bucket, QualityBucket::Good);
+
+        let bucket = QualityBucket::classify(QualityBucket::Good, 15.0, thresholds, hysteresis);
+        assert_eq!(bucket, QualityBucket::Fair);
+
+        let bucket = QualityBucket::classify(QualityBucket::Good, 30.0, thresholds, hysteresis);
+        assert_eq!(bucket, QualityBucket::Poor);
+
+        let bucket = QualityBucket::classify(QualityBucket::Poor, 5.0, thresholds, hysteresis);
+        assert_eq!(bucket, QualityBucket::Good);
+    }
+
+    #[test]
+    fn hysteresis() {
+        let thresholds = [10.0, 20.0];
+        let hysteresis = 0.1;
+
+        let bucket = QualityBucket::classify(QualityBucket::Good, 10.5, thresholds, hysteresis);
+        assert_eq!(bucket, QualityBucket::Good);
+
+        let bucket = QualityBucket::classify(QualityBucket::Good, 11.5, thresholds, hysteresis);
+        assert_eq!(bucket, QualityBucket::Fair);
+
+        let bucket = QualityBucket::classify(QualityBucket::Fair, 9.5, thresholds, hysteresis);
+        assert_eq!(bucket, QualityBucket::Fair);
+
+        let bucket = QualityBucket::classify(QualityBucket::Fair, 8.5, thresholds, hysteresis);
+        assert_eq!(bucket, QualityBucket::Good);
+    }
+
+    #[test]
+    fn change_detection() {
+        let mut quality = ConnectionQuality::default();
+        assert!(!quality.update(0.05, 0.0));
+        assert!(quality.update(0.3, 0.0));
+        assert_eq!(quality.rtt_bucket(), QualityBucket::Poor);
+        assert_eq!(quality.overall(), QualityBucket::Poor);
+        assert!(!quality.update(0.3, 0.0));
+        assert!(quality.update(0.3, 5.0));
+        assert_eq!(quality.loss_bucket(), QualityBucket::Fair);
+    }
+}
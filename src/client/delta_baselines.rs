@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use bytes::Bytes;
+
+use crate::core::{
+    protocol::postcard_utils,
+    replication::replication_registry::{rule_fns::ApplyDiffFn, FnsId},
+    replicon_tick::RepliconTick,
+};
+
+/// Maximum number of stored values for each component.
+///
+/// Matches the number of sends for which the server keeps baselines.
+const MAX_VALUES: usize = 64;
+
+/// Received serialized values of components replicated with
+/// [`RuleFns::with_delta`](crate::core::replication::replication_registry::rule_fns::RuleFns::with_delta).
+///
+/// Keyed by server entities since mutations can be received before their entity is mapped.
+/// The server encodes a value relative to an acknowledged tick, and the value the client has
+/// on that tick is the last one received on or before it.
+#[derive(Resource, Default)]
+pub(crate) struct DeltaBaselines {
+    entities: EntityHashMap<Vec<(FnsId, VecDeque<(RepliconTick, Vec<u8>)>)>>,
+}
+
+impl DeltaBaselines {
+    /// Reads a component with delta functions and returns its full serialized value.
+    ///
+    /// Expects the tick of the baseline value, the data size and the data itself.
+    /// Without a baseline the data is the full value.
+    ///
+    /// The restored value is stored as a baseline for `message_tick`.
+    pub(crate) fn read(
+        &mut self,
+        (entity, fns_id): (Entity, FnsId),
+        apply_diff: ApplyDiffFn,
+        message_tick: RepliconTick,
+        message: &mut Bytes,
+    ) -> postcard::Result<Bytes> {
+        let baseline_tick: Option<RepliconTick> = postcard_utils::from_buf(message)?;
+        let len: usize = postcard_utils::from_buf(message)?;
+        if len > message.len() {
+            return Err(postcard::Error::DeserializeUnexpectedEnd);
+        }
+        let bytes = message.split_to(len);
+
+        let value = match baseline_tick {
+            Some(tick) => {
+                let baseline = self
+                    .get(entity, fns_id, tick)
+                    .ok_or(postcard::Error::DeserializeBadEncoding)?;
+                let mut value = Vec::new();
+                (apply_diff)(baseline, &bytes, &mut value)?;
+                value.into()
+            }
+            None => bytes,
+        };
+
+        self.insert(entity, fns_id, message_tick, &value);
+
+        Ok(value)
+    }
+
+    /// Returns the last value received on or before `tick`.
+    fn get(&self, entity: Entity, fns_id: FnsId, tick: RepliconTick) -> Option<&[u8]> {
+        let components = self.entities.get(&entity)?;
+        let (_, values) = components.iter().find(|&&(id, _)| id == fns_id)?;
+        values
+            .iter()
+            .rev()
+            .find(|&&(value_tick, _)| value_tick <= tick)
+            .map(|(_, bytes)| &**bytes)
+    }
+
+    /// Stores a value, keeping values sorted by tick.
+    ///
+    /// Mutate messages can arrive out of order, so the value is inserted at its position.
+    fn insert(&mut self, entity: Entity, fns_id: FnsId, tick: RepliconTick, bytes: &[u8]) {
+        let components = self.entities.entry(entity).or_default();
+        let index = match components.iter().position(|&(id, _)| id == fns_id) {
+            Some(index) => index,
+            None => {
+                components.push((fns_id, Default::default()));
+                components.len() - 1
+            }
+        };
+        let (_, values) = &mut components[index];
+
+        let index = values.partition_point(|&(value_tick, _)| value_tick < tick);
+        match values.get_mut(index) {
+            Some((value_tick, value)) if *value_tick == tick => {
+                value.clear();
+                value.extend_from_slice(bytes);
+            }
+            _ => {
+                values.insert(index, (tick, bytes.to_vec()));
+                if values.len() > MAX_VALUES {
+                    values.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Removes values of all components for a despawned server entity.
+    pub(crate) fn remove_entity(&mut self, entity: Entity) {
+        self.entities.remove(&entity);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entities.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::core::replication::replication_registry::{
+        rule_fns::{self, RuleFns},
+        ReplicationRegistry,
+    };
+
+    #[test]
+    fn latest_before_tick() {
+        let mut baselines = DeltaBaselines::default();
+        let entity = Entity::from_raw(0);
+        let fns_id = register_fns();
+        baselines.insert(entity, fns_id, RepliconTick::new(5), &[2]);
+        baselines.insert(entity, fns_id, RepliconTick::new(1), &[1]);
+
+        assert_eq!(baselines.get(entity, fns_id, RepliconTick::new(0)), None);
+        assert_eq!(
+            baselines.get(entity, fns_id, RepliconTick::new(4)),
+            Some(&[1][..])
+        );
+        assert_eq!(
+            baselines.get(entity, fns_id, RepliconTick::new(7)),
+            Some(&[2][..])
+        );
+    }
+
+    #[test]
+    fn read_diff() {
+        let mut baselines = DeltaBaselines::default();
+        let entity = Entity::from_raw(0);
+        let fns_id = register_fns();
+        let baseline = [1, 2, 3, 4];
+        let value = [1, 2, 5, 4];
+        baselines.insert(entity, fns_id, RepliconTick::new(1), &baseline);
+
+        let mut diff = Vec::new();
+        rule_fns::byte_diff(&baseline, &value, &mut diff).unwrap();
+        let mut message = Vec::new();
+        postcard_utils::to_extend_mut(&Some(RepliconTick::new(1)), &mut message).unwrap();
+        postcard_utils::to_extend_mut(&diff.len(), &mut message).unwrap();
+        message.extend_from_slice(&diff);
+
+        let mut message = Bytes::from(message);
+        let restored = baselines
+            .read(
+                (entity, fns_id),
+                rule_fns::apply_byte_diff,
+                RepliconTick::new(2),
+                &mut message,
+            )
+            .unwrap();
+        assert_eq!(*restored, value);
+        assert!(message.is_empty());
+        assert_eq!(
+            baselines.get(entity, fns_id, RepliconTick::new(2)),
+            Some(&value[..])
+        );
+    }
+
+    fn register_fns() -> FnsId {
+        let mut world = World::new();
+        let mut registry = ReplicationRegistry::default();
+        let (_, fns_id) =
+            registry.register_rule_fns(&mut world, RuleFns::<TestComponent>::default());
+        fns_id
+    }
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct TestComponent;
+}
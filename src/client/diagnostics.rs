@@ -1,23 +1,38 @@
+use std::time::Duration;
+
 use bevy::diagnostic::DiagnosticPath;
 use bevy::{
     diagnostic::{Diagnostic, Diagnostics, RegisterDiagnostic},
+    ecs::component::{ComponentId, Components},
     prelude::*,
+    utils::HashMap,
 };
 
-use super::{ClientReplicationStats, ClientSet};
-use crate::core::{common_conditions::client_connected, replicon_client::RepliconClient};
+use super::{BufferedMutations, ClientReplicationStats, ClientSet, ServerUpdateTick};
+use crate::core::{
+    common_conditions::client_connected, replication::replication_registry::FnsId,
+    replicon_client::RepliconClient, replicon_tick::RepliconTick,
+};
 
 /// Plugin to write [`Diagnostics`] based on [`ClientReplicationStats`] every second.
 ///
-/// Adds [`ClientReplicationStats`] resource.
+/// Also writes the current size of [`BufferedMutations`] and emits [`MutationBacklogExceeded`]
+/// if [`MutationBacklogThresholds`] is present.
+///
+/// Adds [`ClientReplicationStats`] and [`ClientApplyCosts`] resources.
 pub struct ClientDiagnosticsPlugin;
 
 impl Plugin for ClientDiagnosticsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ClientReplicationStats>()
+            .init_resource::<ClientApplyCosts>()
+            .add_event::<MutationBacklogExceeded>()
             .add_systems(
                 PreUpdate,
-                add_measurements
+                (
+                    add_measurements,
+                    check_backlog.run_if(resource_exists::<MutationBacklogThresholds>),
+                )
                     .in_set(ClientSet::Diagnostics)
                     .run_if(client_connected),
             )
@@ -70,6 +85,21 @@ impl Plugin for ClientDiagnosticsPlugin {
                 Diagnostic::new(REPLICATION_BYTES)
                     .with_suffix(" replication bytes")
                     .with_max_history_length(DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(BUFFERED_MUTATIONS)
+                    .with_suffix(" buffered mutate messages")
+                    .with_max_history_length(DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(BUFFERED_MUTATION_BYTES)
+                    .with_suffix(" buffered mutation bytes")
+                    .with_max_history_length(DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(BUFFERED_MUTATION_AGE)
+                    .with_suffix(" ticks")
+                    .with_max_history_length(DIAGNOSTIC_HISTORY_LEN),
             );
     }
 }
@@ -99,6 +129,16 @@ pub const REPLICATION_MESSAGES: DiagnosticPath =
 /// How many replication bytes received.
 pub const REPLICATION_BYTES: DiagnosticPath = DiagnosticPath::const_new("client/replication/bytes");
 
+/// How many mutate messages are waiting for their update message.
+pub const BUFFERED_MUTATIONS: DiagnosticPath =
+    DiagnosticPath::const_new("client/replication/buffered_mutations");
+/// How many bytes are taken by mutate messages waiting for their update message.
+pub const BUFFERED_MUTATION_BYTES: DiagnosticPath =
+    DiagnosticPath::const_new("client/replication/buffered_mutation_bytes");
+/// How many ticks the oldest buffered mutate message is behind the latest received tick.
+pub const BUFFERED_MUTATION_AGE: DiagnosticPath =
+    DiagnosticPath::const_new("client/replication/buffered_mutation_age");
+
 /// Max diagnostic history length.
 pub const DIAGNOSTIC_HISTORY_LEN: usize = 60;
 
@@ -107,25 +147,224 @@ fn add_measurements(
     stats: Res<ClientReplicationStats>,
     mut last_stats: Local<ClientReplicationStats>,
     client: Res<RepliconClient>,
+    buffered_mutations: Res<BufferedMutations>,
+    update_tick: Res<ServerUpdateTick>,
 ) {
     diagnostics.add_measurement(&RTT, || client.rtt());
     diagnostics.add_measurement(&PACKET_LOSS, || client.packet_loss());
     diagnostics.add_measurement(&SENT_BPS, || client.sent_bps());
     diagnostics.add_measurement(&RECEIVED_BPS, || client.received_bps());
 
+    // Stats could be reset, for example after a reconnect.
     diagnostics.add_measurement(&ENTITIES_CHANGED, || {
-        (stats.entities_changed - last_stats.entities_changed) as f64
+        stats
+            .entities_changed
+            .saturating_sub(last_stats.entities_changed) as f64
     });
     diagnostics.add_measurement(&COMPONENTS_CHANGED, || {
-        (stats.components_changed - last_stats.components_changed) as f64
+        stats
+            .components_changed
+            .saturating_sub(last_stats.components_changed) as f64
+    });
+    diagnostics.add_measurement(&MAPPINGS, || {
+        stats.mappings.saturating_sub(last_stats.mappings) as f64
+    });
+    diagnostics.add_measurement(&DESPAWNS, || {
+        stats.despawns.saturating_sub(last_stats.despawns) as f64
     });
-    diagnostics.add_measurement(&MAPPINGS, || (stats.mappings - last_stats.mappings) as f64);
-    diagnostics.add_measurement(&DESPAWNS, || (stats.despawns - last_stats.despawns) as f64);
     diagnostics.add_measurement(&REPLICATION_MESSAGES, || {
-        (stats.messages - last_stats.messages) as f64
+        stats.messages.saturating_sub(last_stats.messages) as f64
     });
     diagnostics.add_measurement(&REPLICATION_BYTES, || {
-        (stats.bytes - last_stats.bytes) as f64
+        stats.bytes.saturating_sub(last_stats.bytes) as f64
     });
     *last_stats = *stats;
+
+    let backlog = MutationBacklog::new(&buffered_mutations, **update_tick);
+    diagnostics.add_measurement(&BUFFERED_MUTATIONS, || backlog.messages as f64);
+    diagnostics.add_measurement(&BUFFERED_MUTATION_BYTES, || backlog.bytes as f64);
+    diagnostics.add_measurement(&BUFFERED_MUTATION_AGE, || backlog.age as f64);
+}
+
+fn check_backlog(
+    mut was_exceeded: Local<bool>,
+    thresholds: Res<MutationBacklogThresholds>,
+    mut exceeded_events: EventWriter<MutationBacklogExceeded>,
+    buffered_mutations: Res<BufferedMutations>,
+    update_tick: Res<ServerUpdateTick>,
+) {
+    let backlog = MutationBacklog::new(&buffered_mutations, **update_tick);
+    let exceeded = thresholds
+        .max_messages
+        .is_some_and(|max| backlog.messages > max)
+        || thresholds.max_bytes.is_some_and(|max| backlog.bytes > max)
+        || thresholds.max_age.is_some_and(|max| backlog.age > max);
+
+    if exceeded && !*was_exceeded {
+        warn!(
+            "mutation backlog exceeded thresholds with {} messages, {} bytes and {} ticks age",
+            backlog.messages, backlog.bytes, backlog.age
+        );
+        exceeded_events.send(MutationBacklogExceeded {
+            messages: backlog.messages,
+            bytes: backlog.bytes,
+            age: backlog.age,
+        });
+    }
+    *was_exceeded = exceeded;
+}
+
+/// Thresholds for [`BufferedMutations`] after which [`MutationBacklogExceeded`] is emitted.
+///
+/// Requires [`ClientDiagnosticsPlugin`]. A growing backlog is usually the first symptom
+/// of lost or slow update messages.
+///
+/// The resource is not added by default.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{client::diagnostics::MutationBacklogThresholds, prelude::*};
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, RepliconPlugins))
+///     .insert_resource(MutationBacklogThresholds {
+///         max_messages: Some(32),
+///         max_age: Some(10),
+///         ..Default::default()
+///     });
+/// ```
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct MutationBacklogThresholds {
+    /// Maximum number of buffered messages.
+    pub max_messages: Option<usize>,
+
+    /// Maximum total size of buffered messages in bytes.
+    pub max_bytes: Option<usize>,
+
+    /// Maximum number of ticks the oldest buffered message can be behind the latest received tick.
+    pub max_age: Option<u32>,
+}
+
+/// An event that emitted when [`BufferedMutations`] exceeds [`MutationBacklogThresholds`].
+///
+/// Emitted once until the backlog goes back under the thresholds.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MutationBacklogExceeded {
+    /// Number of buffered messages.
+    pub messages: usize,
+
+    /// Total size of buffered messages in bytes.
+    pub bytes: usize,
+
+    /// Number of ticks the oldest buffered message is behind the latest received tick.
+    pub age: u32,
+}
+
+/// Current size of [`BufferedMutations`].
+struct MutationBacklog {
+    messages: usize,
+    bytes: usize,
+    age: u32,
+}
+
+impl MutationBacklog {
+    fn new(buffered_mutations: &BufferedMutations, update_tick: RepliconTick) -> Self {
+        let age = match (
+            buffered_mutations.oldest_tick(),
+            buffered_mutations.newest_tick(),
+        ) {
+            (Some(oldest), Some(newest)) => {
+                let latest = if newest > update_tick {
+                    newest
+                } else {
+                    update_tick
+                };
+                latest - oldest
+            }
+            _ => 0,
+        };
+
+        Self {
+            messages: buffered_mutations.len(),
+            bytes: buffered_mutations.bytes(),
+            age,
+        }
+    }
+}
+
+/// Time spent in write functions of received components, aggregated per [`FnsId`].
+///
+/// Complements [`ClientReplicationStats`] to find heavy custom deserializers.
+/// Measures only deserialization and writing, insertions are applied later as commands.
+///
+/// Statistic will be collected only if the resource is present.
+/// Added by [`ClientDiagnosticsPlugin`].
+#[derive(Resource, Clone, Debug, Default)]
+pub struct ClientApplyCosts(HashMap<FnsId, ApplyCost>);
+
+impl ClientApplyCosts {
+    /// Returns the accumulated cost for the functions.
+    pub fn get(&self, fns_id: FnsId) -> Option<&ApplyCost> {
+        self.0.get(&fns_id)
+    }
+
+    /// Returns an iterator over functions IDs and their accumulated costs.
+    pub fn iter(&self) -> impl Iterator<Item = (FnsId, &ApplyCost)> {
+        self.0.iter().map(|(&fns_id, cost)| (fns_id, cost))
+    }
+
+    /// Resets all accumulated costs.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Adds time spent in write functions for a component.
+    pub(crate) fn record(&mut self, fns_id: FnsId, component_id: ComponentId, elapsed: Duration) {
+        let cost = self.0.entry(fns_id).or_insert_with(|| ApplyCost {
+            component_id,
+            type_name: Default::default(),
+            calls: 0,
+            total: Duration::ZERO,
+        });
+        cost.calls += 1;
+        cost.total += elapsed;
+    }
+
+    /// Resolves type names for costs recorded since the last call.
+    pub(crate) fn resolve_names(&mut self, components: &Components) {
+        for cost in self.0.values_mut().filter(|cost| cost.type_name.is_empty()) {
+            if let Some(info) = components.get_info(cost.component_id) {
+                cost.type_name = info.name().to_string();
+            }
+        }
+    }
+}
+
+/// Accumulated cost of applying a component.
+///
+/// See [`ClientApplyCosts`].
+#[derive(Clone, Debug)]
+pub struct ApplyCost {
+    /// ID of the component.
+    pub component_id: ComponentId,
+
+    /// Type name of the component.
+    pub type_name: String,
+
+    /// Number of applied values.
+    pub calls: usize,
+
+    /// Total time spent in write functions.
+    pub total: Duration,
+}
+
+impl ApplyCost {
+    /// Returns average time per applied value.
+    pub fn average(&self) -> Duration {
+        self.total
+            .checked_div(self.calls as u32)
+            .unwrap_or_default()
+    }
 }
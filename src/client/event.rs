@@ -1,5 +1,6 @@
 use super::{ClientSet, ServerUpdateTick};
 use crate::core::{
+    channels::RepliconChannels,
     common_conditions::*,
     event::{
         ctx::{ClientReceiveCtx, ClientSendCtx},
@@ -65,10 +66,33 @@ impl Plugin for ClientEventPlugin {
             ParamBuilder,
             ParamBuilder,
             ParamBuilder,
+            ParamBuilder,
         )
             .build_state(app.world_mut())
             .build_system(receive);
 
+        let receive_receipts = (
+            FilteredResourcesMutParamBuilder::new(|builder| {
+                for event in event_registry.iter_client_events() {
+                    if event.delivered_id().is_some() {
+                        builder.add_write_by_id(event.reader_id());
+                    }
+                }
+            }),
+            FilteredResourcesMutParamBuilder::new(|builder| {
+                for delivered_id in event_registry
+                    .iter_client_events()
+                    .filter_map(|event| event.delivered_id())
+                {
+                    builder.add_write_by_id(delivered_id);
+                }
+            }),
+            ParamBuilder,
+            ParamBuilder,
+        )
+            .build_state(app.world_mut())
+            .build_system(receive_receipts);
+
         let trigger = (
             FilteredResourcesMutParamBuilder::new(|builder| {
                 for trigger in event_registry.iter_server_triggers() {
@@ -81,6 +105,34 @@ impl Plugin for ClientEventPlugin {
             .build_state(app.world_mut())
             .build_system(trigger);
 
+        let deliver_locally = (
+            FilteredResourcesParamBuilder::new(|builder| {
+                for event in event_registry.iter_client_events() {
+                    if event.delivered_id().is_some() {
+                        builder.add_read_by_id(event.events_id());
+                    }
+                }
+            }),
+            FilteredResourcesMutParamBuilder::new(|builder| {
+                for event in event_registry.iter_client_events() {
+                    if event.delivered_id().is_some() {
+                        builder.add_write_by_id(event.reader_id());
+                    }
+                }
+            }),
+            FilteredResourcesMutParamBuilder::new(|builder| {
+                for delivered_id in event_registry
+                    .iter_client_events()
+                    .filter_map(|event| event.delivered_id())
+                {
+                    builder.add_write_by_id(delivered_id);
+                }
+            }),
+            ParamBuilder,
+        )
+            .build_state(app.world_mut())
+            .build_system(deliver_locally);
+
         let resend_locally = (
             FilteredResourcesMutParamBuilder::new(|builder| {
                 for event in event_registry.iter_client_events() {
@@ -103,6 +155,11 @@ impl Plugin for ClientEventPlugin {
                     builder.add_write_by_id(event.events_id());
                 }
             }),
+            FilteredResourcesMutParamBuilder::new(|builder| {
+                for event in event_registry.iter_client_events() {
+                    builder.add_write_by_id(event.reader_id());
+                }
+            }),
             FilteredResourcesMutParamBuilder::new(|builder| {
                 for event in event_registry.iter_server_events() {
                     builder.add_write_by_id(event.queue_id());
@@ -119,7 +176,7 @@ impl Plugin for ClientEventPlugin {
                 (
                     reset.in_set(ClientSet::ResetEvents),
                     (
-                        receive
+                        (receive, receive_receipts)
                             .after(super::receive_replication)
                             .run_if(client_connected),
                         trigger,
@@ -132,7 +189,9 @@ impl Plugin for ClientEventPlugin {
                 PostUpdate,
                 (
                     send.run_if(client_connected),
-                    resend_locally.run_if(server_or_singleplayer),
+                    (deliver_locally, resend_locally)
+                        .chain()
+                        .run_if(server_or_singleplayer),
                 )
                     .chain()
                     .in_set(ClientSet::Send),
@@ -145,12 +204,14 @@ fn send(
     mut readers: FilteredResourcesMut,
     mut client: ResMut<RepliconClient>,
     registry: Res<AppTypeRegistry>,
-    entity_map: Res<ServerEntityMap>,
+    entity_map: Option<Res<ServerEntityMap>>,
     event_registry: Res<EventRegistry>,
 ) {
+    let empty_map = ServerEntityMap::default();
     let mut ctx = ClientSendCtx {
-        entity_map: &entity_map,
+        entity_map: entity_map.as_deref().unwrap_or(&empty_map),
         registry: &registry.read(),
+        invalid_entities: Vec::new(),
     };
 
     for event in event_registry.iter_client_events() {
@@ -173,16 +234,27 @@ fn receive(
     mut queues: FilteredResourcesMut,
     mut client: ResMut<RepliconClient>,
     registry: Res<AppTypeRegistry>,
-    entity_map: Res<ServerEntityMap>,
+    entity_map: Option<Res<ServerEntityMap>>,
     event_registry: Res<EventRegistry>,
-    update_tick: Res<ServerUpdateTick>,
+    channels: Res<RepliconChannels>,
+    update_tick: Option<Res<ServerUpdateTick>>,
 ) {
+    let empty_map = ServerEntityMap::default();
+    let update_tick = update_tick.map(|tick| **tick).unwrap_or_default();
     let mut ctx = ClientReceiveCtx {
         registry: &registry.read(),
-        entity_map: &entity_map,
+        entity_map: entity_map.as_deref().unwrap_or(&empty_map),
         invalid_entities: Vec::new(),
     };
 
+    for &channel_id in event_registry
+        .ordered_server_channels()
+        .iter()
+        .chain(channels.shared_server_channels())
+    {
+        client.split_ordered_channel(channel_id);
+    }
+
     for event in event_registry.iter_server_events() {
         let events = events
             .get_mut_by_id(event.events_id())
@@ -198,12 +270,34 @@ fn receive(
                 events.into_inner(),
                 queue.into_inner(),
                 &mut client,
-                **update_tick,
+                update_tick,
             )
         };
     }
 }
 
+fn receive_receipts(
+    mut readers: FilteredResourcesMut,
+    mut delivered: FilteredResourcesMut,
+    mut client: ResMut<RepliconClient>,
+    event_registry: Res<EventRegistry>,
+) {
+    for event in event_registry.iter_client_events() {
+        let Some(delivered_id) = event.delivered_id() else {
+            continue;
+        };
+        let reader = readers
+            .get_mut_by_id(event.reader_id())
+            .expect("event reader resource should be accessible");
+        let delivered = delivered
+            .get_mut_by_id(delivered_id)
+            .expect("delivered events resource should be accessible");
+
+        // SAFETY: passed pointers were obtained using this event data.
+        unsafe { event.receive_receipts(reader.into_inner(), delivered.into_inner(), &mut client) };
+    }
+}
+
 fn trigger(
     mut events: FilteredResourcesMut,
     mut commands: Commands,
@@ -217,6 +311,31 @@ fn trigger(
     }
 }
 
+fn deliver_locally(
+    events: FilteredResources,
+    mut readers: FilteredResourcesMut,
+    mut delivered: FilteredResourcesMut,
+    event_registry: Res<EventRegistry>,
+) {
+    for event in event_registry.iter_client_events() {
+        let Some(delivered_id) = event.delivered_id() else {
+            continue;
+        };
+        let events = events
+            .get_by_id(event.events_id())
+            .expect("events resource should be accessible");
+        let reader = readers
+            .get_mut_by_id(event.reader_id())
+            .expect("event reader resource should be accessible");
+        let delivered = delivered
+            .get_mut_by_id(delivered_id)
+            .expect("delivered events resource should be accessible");
+
+        // SAFETY: passed pointers were obtained using this event data.
+        unsafe { event.deliver_locally(&events, reader.into_inner(), delivered.into_inner()) };
+    }
+}
+
 fn resend_locally(
     mut client_events: FilteredResourcesMut,
     mut events: FilteredResourcesMut,
@@ -237,6 +356,7 @@ fn resend_locally(
 
 fn reset(
     mut events: FilteredResourcesMut,
+    mut readers: FilteredResourcesMut,
     mut queues: FilteredResourcesMut,
     event_registry: Res<EventRegistry>,
 ) {
@@ -244,9 +364,12 @@ fn reset(
         let events = events
             .get_mut_by_id(event.events_id())
             .expect("events resource should be accessible");
+        let reader = readers
+            .get_mut_by_id(event.reader_id())
+            .expect("event reader resource should be accessible");
 
-        // SAFETY: passed pointer was obtained using this event data.
-        unsafe { event.reset(events.into_inner()) };
+        // SAFETY: passed pointers were obtained using this event data.
+        unsafe { event.reset(events.into_inner(), reader.into_inner()) };
     }
 
     for event in event_registry.iter_server_events() {
@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+
+use super::{
+    confirm_history::ConfirmHistory, server_mutate_ticks::ServerMutateTicks, ClientSet,
+    ServerUpdateTick,
+};
+use crate::core::{common_conditions::*, replicon_tick::RepliconTick};
+
+/// Extrapolation of replicated components when updates stall.
+pub trait AppExtrapolationExt {
+    /**
+    Registers functions to extrapolate component `C` on client.
+
+    When an entity doesn't receive any updates for [`ExtrapolationSettings::stale_ticks`],
+    it's marked with [`Extrapolated`] and `extrapolate` is called with the last received value
+    and the number of ticks since it was received. The returned value is written into the component.
+
+    When updates resume, the marker is removed and the component is blended from the last
+    extrapolated value to the received one over [`ExtrapolationSettings::blend_ticks`] using
+    `blend` with a factor from 0 to 1.
+
+    Staleness is detected using [`ConfirmHistory`] against the latest known server tick,
+    which is the most recent tick received by any entity or from [`ServerUpdateTick`]
+    and [`ServerMutateTicks`]. So the fallback doesn't engage if nothing is received
+    from the server at all.
+
+    Since the server sends only changed components, register it only for components that
+    constantly change, such as positions of moving objects.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{client::extrapolation::AppExtrapolationExt, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate::<Movement>()
+        .set_extrapolation_fns(extrapolate_movement, blend_movement);
+
+    fn extrapolate_movement(movement: &Movement, ticks: u32) -> Movement {
+        Movement {
+            position: movement.position + movement.velocity * ticks as f32,
+            velocity: movement.velocity,
+        }
+    }
+
+    fn blend_movement(from: &Movement, to: &Movement, factor: f32) -> Movement {
+        Movement {
+            position: from.position.lerp(to.position, factor),
+            velocity: to.velocity,
+        }
+    }
+
+    #[derive(Component, Clone, Deserialize, Serialize)]
+    struct Movement {
+        position: Vec2,
+        velocity: Vec2,
+    }
+    ```
+    **/
+    fn set_extrapolation_fns<C: Component + Clone>(
+        &mut self,
+        extrapolate: ExtrapolateFn<C>,
+        blend: BlendFn<C>,
+    ) -> &mut Self;
+}
+
+impl AppExtrapolationExt for App {
+    fn set_extrapolation_fns<C: Component + Clone>(
+        &mut self,
+        extrapolate: ExtrapolateFn<C>,
+        blend: BlendFn<C>,
+    ) -> &mut Self {
+        self.init_resource::<ExtrapolationSettings>()
+            .insert_resource(ExtrapolationFns { extrapolate, blend })
+            .add_systems(
+                PreUpdate,
+                update_extrapolation::<C>
+                    .after(ClientSet::Receive)
+                    .run_if(client_connected),
+            )
+    }
+}
+
+fn update_extrapolation<C: Component + Clone>(
+    mut commands: Commands,
+    settings: Res<ExtrapolationSettings>,
+    fns: Res<ExtrapolationFns<C>>,
+    update_tick: Res<ServerUpdateTick>,
+    mutate_ticks: Option<Res<ServerMutateTicks>>,
+    histories: Query<&ConfirmHistory>,
+    mut entities: Query<(
+        Entity,
+        Mut<C>,
+        &ConfirmHistory,
+        Option<&mut ExtrapolationState<C>>,
+    )>,
+) {
+    let mut latest_tick = **update_tick;
+    let ticks = mutate_ticks
+        .map(|mutate_ticks| mutate_ticks.last_tick())
+        .into_iter()
+        .chain(histories.iter().map(|history| history.last_tick()));
+    for tick in ticks {
+        if tick > latest_tick {
+            latest_tick = tick;
+        }
+    }
+
+    for (entity, mut component, history, state) in &mut entities {
+        let Some(mut state) = state else {
+            commands.entity(entity).insert(ExtrapolationState {
+                received: component.clone(),
+                received_tick: history.last_tick(),
+                phase: Phase::Received,
+            });
+            continue;
+        };
+
+        // The system doesn't observe its own writes, so it's a received value.
+        if component.is_changed() {
+            state.received = component.clone();
+        }
+
+        if history.last_tick() > state.received_tick {
+            state.received_tick = history.last_tick();
+            if let Phase::Extrapolated { value, .. } = &state.phase {
+                debug!("resuming updates for `{entity}`");
+                state.phase = Phase::Blending {
+                    from: value.clone(),
+                    start_tick: latest_tick,
+                };
+                commands.entity(entity).remove::<Extrapolated>();
+            }
+        }
+
+        let stale_ticks = if latest_tick > state.received_tick {
+            latest_tick - state.received_tick
+        } else {
+            0
+        };
+
+        if stale_ticks >= settings.stale_ticks {
+            if let Phase::Extrapolated { ticks, .. } = state.phase {
+                if ticks == stale_ticks {
+                    continue;
+                }
+            } else {
+                debug!("extrapolating `{entity}` after {stale_ticks} stale ticks");
+                commands.entity(entity).insert(Extrapolated);
+            }
+
+            let value = (fns.extrapolate)(&state.received, stale_ticks);
+            *component = value.clone();
+            state.phase = Phase::Extrapolated {
+                value,
+                ticks: stale_ticks,
+            };
+        } else if let Phase::Blending { from, start_tick } = &state.phase {
+            let elapsed = latest_tick - *start_tick;
+            if elapsed >= settings.blend_ticks {
+                *component = state.received.clone();
+                state.phase = Phase::Received;
+            } else {
+                let factor = elapsed as f32 / settings.blend_ticks as f32;
+                *component = (fns.blend)(from, &state.received, factor);
+            }
+        }
+    }
+}
+
+/// Configuration for extrapolation on client.
+///
+/// See [`AppExtrapolationExt::set_extrapolation_fns`] for details.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct ExtrapolationSettings {
+    /// Number of ticks without updates after which an entity is extrapolated.
+    pub stale_ticks: u32,
+
+    /// Number of ticks to blend from the extrapolated value to the received one.
+    ///
+    /// If zero, the received value is applied immediately.
+    pub blend_ticks: u32,
+}
+
+impl Default for ExtrapolationSettings {
+    fn default() -> Self {
+        Self {
+            stale_ticks: 4,
+            blend_ticks: 4,
+        }
+    }
+}
+
+/// Marks entities whose components are currently extrapolated.
+///
+/// See [`AppExtrapolationExt::set_extrapolation_fns`] for details.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Extrapolated;
+
+/// Predicts a component value from the last received value and the number of ticks since it was received.
+pub type ExtrapolateFn<C> = fn(&C, u32) -> C;
+
+/// Blends between two component values with a factor from 0 to 1.
+pub type BlendFn<C> = fn(&C, &C, f32) -> C;
+
+#[derive(Resource)]
+struct ExtrapolationFns<C> {
+    extrapolate: ExtrapolateFn<C>,
+    blend: BlendFn<C>,
+}
+
+/// Extrapolation state of component `C` for an entity.
+#[derive(Component)]
+struct ExtrapolationState<C> {
+    /// The last value received from server.
+    received: C,
+
+    /// The last confirmed tick for the entity.
+    received_tick: RepliconTick,
+
+    phase: Phase<C>,
+}
+
+enum Phase<C> {
+    /// The component contains the received value.
+    Received,
+
+    /// The component contains a value extrapolated for the given number of ticks.
+    Extrapolated { value: C, ticks: u32 },
+
+    /// The component blends from the last extrapolated value to the received one.
+    Blending { from: C, start_tick: RepliconTick },
+}
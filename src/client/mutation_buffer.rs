@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+/// Policy for mutate messages that arrived before the update message they depend on.
+///
+/// Mutate messages are sent over an unreliable channel and may arrive earlier than
+/// the update message with their update tick. By default, they are buffered until it arrives
+/// without any limits. This resource allows to limit the buffer or apply such messages immediately.
+///
+/// Added by default with values that preserve the default behavior.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{client::mutation_buffer::MutationBufferPolicy, prelude::*};
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, RepliconPlugins));
+///
+/// let mut policy = app.world_mut().resource_mut::<MutationBufferPolicy>();
+/// policy.max_messages = Some(64);
+/// policy.max_age = Some(30);
+/// ```
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MutationBufferPolicy {
+    /// Maximum number of buffered messages.
+    ///
+    /// When exceeded, messages with the oldest ticks are dropped.
+    pub max_messages: Option<usize>,
+
+    /// Maximum number of ticks a buffered message can be behind the latest received tick.
+    ///
+    /// Older messages are dropped.
+    pub max_age: Option<u32>,
+
+    /// Apply mutations immediately, even if the update message with their update tick hasn't arrived.
+    ///
+    /// Mutations for entities that haven't been received yet are ignored, and mutations may be applied
+    /// to entities that miss components from the pending update message. Suitable for games that
+    /// tolerate temporary inconsistency in exchange for lower latency.
+    pub optimistic: bool,
+}
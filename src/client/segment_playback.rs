@@ -0,0 +1,114 @@
+use bevy::{ecs::world::CommandQueue, prelude::*};
+use bytes::Bytes;
+
+use super::{confirm_history::EntityReplicated, delta_baselines::DeltaBaselines, ReceiveParams};
+use crate::core::{
+    protocol::array::{read_array, ArrayKind},
+    replication::{
+        command_markers::{CommandMarkers, EntityMarkers},
+        history_segment::HistorySegment,
+        replication_registry::ReplicationRegistry,
+    },
+    replicon_tick::RepliconTick,
+    server_entity_map::ServerEntityMap,
+};
+
+/// Plays back [`HistorySegment`] frame by frame into a separate world.
+///
+/// Frames are applied through the same functions as regular replication,
+/// including custom ser/de and command markers, so the playback world
+/// can be rendered like the main one, for example for a killcam.
+///
+/// Server entities are mapped to entities inside the playback world,
+/// see [`Self::entity_map`].
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     client::segment_playback::SegmentPlayback,
+///     core::replication::history_segment::HistorySegment, prelude::*,
+/// };
+///
+/// fn play_killcam(world: &mut World, playback_world: &mut World, segment: HistorySegment) {
+///     let mut playback = SegmentPlayback::new(segment);
+///     while let Some(tick) = playback.advance(world, playback_world).unwrap() {
+///         info!("showing {tick:?}");
+///     }
+/// }
+/// ```
+pub struct SegmentPlayback {
+    segment: HistorySegment,
+    next_frame: usize,
+    entity_map: ServerEntityMap,
+    queue: CommandQueue,
+    replicated_events: Events<EntityReplicated>,
+    delta_baselines: DeltaBaselines,
+}
+
+impl SegmentPlayback {
+    /// Creates a new playback starting from the first frame.
+    pub fn new(segment: HistorySegment) -> Self {
+        Self {
+            segment,
+            next_frame: 0,
+            entity_map: Default::default(),
+            queue: Default::default(),
+            replicated_events: Default::default(),
+            delta_baselines: Default::default(),
+        }
+    }
+
+    /// Applies the next frame into `playback_world` and returns its tick.
+    ///
+    /// Replication registry and command markers are taken from `world`,
+    /// which should be the main world of the client app.
+    ///
+    /// Returns [`None`] if all frames were applied.
+    pub fn advance(
+        &mut self,
+        world: &World,
+        playback_world: &mut World,
+    ) -> postcard::Result<Option<RepliconTick>> {
+        let Some(frame) = self.segment.frames.get(self.next_frame) else {
+            return Ok(None);
+        };
+        self.next_frame += 1;
+
+        trace!("playing back history frame for {:?}", frame.tick);
+        let command_markers = world.resource::<CommandMarkers>();
+        let mut entity_markers = EntityMarkers::new(command_markers);
+        let mut params = ReceiveParams {
+            queue: &mut self.queue,
+            entity_markers: &mut entity_markers,
+            entity_map: &mut self.entity_map,
+            replicated_events: &mut self.replicated_events,
+            mutate_ticks: None,
+            stats: None,
+            #[cfg(feature = "client_diagnostics")]
+            apply_costs: None,
+            delta_baselines: &mut self.delta_baselines,
+            command_markers,
+            registry: world.resource::<ReplicationRegistry>(),
+            catching_up: false,
+        };
+
+        let mut message = Bytes::copy_from_slice(&frame.changes);
+        read_array(ArrayKind::Dynamic, &mut message, |message| {
+            super::apply_changes(playback_world, &mut params, message, frame.tick)
+        })?;
+
+        Ok(Some(frame.tick))
+    }
+
+    /// Returns `true` if all frames were applied.
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.segment.len()
+    }
+
+    /// Returns mapping from server entities to entities inside the playback world.
+    pub fn entity_map(&self) -> &ServerEntityMap {
+        &self.entity_map
+    }
+}
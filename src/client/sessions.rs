@@ -0,0 +1,107 @@
+use std::mem;
+
+use bevy::{
+    app::{AppLabel, PluginsState},
+    prelude::*,
+};
+
+use crate::core::replicon_client::RepliconClient;
+
+/**
+Hosting of multiple client sessions in a single app.
+
+All client systems and resources, such as [`RepliconClient`] and
+[`ServerEntityMap`](crate::core::server_entity_map::ServerEntityMap), are singletons.
+To maintain multiple server sessions in one process (e.g. for split-screen),
+each additional session lives in its own [`SubApp`] with a separate world.
+
+A session is configured as a regular [`App`] with [`RepliconPlugins`](crate::RepliconPlugins),
+a messaging backend and the same replication rules and events as the server. The session
+is updated after the main app on each [`App::update`]. Use [`SubApp::set_extract`]
+to pass data between the main world and the session world.
+
+# Examples
+
+```
+use bevy::{app::AppLabel, prelude::*};
+use bevy_replicon::{client::sessions::ClientSessionsAppExt, prelude::*};
+use serde::{Deserialize, Serialize};
+
+#[derive(AppLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Player {
+    First,
+    Second,
+}
+
+let mut app = App::new();
+for player in [Player::First, Player::Second] {
+    let mut session = App::new();
+    session
+        .add_plugins((MinimalPlugins, RepliconPlugins /* and your messaging backend */))
+        .replicate::<Health>();
+
+    app.add_client_session(player, session);
+}
+
+// Access replicated state of a session.
+let mut query = app
+    .client_session_mut(Player::First)
+    .query::<&Health>();
+
+#[derive(Component, Deserialize, Serialize)]
+struct Health(u32);
+```
+**/
+pub trait ClientSessionsAppExt {
+    /// Adds a client session hosted in a sub-app with the given label.
+    ///
+    /// Finishes the session app plugins if they weren't finished yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the session app doesn't contain [`RepliconClient`]
+    /// or a session with this label was already added.
+    fn add_client_session(&mut self, label: impl AppLabel, session: App) -> &mut Self;
+
+    /// Returns the world of a session.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the session doesn't exist.
+    fn client_session(&self, label: impl AppLabel) -> &World;
+
+    /// Like [`Self::client_session`], but returns a mutable reference.
+    fn client_session_mut(&mut self, label: impl AppLabel) -> &mut World;
+}
+
+impl ClientSessionsAppExt for App {
+    fn add_client_session(&mut self, label: impl AppLabel, mut session: App) -> &mut Self {
+        assert!(
+            session.world().contains_resource::<RepliconClient>(),
+            "session `{label:?}` should contain client plugins"
+        );
+        assert!(
+            self.get_sub_app(label.intern()).is_none(),
+            "session `{label:?}` should be added only once"
+        );
+
+        if session.plugins_state() == PluginsState::Ready {
+            session.finish();
+            session.cleanup();
+        }
+
+        debug!("adding client session `{label:?}`");
+        let sub_app = mem::take(session.main_mut());
+        self.insert_sub_app(label, sub_app);
+
+        self
+    }
+
+    fn client_session(&self, label: impl AppLabel) -> &World {
+        self.sub_app(label).world()
+    }
+
+    fn client_session_mut(&mut self, label: impl AppLabel) -> &mut World {
+        self.sub_app_mut(label).world_mut()
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::core::protocol::replication_epoch::ReplicationEpoch;
+
+/// Epoch of the last received update message.
+///
+/// [`None`] until the first update message after connection.
+/// Reset on disconnect.
+///
+/// See also [`ServerEpoch`](crate::server::server_epoch::ServerEpoch).
+#[derive(Clone, Copy, Debug, Default, Deref, Resource)]
+pub struct ReceivedEpoch(pub(super) Option<ReplicationEpoch>);
+
+/// An event that emitted when the server switched to a new [`ReplicationEpoch`].
+///
+/// Before emitting, the client despawns all replicated entities from the previous epoch,
+/// clears [`ServerEntityMap`](crate::core::server_entity_map::ServerEntityMap) and drops
+/// buffered mutate messages from the previous epoch. Entities of the new epoch are
+/// spawned from the same update message.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorldReset {
+    /// The new epoch.
+    pub epoch: ReplicationEpoch,
+}
@@ -1,49 +1,112 @@
+#[cfg(feature = "bevy")]
 pub mod channels;
+#[cfg(feature = "bevy")]
 pub mod common_conditions;
+#[cfg(feature = "bevy")]
+pub mod config_info;
+#[cfg(feature = "server")]
 pub mod connected_clients;
+#[cfg(feature = "bevy")]
+pub mod connection_stats;
+#[cfg(feature = "bevy")]
 pub mod entity_serde;
+#[cfg(feature = "bevy")]
 pub mod event;
-pub mod postcard_utils;
+#[cfg(feature = "bevy")]
+pub mod network_fault;
+pub mod protocol;
+#[cfg(feature = "bevy")]
 pub mod replication;
+#[cfg(feature = "client")]
 pub mod replicon_client;
+#[cfg(feature = "server")]
 pub mod replicon_server;
-pub mod replicon_tick;
+#[cfg(feature = "bevy")]
 pub mod server_entity_map;
+#[cfg(feature = "bevy")]
+pub mod server_pause;
+#[cfg(feature = "bevy")]
+pub mod tick_policy;
+
+pub use protocol::{postcard_utils, protocol_version, replicon_tick};
 
 use std::error::Error;
 
+#[cfg(feature = "bevy")]
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use channels::RepliconChannels;
-use event::event_registry::EventRegistry;
+#[cfg(feature = "bevy")]
+use channels::{ChannelKind, RepliconChannel, RepliconChannels};
+#[cfg(feature = "bevy")]
+use config_info::{EventInfo, MarkerInfo, RepliconConfigInfo, RuleInfo};
+#[cfg(feature = "bevy")]
+use entity_serde::MappedTypes;
+#[cfg(feature = "bevy")]
+use event::{event_registry::EventRegistry, server_event::ServerEventAppExt};
+#[cfg(feature = "bevy")]
+use network_fault::{FaultPolicy, NetworkFault};
+#[cfg(feature = "bevy")]
 use replication::{
-    command_markers::CommandMarkers, replication_registry::ReplicationRegistry,
-    replication_rules::ReplicationRules, track_mutate_messages::TrackMutateMessages, Replicated,
+    command_markers::{CommandMarkers, MarkerConfig},
+    replicated_resources::ReplicatedResources,
+    replication_prefabs::ReplicationPrefabs,
+    replication_registry::ReplicationRegistry,
+    replication_rules::ReplicationRules,
+    track_mutate_messages::TrackMutateMessages,
+    Replicated,
 };
+#[cfg(feature = "bevy")]
+use server_pause::ServerPauseChanged;
+#[cfg(feature = "bevy")]
+use tick_policy::TickRateChanged;
 
 /// Initializes types and resources needed for both client and server.
+#[cfg(feature = "bevy")]
 pub struct RepliconCorePlugin;
 
+#[cfg(feature = "bevy")]
 impl Plugin for RepliconCorePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Replicated>()
+            .register_type::<RepliconChannels>()
+            .register_type::<RepliconChannel>()
+            .register_type::<ChannelKind>()
+            .register_type::<RepliconConfigInfo>()
+            .register_type::<RuleInfo>()
+            .register_type::<EventInfo>()
+            .register_type::<MarkerInfo>()
+            .register_type::<MarkerConfig>()
             .init_resource::<TrackMutateMessages>()
             .init_resource::<RepliconChannels>()
             .init_resource::<ReplicationRegistry>()
             .init_resource::<ReplicationRules>()
+            .init_resource::<ReplicatedResources>()
+            .init_resource::<ReplicationPrefabs>()
             .init_resource::<CommandMarkers>()
-            .init_resource::<EventRegistry>();
+            .init_resource::<EventRegistry>()
+            .init_resource::<MappedTypes>()
+            .init_resource::<FaultPolicy>()
+            .add_event::<NetworkFault>()
+            .add_server_event::<ServerPauseChanged>(ChannelKind::Ordered)
+            .make_independent::<ServerPauseChanged>()
+            .add_server_event::<TickRateChanged>(ChannelKind::Ordered)
+            .make_independent::<TickRateChanged>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        // Collect after all plugins initialization to include all registrations.
+        let config_info = RepliconConfigInfo::new(app.world());
+        app.insert_resource(config_info);
     }
 }
 
 /// Unique client ID.
 ///
 /// Could be a client or a dual server-client.
-#[derive(
-    Debug, Clone, Copy, Hash, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize, Reflect,
-)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
 pub struct ClientId(u64);
 
 impl ClientId {
@@ -33,7 +33,11 @@ impl From<ReplicationChannel> for u8 {
 }
 
 /// A resource with channels used by Replicon.
-#[derive(Clone, Resource)]
+///
+/// Registered for reflection, but backends read channels only on initialization,
+/// so changes made afterward won't affect already created channels.
+#[derive(Clone, Resource, Reflect)]
+#[reflect(Resource)]
 pub struct RepliconChannels {
     /// Stores settings for each server channel.
     server: Vec<RepliconChannel>,
@@ -41,6 +45,25 @@ pub struct RepliconChannels {
     /// Same as [`Self::server`], but for client.
     client: Vec<RepliconChannel>,
 
+    /// Server channels shared by events with [`ChannelSelection::Automatic`].
+    shared_server: Vec<u8>,
+
+    /// Same as [`Self::shared_server`], but for client.
+    shared_client: Vec<u8>,
+
+    /// Number of virtual server channels.
+    ///
+    /// See [`Self::create_virtual_server_channel`].
+    virtual_server: u8,
+
+    /// Same as [`Self::virtual_server`], but for client.
+    virtual_client: u8,
+
+    /// How channels are assigned to newly registered events.
+    ///
+    /// By default set to [`ChannelSelection::Dedicated`].
+    channel_selection: ChannelSelection,
+
     /// Stores the default max memory usage bytes for all channels.
     ///
     /// This value will be used instead of [`None`].
@@ -60,6 +83,11 @@ impl Default for RepliconChannels {
                 ReplicationChannel::Updates.into(),
                 ReplicationChannel::Mutations.into(),
             ],
+            shared_server: Default::default(),
+            shared_client: Default::default(),
+            virtual_server: 0,
+            virtual_client: 0,
+            channel_selection: Default::default(),
             default_max_bytes: 5 * 1024 * 1024,
         }
     }
@@ -71,13 +99,25 @@ impl RepliconChannels {
         self.default_max_bytes = max_bytes;
     }
 
+    /// Sets how channels are assigned to newly registered events.
+    ///
+    /// Affects only events registered after the change.
+    pub fn set_channel_selection(&mut self, selection: ChannelSelection) {
+        self.channel_selection = selection;
+    }
+
+    /// Returns the strategy set by [`Self::set_channel_selection`].
+    pub fn channel_selection(&self) -> ChannelSelection {
+        self.channel_selection
+    }
+
     /// Creates a new server channel and returns its ID.
     ///
     /// # Panics
     ///
     /// Panics if the number of events exceeds [`u8::MAX`].
     pub fn create_server_channel(&mut self, channel: impl Into<RepliconChannel>) -> u8 {
-        if self.server.len() == u8::MAX as usize {
+        if self.server.len() + self.virtual_server as usize >= u8::MAX as usize {
             panic!("number of server channels shouldn't exceed `u8::MAX`");
         }
 
@@ -94,7 +134,7 @@ impl RepliconChannels {
     ///
     /// Panics if the number of events exceeds [`u8::MAX`].
     pub fn create_client_channel(&mut self, channel: impl Into<RepliconChannel>) -> u8 {
-        if self.client.len() == u8::MAX as usize {
+        if self.client.len() + self.virtual_client as usize >= u8::MAX as usize {
             panic!("number of client channels shouldn't exceed `u8::MAX`");
         }
 
@@ -105,6 +145,97 @@ impl RepliconChannels {
         id
     }
 
+    /// Returns a shared server channel that satisfies the requirements of `channel`.
+    ///
+    /// Picks the best matching channel from channels previously returned by this method
+    /// or creates a new one if there is no suitable channel.
+    /// See [`ChannelSelection::Automatic`] for details.
+    pub(crate) fn shared_server_channel(&mut self, channel: impl Into<RepliconChannel>) -> u8 {
+        let channel = channel.into();
+        if let Some(channel_id) = best_match(&self.server, &self.shared_server, &channel) {
+            debug!("reusing shared server channel {channel_id}");
+            return channel_id;
+        }
+
+        let channel_id = self.create_server_channel(channel);
+        self.shared_server.push(channel_id);
+        channel_id
+    }
+
+    /// Same as [`Self::shared_server_channel`], but for client.
+    pub(crate) fn shared_client_channel(&mut self, channel: impl Into<RepliconChannel>) -> u8 {
+        let channel = channel.into();
+        if let Some(channel_id) = best_match(&self.client, &self.shared_client, &channel) {
+            debug!("reusing shared client channel {channel_id}");
+            return channel_id;
+        }
+
+        let channel_id = self.create_client_channel(channel);
+        self.shared_client.push(channel_id);
+        channel_id
+    }
+
+    /// Reserves an ID to identify server messages from a shared channel and returns it.
+    ///
+    /// Virtual channels aren't created on the messaging backend and allocated
+    /// from [`u8::MAX`] downwards to not collide with regular channels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of channels exceeds [`u8::MAX`].
+    pub(crate) fn create_virtual_server_channel(&mut self) -> u8 {
+        if self.server.len() + self.virtual_server as usize >= u8::MAX as usize {
+            panic!("number of server channels shouldn't exceed `u8::MAX`");
+        }
+
+        let id = u8::MAX - self.virtual_server;
+        self.virtual_server += 1;
+        id
+    }
+
+    /// Same as [`Self::create_virtual_server_channel`], but for client.
+    pub(crate) fn create_virtual_client_channel(&mut self) -> u8 {
+        if self.client.len() + self.virtual_client as usize >= u8::MAX as usize {
+            panic!("number of client channels shouldn't exceed `u8::MAX`");
+        }
+
+        let id = u8::MAX - self.virtual_client;
+        self.virtual_client += 1;
+        id
+    }
+
+    /// Returns the number of server channel IDs that can receive messages, including virtual channels.
+    pub(crate) fn server_receive_len(&self) -> usize {
+        if self.virtual_server == 0 {
+            self.server.len()
+        } else {
+            u8::MAX as usize + 1
+        }
+    }
+
+    /// Same as [`Self::server_receive_len`], but for client.
+    pub(crate) fn client_receive_len(&self) -> usize {
+        if self.virtual_client == 0 {
+            self.client.len()
+        } else {
+            u8::MAX as usize + 1
+        }
+    }
+
+    /// Returns a server channel by its ID.
+    ///
+    /// Returns [`None`] if there is no such channel.
+    pub fn server_channel<I: Into<u8>>(&self, channel_id: I) -> Option<&RepliconChannel> {
+        self.server.get(channel_id.into() as usize)
+    }
+
+    /// Returns a client channel by its ID.
+    ///
+    /// Returns [`None`] if there is no such channel.
+    pub fn client_channel<I: Into<u8>>(&self, channel_id: I) -> Option<&RepliconChannel> {
+        self.client.get(channel_id.into() as usize)
+    }
+
     /// Returns a mutable reference to a server channel.
     ///
     /// # Panics
@@ -132,10 +263,57 @@ impl RepliconChannels {
     pub fn client_channels(&self) -> &[RepliconChannel] {
         &self.client
     }
+
+    /// Returns IDs of server channels shared by events with [`ChannelSelection::Automatic`].
+    pub fn shared_server_channels(&self) -> &[u8] {
+        &self.shared_server
+    }
+
+    /// Returns IDs of client channels shared by events with [`ChannelSelection::Automatic`].
+    pub fn shared_client_channels(&self) -> &[u8] {
+        &self.shared_client
+    }
+}
+
+/// Returns ID of the least strict channel from `shared` that satisfies the requirements of `channel`.
+fn best_match(
+    channels: &[RepliconChannel],
+    shared: &[u8],
+    channel: &RepliconChannel,
+) -> Option<u8> {
+    shared
+        .iter()
+        .copied()
+        .filter(|&channel_id| {
+            let shared_channel = &channels[channel_id as usize];
+            shared_channel.priority == channel.priority
+                && shared_channel.kind.satisfies(channel.kind)
+        })
+        .min_by_key(|&channel_id| channels[channel_id as usize].kind as u8)
+}
+
+/// Channel assignment strategy for newly registered events.
+///
+/// See also [`RepliconChannels::set_channel_selection`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Reflect)]
+pub enum ChannelSelection {
+    /// Create a separate channel for each event.
+    #[default]
+    Dedicated,
+    /// Share channels between events.
+    ///
+    /// The channel requested during registration is treated as delivery requirements.
+    /// An existing shared channel with the same priority is picked if its [`ChannelKind`]
+    /// [satisfies](ChannelKind::satisfies) the requested kind, preferring the least strict one.
+    /// Otherwise, a new shared channel is created with the requested settings.
+    ///
+    /// Messages over shared channels carry a 1-byte header to identify the event.
+    /// Useful to keep the number of channels low for backends with per-channel costs.
+    Automatic,
 }
 
 /// Channel configuration.
-#[derive(Clone)]
+#[derive(Clone, Reflect)]
 pub struct RepliconChannel {
     /// Delivery guarantee.
     pub kind: ChannelKind,
@@ -149,12 +327,19 @@ pub struct RepliconChannel {
     ///
     /// If unset, the default value from [`RepliconChannels`] will be used.
     pub max_bytes: Option<usize>,
+
+    /// Priority hint for messaging backends that support it.
+    ///
+    /// Higher values mean more important. Not used by Replicon itself, except that
+    /// [`ChannelSelection::Automatic`] shares channels only between events with the same priority.
+    pub priority: u8,
 }
 
 /// Channel delivery guarantee.
 ///
-/// Can be automatically converted into [`RepliconChannel`] with zero resend time and default max bytes.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Can be automatically converted into [`RepliconChannel`] with zero resend time, default max bytes
+/// and zero priority.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Reflect)]
 pub enum ChannelKind {
     /// Unreliable and unordered.
     Unreliable,
@@ -164,12 +349,34 @@ pub enum ChannelKind {
     Ordered,
 }
 
+impl ChannelKind {
+    /// Returns `true` if messages are guaranteed to be delivered.
+    pub fn is_reliable(self) -> bool {
+        self != Self::Unreliable
+    }
+
+    /// Returns `true` if messages are received in the order they were sent.
+    pub fn is_ordered(self) -> bool {
+        self == Self::Ordered
+    }
+
+    /// Returns `true` if this kind can be used for messages that require `required` kind.
+    ///
+    /// Ordering can be stricter than required, but reliability should match exactly
+    /// since resending unreliable messages wastes bandwidth.
+    pub fn satisfies(self, required: Self) -> bool {
+        self.is_reliable() == required.is_reliable()
+            && (self.is_ordered() || !required.is_ordered())
+    }
+}
+
 impl From<ChannelKind> for RepliconChannel {
     fn from(value: ChannelKind) -> Self {
         Self {
             kind: value,
             resend_time: Duration::ZERO,
             max_bytes: None,
+            priority: 0,
         }
     }
 }
@@ -1,31 +1,53 @@
 use bevy::prelude::*;
 
-use super::{replicon_client::RepliconClient, replicon_server::RepliconServer};
+#[cfg(feature = "client")]
+use super::replicon_client::RepliconClient;
+#[cfg(feature = "server")]
+use super::replicon_server::RepliconServer;
 
 /// Returns `true` if the server is running.
+#[cfg(feature = "server")]
 pub fn server_running(server: Option<Res<RepliconServer>>) -> bool {
     server.is_some_and(|server| server.is_running())
 }
 
+/// Returns `true` if the server is running and paused.
+///
+/// See [`RepliconServer::pause`].
+#[cfg(feature = "server")]
+pub fn server_paused(server: Option<Res<RepliconServer>>) -> bool {
+    server.is_some_and(|server| server.is_running() && server.is_paused())
+}
+
 /// Returns `true` if there is no client or if the existing client is disconnected.
 ///
 /// Can be used instead of the regular [`server_running`] to seamlessly support
 /// singleplayer or listen-server mode (where server is also a player).
+#[cfg(feature = "client")]
 pub fn server_or_singleplayer(client: Option<Res<RepliconClient>>) -> bool {
     client.is_none_or(|client| client.is_disconnected())
 }
 
+/// Always returns `true` because there is no client without the `client` feature.
+#[cfg(not(feature = "client"))]
+pub fn server_or_singleplayer() -> bool {
+    true
+}
+
 /// Returns `true` when the client is connecting.
+#[cfg(feature = "client")]
 pub fn client_connecting(client: Option<Res<RepliconClient>>) -> bool {
     client.is_some_and(|client| client.is_connecting())
 }
 
 /// Returns `true` when the client is connected.
+#[cfg(feature = "client")]
 pub fn client_connected(client: Option<Res<RepliconClient>>) -> bool {
     client.is_some_and(|client| client.is_connected())
 }
 
 /// Returns `true` if the server stopped on this tick.
+#[cfg(feature = "server")]
 pub fn server_just_stopped(
     mut last_running: Local<bool>,
     server: Option<Res<RepliconServer>>,
@@ -38,6 +60,7 @@ pub fn server_just_stopped(
 }
 
 /// Returns `true` when the client just started connecting on this tick.
+#[cfg(feature = "client")]
 pub fn client_started_connecting(
     mut last_connecting: Local<bool>,
     client: Option<Res<RepliconClient>>,
@@ -50,6 +73,7 @@ pub fn client_started_connecting(
 }
 
 /// Returns `true` when the client is connected on this tick.
+#[cfg(feature = "client")]
 pub fn client_just_connected(
     mut last_connected: Local<bool>,
     client: Option<Res<RepliconClient>>,
@@ -62,6 +86,7 @@ pub fn client_just_connected(
 }
 
 /// Returns `true` when the client is disconnected on this tick.
+#[cfg(feature = "client")]
 pub fn client_just_disconnected(
     mut last_not_disconnected: Local<bool>,
     client: Option<Res<RepliconClient>>,
@@ -0,0 +1,140 @@
+use bevy::{ecs::component::ComponentId, prelude::*};
+
+use super::{
+    event::event_registry::EventRegistry,
+    replication::{
+        command_markers::{CommandMarkers, MarkerConfig},
+        replication_rules::ReplicationRules,
+    },
+};
+
+/// Read-only snapshot of the registered replication configuration.
+///
+/// Collected once after all plugins finish their initialization and
+/// registered for reflection, so inspector tools could display it.
+/// Modifying it has no effect on replication.
+///
+/// Channels are not included since [`RepliconChannels`](super::channels::RepliconChannels)
+/// can be reflected directly.
+#[derive(Resource, Reflect, Clone, Debug, Default)]
+#[reflect(Resource)]
+pub struct RepliconConfigInfo {
+    /// Replication rules in order of their priority.
+    pub rules: Vec<RuleInfo>,
+
+    /// Registered server events and triggers.
+    pub server_events: Vec<EventInfo>,
+
+    /// Registered client events and triggers.
+    pub client_events: Vec<EventInfo>,
+
+    /// Registered markers in order of their priority.
+    pub markers: Vec<MarkerInfo>,
+}
+
+impl RepliconConfigInfo {
+    /// Collects the configuration from the registered resources.
+    pub(super) fn new(world: &World) -> Self {
+        let rules = world
+            .resource::<ReplicationRules>()
+            .iter()
+            .map(|rule| RuleInfo {
+                priority: rule.priority,
+                components: rule
+                    .components
+                    .iter()
+                    .map(|&(component_id, _)| component_name(world, component_id))
+                    .collect(),
+            })
+            .collect();
+
+        let event_registry = world.resource::<EventRegistry>();
+        let server_events = event_registry
+            .iter_server_events()
+            .map(|event| EventInfo {
+                type_name: event.type_name().into(),
+                channel_id: event.channel_id(),
+                ordered_channel: event.ordered_channel(),
+                auto_channel: event.auto_channel(),
+                independent: event.is_independent(),
+            })
+            .collect();
+        let client_events = event_registry
+            .iter_client_events()
+            .map(|event| EventInfo {
+                type_name: event.type_name().into(),
+                channel_id: event.channel_id(),
+                ordered_channel: event.ordered_channel(),
+                auto_channel: event.auto_channel(),
+                independent: false,
+            })
+            .collect();
+
+        let markers = world
+            .resource::<CommandMarkers>()
+            .iter()
+            .map(|(component_id, &config)| MarkerInfo {
+                component: component_name(world, component_id),
+                config,
+            })
+            .collect();
+
+        Self {
+            rules,
+            server_events,
+            client_events,
+            markers,
+        }
+    }
+}
+
+fn component_name(world: &World, component_id: ComponentId) -> String {
+    world
+        .components()
+        .get_name(component_id)
+        .expect("registered components should be present in the world")
+        .into()
+}
+
+/// Replication rule from [`RepliconConfigInfo`].
+#[derive(Reflect, Clone, Debug)]
+pub struct RuleInfo {
+    /// Priority of the rule.
+    pub priority: usize,
+
+    /// Type names of the rule components.
+    pub components: Vec<String>,
+}
+
+/// Registered event from [`RepliconConfigInfo`].
+#[derive(Reflect, Clone, Debug)]
+pub struct EventInfo {
+    /// Type name of the event.
+    pub type_name: String,
+
+    /// ID of the channel associated with the event.
+    pub channel_id: u8,
+
+    /// Channel shared with other events to preserve order between them, if any.
+    pub ordered_channel: Option<u8>,
+
+    /// Channel shared with other events by [`ChannelSelection::Automatic`](crate::core::channels::ChannelSelection::Automatic), if any.
+    ///
+    /// If set, [`Self::channel_id`] is virtual and not created on the messaging backend.
+    pub auto_channel: Option<u8>,
+
+    /// Whether the event is applied immediately without waiting for replication.
+    ///
+    /// Always `false` for client events.
+    pub independent: bool,
+}
+
+/// Registered marker from [`RepliconConfigInfo`].
+#[derive(Reflect, Clone, Debug)]
+pub struct MarkerInfo {
+    /// Type name of the marker component.
+    pub component: String,
+
+    /// Marker configuration.
+    pub config: MarkerConfig,
+}
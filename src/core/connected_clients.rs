@@ -1,44 +1,77 @@
-use bevy::prelude::*;
+use std::time::Duration;
 
+use bevy::{prelude::*, utils::HashMap};
+
+use super::connection_stats::{StatsHistory, StatsSample};
 use crate::core::ClientId;
 
 /// Contains all connected clients.
 ///
-/// Inserted as resource by [`ServerPlugin`](crate::server::ServerPlugin).
+/// Each connected client is also represented by an entity with [`ConnectedClient`] component.
+/// The entity is spawned on connection and despawned on disconnect, so users can attach
+/// their own components to it and query client state with regular ECS patterns.
+/// This resource is a compatibility view over these entities: messaging backends update
+/// client statistics here, and they are copied to the components every frame.
+///
+/// Inserted as resource by [`ServerSessionPlugin`](crate::server::ServerSessionPlugin).
 ///
 /// See also [ReplicatedClients](super::replication::replicated_clients::ReplicatedClients).
 #[derive(Resource, Default, Debug, Deref)]
-pub struct ConnectedClients(Vec<ConnectedClient>);
+pub struct ConnectedClients {
+    #[deref]
+    clients: Vec<ConnectedClient>,
+
+    /// Entities that represent connected clients.
+    entities: HashMap<ClientId, Entity>,
+}
 
 impl ConnectedClients {
-    pub(crate) fn add(&mut self, client_id: ClientId) {
-        debug!("adding connected `{client_id:?}`");
+    pub(crate) fn add(&mut self, client_id: ClientId, entity: Entity) {
+        debug!("adding connected `{client_id:?}` as {entity}");
 
-        self.0.push(ConnectedClient::new(client_id));
+        self.clients.push(ConnectedClient::new(client_id));
+        self.entities.insert(client_id, entity);
     }
 
-    pub(crate) fn remove(&mut self, client_id: ClientId) {
+    /// Removes a client and returns its entity.
+    ///
+    /// Returns [`None`] if the client wasn't connected.
+    pub(crate) fn remove(&mut self, client_id: ClientId) -> Option<Entity> {
         debug!("removing disconnected `{client_id:?}`");
 
-        let index = self
-            .iter()
-            .position(|client| client.id == client_id)
-            .unwrap_or_else(|| panic!("{client_id:?} should be added before removal"));
-        self.0.remove(index);
+        let index = self.iter().position(|client| client.id == client_id)?;
+        self.clients.remove(index);
+        self.entities.remove(&client_id)
     }
 
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut ConnectedClient> {
-        self.0.iter_mut()
+        self.clients.iter_mut()
+    }
+
+    /// Returns the entity that represents a connected client.
+    pub fn entity(&self, client_id: ClientId) -> Option<Entity> {
+        self.entities.get(&client_id).copied()
+    }
+
+    /// Returns iterator over connected clients with their entities.
+    pub fn iter_entities(&self) -> impl Iterator<Item = (ClientId, Entity)> + '_ {
+        self.entities
+            .iter()
+            .map(|(&client_id, &entity)| (client_id, entity))
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Statistics of a connected client.
+///
+/// Stored inside [`ConnectedClients`] and as a component on the client entity.
+#[derive(Component, Debug, Clone)]
 pub struct ConnectedClient {
     id: ClientId,
     rtt: f64,
     packet_loss: f64,
     sent_bps: f64,
     received_bps: f64,
+    stats_history: StatsHistory,
 }
 
 impl ConnectedClient {
@@ -49,6 +82,7 @@ impl ConnectedClient {
             packet_loss: 0.0,
             sent_bps: 0.0,
             received_bps: 0.0,
+            stats_history: Default::default(),
         }
     }
 
@@ -128,4 +162,20 @@ impl ConnectedClient {
     pub fn set_received_bps(&mut self, received_bps: f64) {
         self.received_bps = received_bps;
     }
+
+    /// Returns the rolling history of the connection statistics.
+    pub fn stats_history(&self) -> &StatsHistory {
+        &self.stats_history
+    }
+
+    /// Adds the current statistics to the history.
+    pub(crate) fn sample_stats(&mut self, timestamp: Duration) {
+        self.stats_history.push(StatsSample {
+            timestamp,
+            rtt: self.rtt,
+            packet_loss: self.packet_loss,
+            sent_bps: self.sent_bps,
+            received_bps: self.received_bps,
+        });
+    }
 }
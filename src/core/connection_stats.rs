@@ -0,0 +1,172 @@
+use std::{collections::VecDeque, time::Duration};
+
+/// Rolling history of connection statistics.
+///
+/// Samples are taken every [`Self::SAMPLE_INTERVAL`] and kept for [`Self::DURATION`].
+/// Useful for adaptive systems that need stable inputs, like send rate or interpolation delay.
+///
+/// Available via [`RepliconClient::stats_history`](super::replicon_client::RepliconClient::stats_history)
+/// on client and [`ConnectedClient::stats_history`](super::connected_clients::ConnectedClient::stats_history)
+/// on server.
+#[derive(Clone, Debug, Default)]
+pub struct StatsHistory {
+    samples: VecDeque<StatsSample>,
+}
+
+impl StatsHistory {
+    /// How long samples are kept.
+    pub const DURATION: Duration = Duration::from_secs(10);
+
+    /// How often samples are taken.
+    pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Adds a sample and removes samples older than [`Self::DURATION`].
+    ///
+    /// Ignored if the last sample was taken less than [`Self::SAMPLE_INTERVAL`] ago.
+    pub(crate) fn push(&mut self, sample: StatsSample) {
+        if let Some(last) = self.samples.back() {
+            if sample.timestamp < last.timestamp + Self::SAMPLE_INTERVAL {
+                return;
+            }
+        }
+
+        while self
+            .samples
+            .front()
+            .is_some_and(|first| sample.timestamp - first.timestamp > Self::DURATION)
+        {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Returns an iterator over samples from the oldest to the newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &StatsSample> + ExactSizeIterator {
+        self.samples.iter()
+    }
+
+    /// Returns the number of samples.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns `true` if there are no samples.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Returns the round-trip time percentile in seconds.
+    ///
+    /// See [`Self::percentile`] for details.
+    pub fn rtt_percentile(&self, percentile: f64) -> f64 {
+        self.percentile(percentile, |sample| sample.rtt)
+    }
+
+    /// Returns the packet loss percentile.
+    ///
+    /// See [`Self::percentile`] for details.
+    pub fn packet_loss_percentile(&self, percentile: f64) -> f64 {
+        self.percentile(percentile, |sample| sample.packet_loss)
+    }
+
+    /// Returns the percentile of bytes sent per second.
+    ///
+    /// See [`Self::percentile`] for details.
+    pub fn sent_bps_percentile(&self, percentile: f64) -> f64 {
+        self.percentile(percentile, |sample| sample.sent_bps)
+    }
+
+    /// Returns the percentile of bytes received per second.
+    ///
+    /// See [`Self::percentile`] for details.
+    pub fn received_bps_percentile(&self, percentile: f64) -> f64 {
+        self.percentile(percentile, |sample| sample.received_bps)
+    }
+
+    /// Returns the value at the specified percentile using the nearest-rank method.
+    ///
+    /// `percentile` is a fraction from 0.0 to 1.0, e.g. 0.5 for the median and 0.95 for the 95th percentile.
+    /// Returns zero if there are no samples.
+    pub fn percentile(&self, percentile: f64, value: impl Fn(&StatsSample) -> f64) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+
+        let mut values: Vec<_> = self.samples.iter().map(value).collect();
+        values.sort_unstable_by(f64::total_cmp);
+
+        let index = (percentile.clamp(0.0, 1.0) * (values.len() - 1) as f64).round() as usize;
+        values[index]
+    }
+}
+
+/// A single sample of [`StatsHistory`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatsSample {
+    /// Time since the app startup when the sample was taken.
+    pub timestamp: Duration,
+
+    /// Round-trip time in seconds.
+    pub rtt: f64,
+
+    /// Packet loss %.
+    pub packet_loss: f64,
+
+    /// Bytes sent per second.
+    pub sent_bps: f64,
+
+    /// Bytes received per second.
+    pub received_bps: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sampling() {
+        let mut history = StatsHistory::default();
+        history.push(sample(Duration::ZERO, 0.0));
+        history.push(sample(StatsHistory::SAMPLE_INTERVAL / 2, 0.0));
+        assert_eq!(history.len(), 1, "samples should be throttled");
+
+        let count =
+            (StatsHistory::DURATION.as_millis() / StatsHistory::SAMPLE_INTERVAL.as_millis()) as u32;
+        for index in 1..=count * 2 {
+            history.push(sample(StatsHistory::SAMPLE_INTERVAL * index, 0.0));
+        }
+        assert_eq!(history.len(), count as usize + 1);
+    }
+
+    #[test]
+    fn percentiles() {
+        let mut history = StatsHistory::default();
+        assert_eq!(history.rtt_percentile(0.5), 0.0);
+
+        for index in 0..=10 {
+            history.push(sample(
+                StatsHistory::SAMPLE_INTERVAL * index,
+                (10 - index) as f64,
+            ));
+        }
+
+        assert_eq!(history.rtt_percentile(0.0), 0.0);
+        assert_eq!(history.rtt_percentile(0.5), 5.0);
+        assert_eq!(history.rtt_percentile(0.9), 9.0);
+        assert_eq!(history.rtt_percentile(1.0), 10.0);
+    }
+
+    fn sample(timestamp: Duration, rtt: f64) -> StatsSample {
+        StatsSample {
+            timestamp,
+            rtt,
+            packet_loss: 0.0,
+            sent_bps: 0.0,
+            received_bps: 0.0,
+        }
+    }
+}
@@ -3,43 +3,165 @@
 use bevy::prelude::*;
 use bytes::Bytes;
 
-use super::postcard_utils;
+use super::{protocol::entity, ClientId};
 
 /// Deserializes `entity` from compressed index and generation.
 ///
-/// For details see [`serialize_entity`].
+/// For details see [`entity::serialize`].
 pub fn deserialize_entity(message: &mut Bytes) -> postcard::Result<Entity> {
-    let flagged_index: u64 = postcard_utils::from_buf(message)?;
-    let has_generation = (flagged_index & 1) > 0;
-    let generation = if has_generation {
-        postcard_utils::from_buf::<u32, _>(message)? + 1
-    } else {
-        1u32
-    };
-
-    let bits = (generation as u64) << 32 | (flagged_index >> 1);
-
+    let bits = entity::deserialize(message)?;
     Ok(Entity::from_bits(bits))
 }
 
 /// Serializes `entity` by writing its index and generation as separate varints.
 ///
-/// The index is first prepended with a bit flag to indicate if the generation
-/// is serialized or not. It is not serialized if <= 1; note that generations are [`NonZeroU32`](std::num::NonZeroU32)
-/// and a value of zero is used in [`Option<Entity>`] to signify [`None`], so generation 1 is the first
-/// generation.
-///
 /// See also [`deserialize_entity`].
 pub fn serialize_entity(message: &mut Vec<u8>, entity: Entity) -> postcard::Result<()> {
-    let mut flagged_index = (entity.index() as u64) << 1;
-    let flag = entity.generation() > 1;
-    flagged_index |= flag as u64;
-
-    postcard_utils::to_extend_mut(&flagged_index, message)?;
-    if flag {
-        let generation = entity.generation() - 1;
-        postcard_utils::to_extend_mut(&generation, message)?;
+    entity::serialize(message, entity.to_bits())
+}
+
+/// Key for permutations of entity indices on the wire.
+///
+/// Hides the allocation order and the total number of entities on server from clients.
+/// Insert this resource on server to enable it. The key never leaves the server,
+/// clients see obfuscated entities as regular server entities.
+///
+/// Each client gets its own [`EntityPermutation`] derived from the key and its [`ClientId`],
+/// so clients can't match their entities against each other. Because of this, entities are
+/// written separately for each client instead of being shared between messages.
+///
+/// Applied to all entities written by replication, to targets of remote triggers and to
+/// entities inside mapped client events. Component and server event data is serialized once
+/// for all clients, so entities inside it can't be obfuscated and mapping them on client
+/// will fail. A warning is logged for types registered with
+/// [`AppRuleExt::replicate_mapped`](super::replication::replication_rules::AppRuleExt::replicate_mapped)
+/// or [`ServerEventAppExt::add_mapped_server_event`](super::event::server_event::ServerEventAppExt::add_mapped_server_event)
+/// when obfuscation is enabled.
+///
+/// The permutation is not a cryptographic cipher, it only prevents trivial inference.
+/// Obfuscated indices are spread over the whole `u32` range, so each entity takes up to 5 bytes
+/// instead of 1-2 and hidden entities are no longer packed into ranges.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{core::entity_serde::EntityObfuscation, prelude::*};
+///
+/// # let mut app = App::new();
+/// app.insert_resource(EntityObfuscation::random());
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntityObfuscation {
+    key: u64,
+}
+
+impl EntityObfuscation {
+    /// Creates obfuscation from a key.
+    pub fn new(key: u64) -> Self {
+        Self { key }
+    }
+
+    /// Creates obfuscation from a random key.
+    pub fn random() -> Self {
+        use std::hash::{BuildHasher, Hasher, RandomState};
+
+        Self::new(RandomState::new().build_hasher().finish())
+    }
+
+    /// Derives the permutation for entities sent to the client.
+    pub fn for_client(&self, client_id: ClientId) -> EntityPermutation {
+        let mut state = self.key ^ client_id.get();
+        EntityPermutation::new(splitmix64(&mut state))
+    }
+}
+
+/// Keyed permutation of entity indices for a single client.
+///
+/// See [`EntityObfuscation::for_client`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntityPermutation {
+    rounds: [ObfuscationRound; 2],
+}
+
+impl EntityPermutation {
+    /// Creates a permutation from a key.
+    pub fn new(key: u64) -> Self {
+        let mut state = key;
+        let rounds = [(); 2].map(|_| {
+            let value = splitmix64(&mut state);
+            let multiplier = (value >> 32) as u32 | 1;
+            ObfuscationRound {
+                xor: value as u32,
+                multiplier,
+                inverse: mod_inverse(multiplier),
+            }
+        });
+
+        Self { rounds }
     }
 
-    Ok(())
+    /// Returns the entity as it will be seen by the client.
+    ///
+    /// Generation is preserved.
+    pub fn obfuscate(&self, entity: Entity) -> Entity {
+        let mut index = entity.index();
+        for round in &self.rounds {
+            index ^= round.xor;
+            index = index.wrapping_mul(round.multiplier);
+            index ^= index >> 16;
+        }
+
+        with_index(entity, index)
+    }
+
+    /// Returns the server entity for an entity received from the client.
+    ///
+    /// Inverse of [`Self::obfuscate`].
+    pub fn reveal(&self, entity: Entity) -> Entity {
+        let mut index = entity.index();
+        for round in self.rounds.iter().rev() {
+            index ^= index >> 16;
+            index = index.wrapping_mul(round.inverse);
+            index ^= round.xor;
+        }
+
+        with_index(entity, index)
+    }
+}
+
+/// Names of types registered with entity mapping for data that is shared between clients.
+///
+/// Used to warn about them when [`EntityObfuscation`] is enabled.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub(crate) struct MappedTypes(Vec<&'static str>);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ObfuscationRound {
+    xor: u32,
+    multiplier: u32,
+    inverse: u32,
+}
+
+fn with_index(entity: Entity, index: u32) -> Entity {
+    let bits = (entity.generation() as u64) << 32 | index as u64;
+    Entity::from_bits(bits)
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Returns the multiplicative inverse of an odd number modulo 2^32.
+fn mod_inverse(value: u32) -> u32 {
+    // Each Newton iteration doubles the number of correct bits.
+    let mut inverse = value;
+    for _ in 0..4 {
+        inverse = inverse.wrapping_mul(2u32.wrapping_sub(value.wrapping_mul(inverse)));
+    }
+    inverse
 }
@@ -3,6 +3,8 @@ pub mod client_trigger;
 pub mod ctx;
 pub mod event_fns;
 pub(crate) mod event_registry;
+pub mod rate_limit;
 pub mod server_event;
 pub mod server_trigger;
+pub mod tagged_event;
 pub mod trigger;
@@ -1,24 +1,35 @@
 use std::any;
+#[cfg(feature = "client")]
+use std::collections::VecDeque;
+#[cfg(feature = "server")]
+use std::time::Duration;
 
+#[cfg(feature = "client")]
+use bevy::{ecs::event::EventCursor, ptr::Ptr};
 use bevy::{
-    ecs::{component::ComponentId, entity::MapEntities, event::EventCursor},
+    ecs::{component::ComponentId, entity::MapEntities},
     prelude::*,
-    ptr::{Ptr, PtrMut},
+    ptr::PtrMut,
 };
 use bytes::Bytes;
 use serde::{de::DeserializeOwned, Serialize};
 
+#[cfg(feature = "server")]
+use super::rate_limit::ClientEventBudgets;
 use super::{
     ctx::{ClientSendCtx, ServerReceiveCtx},
     event_fns::{EventDeserializeFn, EventFns, EventSerializeFn, UntypedEventFns},
     event_registry::EventRegistry,
+    rate_limit::ClientEventRateLimit,
+    tagged_event::{deserialize_tagged, serialize_tagged, TaggedEvent},
 };
+#[cfg(feature = "client")]
+use crate::core::replicon_client::RepliconClient;
+#[cfg(feature = "server")]
+use crate::core::replicon_server::RepliconServer;
 use crate::core::{
-    channels::{RepliconChannel, RepliconChannels},
-    postcard_utils,
-    replicon_client::RepliconClient,
-    replicon_server::RepliconServer,
-    ClientId,
+    channels::{ChannelKind, ChannelSelection, RepliconChannel, RepliconChannels},
+    postcard_utils, ClientId,
 };
 
 /// An extension trait for [`App`] for creating client events.
@@ -35,6 +46,9 @@ pub trait ClientEventAppExt {
     /// But be careful, since on listen servers all events `E` are drained,
     /// which could break other Bevy or third-party plugin systems that listen for `E`.
     ///
+    /// Registering `E` again as a client event replaces its functions and channel
+    /// configuration, but keeps the previously assigned channel ID.
+    ///
     /// See also [`Self::add_client_event_with`] and the [corresponding section](../index.html#from-client-to-server)
     /// from the quick start guide.
     fn add_client_event<E: Event + Serialize + DeserializeOwned>(
@@ -47,6 +61,9 @@ pub trait ClientEventAppExt {
     /// Same as [`Self::add_client_event`], but additionally maps client entities to server inside the event before sending.
     ///
     /// Always use it for events that contain entities.
+    /// Entities are mapped via [`ServerEntityMap`](crate::core::server_entity_map::ServerEntityMap),
+    /// so only replicated entities can be referenced.
+    /// Events that reference entities unknown to the server are discarded with an error.
     fn add_mapped_client_event<E: Event + Serialize + DeserializeOwned + MapEntities + Clone>(
         &mut self,
         channel: impl Into<RepliconChannel>,
@@ -54,7 +71,21 @@ pub trait ClientEventAppExt {
         self.add_client_event_with(
             channel,
             default_serialize_mapped::<E>,
-            default_deserialize::<E>,
+            default_deserialize_mapped::<E>,
+        )
+    }
+
+    /// Same as [`Self::add_client_event`], but encodes enum variants with stable tags.
+    ///
+    /// See [`TaggedEvent`] for details.
+    fn add_tagged_client_event<E: TaggedEvent>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        self.add_client_event_with(
+            channel,
+            serialize_tagged::<ClientSendCtx, E>,
+            deserialize_tagged::<ServerReceiveCtx, E>,
         )
     }
 
@@ -116,6 +147,109 @@ pub trait ClientEventAppExt {
         serialize: EventSerializeFn<ClientSendCtx, E>,
         deserialize: EventDeserializeFn<ServerReceiveCtx, E>,
     ) -> &mut Self;
+
+    /// Enables delivery receipts for a previously registered client event `E`.
+    ///
+    /// For each received event the server will automatically reply with a receipt
+    /// and [`EventDelivered<E>`] will be emitted on the client that sent it.
+    /// Useful for important events sent over unreliable channels.
+    ///
+    /// When [`RepliconClient`] is inactive, [`EventDelivered<E>`] will be emitted immediately
+    /// because the event is re-emitted locally.
+    ///
+    /// The client keeps copies of up to [`MAX_PENDING_RECEIPTS`] sent events to emit them with receipts.
+    /// If there are more unconfirmed events, the oldest ones are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `E` wasn't registered as a client event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_replicon::prelude::*;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins(RepliconPlugins);
+    /// app.add_client_event::<Purchase>(ChannelKind::Unreliable)
+    ///     .enable_delivery_receipts::<Purchase>();
+    ///
+    /// #[derive(Event, Serialize, Deserialize, Clone)]
+    /// struct Purchase {
+    ///     item_id: usize,
+    /// }
+    /// ```
+    fn enable_delivery_receipts<E: Event + Clone>(&mut self) -> &mut Self;
+
+    /// Limits how often the server accepts a previously registered client event `E` from each client.
+    ///
+    /// Excess events are handled according to [`ClientEventRateLimit::excess`].
+    /// Useful to bound the work the server does for hostile or buggy clients.
+    ///
+    /// Only affects the server, but can be called on both sides for simplicity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `E` wasn't registered as a client event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_replicon::{
+    ///     core::event::rate_limit::{ClientEventRateLimit, ExcessPolicy},
+    ///     prelude::*,
+    /// };
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins(RepliconPlugins);
+    /// app.add_client_event::<AimDirection>(ChannelKind::Unreliable)
+    ///     .set_client_event_rate_limit::<AimDirection>(
+    ///         ClientEventRateLimit::per_second(30).with_excess(ExcessPolicy::Coalesce),
+    ///     );
+    ///
+    /// #[derive(Event, Serialize, Deserialize)]
+    /// struct AimDirection(Vec2);
+    /// ```
+    fn set_client_event_rate_limit<E: Event>(&mut self, limit: ClientEventRateLimit) -> &mut Self;
+
+    /// Guarantees that `B` events are received on server before `A` events that were sent after them.
+    ///
+    /// By default, each event uses its own channel, so there is no delivery order between different
+    /// event types. After calling this method, both events will be routed through a shared
+    /// [`ChannelKind::Ordered`] channel with a small header that identifies the event type.
+    /// Calling it for events that are already ordered with other events joins their groups.
+    ///
+    /// Should be called on both client and server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `B` and `A` weren't registered as client events, if `B` was registered after `A`
+    /// or if they are already ordered with different events.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_replicon::prelude::*;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins(RepliconPlugins);
+    /// app.add_client_event::<SelectTeam>(ChannelKind::Ordered)
+    ///     .add_client_event::<Ready>(ChannelKind::Ordered)
+    ///     .order_client_events::<SelectTeam, Ready>();
+    ///
+    /// #[derive(Event, Deserialize, Serialize)]
+    /// struct SelectTeam(u8);
+    ///
+    /// #[derive(Event, Deserialize, Serialize)]
+    /// struct Ready;
+    /// ```
+    fn order_client_events<B: Event, A: Event>(&mut self) -> &mut Self;
 }
 
 impl ClientEventAppExt for App {
@@ -128,22 +262,224 @@ impl ClientEventAppExt for App {
         debug!("registering event `{}`", any::type_name::<E>());
 
         let event_fns = EventFns::new(serialize, deserialize);
+        let channel = channel.into();
+        if ClientEvent::reregister(self.world_mut(), channel.clone(), event_fns) {
+            return self;
+        }
+
         let event = ClientEvent::new(self, channel, event_fns);
         let mut event_registry = self.world_mut().resource_mut::<EventRegistry>();
         event_registry.register_client_event(event);
 
         self
     }
+
+    fn enable_delivery_receipts<E: Event + Clone>(&mut self) -> &mut Self {
+        let events_id = self
+            .world()
+            .components()
+            .resource_id::<Events<E>>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "event `{}` should be previously registered",
+                    any::type_name::<E>()
+                )
+            });
+
+        let channel_id = self
+            .world_mut()
+            .resource_mut::<RepliconChannels>()
+            .create_server_channel(ChannelKind::Unordered);
+
+        #[cfg(feature = "client")]
+        let delivered_id = {
+            self.add_event::<EventDelivered<E>>();
+            self.world()
+                .resource_id::<Events<EventDelivered<E>>>()
+                .unwrap()
+        };
+
+        let mut event_registry = self.world_mut().resource_mut::<EventRegistry>();
+        let event = event_registry
+            .iter_client_events_mut()
+            .find(|event| event.events_id() == events_id)
+            .unwrap_or_else(|| {
+                panic!(
+                    "event `{}` should be previously registered as a client event",
+                    any::type_name::<E>()
+                )
+            });
+
+        event.receipts = Some(EventReceipts {
+            channel_id,
+            #[cfg(feature = "client")]
+            delivered_id,
+        });
+
+        #[cfg(feature = "client")]
+        {
+            self.world_mut().resource_mut::<ClientEventReader<E>>().sent =
+                Some(SentEvents::new(E::clone));
+        }
+
+        self
+    }
+
+    fn set_client_event_rate_limit<E: Event>(&mut self, limit: ClientEventRateLimit) -> &mut Self {
+        let events_id = self
+            .world()
+            .components()
+            .resource_id::<Events<E>>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "event `{}` should be previously registered",
+                    any::type_name::<E>()
+                )
+            });
+
+        let mut event_registry = self.world_mut().resource_mut::<EventRegistry>();
+        let event = event_registry
+            .iter_client_events_mut()
+            .find(|event| event.events_id() == events_id)
+            .unwrap_or_else(|| {
+                panic!(
+                    "event `{}` should be previously registered as a client event",
+                    any::type_name::<E>()
+                )
+            });
+
+        event.rate_limit = Some(limit);
+
+        self
+    }
+
+    fn order_client_events<B: Event, A: Event>(&mut self) -> &mut Self {
+        let before_id = events_id::<B>(self.world());
+        let after_id = events_id::<A>(self.world());
+
+        let event_registry = self.world().resource::<EventRegistry>();
+        let before_index = client_event_index::<B>(event_registry, before_id);
+        let after_index = client_event_index::<A>(event_registry, after_id);
+        assert!(
+            before_index < after_index,
+            "event `{}` should be registered before `{}`",
+            any::type_name::<B>(),
+            any::type_name::<A>()
+        );
+
+        let (before_channel, after_channel) = {
+            let mut events = event_registry.iter_client_events();
+            let before = events.nth(before_index).unwrap();
+            let after = events.nth(after_index - before_index - 1).unwrap();
+            (before.ordered_channel, after.ordered_channel)
+        };
+
+        let ordered_channel = match (before_channel, after_channel) {
+            (Some(before_channel), Some(after_channel)) => {
+                assert_eq!(
+                    before_channel,
+                    after_channel,
+                    "events `{}` and `{}` are already ordered with other events",
+                    any::type_name::<B>(),
+                    any::type_name::<A>()
+                );
+                before_channel
+            }
+            (Some(channel_id), None) | (None, Some(channel_id)) => channel_id,
+            (None, None) => {
+                let channel_id = self
+                    .world_mut()
+                    .resource_mut::<RepliconChannels>()
+                    .create_client_channel(ChannelKind::Ordered);
+                self.world_mut()
+                    .resource_mut::<EventRegistry>()
+                    .add_ordered_client_channel(channel_id);
+                channel_id
+            }
+        };
+
+        debug!(
+            "ordering event `{}` after `{}` using channel {ordered_channel}",
+            any::type_name::<A>(),
+            any::type_name::<B>()
+        );
+
+        let mut event_registry = self.world_mut().resource_mut::<EventRegistry>();
+        for event in event_registry
+            .iter_client_events_mut()
+            .filter(|event| event.events_id() == before_id || event.events_id() == after_id)
+        {
+            event.ordered_channel = Some(ordered_channel);
+        }
+
+        self
+    }
+}
+
+/// Extension trait for sending client events.
+///
+/// Useful for command-driven flows and exclusive systems where [`EventWriter`] isn't available.
+///
+/// See also [`ClientEventAppExt`].
+#[cfg(feature = "client")]
+pub trait ClientEventExt {
+    /// Like [`EventWriter::send`], but for client events.
+    ///
+    /// Sends `E` to server or locally as [`FromClient<E>`] if
+    /// [`RepliconClient`] is inactive.
+    fn client_send<E: Event>(&mut self, event: E);
+}
+
+#[cfg(feature = "client")]
+impl ClientEventExt for Commands<'_, '_> {
+    fn client_send<E: Event>(&mut self, event: E) {
+        self.send_event(event);
+    }
+}
+
+#[cfg(feature = "client")]
+impl ClientEventExt for World {
+    fn client_send<E: Event>(&mut self, event: E) {
+        self.send_event(event);
+    }
+}
+
+fn events_id<E: Event>(world: &World) -> ComponentId {
+    world
+        .components()
+        .resource_id::<Events<E>>()
+        .unwrap_or_else(|| {
+            panic!(
+                "event `{}` should be previously registered",
+                any::type_name::<E>()
+            )
+        })
+}
+
+fn client_event_index<E: Event>(event_registry: &EventRegistry, events_id: ComponentId) -> usize {
+    event_registry
+        .iter_client_events()
+        .position(|event| event.events_id() == events_id)
+        .unwrap_or_else(|| {
+            panic!(
+                "event `{}` should be previously registered as a client event",
+                any::type_name::<E>()
+            )
+        })
 }
 
 /// Type-erased functions and metadata for a registered client event.
 ///
 /// Needed so events of different types can be processed together.
 pub(crate) struct ClientEvent {
+    /// Type name of the event.
+    type_name: &'static str,
+
     /// ID of [`Events<E>`] resource.
     events_id: ComponentId,
 
     /// ID of [`ClientEventReader<E>`] resource.
+    #[cfg(feature = "client")]
     reader_id: ComponentId,
 
     /// ID of [`Events<FromClient<E>>`] resource.
@@ -152,9 +488,36 @@ pub(crate) struct ClientEvent {
     /// Used channel.
     channel_id: u8,
 
+    /// Channel shared with other events to preserve order between them.
+    ///
+    /// If set, messages are sent over it with [`Self::channel_id`] as a header.
+    /// See [`ClientEventAppExt::order_client_events`].
+    ordered_channel: Option<u8>,
+
+    /// Channel shared with other events selected by [`ChannelSelection::Automatic`].
+    ///
+    /// If set, [`Self::channel_id`] is virtual and used only as a header.
+    /// Ignored if [`Self::ordered_channel`] is set.
+    auto_channel: Option<u8>,
+
+    /// Delivery receipts configuration, if enabled.
+    receipts: Option<EventReceipts>,
+
+    /// Maximum accepted rate of events from each client, if limited.
+    #[cfg_attr(not(feature = "server"), allow(dead_code))]
+    rate_limit: Option<ClientEventRateLimit>,
+
+    #[cfg(feature = "client")]
     send: SendFn,
+    #[cfg(feature = "server")]
     receive: ReceiveFn,
+    #[cfg(feature = "client")]
     resend_locally: ResendLocallyFn,
+    #[cfg(feature = "client")]
+    receive_receipts: ReceiveReceiptsFn,
+    #[cfg(feature = "client")]
+    deliver_locally: DeliverLocallyFn,
+    #[cfg(feature = "client")]
     reset: ResetFn,
     event_fns: UntypedEventFns,
 }
@@ -165,36 +528,109 @@ impl ClientEvent {
         channel: impl Into<RepliconChannel>,
         event_fns: EventFns<ClientSendCtx, ServerReceiveCtx, E, I>,
     ) -> Self {
-        let channel_id = app
-            .world_mut()
-            .resource_mut::<RepliconChannels>()
-            .create_client_channel(channel);
+        let mut channels = app.world_mut().resource_mut::<RepliconChannels>();
+        let (channel_id, auto_channel) = match channels.channel_selection() {
+            ChannelSelection::Dedicated => (channels.create_client_channel(channel), None),
+            ChannelSelection::Automatic => {
+                let auto_channel = channels.shared_client_channel(channel);
+                (channels.create_virtual_client_channel(), Some(auto_channel))
+            }
+        };
 
-        app.add_event::<E>()
-            .add_event::<FromClient<E>>()
-            .init_resource::<ClientEventReader<E>>();
+        app.add_event::<E>().add_event::<FromClient<E>>();
+        #[cfg(feature = "client")]
+        app.init_resource::<ClientEventReader<E>>();
 
         let events_id = app.world().resource_id::<Events<E>>().unwrap();
         let client_events_id = app.world().resource_id::<Events<FromClient<E>>>().unwrap();
+        #[cfg(feature = "client")]
         let reader_id = app.world().resource_id::<ClientEventReader<E>>().unwrap();
 
         Self {
+            type_name: any::type_name::<I>(),
             events_id,
+            #[cfg(feature = "client")]
             reader_id,
             client_events_id,
             channel_id,
+            ordered_channel: None,
+            auto_channel,
+            receipts: None,
+            rate_limit: None,
+            #[cfg(feature = "client")]
             send: Self::send_typed::<E, I>,
+            #[cfg(feature = "server")]
             receive: Self::receive_typed::<E, I>,
+            #[cfg(feature = "client")]
             resend_locally: Self::resend_locally_typed::<E>,
+            #[cfg(feature = "client")]
+            receive_receipts: Self::receive_receipts_typed::<E>,
+            #[cfg(feature = "client")]
+            deliver_locally: Self::deliver_locally_typed::<E>,
+            #[cfg(feature = "client")]
             reset: Self::reset_typed::<E>,
             event_fns: event_fns.into(),
         }
     }
 
+    /// Replaces functions and channel configuration of an already registered event.
+    ///
+    /// Returns `false` if the event wasn't registered.
+    pub(super) fn reregister<E: Event, I: 'static>(
+        world: &mut World,
+        channel: RepliconChannel,
+        event_fns: EventFns<ClientSendCtx, ServerReceiveCtx, E, I>,
+    ) -> bool {
+        let Some(events_id) = world.components().resource_id::<Events<E>>() else {
+            return false;
+        };
+
+        world.resource_scope(|world, mut event_registry: Mut<EventRegistry>| {
+            let Some(event) = event_registry
+                .iter_client_events_mut()
+                .find(|event| event.events_id == events_id)
+            else {
+                return false;
+            };
+
+            debug!("re-registering event `{}`", any::type_name::<I>());
+            let mut channels = world.resource_mut::<RepliconChannels>();
+            match &mut event.auto_channel {
+                Some(auto_channel) => *auto_channel = channels.shared_client_channel(channel),
+                None => *channels.client_channel_mut(event.channel_id) = channel,
+            }
+            event.event_fns = event_fns.into();
+
+            true
+        })
+    }
+
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub(crate) fn channel_id(&self) -> u8 {
+        self.channel_id
+    }
+
+    pub(crate) fn ordered_channel(&self) -> Option<u8> {
+        self.ordered_channel
+    }
+
+    pub(crate) fn auto_channel(&self) -> Option<u8> {
+        self.auto_channel
+    }
+
+    /// Returns the channel over which messages are sent with [`Self::channel_id`] as a header, if any.
+    fn shared_channel(&self) -> Option<u8> {
+        self.ordered_channel.or(self.auto_channel)
+    }
+
     pub(crate) fn events_id(&self) -> ComponentId {
         self.events_id
     }
 
+    #[cfg(feature = "client")]
     pub(crate) fn reader_id(&self) -> ComponentId {
         self.reader_id
     }
@@ -203,12 +639,19 @@ impl ClientEvent {
         self.client_events_id
     }
 
+    /// Returns ID of [`Events<EventDelivered<E>>`] resource if delivery receipts are enabled.
+    #[cfg(feature = "client")]
+    pub(crate) fn delivered_id(&self) -> Option<ComponentId> {
+        self.receipts.as_ref().map(|receipts| receipts.delivered_id)
+    }
+
     /// Sends an event to the server.
     ///
     /// # Safety
     ///
     /// The caller must ensure that `events` is [`Events<E>`], `reader` is [`ClientEventReader<E>`]
     /// and this instance was created for `E`.
+    #[cfg(feature = "client")]
     pub(crate) unsafe fn send(
         &self,
         ctx: &mut ClientSendCtx,
@@ -225,6 +668,7 @@ impl ClientEvent {
     ///
     /// The caller must ensure that `events` is [`Events<E>`], `reader` is [`ClientEventReader<E>`],
     /// and this instance was created for `E` and `I`.
+    #[cfg(feature = "client")]
     unsafe fn send_typed<E: Event, I: 'static>(
         &self,
         ctx: &mut ClientSendCtx,
@@ -233,13 +677,35 @@ impl ClientEvent {
         client: &mut RepliconClient,
     ) {
         let reader: &mut ClientEventReader<E> = reader.deref_mut();
-        for event in reader.read(events.deref()) {
+        for event in reader.cursor.read(events.deref()) {
             let mut message = Vec::new();
+            if self.shared_channel().is_some() {
+                message.push(self.channel_id);
+            }
+            let sequence = reader.sent.as_mut().map(|sent| sent.push(event));
+            if let Some(sequence) = sequence {
+                postcard_utils::to_extend_mut(&sequence, &mut message)
+                    .expect("event sequence should be serializable");
+            }
             self.serialize::<E, I>(ctx, event, &mut message)
                 .expect("client event should be serializable");
 
+            if !ctx.invalid_entities.is_empty() {
+                error!(
+                    "discarding event `{}` with unmapped entities `{:?}`, \
+                    make sure that the event references only replicated entities",
+                    any::type_name::<E>(),
+                    ctx.invalid_entities,
+                );
+                ctx.invalid_entities.clear();
+                if let (Some(sent), Some(sequence)) = (&mut reader.sent, sequence) {
+                    sent.remove(sequence);
+                }
+                continue;
+            }
+
             debug!("sending event `{}`", any::type_name::<E>());
-            client.send(self.channel_id, message);
+            client.send(self.shared_channel().unwrap_or(self.channel_id), message);
         }
     }
 
@@ -249,13 +715,16 @@ impl ClientEvent {
     ///
     /// The caller must ensure that `client_events` is [`Events<FromClient<E>>`]
     /// and this instance was created for `E`.
+    #[cfg(feature = "server")]
     pub(crate) unsafe fn receive(
         &self,
         ctx: &mut ServerReceiveCtx,
         client_events: PtrMut,
         server: &mut RepliconServer,
+        budgets: &mut ClientEventBudgets,
+        now: Duration,
     ) {
-        (self.receive)(self, ctx, client_events, server);
+        (self.receive)(self, ctx, client_events, server, budgets, now);
     }
 
     /// Typed version of [`Self::receive`].
@@ -264,36 +733,206 @@ impl ClientEvent {
     ///
     /// The caller must ensure that `client_events` is [`Events<FromClient<E>>`]
     /// and this instance was created for `E` and `I`.
+    #[cfg(feature = "server")]
     unsafe fn receive_typed<E: Event, I: 'static>(
         &self,
         ctx: &mut ServerReceiveCtx,
         client_events: PtrMut,
         server: &mut RepliconServer,
+        budgets: &mut ClientEventBudgets,
+        now: Duration,
     ) {
         let client_events: &mut Events<FromClient<E>> = client_events.deref_mut();
-        for (client_id, mut message) in server.receive(self.channel_id) {
-            match self.deserialize::<E, I>(ctx, &mut message) {
-                Ok(event) => {
+        let mut receipts = Vec::new();
+        if let Some(limit) = self.rate_limit {
+            for (client_id, message) in budgets.drain_coalesced(self.events_id, limit, now) {
+                self.apply_message::<E, I>(ctx, client_events, client_id, message, &mut receipts);
+            }
+        }
+
+        for (client_id, message) in server.receive(self.channel_id) {
+            if let Some(limit) = self.rate_limit {
+                if !budgets.accept(self.events_id, client_id, limit, now, &message) {
+                    debug!(
+                        "event `{}` from `{client_id:?}` exceeded the rate limit",
+                        any::type_name::<E>()
+                    );
+                    continue;
+                }
+            }
+
+            self.apply_message::<E, I>(ctx, client_events, client_id, message, &mut receipts);
+        }
+
+        if let Some(event_receipts) = &self.receipts {
+            for (client_id, sequence) in receipts {
+                let mut message = Vec::new();
+                postcard_utils::to_extend_mut(&sequence, &mut message)
+                    .expect("event sequence should be serializable");
+                server.send(client_id, event_receipts.channel_id, message);
+            }
+        }
+    }
+
+    /// Deserializes an event from a client message and emits it as [`FromClient<E>`].
+    ///
+    /// If receipts are enabled, the event sequence will be pushed into `receipts`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that this instance was created for `E` and `I`.
+    #[cfg(feature = "server")]
+    unsafe fn apply_message<E: Event, I: 'static>(
+        &self,
+        ctx: &mut ServerReceiveCtx,
+        client_events: &mut Events<FromClient<E>>,
+        client_id: ClientId,
+        mut message: Bytes,
+        receipts: &mut Vec<(ClientId, u64)>,
+    ) {
+        let sequence: Option<u64> = if self.receipts.is_some() {
+            match postcard_utils::from_buf(&mut message) {
+                Ok(sequence) => Some(sequence),
+                Err(e) => {
                     debug!(
-                        "applying event `{}` from `{client_id:?}`",
+                        "ignoring event `{}` from {client_id:?} with invalid sequence: {e}",
                         any::type_name::<E>()
                     );
-                    client_events.send(FromClient { client_id, event });
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+
+        ctx.set_client(client_id);
+        match self.deserialize::<E, I>(ctx, &mut message) {
+            Ok(event) => {
+                debug!(
+                    "applying event `{}` from `{client_id:?}`",
+                    any::type_name::<E>()
+                );
+                client_events.send(FromClient { client_id, event });
+                if let Some(sequence) = sequence {
+                    receipts.push((client_id, sequence));
+                }
+            }
+            Err(e) => debug!(
+                "ignoring event `{}` from {client_id:?} that failed to deserialize: {e}",
+                any::type_name::<E>()
+            ),
+        }
+    }
+
+    /// Receives delivery receipts from the server and emits [`EventDelivered<E>`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `reader` is [`ClientEventReader<E>`], `delivered` is [`Events<EventDelivered<E>>`]
+    /// and this instance was created for `E`.
+    #[cfg(feature = "client")]
+    pub(crate) unsafe fn receive_receipts(
+        &self,
+        reader: PtrMut,
+        delivered: PtrMut,
+        client: &mut RepliconClient,
+    ) {
+        (self.receive_receipts)(self, reader, delivered, client);
+    }
+
+    /// Typed version of [`Self::receive_receipts`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `reader` is [`ClientEventReader<E>`], `delivered` is [`Events<EventDelivered<E>>`]
+    /// and this instance was created for `E`.
+    #[cfg(feature = "client")]
+    unsafe fn receive_receipts_typed<E: Event>(
+        &self,
+        reader: PtrMut,
+        delivered: PtrMut,
+        client: &mut RepliconClient,
+    ) {
+        let reader: &mut ClientEventReader<E> = reader.deref_mut();
+        let delivered: &mut Events<EventDelivered<E>> = delivered.deref_mut();
+        let receipts = self
+            .receipts
+            .as_ref()
+            .expect("receipts should be enabled for the event");
+        let sent = reader
+            .sent
+            .as_mut()
+            .expect("sent events should be tracked with enabled receipts");
+
+        for mut message in client.receive(receipts.channel_id) {
+            match postcard_utils::from_buf(&mut message) {
+                Ok(sequence) => {
+                    if let Some(event) = sent.remove(sequence) {
+                        debug!(
+                            "received receipt for event `{}` with sequence {sequence}",
+                            any::type_name::<E>()
+                        );
+                        delivered.send(EventDelivered { sequence, event });
+                    } else {
+                        debug!(
+                            "ignoring receipt for unknown event `{}` with sequence {sequence}",
+                            any::type_name::<E>()
+                        );
+                    }
                 }
                 Err(e) => debug!(
-                    "ignoring event `{}` from {client_id:?} that failed to deserialize: {e}",
+                    "unable to deserialize receipt for event `{}`: {e}",
                     any::type_name::<E>()
                 ),
             }
         }
     }
 
+    /// Emits [`EventDelivered<E>`] for all new events `E` without sending them.
+    ///
+    /// Should be called before [`Self::resend_locally`] to read events before draining.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `events` is [`Events<E>`], `reader` is [`ClientEventReader<E>`],
+    /// `delivered` is [`Events<EventDelivered<E>>`] and this instance was created for `E`.
+    #[cfg(feature = "client")]
+    pub(crate) unsafe fn deliver_locally(&self, events: &Ptr, reader: PtrMut, delivered: PtrMut) {
+        (self.deliver_locally)(events, reader, delivered);
+    }
+
+    /// Typed version of [`Self::deliver_locally`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `events` is [`Events<E>`], `reader` is [`ClientEventReader<E>`]
+    /// and `delivered` is [`Events<EventDelivered<E>>`].
+    #[cfg(feature = "client")]
+    unsafe fn deliver_locally_typed<E: Event>(events: &Ptr, reader: PtrMut, delivered: PtrMut) {
+        let reader: &mut ClientEventReader<E> = reader.deref_mut();
+        let delivered: &mut Events<EventDelivered<E>> = delivered.deref_mut();
+        let sent = reader
+            .sent
+            .as_mut()
+            .expect("sent events should be tracked with enabled receipts");
+
+        for event in reader.cursor.read(events.deref()) {
+            let sequence = sent.next_sequence;
+            sent.next_sequence += 1;
+            delivered.send(EventDelivered {
+                sequence,
+                event: (sent.clone)(event),
+            });
+        }
+    }
+
     /// Drains events `E` and re-emits them as [`FromClient<E>`].
     ///
     /// # Safety
     ///
     /// The caller must ensure that `events` is [`Events<E>`], `client_events` is [`Events<FromClient<E>>`]
     /// and this instance was created for `E`.
+    #[cfg(feature = "client")]
     pub(crate) unsafe fn resend_locally(&self, client_events: PtrMut, events: PtrMut) {
         (self.resend_locally)(client_events, events);
     }
@@ -303,6 +942,7 @@ impl ClientEvent {
     /// # Safety
     ///
     /// The caller must ensure that `events` is [`Events<E>`] and `server_events` is [`Events<ToClients<E>>`].
+    #[cfg(feature = "client")]
     unsafe fn resend_locally_typed<E: Event>(server_events: PtrMut, events: PtrMut) {
         let client_events: &mut Events<FromClient<E>> = server_events.deref_mut();
         let events: &mut Events<E> = events.deref_mut();
@@ -319,27 +959,34 @@ impl ClientEvent {
         }
     }
 
-    /// Drains all events.
+    /// Drains all events and discards events waiting for receipts.
     ///
     /// # Safety
     ///
-    /// The caller must ensure that `events` is [`Events<E>`]
+    /// The caller must ensure that `events` is [`Events<E>`], `reader` is [`ClientEventReader<E>`]
     /// and this instance was created for `E`.
-    pub(crate) unsafe fn reset(&self, events: PtrMut) {
-        (self.reset)(events);
+    #[cfg(feature = "client")]
+    pub(crate) unsafe fn reset(&self, events: PtrMut, reader: PtrMut) {
+        (self.reset)(events, reader);
     }
 
     /// Typed version of [`ClientEvent::reset`].
     ///
     /// # Safety
     ///
-    /// The caller must ensure that `events` is [`Events<E>`].
-    unsafe fn reset_typed<E: Event>(events: PtrMut) {
+    /// The caller must ensure that `events` is [`Events<E>`] and `reader` is [`ClientEventReader<E>`].
+    #[cfg(feature = "client")]
+    unsafe fn reset_typed<E: Event>(events: PtrMut, reader: PtrMut) {
         let events: &mut Events<E> = events.deref_mut();
         let drained_count = events.drain().count();
         if drained_count > 0 {
             warn!("discarded {drained_count} events due to a disconnect");
         }
+
+        let reader: &mut ClientEventReader<E> = reader.deref_mut();
+        if let Some(sent) = &mut reader.sent {
+            sent.pending.clear();
+        }
     }
 
     /// Serializes an event into a message.
@@ -347,6 +994,7 @@ impl ClientEvent {
     /// # Safety
     ///
     /// The caller must ensure that this instance was created for `E` and `I`.
+    #[cfg(feature = "client")]
     unsafe fn serialize<E: 'static, I: 'static>(
         &self,
         ctx: &mut ClientSendCtx,
@@ -363,6 +1011,7 @@ impl ClientEvent {
     /// # Safety
     ///
     /// The caller must ensure that this instance was created for `E` and `I`.
+    #[cfg(feature = "server")]
     unsafe fn deserialize<E: 'static, I: 'static>(
         &self,
         ctx: &mut ServerReceiveCtx,
@@ -375,27 +1024,121 @@ impl ClientEvent {
 }
 
 /// Signature of client event sending functions.
+#[cfg(feature = "client")]
 type SendFn = unsafe fn(&ClientEvent, &mut ClientSendCtx, &Ptr, PtrMut, &mut RepliconClient);
 
 /// Signature of client event receiving functions.
-type ReceiveFn = unsafe fn(&ClientEvent, &mut ServerReceiveCtx, PtrMut, &mut RepliconServer);
+#[cfg(feature = "server")]
+type ReceiveFn = unsafe fn(
+    &ClientEvent,
+    &mut ServerReceiveCtx,
+    PtrMut,
+    &mut RepliconServer,
+    &mut ClientEventBudgets,
+    Duration,
+);
 
 /// Signature of client event resending functions.
+#[cfg(feature = "client")]
 type ResendLocallyFn = unsafe fn(PtrMut, PtrMut);
 
+/// Signature of client event receipts receiving functions.
+#[cfg(feature = "client")]
+type ReceiveReceiptsFn = unsafe fn(&ClientEvent, PtrMut, PtrMut, &mut RepliconClient);
+
+/// Signature of client event local delivery functions.
+#[cfg(feature = "client")]
+type DeliverLocallyFn = unsafe fn(&Ptr, PtrMut, PtrMut);
+
 /// Signature of client event reset functions.
-type ResetFn = unsafe fn(PtrMut);
+#[cfg(feature = "client")]
+type ResetFn = unsafe fn(PtrMut, PtrMut);
+
+/// Delivery receipts configuration for a client event.
+struct EventReceipts {
+    /// Server channel for receipts.
+    channel_id: u8,
+
+    /// ID of [`Events<EventDelivered<E>>`] resource.
+    #[cfg(feature = "client")]
+    delivered_id: ComponentId,
+}
 
 /// Tracks read events for [`ClientEventPlugin::send`].
 ///
 /// Unlike with server events, we don't always drain all events in [`ClientEventPlugin::resend_locally`].
-#[derive(Resource, Deref, DerefMut)]
-struct ClientEventReader<E: Event>(EventCursor<E>);
+#[cfg(feature = "client")]
+#[derive(Resource)]
+struct ClientEventReader<E: Event> {
+    cursor: EventCursor<E>,
+
+    /// Events waiting for receipts, present only if receipts are enabled.
+    sent: Option<SentEvents<E>>,
+}
 
+#[cfg(feature = "client")]
 impl<E: Event> FromWorld for ClientEventReader<E> {
     fn from_world(world: &mut World) -> Self {
         let events = world.resource::<Events<E>>();
-        Self(events.get_cursor())
+        Self {
+            cursor: events.get_cursor(),
+            sent: None,
+        }
+    }
+}
+
+/// Maximum number of sent events waiting for delivery receipts.
+///
+/// See also [`ClientEventAppExt::enable_delivery_receipts`].
+#[cfg(feature = "client")]
+pub const MAX_PENDING_RECEIPTS: usize = 256;
+
+/// Copies of sent events that wait for receipts.
+#[cfg(feature = "client")]
+struct SentEvents<E> {
+    /// Sequence that will be assigned to the next sent event.
+    next_sequence: u64,
+
+    /// Sent events with their sequences in the order of sending.
+    pending: VecDeque<(u64, E)>,
+
+    /// Cloning function for `E`.
+    ///
+    /// Stored as a pointer to avoid requiring [`Clone`] for all client events.
+    clone: fn(&E) -> E,
+}
+
+#[cfg(feature = "client")]
+impl<E> SentEvents<E> {
+    fn new(clone: fn(&E) -> E) -> Self {
+        Self {
+            next_sequence: 0,
+            pending: Default::default(),
+            clone,
+        }
+    }
+
+    /// Stores a copy of the event and returns its assigned sequence.
+    fn push(&mut self, event: &E) -> u64 {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        if self.pending.len() == MAX_PENDING_RECEIPTS {
+            let (sequence, _) = self.pending.pop_front().unwrap();
+            debug!("discarding event with sequence {sequence} that didn't receive a receipt");
+        }
+        self.pending.push_back((sequence, (self.clone)(event)));
+
+        sequence
+    }
+
+    /// Removes the event with the given sequence.
+    fn remove(&mut self, sequence: u64) -> Option<E> {
+        let index = self
+            .pending
+            .iter()
+            .position(|&(pending_sequence, _)| pending_sequence == sequence)?;
+        self.pending.remove(index).map(|(_, event)| event)
     }
 }
 
@@ -408,6 +1151,19 @@ pub struct FromClient<T> {
     pub event: T,
 }
 
+/// An event indicating that the server received a client event `T`.
+///
+/// Emitted only on client for events with enabled receipts.
+/// See also [`ClientEventAppExt::enable_delivery_receipts`].
+#[cfg(feature = "client")]
+#[derive(Clone, Copy, Event, Deref, DerefMut)]
+pub struct EventDelivered<T> {
+    /// Sequence assigned to the event on sending.
+    pub sequence: u64,
+    #[deref]
+    pub event: T,
+}
+
 /// Default event serialization function.
 pub fn default_serialize<E: Event + Serialize>(
     _ctx: &mut ClientSendCtx,
@@ -435,3 +1191,16 @@ pub fn default_deserialize<E: Event + DeserializeOwned>(
 ) -> postcard::Result<E> {
     postcard_utils::from_buf(message)
 }
+
+/// Like [`default_deserialize`], but also maps entities.
+///
+/// Reveals entities if [`EntityObfuscation`](crate::core::entity_serde::EntityObfuscation) is enabled.
+pub fn default_deserialize_mapped<E: Event + DeserializeOwned + MapEntities>(
+    ctx: &mut ServerReceiveCtx,
+    message: &mut Bytes,
+) -> postcard::Result<E> {
+    let mut event: E = postcard_utils::from_buf(message)?;
+    event.map_entities(ctx);
+
+    Ok(event)
+}
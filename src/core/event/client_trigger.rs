@@ -1,15 +1,21 @@
 use std::any;
 
-use bevy::{ecs::entity::MapEntities, prelude::*, ptr::PtrMut};
+#[cfg(feature = "server")]
+use bevy::ptr::PtrMut;
+use bevy::{ecs::entity::MapEntities, prelude::*};
 use bytes::Bytes;
 use serde::{de::DeserializeOwned, Serialize};
 
+#[cfg(feature = "server")]
+use super::client_event::FromClient;
+#[cfg(feature = "client")]
+use super::trigger::RemoteTargets;
 use super::{
-    client_event::{self, ClientEvent, FromClient},
+    client_event::{self, ClientEvent},
     ctx::{ClientSendCtx, ServerReceiveCtx},
     event_fns::{EventDeserializeFn, EventFns, EventSerializeFn},
     event_registry::EventRegistry,
-    trigger::{RemoteTargets, RemoteTrigger},
+    trigger::RemoteTrigger,
 };
 use crate::core::{channels::RepliconChannel, entity_serde, postcard_utils};
 
@@ -47,7 +53,7 @@ pub trait ClientTriggerAppExt {
         self.add_client_trigger_with(
             channel,
             client_event::default_serialize_mapped::<E>,
-            client_event::default_deserialize::<E>,
+            client_event::default_deserialize_mapped::<E>,
         )
     }
 
@@ -73,6 +79,10 @@ impl ClientTriggerAppExt for App {
 
         let event_fns = EventFns::new(serialize, deserialize)
             .with_outer(trigger_serialize, trigger_deserialize);
+        let channel = channel.into();
+        if ClientEvent::reregister(self.world_mut(), channel.clone(), event_fns) {
+            return self;
+        }
 
         let trigger = ClientTrigger::new(self, channel, event_fns);
         let mut event_registry = self.world_mut().resource_mut::<EventRegistry>();
@@ -85,6 +95,7 @@ impl ClientTriggerAppExt for App {
 /// Small abstraction on top of [`ClientEvent`] that stores a function to trigger them.
 pub(crate) struct ClientTrigger {
     event: ClientEvent,
+    #[cfg(feature = "server")]
     trigger: TriggerFn,
 }
 
@@ -96,10 +107,12 @@ impl ClientTrigger {
     ) -> Self {
         Self {
             event: ClientEvent::new(app, channel, event_fns),
+            #[cfg(feature = "server")]
             trigger: Self::trigger_typed::<E>,
         }
     }
 
+    #[cfg(feature = "server")]
     pub(crate) fn trigger(&self, commands: &mut Commands, events: PtrMut) {
         unsafe {
             (self.trigger)(commands, events);
@@ -112,6 +125,7 @@ impl ClientTrigger {
     ///
     /// The caller must ensure that `client_events` is [`Events<FromClient<RemoteTrigger<E>>>`]
     /// and this instance was created for `E`.
+    #[cfg(feature = "server")]
     unsafe fn trigger_typed<E: Event>(commands: &mut Commands, client_events: PtrMut) {
         let client_events: &mut Events<FromClient<RemoteTrigger<E>>> = client_events.deref_mut();
         for FromClient { client_id, event } in client_events.drain() {
@@ -132,9 +146,14 @@ impl ClientTrigger {
     pub(crate) fn event(&self) -> &ClientEvent {
         &self.event
     }
+
+    pub(super) fn event_mut(&mut self) -> &mut ClientEvent {
+        &mut self.event
+    }
 }
 
 /// Signature of client trigger functions.
+#[cfg(feature = "server")]
 type TriggerFn = unsafe fn(&mut Commands, PtrMut);
 
 /// Serializes targets for [`RemoteTrigger`], maps them and delegates the event
@@ -169,7 +188,7 @@ fn trigger_deserialize<'a, E>(
     let mut targets = Vec::with_capacity(len);
     for _ in 0..len {
         let entity = entity_serde::deserialize_entity(message)?;
-        targets.push(entity);
+        targets.push(ctx.map_entity(entity));
     }
 
     let event = (deserialize)(ctx, message)?;
@@ -180,6 +199,7 @@ fn trigger_deserialize<'a, E>(
 /// Extension trait for triggering client events.
 ///
 /// See also [`ClientTriggerAppExt`].
+#[cfg(feature = "client")]
 pub trait ClientTriggerExt {
     /// Like [`Commands::trigger`], but triggers [`FromClient`] on server and locally
     /// if [`RepliconClient`](crate::core::replicon_client::RepliconClient) is inactive.
@@ -187,9 +207,12 @@ pub trait ClientTriggerExt {
 
     /// Like [`Self::client_trigger`], but allows you to specify target entities, similar to
     /// [`Commands::trigger_targets`].
+    ///
+    /// Targets are always mapped to server entities, triggers with targets unknown to the server are discarded with an error.
     fn client_trigger_targets(&mut self, event: impl Event, targets: impl RemoteTargets);
 }
 
+#[cfg(feature = "client")]
 impl ClientTriggerExt for Commands<'_, '_> {
     fn client_trigger(&mut self, event: impl Event) {
         self.client_trigger_targets(event, []);
@@ -203,6 +226,7 @@ impl ClientTriggerExt for Commands<'_, '_> {
     }
 }
 
+#[cfg(feature = "client")]
 impl ClientTriggerExt for World {
     fn client_trigger(&mut self, event: impl Event) {
         self.client_trigger_targets(event, []);
@@ -1,6 +1,8 @@
 use bevy::{prelude::*, reflect::TypeRegistry};
 
-use crate::core::server_entity_map::ServerEntityMap;
+#[cfg(feature = "server")]
+use crate::core::{entity_serde::EntityObfuscation, ClientId};
+use crate::core::{entity_serde::EntityPermutation, server_entity_map::ServerEntityMap};
 
 /// Event sending context for client.
 #[non_exhaustive]
@@ -10,15 +12,21 @@ pub struct ClientSendCtx<'a> {
 
     /// Maps server entities to client entities and vice versa.
     pub entity_map: &'a ServerEntityMap,
+
+    /// Entities that couldn't be mapped by [`EntityMapper::map_entity`].
+    ///
+    /// We needed it because [`EntityMapper`] doesn't provide a way to handle errors.
+    pub(crate) invalid_entities: Vec<Entity>,
 }
 
 impl EntityMapper for ClientSendCtx<'_> {
     fn map_entity(&mut self, entity: Entity) -> Entity {
-        *self
-            .entity_map
-            .to_server()
-            .get(&entity)
-            .unwrap_or_else(|| panic!("client {entity:?} should have a mapping"))
+        if let Some(mapped_entity) = self.entity_map.to_server().get(&entity) {
+            *mapped_entity
+        } else {
+            self.invalid_entities.push(entity);
+            Entity::PLACEHOLDER
+        }
     }
 }
 
@@ -27,6 +35,33 @@ impl EntityMapper for ClientSendCtx<'_> {
 pub struct ServerReceiveCtx<'a> {
     /// Registry of reflected types.
     pub registry: &'a TypeRegistry,
+
+    /// Permutation for server entities of the sending client if [`EntityObfuscation`] is enabled.
+    pub entity_permutation: Option<EntityPermutation>,
+
+    /// Obfuscation from which [`Self::entity_permutation`] is derived for each client.
+    #[cfg(feature = "server")]
+    pub(crate) obfuscation: Option<EntityObfuscation>,
+}
+
+impl ServerReceiveCtx<'_> {
+    /// Updates [`Self::entity_permutation`] for a message from the client.
+    #[cfg(feature = "server")]
+    pub(crate) fn set_client(&mut self, client_id: ClientId) {
+        self.entity_permutation = self
+            .obfuscation
+            .map(|obfuscation| obfuscation.for_client(client_id));
+    }
+}
+
+/// Reveals server entities received from a client if [`EntityObfuscation`] is enabled.
+impl EntityMapper for ServerReceiveCtx<'_> {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        match self.entity_permutation {
+            Some(permutation) => permutation.reveal(entity),
+            None => entity,
+        }
+    }
 }
 
 /// Event sending context for server.
@@ -34,6 +69,35 @@ pub struct ServerReceiveCtx<'a> {
 pub struct ServerSendCtx<'a> {
     /// Registry of reflected types.
     pub registry: &'a TypeRegistry,
+
+    /// Permutation for server entities of the receiving client if [`EntityObfuscation`] is enabled.
+    ///
+    /// Events are serialized for each client separately only when obfuscation is enabled.
+    pub entity_permutation: Option<EntityPermutation>,
+
+    /// Obfuscation from which [`Self::entity_permutation`] is derived for each client.
+    #[cfg(feature = "server")]
+    pub(crate) obfuscation: Option<EntityObfuscation>,
+}
+
+impl ServerSendCtx<'_> {
+    /// Updates [`Self::entity_permutation`] for a message to the client.
+    #[cfg(feature = "server")]
+    pub(crate) fn set_client(&mut self, client_id: ClientId) {
+        self.entity_permutation = self
+            .obfuscation
+            .map(|obfuscation| obfuscation.for_client(client_id));
+    }
+}
+
+/// Obfuscates server entities sent to clients if [`EntityObfuscation`] is enabled.
+impl EntityMapper for ServerSendCtx<'_> {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        match self.entity_permutation {
+            Some(permutation) => permutation.obfuscate(entity),
+            None => entity,
+        }
+    }
 }
 
 /// Event receiving context for client.
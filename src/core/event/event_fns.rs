@@ -123,6 +123,14 @@ pub(super) struct EventFns<S, D, E, I = E> {
     deserialize: EventDeserializeFn<D, I>,
 }
 
+impl<S, D, E, I> Clone for EventFns<S, D, E, I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, D, E, I> Copy for EventFns<S, D, E, I> {}
+
 impl<S, D, E> EventFns<S, D, E, E> {
     /// Creates a new instance with default outer functions.
     pub(super) fn new(
@@ -15,6 +15,12 @@ pub(crate) struct EventRegistry {
     client_events: Vec<ClientEvent>,
     server_triggers: Vec<ServerTrigger>,
     client_triggers: Vec<ClientTrigger>,
+
+    /// Server channels shared by ordered server events.
+    ordered_server_channels: Vec<u8>,
+
+    /// Client channels shared by ordered client events.
+    ordered_client_channels: Vec<u8>,
 }
 
 impl EventRegistry {
@@ -34,6 +40,22 @@ impl EventRegistry {
         self.client_triggers.push(trigger);
     }
 
+    pub(super) fn add_ordered_server_channel(&mut self, channel_id: u8) {
+        self.ordered_server_channels.push(channel_id);
+    }
+
+    pub(super) fn add_ordered_client_channel(&mut self, channel_id: u8) {
+        self.ordered_client_channels.push(channel_id);
+    }
+
+    pub(crate) fn ordered_server_channels(&self) -> &[u8] {
+        &self.ordered_server_channels
+    }
+
+    pub(crate) fn ordered_client_channels(&self) -> &[u8] {
+        &self.ordered_client_channels
+    }
+
     pub(crate) fn iter_server_events_mut(&mut self) -> impl Iterator<Item = &mut ServerEvent> {
         self.server_events.iter_mut().chain(
             self.server_triggers
@@ -42,6 +64,14 @@ impl EventRegistry {
         )
     }
 
+    pub(crate) fn iter_client_events_mut(&mut self) -> impl Iterator<Item = &mut ClientEvent> {
+        self.client_events.iter_mut().chain(
+            self.client_triggers
+                .iter_mut()
+                .map(|trigger| trigger.event_mut()),
+        )
+    }
+
     pub(crate) fn iter_server_events(&self) -> impl Iterator<Item = &ServerEvent> {
         self.server_events
             .iter()
@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+use bevy::{ecs::component::ComponentId, prelude::*, utils::HashMap};
+use bytes::Bytes;
+
+use crate::core::ClientId;
+
+/// Limits how many client events of a specific type the server accepts from each client.
+///
+/// See also [`ClientEventAppExt::set_client_event_rate_limit`](super::client_event::ClientEventAppExt::set_client_event_rate_limit).
+#[derive(Clone, Copy, Debug)]
+pub struct ClientEventRateLimit {
+    /// Maximum number of accepted events from a single client within [`Self::period`].
+    pub max_events: u32,
+
+    /// Duration of the window in which events are counted.
+    pub period: Duration,
+
+    /// What to do with events that exceed the limit.
+    pub excess: ExcessPolicy,
+}
+
+impl ClientEventRateLimit {
+    /// Creates a limit of `max_events` per second that drops the excess.
+    pub fn per_second(max_events: u32) -> Self {
+        Self {
+            max_events,
+            period: Duration::from_secs(1),
+            excess: ExcessPolicy::Drop,
+        }
+    }
+
+    /// Sets the policy for events that exceed the limit.
+    pub fn with_excess(mut self, excess: ExcessPolicy) -> Self {
+        self.excess = excess;
+        self
+    }
+}
+
+/// Policy for client events that exceed [`ClientEventRateLimit`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExcessPolicy {
+    /// Discard excess events.
+    #[default]
+    Drop,
+
+    /// Keep only the latest excess event and accept it when the next window starts.
+    ///
+    /// Useful for state-like events, such as inputs, where only the last value matters.
+    Coalesce,
+}
+
+/// Per-client state for rate-limited client events.
+///
+/// Updated on server during receiving.
+#[derive(Resource, Default)]
+pub(crate) struct ClientEventBudgets(HashMap<ComponentId, HashMap<ClientId, EventBudget>>);
+
+impl ClientEventBudgets {
+    /// Returns `true` if an event from the client fits into the limit and counts it.
+    ///
+    /// If the event doesn't fit and the policy is [`ExcessPolicy::Coalesce`],
+    /// the message will be stored to be returned from [`Self::drain_coalesced`] later.
+    pub(crate) fn accept(
+        &mut self,
+        events_id: ComponentId,
+        client_id: ClientId,
+        limit: ClientEventRateLimit,
+        now: Duration,
+        message: &Bytes,
+    ) -> bool {
+        let budget = self
+            .0
+            .entry(events_id)
+            .or_default()
+            .entry(client_id)
+            .or_default();
+
+        budget.refresh(limit, now);
+        if budget.count < limit.max_events {
+            budget.count += 1;
+            return true;
+        }
+
+        if limit.excess == ExcessPolicy::Coalesce {
+            budget.coalesced = Some(message.clone());
+        }
+
+        false
+    }
+
+    /// Returns coalesced messages for clients whose window has been restarted.
+    pub(crate) fn drain_coalesced(
+        &mut self,
+        events_id: ComponentId,
+        limit: ClientEventRateLimit,
+        now: Duration,
+    ) -> impl Iterator<Item = (ClientId, Bytes)> + '_ {
+        self.0
+            .get_mut(&events_id)
+            .into_iter()
+            .flat_map(|budgets| budgets.iter_mut())
+            .filter_map(move |(&client_id, budget)| {
+                budget.coalesced.as_ref()?;
+                budget.refresh(limit, now);
+                if budget.count >= limit.max_events {
+                    return None;
+                }
+
+                budget.count += 1;
+                budget.coalesced.take().map(|message| (client_id, message))
+            })
+    }
+
+    /// Removes all state for a client.
+    pub(crate) fn remove_client(&mut self, client_id: ClientId) {
+        for budgets in self.0.values_mut() {
+            budgets.remove(&client_id);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+#[derive(Default)]
+struct EventBudget {
+    /// Start of the current window.
+    window_start: Duration,
+
+    /// Number of accepted events in the current window.
+    count: u32,
+
+    /// Latest excess message for [`ExcessPolicy::Coalesce`].
+    coalesced: Option<Bytes>,
+}
+
+impl EventBudget {
+    /// Restarts the window if it's over.
+    fn refresh(&mut self, limit: ClientEventRateLimit, now: Duration) {
+        if now.saturating_sub(self.window_start) >= limit.period {
+            self.window_start = now;
+            self.count = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop() {
+        let mut budgets = ClientEventBudgets::default();
+        let limit = ClientEventRateLimit::per_second(2);
+        let events_id = ComponentId::new(0);
+        let client_id = ClientId::new(1);
+        let message = Bytes::new();
+
+        let now = Duration::from_secs(1);
+        assert!(budgets.accept(events_id, client_id, limit, now, &message));
+        assert!(budgets.accept(events_id, client_id, limit, now, &message));
+        assert!(!budgets.accept(events_id, client_id, limit, now, &message));
+        assert!(budgets.accept(events_id, ClientId::new(2), limit, now, &message));
+        assert_eq!(budgets.drain_coalesced(events_id, limit, now).count(), 0);
+
+        let now = now + limit.period;
+        assert!(budgets.accept(events_id, client_id, limit, now, &message));
+    }
+
+    #[test]
+    fn coalesce() {
+        let mut budgets = ClientEventBudgets::default();
+        let limit = ClientEventRateLimit::per_second(1).with_excess(ExcessPolicy::Coalesce);
+        let events_id = ComponentId::new(0);
+        let client_id = ClientId::new(1);
+
+        let now = Duration::from_secs(1);
+        assert!(budgets.accept(events_id, client_id, limit, now, &Bytes::from_static(&[0])));
+        assert!(!budgets.accept(events_id, client_id, limit, now, &Bytes::from_static(&[1])));
+        assert!(!budgets.accept(events_id, client_id, limit, now, &Bytes::from_static(&[2])));
+        assert_eq!(budgets.drain_coalesced(events_id, limit, now).count(), 0);
+
+        let now = now + limit.period;
+        let coalesced: Vec<_> = budgets.drain_coalesced(events_id, limit, now).collect();
+        assert_eq!(coalesced, [(client_id, Bytes::from_static(&[2]))]);
+        assert!(!budgets.accept(events_id, client_id, limit, now, &Bytes::new()));
+    }
+}
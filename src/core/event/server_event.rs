@@ -1,12 +1,20 @@
-use std::{any, collections::HashSet, marker::PhantomData, mem};
+use std::any;
+#[cfg(feature = "client")]
+use std::marker::PhantomData;
+#[cfg(feature = "server")]
+use std::{collections::HashSet, mem};
 
 use bevy::{
     ecs::{component::ComponentId, entity::MapEntities},
     prelude::*,
-    ptr::{Ptr, PtrMut},
+    ptr::PtrMut,
 };
+#[cfg(feature = "server")]
+use bevy::{ptr::Ptr, utils::HashMap};
 use bytes::Bytes;
+#[cfg(feature = "client")]
 use ordered_multimap::ListOrderedMultimap;
+#[cfg(feature = "server")]
 use postcard::experimental::{max_size::MaxSize, serialized_size};
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -14,15 +22,21 @@ use super::{
     ctx::{ClientReceiveCtx, ServerSendCtx},
     event_fns::{EventDeserializeFn, EventFns, EventSerializeFn, UntypedEventFns},
     event_registry::EventRegistry,
+    tagged_event::{deserialize_tagged, serialize_tagged, TaggedEvent},
 };
+#[cfg(feature = "client")]
+use crate::core::replicon_client::RepliconClient;
 use crate::core::{
-    channels::{RepliconChannel, RepliconChannels},
-    connected_clients::ConnectedClients,
+    channels::{ChannelKind, ChannelSelection, RepliconChannel, RepliconChannels},
+    entity_serde::MappedTypes,
     postcard_utils,
+    replicon_tick::RepliconTick,
+};
+#[cfg(feature = "server")]
+use crate::core::{
+    connected_clients::ConnectedClients,
     replication::replicated_clients::{ReplicatedClient, ReplicatedClients},
-    replicon_client::RepliconClient,
     replicon_server::RepliconServer,
-    replicon_tick::RepliconTick,
     ClientId,
 };
 
@@ -34,8 +48,13 @@ pub trait ServerEventAppExt {
     /// If [`ClientId::SERVER`] is a recipient of the event, then [`ToClients<E>`] will be drained
     /// after sending to clients and `E` events will be emitted on the server.
     ///
+    /// Events are stamped with the server tick and emitted on client only after
+    /// the replication for this tick is applied, see [`Self::make_independent`] for details.
+    ///
     /// Can be called for already existing regular events, a duplicate registration
     /// for `E` won't be created.
+    /// Registering `E` again as a server event replaces its functions and channel
+    /// configuration, but keeps the previously assigned channel ID.
     ///
     /// See also [`Self::add_server_event_with`] and the [corresponding section](../index.html#from-server-to-client)
     /// from the quick start guide.
@@ -49,15 +68,27 @@ pub trait ServerEventAppExt {
     /// Same as [`Self::add_server_event`], but additionally maps server entities to client inside the event after receiving.
     ///
     /// Always use it for events that contain entities.
+    /// Entities are mapped via [`ServerEntityMap`](crate::core::server_entity_map::ServerEntityMap),
+    /// events are applied after the replication of their tick, so entities spawned on the same tick are already mapped.
+    /// Events that reference entities unknown to the client are discarded with an error.
+    ///
     /// See also [`Self::add_server_event`].
     fn add_mapped_server_event<E: Event + Serialize + DeserializeOwned + MapEntities>(
         &mut self,
         channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+
+    /// Same as [`Self::add_server_event`], but encodes enum variants with stable tags.
+    ///
+    /// See [`TaggedEvent`] for details.
+    fn add_tagged_server_event<E: TaggedEvent>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
     ) -> &mut Self {
         self.add_server_event_with(
             channel,
-            default_serialize::<E>,
-            default_deserialize_mapped::<E>,
+            serialize_tagged::<ServerSendCtx, E>,
+            deserialize_tagged::<ClientReceiveCtx, E>,
         )
     }
 
@@ -141,9 +172,89 @@ pub trait ServerEventAppExt {
     ///
     /// </div>
     fn make_independent<E: Event>(&mut self) -> &mut Self;
+
+    /**
+    Sets what to do with event `E` for clients that are still syncing.
+
+    A client is syncing after connection until the initial state of the world is sent to it
+    (see [`ReplicatedClient::is_baseline_sent`]). Events sent during this time may reference entities
+    the client doesn't know yet.
+
+    By default it's [`SyncingPolicy::Send`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{core::event::server_event::SyncingPolicy, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.add_server_event::<Greeting>(ChannelKind::Ordered)
+        .set_syncing_policy::<Greeting>(SyncingPolicy::Buffer);
+
+    #[derive(Event, Deserialize, Serialize)]
+    struct Greeting(String);
+    ```
+    */
+    fn set_syncing_policy<E: Event>(&mut self, policy: SyncingPolicy) -> &mut Self;
+
+    /**
+    Guarantees that `B` events are received on client before `A` events that were sent after them.
+
+    By default, each event uses its own channel, so there is no delivery order between different
+    event types. After calling this method, both events will be routed through a shared
+    [`ChannelKind::Ordered`] channel with a small header that identifies the event type.
+    Calling it for events that are already ordered with other events joins their groups,
+    so a chain of events can be ordered by calling this method for each pair.
+
+    Should be called on both client and server.
+
+    # Panics
+
+    Panics if `B` and `A` weren't registered as server events, if `B` was registered after `A`,
+    if they have different independence (see [`Self::make_independent`])
+    or if they are already ordered with different events.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.add_server_event::<PlayerJoined>(ChannelKind::Ordered)
+        .add_server_event::<ChatMessage>(ChannelKind::Ordered)
+        .order_server_events::<PlayerJoined, ChatMessage>();
+
+    #[derive(Event, Deserialize, Serialize)]
+    struct PlayerJoined(String);
+
+    #[derive(Event, Deserialize, Serialize)]
+    struct ChatMessage(String);
+    ```
+    */
+    fn order_server_events<B: Event, A: Event>(&mut self) -> &mut Self;
 }
 
 impl ServerEventAppExt for App {
+    fn add_mapped_server_event<E: Event + Serialize + DeserializeOwned + MapEntities>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<MappedTypes>()
+            .push(any::type_name::<E>());
+        self.add_server_event_with(
+            channel,
+            default_serialize::<E>,
+            default_deserialize_mapped::<E>,
+        )
+    }
+
     fn add_server_event_with<E: Event>(
         &mut self,
         channel: impl Into<RepliconChannel>,
@@ -153,6 +264,11 @@ impl ServerEventAppExt for App {
         debug!("registering event `{}`", any::type_name::<E>());
 
         let event_fns = EventFns::new(serialize, deserialize);
+        let channel = channel.into();
+        if ServerEvent::reregister(self.world_mut(), channel.clone(), event_fns) {
+            return self;
+        }
+
         let event = ServerEvent::new(self, channel, event_fns);
         let mut event_registry = self.world_mut().resource_mut::<EventRegistry>();
         event_registry.register_server_event(event);
@@ -183,16 +299,164 @@ impl ServerEventAppExt for App {
                 )
             });
 
+        assert!(
+            event.ordered_channel.is_none(),
+            "event `{}` should be marked as independent before ordering",
+            any::type_name::<E>()
+        );
         event.independent = true;
 
         self
     }
+
+    fn set_syncing_policy<E: Event>(&mut self, policy: SyncingPolicy) -> &mut Self {
+        let events_id = events_id::<E>(self.world());
+        let mut event_registry = self.world_mut().resource_mut::<EventRegistry>();
+        let event = event_registry
+            .iter_server_events_mut()
+            .find(|event| event.events_id() == events_id)
+            .unwrap_or_else(|| {
+                panic!(
+                    "event `{}` should be previously registered as a server event",
+                    any::type_name::<E>()
+                )
+            });
+
+        debug!("setting `{policy:?}` for event `{}`", any::type_name::<E>());
+        event.syncing_policy = policy;
+
+        self
+    }
+
+    fn order_server_events<B: Event, A: Event>(&mut self) -> &mut Self {
+        let before_id = events_id::<B>(self.world());
+        let after_id = events_id::<A>(self.world());
+
+        let event_registry = self.world().resource::<EventRegistry>();
+        let before_index = server_event_index::<B>(event_registry, before_id);
+        let after_index = server_event_index::<A>(event_registry, after_id);
+        assert!(
+            before_index < after_index,
+            "event `{}` should be registered before `{}`",
+            any::type_name::<B>(),
+            any::type_name::<A>()
+        );
+
+        let (before_channel, after_channel) = {
+            let mut events = event_registry.iter_server_events();
+            let before = events.nth(before_index).unwrap();
+            let after = events.nth(after_index - before_index - 1).unwrap();
+            assert_eq!(
+                before.is_independent(),
+                after.is_independent(),
+                "events `{}` and `{}` should have the same independence",
+                any::type_name::<B>(),
+                any::type_name::<A>()
+            );
+            (before.ordered_channel, after.ordered_channel)
+        };
+
+        let ordered_channel = match (before_channel, after_channel) {
+            (Some(before_channel), Some(after_channel)) => {
+                assert_eq!(
+                    before_channel,
+                    after_channel,
+                    "events `{}` and `{}` are already ordered with other events",
+                    any::type_name::<B>(),
+                    any::type_name::<A>()
+                );
+                before_channel
+            }
+            (Some(channel_id), None) | (None, Some(channel_id)) => channel_id,
+            (None, None) => {
+                let channel_id = self
+                    .world_mut()
+                    .resource_mut::<RepliconChannels>()
+                    .create_server_channel(ChannelKind::Ordered);
+                self.world_mut()
+                    .resource_mut::<EventRegistry>()
+                    .add_ordered_server_channel(channel_id);
+                channel_id
+            }
+        };
+
+        debug!(
+            "ordering event `{}` after `{}` using channel {ordered_channel}",
+            any::type_name::<A>(),
+            any::type_name::<B>()
+        );
+
+        let mut event_registry = self.world_mut().resource_mut::<EventRegistry>();
+        for event in event_registry
+            .iter_server_events_mut()
+            .filter(|event| event.events_id() == before_id || event.events_id() == after_id)
+        {
+            event.ordered_channel = Some(ordered_channel);
+        }
+
+        self
+    }
+}
+
+/// Extension trait for sending server events.
+///
+/// Useful for command-driven flows and exclusive systems where [`EventWriter`] isn't available.
+///
+/// See also [`ServerEventAppExt`].
+#[cfg(feature = "server")]
+pub trait ServerEventExt {
+    /// Like [`EventWriter::send`], but for server events.
+    ///
+    /// Sends `E` to clients and locally if [`ClientId::SERVER`](crate::core::ClientId::SERVER)
+    /// is a recipient of the event.
+    fn server_send<E: Event>(&mut self, event: ToClients<E>);
+}
+
+#[cfg(feature = "server")]
+impl ServerEventExt for Commands<'_, '_> {
+    fn server_send<E: Event>(&mut self, event: ToClients<E>) {
+        self.send_event(event);
+    }
+}
+
+#[cfg(feature = "server")]
+impl ServerEventExt for World {
+    fn server_send<E: Event>(&mut self, event: ToClients<E>) {
+        self.send_event(event);
+    }
+}
+
+fn events_id<E: Event>(world: &World) -> ComponentId {
+    world
+        .components()
+        .resource_id::<Events<E>>()
+        .unwrap_or_else(|| {
+            panic!(
+                "event `{}` should be previously registered",
+                any::type_name::<E>()
+            )
+        })
+}
+
+fn server_event_index<E: Event>(event_registry: &EventRegistry, events_id: ComponentId) -> usize {
+    event_registry
+        .iter_server_events()
+        .position(|event| event.events_id() == events_id)
+        .unwrap_or_else(|| {
+            panic!(
+                "event `{}` should be previously registered as a server event",
+                any::type_name::<E>()
+            )
+        })
 }
 
 /// Type-erased functions and metadata for a registered server event.
 ///
 /// Needed so events of different types can be processed together.
 pub(crate) struct ServerEvent {
+    /// Type name of the event.
+    type_name: &'static str,
+
     /// Whether this event depends on replication or not.
     ///
     /// Events like a chat message event do not have to wait for replication to
@@ -200,21 +464,43 @@ pub(crate) struct ServerEvent {
     /// immediately.
     independent: bool,
 
+    /// What to do with the event for clients that are still syncing.
+    #[cfg_attr(not(feature = "server"), allow(dead_code))]
+    syncing_policy: SyncingPolicy,
+
     /// ID of [`Events<E>`].
     events_id: ComponentId,
 
     /// ID of [`Events<ToClients<E>>`].
+    #[cfg(feature = "server")]
     server_events_id: ComponentId,
 
     /// ID of [`ServerEventQueue<T>`].
+    #[cfg(feature = "client")]
     queue_id: ComponentId,
 
     /// Used channel.
     channel_id: u8,
 
+    /// Channel shared with other events to preserve order between them.
+    ///
+    /// If set, messages are sent over it with [`Self::channel_id`] as a header.
+    /// See [`ServerEventAppExt::order_server_events`].
+    ordered_channel: Option<u8>,
+
+    /// Channel shared with other events selected by [`ChannelSelection::Automatic`].
+    ///
+    /// If set, [`Self::channel_id`] is virtual and used only as a header.
+    /// Ignored if [`Self::ordered_channel`] is set.
+    auto_channel: Option<u8>,
+
+    #[cfg(feature = "server")]
     send_or_buffer: SendOrBufferFn,
+    #[cfg(feature = "client")]
     receive: ReceiveFn,
+    #[cfg(feature = "server")]
     resend_locally: ResendLocallyFn,
+    #[cfg(feature = "client")]
     reset: ResetFn,
     event_fns: UntypedEventFns,
 }
@@ -225,46 +511,119 @@ impl ServerEvent {
         channel: impl Into<RepliconChannel>,
         event_fns: EventFns<ServerSendCtx, ClientReceiveCtx, E, I>,
     ) -> Self {
-        let channel_id = app
-            .world_mut()
-            .resource_mut::<RepliconChannels>()
-            .create_server_channel(channel);
+        let mut channels = app.world_mut().resource_mut::<RepliconChannels>();
+        let (channel_id, auto_channel) = match channels.channel_selection() {
+            ChannelSelection::Dedicated => (channels.create_server_channel(channel), None),
+            ChannelSelection::Automatic => {
+                let auto_channel = channels.shared_server_channel(channel);
+                (channels.create_virtual_server_channel(), Some(auto_channel))
+            }
+        };
 
-        app.add_event::<E>()
-            .add_event::<ToClients<E>>()
-            .init_resource::<ServerEventQueue<E>>();
+        app.add_event::<E>();
+        #[cfg(feature = "server")]
+        app.add_event::<ToClients<E>>();
+        #[cfg(feature = "client")]
+        app.init_resource::<ServerEventQueue<E>>();
 
         let events_id = app.world().resource_id::<Events<E>>().unwrap();
+        #[cfg(feature = "server")]
         let server_events_id = app.world().resource_id::<Events<ToClients<E>>>().unwrap();
+        #[cfg(feature = "client")]
         let queue_id = app.world().resource_id::<ServerEventQueue<E>>().unwrap();
 
         Self {
+            type_name: any::type_name::<I>(),
             independent: false,
+            syncing_policy: Default::default(),
             events_id,
+            #[cfg(feature = "server")]
             server_events_id,
+            #[cfg(feature = "client")]
             queue_id,
             channel_id,
+            ordered_channel: None,
+            auto_channel,
+            #[cfg(feature = "server")]
             send_or_buffer: Self::send_or_buffer_typed::<E, I>,
+            #[cfg(feature = "client")]
             receive: Self::receive_typed::<E, I>,
+            #[cfg(feature = "server")]
             resend_locally: Self::resend_locally_typed::<E>,
+            #[cfg(feature = "client")]
             reset: Self::reset_typed::<E>,
             event_fns: event_fns.into(),
         }
     }
 
+    /// Replaces functions and channel configuration of an already registered event.
+    ///
+    /// Returns `false` if the event wasn't registered.
+    pub(super) fn reregister<E: Event, I: 'static>(
+        world: &mut World,
+        channel: RepliconChannel,
+        event_fns: EventFns<ServerSendCtx, ClientReceiveCtx, E, I>,
+    ) -> bool {
+        let Some(events_id) = world.components().resource_id::<Events<E>>() else {
+            return false;
+        };
+
+        world.resource_scope(|world, mut event_registry: Mut<EventRegistry>| {
+            let Some(event) = event_registry
+                .iter_server_events_mut()
+                .find(|event| event.events_id == events_id)
+            else {
+                return false;
+            };
+
+            debug!("re-registering event `{}`", any::type_name::<I>());
+            let mut channels = world.resource_mut::<RepliconChannels>();
+            match &mut event.auto_channel {
+                Some(auto_channel) => *auto_channel = channels.shared_server_channel(channel),
+                None => *channels.server_channel_mut(event.channel_id) = channel,
+            }
+            event.event_fns = event_fns.into();
+
+            true
+        })
+    }
+
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub(crate) fn channel_id(&self) -> u8 {
+        self.channel_id
+    }
+
+    pub(crate) fn ordered_channel(&self) -> Option<u8> {
+        self.ordered_channel
+    }
+
+    pub(crate) fn auto_channel(&self) -> Option<u8> {
+        self.auto_channel
+    }
+
+    /// Returns the channel over which messages are sent with [`Self::channel_id`] as a header, if any.
+    fn shared_channel(&self) -> Option<u8> {
+        self.ordered_channel.or(self.auto_channel)
+    }
+
     pub(crate) fn events_id(&self) -> ComponentId {
         self.events_id
     }
 
+    #[cfg(feature = "server")]
     pub(crate) fn server_events_id(&self) -> ComponentId {
         self.server_events_id
     }
 
+    #[cfg(feature = "client")]
     pub(crate) fn queue_id(&self) -> ComponentId {
         self.queue_id
     }
 
-    pub(super) fn is_independent(&self) -> bool {
+    pub(crate) fn is_independent(&self) -> bool {
         self.independent
     }
 
@@ -274,12 +633,14 @@ impl ServerEvent {
     ///
     /// The caller must ensure that `server_events` is [`Events<ToClients<E>>`]
     /// and this instance was created for `E`.
+    #[cfg(feature = "server")]
     pub(crate) unsafe fn send_or_buffer(
         &self,
         ctx: &mut ServerSendCtx,
         server_events: &Ptr,
         server: &mut RepliconServer,
         connected_clients: &ConnectedClients,
+        replicated_clients: Option<&ReplicatedClients>,
         buffered_events: &mut BufferedServerEvents,
     ) {
         (self.send_or_buffer)(
@@ -288,6 +649,7 @@ impl ServerEvent {
             server_events,
             server,
             connected_clients,
+            replicated_clients,
             buffered_events,
         );
     }
@@ -298,12 +660,14 @@ impl ServerEvent {
     ///
     /// The caller must ensure that `server_events` is [`Events<ToClients<E>>`]
     /// and this instance was created for `E` and `I`.
+    #[cfg(feature = "server")]
     unsafe fn send_or_buffer_typed<E: Event, I: 'static>(
         &self,
         ctx: &mut ServerSendCtx,
         server_events: &Ptr,
         server: &mut RepliconServer,
         connected_clients: &ConnectedClients,
+        replicated_clients: Option<&ReplicatedClients>,
         buffered_events: &mut BufferedServerEvents,
     ) {
         let events: &Events<ToClients<E>> = server_events.deref();
@@ -312,23 +676,79 @@ impl ServerEvent {
         for ToClients { event, mode } in events.get_cursor().read(events) {
             debug!("sending event `{}` with `{mode:?}`", any::type_name::<E>());
 
-            if self.is_independent() {
-                self.send_independent_event::<E, I>(ctx, event, mode, server, connected_clients)
-                    .expect("independent server event should be serializable");
-            } else {
-                self.buffer_event::<E, I>(ctx, event, *mode, buffered_events)
-                    .expect("server event should be serializable");
+            if ctx.obfuscation.is_none() {
+                self.send_or_buffer_event::<E, I>(
+                    ctx,
+                    event,
+                    *mode,
+                    server,
+                    connected_clients,
+                    replicated_clients,
+                    buffered_events,
+                );
+                continue;
             }
+
+            // Each client has its own entity permutation, so the event is serialized for every recipient.
+            for client_id in mode.recipients(connected_clients, replicated_clients) {
+                ctx.set_client(client_id);
+                self.send_or_buffer_event::<E, I>(
+                    ctx,
+                    event,
+                    SendMode::Direct(client_id),
+                    server,
+                    connected_clients,
+                    replicated_clients,
+                    buffered_events,
+                );
+            }
+        }
+    }
+
+    /// Sends independent event `E` or buffers a regular one.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that this instance was created for `E` and `I`.
+    #[cfg(feature = "server")]
+    unsafe fn send_or_buffer_event<E: Event, I: 'static>(
+        &self,
+        ctx: &mut ServerSendCtx,
+        event: &E,
+        mode: SendMode,
+        server: &mut RepliconServer,
+        connected_clients: &ConnectedClients,
+        replicated_clients: Option<&ReplicatedClients>,
+        buffered_events: &mut BufferedServerEvents,
+    ) {
+        if self.is_independent() || replicated_clients.is_none() {
+            self.send_independent_event::<E, I>(
+                ctx,
+                event,
+                &mode,
+                server,
+                connected_clients,
+                replicated_clients,
+                buffered_events,
+            )
+            .expect("independent server event should be serializable");
+        } else {
+            self.buffer_event::<E, I>(ctx, event, mode, buffered_events)
+                .expect("server event should be serializable");
         }
     }
 
     /// Sends independent event `E` based on a mode.
     ///
+    /// Also used for regular events when replication is disabled.
+    /// Such events are sent with the default tick to be applied immediately.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that this instance was created for `E` and `I`.
     ///
     /// For regular events see [`Self::buffer_event`].
+    #[cfg(feature = "server")]
     unsafe fn send_independent_event<E: Event, I: 'static>(
         &self,
         ctx: &mut ServerSendCtx,
@@ -336,27 +756,74 @@ impl ServerEvent {
         mode: &SendMode,
         server: &mut RepliconServer,
         connected_clients: &ConnectedClients,
+        replicated_clients: Option<&ReplicatedClients>,
+        buffered_events: &mut BufferedServerEvents,
     ) -> postcard::Result<()> {
         let mut message = Vec::new();
+        if self.shared_channel().is_some() {
+            message.push(self.channel_id);
+        }
+        if !self.is_independent() {
+            postcard_utils::to_extend_mut(&RepliconTick::default(), &mut message)?;
+        }
         self.serialize::<E, I>(ctx, event, &mut message)?;
         let message: Bytes = message.into();
 
+        let channel_id = self.shared_channel().unwrap_or(self.channel_id);
         match *mode {
             SendMode::Broadcast => {
                 for client in connected_clients.iter() {
-                    server.send(client.id(), self.channel_id, message.clone());
+                    self.send_or_hold(
+                        server,
+                        replicated_clients,
+                        buffered_events,
+                        client.id(),
+                        channel_id,
+                        message.clone(),
+                    );
                 }
             }
             SendMode::BroadcastExcept(id) => {
                 for client in connected_clients.iter() {
                     if client.id() != id {
-                        server.send(client.id(), self.channel_id, message.clone());
+                        self.send_or_hold(
+                            server,
+                            replicated_clients,
+                            buffered_events,
+                            client.id(),
+                            channel_id,
+                            message.clone(),
+                        );
                     }
                 }
             }
             SendMode::Direct(client_id) => {
                 if client_id != ClientId::SERVER {
-                    server.send(client_id, self.channel_id, message.clone());
+                    self.send_or_hold(
+                        server,
+                        replicated_clients,
+                        buffered_events,
+                        client_id,
+                        channel_id,
+                        message.clone(),
+                    );
+                }
+            }
+            SendMode::Observers(entity) => {
+                for client in replicated_clients
+                    .iter()
+                    .flat_map(|replicated_clients| replicated_clients.iter())
+                    .filter(|client| !client.is_virtual())
+                    .filter(|client| client.visibility().is_visible(entity))
+                {
+                    self.send_or_hold(
+                        server,
+                        replicated_clients,
+                        buffered_events,
+                        client.id(),
+                        channel_id,
+                        message.clone(),
+                    );
                 }
             }
         }
@@ -364,6 +831,44 @@ impl ServerEvent {
         Ok(())
     }
 
+    /// Sends a message with an independent event or applies [`Self::syncing_policy`]
+    /// if the client is still syncing.
+    #[cfg(feature = "server")]
+    fn send_or_hold(
+        &self,
+        server: &mut RepliconServer,
+        replicated_clients: Option<&ReplicatedClients>,
+        buffered_events: &mut BufferedServerEvents,
+        client_id: ClientId,
+        channel_id: u8,
+        message: Bytes,
+    ) {
+        let syncing = replicated_clients.is_some_and(|clients| {
+            clients
+                .get_client(client_id)
+                .is_none_or(|client| !client.is_baseline_sent())
+        });
+        if !syncing {
+            server.send(client_id, channel_id, message);
+            return;
+        }
+
+        match self.syncing_policy {
+            SyncingPolicy::Send => server.send(client_id, channel_id, message),
+            SyncingPolicy::Buffer => {
+                trace!("holding event for syncing `{client_id:?}`");
+                buffered_events.hold(
+                    client_id,
+                    HeldServerEvent::Ready {
+                        channel_id,
+                        message,
+                    },
+                );
+            }
+            SyncingPolicy::Drop => trace!("dropping event for syncing `{client_id:?}`"),
+        }
+    }
+
     /// Buffers event `E` based on a mode.
     ///
     /// # Safety
@@ -371,6 +876,7 @@ impl ServerEvent {
     /// The caller must ensure that this instance was created for `E` and `I`.
     ///
     /// For independent events see [`Self::send_independent_event`].
+    #[cfg(feature = "server")]
     unsafe fn buffer_event<E: Event, I: 'static>(
         &self,
         ctx: &mut ServerSendCtx,
@@ -379,7 +885,18 @@ impl ServerEvent {
         buffered_events: &mut BufferedServerEvents,
     ) -> postcard::Result<()> {
         let message = self.serialize_with_padding::<E, I>(ctx, event)?;
-        buffered_events.insert(mode, self.channel_id, message);
+        match self.shared_channel() {
+            Some(channel_id) => buffered_events.insert(
+                mode,
+                channel_id,
+                Some(self.channel_id),
+                self.syncing_policy,
+                message,
+            ),
+            None => {
+                buffered_events.insert(mode, self.channel_id, None, self.syncing_policy, message)
+            }
+        }
         Ok(())
     }
 
@@ -390,6 +907,7 @@ impl ServerEvent {
     /// # Safety
     ///
     /// The caller must ensure that this instance was created for `E` and `I`.
+    #[cfg(feature = "server")]
     unsafe fn serialize_with_padding<E: Event, I: 'static>(
         &self,
         ctx: &mut ServerSendCtx,
@@ -408,6 +926,7 @@ impl ServerEvent {
     ///
     /// The caller must ensure that `events` is [`Events<E>`], `queue` is [`ServerEventQueue<E>`],
     /// and this instance was created for `E`.
+    #[cfg(feature = "client")]
     pub(crate) unsafe fn receive(
         &self,
         ctx: &mut ClientReceiveCtx,
@@ -425,6 +944,7 @@ impl ServerEvent {
     ///
     /// The caller must ensure that `events` is [`Events<E>`], `queue` is [`ServerEventQueue<E>`]
     /// and this instance was created for `E` and `I`.
+    #[cfg(feature = "client")]
     unsafe fn receive_typed<E: Event, I: 'static>(
         &self,
         ctx: &mut ClientReceiveCtx,
@@ -436,6 +956,7 @@ impl ServerEvent {
         let events: &mut Events<E> = events.deref_mut();
         let queue: &mut ServerEventQueue<E> = queue.deref_mut();
 
+        let mut decode_errors = 0;
         while let Some((tick, mut message)) = queue.pop_if_le(update_tick) {
             match self.deserialize::<E, I>(ctx, &mut message) {
                 Ok(event) => {
@@ -445,10 +966,17 @@ impl ServerEvent {
                     );
                     events.send(event);
                 }
-                Err(e) => error!(
-                "ignoring event `{}` from queue with `{tick:?}` that failed to deserialize: {e}",
-                any::type_name::<E>()
-            ),
+                Err(postcard::Error::DeserializeBadEnum) => warn!(
+                    "ignoring event `{}` from queue with `{tick:?}` with unknown variant",
+                    any::type_name::<E>()
+                ),
+                Err(e) => {
+                    error!(
+                        "ignoring event `{}` from queue with `{tick:?}` that failed to deserialize: {e}",
+                        any::type_name::<E>()
+                    );
+                    decode_errors += 1;
+                }
             }
         }
 
@@ -461,6 +989,7 @@ impl ServerEvent {
                             "ignoring event `{}` because it's tick failed to deserialize: {e}",
                             any::type_name::<E>()
                         );
+                        decode_errors += 1;
                         continue;
                     }
                 };
@@ -481,12 +1010,20 @@ impl ServerEvent {
                     debug!("applying event `{}`", any::type_name::<E>());
                     events.send(event);
                 }
-                Err(e) => error!(
-                    "ignoring event `{}` that failed to deserialize: {e}",
+                Err(postcard::Error::DeserializeBadEnum) => warn!(
+                    "ignoring event `{}` with unknown variant",
                     any::type_name::<E>()
                 ),
+                Err(e) => {
+                    error!(
+                        "ignoring event `{}` that failed to deserialize: {e}",
+                        any::type_name::<E>()
+                    );
+                    decode_errors += 1;
+                }
             }
         }
+        client.add_decode_errors(decode_errors);
     }
 
     /// Drains events [`ToClients<E>`] and re-emits them as `E` if the server is in the list of the event recipients.
@@ -495,6 +1032,7 @@ impl ServerEvent {
     ///
     /// The caller must ensure that `events` is [`Events<E>`], `server_events` is [`Events<ToClients<E>>`],
     /// and this instance was created for `E`.
+    #[cfg(feature = "server")]
     pub(crate) unsafe fn resend_locally(&self, server_events: PtrMut, events: PtrMut) {
         (self.resend_locally)(server_events, events);
     }
@@ -504,6 +1042,7 @@ impl ServerEvent {
     /// # Safety
     ///
     /// The caller must ensure that `events` is [`Events<E>`] and `server_events` is [`Events<ToClients<E>>`].
+    #[cfg(feature = "server")]
     unsafe fn resend_locally_typed<E: Event>(server_events: PtrMut, events: PtrMut) {
         let server_events: &mut Events<ToClients<E>> = server_events.deref_mut();
         let events: &mut Events<E> = events.deref_mut();
@@ -523,6 +1062,10 @@ impl ServerEvent {
                         events.send(event);
                     }
                 }
+                SendMode::Observers(_) => {
+                    // Server can see all entities.
+                    events.send(event);
+                }
             }
         }
     }
@@ -535,6 +1078,7 @@ impl ServerEvent {
     ///
     /// The caller must ensure that `queue` is [`Events<E>`]
     /// and this instance was created for `E`.
+    #[cfg(feature = "client")]
     pub(crate) unsafe fn reset(&self, queue: PtrMut) {
         (self.reset)(queue);
     }
@@ -544,6 +1088,7 @@ impl ServerEvent {
     /// # Safety
     ///
     /// The caller must ensure that `queue` is [`Events<E>`].
+    #[cfg(feature = "client")]
     unsafe fn reset_typed<E: Event>(queue: PtrMut) {
         let queue: &mut ServerEventQueue<E> = queue.deref_mut();
         if !queue.is_empty() {
@@ -560,6 +1105,7 @@ impl ServerEvent {
     /// # Safety
     ///
     /// The caller must ensure that this instance was created for `E` and `I`.
+    #[cfg(feature = "server")]
     unsafe fn serialize<E: Event, I: 'static>(
         &self,
         ctx: &mut ServerSendCtx,
@@ -576,6 +1122,7 @@ impl ServerEvent {
     /// # Safety
     ///
     /// The caller must ensure that this instance was created for `E` and `I`.
+    #[cfg(feature = "client")]
     unsafe fn deserialize<E: Event, I: 'static>(
         &self,
         ctx: &mut ClientReceiveCtx,
@@ -601,16 +1148,19 @@ impl ServerEvent {
 }
 
 /// Signature of server event sending functions.
+#[cfg(feature = "server")]
 type SendOrBufferFn = unsafe fn(
     &ServerEvent,
     &mut ServerSendCtx,
     &Ptr,
     &mut RepliconServer,
     &ConnectedClients,
+    Option<&ReplicatedClients>,
     &mut BufferedServerEvents,
 );
 
 /// Signature of server event receiving functions.
+#[cfg(feature = "client")]
 type ReceiveFn = unsafe fn(
     &ServerEvent,
     &mut ClientReceiveCtx,
@@ -621,12 +1171,16 @@ type ReceiveFn = unsafe fn(
 );
 
 /// Signature of server event resending functions.
+#[cfg(feature = "server")]
 type ResendLocallyFn = unsafe fn(PtrMut, PtrMut);
 
 /// Signature of server event reset functions.
+#[cfg(feature = "client")]
 type ResetFn = unsafe fn(PtrMut);
 
 /// Cached message for use in [`BufferedServerEvents`].
+#[cfg(feature = "server")]
+#[derive(Clone)]
 enum SerializedMessage {
     /// A message without serialized tick.
     ///
@@ -645,6 +1199,7 @@ enum SerializedMessage {
     },
 }
 
+#[cfg(feature = "server")]
 impl SerializedMessage {
     /// Optimized to avoid reallocations when clients have the same update tick as other clients receiving the
     /// same message.
@@ -688,24 +1243,41 @@ impl SerializedMessage {
     }
 }
 
+#[cfg(feature = "server")]
+#[derive(Clone)]
 struct BufferedServerEvent {
     mode: SendMode,
     channel: u8,
+    /// Channel ID to prepend for events sent over a shared ordered channel.
+    header: Option<u8>,
+    syncing_policy: SyncingPolicy,
     message: SerializedMessage,
 }
 
+#[cfg(feature = "server")]
 impl BufferedServerEvent {
     fn send(
         &mut self,
         server: &mut RepliconServer,
         client: &ReplicatedClient,
     ) -> postcard::Result<()> {
-        let message = self.message.get_bytes(client.update_tick())?;
+        if client.is_virtual() {
+            return Ok(());
+        }
+
+        let mut message = self.message.get_bytes(client.update_tick())?;
+        if let Some(header) = self.header {
+            let mut with_header = Vec::with_capacity(message.len() + 1);
+            with_header.push(header);
+            with_header.extend_from_slice(&message);
+            message = with_header.into();
+        }
         server.send(client.id(), self.channel, message);
         Ok(())
     }
 }
 
+#[cfg(feature = "server")]
 #[derive(Default)]
 struct BufferedServerEventSet {
     events: Vec<BufferedServerEvent>,
@@ -713,6 +1285,7 @@ struct BufferedServerEventSet {
     excluded: HashSet<ClientId>,
 }
 
+#[cfg(feature = "server")]
 impl BufferedServerEventSet {
     fn clear(&mut self) {
         self.events.clear();
@@ -725,6 +1298,7 @@ impl BufferedServerEventSet {
 /// This exists because replication does not scan the world every tick. If a server event is sent in the same
 /// tick as a spawn and the event references that spawn, then the server event's update tick needs to be synchronized
 /// with that spawn on the client. We buffer the event until the spawn can be detected.
+#[cfg(feature = "server")]
 #[derive(Resource, Default)]
 pub(crate) struct BufferedServerEvents {
     buffer: Vec<BufferedServerEventSet>,
@@ -733,8 +1307,12 @@ pub(crate) struct BufferedServerEvents {
     ///
     /// These are cleared before insertion.
     cache: Vec<BufferedServerEventSet>,
+
+    /// Events for syncing clients with [`SyncingPolicy::Buffer`].
+    held: HashMap<ClientId, Vec<HeldServerEvent>>,
 }
 
+#[cfg(feature = "server")]
 impl BufferedServerEvents {
     pub(crate) fn start_tick(&mut self) {
         self.buffer.push(self.cache.pop().unwrap_or_default());
@@ -744,7 +1322,14 @@ impl BufferedServerEvents {
         self.buffer.last_mut()
     }
 
-    fn insert(&mut self, mode: SendMode, channel: u8, message: SerializedMessage) {
+    fn insert(
+        &mut self,
+        mode: SendMode,
+        channel: u8,
+        header: Option<u8>,
+        syncing_policy: SyncingPolicy,
+        message: SerializedMessage,
+    ) {
         let buffer = self
             .active_tick()
             .expect("`BufferedServerEvents::start_tick` should be called before buffering");
@@ -752,10 +1337,22 @@ impl BufferedServerEvents {
         buffer.events.push(BufferedServerEvent {
             mode,
             channel,
+            header,
+            syncing_policy,
             message,
         });
     }
 
+    /// Keeps an event for a syncing client until the initial state of the world is sent to it.
+    fn hold(&mut self, client_id: ClientId, event: HeldServerEvent) {
+        self.held.entry(client_id).or_default().push(event);
+    }
+
+    /// Removes held events for a disconnected client.
+    pub(crate) fn remove_client(&mut self, client_id: ClientId) {
+        self.held.remove(&client_id);
+    }
+
     /// Used to prevent newly-connected clients from receiving old events.
     pub(crate) fn exclude_client(&mut self, client: ClientId) {
         for set in self.buffer.iter_mut() {
@@ -766,8 +1363,11 @@ impl BufferedServerEvents {
     pub(crate) fn send_all(
         &mut self,
         server: &mut RepliconServer,
+        connected_clients: &ConnectedClients,
         replicated_clients: &ReplicatedClients,
     ) -> postcard::Result<()> {
+        self.send_held(server, replicated_clients)?;
+
         for mut set in self.buffer.drain(..) {
             for mut event in set.events.drain(..) {
                 match event.mode {
@@ -797,6 +1397,31 @@ impl BufferedServerEvents {
                             }
                         }
                     }
+                    SendMode::Observers(entity) => {
+                        for client in replicated_clients
+                            .iter()
+                            .filter(|c| !set.excluded.contains(&c.id()))
+                            .filter(|c| c.visibility().is_visible(entity))
+                        {
+                            event.send(server, client)?;
+                        }
+                    }
+                }
+
+                if event.syncing_policy == SyncingPolicy::Buffer {
+                    // Clients without enabled replication are syncing.
+                    for client in connected_clients
+                        .iter()
+                        .filter(|c| !set.excluded.contains(&c.id()))
+                        .filter(|c| replicated_clients.get_client(c.id()).is_none())
+                        .filter(|c| event.mode.includes(c.id()))
+                    {
+                        trace!("holding event for syncing `{:?}`", client.id());
+                        self.held
+                            .entry(client.id())
+                            .or_default()
+                            .push(HeldServerEvent::Ticked(event.clone()));
+                    }
                 }
             }
             set.clear();
@@ -805,15 +1430,64 @@ impl BufferedServerEvents {
         Ok(())
     }
 
+    /// Sends held events to clients that received the initial state of the world.
+    fn send_held(
+        &mut self,
+        server: &mut RepliconServer,
+        replicated_clients: &ReplicatedClients,
+    ) -> postcard::Result<()> {
+        if self.held.is_empty() {
+            return Ok(());
+        }
+
+        for client in replicated_clients
+            .iter()
+            .filter(|client| client.is_baseline_sent())
+        {
+            let Some(events) = self.held.remove(&client.id()) else {
+                continue;
+            };
+
+            debug!(
+                "sending {} held event(s) to synced `{:?}`",
+                events.len(),
+                client.id()
+            );
+            for event in events {
+                match event {
+                    HeldServerEvent::Ready {
+                        channel_id,
+                        message,
+                    } => server.send(client.id(), channel_id, message),
+                    HeldServerEvent::Ticked(mut event) => event.send(server, client)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn clear(&mut self) {
         for mut set in self.buffer.drain(..) {
             set.clear();
             self.cache.push(set);
         }
+        self.held.clear();
     }
 }
 
+/// Event kept in [`BufferedServerEvents`] until the client finishes syncing.
+#[cfg(feature = "server")]
+enum HeldServerEvent {
+    /// Independent event with serialized message.
+    Ready { channel_id: u8, message: Bytes },
+
+    /// Regular event that will be serialized with the client's update tick.
+    Ticked(BufferedServerEvent),
+}
+
 /// An event that will be send to client(s).
+#[cfg(feature = "server")]
 #[derive(Clone, Copy, Debug, Event, Deref, DerefMut)]
 pub struct ToClients<T> {
     pub mode: SendMode,
@@ -822,17 +1496,76 @@ pub struct ToClients<T> {
 }
 
 /// Type of server message sending.
+#[cfg(feature = "server")]
 #[derive(Clone, Copy, Debug)]
 pub enum SendMode {
     Broadcast,
     BroadcastExcept(ClientId),
     Direct(ClientId),
+    /// Send to all clients that can see the entity.
+    ///
+    /// Recipients are resolved from [`ClientVisibility`](crate::core::replication::replicated_clients::client_visibility::ClientVisibility)
+    /// at send time, so the event is consistent with what each client has.
+    /// Clients without enabled replication never receive it.
+    Observers(Entity),
+}
+
+#[cfg(feature = "server")]
+impl SendMode {
+    /// Returns `true` if the client is a recipient, ignoring visibility for [`SendMode::Observers`].
+    fn includes(self, client_id: ClientId) -> bool {
+        match self {
+            SendMode::Broadcast => true,
+            SendMode::BroadcastExcept(except_id) => client_id != except_id,
+            SendMode::Direct(direct_id) => client_id == direct_id,
+            SendMode::Observers(_) => false,
+        }
+    }
+
+    /// Returns connected clients that receive an event sent with this mode.
+    fn recipients<'a>(
+        self,
+        connected_clients: &'a ConnectedClients,
+        replicated_clients: Option<&'a ReplicatedClients>,
+    ) -> impl Iterator<Item = ClientId> + 'a {
+        connected_clients
+            .iter()
+            .map(|client| client.id())
+            .filter(move |&client_id| match self {
+                SendMode::Observers(entity) => replicated_clients
+                    .and_then(|clients| clients.get_client(client_id))
+                    .is_some_and(|client| client.visibility().is_visible(entity)),
+                mode => mode.includes(client_id),
+            })
+    }
+}
+
+/// What to do with a server event for clients that are still syncing.
+///
+/// See [`ServerEventAppExt::set_syncing_policy`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SyncingPolicy {
+    /// Send immediately.
+    ///
+    /// Regular events are sent only to clients with enabled replication
+    /// (see [`ServerPlugin::replicate_after_connect`](crate::server::ServerPlugin::replicate_after_connect)).
+    #[default]
+    Send,
+
+    /// Keep events until the initial state of the world is sent to the client.
+    ///
+    /// Held events are sent before the events of the current tick.
+    Buffer,
+
+    /// Don't send events to syncing clients.
+    Drop,
 }
 
 /// Stores all received events from server that arrived earlier then replication message with their tick.
 ///
 /// Stores data sorted by ticks and maintains order of arrival.
 /// Needed to ensure that when an event is triggered, all the data that it affects or references already exists.
+#[cfg(feature = "client")]
 #[derive(Resource, Deref, DerefMut)]
 struct ServerEventQueue<E> {
     #[deref]
@@ -840,6 +1573,7 @@ struct ServerEventQueue<E> {
     marker: PhantomData<E>,
 }
 
+#[cfg(feature = "client")]
 impl<E> ServerEventQueue<E> {
     /// Pops the next event that is at least as old as the specified replicon tick.
     fn pop_if_le(&mut self, update_tick: RepliconTick) -> Option<(RepliconTick, Bytes)> {
@@ -853,6 +1587,7 @@ impl<E> ServerEventQueue<E> {
     }
 }
 
+#[cfg(feature = "client")]
 impl<E> Default for ServerEventQueue<E> {
     fn default() -> Self {
         Self {
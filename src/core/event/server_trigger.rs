@@ -1,6 +1,8 @@
 use std::any;
 
-use bevy::{ecs::entity::MapEntities, prelude::*, ptr::PtrMut};
+#[cfg(feature = "client")]
+use bevy::ptr::PtrMut;
+use bevy::{ecs::entity::MapEntities, prelude::*};
 use bytes::Bytes;
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -8,10 +10,16 @@ use super::{
     ctx::{ClientReceiveCtx, ServerSendCtx},
     event_fns::{EventDeserializeFn, EventFns, EventSerializeFn},
     event_registry::EventRegistry,
-    server_event::{self, ServerEvent, ToClients},
-    trigger::{RemoteTargets, RemoteTrigger},
+    server_event::{self, ServerEvent},
+    trigger::RemoteTrigger,
+};
+#[cfg(feature = "server")]
+use super::{server_event::ToClients, trigger::RemoteTargets};
+use crate::core::{
+    channels::RepliconChannel,
+    entity_serde::{self, MappedTypes},
+    postcard_utils,
 };
-use crate::core::{channels::RepliconChannel, entity_serde, postcard_utils};
 
 /// An extension trait for [`App`] for creating server triggers.
 ///
@@ -43,13 +51,7 @@ pub trait ServerTriggerAppExt {
     fn add_mapped_server_trigger<E: Event + Serialize + DeserializeOwned + MapEntities>(
         &mut self,
         channel: impl Into<RepliconChannel>,
-    ) -> &mut Self {
-        self.add_server_trigger_with(
-            channel,
-            server_event::default_serialize::<E>,
-            server_event::default_deserialize_mapped::<E>,
-        )
-    }
+    ) -> &mut Self;
 
     /// Same as [`Self::add_server_trigger`], but uses the specified functions for serialization and deserialization.
     ///
@@ -63,6 +65,20 @@ pub trait ServerTriggerAppExt {
 }
 
 impl ServerTriggerAppExt for App {
+    fn add_mapped_server_trigger<E: Event + Serialize + DeserializeOwned + MapEntities>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<MappedTypes>()
+            .push(any::type_name::<E>());
+        self.add_server_trigger_with(
+            channel,
+            server_event::default_serialize::<E>,
+            server_event::default_deserialize_mapped::<E>,
+        )
+    }
+
     fn add_server_trigger_with<E: Event>(
         &mut self,
         channel: impl Into<RepliconChannel>,
@@ -73,6 +89,11 @@ impl ServerTriggerAppExt for App {
 
         let event_fns = EventFns::new(serialize, deserialize)
             .with_outer(trigger_serialize, trigger_deserialize);
+        let channel = channel.into();
+        if ServerEvent::reregister(self.world_mut(), channel.clone(), event_fns) {
+            return self;
+        }
+
         let trigger = ServerTrigger::new(self, channel, event_fns);
         let mut event_registry = self.world_mut().resource_mut::<EventRegistry>();
         event_registry.register_server_trigger(trigger);
@@ -83,6 +104,7 @@ impl ServerTriggerAppExt for App {
 
 /// Small abstraction on top of [`ServerEvent`] that stores a function to trigger them.
 pub(crate) struct ServerTrigger {
+    #[cfg(feature = "client")]
     trigger: TriggerFn,
     event: ServerEvent,
 }
@@ -95,11 +117,13 @@ impl ServerTrigger {
     ) -> Self {
         let event = ServerEvent::new(app, channel, event_fns);
         Self {
+            #[cfg(feature = "client")]
             trigger: Self::trigger_typed::<E>,
             event,
         }
     }
 
+    #[cfg(feature = "client")]
     pub(crate) fn trigger(&self, commands: &mut Commands, events: PtrMut) {
         unsafe {
             (self.trigger)(commands, events);
@@ -112,6 +136,7 @@ impl ServerTrigger {
     ///
     /// The caller must ensure that `events` is [`Events<RemoteTrigger<E>>`]
     /// and this instance was created for `E`.
+    #[cfg(feature = "client")]
     unsafe fn trigger_typed<E: Event>(commands: &mut Commands, events: PtrMut) {
         let events: &mut Events<RemoteTrigger<E>> = events.deref_mut();
         for trigger in events.drain() {
@@ -130,6 +155,7 @@ impl ServerTrigger {
 }
 
 /// Signature of server trigger functions.
+#[cfg(feature = "client")]
 type TriggerFn = unsafe fn(&mut Commands, PtrMut);
 
 /// Serializes targets for [`RemoteTrigger`] and delegates the event
@@ -144,6 +170,7 @@ fn trigger_serialize<'a, E>(
 ) -> postcard::Result<()> {
     postcard_utils::to_extend_mut(&trigger.targets.len(), message)?;
     for &entity in &trigger.targets {
+        let entity = ctx.map_entity(entity);
         entity_serde::serialize_entity(message, entity)?;
     }
 
@@ -174,6 +201,7 @@ fn trigger_deserialize<'a, E>(
 /// Extension trait for triggering server events.
 ///
 /// See also [`ServerTriggerAppExt`].
+#[cfg(feature = "server")]
 pub trait ServerTriggerExt {
     /// Like [`Commands::trigger`], but triggers `E` on server and locally
     /// if [`ClientId::SERVER`](crate::core::ClientId::SERVER) is a recipient of the event).
@@ -181,9 +209,13 @@ pub trait ServerTriggerExt {
 
     /// Like [`Self::server_trigger`], but allows you to specify target entities, similar to
     /// [`Commands::trigger_targets`].
+    ///
+    /// Targets are always mapped to client entities and triggered after the replication of their tick,
+    /// triggers with targets unknown to the client are discarded with an error.
     fn server_trigger_targets(&mut self, event: ToClients<impl Event>, targets: impl RemoteTargets);
 }
 
+#[cfg(feature = "server")]
 impl ServerTriggerExt for Commands<'_, '_> {
     fn server_trigger(&mut self, event: ToClients<impl Event>) {
         self.server_trigger_targets(event, []);
@@ -204,6 +236,7 @@ impl ServerTriggerExt for Commands<'_, '_> {
     }
 }
 
+#[cfg(feature = "server")]
 impl ServerTriggerExt for World {
     fn server_trigger(&mut self, event: ToClients<impl Event>) {
         self.server_trigger_targets(event, []);
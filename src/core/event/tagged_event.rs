@@ -0,0 +1,148 @@
+use std::any;
+
+use bevy::prelude::*;
+use bytes::{Buf, Bytes};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::core::postcard_utils;
+
+/**
+Enum event whose variants are identified on the wire by stable tags instead of declaration order.
+
+By default, postcard encodes enum variants by their index, so inserting or reordering
+variants breaks compatibility with peers built from an older version of the protocol.
+Tagged events encode the variant with its tag from [`Self::TAGS`] instead.
+Events with unknown tags are dropped with a warning, and extra trailing fields
+appended to a known variant are ignored, so older peers keep working after protocol additions.
+
+Register it with [`ServerEventAppExt::add_tagged_server_event`](super::server_event::ServerEventAppExt::add_tagged_server_event)
+or [`ClientEventAppExt::add_tagged_client_event`](super::client_event::ClientEventAppExt::add_tagged_client_event).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::event::tagged_event::TaggedEvent, prelude::*};
+use serde::{Deserialize, Serialize};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.add_tagged_server_event::<LobbyEvent>(ChannelKind::Ordered);
+
+#[derive(Event, Deserialize, Serialize)]
+enum LobbyEvent {
+    Joined(String),
+    // Added in a newer version, older clients will ignore it.
+    Kicked { name: String, reason: String },
+    Left(String),
+}
+
+impl TaggedEvent for LobbyEvent {
+    // `Left` keeps its tag from the previous version.
+    const TAGS: &'static [u32] = &[0, 2, 1];
+}
+```
+**/
+pub trait TaggedEvent: Event + Serialize + DeserializeOwned {
+    /// Stable tags for each variant in declaration order.
+    ///
+    /// Tags should be unique and never reused for a different variant.
+    const TAGS: &'static [u32];
+}
+
+/// Serializes a tagged event, replacing variant index with its tag.
+///
+/// Generic over the context to be usable for both server and client events.
+pub fn serialize_tagged<C, E: TaggedEvent>(
+    _ctx: &mut C,
+    event: &E,
+    message: &mut Vec<u8>,
+) -> postcard::Result<()> {
+    let mut bytes = Vec::new();
+    postcard_utils::to_extend_mut(event, &mut bytes)?;
+    let (index, variant) = postcard::take_from_bytes::<u32>(&bytes)?;
+    let tag = *E::TAGS.get(index as usize).unwrap_or_else(|| {
+        panic!(
+            "variant {index} of `{}` should have a tag",
+            any::type_name::<E>()
+        )
+    });
+
+    postcard_utils::to_extend_mut(&tag, message)?;
+    message.extend_from_slice(variant);
+
+    Ok(())
+}
+
+/// Deserializes a tagged event, replacing its tag with variant index.
+///
+/// Returns [`postcard::Error::DeserializeBadEnum`] for unknown tags.
+/// Generic over the context to be usable for both server and client events.
+pub fn deserialize_tagged<C, E: TaggedEvent>(
+    _ctx: &mut C,
+    message: &mut Bytes,
+) -> postcard::Result<E> {
+    let tag: u32 = postcard_utils::from_buf(message)?;
+    let Some(index) = E::TAGS.iter().position(|&known| known == tag) else {
+        debug!("received unknown tag {tag} for `{}`", any::type_name::<E>());
+        message.advance(message.remaining());
+        return Err(postcard::Error::DeserializeBadEnum);
+    };
+
+    let mut index_bytes = Vec::new();
+    postcard_utils::to_extend_mut(&(index as u32), &mut index_bytes)?;
+    let event = postcard_utils::from_buf(&mut index_bytes.chain(&mut *message))?;
+
+    // Ignore fields that were appended in newer versions.
+    message.advance(message.remaining());
+
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[test]
+    fn stable_tags() {
+        let mut message = Vec::new();
+        serialize_tagged(&mut (), &NewEvent::Left(1), &mut message).unwrap();
+
+        let event: OldEvent = deserialize_tagged(&mut (), &mut message.into()).unwrap();
+        assert_eq!(event, OldEvent::Left(1));
+    }
+
+    #[test]
+    fn unknown_tag() {
+        let mut message = Vec::new();
+        serialize_tagged(&mut (), &NewEvent::Kicked(1, 2), &mut message).unwrap();
+
+        let mut message = message.into();
+        let result = deserialize_tagged::<_, OldEvent>(&mut (), &mut message);
+        assert!(matches!(result, Err(postcard::Error::DeserializeBadEnum)));
+        assert!(message.is_empty());
+    }
+
+    #[derive(Event, Serialize, Deserialize, Debug, PartialEq, Eq)]
+    enum OldEvent {
+        Joined(u8),
+        Left(u8),
+    }
+
+    impl TaggedEvent for OldEvent {
+        const TAGS: &'static [u32] = &[0, 1];
+    }
+
+    #[derive(Event, Serialize, Deserialize, Debug, PartialEq, Eq)]
+    enum NewEvent {
+        Joined(u8),
+        Kicked(u8, u8),
+        Left(u8),
+    }
+
+    impl TaggedEvent for NewEvent {
+        const TAGS: &'static [u32] = &[0, 2, 1];
+    }
+}
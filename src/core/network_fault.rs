@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use thiserror::Error;
+
+use super::ClientId;
+
+/// Controls how faults caused by malformed input from the messaging backend or a remote peer
+/// are handled.
+///
+/// Faults are collected by [`RepliconServer`](super::replicon_server::RepliconServer) and
+/// [`RepliconClient`](super::replicon_client::RepliconClient) and processed once per frame.
+/// The offending input is always discarded.
+///
+/// By default panics in debug builds to surface backend bugs early and emits [`NetworkFault`]
+/// events in release builds, so a single malformed message can't abort a production server.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FaultPolicy {
+    /// Panic on the first fault.
+    Panic,
+    /// Log each fault and emit it as a [`NetworkFault`] event.
+    Emit,
+}
+
+impl FaultPolicy {
+    /// Handles the fault according to the policy.
+    pub(crate) fn handle(self, fault: NetworkFault, fault_events: &mut EventWriter<NetworkFault>) {
+        match self {
+            FaultPolicy::Panic => panic!("{fault}"),
+            FaultPolicy::Emit => {
+                error!("{fault}");
+                fault_events.send(fault);
+            }
+        }
+    }
+}
+
+impl Default for FaultPolicy {
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            Self::Panic
+        } else {
+            Self::Emit
+        }
+    }
+}
+
+/// An event for a fault caused by malformed input.
+///
+/// Emitted only with [`FaultPolicy::Emit`].
+#[derive(Event, Error, Clone, Debug, PartialEq, Eq)]
+pub enum NetworkFault {
+    /// A message was received or requested over a channel that wasn't registered.
+    #[error("channel {channel_id} is not registered")]
+    UnknownChannel {
+        /// Sender of the message if it was received on the server.
+        client_id: Option<ClientId>,
+        /// ID of the requested channel.
+        channel_id: u8,
+    },
+
+    /// The messaging backend reported a disconnect for a client that wasn't connected
+    /// or a replication message was received from a client without enabled replication.
+    #[error("`{0:?}` is not connected or replicated")]
+    UnknownClient(ClientId),
+
+    /// A replication message from the server couldn't be decoded.
+    #[error("unable to apply replication message: {0}")]
+    Replication(postcard::Error),
+}
@@ -0,0 +1,34 @@
+//! Wire format of replication messages.
+//!
+//! Contains only encoding primitives that don't require a Bevy world, so external tools
+//! (packet analyzers, relay servers, server meshes) can read and write messages
+//! without running an app. It's the only module available without the `bevy` feature.
+//!
+//! Update messages start with [`ProtocolVersion`](protocol_version::ProtocolVersion),
+//! [`CompressionKind`](compression_kind::CompressionKind),
+//! [`UpdateMessageFlags`](update_message_flags::UpdateMessageFlags),
+//! [`ReplicationEpoch`](replication_epoch::ReplicationEpoch) if it's not the default,
+//! [`RepliconTick`](replicon_tick::RepliconTick) and the number of ticks covered by the message
+//! if it's not 1. Presence of the optional fields is indicated by the flags.
+//! Each remaining flag is followed by an array in the order of the flags, where only the last one is
+//! [`ArrayKind::Dynamic`](array::ArrayKind::Dynamic). Entities are written using [`entity`].
+//!
+//! Mutate messages start with [`ProtocolVersion`](protocol_version::ProtocolVersion),
+//! [`CompressionKind`](compression_kind::CompressionKind),
+//! [`ReplicationEpoch`](replication_epoch::ReplicationEpoch), the tick of the update message they
+//! depend on, [`RepliconTick`](replicon_tick::RepliconTick),
+//! optionally the number of mutate messages for the tick and the mutate index.
+//! They are followed by entities with the size of their mutations and the mutations themselves.
+//!
+//! If a message is compressed, everything after [`CompressionKind`](compression_kind::CompressionKind)
+//! is a single compressed frame.
+
+pub mod array;
+pub mod bit_packing;
+pub mod compression_kind;
+pub mod entity;
+pub mod postcard_utils;
+pub mod protocol_version;
+pub mod replication_epoch;
+pub mod replicon_tick;
+pub mod update_message_flags;
@@ -0,0 +1,86 @@
+//! Arrays inside messages.
+
+use bytes::{Buf, Bytes};
+
+use super::postcard_utils;
+
+/// Calls `f` for each element of an array and returns the number of elements.
+///
+/// `f` is expected to consume exactly one element from the message.
+pub fn read_array(
+    kind: ArrayKind,
+    message: &mut Bytes,
+    mut f: impl FnMut(&mut Bytes) -> postcard::Result<()>,
+) -> postcard::Result<usize> {
+    match kind {
+        ArrayKind::Sized => {
+            let len = postcard_utils::from_buf(message)?;
+            for _ in 0..len {
+                (f)(message)?;
+            }
+
+            Ok(len)
+        }
+        ArrayKind::Dynamic => {
+            let mut len = 0;
+            while message.has_remaining() {
+                (f)(message)?;
+                len += 1;
+            }
+
+            Ok(len)
+        }
+    }
+}
+
+/// Type of serialized array.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ArrayKind {
+    /// Size is serialized before the array.
+    Sized,
+    /// Size is unknown, means that all bytes needs to be consumed.
+    Dynamic,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sized() -> postcard::Result<()> {
+        let mut message = Vec::new();
+        postcard_utils::to_extend_mut(&2usize, &mut message)?;
+        postcard_utils::to_extend_mut(&1u8, &mut message)?;
+        postcard_utils::to_extend_mut(&2u8, &mut message)?;
+        postcard_utils::to_extend_mut(&3u8, &mut message)?;
+
+        let mut message = Bytes::from(message);
+        let mut values = Vec::new();
+        let len = read_array(ArrayKind::Sized, &mut message, |message| {
+            values.push(postcard_utils::from_buf::<u8, _>(message)?);
+            Ok(())
+        })?;
+        assert_eq!(len, 2);
+        assert_eq!(values, [1, 2]);
+        assert_eq!(message.len(), 1, "trailing data should be left unread");
+
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic() -> postcard::Result<()> {
+        let mut message = Vec::new();
+        postcard_utils::to_extend_mut(&1u8, &mut message)?;
+        postcard_utils::to_extend_mut(&2u8, &mut message)?;
+
+        let mut message = Bytes::from(message);
+        let len = read_array(ArrayKind::Dynamic, &mut message, |message| {
+            postcard_utils::from_buf::<u8, _>(message)?;
+            Ok(())
+        })?;
+        assert_eq!(len, 2);
+        assert!(message.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+//! Sub-byte encoding for flags, small enums and bools inside component data.
+//!
+//! Postcard writes every `bool` and small enum as a separate byte. [`BitWriter`] packs such
+//! values into shared bytes, and [`BitReader`] reads them back in the same order.
+//!
+//! Bits are written directly to the message, so the writer and the reader can be freely
+//! mixed with [`postcard_utils`](super::postcard_utils) calls. Unused bits of the last
+//! byte are padded with zeroes and skipped by the reader.
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_replicon::{
+//!     bytes::Bytes,
+//!     core::{
+//!         postcard_utils,
+//!         protocol::bit_packing::{BitReader, BitWriter},
+//!         replication::replication_registry::{
+//!             ctx::{SerializeCtx, WriteCtx},
+//!             rule_fns::RuleFns,
+//!         },
+//!     },
+//!     prelude::*,
+//! };
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(RepliconPlugins);
+//! app.replicate_with(RuleFns::new(serialize_player, deserialize_player));
+//!
+//! fn serialize_player(
+//!     _ctx: &SerializeCtx,
+//!     player: &Player,
+//!     message: &mut Vec<u8>,
+//! ) -> postcard::Result<()> {
+//!     let mut writer = BitWriter::new(message);
+//!     writer.write_bool(player.crouching);
+//!     writer.write_bool(player.sprinting);
+//!     writer.write_bits(player.team as u32, 2);
+//!     // All 3 values above fit into a single byte.
+//!
+//!     postcard_utils::to_extend_mut(&player.health, message)
+//! }
+//!
+//! fn deserialize_player(
+//!     _ctx: &mut WriteCtx,
+//!     message: &mut Bytes,
+//! ) -> postcard::Result<Player> {
+//!     let mut reader = BitReader::new(message);
+//!     let crouching = reader.read_bool()?;
+//!     let sprinting = reader.read_bool()?;
+//!     let team = match reader.read_bits(2)? {
+//!         0 => Team::Red,
+//!         1 => Team::Blue,
+//!         2 => Team::Spectator,
+//!         _ => return Err(postcard::Error::DeserializeBadEnum),
+//!     };
+//!
+//!     let health = postcard_utils::from_buf(message)?;
+//!
+//!     Ok(Player {
+//!         crouching,
+//!         sprinting,
+//!         team,
+//!         health,
+//!     })
+//! }
+//!
+//! #[derive(Component)]
+//! struct Player {
+//!     crouching: bool,
+//!     sprinting: bool,
+//!     team: Team,
+//!     health: u16,
+//! }
+//!
+//! #[derive(Clone, Copy)]
+//! enum Team {
+//!     Red,
+//!     Blue,
+//!     Spectator,
+//! }
+//! ```
+
+use bytes::Buf;
+
+/// Writes values with the specified number of bits into a message.
+///
+/// See the module-level documentation for details.
+pub struct BitWriter<'a> {
+    message: &'a mut Vec<u8>,
+
+    /// Number of bits used in the last byte of the message by this writer.
+    ///
+    /// 0 means that the next bit requires a new byte.
+    used_bits: u32,
+}
+
+impl<'a> BitWriter<'a> {
+    /// Creates a writer that appends bits to the end of the message.
+    pub fn new(message: &'a mut Vec<u8>) -> Self {
+        Self {
+            message,
+            used_bits: 0,
+        }
+    }
+
+    /// Writes a single bit.
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_bits(value.into(), 1);
+    }
+
+    /// Writes the lowest `bits` bits of `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is greater than 32 or in debug builds if `value` doesn't fit into `bits`.
+    pub fn write_bits(&mut self, mut value: u32, mut bits: u32) {
+        assert!(bits <= u32::BITS, "can't write more than 32 bits at once");
+        debug_assert!(
+            bits == u32::BITS || value >> bits == 0,
+            "`{value}` should fit into {bits} bits"
+        );
+
+        while bits > 0 {
+            if self.used_bits == 0 {
+                self.message.push(0);
+            }
+
+            let written = bits.min(u8::BITS - self.used_bits);
+            let mask = (1 << written) - 1;
+            let last = self.message.last_mut().unwrap();
+            *last |= ((value & mask) << self.used_bits) as u8;
+
+            value >>= written;
+            bits -= written;
+            self.used_bits = (self.used_bits + written) % u8::BITS;
+        }
+    }
+}
+
+/// Reads values written by [`BitWriter`].
+///
+/// See the module-level documentation for details.
+pub struct BitReader<'a, B: Buf> {
+    buf: &'a mut B,
+
+    /// Last byte taken from the buffer.
+    byte: u8,
+
+    /// Number of bits in [`Self::byte`] that weren't read yet.
+    remaining_bits: u32,
+}
+
+impl<'a, B: Buf> BitReader<'a, B> {
+    /// Creates a reader that takes bytes from the buffer as needed.
+    pub fn new(buf: &'a mut B) -> Self {
+        Self {
+            buf,
+            byte: 0,
+            remaining_bits: 0,
+        }
+    }
+
+    /// Reads a single bit.
+    pub fn read_bool(&mut self) -> postcard::Result<bool> {
+        self.read_bits(1).map(|value| value != 0)
+    }
+
+    /// Reads `bits` bits into the lowest bits of the returned value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is greater than 32.
+    pub fn read_bits(&mut self, bits: u32) -> postcard::Result<u32> {
+        assert!(bits <= u32::BITS, "can't read more than 32 bits at once");
+
+        let mut value = 0;
+        let mut read_bits = 0;
+        while read_bits < bits {
+            if self.remaining_bits == 0 {
+                if !self.buf.has_remaining() {
+                    return Err(postcard::Error::DeserializeUnexpectedEnd);
+                }
+                self.byte = self.buf.get_u8();
+                self.remaining_bits = u8::BITS;
+            }
+
+            let read = (bits - read_bits).min(self.remaining_bits);
+            let offset = u8::BITS - self.remaining_bits;
+            let mask = (1 << read) - 1;
+            value |= ((self.byte as u32 >> offset) & mask) << read_bits;
+
+            read_bits += read;
+            self.remaining_bits -= read;
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn shared_byte() {
+        let mut message = Vec::new();
+        let mut writer = BitWriter::new(&mut message);
+        writer.write_bool(true);
+        writer.write_bool(false);
+        writer.write_bits(0b101, 3);
+        assert_eq!(message, [0b10101]);
+
+        let mut message = Bytes::from(message);
+        let mut reader = BitReader::new(&mut message);
+        assert!(reader.read_bool().unwrap());
+        assert!(!reader.read_bool().unwrap());
+        assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+        assert!(message.is_empty());
+    }
+
+    #[test]
+    fn across_bytes() {
+        let mut message = vec![42];
+        let mut writer = BitWriter::new(&mut message);
+        writer.write_bits(0b111, 3);
+        writer.write_bits(u32::MAX, 32);
+        writer.write_bits(0x1234, 13);
+        writer.write_bits(0, 0);
+        assert_eq!(message.len(), 1 + 6);
+        message.push(7);
+
+        let mut message = Bytes::from(message);
+        assert_eq!(message.get_u8(), 42);
+        let mut reader = BitReader::new(&mut message);
+        assert_eq!(reader.read_bits(3).unwrap(), 0b111);
+        assert_eq!(reader.read_bits(32).unwrap(), u32::MAX);
+        assert_eq!(reader.read_bits(13).unwrap(), 0x1234);
+        assert_eq!(reader.read_bits(0).unwrap(), 0);
+        assert_eq!(message.get_u8(), 7, "unused bits should be skipped");
+    }
+
+    #[test]
+    fn unexpected_end() {
+        let mut message = Bytes::from_static(&[u8::MAX]);
+        let mut reader = BitReader::new(&mut message);
+        assert_eq!(reader.read_bits(6).unwrap(), 0b111111);
+        assert!(reader.read_bits(3).is_err());
+    }
+}
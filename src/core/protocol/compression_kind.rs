@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Compression of a replication message.
+///
+/// Written right after [`ProtocolVersion`](super::protocol_version::ProtocolVersion)
+/// in every update and mutate message. If the message is compressed, all bytes after it
+/// are a single compressed frame that contains the rest of the message.
+///
+/// Messages can be compressed by the server with the `zstd` feature.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum CompressionKind {
+    /// The message is not compressed.
+    #[default]
+    None,
+    /// The message is compressed with zstd.
+    ///
+    /// Decompression requires the `zstd` feature.
+    Zstd,
+    /// The message is compressed with zstd using a shared dictionary.
+    ///
+    /// Decompressed by `DictionaryCompressionPlugin` before replication receives the message.
+    Dictionary,
+}
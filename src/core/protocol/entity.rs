@@ -0,0 +1,75 @@
+//! Compact encoding of entities.
+//!
+//! Entities are represented as bits in the layout of Bevy's `Entity::to_bits`:
+//! generation in the upper 32 bits and index in the lower 32 bits.
+
+use bytes::Bytes;
+
+use super::postcard_utils;
+
+/// Deserializes entity bits from compressed index and generation.
+///
+/// For details see [`serialize`].
+pub fn deserialize(message: &mut Bytes) -> postcard::Result<u64> {
+    let flagged_index: u64 = postcard_utils::from_buf(message)?;
+    let has_generation = (flagged_index & 1) > 0;
+    let generation = if has_generation {
+        postcard_utils::from_buf::<u32, _>(message)? + 1
+    } else {
+        1u32
+    };
+
+    Ok((generation as u64) << 32 | (flagged_index >> 1))
+}
+
+/// Serializes entity bits by writing its index and generation as separate varints.
+///
+/// The index is first prepended with a bit flag to indicate if the generation
+/// is serialized or not. It is not serialized if <= 1; note that generations are [`NonZeroU32`](std::num::NonZeroU32)
+/// and a value of zero is used in `Option<Entity>` to signify [`None`], so generation 1 is the first
+/// generation.
+///
+/// See also [`deserialize`].
+pub fn serialize(message: &mut Vec<u8>, bits: u64) -> postcard::Result<()> {
+    let index = bits as u32;
+    let generation = (bits >> 32) as u32;
+
+    let mut flagged_index = (index as u64) << 1;
+    let flag = generation > 1;
+    flagged_index |= flag as u64;
+
+    postcard_utils::to_extend_mut(&flagged_index, message)?;
+    if flag {
+        postcard_utils::to_extend_mut(&(generation - 1), message)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() -> postcard::Result<()> {
+        for bits in [1 << 32, 1 << 32 | 42, 3 << 32 | u32::MAX as u64] {
+            let mut message = Vec::new();
+            serialize(&mut message, bits)?;
+
+            let mut message = Bytes::from(message);
+            assert_eq!(deserialize(&mut message)?, bits);
+            assert!(message.is_empty());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn first_generation_omitted() -> postcard::Result<()> {
+        let mut message = Vec::new();
+        serialize(&mut message, 1 << 32 | 5)?;
+        assert_eq!(message, [10]);
+
+        Ok(())
+    }
+}
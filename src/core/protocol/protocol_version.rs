@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Version of the replication wire format.
+///
+/// Written as the first byte of every update and mutate message.
+/// Clients ignore replication messages with a different version and log an error.
+///
+/// # Compatibility policy
+///
+/// The version is incremented on any change to the encoding of replication messages,
+/// including changes in patch releases. Peers are compatible only if their versions are equal.
+///
+/// Not covered by the version:
+/// - Serialization of components and events, since it's defined by the user.
+/// - Registration order of replicated components and events, which should match on both peers.
+/// - Messages of the messaging backend.
+///
+/// To pin compatibility in your project, you can compare messages produced from a known world
+/// using `MessageSinks::capture` with stored bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct ProtocolVersion(u8);
+
+impl ProtocolVersion {
+    /// Version of the wire format implemented by this crate.
+    pub const CURRENT: Self = Self(5);
+
+    /// Creates a version from its raw value.
+    pub const fn new(version: u8) -> Self {
+        Self(version)
+    }
+
+    /// Returns the raw value.
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
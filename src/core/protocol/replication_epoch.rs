@@ -0,0 +1,36 @@
+use postcard::experimental::max_size::MaxSize;
+use serde::{Deserialize, Serialize};
+
+/// Counter of full world resets on the server.
+///
+/// Written right after [`ProtocolVersion`](super::protocol_version::ProtocolVersion)
+/// and [`CompressionKind`](super::compression_kind::CompressionKind) in every mutate message.
+/// Update messages include it after [`UpdateMessageFlags`](super::update_message_flags::UpdateMessageFlags)
+/// only if it's not the default.
+/// Clients discard all replicated state when they receive an update message with a different epoch.
+///
+/// All operations on it are wrapping.
+///
+/// See also `ServerEpoch` and `WorldReset`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, MaxSize)]
+pub struct ReplicationEpoch(u8);
+
+impl ReplicationEpoch {
+    /// Creates a new instance wrapping the given value.
+    #[inline]
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    /// Gets the value of this epoch.
+    #[inline]
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the epoch that follows this one.
+    #[inline]
+    pub fn next(self) -> Self {
+        Self(self.0.wrapping_add(1))
+    }
+}
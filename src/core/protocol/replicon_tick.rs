@@ -6,12 +6,11 @@ use std::{
 use postcard::experimental::max_size::MaxSize;
 use serde::{Deserialize, Serialize};
 
-/// Like [`Tick`](bevy::ecs::component::Tick), but for replication.
+/// Like Bevy's `Tick`, but for replication.
 ///
 /// All operations on it are wrapping.
 ///
-/// See also [`ServerUpdateTick`](crate::client::ServerUpdateTick) and
-/// [`ServerTick`](crate::server::server_tick::ServerTick).
+/// See also `ServerUpdateTick` and `ServerTick`.
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, MaxSize)]
 pub struct RepliconTick(u32);
 
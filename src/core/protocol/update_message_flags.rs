@@ -5,19 +5,36 @@ bitflags! {
     /// Types of data included in the update message if the bit is set.
     ///
     /// Serialized at the beginning of the message.
+    ///
+    /// [`Self::EPOCH`] and [`Self::TICKS_COVERED`] mark optional header fields,
+    /// other flags mark arrays returned by [`Self::sections`].
     #[derive(Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
-    pub(crate) struct UpdateMessageFlags: u8 {
+    pub struct UpdateMessageFlags: u8 {
         const MAPPINGS = 0b00000001;
         const DESPAWNS = 0b00000010;
-        const REMOVALS = 0b00000100;
-        const CHANGES = 0b00001000;
+        const DESPAWN_BATCHES = 0b00000100;
+        const REMOVALS = 0b00001000;
+        const RESOURCES = 0b00010000;
+        const CHANGES = 0b00100000;
+        const EPOCH = 0b01000000;
+        const TICKS_COVERED = 0b10000000;
     }
 }
 
 impl UpdateMessageFlags {
+    /// Returns flags without the header fields.
+    pub fn sections(self) -> UpdateMessageFlags {
+        self.difference(UpdateMessageFlags::EPOCH | UpdateMessageFlags::TICKS_COVERED)
+    }
+
     /// Returns the last set flag in the message.
-    pub(crate) fn last(self) -> UpdateMessageFlags {
-        debug_assert!(!self.is_empty());
+    ///
+    /// Returns empty flags if no flags are set.
+    pub fn last(self) -> UpdateMessageFlags {
+        if self.is_empty() {
+            return self;
+        }
+
         let zeroes = u8::BITS - 1 - self.bits().leading_zeros();
         UpdateMessageFlags::from_bits_retain(1 << zeroes)
     }
@@ -38,12 +55,16 @@ mod tests {
             UpdateMessageFlags::MAPPINGS
         );
         assert_eq!(
-            UpdateMessageFlags::all().last(),
+            UpdateMessageFlags::all().sections().last(),
             UpdateMessageFlags::CHANGES
         );
         assert_eq!(
             (UpdateMessageFlags::DESPAWNS | UpdateMessageFlags::REMOVALS).last(),
             UpdateMessageFlags::REMOVALS
         );
+        assert_eq!(
+            UpdateMessageFlags::empty().last(),
+            UpdateMessageFlags::empty()
+        );
     }
 }
@@ -1,11 +1,17 @@
 pub mod command_markers;
 pub mod deferred_entity;
+pub(crate) mod entity_batch;
+pub mod field_groups;
+pub mod history_segment;
 pub(crate) mod mutate_index;
+#[cfg(feature = "server")]
 pub mod replicated_clients;
+pub mod replicated_resources;
+pub mod replicated_singletons;
+pub mod replication_prefabs;
 pub mod replication_registry;
 pub mod replication_rules;
 pub mod track_mutate_messages;
-pub mod update_message_flags;
 
 use bevy::prelude::*;
 
@@ -1,4 +1,4 @@
-use std::cmp::Reverse;
+use std::{any, cmp::Reverse};
 
 use bevy::{ecs::component::ComponentId, prelude::*};
 
@@ -24,6 +24,9 @@ pub trait AppMarkerExt {
     ///
     /// This function registers markers with default [`MarkerConfig`].
     /// See also [`Self::register_marker_with`].
+    ///
+    /// Registering the same marker again only updates its configuration,
+    /// keeping previously assigned functions.
     fn register_marker<M: Component>(&mut self) -> &mut Self;
 
     /// Same as [`Self::register_marker`], but also accepts marker configuration.
@@ -133,13 +136,20 @@ impl AppMarkerExt for App {
     fn register_marker_with<M: Component>(&mut self, config: MarkerConfig) -> &mut Self {
         let component_id = self.world_mut().register_component::<M>();
         let mut command_markers = self.world_mut().resource_mut::<CommandMarkers>();
+        let old_marker_id = command_markers.remove(component_id);
         let marker_id = command_markers.insert(CommandMarker {
             component_id,
             config,
         });
 
         let mut replicaton_fns = self.world_mut().resource_mut::<ReplicationRegistry>();
-        replicaton_fns.register_marker(marker_id);
+        match old_marker_id {
+            Some(old_marker_id) => {
+                debug!("re-registering marker `{}`", any::type_name::<M>());
+                replicaton_fns.move_marker(old_marker_id, marker_id);
+            }
+            None => replicaton_fns.register_marker(marker_id),
+        }
 
         self
     }
@@ -192,6 +202,20 @@ impl CommandMarkers {
         CommandMarkerIndex(index)
     }
 
+    /// Removes a marker and returns its old index if it was registered.
+    ///
+    /// May invalidate previously returned [`CommandMarkerIndex`].
+    fn remove(&mut self, component_id: ComponentId) -> Option<CommandMarkerIndex> {
+        let index = self
+            .0
+            .iter()
+            .position(|marker| marker.component_id == component_id)?;
+
+        self.0.remove(index);
+
+        Some(CommandMarkerIndex(index))
+    }
+
     /// Returns marker ID from its component ID.
     fn marker_id(&self, component_id: ComponentId) -> CommandMarkerIndex {
         let index = self
@@ -203,6 +227,13 @@ impl CommandMarkers {
         CommandMarkerIndex(index)
     }
 
+    /// Returns registered markers and their configurations in order of their priority.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (ComponentId, &MarkerConfig)> {
+        self.0
+            .iter()
+            .map(|marker| (marker.component_id, &marker.config))
+    }
+
     pub(super) fn iter_require_history(&self) -> impl Iterator<Item = bool> + '_ {
         self.0.iter().map(|marker| marker.config.need_history)
     }
@@ -220,7 +251,7 @@ struct CommandMarker {
 }
 
 /// Parameters for a marker.
-#[derive(Default)]
+#[derive(Default, Clone, Copy, Debug, Reflect)]
 pub struct MarkerConfig {
     /// Priority of this marker.
     ///
@@ -248,6 +279,13 @@ pub(crate) struct EntityMarkers {
 }
 
 impl EntityMarkers {
+    pub(crate) fn new(markers: &CommandMarkers) -> Self {
+        Self {
+            markers: Vec::with_capacity(markers.0.len()),
+            need_history: false,
+        }
+    }
+
     pub(crate) fn read<'a>(
         &'a mut self,
         markers: &CommandMarkers,
@@ -281,11 +319,7 @@ impl EntityMarkers {
 
 impl FromWorld for EntityMarkers {
     fn from_world(world: &mut World) -> Self {
-        let markers = world.resource::<CommandMarkers>();
-        Self {
-            markers: Vec::with_capacity(markers.0.len()),
-            need_history: false,
-        }
+        Self::new(world.resource::<CommandMarkers>())
     }
 }
 
@@ -339,6 +373,34 @@ mod tests {
         assert_eq!(priorities, [2, 1, 0, 0]);
     }
 
+    #[test]
+    fn reregistration() {
+        let mut app = App::new();
+        app.init_resource::<CommandMarkers>()
+            .init_resource::<ReplicationRegistry>()
+            .register_marker::<DummyMarkerA>()
+            .register_marker::<DummyMarkerB>()
+            .set_marker_fns::<DummyMarkerA, DummyComponent>(
+                command_fns::default_write,
+                command_fns::default_remove::<DummyComponent>,
+            )
+            .register_marker_with::<DummyMarkerA>(MarkerConfig {
+                priority: 1,
+                ..Default::default()
+            });
+
+        let markers = app.world().resource::<CommandMarkers>();
+        let priorities: Vec<_> = markers
+            .0
+            .iter()
+            .map(|marker| marker.config.priority)
+            .collect();
+        assert_eq!(priorities, [1, 0]);
+
+        let marker_a = app.world().component_id::<DummyMarkerA>().unwrap();
+        assert_eq!(*markers.marker_id(marker_a), 0);
+    }
+
     #[derive(Component)]
     struct DummyMarkerA;
 
@@ -0,0 +1,140 @@
+//! Compact encoding for large sets of entities, such as entities that lost visibility at once.
+
+use bevy::prelude::*;
+#[cfg(feature = "client")]
+use bytes::Bytes;
+
+use crate::core::postcard_utils;
+
+/// Minimum number of entities to encode as a batch.
+///
+/// Smaller groups are cheaper to serialize individually.
+#[cfg(feature = "server")]
+pub(crate) const MIN_BATCH_LEN: usize = 8;
+
+/// Maximum distance between indices of adjacent entities in a batch.
+#[cfg(feature = "server")]
+const MAX_GAP: u32 = 16;
+
+/// Maximum number of indices covered by a single batch.
+#[cfg(feature = "server")]
+const MAX_SPAN: u32 = 1024;
+
+/// Sorts entities and splits them into groups that can be encoded as batches.
+///
+/// Groups shorter than [`MIN_BATCH_LEN`] should be serialized individually.
+#[cfg(feature = "server")]
+pub(crate) fn split(entities: &mut [Entity]) -> impl Iterator<Item = &[Entity]> {
+    entities.sort_unstable_by_key(|entity| (entity.generation(), entity.index()));
+
+    let mut rest = &*entities;
+    std::iter::from_fn(move || {
+        let (first, _) = rest.split_first()?;
+        let mut len = 1;
+        for pair in rest.windows(2) {
+            let [prev, next] = pair else { unreachable!() };
+            if next.generation() != first.generation()
+                || next.index() - prev.index() > MAX_GAP
+                || next.index() - first.index() >= MAX_SPAN
+            {
+                break;
+            }
+            len += 1;
+        }
+
+        let (group, remaining) = rest.split_at(len);
+        rest = remaining;
+        Some(group)
+    })
+}
+
+/// Serializes a group of entities from [`split`] as a batch.
+///
+/// Written as generation, index of the first entity and a bitset where each bit
+/// indicates presence of an entity with the corresponding index offset.
+#[cfg(feature = "server")]
+pub(crate) fn serialize(message: &mut Vec<u8>, group: &[Entity]) -> postcard::Result<()> {
+    let first = group.first().expect("group shouldn't be empty");
+    let last = group.last().unwrap();
+
+    let span = (last.index() - first.index()) as usize + 1;
+    let mut bits = vec![0u8; span.div_ceil(u8::BITS as usize)];
+    for entity in group {
+        let offset = (entity.index() - first.index()) as usize;
+        bits[offset / u8::BITS as usize] |= 1 << (offset % u8::BITS as usize);
+    }
+
+    postcard_utils::to_extend_mut(&first.generation(), message)?;
+    postcard_utils::to_extend_mut(&first.index(), message)?;
+    postcard_utils::to_extend_mut(&bits, message)?;
+
+    Ok(())
+}
+
+/// Deserializes a batch written by [`serialize`] and calls `f` for each entity.
+#[cfg(feature = "client")]
+pub(crate) fn deserialize(
+    message: &mut Bytes,
+    mut f: impl FnMut(Entity) -> postcard::Result<()>,
+) -> postcard::Result<()> {
+    let generation: u32 = postcard_utils::from_buf(message)?;
+    let first_index: u32 = postcard_utils::from_buf(message)?;
+    let bits: Vec<u8> = postcard_utils::from_buf(message)?;
+
+    for (byte_index, byte) in bits.into_iter().enumerate() {
+        for bit in 0..u8::BITS {
+            if byte & (1 << bit) != 0 {
+                let index = first_index + byte_index as u32 * u8::BITS + bit;
+                let bits = (generation as u64) << 32 | index as u64;
+                let entity =
+                    Entity::try_from_bits(bits).map_err(|_| postcard::Error::SerdeDeCustom)?;
+                (f)(entity)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "client", feature = "server"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_groups() {
+        let mut entities: Vec<_> = (0..10)
+            .chain(100..103)
+            .map(Entity::from_raw)
+            .chain([Entity::from_bits(2 << 32 | 5)])
+            .rev()
+            .collect();
+
+        let groups: Vec<_> = split(&mut entities).map(<[Entity]>::len).collect();
+        assert_eq!(groups, [10, 3, 1]);
+    }
+
+    #[test]
+    fn round_trip() -> postcard::Result<()> {
+        let mut entities: Vec<_> = [0, 1, 3, 8, 9, 20]
+            .into_iter()
+            .map(|index| Entity::from_bits(3 << 32 | index))
+            .collect();
+
+        let mut message = Vec::new();
+        for group in split(&mut entities) {
+            serialize(&mut message, group)?;
+        }
+
+        let mut message = Bytes::from(message);
+        let mut deserialized = Vec::new();
+        deserialize(&mut message, |entity| {
+            deserialized.push(entity);
+            Ok(())
+        })?;
+
+        assert_eq!(deserialized, entities);
+        assert!(message.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+//! Replication of only changed parts of large components.
+
+use bytes::Bytes;
+
+pub use bevy_replicon_derive::FieldGroups;
+
+/**
+Splits a component into groups of fields that are replicated independently.
+
+Mutations of such components contain a change mask followed by only the groups
+that changed since the last acknowledged mutation for the client. This avoids
+splitting large structs into many tiny components just to save bandwidth.
+Insertions always contain all groups.
+
+Usually derived. Each field forms its own group, unless fields are combined
+with the `#[field_group(name)]` attribute. Groups are numbered in order of their first appearance.
+Fields need to implement [`Serialize`](serde::Serialize) and [`DeserializeOwned`](serde::de::DeserializeOwned),
+entities inside fields are not mapped.
+
+To replicate a component with groups, use [`RuleFns::field_groups`](super::replication_registry::rule_fns::RuleFns::field_groups).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replication::{field_groups::FieldGroups, replication_registry::rule_fns::RuleFns},
+    prelude::*,
+};
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.replicate_with(RuleFns::<Character>::field_groups());
+
+#[derive(Component, Default, FieldGroups)]
+struct Character {
+    #[field_group(vitals)]
+    health: u32,
+    #[field_group(vitals)]
+    mana: u32,
+    position: Vec3,
+    name: String,
+}
+```
+**/
+pub trait FieldGroups {
+    /// Number of groups.
+    ///
+    /// Shouldn't exceed 64.
+    const GROUPS: u8;
+
+    /// Serializes all fields of a group into a message.
+    fn serialize_group(&self, group: u8, message: &mut Vec<u8>) -> postcard::Result<()>;
+
+    /// Deserializes all fields of a group from a message.
+    fn deserialize_group(&mut self, group: u8, message: &mut Bytes) -> postcard::Result<()>;
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::core::replicon_tick::RepliconTick;
+
+/// Serialized replication history of specific entities within a range of ticks.
+///
+/// Extracted on server with
+/// [`ReplicationHistory::extract`](crate::server::replication_history::ReplicationHistory::extract)
+/// and played back on client with [`SegmentPlayback`](crate::client::segment_playback::SegmentPlayback).
+/// Can be sent as a regular server event, useful for killcams or short replays.
+///
+/// The first frame contains the full state of entities at the beginning of the range,
+/// the following frames contain only changes.
+#[derive(Event, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct HistorySegment {
+    pub(crate) frames: Vec<HistoryFrame>,
+}
+
+impl HistorySegment {
+    /// Returns the number of frames in the segment.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if the segment has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns iterator over ticks of all frames, from oldest to newest.
+    pub fn ticks(&self) -> impl Iterator<Item = RepliconTick> + '_ {
+        self.frames.iter().map(|frame| frame.tick)
+    }
+}
+
+/// Changes of entities for a single tick.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct HistoryFrame {
+    pub(crate) tick: RepliconTick,
+
+    /// Entity changes in the same format as inside the update message.
+    pub(crate) changes: Vec<u8>,
+}
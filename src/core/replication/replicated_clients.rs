@@ -9,7 +9,7 @@ use bevy::{
 };
 
 use super::mutate_index::MutateIndex;
-use crate::core::{replicon_tick::RepliconTick, ClientId};
+use crate::core::{channels::ReplicationChannel, replicon_tick::RepliconTick, ClientId};
 use client_visibility::ClientVisibility;
 
 /// Stores information about connected clients which are enabled for replication.
@@ -22,6 +22,7 @@ pub struct ReplicatedClients {
     clients: Vec<ReplicatedClient>,
     policy: VisibilityPolicy,
     replicate_after_connect: bool,
+    bandwidth_cap: Option<BandwidthCap>,
 }
 
 impl ReplicatedClients {
@@ -33,6 +34,7 @@ impl ReplicatedClients {
             clients: Default::default(),
             policy,
             replicate_after_connect,
+            bandwidth_cap: None,
         }
     }
 
@@ -41,6 +43,38 @@ impl ReplicatedClients {
         self.policy
     }
 
+    /// Switches all clients to a different [`VisibilityPolicy`].
+    ///
+    /// Lists of the previous policy are discarded and each client starts with an empty list,
+    /// which can be filled right after the call.
+    /// On the next replication tick, entities that become hidden will be despawned on clients
+    /// and entities that become visible will be sent as new.
+    ///
+    /// Does nothing for clients that already use this policy.
+    pub fn set_visibility_policy(&mut self, policy: VisibilityPolicy) {
+        debug!("changing visibility policy to `{policy:?}`");
+        self.policy = policy;
+        for client in &mut self.clients {
+            client.visibility.set_policy(policy);
+        }
+    }
+
+    /// Returns the [`BandwidthCap`] assigned to newly replicated clients.
+    pub fn bandwidth_cap(&self) -> Option<BandwidthCap> {
+        self.bandwidth_cap
+    }
+
+    /// Sets the bandwidth cap for all clients, including clients that will be replicated later.
+    ///
+    /// Individual clients can override it with [`ReplicatedClient::set_bandwidth_cap`].
+    pub fn set_bandwidth_cap(&mut self, cap: Option<BandwidthCap>) {
+        debug!("changing bandwidth cap to `{cap:?}`");
+        self.bandwidth_cap = cap;
+        for client in &mut self.clients {
+            client.set_bandwidth_cap(cap);
+        }
+    }
+
     /// Returns if clients will automatically have replication enabled for them after they connect.
     pub fn replicate_after_connect(&self) -> bool {
         self.replicate_after_connect
@@ -115,6 +149,58 @@ impl ReplicatedClients {
         self.clients.is_empty()
     }
 
+    /**
+    Adds a virtual client without a backend connection.
+
+    Virtual clients go through the same visibility and replication logic as regular clients,
+    but messages for them are not sent to the messaging backend. Instead, the received data
+    is counted in [`ReplicatedClient::virtual_stats`] and all mutate messages are acknowledged
+    immediately. Server events are not sent to virtual clients.
+
+    Useful for server-side bots that need to reuse visibility logic or for load testing without sockets.
+    The ID should not collide with IDs assigned by the messaging backend.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    fn spawn_bot(mut replicated_clients: ResMut<ReplicatedClients>) {
+        replicated_clients.add_virtual_client(ClientId::new(u64::MAX));
+    }
+    ```
+    **/
+    pub fn add_virtual_client(&mut self, client_id: ClientId) {
+        if self.clients.iter().any(|client| client.id == client_id) {
+            warn!("ignoring attempt to add virtual `{client_id:?}` that already has replication enabled");
+            return;
+        }
+
+        debug!("adding virtual `{client_id:?}`");
+        let mut client = ReplicatedClient::new(client_id, self.policy);
+        client.bandwidth_cap = self.bandwidth_cap;
+        client.virtual_stats = Some(Default::default());
+        self.clients.push(client);
+    }
+
+    /// Removes a client added with [`Self::add_virtual_client`].
+    ///
+    /// Returns `true` if the client was removed.
+    pub fn remove_virtual_client(&mut self, client_id: ClientId) -> bool {
+        let Some(index) = self
+            .clients
+            .iter()
+            .position(|client| client.id == client_id && client.is_virtual())
+        else {
+            return false;
+        };
+
+        debug!("removing virtual `{client_id:?}`");
+        self.clients.remove(index);
+        true
+    }
+
     /// Initializes a new [`ReplicatedClient`] for this client.
     ///
     /// Reuses the memory from the buffers if available.
@@ -126,12 +212,13 @@ impl ReplicatedClients {
 
         debug!("starting replication for `{client_id:?}`");
 
-        let client = if let Some(mut client) = client_buffers.clients.pop() {
-            client.reset(client_id);
+        let mut client = if let Some(mut client) = client_buffers.clients.pop() {
+            client.reset(client_id, self.policy);
             client
         } else {
             ReplicatedClient::new(client_id, self.policy)
         };
+        client.bandwidth_cap = self.bandwidth_cap;
 
         self.clients.push(client);
     }
@@ -184,6 +271,31 @@ pub struct ReplicatedClient {
     /// message to arrive.
     update_tick: RepliconTick,
 
+    /// Whether replication was sent to the client at least once since replication started.
+    ///
+    /// See [`Self::is_baseline_sent`].
+    baseline_sent: bool,
+
+    /// Send mutations only on every N-th tick.
+    ///
+    /// See [`Self::set_mutations_interval`].
+    mutations_interval: u32,
+
+    /// Maximum bytes of mutations sent to the client.
+    ///
+    /// See [`Self::set_bandwidth_cap`].
+    bandwidth_cap: Option<BandwidthCap>,
+
+    /// Bytes available for [`BandwidthCap::PerSecond`].
+    ///
+    /// Can be negative if the last sent entity exceeded the allowance.
+    bandwidth_allowance: f64,
+
+    /// Time of the last [`Self::refill_bandwidth`] call.
+    ///
+    /// [`None`] if the allowance should start full.
+    bandwidth_refilled: Option<Duration>,
+
     /// Mutate message indices mapped to their info.
     mutations: HashMap<MutateIndex, MutateInfo>,
 
@@ -191,6 +303,11 @@ pub struct ReplicatedClient {
     ///
     /// See also [`Self::register_mutate_message`].
     mutate_index: MutateIndex,
+
+    /// Received data if the client is virtual.
+    ///
+    /// See [`ReplicatedClients::add_virtual_client`].
+    virtual_stats: Option<VirtualClientStats>,
 }
 
 impl ReplicatedClient {
@@ -200,8 +317,14 @@ impl ReplicatedClient {
             mutation_ticks: Default::default(),
             visibility: ClientVisibility::new(policy),
             update_tick: Default::default(),
+            baseline_sent: false,
+            mutations_interval: 1,
+            bandwidth_cap: None,
+            bandwidth_allowance: 0.0,
+            bandwidth_refilled: None,
             mutations: Default::default(),
             mutate_index: Default::default(),
+            virtual_stats: None,
         }
     }
 
@@ -220,6 +343,29 @@ impl ReplicatedClient {
         &mut self.visibility
     }
 
+    /// Returns `true` if the client was added with [`ReplicatedClients::add_virtual_client`].
+    pub fn is_virtual(&self) -> bool {
+        self.virtual_stats.is_some()
+    }
+
+    /// Returns received data if the client is virtual.
+    pub fn virtual_stats(&self) -> Option<&VirtualClientStats> {
+        self.virtual_stats.as_ref()
+    }
+
+    /// Counts a message received by a virtual client.
+    pub(crate) fn receive_virtual(&mut self, channel: ReplicationChannel, bytes: usize) {
+        let Some(stats) = &mut self.virtual_stats else {
+            return;
+        };
+
+        match channel {
+            ReplicationChannel::Updates => stats.update_messages += 1,
+            ReplicationChannel::Mutations => stats.mutate_messages += 1,
+        }
+        stats.bytes += bytes;
+    }
+
     /// Sets the client's update tick.
     pub(crate) fn set_update_tick(&mut self, tick: RepliconTick) {
         self.update_tick = tick;
@@ -231,6 +377,100 @@ impl ReplicatedClient {
         self.update_tick
     }
 
+    /// Returns `true` if the initial state of the world was sent to the client.
+    ///
+    /// The client is considered syncing until then.
+    /// Regular server events are applied on client only after the update message with their tick,
+    /// so events sent after this point can safely reference replicated entities.
+    ///
+    /// See also [`ServerEventAppExt::set_syncing_policy`](crate::core::event::server_event::ServerEventAppExt::set_syncing_policy).
+    pub fn is_baseline_sent(&self) -> bool {
+        self.baseline_sent
+    }
+
+    /// Marks that the initial state of the world was sent to the client.
+    pub(crate) fn set_baseline_sent(&mut self) {
+        self.baseline_sent = true;
+    }
+
+    /// Sets how often mutations are sent to the client, in ticks.
+    ///
+    /// Useful to reduce traffic for clients that don't need frequent updates.
+    /// Mutations from skipped ticks are not lost, they will be sent with the next mutate message.
+    /// Insertions, removals and despawns are always sent on each tick.
+    ///
+    /// By default it's 1, which means sending on every tick.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is 0.
+    pub fn set_mutations_interval(&mut self, interval: u32) {
+        assert_ne!(interval, 0, "mutations interval can't be 0");
+        self.mutations_interval = interval;
+    }
+
+    /// Returns how often mutations are sent to the client, in ticks.
+    ///
+    /// See also [`Self::set_mutations_interval`].
+    pub fn mutations_interval(&self) -> u32 {
+        self.mutations_interval
+    }
+
+    /// Sets the maximum number of bytes of mutations sent to the client.
+    ///
+    /// Once the cap is reached, remaining entities with mutations are deferred to the next ticks.
+    /// Entities are picked by their accumulated [`ReplicationPriority`](crate::server::send_budget::ReplicationPriority),
+    /// so deferred entities are eventually sent too. Insertions, removals and despawns are always sent
+    /// since they are reliable.
+    ///
+    /// At least one entity is sent if the cap isn't exhausted, even if it doesn't fit.
+    /// Exceeded bytes are subtracted from the next ticks for [`BandwidthCap::PerSecond`].
+    ///
+    /// Combined with [`SendBudget`](crate::server::send_budget::SendBudget) if it's present, the lowest limit is used.
+    ///
+    /// By default it's taken from [`ReplicatedClients::bandwidth_cap`].
+    pub fn set_bandwidth_cap(&mut self, cap: Option<BandwidthCap>) {
+        self.bandwidth_cap = cap;
+        self.bandwidth_refilled = None;
+    }
+
+    /// Returns the maximum number of bytes of mutations sent to the client.
+    ///
+    /// See also [`Self::set_bandwidth_cap`].
+    pub fn bandwidth_cap(&self) -> Option<BandwidthCap> {
+        self.bandwidth_cap
+    }
+
+    /// Returns the number of bytes available for mutations at `now`.
+    ///
+    /// Returns [`None`] if the client has no cap.
+    pub(crate) fn refill_bandwidth(&mut self, now: Duration) -> Option<usize> {
+        match self.bandwidth_cap? {
+            BandwidthCap::PerTick(bytes) => Some(bytes),
+            BandwidthCap::PerSecond(bytes) => {
+                let rate = bytes as f64;
+                self.bandwidth_allowance = match self.bandwidth_refilled {
+                    Some(refilled) => {
+                        let elapsed = now.saturating_sub(refilled).as_secs_f64();
+                        // Save unused bytes for at most one second to allow only short bursts.
+                        (self.bandwidth_allowance + rate * elapsed).min(rate)
+                    }
+                    None => rate,
+                };
+                self.bandwidth_refilled = Some(now);
+
+                Some(self.bandwidth_allowance.max(0.0) as usize)
+            }
+        }
+    }
+
+    /// Subtracts sent bytes from the allowance for [`BandwidthCap::PerSecond`].
+    pub(crate) fn consume_bandwidth(&mut self, bytes: usize) {
+        if let Some(BandwidthCap::PerSecond(_)) = self.bandwidth_cap {
+            self.bandwidth_allowance -= bytes as f64;
+        }
+    }
+
     /// Clears all entities for unacknowledged mutate messages, returning them as an iterator.
     ///
     /// Keeps the allocated memory for reuse.
@@ -243,15 +483,24 @@ impl ReplicatedClient {
     /// Resets all data.
     ///
     /// Keeps the allocated memory for reuse.
-    fn reset(&mut self, id: ClientId) {
+    fn reset(&mut self, id: ClientId, policy: VisibilityPolicy) {
         self.id = id;
-        self.visibility.clear();
+        // The policy could be changed since the client was buffered.
+        if self.visibility.policy() == policy {
+            self.visibility.clear();
+        } else {
+            self.visibility = ClientVisibility::new(policy);
+        }
         self.mutation_ticks.clear();
         self.mutations.clear();
         self.mutate_index = Default::default();
+        self.baseline_sent = false;
+        self.mutations_interval = 1;
+        self.bandwidth_refilled = None;
+        self.virtual_stats = None;
     }
 
-    /// Registers mutate message at specified `tick` and `timestamp` and returns its index with entities to fill.
+    /// Registers mutate message at specified `tick`, `sent_tick` and `timestamp` and returns its index with entities to fill.
     ///
     /// Used later to acknowledge updated entities.
     #[must_use]
@@ -259,6 +508,7 @@ impl ReplicatedClient {
         &mut self,
         client_buffers: &mut ClientBuffers,
         tick: Tick,
+        sent_tick: RepliconTick,
         timestamp: Duration,
     ) -> (MutateIndex, &mut Vec<Entity>) {
         let mutate_index = self.mutate_index.advance();
@@ -267,6 +517,7 @@ impl ReplicatedClient {
         entities.clear();
         let mutate_info = MutateInfo {
             tick,
+            sent_tick,
             timestamp,
             entities,
         };
@@ -292,6 +543,16 @@ impl ReplicatedClient {
         self.mutation_ticks.get(&entity).copied()
     }
 
+    /// Forgets the mutation tick for an entity, so it will be sent in full on the next tick.
+    pub(crate) fn remove_mutation_tick(&mut self, entity: Entity) {
+        self.mutation_ticks.remove(&entity);
+    }
+
+    /// Forgets all mutation ticks, so all visible entities will be sent in full on the next tick.
+    pub(crate) fn clear_mutation_ticks(&mut self) {
+        self.mutation_ticks.clear();
+    }
+
     /// Marks mutate message as acknowledged by its index.
     ///
     /// Mutation tick for all entities from this mutate message will be set to the message tick if it's higher.
@@ -346,16 +607,20 @@ impl ReplicatedClient {
         })
     }
 
-    /// Removes all mutate messages older then `min_timestamp`.
+    /// Removes all mutate messages older then `min_timestamp` that were sent more than `timeout_ticks` ago.
     ///
     /// Keeps allocated memory in the buffers for reuse.
     pub(crate) fn cleanup_older_mutations(
         &mut self,
         client_buffers: &mut ClientBuffers,
+        server_tick: RepliconTick,
+        timeout_ticks: u32,
         min_timestamp: Duration,
     ) {
         self.mutations.retain(|_, mutate_info| {
-            if mutate_info.timestamp < min_timestamp {
+            if mutate_info.timestamp < min_timestamp
+                && server_tick - mutate_info.sent_tick > timeout_ticks
+            {
                 client_buffers
                     .entities
                     .push(mem::take(&mut mutate_info.entities));
@@ -367,6 +632,19 @@ impl ReplicatedClient {
     }
 }
 
+/// Replication data received by a virtual client.
+///
+/// See [`ReplicatedClients::add_virtual_client`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VirtualClientStats {
+    /// Number of received update messages.
+    pub update_messages: usize,
+    /// Number of received mutate messages.
+    pub mutate_messages: usize,
+    /// Total size of received messages in bytes.
+    pub bytes: usize,
+}
+
 /// Reusable buffers for [`ReplicatedClients`] and [`ReplicatedClient`].
 #[derive(Default, Resource)]
 pub(crate) struct ClientBuffers {
@@ -383,12 +661,28 @@ pub(crate) struct ClientBuffers {
 
 struct MutateInfo {
     tick: Tick,
+    sent_tick: RepliconTick,
     timestamp: Duration,
     entities: Vec<Entity>,
 }
 
+/// Maximum number of bytes of mutations sent to a client.
+///
+/// See [`ReplicatedClient::set_bandwidth_cap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BandwidthCap {
+    /// Limit for each replication tick.
+    PerTick(usize),
+    /// Limit averaged over time.
+    ///
+    /// Unused bytes are saved for at most one second, which allows short bursts.
+    PerSecond(usize),
+}
+
 /// Controls how visibility will be managed via [`ClientVisibility`].
-#[derive(Default, Debug, Clone, Copy)]
+///
+/// Can be changed at runtime with [`ReplicatedClients::set_visibility_policy`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VisibilityPolicy {
     /// All entities are visible by default and visibility can't be changed.
     #[default]
@@ -1,3 +1,5 @@
+use std::mem;
+
 use bevy::{
     ecs::entity::{EntityHashMap, EntityHashSet},
     prelude::*,
@@ -9,6 +11,17 @@ use super::VisibilityPolicy;
 /// Entity visibility settings for a client.
 pub struct ClientVisibility {
     filter: VisibilityFilter,
+
+    /// Filter that was active before [`Self::set_policy`].
+    ///
+    /// Kept until [`Self::finish_transition`] to compute visibility changes.
+    previous: Option<VisibilityFilter>,
+
+    /// Entities that became visible due to a policy change.
+    gained: EntityHashSet,
+
+    /// Entities that became hidden due to a policy change.
+    lost: EntityHashSet,
 }
 
 impl ClientVisibility {
@@ -31,13 +44,80 @@ impl ClientVisibility {
 
     /// Creates a new instance with a specific filter.
     fn with_filter(filter: VisibilityFilter) -> Self {
-        Self { filter }
+        Self {
+            filter,
+            previous: None,
+            gained: Default::default(),
+            lost: Default::default(),
+        }
+    }
+
+    /// Returns the policy of the current filter.
+    pub fn policy(&self) -> VisibilityPolicy {
+        match self.filter {
+            VisibilityFilter::All => VisibilityPolicy::All,
+            VisibilityFilter::Blacklist { .. } => VisibilityPolicy::Blacklist,
+            VisibilityFilter::Whitelist { .. } => VisibilityPolicy::Whitelist,
+        }
+    }
+
+    /// Switches to a filter for a different policy.
+    ///
+    /// The new filter starts empty. Entities whose visibility differs between the filters
+    /// are computed in [`Self::finish_transition`]. Does nothing if the policy is the same.
+    pub(super) fn set_policy(&mut self, policy: VisibilityPolicy) {
+        if self.policy() == policy {
+            return;
+        }
+
+        let previous = mem::replace(&mut self.filter, Self::new(policy).filter);
+        // If the policy was changed multiple times before the transition,
+        // the client still has entities from the first filter.
+        self.previous.get_or_insert(previous);
+    }
+
+    /// Returns `true` if the policy was changed and [`Self::finish_transition`] needs to be called.
+    pub(crate) fn in_transition(&self) -> bool {
+        self.previous.is_some()
+    }
+
+    /// Compares visibility of all replicated `entities` between the previous and the current filters.
+    ///
+    /// Entities that the client had, but that are hidden by the current filter will be reported as lost.
+    /// Entities that the client didn't have, but that are visible by the current filter will be reported as gained.
+    pub(crate) fn finish_transition(&mut self, entities: impl Iterator<Item = Entity>) {
+        let Some(previous) = self.previous.take() else {
+            return;
+        };
+
+        for entity in entities {
+            // Newly gained entities weren't sent yet, while lost entities weren't despawned yet.
+            let had_entity = match previous.state(entity) {
+                Visibility::Visible => true,
+                Visibility::Gained => false,
+                Visibility::Hidden => previous.is_lost(entity),
+            };
+            match (had_entity, self.filter.state(entity)) {
+                (true, Visibility::Hidden) if !self.filter.is_lost(entity) => {
+                    self.lost.insert(entity);
+                }
+                // The entity was added to the new list, but the client already has it.
+                (true, Visibility::Gained) => self.filter.confirm_gained(entity),
+                (false, Visibility::Visible) => {
+                    self.gained.insert(entity);
+                }
+                _ => (),
+            }
+        }
     }
 
     /// Resets the filter state to as it was after [`Self::new`].
     ///
     /// `cached_visibility` remains untouched.
     pub(super) fn clear(&mut self) {
+        self.previous = None;
+        self.gained.clear();
+        self.lost.clear();
         match &mut self.filter {
             VisibilityFilter::All => (),
             VisibilityFilter::Blacklist {
@@ -63,8 +143,13 @@ impl ClientVisibility {
 
     /// Updates list information and its sets based on the filter.
     ///
+    /// Entities for which `deferred` returns `true` keep [`Visibility::Gained`]
+    /// until they are replicated.
+    ///
     /// Should be called after each tick.
-    pub(crate) fn update(&mut self) {
+    pub(crate) fn update(&mut self, deferred: impl Fn(Entity) -> bool) {
+        self.gained.retain(|&entity| deferred(entity));
+        self.lost.clear();
         match &mut self.filter {
             VisibilityFilter::All => (),
             VisibilityFilter::Blacklist {
@@ -73,9 +158,13 @@ impl ClientVisibility {
                 removed,
             } => {
                 // Remove all entities queued for removal.
-                for entity in removed.drain() {
+                removed.retain(|&entity| {
+                    if deferred(entity) {
+                        return true;
+                    }
                     list.remove(&entity);
-                }
+                    false
+                });
                 added.clear();
             }
             VisibilityFilter::Whitelist {
@@ -85,9 +174,13 @@ impl ClientVisibility {
             } => {
                 // Change all recently added entities to `WhitelistInfo::Visible`
                 // from `WhitelistInfo::JustVisible`.
-                for entity in added.drain() {
+                added.retain(|&entity| {
+                    if deferred(entity) {
+                        return true;
+                    }
                     list.insert(entity, WhitelistInfo::Visible);
-                }
+                    false
+                });
                 removed.clear();
             }
         }
@@ -95,8 +188,10 @@ impl ClientVisibility {
 
     /// Removes a despawned entity tracked by this client.
     pub(super) fn remove_despawned(&mut self, entity: Entity) {
+        self.gained.remove(&entity);
+        self.lost.remove(&entity);
         match &mut self.filter {
-            VisibilityFilter::All { .. } => (),
+            VisibilityFilter::All => (),
             VisibilityFilter::Blacklist {
                 list,
                 added,
@@ -122,13 +217,14 @@ impl ClientVisibility {
 
     /// Drains all entities for which visibility was lost during this tick.
     pub(super) fn drain_lost(&mut self) -> impl Iterator<Item = Entity> + '_ {
-        match &mut self.filter {
-            VisibilityFilter::All { .. } => VisibilityLostIter::AllVisible,
+        let lost = match &mut self.filter {
+            VisibilityFilter::All => VisibilityLostIter::AllVisible,
             VisibilityFilter::Blacklist { added, .. } => VisibilityLostIter::Lost(added.drain()),
             VisibilityFilter::Whitelist { removed, .. } => {
                 VisibilityLostIter::Lost(removed.drain())
             }
-        }
+        };
+        lost.chain(self.lost.drain())
     }
 
     /// Sets visibility for a specific entity.
@@ -136,7 +232,7 @@ impl ClientVisibility {
     /// Does nothing if the visibility policy for the server plugin is set to [`VisibilityPolicy::All`].
     pub fn set_visibility(&mut self, entity: Entity, visible: bool) {
         match &mut self.filter {
-            VisibilityFilter::All { .. } => {
+            VisibilityFilter::All => {
                 if visible {
                     debug!(
                         "ignoring visibility enable due to {:?}",
@@ -227,19 +323,12 @@ impl ClientVisibility {
 
     /// Returns visibility of a specific entity.
     pub(crate) fn state(&self, entity: Entity) -> Visibility {
-        match &self.filter {
-            VisibilityFilter::All => Visibility::Visible,
-            VisibilityFilter::Blacklist { list, .. } => match list.get(&entity) {
-                Some(BlacklistInfo::QueuedForRemoval) => Visibility::Gained,
-                Some(BlacklistInfo::Hidden) => Visibility::Hidden,
-                None => Visibility::Visible,
-            },
-            VisibilityFilter::Whitelist { list, .. } => match list.get(&entity) {
-                Some(WhitelistInfo::JustAdded) => Visibility::Gained,
-                Some(WhitelistInfo::Visible) => Visibility::Visible,
-                None => Visibility::Hidden,
-            },
+        let state = self.filter.state(entity);
+        if state == Visibility::Visible && self.gained.contains(&entity) {
+            return Visibility::Gained;
         }
+
+        state
     }
 }
 
@@ -278,6 +367,50 @@ enum VisibilityFilter {
     },
 }
 
+impl VisibilityFilter {
+    fn state(&self, entity: Entity) -> Visibility {
+        match self {
+            VisibilityFilter::All => Visibility::Visible,
+            VisibilityFilter::Blacklist { list, .. } => match list.get(&entity) {
+                Some(BlacklistInfo::QueuedForRemoval) => Visibility::Gained,
+                Some(BlacklistInfo::Hidden) => Visibility::Hidden,
+                None => Visibility::Visible,
+            },
+            VisibilityFilter::Whitelist { list, .. } => match list.get(&entity) {
+                Some(WhitelistInfo::JustAdded) => Visibility::Gained,
+                Some(WhitelistInfo::Visible) => Visibility::Visible,
+                None => Visibility::Hidden,
+            },
+        }
+    }
+
+    /// Marks an entity that gained visibility in this tick as already visible.
+    fn confirm_gained(&mut self, entity: Entity) {
+        match self {
+            VisibilityFilter::All => (),
+            VisibilityFilter::Blacklist { list, removed, .. } => {
+                if removed.remove(&entity) {
+                    list.remove(&entity);
+                }
+            }
+            VisibilityFilter::Whitelist { list, added, .. } => {
+                if added.remove(&entity) {
+                    list.insert(entity, WhitelistInfo::Visible);
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if visibility of the entity was lost during this tick.
+    fn is_lost(&self, entity: Entity) -> bool {
+        match self {
+            VisibilityFilter::All => false,
+            VisibilityFilter::Blacklist { added, .. } => added.contains(&entity),
+            VisibilityFilter::Whitelist { removed, .. } => removed.contains(&entity),
+        }
+    }
+}
+
 #[derive(PartialEq, Clone, Copy)]
 enum WhitelistInfo {
     Visible,
@@ -361,7 +494,7 @@ mod tests {
         assert!(added.contains(&Entity::PLACEHOLDER));
         assert!(!removed.contains(&Entity::PLACEHOLDER));
 
-        visibility.update();
+        visibility.update(|_| false);
 
         let VisibilityFilter::Blacklist {
             list,
@@ -403,7 +536,7 @@ mod tests {
     fn blacklist_removal() {
         let mut visibility = ClientVisibility::new(VisibilityPolicy::Blacklist);
         visibility.set_visibility(Entity::PLACEHOLDER, false);
-        visibility.update();
+        visibility.update(|_| false);
         visibility.set_visibility(Entity::PLACEHOLDER, true);
         assert!(visibility.is_visible(Entity::PLACEHOLDER));
 
@@ -420,7 +553,7 @@ mod tests {
         assert!(!added.contains(&Entity::PLACEHOLDER));
         assert!(removed.contains(&Entity::PLACEHOLDER));
 
-        visibility.update();
+        visibility.update(|_| false);
 
         let VisibilityFilter::Blacklist {
             list,
@@ -463,7 +596,7 @@ mod tests {
     fn blacklist_duplicate_insertion() {
         let mut visibility = ClientVisibility::new(VisibilityPolicy::Blacklist);
         visibility.set_visibility(Entity::PLACEHOLDER, false);
-        visibility.update();
+        visibility.update(|_| false);
 
         // Duplicate insertion.
         visibility.set_visibility(Entity::PLACEHOLDER, false);
@@ -502,7 +635,7 @@ mod tests {
         assert!(added.contains(&Entity::PLACEHOLDER));
         assert!(!removed.contains(&Entity::PLACEHOLDER));
 
-        visibility.update();
+        visibility.update(|_| false);
 
         let VisibilityFilter::Whitelist {
             list,
@@ -544,7 +677,7 @@ mod tests {
     fn whitelist_removal() {
         let mut visibility = ClientVisibility::new(VisibilityPolicy::Whitelist);
         visibility.set_visibility(Entity::PLACEHOLDER, true);
-        visibility.update();
+        visibility.update(|_| false);
         visibility.set_visibility(Entity::PLACEHOLDER, false);
         assert!(!visibility.is_visible(Entity::PLACEHOLDER));
 
@@ -561,7 +694,7 @@ mod tests {
         assert!(!added.contains(&Entity::PLACEHOLDER));
         assert!(removed.contains(&Entity::PLACEHOLDER));
 
-        visibility.update();
+        visibility.update(|_| false);
 
         let VisibilityFilter::Whitelist {
             list,
@@ -604,7 +737,7 @@ mod tests {
     fn whitelist_duplicate_insertion() {
         let mut visibility = ClientVisibility::new(VisibilityPolicy::Whitelist);
         visibility.set_visibility(Entity::PLACEHOLDER, true);
-        visibility.update();
+        visibility.update(|_| false);
 
         // Duplicate insertion.
         visibility.set_visibility(Entity::PLACEHOLDER, true);
@@ -623,4 +756,59 @@ mod tests {
         assert!(!added.contains(&Entity::PLACEHOLDER));
         assert!(!removed.contains(&Entity::PLACEHOLDER));
     }
+
+    #[test]
+    fn whitelist_to_all() {
+        let mut visibility = ClientVisibility::new(VisibilityPolicy::Whitelist);
+        let visible = Entity::from_raw(0);
+        let hidden = Entity::from_raw(1);
+        visibility.set_visibility(visible, true);
+        visibility.update(|_| false);
+
+        visibility.set_policy(VisibilityPolicy::All);
+        assert!(visibility.in_transition());
+
+        visibility.finish_transition([visible, hidden].into_iter());
+        assert!(!visibility.in_transition());
+        assert!(visibility.state(visible) == Visibility::Visible);
+        assert!(visibility.state(hidden) == Visibility::Gained);
+        assert_eq!(visibility.drain_lost().count(), 0);
+
+        visibility.update(|_| false);
+        assert!(visibility.state(hidden) == Visibility::Visible);
+    }
+
+    #[test]
+    fn all_to_whitelist() {
+        let mut visibility = ClientVisibility::new(VisibilityPolicy::All);
+        let visible = Entity::from_raw(0);
+        let hidden = Entity::from_raw(1);
+
+        visibility.set_policy(VisibilityPolicy::Whitelist);
+        visibility.set_visibility(visible, true);
+        visibility.finish_transition([visible, hidden].into_iter());
+
+        assert!(
+            visibility.state(visible) == Visibility::Visible,
+            "entity was visible before the change"
+        );
+        assert!(visibility.state(hidden) == Visibility::Hidden);
+        assert_eq!(visibility.drain_lost().collect::<Vec<_>>(), [hidden]);
+    }
+
+    #[test]
+    fn blacklist_to_whitelist() {
+        let mut visibility = ClientVisibility::new(VisibilityPolicy::Blacklist);
+        let visible = Entity::from_raw(0);
+        let hidden = Entity::from_raw(1);
+        visibility.set_visibility(hidden, false);
+        visibility.update(|_| false);
+
+        visibility.set_policy(VisibilityPolicy::Whitelist);
+        visibility.set_visibility(hidden, true);
+        visibility.finish_transition([visible, hidden].into_iter());
+
+        assert!(visibility.state(hidden) == Visibility::Gained);
+        assert_eq!(visibility.drain_lost().collect::<Vec<_>>(), [visible]);
+    }
 }
@@ -0,0 +1,127 @@
+use std::any::{self, TypeId};
+
+use bevy::prelude::*;
+use bytes::Bytes;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::core::postcard_utils;
+#[cfg(feature = "server")]
+use crate::server::resource_buffer::{self, ResourceBuffer};
+
+/// Replication of resources.
+pub trait AppResourceExt {
+    /**
+    Registers a resource `R` for replication.
+
+    While the server is running, inserted and changed values of `R` are sent to all replicated clients
+    and removals of `R` remove it on clients. Newly replicated clients receive the current value with their
+    first update message. Resources are applied on clients before component changes of the same message.
+
+    Resource will be serialized and deserialized as-is using postcard. Entities inside resources aren't mapped.
+
+    Resources should be registered on both client and server in the same order.
+    Registering the same resource again has no effect.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_resource::<MatchSettings>();
+
+    #[derive(Resource, Deserialize, Serialize)]
+    struct MatchSettings {
+        max_score: u32,
+    }
+    ```
+    **/
+    fn replicate_resource<R>(&mut self) -> &mut Self
+    where
+        R: Resource + Serialize + DeserializeOwned;
+}
+
+impl AppResourceExt for App {
+    fn replicate_resource<R>(&mut self) -> &mut Self
+    where
+        R: Resource + Serialize + DeserializeOwned,
+    {
+        let mut resources = self.world_mut().resource_mut::<ReplicatedResources>();
+        if resources.id::<R>().is_some() {
+            debug!(
+                "ignoring repeated registration of `{}`",
+                any::type_name::<R>()
+            );
+            return self;
+        }
+
+        resources.fns.push((
+            TypeId::of::<R>(),
+            ResourceFns {
+                write: write_resource::<R>,
+                remove: remove_resource::<R>,
+            },
+        ));
+
+        #[cfg(feature = "server")]
+        self.init_resource::<ResourceBuffer>().add_systems(
+            PostUpdate,
+            resource_buffer::buffer_resource::<R>
+                .map(Result::unwrap)
+                .before(crate::server::ServerSet::Send)
+                .run_if(crate::core::common_conditions::server_running),
+        );
+
+        self
+    }
+}
+
+/// Functions for all resources registered with [`AppResourceExt::replicate_resource`].
+///
+/// Resources are identified by their registration index.
+#[derive(Resource, Default)]
+pub(crate) struct ReplicatedResources {
+    fns: Vec<(TypeId, ResourceFns)>,
+}
+
+impl ReplicatedResources {
+    /// Returns ID of a registered resource.
+    pub(crate) fn id<R: Resource>(&self) -> Option<usize> {
+        self.fns
+            .iter()
+            .position(|&(type_id, _)| type_id == TypeId::of::<R>())
+    }
+
+    /// Returns functions for a resource with the given ID.
+    #[cfg_attr(not(feature = "client"), allow(dead_code))]
+    pub(crate) fn get(&self, id: usize) -> Option<ResourceFns> {
+        self.fns.get(id).map(|&(_, fns)| fns)
+    }
+}
+
+/// Type-erased client functions for a replicated resource.
+#[derive(Clone, Copy)]
+#[cfg_attr(not(feature = "client"), allow(dead_code))]
+pub(crate) struct ResourceFns {
+    /// Deserializes the resource and inserts it into the world.
+    pub(crate) write: fn(&mut World, &mut Bytes) -> postcard::Result<()>,
+
+    /// Removes the resource from the world.
+    pub(crate) remove: fn(&mut World),
+}
+
+fn write_resource<R: Resource + DeserializeOwned>(
+    world: &mut World,
+    message: &mut Bytes,
+) -> postcard::Result<()> {
+    let resource: R = postcard_utils::from_buf(message)?;
+    world.insert_resource(resource);
+    Ok(())
+}
+
+fn remove_resource<R: Resource>(world: &mut World) {
+    world.remove_resource::<R>();
+}
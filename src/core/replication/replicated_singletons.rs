@@ -0,0 +1,144 @@
+use std::{any, marker::PhantomData};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::replication_rules::AppRuleExt;
+#[cfg(feature = "server")]
+use super::Replicated;
+#[cfg(feature = "server")]
+use crate::core::common_conditions::server_running;
+
+/// Replication of entities that always exist in a single instance.
+pub trait AppSingletonExt {
+    /**
+    Registers a singleton entity with bundle `B`.
+
+    While the server is running, it ensures that exactly one entity with `B` spawned by
+    [`Default`] and marked with [`Replicated`] exists. If the entity is despawned,
+    for example by [`ServerResetExt::despawn_all_replicated`](crate::server::server_epoch::ServerResetExt::despawn_all_replicated),
+    it will be recreated with the default values on the next update.
+
+    On both client and server, the entity is accessible via the [`ReplicatedSingleton<B>`] resource,
+    which exists while the entity exists.
+
+    Singletons should be registered on both client and server. All bundle components
+    need to be registered for replication separately.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication::replicated_singletons::{AppSingletonExt, ReplicatedSingleton},
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate::<MatchState>()
+        .replicate_singleton::<MatchState>()
+        .add_systems(Update, show_round);
+
+    fn show_round(
+        singleton: Option<Res<ReplicatedSingleton<MatchState>>>,
+        states: Query<&MatchState>,
+    ) {
+        if let Some(state) = singleton.and_then(|singleton| states.get(singleton.entity()).ok()) {
+            info!("round {}", state.round);
+        }
+    }
+
+    #[derive(Component, Default, Deserialize, Serialize)]
+    struct MatchState {
+        round: u32,
+    }
+    ```
+    **/
+    fn replicate_singleton<B: Bundle + Default>(&mut self) -> &mut Self;
+}
+
+impl AppSingletonExt for App {
+    fn replicate_singleton<B: Bundle + Default>(&mut self) -> &mut Self {
+        self.replicate::<Singleton<B>>()
+            .add_observer(insert_singleton::<B>)
+            .add_observer(remove_singleton::<B>);
+
+        #[cfg(feature = "server")]
+        self.add_systems(PreUpdate, ensure_singleton::<B>.run_if(server_running));
+
+        self
+    }
+}
+
+fn insert_singleton<B: Bundle>(
+    trigger: Trigger<OnAdd, Singleton<B>>,
+    mut commands: Commands,
+    singleton: Option<Res<ReplicatedSingleton<B>>>,
+) {
+    if let Some(singleton) = singleton {
+        warn!(
+            "replacing singleton `{}` from `{}` to `{}`",
+            any::type_name::<B>(),
+            singleton.entity,
+            trigger.entity()
+        );
+    }
+
+    commands.insert_resource(ReplicatedSingleton::<B> {
+        entity: trigger.entity(),
+        marker: PhantomData,
+    });
+}
+
+fn remove_singleton<B: Bundle>(trigger: Trigger<OnRemove, Singleton<B>>, mut commands: Commands) {
+    let entity = trigger.entity();
+    commands.queue(move |world: &mut World| {
+        // The singleton could be replaced with another entity.
+        if world
+            .get_resource::<ReplicatedSingleton<B>>()
+            .is_some_and(|singleton| singleton.entity == entity)
+        {
+            world.remove_resource::<ReplicatedSingleton<B>>();
+        }
+    });
+}
+
+#[cfg(feature = "server")]
+fn ensure_singleton<B: Bundle + Default>(
+    mut commands: Commands,
+    singletons: Query<(), With<Singleton<B>>>,
+) {
+    if singletons.is_empty() {
+        debug!("spawning singleton `{}`", any::type_name::<B>());
+        commands.spawn((Replicated, Singleton::<B>::default(), B::default()));
+    }
+}
+
+/// Handle to the entity registered with [`AppSingletonExt::replicate_singleton`].
+#[derive(Resource)]
+pub struct ReplicatedSingleton<B: Bundle> {
+    entity: Entity,
+    marker: PhantomData<B>,
+}
+
+impl<B: Bundle> ReplicatedSingleton<B> {
+    /// Returns the singleton entity.
+    ///
+    /// On client it's the client entity mapped to the server singleton.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+/// Replicated marker for the singleton entity with bundle `B`.
+#[derive(Component, Deserialize, Serialize)]
+#[serde(bound = "")]
+struct Singleton<B: Bundle>(PhantomData<B>);
+
+impl<B: Bundle> Default for Singleton<B> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -0,0 +1,187 @@
+use std::any::Any;
+
+use bevy::{
+    ecs::{archetype::Archetype, component::ComponentId},
+    prelude::*,
+    ptr::Ptr,
+};
+
+/// Prefab registration for [`App`].
+pub trait AppPrefabExt {
+    /**
+    Registers a prefab associated with the marker component `P`.
+
+    A prefab is a set of components with default values. When a replicated entity
+    with `P` becomes visible for a client, components from the prefab whose values
+    are equal to the defaults won't be serialized. Only the marker and the overrides
+    will be sent. On insertion of `P`, all missing prefab components will be inserted
+    with their default values. This happens on both client and server, similar to
+    required components.
+
+    Prefabs should be registered on both client and server. The marker and all prefab components
+    need to be registered for replication separately.
+
+    Prefab components shouldn't be removed from an entity while it contains the marker.
+    Otherwise newly connected clients will get the default values for the removed components.
+    Entities whose archetype lacks any of the prefab components are replicated without elision.
+
+    # Examples
+
+    ```
+    # use bevy::prelude::*;
+    # use bevy_replicon::{core::replication::replication_prefabs::{AppPrefabExt, ReplicationPrefab}, prelude::*};
+    # use serde::{Deserialize, Serialize};
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate::<Enemy>()
+        .replicate::<Health>()
+        .replicate::<Speed>()
+        .register_prefab::<Enemy>(
+            ReplicationPrefab::default()
+                .with(Health(100))
+                .with(Speed(2.0)),
+        );
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Enemy;
+
+    #[derive(Component, Deserialize, Serialize, Clone, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Component, Deserialize, Serialize, Clone, PartialEq)]
+    struct Speed(f32);
+    ```
+    **/
+    fn register_prefab<P: Component>(&mut self, prefab: ReplicationPrefab) -> &mut Self;
+}
+
+impl AppPrefabExt for App {
+    fn register_prefab<P: Component>(&mut self, mut prefab: ReplicationPrefab) -> &mut Self {
+        prefab.marker_id = self.world_mut().register_component::<P>();
+        for component in &mut prefab.components {
+            component.component_id = (component.register)(self.world_mut());
+        }
+
+        let mut prefabs = self.world_mut().resource_mut::<ReplicationPrefabs>();
+        let index = prefabs.0.len();
+        prefabs.0.push(prefab);
+
+        self.add_observer(
+            move |trigger: Trigger<OnAdd, P>,
+                  mut commands: Commands,
+                  prefabs: Res<ReplicationPrefabs>| {
+                let mut entity = commands.entity(trigger.entity());
+                for component in &prefabs.0[index].components {
+                    (component.insert)(&*component.value, &mut entity);
+                }
+            },
+        )
+    }
+}
+
+/// All registered prefabs.
+#[derive(Resource, Default)]
+pub(crate) struct ReplicationPrefabs(Vec<ReplicationPrefab>);
+
+impl ReplicationPrefabs {
+    /// Returns the index of the first prefab that matches the archetype.
+    ///
+    /// Archetype matches if it contains the prefab marker and all its components.
+    pub(crate) fn find(&self, archetype: &Archetype) -> Option<usize> {
+        self.0.iter().position(|prefab| prefab.matches(archetype))
+    }
+
+    /// Returns the index of the component inside the prefab.
+    pub(crate) fn component_index(
+        &self,
+        prefab_index: usize,
+        component_id: ComponentId,
+    ) -> Option<usize> {
+        self.0[prefab_index]
+            .components
+            .iter()
+            .position(|component| component.component_id == component_id)
+    }
+
+    /// Returns `true` if the component value is equal to its default in the prefab.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` points to the component at `component_index`.
+    pub(crate) unsafe fn is_default(
+        &self,
+        prefab_index: usize,
+        component_index: usize,
+        ptr: Ptr,
+    ) -> bool {
+        let component = &self.0[prefab_index].components[component_index];
+        (component.is_default)(&*component.value, ptr)
+    }
+}
+
+/// Set of components with default values associated with a marker.
+///
+/// See [`AppPrefabExt::register_prefab`].
+pub struct ReplicationPrefab {
+    marker_id: ComponentId,
+    components: Vec<PrefabComponent>,
+}
+
+impl Default for ReplicationPrefab {
+    fn default() -> Self {
+        // IDs will be assigned on registration.
+        Self {
+            marker_id: ComponentId::new(usize::MAX),
+            components: Default::default(),
+        }
+    }
+}
+
+impl ReplicationPrefab {
+    /// Adds a component with its default value to the prefab.
+    pub fn with<C: Component + Clone + PartialEq>(mut self, value: C) -> Self {
+        self.components.push(PrefabComponent {
+            component_id: ComponentId::new(usize::MAX),
+            value: Box::new(value),
+            register: World::register_component::<C>,
+            is_default: is_default::<C>,
+            insert: insert::<C>,
+        });
+        self
+    }
+
+    fn matches(&self, archetype: &Archetype) -> bool {
+        archetype.contains(self.marker_id)
+            && self
+                .components
+                .iter()
+                .all(|component| archetype.contains(component.component_id))
+    }
+}
+
+struct PrefabComponent {
+    component_id: ComponentId,
+    value: Box<dyn Any + Send + Sync>,
+    register: fn(&mut World) -> ComponentId,
+    is_default: unsafe fn(&dyn Any, Ptr) -> bool,
+    insert: fn(&dyn Any, &mut EntityCommands),
+}
+
+/// Compares the component behind the pointer with the default value.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` points to `C`.
+unsafe fn is_default<C: Component + PartialEq>(value: &dyn Any, ptr: Ptr) -> bool {
+    let value = value
+        .downcast_ref::<C>()
+        .expect("prefab value should match its component type");
+    ptr.deref::<C>() == value
+}
+
+fn insert<C: Component + Clone>(value: &dyn Any, entity: &mut EntityCommands) {
+    let value = value
+        .downcast_ref::<C>()
+        .expect("prefab value should match its component type");
+    entity.insert_if_new(value.clone());
+}
@@ -1,6 +1,7 @@
 pub mod command_fns;
 pub mod component_fns;
 pub mod ctx;
+pub mod quantization;
 pub mod rule_fns;
 pub mod test_fns;
 
@@ -34,6 +35,12 @@ pub struct ReplicationRegistry {
     /// [`ReplicationRule`](super::replication_rules::ReplicationRule)
     rules: Vec<(UntypedRuleFns, usize)>,
 
+    /// IDs of rule functions for zero-sized components.
+    ///
+    /// Insertions of such components are packed into a per-entity bitset
+    /// where the bit index is the position in this list.
+    flags: Vec<FnsId>,
+
     /// Number of registered markers.
     ///
     /// Used to initialize new [`ComponentFns`] with the registered number of slots.
@@ -52,6 +59,16 @@ impl ReplicationRegistry {
         }
     }
 
+    /// Moves marker slot for component functions.
+    ///
+    /// Should be used after re-inserting an already registered marker into
+    /// [`CommandMarkers`](super::command_markers::CommandMarkers).
+    pub(super) fn move_marker(&mut self, from: CommandMarkerIndex, to: CommandMarkerIndex) {
+        for (_, command_fns) in &mut self.components {
+            command_fns.move_marker_slot(from, to);
+        }
+    }
+
     /// Associates command functions with a marker for a component.
     ///
     /// **Must** be called **after** calling [`Self::register_marker`] with `marker_id`.
@@ -108,8 +125,27 @@ impl ReplicationRegistry {
     ) -> (ComponentId, FnsId) {
         let (index, component_id) = self.init_component_fns::<C>(world);
         self.rules.push((rule_fns.into(), index));
+        let fns_id = FnsId(self.rules.len() - 1);
 
-        (component_id, FnsId(self.rules.len() - 1))
+        if size_of::<C>() == 0 && self.flags.len() < u64::BITS as usize {
+            self.flags.push(fns_id);
+        }
+
+        (component_id, fns_id)
+    }
+
+    /// Moves serialization/deserialization functions from `new` into the `old` slot.
+    ///
+    /// Used on re-registration of a rule to keep the previously assigned [`FnsId`].
+    /// If `new` is the last registered slot, it will be removed.
+    pub(super) fn reuse_rule_fns(&mut self, old: FnsId, new: FnsId) {
+        self.rules.swap(old.0, new.0);
+        if new.0 == self.rules.len() - 1 {
+            self.rules.pop();
+            if let Some(index) = self.flags.iter().position(|&fns_id| fns_id == new) {
+                self.flags.remove(index);
+            }
+        }
     }
 
     /// Initializes [`ComponentFns`] for a component and returns its index and ID.
@@ -135,15 +171,49 @@ impl ReplicationRegistry {
     ///
     /// See also [`Self::register_rule_fns`].
     pub(crate) fn get(&self, fns_id: FnsId) -> (ComponentId, &ComponentFns, &UntypedRuleFns) {
-        let (rule_fns, index) = self
-            .rules
-            .get(fns_id.0)
-            .unwrap_or_else(|| panic!("replication `{fns_id:?}` should be registered first"));
+        self.try_get(fns_id)
+            .unwrap_or_else(|| panic!("replication `{fns_id:?}` should be registered first"))
+    }
+
+    /// Like [`Self::get`], but returns [`None`] for unregistered IDs.
+    ///
+    /// Used for IDs received over the network.
+    pub(crate) fn try_get(
+        &self,
+        fns_id: FnsId,
+    ) -> Option<(ComponentId, &ComponentFns, &UntypedRuleFns)> {
+        let (rule_fns, index) = self.rules.get(fns_id.0)?;
 
         // SAFETY: index obtained from `rules` is always valid.
         let (component_id, command_fns) = unsafe { self.components.get_unchecked(*index) };
 
-        (*component_id, command_fns, rule_fns)
+        Some((*component_id, command_fns, rule_fns))
+    }
+
+    /// Returns `true` if any rule has delta functions.
+    ///
+    /// See [`RuleFns::with_delta`](rule_fns::RuleFns::with_delta).
+    pub(crate) fn has_delta(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|(rule_fns, _)| rule_fns.delta().is_some())
+    }
+
+    /// Returns the bit index for a zero-sized component.
+    ///
+    /// See also [`Self::flag_fns_id`].
+    pub(crate) fn flag_bit(&self, fns_id: FnsId) -> Option<u8> {
+        self.flags
+            .iter()
+            .position(|&flag_id| flag_id == fns_id)
+            .map(|bit| bit as u8)
+    }
+
+    /// Returns the functions ID for a bit index from [`Self::flag_bit`].
+    ///
+    /// Returns [`None`] for unregistered bits.
+    pub(crate) fn flag_fns_id(&self, bit: u32) -> Option<FnsId> {
+        self.flags.get(bit as usize).copied()
     }
 }
 
@@ -153,6 +223,7 @@ impl Default for ReplicationRegistry {
             despawn: despawn_recursive,
             components: Default::default(),
             rules: Default::default(),
+            flags: Default::default(),
             marker_slots: 0,
         }
     }
@@ -16,6 +16,7 @@ use crate::core::replication::{
 /// Stores type-erased command functions and functions that will restore original types.
 pub(crate) struct ComponentFns {
     serialize: UntypedSerializeFn,
+    serialize_group: UntypedSerializeGroupFn,
     write: UntypedWriteFn,
     consume: UntypedConsumeFn,
     commands: UntypedCommandFns,
@@ -27,6 +28,7 @@ impl ComponentFns {
     pub(super) fn new<C: Component>(marker_slots: usize) -> Self {
         Self {
             serialize: untyped_serialize::<C>,
+            serialize_group: untyped_serialize_group::<C>,
             write: untyped_write::<C>,
             consume: untyped_consume::<C>,
             commands: UntypedCommandFns::default_fns::<C>(),
@@ -41,6 +43,12 @@ impl ComponentFns {
         self.markers.insert(*marker_id, None);
     }
 
+    /// Moves a marker slot with its functions to a new position.
+    pub(super) fn move_marker_slot(&mut self, from: CommandMarkerIndex, to: CommandMarkerIndex) {
+        let fns = self.markers.remove(*from);
+        self.markers.insert(*to, fns);
+    }
+
     /// Assigns functions to a marker slot.
     ///
     /// # Safety
@@ -92,6 +100,25 @@ impl ComponentFns {
         (self.serialize)(ctx, rule_fns, ptr, message)
     }
 
+    /// Same as [`Self::serialize`], but serializes only a single field group.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as this instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rule_fns` weren't created with field groups.
+    pub(crate) unsafe fn serialize_group(
+        &self,
+        rule_fns: &UntypedRuleFns,
+        ptr: Ptr,
+        group: u8,
+        message: &mut Vec<u8>,
+    ) -> postcard::Result<()> {
+        (self.serialize_group)(rule_fns, ptr, group, message)
+    }
+
     /// Calls the assigned writing function based on entity markers.
     ///
     /// The first-found write function whose marker is present on the entity will be selected
@@ -175,6 +202,10 @@ impl ComponentFns {
 type UntypedSerializeFn =
     unsafe fn(&SerializeCtx, &UntypedRuleFns, Ptr, &mut Vec<u8>) -> postcard::Result<()>;
 
+/// Signature of component group serialization functions that restore the original type.
+type UntypedSerializeGroupFn =
+    unsafe fn(&UntypedRuleFns, Ptr, u8, &mut Vec<u8>) -> postcard::Result<()>;
+
 /// Signature of component writing functions that restore the original type.
 type UntypedWriteFn = unsafe fn(
     &mut WriteCtx,
@@ -203,6 +234,21 @@ unsafe fn untyped_serialize<C: Component>(
     rule_fns.serialize(ctx, ptr.deref::<C>(), message)
 }
 
+/// Same as [`untyped_serialize`], but serializes only a single field group.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` and `rule_fns` were created for `C`.
+unsafe fn untyped_serialize_group<C: Component>(
+    rule_fns: &UntypedRuleFns,
+    ptr: Ptr,
+    group: u8,
+    message: &mut Vec<u8>,
+) -> postcard::Result<()> {
+    let rule_fns = rule_fns.typed::<C>();
+    rule_fns.serialize_group(ptr.deref::<C>(), group, message)
+}
+
 /// Resolves `rule_fns` to `C` and calls [`UntypedCommandFns::write`] for `C`.
 ///
 /// # Safety
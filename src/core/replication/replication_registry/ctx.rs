@@ -5,6 +5,9 @@ use crate::core::{
 };
 
 /// Replication context for serialization function.
+///
+/// See also [`BitWriter`](crate::core::protocol::bit_packing::BitWriter) to pack flags and small values
+/// into shared bytes.
 #[non_exhaustive]
 pub struct SerializeCtx {
     /// ID of the serializing component.
@@ -12,6 +15,12 @@ pub struct SerializeCtx {
 
     /// Current tick.
     pub server_tick: RepliconTick,
+
+    /// Whether only changes since the last sent tick should be written.
+    ///
+    /// Can be `true` only for rules with
+    /// [`RuleFns::with_diffs`](super::rule_fns::RuleFns::with_diffs).
+    pub diff: bool,
 }
 
 /// Replication context for writing and deserialization.
@@ -30,7 +39,7 @@ pub struct WriteCtx<'a, 'w, 's> {
     pub message_tick: RepliconTick,
 
     /// Disables mapping logic to avoid spawning entities for consume functions.
-    pub(super) ignore_mapping: bool,
+    pub(crate) ignore_mapping: bool,
 }
 
 impl<'a, 'w, 's> WriteCtx<'a, 'w, 's> {
@@ -72,6 +81,11 @@ pub struct RemoveCtx<'a, 'w, 's> {
 
     /// Tick for the currently processing message.
     pub message_tick: RepliconTick,
+
+    /// Whether the component was updated to its last server value right before the removal.
+    ///
+    /// Can be `true` only for rules with [`RuleFns::with_final_value`](super::rule_fns::RuleFns::with_final_value).
+    pub final_value: bool,
 }
 
 /// Replication context for despawn.
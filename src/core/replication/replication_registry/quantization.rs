@@ -0,0 +1,405 @@
+//! Helpers to serialize floats with reduced precision.
+//!
+//! Full-precision floats are rarely needed for replication. Positions usually need only
+//! a fixed number of digits after the point and rotations are unit quaternions, so they can
+//! be packed much tighter than 4 floats.
+//!
+//! The module provides:
+//! - Fixed-point vectors, see [`serialize_fixed_vec3`] and [`RuleFns::fixed_point`].
+//! - Rotations packed with the smallest-three method, see [`serialize_quat`] and [`RuleFns::smallest_three`].
+//! - Half-precision floats, see [`serialize_half`] and [`RuleFns::half_float`].
+//!
+//! Presets work for newtypes that dereference into the quantized value and can be created from it.
+//! For other components use the serialization functions inside your own [`RuleFns`].
+//!
+//! # Examples
+//!
+//! Quantize only [`Transform`]:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_replicon::{
+//!     bytes::Bytes,
+//!     core::replication::replication_registry::{
+//!         ctx::{SerializeCtx, WriteCtx},
+//!         quantization,
+//!         rule_fns::RuleFns,
+//!     },
+//!     prelude::*,
+//! };
+//! use serde::{Deserialize, Serialize};
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(RepliconPlugins);
+//! app.replicate_with(RuleFns::<Position>::fixed_point::<100>())
+//!     .replicate_with(RuleFns::new(serialize_transform, deserialize_transform));
+//!
+//! /// Position with centimeter precision.
+//! #[derive(Component, Deref, Deserialize, Serialize)]
+//! struct Position(Vec3);
+//!
+//! impl From<Vec3> for Position {
+//!     fn from(value: Vec3) -> Self {
+//!         Self(value)
+//!     }
+//! }
+//!
+//! fn serialize_transform(
+//!     _ctx: &SerializeCtx,
+//!     transform: &Transform,
+//!     message: &mut Vec<u8>,
+//! ) -> postcard::Result<()> {
+//!     quantization::serialize_fixed_vec3(transform.translation, 100.0, message)?;
+//!     quantization::serialize_quat(transform.rotation, message)
+//! }
+//!
+//! fn deserialize_transform(
+//!     _ctx: &mut WriteCtx,
+//!     message: &mut Bytes,
+//! ) -> postcard::Result<Transform> {
+//!     let translation = quantization::deserialize_fixed_vec3(100.0, message)?;
+//!     let rotation = quantization::deserialize_quat(message)?;
+//!     Ok(Transform::from_translation(translation).with_rotation(rotation))
+//! }
+//! ```
+
+use std::{f32::consts::FRAC_1_SQRT_2, ops::Deref};
+
+use bevy::prelude::*;
+use bytes::Bytes;
+
+use super::{
+    ctx::{SerializeCtx, WriteCtx},
+    rule_fns::RuleFns,
+};
+use crate::core::postcard_utils;
+
+impl<C: Component + Deref<Target = Vec3> + From<Vec3>> RuleFns<C> {
+    /// Creates functions that serialize the vector as fixed-point numbers with `SCALE` units per 1.0.
+    ///
+    /// For example, use 100 for centimeter precision if the vector represents a position in meters.
+    ///
+    /// See [`serialize_fixed_vec3`] for details.
+    pub fn fixed_point<const SCALE: u32>() -> Self {
+        Self::new(
+            serialize_fixed_point::<C, SCALE>,
+            deserialize_fixed_point::<C, SCALE>,
+        )
+    }
+}
+
+impl<C: Component + Deref<Target = Quat> + From<Quat>> RuleFns<C> {
+    /// Creates functions that pack the rotation into 4 bytes.
+    ///
+    /// See [`serialize_quat`] for details.
+    pub fn smallest_three() -> Self {
+        Self::new(
+            serialize_smallest_three::<C>,
+            deserialize_smallest_three::<C>,
+        )
+    }
+}
+
+impl<C: Component + Deref<Target = f32> + From<f32>> RuleFns<C> {
+    /// Creates functions that serialize the value as a half-precision float.
+    ///
+    /// See [`serialize_half`] for details.
+    pub fn half_float() -> Self {
+        Self::new(serialize_half_float::<C>, deserialize_half_float::<C>)
+    }
+}
+
+/// Functions for [`RuleFns::fixed_point`].
+fn serialize_fixed_point<C: Deref<Target = Vec3>, const SCALE: u32>(
+    _ctx: &SerializeCtx,
+    component: &C,
+    message: &mut Vec<u8>,
+) -> postcard::Result<()> {
+    serialize_fixed_vec3(**component, SCALE as f32, message)
+}
+
+fn deserialize_fixed_point<C: From<Vec3>, const SCALE: u32>(
+    _ctx: &mut WriteCtx,
+    message: &mut Bytes,
+) -> postcard::Result<C> {
+    deserialize_fixed_vec3(SCALE as f32, message).map(Into::into)
+}
+
+/// Functions for [`RuleFns::smallest_three`].
+fn serialize_smallest_three<C: Deref<Target = Quat>>(
+    _ctx: &SerializeCtx,
+    component: &C,
+    message: &mut Vec<u8>,
+) -> postcard::Result<()> {
+    serialize_quat(**component, message)
+}
+
+fn deserialize_smallest_three<C: From<Quat>>(
+    _ctx: &mut WriteCtx,
+    message: &mut Bytes,
+) -> postcard::Result<C> {
+    deserialize_quat(message).map(Into::into)
+}
+
+/// Functions for [`RuleFns::half_float`].
+fn serialize_half_float<C: Deref<Target = f32>>(
+    _ctx: &SerializeCtx,
+    component: &C,
+    message: &mut Vec<u8>,
+) -> postcard::Result<()> {
+    serialize_half(**component, message)
+}
+
+fn deserialize_half_float<C: From<f32>>(
+    _ctx: &mut WriteCtx,
+    message: &mut Bytes,
+) -> postcard::Result<C> {
+    deserialize_half(message).map(Into::into)
+}
+
+/// Serializes a vector as fixed-point numbers with `scale` units per 1.0.
+///
+/// Each component is multiplied by `scale`, rounded and written as a variable-length integer,
+/// so small values take fewer bytes. Values outside of [`i32`] range after scaling are saturated.
+pub fn serialize_fixed_vec3(
+    value: Vec3,
+    scale: f32,
+    message: &mut Vec<u8>,
+) -> postcard::Result<()> {
+    let fixed = (value * scale).round().as_ivec3();
+    postcard_utils::to_extend_mut(&fixed.to_array(), message)
+}
+
+/// Deserializes a vector written by [`serialize_fixed_vec3`].
+///
+/// `scale` should match the one used for serialization.
+pub fn deserialize_fixed_vec3(scale: f32, message: &mut Bytes) -> postcard::Result<Vec3> {
+    let fixed: [i32; 3] = postcard_utils::from_buf(message)?;
+    Ok(IVec3::from_array(fixed).as_vec3() / scale)
+}
+
+/// Number of bits for each of the three smallest quaternion components.
+const QUAT_BITS: u32 = 10;
+
+/// Maximum absolute value of a quantized quaternion component.
+const QUAT_MAX: i32 = (1 << (QUAT_BITS - 1)) - 1;
+
+/// Serializes a unit quaternion into 4 bytes using the smallest-three method.
+///
+/// The component with the largest magnitude is dropped and restored from the others
+/// since the quaternion is normalized. The remaining three are within ±1/√2
+/// and quantized to 10 bits each, which gives a precision of about 0.0014.
+/// The sign is normalized, so `q` and `-q` (the same rotation) produce the same output.
+///
+/// The quaternion should be normalized.
+pub fn serialize_quat(quat: Quat, message: &mut Vec<u8>) -> postcard::Result<()> {
+    let values = quat.to_array();
+    let (largest, &largest_value) = values
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+        .unwrap();
+    let sign = largest_value.signum();
+
+    let mut packed = largest as u32;
+    for (_, &value) in values
+        .iter()
+        .enumerate()
+        .filter(|&(index, _)| index != largest)
+    {
+        let normalized = (value * sign / FRAC_1_SQRT_2).clamp(-1.0, 1.0);
+        let quantized = (normalized * QUAT_MAX as f32).round() as i32 + QUAT_MAX;
+        packed = (packed << QUAT_BITS) | quantized as u32;
+    }
+
+    postcard_utils::to_extend_mut(&packed.to_le_bytes(), message)
+}
+
+/// Deserializes a quaternion written by [`serialize_quat`].
+pub fn deserialize_quat(message: &mut Bytes) -> postcard::Result<Quat> {
+    let bytes: [u8; 4] = postcard_utils::from_buf(message)?;
+    let mut packed = u32::from_le_bytes(bytes);
+
+    let mut smallest = [0.0; 3];
+    for value in smallest.iter_mut().rev() {
+        let quantized = (packed & ((1 << QUAT_BITS) - 1)) as i32 - QUAT_MAX;
+        *value = quantized as f32 / QUAT_MAX as f32 * FRAC_1_SQRT_2;
+        packed >>= QUAT_BITS;
+    }
+
+    let largest = packed as usize;
+    if largest > 3 {
+        return Err(postcard::Error::DeserializeBadEncoding);
+    }
+
+    let sum: f32 = smallest.iter().map(|value| value * value).sum();
+    let mut values = [0.0; 4];
+    let mut smallest = smallest.into_iter();
+    for (index, value) in values.iter_mut().enumerate() {
+        *value = if index == largest {
+            (1.0 - sum).max(0.0).sqrt()
+        } else {
+            smallest.next().unwrap()
+        };
+    }
+
+    Ok(Quat::from_array(values).normalize())
+}
+
+/// Serializes a float as a half-precision float in 2 bytes.
+///
+/// Has about 3 significant decimal digits and a maximum value of 65504, larger values become infinity.
+/// Suitable for values in a known small range, such as health or normalized direction components.
+///
+/// See also [`f32_to_half`].
+pub fn serialize_half(value: f32, message: &mut Vec<u8>) -> postcard::Result<()> {
+    postcard_utils::to_extend_mut(&f32_to_half(value).to_le_bytes(), message)
+}
+
+/// Deserializes a float written by [`serialize_half`].
+pub fn deserialize_half(message: &mut Bytes) -> postcard::Result<f32> {
+    let bytes: [u8; 2] = postcard_utils::from_buf(message)?;
+    Ok(half_to_f32(u16::from_le_bytes(bytes)))
+}
+
+/// Converts a float into bits of a half-precision float.
+///
+/// Rounds to the nearest representable value, ties to even.
+pub fn f32_to_half(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exponent == 0xff {
+        // Keep NaN as NaN by setting a mantissa bit.
+        let nan = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+
+    if half_exponent <= 0 {
+        if half_exponent < -10 {
+            return sign;
+        }
+
+        // Subnormal, shift the mantissa together with the implicit bit.
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - half_exponent) as u32;
+        let round_bit = 1 << (shift - 1);
+        let mut half_mantissa = mantissa >> shift;
+        if mantissa & round_bit != 0 && mantissa & (3 * round_bit - 1) != 0 {
+            half_mantissa += 1;
+        }
+        return sign | half_mantissa as u16;
+    }
+
+    let round_bit = 0x1000;
+    let mut half = ((half_exponent as u32) << 10) | (mantissa >> 13);
+    if mantissa & round_bit != 0 && mantissa & (3 * round_bit - 1) != 0 {
+        // Carry into the exponent is correct and could produce infinity.
+        half += 1;
+    }
+    sign | half as u16
+}
+
+/// Converts bits of a half-precision float into a float.
+pub fn half_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exponent = ((half >> 10) & 0x1f) as u32;
+    let mantissa = (half & 0x3ff) as u32;
+
+    match exponent {
+        0 => {
+            // Zero or subnormal, exactly representable in f32.
+            let value = mantissa as f32 / (1 << 24) as f32;
+            if sign != 0 {
+                -value
+            } else {
+                value
+            }
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (mantissa << 13)),
+        _ => f32::from_bits(sign | ((exponent + 127 - 15) << 23) | (mantissa << 13)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_vec3() {
+        let value = Vec3::new(1.234, -5.678, 1000.0);
+        let mut message = Vec::new();
+        serialize_fixed_vec3(value, 100.0, &mut message).unwrap();
+
+        let restored = deserialize_fixed_vec3(100.0, &mut message.into()).unwrap();
+        assert!(restored.abs_diff_eq(value, 0.005));
+    }
+
+    #[test]
+    fn quat() {
+        for quat in [
+            Quat::IDENTITY,
+            -Quat::IDENTITY,
+            Quat::from_rotation_x(1.0),
+            Quat::from_euler(EulerRot::XYZ, 0.3, -2.0, 1.5),
+            Quat::from_axis_angle(Vec3::ONE.normalize(), -3.0),
+        ] {
+            let mut message = Vec::new();
+            serialize_quat(quat, &mut message).unwrap();
+            assert_eq!(message.len(), 4);
+
+            let restored = deserialize_quat(&mut message.into()).unwrap();
+            assert!(
+                restored.angle_between(quat) < 0.01,
+                "`{restored}` should be close to `{quat}`"
+            );
+        }
+    }
+
+    #[test]
+    fn half() {
+        for (value, expected) in [
+            (0.0, 0.0),
+            (-0.0, -0.0),
+            (1.0, 1.0),
+            (-2.5, -2.5),
+            (65504.0, 65504.0),
+            (1.0e6, f32::INFINITY),
+            (f32::NEG_INFINITY, f32::NEG_INFINITY),
+            (6.0e-8, 5.9604645e-8),
+            (1.0e-9, 0.0),
+            (0.1, 0.099975586),
+        ] {
+            let mut message = Vec::new();
+            serialize_half(value, &mut message).unwrap();
+            assert_eq!(message.len(), 2);
+
+            let restored = deserialize_half(&mut message.into()).unwrap();
+            assert_eq!(
+                restored, expected,
+                "`{value}` should be restored as `{expected}`"
+            );
+            assert_eq!(restored.is_sign_negative(), expected.is_sign_negative());
+        }
+
+        assert!(half_to_f32(f32_to_half(f32::NAN)).is_nan());
+    }
+
+    #[test]
+    fn half_rounding() {
+        // Exactly between 1.0 and the next half, should round to even.
+        let halfway = f32::from_bits(1.0f32.to_bits() | 0x1000);
+        assert_eq!(f32_to_half(halfway), f32_to_half(1.0));
+
+        // Slightly above the halfway point.
+        let above = f32::from_bits(1.0f32.to_bits() | 0x1001);
+        assert_eq!(f32_to_half(above), f32_to_half(1.0) + 1);
+    }
+}
@@ -8,7 +8,7 @@ use bytes::Bytes;
 use serde::{de::DeserializeOwned, Serialize};
 
 use super::ctx::{SerializeCtx, WriteCtx};
-use crate::core::postcard_utils;
+use crate::core::{postcard_utils, replication::field_groups::FieldGroups};
 
 /// Type-erased version of [`RuleFns`].
 ///
@@ -21,9 +21,65 @@ pub(crate) struct UntypedRuleFns {
     deserialize: unsafe fn(),
     deserialize_in_place: unsafe fn(),
     consume: unsafe fn(),
+    default_fns: Option<(unsafe fn(), unsafe fn())>,
+    diffs: bool,
+    delta: Option<(DiffFn, ApplyDiffFn)>,
+    final_value: bool,
+    field_groups: Option<(u8, unsafe fn())>,
+    mapped: bool,
 }
 
 impl UntypedRuleFns {
+    /// Returns `true` if mutations should be sent as diffs.
+    ///
+    /// See [`RuleFns::with_diffs`].
+    pub(crate) fn diffs(&self) -> bool {
+        self.diffs
+    }
+
+    /// Returns functions to encode mutations relative to the last acknowledged value.
+    ///
+    /// Always [`None`] for rules with diffs or field groups.
+    ///
+    /// See [`RuleFns::with_delta`].
+    pub(crate) fn delta(&self) -> Option<(DiffFn, ApplyDiffFn)> {
+        self.delta
+            .filter(|_| !self.diffs && self.field_groups.is_none())
+    }
+
+    /// Returns `true` if the last value should be sent with removals.
+    ///
+    /// See [`RuleFns::with_final_value`].
+    pub(crate) fn final_value(&self) -> bool {
+        self.final_value
+    }
+
+    /// Returns the number of field groups if the component is replicated by groups.
+    ///
+    /// See [`RuleFns::field_groups`].
+    pub(crate) fn field_groups(&self) -> Option<u8> {
+        self.field_groups.map(|(groups, _)| groups)
+    }
+
+    /// Returns `true` if the serialized data is prefixed with a default flag.
+    ///
+    /// See [`RuleFns::with_default_elision`].
+    pub(crate) fn default_elision(&self) -> bool {
+        self.default_fns.is_some()
+    }
+
+    /// Returns `true` if the functions map entities inside the component.
+    ///
+    /// See [`RuleFns::default_mapped`].
+    pub(crate) fn mapped(&self) -> bool {
+        self.mapped
+    }
+
+    /// Returns the type ID of the component for which the functions were created.
+    pub(crate) fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
     /// Restores the original [`RuleFns`] from which this type was created.
     ///
     /// # Safety
@@ -47,6 +103,18 @@ impl UntypedRuleFns {
                 mem::transmute::<unsafe fn(), DeserializeInPlaceFn<C>>(self.deserialize_in_place)
             },
             consume: unsafe { mem::transmute::<unsafe fn(), ConsumeFn<C>>(self.consume) },
+            default_fns: self.default_fns.map(|(is_default, default)| DefaultFns {
+                is_default: unsafe { mem::transmute::<unsafe fn(), fn(&C) -> bool>(is_default) },
+                default: unsafe { mem::transmute::<unsafe fn(), fn() -> C>(default) },
+            }),
+            diffs: self.diffs,
+            delta: self.delta,
+            final_value: self.final_value,
+            field_groups: self.field_groups.map(|(groups, serialize)| FieldGroupFns {
+                groups,
+                serialize: unsafe { mem::transmute::<unsafe fn(), SerializeGroupFn<C>>(serialize) },
+            }),
+            mapped: self.mapped,
         }
     }
 }
@@ -65,6 +133,22 @@ impl<C: Component> From<RuleFns<C>> for UntypedRuleFns {
                 mem::transmute::<DeserializeInPlaceFn<C>, unsafe fn()>(value.deserialize_in_place)
             },
             consume: unsafe { mem::transmute::<ConsumeFn<C>, unsafe fn()>(value.consume) },
+            default_fns: value.default_fns.map(|default_fns| unsafe {
+                (
+                    mem::transmute::<fn(&C) -> bool, unsafe fn()>(default_fns.is_default),
+                    mem::transmute::<fn() -> C, unsafe fn()>(default_fns.default),
+                )
+            }),
+            diffs: value.diffs,
+            delta: value.delta,
+            final_value: value.final_value,
+            field_groups: value.field_groups.map(|field_groups| unsafe {
+                (
+                    field_groups.groups,
+                    mem::transmute::<SerializeGroupFn<C>, unsafe fn()>(field_groups.serialize),
+                )
+            }),
+            mapped: value.mapped,
         }
     }
 }
@@ -78,6 +162,12 @@ pub struct RuleFns<C> {
     deserialize: DeserializeFn<C>,
     deserialize_in_place: DeserializeInPlaceFn<C>,
     consume: ConsumeFn<C>,
+    default_fns: Option<DefaultFns<C>>,
+    diffs: bool,
+    delta: Option<(DiffFn, ApplyDiffFn)>,
+    final_value: bool,
+    field_groups: Option<FieldGroupFns<C>>,
+    mapped: bool,
 }
 
 impl<C: Component> RuleFns<C> {
@@ -90,6 +180,12 @@ impl<C: Component> RuleFns<C> {
             deserialize,
             deserialize_in_place: in_place_as_deserialize::<C>,
             consume: consume_as_deserialize,
+            default_fns: None,
+            diffs: false,
+            delta: None,
+            final_value: false,
+            field_groups: None,
+            mapped: false,
         }
     }
 
@@ -120,6 +216,59 @@ impl<C: Component> RuleFns<C> {
         self
     }
 
+    /// Sends mutations as diffs over the reliable channel.
+    ///
+    /// By default mutations are sent over [`ReplicationChannel::Mutations`](crate::core::channels::ReplicationChannel::Mutations)
+    /// and may be lost, so each one contains the full component. With this option, mutations
+    /// are included into update messages instead and serialized with [`SerializeCtx::diff`] set to `true`.
+    /// Since update messages are reliable and ordered, every diff is delivered exactly once,
+    /// so the serialization function needs to write only changes since the last sent tick.
+    ///
+    /// Insertions are still serialized with [`SerializeCtx::diff`] set to `false`.
+    /// On client both are passed to the in-place deserialization if the component is already present,
+    /// so the serialized data should indicate which one it is.
+    ///
+    /// See [`ReplicatedCollection`](crate::addons::collection::ReplicatedCollection) for an example.
+    pub fn with_diffs(mut self) -> Self {
+        self.diffs = true;
+        self
+    }
+
+    /// Encodes mutations relative to the last value acknowledged by each client.
+    ///
+    /// The server keeps serialized values from previous ticks and, instead of the full component,
+    /// sends the output of `diff` between the value the client is known to have and the current one.
+    /// The client keeps received values and restores the full component with `apply_diff` before
+    /// passing it to the regular deserialization. If there is no known value for the client
+    /// (the entity just became visible or the acknowledgment is too old), the full component is sent.
+    ///
+    /// Both functions work on serialized bytes, see [`byte_diff`] and [`apply_byte_diff`]
+    /// for a generic implementation. It's worth it for large components with only a few
+    /// fields changing on each tick.
+    ///
+    /// Ignored for rules created with [`Self::with_diffs`] or [`Self::field_groups`].
+    /// Not compatible with [`MutationBufferPolicy::optimistic`](crate::client::mutation_buffer::MutationBufferPolicy::optimistic)
+    /// since mutations could be applied before the values they are based on.
+    pub fn with_delta(mut self, diff: DiffFn, apply_diff: ApplyDiffFn) -> Self {
+        self.delta = Some((diff, apply_diff));
+        self
+    }
+
+    /// Includes the last value of the component into its removal.
+    ///
+    /// When the component is removed on server, its value is captured and sent together with the removal.
+    /// On client the value is written using the regular write functions right before the removal,
+    /// and [`RemoveCtx::final_value`](super::ctx::RemoveCtx::final_value) is set to `true`.
+    /// This allows custom remove functions from markers to animate the removal using the correct final state
+    /// instead of the last replicated one.
+    ///
+    /// On server values are captured by an observer that is created in [`App::finish`],
+    /// so the rule should be registered before it.
+    pub fn with_final_value(mut self) -> Self {
+        self.final_value = true;
+        self
+    }
+
     /// Serializes a component into a message.
     pub(super) fn serialize(
         &self,
@@ -127,13 +276,47 @@ impl<C: Component> RuleFns<C> {
         component: &C,
         message: &mut Vec<u8>,
     ) -> postcard::Result<()> {
+        if let Some(default_fns) = &self.default_fns {
+            let is_default = (default_fns.is_default)(component);
+            postcard_utils::to_extend_mut(&is_default, message)?;
+            if is_default {
+                return Ok(());
+            }
+        }
+
         (self.serialize)(ctx, component, message)
     }
 
+    /// Serializes a single field group of a component into a message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rule wasn't created with [`Self::field_groups`].
+    pub(super) fn serialize_group(
+        &self,
+        component: &C,
+        group: u8,
+        message: &mut Vec<u8>,
+    ) -> postcard::Result<()> {
+        let field_groups = self
+            .field_groups
+            .as_ref()
+            .expect("rule should be created with field groups");
+
+        (field_groups.serialize)(component, group, message)
+    }
+
     /// Deserializes a component from a message.
     ///
     /// Use this function when inserting a new component.
     pub fn deserialize(&self, ctx: &mut WriteCtx, message: &mut Bytes) -> postcard::Result<C> {
+        if let Some(default_fns) = &self.default_fns {
+            let is_default: bool = postcard_utils::from_buf(message)?;
+            if is_default {
+                return Ok((default_fns.default)());
+            }
+        }
+
         (self.deserialize)(ctx, message)
     }
 
@@ -146,15 +329,67 @@ impl<C: Component> RuleFns<C> {
         component: &mut C,
         message: &mut Bytes,
     ) -> postcard::Result<()> {
+        if let Some(default_fns) = &self.default_fns {
+            let is_default: bool = postcard_utils::from_buf(message)?;
+            if is_default {
+                *component = (default_fns.default)();
+                return Ok(());
+            }
+        }
+
         (self.deserialize_in_place)(self.deserialize, ctx, component, message)
     }
 
     /// Consumes a component from a message.
     pub(super) fn consume(&self, ctx: &mut WriteCtx, message: &mut Bytes) -> postcard::Result<()> {
+        if self.default_fns.is_some() {
+            let is_default: bool = postcard_utils::from_buf(message)?;
+            if is_default {
+                return Ok(());
+            }
+        }
+
         (self.consume)(self.deserialize, ctx, message)
     }
 }
 
+impl<C: Component + Default + PartialEq> RuleFns<C> {
+    /// Enables a fast path for components equal to their [`Default`] value.
+    ///
+    /// Such components will be encoded as a single flag instead of the full payload,
+    /// and the client will construct the default value on write.
+    /// Other values will be prefixed with this flag, so it's only worth it for
+    /// components that are often equal to their default.
+    pub fn with_default_elision(mut self) -> Self {
+        self.default_fns = Some(DefaultFns {
+            is_default: is_default::<C>,
+            default: C::default,
+        });
+        self
+    }
+}
+
+impl<C: Component + FieldGroups + Default> RuleFns<C> {
+    /// Creates a new instance that replicates a component by groups of fields.
+    ///
+    /// Mutations will include only groups that changed since the last acknowledged
+    /// mutation for the client, prefixed with a change mask. Groups are compared by
+    /// their serialized bytes, so assigning the same value won't cause them to be sent.
+    ///
+    /// On insertion the client deserializes all groups into [`Default`] value of the component.
+    ///
+    /// See [`FieldGroups`] for details.
+    pub fn field_groups() -> Self {
+        let mut rule_fns = Self::new(serialize_groups::<C>, deserialize_groups::<C>)
+            .with_in_place(deserialize_groups_in_place::<C>);
+        rule_fns.field_groups = Some(FieldGroupFns {
+            groups: C::GROUPS,
+            serialize: C::serialize_group,
+        });
+        rule_fns
+    }
+}
+
 impl<C: Component + Serialize + DeserializeOwned + MapEntities> RuleFns<C> {
     /// Like [`Self::default`], but uses a special deserialization function to map server
     /// entities inside the component into client entities.
@@ -163,7 +398,9 @@ impl<C: Component + Serialize + DeserializeOwned + MapEntities> RuleFns<C> {
     ///
     /// See also [`default_serialize`], [`default_deserialize_mapped`] and [`in_place_as_deserialize`].
     pub fn default_mapped() -> Self {
-        Self::new(default_serialize::<C>, default_deserialize_mapped::<C>)
+        let mut rule_fns = Self::new(default_serialize::<C>, default_deserialize_mapped::<C>);
+        rule_fns.mapped = true;
+        rule_fns
     }
 }
 
@@ -178,6 +415,81 @@ impl<C: Component + Serialize + DeserializeOwned> Default for RuleFns<C> {
     }
 }
 
+/// Functions for [`RuleFns::with_default_elision`].
+struct DefaultFns<C> {
+    is_default: fn(&C) -> bool,
+    default: fn() -> C,
+}
+
+fn is_default<C: Default + PartialEq>(component: &C) -> bool {
+    *component == C::default()
+}
+
+/// Functions for [`RuleFns::field_groups`].
+struct FieldGroupFns<C> {
+    groups: u8,
+    serialize: SerializeGroupFn<C>,
+}
+
+/// Signature of [`FieldGroups::serialize_group`].
+type SerializeGroupFn<C> = fn(&C, u8, &mut Vec<u8>) -> postcard::Result<()>;
+
+/// Serializes a mask with all groups set followed by all groups.
+fn serialize_groups<C: Component + FieldGroups>(
+    _ctx: &SerializeCtx,
+    component: &C,
+    message: &mut Vec<u8>,
+) -> postcard::Result<()> {
+    let mask = u64::MAX >> (u64::BITS - C::GROUPS as u32);
+    postcard_utils::to_extend_mut(&mask, message)?;
+    for group in 0..C::GROUPS {
+        component.serialize_group(group, message)?;
+    }
+
+    Ok(())
+}
+
+/// Deserializes groups from the mask into the default component.
+fn deserialize_groups<C: Component + FieldGroups + Default>(
+    ctx: &mut WriteCtx,
+    message: &mut Bytes,
+) -> postcard::Result<C> {
+    let mut component = C::default();
+    deserialize_groups_in_place(deserialize_groups, ctx, &mut component, message)?;
+    Ok(component)
+}
+
+/// Deserializes groups from the mask, keeping other fields unchanged.
+fn deserialize_groups_in_place<C: Component + FieldGroups>(
+    _deserialize: DeserializeFn<C>,
+    _ctx: &mut WriteCtx,
+    component: &mut C,
+    message: &mut Bytes,
+) -> postcard::Result<()> {
+    let mut mask: u64 = postcard_utils::from_buf(message)?;
+    while mask != 0 {
+        let group = mask.trailing_zeros() as u8;
+        component.deserialize_group(group, message)?;
+        mask &= mask - 1;
+    }
+
+    Ok(())
+}
+
+/// Signature of delta encoding functions.
+///
+/// Accepts serialized baseline and current values and writes the difference between them.
+///
+/// See [`RuleFns::with_delta`].
+pub type DiffFn = fn(&[u8], &[u8], &mut Vec<u8>) -> postcard::Result<()>;
+
+/// Signature of delta decoding functions.
+///
+/// Accepts serialized baseline value and the difference from [`DiffFn`] and writes the restored value.
+///
+/// See [`RuleFns::with_delta`].
+pub type ApplyDiffFn = fn(&[u8], &[u8], &mut Vec<u8>) -> postcard::Result<()>;
+
 /// Signature of component serialization functions.
 pub type SerializeFn<C> = fn(&SerializeCtx, &C, &mut Vec<u8>) -> postcard::Result<()>;
 
@@ -244,3 +556,103 @@ pub fn consume_as_deserialize<C: Component>(
     ctx.ignore_mapping = false;
     Ok(())
 }
+
+/// Default delta encoding function.
+///
+/// Writes the value length followed by runs of bytes that differ from the baseline,
+/// each prefixed with the number of skipped equal bytes and the run length.
+pub fn byte_diff(baseline: &[u8], value: &[u8], message: &mut Vec<u8>) -> postcard::Result<()> {
+    // Runs separated by fewer equal bytes are merged since each run costs at least 2 bytes.
+    const MIN_GAP: usize = 3;
+
+    postcard_utils::to_extend_mut(&value.len(), message)?;
+
+    let is_changed = |index: usize| baseline.get(index) != Some(&value[index]);
+    let mut last_end = 0;
+    let mut index = 0;
+    while index < value.len() {
+        if !is_changed(index) {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        let mut end = index + 1;
+        while end < value.len() && (end..(end + MIN_GAP).min(value.len())).any(is_changed) {
+            end += 1;
+        }
+
+        postcard_utils::to_extend_mut(&(start - last_end), message)?;
+        postcard_utils::to_extend_mut(&(end - start), message)?;
+        message.extend_from_slice(&value[start..end]);
+        last_end = end;
+        index = end;
+    }
+
+    Ok(())
+}
+
+/// Default delta decoding function for [`byte_diff`].
+pub fn apply_byte_diff(baseline: &[u8], diff: &[u8], value: &mut Vec<u8>) -> postcard::Result<()> {
+    let mut diff = diff;
+    let (len, remaining) = postcard::take_from_bytes::<usize>(diff)?;
+    diff = remaining;
+
+    value.clear();
+    value.extend_from_slice(&baseline[..len.min(baseline.len())]);
+    value.resize(len, 0);
+
+    let mut position = 0;
+    while !diff.is_empty() {
+        let (skip, remaining) = postcard::take_from_bytes::<usize>(diff)?;
+        let (run, remaining) = postcard::take_from_bytes::<usize>(remaining)?;
+        let start = position + skip;
+        let end = start + run;
+        let (bytes, remaining) = remaining
+            .split_at_checked(run)
+            .ok_or(postcard::Error::DeserializeUnexpectedEnd)?;
+        value
+            .get_mut(start..end)
+            .ok_or(postcard::Error::DeserializeBadEncoding)?
+            .copy_from_slice(bytes);
+        position = end;
+        diff = remaining;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_diff_roundtrip() {
+        for (baseline, value) in [
+            (&[1, 2, 3, 4, 5, 6, 7, 8][..], &[1, 2, 0, 4, 5, 6, 7, 9][..]),
+            (&[1, 2, 3], &[1, 2, 3, 4, 5]),
+            (&[1, 2, 3, 4, 5], &[0, 2]),
+            (&[], &[1, 2, 3]),
+            (&[1, 2, 3], &[1, 2, 3]),
+        ] {
+            let mut diff = Vec::new();
+            byte_diff(baseline, value, &mut diff).unwrap();
+
+            let mut restored = Vec::new();
+            apply_byte_diff(baseline, &diff, &mut restored).unwrap();
+            assert_eq!(restored, value);
+        }
+    }
+
+    #[test]
+    fn byte_diff_size() {
+        let baseline = [0; 64];
+        let mut value = baseline;
+        value[10] = 1;
+        value[40] = 1;
+
+        let mut diff = Vec::new();
+        byte_diff(&baseline, &value, &mut diff).unwrap();
+        assert_eq!(diff.len(), 7, "should contain length and two runs");
+    }
+}
@@ -101,6 +101,7 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
         let ctx = SerializeCtx {
             server_tick,
             component_id,
+            diff: false,
         };
         let ptr = self.get_by_id(component_id).unwrap_or_else(|_| {
             let components = self.world().components();
@@ -178,6 +179,7 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
                     commands: &mut commands,
                     message_tick,
                     component_id,
+                    final_value: false,
                 };
 
                 component_fns.remove(&mut ctx, &entity_markers, &mut entity);
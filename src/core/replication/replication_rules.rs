@@ -1,4 +1,4 @@
-use std::cmp::Reverse;
+use std::{any, cmp::Reverse};
 
 use bevy::{
     ecs::{archetype::Archetype, component::ComponentId, entity::MapEntities},
@@ -8,6 +8,7 @@ use bevy::{
 use serde::{de::DeserializeOwned, Serialize};
 
 use super::replication_registry::{rule_fns::RuleFns, FnsId, ReplicationRegistry};
+use crate::core::entity_serde::MappedTypes;
 
 /// Replication functions for [`App`].
 pub trait AppRuleExt {
@@ -21,6 +22,9 @@ pub trait AppRuleExt {
     ///
     /// If your component contains any [`Entity`] inside, use [`Self::replicate_mapped`].
     ///
+    /// Registering a rule with the same components again replaces its functions, but keeps
+    /// the previously assigned IDs. This makes it safe to call from plugins that may be rebuilt.
+    ///
     /// See also [`Self::replicate_with`] and the section on [`components`](../../index.html#components)
     /// from the quick start guide.
     fn replicate<C>(&mut self) -> &mut Self
@@ -59,10 +63,7 @@ pub trait AppRuleExt {
     **/
     fn replicate_mapped<C>(&mut self) -> &mut Self
     where
-        C: Component + Serialize + DeserializeOwned + MapEntities,
-    {
-        self.replicate_with::<C>(RuleFns::default_mapped())
-    }
+        C: Component + Serialize + DeserializeOwned + MapEntities;
 
     /**
     Same as [`Self::replicate`], but uses the specified functions for serialization and deserialization.
@@ -315,34 +316,40 @@ pub trait AppRuleExt {
 }
 
 impl AppRuleExt for App {
+    fn replicate_mapped<C>(&mut self) -> &mut Self
+    where
+        C: Component + Serialize + DeserializeOwned + MapEntities,
+    {
+        self.world_mut()
+            .resource_mut::<MappedTypes>()
+            .push(any::type_name::<C>());
+        self.replicate_with::<C>(RuleFns::default_mapped())
+    }
+
     fn replicate_with<C>(&mut self, rule_fns: RuleFns<C>) -> &mut Self
     where
         C: Component,
     {
-        let rule =
-            self.world_mut()
-                .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
-                    let fns_info = registry.register_rule_fns(world, rule_fns);
-                    ReplicationRule::new(vec![fns_info])
-                });
-
         self.world_mut()
-            .resource_mut::<ReplicationRules>()
-            .insert(rule);
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                let fns_info = registry.register_rule_fns(world, rule_fns);
+                let rule = ReplicationRule::new(vec![fns_info]);
+                world
+                    .resource_mut::<ReplicationRules>()
+                    .insert(&mut registry, rule);
+            });
 
         self
     }
 
     fn replicate_group<C: GroupReplication>(&mut self) -> &mut Self {
-        let rule =
-            self.world_mut()
-                .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
-                    C::register(world, &mut registry)
-                });
-
         self.world_mut()
-            .resource_mut::<ReplicationRules>()
-            .insert(rule);
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                let rule = C::register(world, &mut registry);
+                world
+                    .resource_mut::<ReplicationRules>()
+                    .insert(&mut registry, rule);
+            });
 
         self
     }
@@ -354,7 +361,29 @@ pub struct ReplicationRules(Vec<ReplicationRule>);
 
 impl ReplicationRules {
     /// Inserts a new rule, maintaining sorting by their priority in descending order.
-    fn insert(&mut self, rule: ReplicationRule) {
+    ///
+    /// If a rule with the same components is already registered, it will be replaced,
+    /// but the previously assigned [`FnsId`]s will be reused.
+    fn insert(&mut self, registry: &mut ReplicationRegistry, mut rule: ReplicationRule) {
+        if let Some(index) = self
+            .iter()
+            .position(|old_rule| old_rule.same_components(&rule))
+        {
+            debug!("re-registering rule with priority {}", rule.priority);
+            let old_rule = self.0.remove(index);
+
+            // Iterate in reverse order to remove new slots from the end.
+            for (component_id, fns_id) in rule.components.iter_mut().rev() {
+                let &(_, old_fns_id) = old_rule
+                    .components
+                    .iter()
+                    .find(|(old_id, _)| old_id == component_id)
+                    .expect("rules with the same components should contain the component");
+                registry.reuse_rule_fns(old_fns_id, *fns_id);
+                *fns_id = old_fns_id;
+            }
+        }
+
         let index = self
             .binary_search_by_key(&Reverse(rule.priority), |rule| Reverse(rule.priority))
             .unwrap_or_else(|index| index);
@@ -384,6 +413,17 @@ impl ReplicationRule {
         }
     }
 
+    /// Returns `true` if both rules consist of the same components.
+    fn same_components(&self, other: &ReplicationRule) -> bool {
+        self.components.len() == other.components.len()
+            && self.components.iter().all(|&(component_id, _)| {
+                other
+                    .components
+                    .iter()
+                    .any(|&(other_id, _)| other_id == component_id)
+            })
+    }
+
     /// Determines whether an archetype contains all components required by the rule.
     pub(crate) fn matches(&self, archetype: &Archetype) -> bool {
         self.components
@@ -522,6 +562,32 @@ mod tests {
         assert_eq!(priorities, [2, 2, 1, 1, 1, 1]);
     }
 
+    #[test]
+    fn reregistration() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationRegistry>()
+            .replicate::<ComponentA>()
+            .replicate_group::<(ComponentA, ComponentB)>();
+
+        let fns_ids = collect_fns_ids(app.world().resource::<ReplicationRules>());
+
+        app.replicate_group::<(ComponentB, ComponentA)>()
+            .replicate::<ComponentA>();
+
+        let replication_rules = app.world().resource::<ReplicationRules>();
+        let priorities: Vec<_> = replication_rules.iter().map(|rule| rule.priority).collect();
+        assert_eq!(priorities, [2, 1]);
+        assert_eq!(collect_fns_ids(replication_rules), fns_ids);
+    }
+
+    fn collect_fns_ids(replication_rules: &ReplicationRules) -> HashSet<(ComponentId, FnsId)> {
+        replication_rules
+            .iter()
+            .flat_map(|rule| rule.components.iter().copied())
+            .collect()
+    }
+
     #[derive(Serialize, Deserialize, Component)]
     struct ComponentA;
 
@@ -1,7 +1,10 @@
+use std::{mem, time::Duration};
+
 use bevy::prelude::*;
-use bytes::Bytes;
+use bytes::{Buf, Bytes};
 
-use crate::core::ClientId;
+use super::connection_stats::{StatsHistory, StatsSample};
+use crate::core::{network_fault::NetworkFault, ClientId};
 
 /// Stores information about a client independent from the messaging backend.
 ///
@@ -34,6 +37,13 @@ pub struct RepliconClient {
     packet_loss: f64,
     sent_bps: f64,
     received_bps: f64,
+    stats_history: StatsHistory,
+
+    /// Number of received messages that failed to decode since the connection.
+    decode_errors: usize,
+
+    /// Faults that will be processed according to [`FaultPolicy`](super::network_fault::FaultPolicy).
+    faults: Vec<NetworkFault>,
 }
 
 impl RepliconClient {
@@ -42,17 +52,52 @@ impl RepliconClient {
         self.received_messages.resize(channels_count, Vec::new());
     }
 
+    /// Moves messages from an ordered event channel to channels specified in their headers.
+    ///
+    /// See [`ServerEventAppExt::order_server_events`](super::event::server_event::ServerEventAppExt::order_server_events).
+    pub(crate) fn split_ordered_channel(&mut self, channel_id: u8) {
+        let messages = mem::take(&mut self.received_messages[channel_id as usize]);
+        for mut message in messages {
+            if !message.has_remaining() {
+                error!("ignoring ordered event message without header");
+                self.decode_errors += 1;
+                continue;
+            }
+            let target_id = message.get_u8();
+            let Some(channel_messages) = self.received_messages.get_mut(target_id as usize) else {
+                error!("ignoring ordered event message for unknown channel {target_id}");
+                self.decode_errors += 1;
+                continue;
+            };
+            channel_messages.push(message);
+        }
+    }
+
     /// Returns number of received messages for a channel.
     ///
     /// See also [`Self::receive`].
     pub(crate) fn received_count<I: Into<u8>>(&self, channel_id: I) -> usize {
         let channel_id = channel_id.into();
-        let channel_messages = self
-            .received_messages
+        self.received_messages
             .get(channel_id as usize)
-            .unwrap_or_else(|| panic!("client should have a receive channel with id {channel_id}"));
+            .map_or(0, Vec::len)
+    }
+
+    /// Returns received messages for a channel for in-place processing.
+    ///
+    /// Returns [`None`] and reports a fault if the channel isn't registered.
+    #[cfg(feature = "zstd")]
+    pub(crate) fn received_mut<I: Into<u8>>(&mut self, channel_id: I) -> Option<&mut Vec<Bytes>> {
+        let channel_id = channel_id.into();
+        if channel_id as usize >= self.received_messages.len() {
+            self.report_fault(NetworkFault::UnknownChannel {
+                client_id: None,
+                channel_id,
+            });
+            return None;
+        }
 
-        channel_messages.len()
+        self.received_messages.get_mut(channel_id as usize)
     }
 
     /// Receives all available messages from the server over a channel.
@@ -71,21 +116,28 @@ impl RepliconClient {
         }
 
         let channel_id = channel_id.into();
-        let channel_messages = self
-            .received_messages
-            .get_mut(channel_id as usize)
-            .unwrap_or_else(|| panic!("client should have a receive channel with id {channel_id}"));
-
-        trace!(
-            "received {} message(s) totaling {} bytes from channel {channel_id}",
-            channel_messages.len(),
-            channel_messages
-                .iter()
-                .map(|bytes| bytes.len())
-                .sum::<usize>()
-        );
-
-        channel_messages.drain(..)
+        if channel_id as usize >= self.received_messages.len() {
+            self.report_fault(NetworkFault::UnknownChannel {
+                client_id: None,
+                channel_id,
+            });
+        }
+
+        let channel_messages = self.received_messages.get_mut(channel_id as usize);
+        if let Some(channel_messages) = &channel_messages {
+            trace!(
+                "received {} message(s) totaling {} bytes from channel {channel_id}",
+                channel_messages.len(),
+                channel_messages
+                    .iter()
+                    .map(|bytes| bytes.len())
+                    .sum::<usize>()
+            );
+        }
+
+        channel_messages
+            .into_iter()
+            .flat_map(|channel_messages| channel_messages.drain(..))
     }
 
     /// Sends a message to the server over a channel.
@@ -132,6 +184,8 @@ impl RepliconClient {
             self.packet_loss = 0.0;
             self.sent_bps = 0.0;
             self.received_bps = 0.0;
+            self.stats_history.clear();
+            self.decode_errors = 0;
         }
 
         self.status = status;
@@ -207,14 +261,30 @@ impl RepliconClient {
         }
 
         let channel_id = channel_id.into();
-        let channel_messages = self
-            .received_messages
-            .get_mut(channel_id as usize)
-            .unwrap_or_else(|| panic!("client should have a channel with id {channel_id}"));
+        let Some(channel_messages) = self.received_messages.get_mut(channel_id as usize) else {
+            self.report_fault(NetworkFault::UnknownChannel {
+                client_id: None,
+                channel_id,
+            });
+            return;
+        };
 
         channel_messages.push(message.into());
     }
 
+    /// Records a fault caused by malformed input.
+    ///
+    /// The fault will be handled by the configured [`FaultPolicy`](super::network_fault::FaultPolicy).
+    pub(crate) fn report_fault(&mut self, fault: NetworkFault) {
+        debug!("recording fault: {fault}");
+        self.faults.push(fault);
+    }
+
+    /// Drains all recorded faults.
+    pub(crate) fn drain_faults(&mut self) -> impl Iterator<Item = NetworkFault> + '_ {
+        self.faults.drain(..)
+    }
+
     /// Returns the round-time trip in seconds for the connection.
     ///
     /// Returns zero if not provided by the backend.
@@ -286,6 +356,34 @@ impl RepliconClient {
     pub fn set_received_bps(&mut self, received_bps: f64) {
         self.received_bps = received_bps;
     }
+
+    /// Returns the rolling history of the connection statistics.
+    pub fn stats_history(&self) -> &StatsHistory {
+        &self.stats_history
+    }
+
+    /// Returns the number of received messages that failed to decode since the connection.
+    ///
+    /// Includes server events that failed to deserialize.
+    pub fn decode_errors(&self) -> usize {
+        self.decode_errors
+    }
+
+    /// Adds the number of messages that failed to decode.
+    pub(crate) fn add_decode_errors(&mut self, count: usize) {
+        self.decode_errors += count;
+    }
+
+    /// Adds the current statistics to the history.
+    pub(crate) fn sample_stats(&mut self, timestamp: Duration) {
+        self.stats_history.push(StatsSample {
+            timestamp,
+            rtt: self.rtt,
+            packet_loss: self.packet_loss,
+            sent_bps: self.sent_bps,
+            received_bps: self.received_bps,
+        });
+    }
 }
 
 /// Connection status of the [`RepliconClient`].
@@ -1,7 +1,9 @@
-use bevy::prelude::*;
-use bytes::Bytes;
+use std::mem;
 
-use crate::core::ClientId;
+use bevy::{prelude::*, utils::HashMap};
+use bytes::{Buf, Bytes};
+
+use crate::core::{network_fault::NetworkFault, ClientId};
 
 /// Stores information about the server independent from the messaging backend.
 ///
@@ -12,7 +14,7 @@ use crate::core::ClientId;
 /// - For sending messages, [`Self::drain_sent`] should be used to drain all sent messages.
 ///   A system to forward messages from Replicon to the backend should run in [`ServerSet::SendPackets`](crate::server::ServerSet::SendPackets).
 ///
-/// Inserted as resource by [`ServerPlugin`](crate::server::ServerPlugin).
+/// Inserted as resource by [`ServerSessionPlugin`](crate::server::ServerSessionPlugin).
 #[derive(Resource, Default)]
 pub struct RepliconServer {
     /// Indicates if the server is open for connections.
@@ -20,6 +22,11 @@ pub struct RepliconServer {
     /// By default set to `false`.
     running: bool,
 
+    /// Indicates if the server is paused.
+    ///
+    /// See [`Self::pause`].
+    paused: bool,
+
     /// List of received messages for each channel.
     ///
     /// Top index is channel ID.
@@ -28,6 +35,20 @@ pub struct RepliconServer {
 
     /// List of sent messages for each channel since the last tick.
     sent_messages: Vec<(ClientId, u8, Bytes)>,
+
+    /// IDs of server channels with [`ChannelKind::Unreliable`](super::channels::ChannelKind::Unreliable).
+    unreliable_channels: Vec<u8>,
+
+    /// Maximum number of queued sent messages for each client on each unreliable channel.
+    ///
+    /// See [`Self::set_max_unreliable_queue`].
+    max_unreliable_queue: Option<usize>,
+
+    /// Intermediate buffer to count queued messages for each client and channel.
+    queue_lens: HashMap<(ClientId, u8), usize>,
+
+    /// Faults that will be processed according to [`FaultPolicy`](super::network_fault::FaultPolicy).
+    faults: Vec<NetworkFault>,
 }
 
 impl RepliconServer {
@@ -36,6 +57,33 @@ impl RepliconServer {
         self.received_messages.resize(channels_count, Vec::new());
     }
 
+    /// Stores IDs of server channels whose messages can be dropped from the send queue.
+    ///
+    /// See [`Self::set_max_unreliable_queue`].
+    pub(crate) fn setup_unreliable_channels(&mut self, channel_ids: impl IntoIterator<Item = u8>) {
+        self.unreliable_channels.clear();
+        self.unreliable_channels.extend(channel_ids);
+    }
+
+    /// Moves messages from an ordered event channel to channels specified in their headers.
+    ///
+    /// See [`ClientEventAppExt::order_client_events`](super::event::client_event::ClientEventAppExt::order_client_events).
+    pub(crate) fn split_ordered_channel(&mut self, channel_id: u8) {
+        let messages = mem::take(&mut self.received_messages[channel_id as usize]);
+        for (client_id, mut message) in messages {
+            if !message.has_remaining() {
+                debug!("ignoring ordered event message without header from `{client_id:?}`");
+                continue;
+            }
+            let target_id = message.get_u8();
+            let Some(channel_messages) = self.received_messages.get_mut(target_id as usize) else {
+                debug!("ignoring ordered event message for unknown channel {target_id} from `{client_id:?}`");
+                continue;
+            };
+            channel_messages.push((client_id, message));
+        }
+    }
+
     /// Removes a disconnected client.
     pub(crate) fn remove_client(&mut self, client_id: ClientId) {
         for receive_channel in &mut self.received_messages {
@@ -64,21 +112,28 @@ impl RepliconServer {
         }
 
         let channel_id = channel_id.into();
-        let channel_messages = self
-            .received_messages
-            .get_mut(channel_id as usize)
-            .unwrap_or_else(|| panic!("server should have a receive channel with id {channel_id}"));
+        if channel_id as usize >= self.received_messages.len() {
+            self.report_fault(NetworkFault::UnknownChannel {
+                client_id: None,
+                channel_id,
+            });
+        }
 
-        trace!(
-            "received {} message(s) totaling {} bytes from channel {channel_id}",
-            channel_messages.len(),
-            channel_messages
-                .iter()
-                .map(|(_, bytes)| bytes.len())
-                .sum::<usize>()
-        );
+        let channel_messages = self.received_messages.get_mut(channel_id as usize);
+        if let Some(channel_messages) = &channel_messages {
+            trace!(
+                "received {} message(s) totaling {} bytes from channel {channel_id}",
+                channel_messages.len(),
+                channel_messages
+                    .iter()
+                    .map(|(_, bytes)| bytes.len())
+                    .sum::<usize>()
+            );
+        }
 
-        channel_messages.drain(..)
+        channel_messages
+            .into_iter()
+            .flat_map(|channel_messages| channel_messages.drain(..))
     }
 
     /// Sends a message to a client over a channel.
@@ -122,6 +177,7 @@ impl RepliconServer {
                 receive_channel.clear();
             }
             self.sent_messages.clear();
+            self.paused = false;
         }
 
         self.running = running;
@@ -133,6 +189,86 @@ impl RepliconServer {
         self.running
     }
 
+    /// Pauses the server.
+    ///
+    /// While paused, [`RepliconTick`](super::replicon_tick::RepliconTick) is not incremented,
+    /// so nothing is replicated. Clients will be notified via
+    /// [`ServerPauseChanged`](super::server_pause::ServerPauseChanged).
+    /// Useful for single-player-hosted games opening a menu or for maintenance pauses.
+    ///
+    /// Messages and events are still exchanged. Stopping the server resets the pause.
+    pub fn pause(&mut self) {
+        debug!("pausing `RepliconServer`");
+        self.paused = true;
+    }
+
+    /// Resumes the server after [`Self::pause`].
+    ///
+    /// On the next tick all replicated entities will be sent to clients in full.
+    pub fn resume(&mut self) {
+        debug!("resuming `RepliconServer`");
+        self.paused = false;
+    }
+
+    /// Returns `true` if the server is paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Limits the number of sent messages queued for each client on each unreliable channel.
+    ///
+    /// If the messaging backend can't drain sent messages fast enough, for example during a client stall,
+    /// the oldest messages that exceed the limit are dropped before [`ServerSet::SendPackets`](crate::server::ServerSet::SendPackets).
+    /// This prevents unbounded memory growth. Dropped mutate messages are superseded by newer ones,
+    /// and their mutations will be resent since they won't be acknowledged.
+    ///
+    /// Messages on reliable channels are never dropped.
+    ///
+    /// By default set to [`None`], which means no limit.
+    pub fn set_max_unreliable_queue(&mut self, max_messages: Option<usize>) {
+        debug!("setting maximum unreliable queue to `{max_messages:?}`");
+        self.max_unreliable_queue = max_messages;
+    }
+
+    /// Returns the limit set by [`Self::set_max_unreliable_queue`].
+    #[inline]
+    pub fn max_unreliable_queue(&self) -> Option<usize> {
+        self.max_unreliable_queue
+    }
+
+    /// Drops the oldest sent messages on unreliable channels that exceed [`Self::max_unreliable_queue`].
+    pub(crate) fn trim_unreliable_queues(&mut self) {
+        let Some(max_messages) = self.max_unreliable_queue else {
+            return;
+        };
+
+        self.queue_lens.clear();
+        for &(client_id, channel_id, _) in &self.sent_messages {
+            if self.unreliable_channels.contains(&channel_id) {
+                *self.queue_lens.entry((client_id, channel_id)).or_default() += 1;
+            }
+        }
+
+        if self.queue_lens.values().all(|&len| len <= max_messages) {
+            return;
+        }
+
+        // Messages are stored in the order they were sent, so the oldest come first.
+        self.sent_messages.retain(|&(client_id, channel_id, _)| {
+            let Some(len) = self.queue_lens.get_mut(&(client_id, channel_id)) else {
+                return true;
+            };
+            if *len > max_messages {
+                trace!("dropping queued message over channel {channel_id} for `{client_id:?}`");
+                *len -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+
     /// Retains only the messages specified by the predicate.
     ///
     /// Used for testing.
@@ -143,6 +279,24 @@ impl RepliconServer {
         self.sent_messages.retain(f)
     }
 
+    /// Retains only the received messages specified by the predicate.
+    ///
+    /// The predicate receives the channel ID and the message with its sender.
+    pub(crate) fn retain_received<F>(&mut self, mut f: F)
+    where
+        F: FnMut(u8, &(ClientId, Bytes)) -> bool,
+    {
+        for (channel_id, receive_channel) in self.received_messages.iter_mut().enumerate() {
+            receive_channel.retain(|message| f(channel_id as u8, message));
+        }
+    }
+
+    /// Returns an iterator over sent messages for in-place processing.
+    #[cfg(feature = "zstd")]
+    pub(crate) fn iter_sent_mut(&mut self) -> impl Iterator<Item = &mut (ClientId, u8, Bytes)> {
+        self.sent_messages.iter_mut()
+    }
+
     /// Removes all sent messages, returning them as an iterator with client ID and channel.
     ///
     /// <div class="warning">
@@ -173,11 +327,27 @@ impl RepliconServer {
         }
 
         let channel_id = channel_id.into();
-        let receive_channel = self
-            .received_messages
-            .get_mut(channel_id as usize)
-            .unwrap_or_else(|| panic!("server should have a receive channel with id {channel_id}"));
+        let Some(receive_channel) = self.received_messages.get_mut(channel_id as usize) else {
+            self.report_fault(NetworkFault::UnknownChannel {
+                client_id: Some(client_id),
+                channel_id,
+            });
+            return;
+        };
 
         receive_channel.push((client_id, message.into()));
     }
+
+    /// Records a fault caused by malformed input.
+    ///
+    /// The fault will be handled by the configured [`FaultPolicy`](super::network_fault::FaultPolicy).
+    pub(crate) fn report_fault(&mut self, fault: NetworkFault) {
+        debug!("recording fault: {fault}");
+        self.faults.push(fault);
+    }
+
+    /// Drains all recorded faults.
+    pub(crate) fn drain_faults(&mut self) -> impl Iterator<Item = NetworkFault> + '_ {
+        self.faults.drain(..)
+    }
 }
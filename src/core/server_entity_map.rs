@@ -10,24 +10,77 @@ use bevy::{ecs::entity::EntityHashMap, prelude::*, utils::hashbrown::hash_map::E
 pub struct ServerEntityMap {
     server_to_client: EntityHashMap<Entity>,
     client_to_server: EntityHashMap<Entity>,
+    conflict_policy: MappingConflictPolicy,
+
+    /// Rejected conflicts that will be emitted as [`MappingConflict`] events.
+    conflicts: Vec<MappingConflict>,
 }
 
 impl ServerEntityMap {
     /// Inserts a server-client pair into the map.
     ///
-    /// # Panics
+    /// If the server entity or the client entity is already mapped to another entity,
+    /// the configured [`MappingConflictPolicy`] is applied.
     ///
-    /// Panics if this mapping is already present.
+    /// Returns `true` if the pair is present in the map after the call.
     #[inline]
-    pub fn insert(&mut self, server_entity: Entity, client_entity: Entity) {
-        if let Some(existing_entity) = self.server_to_client.insert(server_entity, client_entity) {
-            if client_entity != existing_entity {
-                panic!("mapping {server_entity:?} to {client_entity:?}, but it's already mapped to {existing_entity:?}");
-            } else {
+    pub fn insert(&mut self, server_entity: Entity, client_entity: Entity) -> bool {
+        let conflict = MappingConflict {
+            server_entity,
+            client_entity,
+            mapped_client: self
+                .server_to_client
+                .get(&server_entity)
+                .copied()
+                .filter(|&entity| entity != client_entity),
+            mapped_server: self
+                .client_to_server
+                .get(&client_entity)
+                .copied()
+                .filter(|&entity| entity != server_entity),
+        };
+
+        if conflict.mapped_client.is_none() && conflict.mapped_server.is_none() {
+            if self
+                .server_to_client
+                .insert(server_entity, client_entity)
+                .is_some()
+            {
                 warn!("received duplicate mapping from {server_entity:?} to {client_entity:?}");
             }
+            self.client_to_server.insert(client_entity, server_entity);
+            return true;
+        }
+
+        if !self.conflict_policy.resolve(&conflict) {
+            self.conflicts.push(conflict);
+            return false;
+        }
+
+        if let Some(mapped_client) = conflict.mapped_client {
+            self.client_to_server.remove(&mapped_client);
+        }
+        if let Some(mapped_server) = conflict.mapped_server {
+            self.server_to_client.remove(&mapped_server);
         }
+        self.server_to_client.insert(server_entity, client_entity);
         self.client_to_server.insert(client_entity, server_entity);
+
+        true
+    }
+
+    /// Returns the policy for inserting mappings that conflict with existing ones.
+    pub fn conflict_policy(&self) -> MappingConflictPolicy {
+        self.conflict_policy
+    }
+
+    /// Sets the policy for inserting mappings that conflict with existing ones.
+    pub fn set_conflict_policy(&mut self, policy: MappingConflictPolicy) {
+        self.conflict_policy = policy;
+    }
+
+    pub(crate) fn drain_conflicts(&mut self) -> impl Iterator<Item = MappingConflict> + '_ {
+        self.conflicts.drain(..)
     }
 
     /// Converts server entity into client entity or inserts a new mapping with `f`
@@ -60,10 +113,80 @@ impl ServerEntityMap {
         }
     }
 
-    pub(crate) fn get_by_server(&mut self, server_entity: Entity) -> Option<Entity> {
+    /// Returns the client entity mapped to the server entity.
+    #[inline]
+    pub fn get_by_server(&self, server_entity: Entity) -> Option<Entity> {
         self.server_to_client.get(&server_entity).copied()
     }
 
+    /// Returns the server entity mapped to the client entity.
+    #[inline]
+    pub fn get_by_client(&self, client_entity: Entity) -> Option<Entity> {
+        self.client_to_server.get(&client_entity).copied()
+    }
+
+    /// Like [`Self::get_by_server`], but reports if the map contains the same entity index
+    /// with a different generation.
+    ///
+    /// Useful for diagnosing stale entities. Mismatch search iterates over all mappings.
+    pub fn lookup_by_server(&self, server_entity: Entity) -> EntityLookup {
+        lookup(&self.server_to_client, server_entity)
+    }
+
+    /// Like [`Self::get_by_client`], but reports if the map contains the same entity index
+    /// with a different generation.
+    ///
+    /// Useful for diagnosing stale entities. Mismatch search iterates over all mappings.
+    pub fn lookup_by_client(&self, client_entity: Entity) -> EntityLookup {
+        lookup(&self.client_to_server, client_entity)
+    }
+
+    /// Returns an iterator over all mappings as `(server_entity, client_entity)` pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.server_to_client
+            .iter()
+            .map(|(&server_entity, &client_entity)| (server_entity, client_entity))
+    }
+
+    /// Keeps only mappings for which `f` returns `true`.
+    ///
+    /// The closure receives `(server_entity, client_entity)` pairs.
+    /// Returns the number of removed mappings.
+    pub fn retain(&mut self, mut f: impl FnMut(Entity, Entity) -> bool) -> usize {
+        let len = self.len();
+        let client_to_server = &mut self.client_to_server;
+        self.server_to_client
+            .retain(|&server_entity, &mut client_entity| {
+                let keep = f(server_entity, client_entity);
+                if !keep {
+                    client_to_server.remove(&client_entity);
+                }
+                keep
+            });
+
+        len - self.len()
+    }
+
+    /// Returns the number of mappings.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.server_to_client.len()
+    }
+
+    /// Returns `true` if there are no mappings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.server_to_client.is_empty()
+    }
+
+    /// Returns the number of mappings the map can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.server_to_client
+            .capacity()
+            .min(self.client_to_server.capacity())
+    }
+
     pub(crate) fn remove_by_server(&mut self, server_entity: Entity) -> Option<Entity> {
         let client_entity = self.server_to_client.remove(&server_entity);
         if let Some(client_entity) = client_entity {
@@ -99,3 +222,252 @@ impl ServerEntityMap {
         self.server_to_client.clear();
     }
 }
+
+fn lookup(map: &EntityHashMap<Entity>, entity: Entity) -> EntityLookup {
+    if let Some(&mapped) = map.get(&entity) {
+        return EntityLookup::Mapped(mapped);
+    }
+
+    map.iter()
+        .find(|(key, _)| key.index() == entity.index())
+        .map(|(&stored, &mapped)| EntityLookup::GenerationMismatch { stored, mapped })
+        .unwrap_or(EntityLookup::NotMapped)
+}
+
+/// Result of [`ServerEntityMap::lookup_by_server`] and [`ServerEntityMap::lookup_by_client`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityLookup {
+    /// The entity is mapped to the contained entity.
+    Mapped(Entity),
+
+    /// The entity is not mapped, but an entity with the same index and a different generation is.
+    ///
+    /// Usually indicates a stale mapping, e.g. when a despawn wasn't received.
+    GenerationMismatch {
+        /// Entity with the same index from the map.
+        stored: Entity,
+
+        /// Entity to which `stored` is mapped.
+        mapped: Entity,
+    },
+
+    /// The entity is not mapped.
+    NotMapped,
+}
+
+impl EntityLookup {
+    /// Returns the mapped entity if found.
+    pub fn mapped(self) -> Option<Entity> {
+        match self {
+            EntityLookup::Mapped(entity) => Some(entity),
+            _ => None,
+        }
+    }
+}
+
+/// Defines how to handle a mapping that conflicts with an existing one.
+///
+/// A conflict happens when the server entity or the client entity of a new mapping
+/// is already mapped to a different entity. Used by [`ServerEntityMap`] and
+/// [`ClientEntityMap`](crate::server::client_entity_map::ClientEntityMap).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MappingConflictPolicy {
+    /// Keep existing mappings and discard the new one.
+    ///
+    /// The conflict will be reported via an event.
+    Reject,
+
+    /// Remove existing mappings that involve any of the entities and insert the new one.
+    Overwrite,
+
+    /// Panic in debug builds and behave as [`Self::Reject`] in release builds.
+    #[default]
+    PanicInDebug,
+}
+
+impl MappingConflictPolicy {
+    /// Returns `true` if the new mapping should overwrite the conflicting ones.
+    pub(crate) fn resolve(self, conflict: &MappingConflict) -> bool {
+        match self {
+            MappingConflictPolicy::Reject => {
+                warn!("rejecting conflicting mapping: {conflict:?}");
+                false
+            }
+            MappingConflictPolicy::Overwrite => {
+                debug!("overwriting with conflicting mapping: {conflict:?}");
+                true
+            }
+            MappingConflictPolicy::PanicInDebug => {
+                if cfg!(debug_assertions) {
+                    panic!("unable to insert conflicting mapping: {conflict:?}");
+                }
+                error!("rejecting conflicting mapping: {conflict:?}");
+                false
+            }
+        }
+    }
+}
+
+/// An event that is emitted when a mapping was rejected due to [`MappingConflictPolicy`].
+///
+/// On client it's emitted for [`ServerEntityMap`].
+/// For the server see [`ClientMappingConflict`](crate::server::client_entity_map::ClientMappingConflict).
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MappingConflict {
+    pub server_entity: Entity,
+    pub client_entity: Entity,
+
+    /// Client entity to which the server entity is already mapped.
+    pub mapped_client: Option<Entity>,
+
+    /// Server entity to which the client entity is already mapped.
+    pub mapped_server: Option<Entity>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate() {
+        let mut entity_map = ServerEntityMap::default();
+        let server_entity = Entity::from_raw(0);
+        let client_entity = Entity::from_raw(1);
+
+        assert!(entity_map.insert(server_entity, client_entity));
+        assert!(entity_map.insert(server_entity, client_entity));
+        assert_eq!(entity_map.drain_conflicts().count(), 0);
+    }
+
+    #[test]
+    fn reject() {
+        let mut entity_map = ServerEntityMap::default();
+        entity_map.set_conflict_policy(MappingConflictPolicy::Reject);
+
+        let server_entity = Entity::from_raw(0);
+        let client_entity = Entity::from_raw(1);
+        let other_entity = Entity::from_raw(2);
+
+        assert!(entity_map.insert(server_entity, client_entity));
+        assert!(!entity_map.insert(server_entity, other_entity));
+        assert!(!entity_map.insert(other_entity, client_entity));
+
+        assert_eq!(
+            entity_map.to_client().get(&server_entity),
+            Some(&client_entity)
+        );
+        assert_eq!(
+            entity_map.to_server().get(&client_entity),
+            Some(&server_entity)
+        );
+
+        let conflicts: Vec<_> = entity_map.drain_conflicts().collect();
+        assert_eq!(
+            conflicts,
+            [
+                MappingConflict {
+                    server_entity,
+                    client_entity: other_entity,
+                    mapped_client: Some(client_entity),
+                    mapped_server: None,
+                },
+                MappingConflict {
+                    server_entity: other_entity,
+                    client_entity,
+                    mapped_client: None,
+                    mapped_server: Some(server_entity),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn overwrite() {
+        let mut entity_map = ServerEntityMap::default();
+        entity_map.set_conflict_policy(MappingConflictPolicy::Overwrite);
+
+        let server_entity = Entity::from_raw(0);
+        let client_entity = Entity::from_raw(1);
+        let other_entity = Entity::from_raw(2);
+
+        assert!(entity_map.insert(server_entity, client_entity));
+        assert!(entity_map.insert(server_entity, other_entity));
+
+        assert_eq!(
+            entity_map.to_client().get(&server_entity),
+            Some(&other_entity)
+        );
+        assert_eq!(entity_map.to_server().get(&client_entity), None);
+        assert_eq!(
+            entity_map.to_server().get(&other_entity),
+            Some(&server_entity)
+        );
+        assert_eq!(entity_map.drain_conflicts().count(), 0);
+    }
+
+    #[test]
+    fn lookup() {
+        let mut entity_map = ServerEntityMap::default();
+
+        let server_entity = Entity::from_raw(0);
+        let client_entity = Entity::from_raw(1);
+        entity_map.insert(server_entity, client_entity);
+
+        assert_eq!(
+            entity_map.lookup_by_server(server_entity),
+            EntityLookup::Mapped(client_entity)
+        );
+        assert_eq!(
+            entity_map.lookup_by_client(client_entity),
+            EntityLookup::Mapped(server_entity)
+        );
+
+        let stale_entity = Entity::from_bits(2 << 32);
+        assert_eq!(
+            entity_map.lookup_by_server(stale_entity),
+            EntityLookup::GenerationMismatch {
+                stored: server_entity,
+                mapped: client_entity,
+            }
+        );
+        assert_eq!(
+            entity_map.lookup_by_server(Entity::from_raw(2)),
+            EntityLookup::NotMapped
+        );
+    }
+
+    #[test]
+    fn retain() {
+        let mut entity_map = ServerEntityMap::default();
+        for index in 0..4 {
+            entity_map.insert(Entity::from_raw(index), Entity::from_raw(index + 10));
+        }
+        assert_eq!(entity_map.len(), 4);
+        assert!(entity_map.capacity() >= 4);
+
+        let removed = entity_map.retain(|server_entity, _| server_entity.index() % 2 == 0);
+        assert_eq!(removed, 2);
+        assert_eq!(entity_map.len(), 2);
+        assert_eq!(entity_map.to_server().len(), 2);
+
+        let mut mappings: Vec<_> = entity_map.iter().collect();
+        mappings.sort();
+        assert_eq!(
+            mappings,
+            [
+                (Entity::from_raw(0), Entity::from_raw(10)),
+                (Entity::from_raw(2), Entity::from_raw(12)),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_in_debug() {
+        let mut entity_map = ServerEntityMap::default();
+
+        let server_entity = Entity::from_raw(0);
+        entity_map.insert(server_entity, Entity::from_raw(1));
+        entity_map.insert(server_entity, Entity::from_raw(2));
+    }
+}
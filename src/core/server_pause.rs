@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// An event that sent to clients when the server is paused or resumed.
+///
+/// Also sent to clients that connect while the server is paused.
+/// On client the state is also available via [`ServerPaused`](crate::client::ServerPaused).
+///
+/// See [`RepliconServer::pause`](super::replicon_server::RepliconServer::pause).
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ServerPauseChanged {
+    /// `true` if the server was paused, `false` if resumed.
+    pub paused: bool,
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Controls how often [`RepliconTick`](super::replicon_tick::RepliconTick) is incremented on the server.
+///
+/// When [`RepliconTick`](super::replicon_tick::RepliconTick) is mutated, the server's replication
+/// system will run. This means the tick policy controls how often server state is replicated.
+///
+/// Note that component mutations are replicated over the unreliable channel, so if a component mutate message is lost
+/// then component mutations won't be resent until the server's replication system runs again.
+///
+/// Can be changed at runtime via `ServerTickPolicy` resource on server.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TickPolicy {
+    /// The replicon tick is incremented at most max ticks per second. In practice the tick rate may be lower if the
+    /// app's update cycle duration is too long.
+    ///
+    /// By default it's 30 ticks per second. 0 is treated as 1.
+    MaxTickRate(u16),
+    /// The replicon tick is incremented every frame.
+    EveryFrame,
+    /// The user should manually configure `increment_tick` or manually increment
+    /// [`RepliconTick`](super::replicon_tick::RepliconTick).
+    Manual,
+}
+
+/// An event that sent to clients when the server changes its [`TickPolicy`] at runtime.
+///
+/// Also sent to clients that connect after the change.
+/// Emitted on server too, since it's broadcasted to all clients including the server itself.
+///
+/// Useful to adjust interpolation delays or prediction windows that depend on the tick rate.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TickRateChanged {
+    /// The new policy.
+    pub policy: TickPolicy,
+}
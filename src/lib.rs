@@ -468,6 +468,9 @@ require access to the [`AppTypeRegistry`] resource.
 
 Don't forget to validate the contents of every [`Box<dyn Reflect>`] from a client, it could be anything!
 
+Events can also be sent via [`ClientEventExt::client_send`] on [`Commands`] or [`World`], which
+is useful in exclusive systems and command-driven flows.
+
 Alternatively you can use triggers with similar API. First, you need to register the event
 with [`ClientTriggerAppExt::add_client_trigger()`] and then use [`ClientTriggerExt::client_trigger`]:
 
@@ -544,6 +547,9 @@ If the event contains an entity, then
 For events that require special serialization and deserialization functions you can use
 [`ServerEventAppExt::add_server_event_with()`].
 
+Similar to client events, you can send server events via [`ServerEventExt::server_send`]
+on [`Commands`] or [`World`].
+
 Trigger-based API available for server events as well. First, you need to register the event
 with [`ServerTriggerAppExt::add_server_trigger()`] and then use [`ServerTriggerExt::server_trigger`]:
 
@@ -661,6 +667,8 @@ But on server we use `debug` for it to avoid flooding server logs with errors ca
 */
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+#[cfg(feature = "bevy")]
+pub mod addons;
 #[cfg(feature = "client")]
 pub mod client;
 pub mod core;
@@ -673,56 +681,106 @@ pub mod server;
 #[cfg(all(feature = "server", feature = "client"))]
 pub mod test_app;
 
+#[cfg(feature = "bevy")]
 pub mod prelude {
-    pub use super::{
-        core::{
-            channels::{ChannelKind, RepliconChannel, RepliconChannels},
-            common_conditions::*,
-            connected_clients::ConnectedClients,
-            event::{
-                client_event::{ClientEventAppExt, FromClient},
-                client_trigger::{ClientTriggerAppExt, ClientTriggerExt},
-                server_event::{SendMode, ServerEventAppExt, ToClients},
-                server_trigger::{ServerTriggerAppExt, ServerTriggerExt},
-            },
-            replication::{
-                command_markers::AppMarkerExt,
-                replicated_clients::{
-                    client_visibility::ClientVisibility, ReplicatedClient, ReplicatedClients,
-                    VisibilityPolicy,
+    pub use shared::*;
+
+    #[cfg(feature = "client")]
+    pub use client::*;
+
+    #[cfg(feature = "server")]
+    pub use server::*;
+
+    /// Types for both client and server.
+    pub mod shared {
+        pub use crate::{
+            core::{
+                channels::{ChannelKind, ChannelSelection, RepliconChannel, RepliconChannels},
+                common_conditions::server_or_singleplayer,
+                event::{
+                    client_event::{ClientEventAppExt, FromClient},
+                    client_trigger::ClientTriggerAppExt,
+                    server_event::ServerEventAppExt,
+                    server_trigger::ServerTriggerAppExt,
+                },
+                replication::{
+                    command_markers::AppMarkerExt, replicated_resources::AppResourceExt,
+                    replication_rules::AppRuleExt, Replicated,
                 },
-                replication_rules::AppRuleExt,
-                Replicated,
+                BackendError, ClientId, DisconnectReason, RepliconCorePlugin,
             },
-            replicon_client::{RepliconClient, RepliconClientStatus},
-            replicon_server::RepliconServer,
-            BackendError, ClientId, DisconnectReason, RepliconCorePlugin,
-        },
-        RepliconPlugins,
-    };
+            EventsOnlyPlugins, RepliconPlugins,
+        };
+
+        #[cfg(feature = "parent_sync")]
+        pub use crate::parent_sync::{ParentSync, ParentSyncPlugin};
+    }
 
+    /// Client-only types.
+    ///
+    /// Available only with the `client` feature.
     #[cfg(feature = "client")]
-    pub use super::client::{
-        event::ClientEventPlugin, ClientPlugin, ClientReplicationStats, ClientSet,
-    };
+    pub mod client {
+        pub use crate::{
+            client::{
+                event::ClientEventPlugin, ClientPlugin, ClientReplicationPlugin,
+                ClientReplicationStats, ClientSessionPlugin, ClientSet,
+            },
+            core::{
+                common_conditions::{
+                    client_connected, client_connecting, client_just_connected,
+                    client_just_disconnected, client_started_connecting,
+                },
+                event::{
+                    client_event::{ClientEventExt, EventDelivered},
+                    client_trigger::ClientTriggerExt,
+                },
+                replicon_client::{RepliconClient, RepliconClientStatus},
+            },
+        };
+
+        #[cfg(feature = "client_diagnostics")]
+        pub use crate::client::diagnostics::ClientDiagnosticsPlugin;
+    }
 
+    /// Server-only types.
+    ///
+    /// Available only with the `server` feature.
     #[cfg(feature = "server")]
-    pub use super::server::{
-        client_entity_map::{ClientEntityMap, ClientMapping},
-        event::ServerEventPlugin,
-        ClientConnected, ClientDisconnected, ServerPlugin, ServerSet, StartReplication, TickPolicy,
-    };
-
-    #[cfg(feature = "client_diagnostics")]
-    pub use super::client::diagnostics::ClientDiagnosticsPlugin;
-    #[cfg(feature = "parent_sync")]
-    pub use super::parent_sync::{ParentSync, ParentSyncPlugin};
+    pub mod server {
+        pub use crate::{
+            core::{
+                common_conditions::{server_just_stopped, server_paused, server_running},
+                connected_clients::{ConnectedClient, ConnectedClients},
+                event::{
+                    server_event::{SendMode, ServerEventExt, ToClients},
+                    server_trigger::ServerTriggerExt,
+                },
+                replication::replicated_clients::{
+                    client_visibility::ClientVisibility, BandwidthCap, ReplicatedClient,
+                    ReplicatedClients, VisibilityPolicy,
+                },
+                replicon_server::RepliconServer,
+            },
+            server::{
+                client_entity_map::{
+                    ClientEntityMap, ClientMapping, ClientMappingConflict, ClientMappingExpired,
+                },
+                event::ServerEventPlugin,
+                server_epoch::ServerResetExt,
+                ClientConnected, ClientDisconnected, ServerPlugin, ServerReplicationPlugin,
+                ServerSessionPlugin, ServerSet, StartReplication, TickPolicy,
+            },
+        };
+    }
 }
 
 pub use bytes;
 pub use postcard;
 
+#[cfg(feature = "bevy")]
 use bevy::{app::PluginGroupBuilder, prelude::*};
+#[cfg(feature = "bevy")]
 use prelude::*;
 
 /// Plugin group for all replicon plugins.
@@ -735,8 +793,10 @@ use prelude::*;
 /// * [`ClientEventPlugin`] - with feature `client`.
 /// * [`ParentSyncPlugin`] - with feature `parent_sync`.
 /// * [`ClientDiagnosticsPlugin`] - with feature `client_diagnostics`.
+#[cfg(feature = "bevy")]
 pub struct RepliconPlugins;
 
+#[cfg(feature = "bevy")]
 impl PluginGroup for RepliconPlugins {
     fn build(self) -> PluginGroupBuilder {
         let mut group = PluginGroupBuilder::start::<Self>();
@@ -765,3 +825,41 @@ impl PluginGroup for RepliconPlugins {
         group
     }
 }
+
+/// Plugin group for networked events without replication.
+///
+/// Sets up channels, client and server resources, connection events and remote events,
+/// but omits all replication systems. Useful for games that want replicon's messaging
+/// with a custom state synchronization. Replication registration methods are still
+/// available, but registered rules have no effect.
+///
+/// Contains the following:
+/// * [`RepliconCorePlugin`].
+/// * [`ServerSessionPlugin`] - with feature `server`.
+/// * [`ServerEventPlugin`] - with feature `server`.
+/// * [`ClientSessionPlugin`] - with feature `client`.
+/// * [`ClientEventPlugin`] - with feature `client`.
+#[cfg(feature = "bevy")]
+pub struct EventsOnlyPlugins;
+
+#[cfg(feature = "bevy")]
+impl PluginGroup for EventsOnlyPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let mut group = PluginGroupBuilder::start::<Self>();
+        group = group.add(RepliconCorePlugin);
+
+        #[cfg(feature = "server")]
+        {
+            group = group
+                .add(ServerSessionPlugin::default())
+                .add(ServerEventPlugin);
+        }
+
+        #[cfg(feature = "client")]
+        {
+            group = group.add(ClientSessionPlugin).add(ClientEventPlugin);
+        }
+
+        group
+    }
+}
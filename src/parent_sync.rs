@@ -9,10 +9,11 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "client")]
 use crate::client::ClientSet;
-use crate::core::{
-    common_conditions::*, replication::replication_rules::AppRuleExt,
-    replicon_client::RepliconClient,
-};
+#[cfg(feature = "server")]
+use crate::core::common_conditions::*;
+use crate::core::replication::replication_rules::AppRuleExt;
+#[cfg(all(feature = "server", feature = "client"))]
+use crate::core::replicon_client::RepliconClient;
 #[cfg(feature = "server")]
 use crate::server::ServerSet;
 
@@ -78,9 +79,10 @@ fn store_changes(mut hierarchy: Query<(&Parent, &mut ParentSync), Changed<Parent
 #[cfg(feature = "server")]
 fn init<C: Component>(
     trigger: Trigger<OnAdd, C>,
-    client: Option<Res<RepliconClient>>,
+    #[cfg(feature = "client")] client: Option<Res<RepliconClient>>,
     mut hierarchy: Query<(&Parent, &mut ParentSync)>,
 ) {
+    #[cfg(feature = "client")]
     if !server_or_singleplayer(client) {
         return;
     }
@@ -93,9 +95,10 @@ fn init<C: Component>(
 #[cfg(feature = "server")]
 fn store_removals(
     trigger: Trigger<OnRemove, Parent>,
-    client: Option<Res<RepliconClient>>,
+    #[cfg(feature = "client")] client: Option<Res<RepliconClient>>,
     mut hierarchy: Query<&mut ParentSync>,
 ) {
+    #[cfg(feature = "client")]
     if !server_or_singleplayer(client) {
         return;
     }
@@ -1,50 +1,115 @@
+pub mod bandwidth_heatmap;
+pub mod change_journal;
+pub mod client_bundles;
 pub mod client_entity_map;
+pub(super) mod delta_snapshots;
 pub(super) mod despawn_buffer;
 pub mod event;
+pub(super) mod field_group_states;
+pub mod idle_detection;
+pub mod isolation_audit;
+pub mod latency_injection;
+pub mod message_coalescing;
+#[cfg(feature = "zstd")]
+pub mod message_compression;
+pub mod relevance;
 pub(super) mod removal_buffer;
 pub(super) mod replicated_archetypes;
+pub mod replication_audit;
+pub mod replication_budget;
+pub mod replication_constraints;
+pub mod replication_history;
 pub(super) mod replication_messages;
+pub mod replication_preview;
 mod replication_read_world;
+pub mod replication_transaction;
+pub mod replication_worker;
+pub(super) mod resource_buffer;
+pub mod rooms;
+pub mod send_budget;
+pub mod serialization_cache;
+pub mod server_epoch;
 pub mod server_tick;
+pub mod session_store;
+pub mod smoke_test;
 
 use std::{ops::Range, time::Duration};
 
 use bevy::{
-    ecs::{component::StorageType, system::SystemChangeTick},
+    ecs::{
+        archetype::{Archetype, ArchetypeEntity},
+        component::{StorageType, Tick},
+        system::{SystemChangeTick, SystemParam},
+    },
     prelude::*,
     ptr::Ptr,
     time::common_conditions::on_timer,
+    utils::Instant,
 };
 use bytes::Buf;
+use postcard::experimental::serialized_size;
 use replication_read_world::ReplicationReadWorld;
 
+pub use crate::core::tick_policy::TickPolicy;
 use crate::core::{
-    channels::{ReplicationChannel, RepliconChannels},
-    common_conditions::{server_just_stopped, server_running},
-    connected_clients::ConnectedClients,
-    event::server_event::BufferedServerEvents,
+    channels::{ChannelKind, ReplicationChannel, RepliconChannels},
+    common_conditions::{server_just_stopped, server_paused, server_running},
+    connected_clients::{ConnectedClient, ConnectedClients},
+    entity_serde::{EntityObfuscation, MappedTypes},
+    event::server_event::{SendMode, ToClients},
+    network_fault::{FaultPolicy, NetworkFault},
     postcard_utils,
+    protocol::replication_epoch::ReplicationEpoch,
     replication::{
+        entity_batch,
         replicated_clients::{
-            client_visibility::Visibility, ClientBuffers, ReplicatedClients, VisibilityPolicy,
+            client_visibility::Visibility, BandwidthCap, ClientBuffers, ReplicatedClients,
+            VisibilityPolicy,
         },
+        replication_prefabs::ReplicationPrefabs,
         replication_registry::{
-            component_fns::ComponentFns, ctx::SerializeCtx, rule_fns::UntypedRuleFns,
-            ReplicationRegistry,
+            component_fns::ComponentFns,
+            ctx::SerializeCtx,
+            rule_fns::{DiffFn, UntypedRuleFns},
+            FnsId, ReplicationRegistry,
         },
         replication_rules::ReplicationRules,
         track_mutate_messages::TrackMutateMessages,
     },
     replicon_server::RepliconServer,
     replicon_tick::RepliconTick,
+    server_pause::ServerPauseChanged,
+    tick_policy::TickRateChanged,
     ClientId, DisconnectReason,
 };
-use client_entity_map::ClientEntityMap;
+use bandwidth_heatmap::BandwidthHeatmap;
+use change_journal::ChangeJournal;
+use client_bundles::ClientBundles;
+use client_entity_map::{ClientEntityMap, ClientMappingConflict, ClientMappingExpired};
+use delta_snapshots::DeltaSnapshots;
 use despawn_buffer::{DespawnBuffer, DespawnBufferPlugin};
+use field_group_states::FieldGroupStates;
+use idle_detection::{ClientActive, ClientIdle, IdleDetection};
+use isolation_audit::IsolationAudit;
+use latency_injection::LatencyInjection;
+use message_coalescing::MessageCoalescing;
+#[cfg(feature = "zstd")]
+use message_compression::MessageCompression;
 use removal_buffer::{RemovalBuffer, RemovalBufferPlugin};
-use replicated_archetypes::{ReplicatedArchetypes, ReplicatedComponent};
+use replicated_archetypes::{ReplicatedArchetype, ReplicatedArchetypes, ReplicatedComponent};
+use replication_audit::{AuditAction, AuditEntry, ReplicationAudit};
+use replication_budget::{DespawnBudget, ReplicationBudget, ReplicationBudgetExceeded};
+use replication_constraints::{ConstraintViolation, ReplicationConstraints};
+use replication_history::ReplicationHistory;
 use replication_messages::{serialized_data::SerializedData, ReplicationMessages};
-use server_tick::ServerTick;
+use replication_transaction::ReplicationTransactions;
+use replication_worker::ReplicationWorker;
+use resource_buffer::ResourceBuffer;
+use send_budget::{ReplicationPriority, SendBudget};
+use serialization_cache::SerializationCache;
+use server_epoch::ServerEpoch;
+use server_tick::{ServerTick, ServerTickPolicy};
+use session_store::SessionStore;
 
 pub struct ServerPlugin {
     /// Tick configuration.
@@ -56,8 +121,19 @@ pub struct ServerPlugin {
     /// The time after which mutations will be considered lost if an acknowledgment is not received for them.
     ///
     /// In practice mutations will live at least `mutations_timeout`, and at most `2*mutations_timeout`.
+    /// See also [`Self::mutations_timeout_ticks`].
     pub mutations_timeout: Duration,
 
+    /// The number of server ticks that should pass before mutations can be considered lost.
+    ///
+    /// Mutations are discarded only after both this number of ticks and [`Self::mutations_timeout`] have passed.
+    /// [`ServerTick`] doesn't advance while the server is paused or not ticking (for example, during a long
+    /// stall), so unacknowledged mutations survive pauses of any duration and can still be acknowledged
+    /// by late acks.
+    ///
+    /// By default it's 30 ticks.
+    pub mutations_timeout_ticks: u32,
+
     /// If enabled, replication will be started automatically after connection.
     ///
     /// If disabled, replication should be started manually by sending the [`StartReplication`] event.
@@ -66,6 +142,34 @@ pub struct ServerPlugin {
     /// All events from server will be buffered on client until replication starts, except the ones marked as independent.
     /// See also [`ServerEventAppExt::make_independent`](crate::core::event::server_event::ServerEventAppExt::make_independent).
     pub replicate_after_connect: bool,
+
+    /// Number of server ticks to accumulate before sending replication.
+    ///
+    /// Useful to run several simulation steps per network send (e.g. 60 Hz simulation with 20 Hz send rate).
+    /// Mutations that happened during these ticks are coalesced to the latest value, while insertions,
+    /// removals and despawns are all included. Non-independent server events are buffered until the next send.
+    ///
+    /// Messages carry the range of ticks they cover, available on client via
+    /// [`ServerTickRange`](crate::client::ServerTickRange).
+    ///
+    /// By default it's 1, which means sending on every tick.
+    pub ticks_per_send: u32,
+
+    /// Number of server ticks after which pending [`ClientEntityMap`] mappings expire.
+    ///
+    /// A mapping stays pending until its server entity is replicated to the client.
+    /// Expired mappings are reported via [`ClientMappingExpired`].
+    ///
+    /// By default it's 300 ticks.
+    pub mappings_timeout: u32,
+
+    /// Maximum number of bytes of mutations sent to each client.
+    ///
+    /// Can be changed at runtime with [`ReplicatedClients::set_bandwidth_cap`]
+    /// or for individual clients with [`ReplicatedClient::set_bandwidth_cap`](crate::core::replication::replicated_clients::ReplicatedClient::set_bandwidth_cap).
+    ///
+    /// By default it's [`None`], which means no limit.
+    pub bandwidth_cap: Option<BandwidthCap>,
 }
 
 impl Default for ServerPlugin {
@@ -74,27 +178,64 @@ impl Default for ServerPlugin {
             tick_policy: TickPolicy::MaxTickRate(30),
             visibility_policy: Default::default(),
             mutations_timeout: Duration::from_secs(10),
+            mutations_timeout_ticks: 30,
             replicate_after_connect: true,
+            ticks_per_send: 1,
+            mappings_timeout: 300,
+            bandwidth_cap: None,
         }
     }
 }
 
 /// Server functionality and replication sending.
 ///
+/// Combination of [`ServerSessionPlugin`] and [`ServerReplicationPlugin`].
 /// Can be disabled for client-only apps.
 impl Plugin for ServerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((DespawnBufferPlugin, RemovalBufferPlugin))
-            .init_resource::<RepliconServer>()
+        app.add_plugins((
+            ServerSessionPlugin {
+                tick_policy: self.tick_policy,
+            },
+            ServerReplicationPlugin {
+                visibility_policy: self.visibility_policy,
+                mutations_timeout: self.mutations_timeout,
+                mutations_timeout_ticks: self.mutations_timeout_ticks,
+                replicate_after_connect: self.replicate_after_connect,
+                ticks_per_send: self.ticks_per_send,
+                mappings_timeout: self.mappings_timeout,
+                bandwidth_cap: self.bandwidth_cap,
+            },
+        ));
+    }
+}
+
+/// Core server functionality: connection tracking, channels, ticks and pause.
+///
+/// Added by [`ServerPlugin`]. Can be used directly together with
+/// [`ServerEventPlugin`](event::ServerEventPlugin) for apps that need only the messaging layer,
+/// like matchmaking services or relay nodes.
+pub struct ServerSessionPlugin {
+    /// Tick configuration.
+    pub tick_policy: TickPolicy,
+}
+
+impl Default for ServerSessionPlugin {
+    fn default() -> Self {
+        Self {
+            tick_policy: TickPolicy::MaxTickRate(30),
+        }
+    }
+}
+
+impl Plugin for ServerSessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RepliconServer>()
             .init_resource::<ServerTick>()
-            .init_resource::<ClientBuffers>()
-            .init_resource::<ClientEntityMap>()
+            .insert_resource(ServerTickPolicy::new(self.tick_policy))
             .init_resource::<ConnectedClients>()
-            .insert_resource(ReplicatedClients::new(
-                self.visibility_policy,
-                self.replicate_after_connect,
-            ))
-            .init_resource::<BufferedServerEvents>()
+            .add_event::<ClientIdle>()
+            .add_event::<ClientActive>()
             .configure_sets(
                 PreUpdate,
                 (
@@ -115,13 +256,140 @@ impl Plugin for ServerPlugin {
             )
             .add_observer(handle_connects)
             .add_observer(handle_disconnects)
-            .add_observer(enable_replication)
+            .add_observer(session_store::restore_session)
+            .add_observer(session_store::store_session)
+            .add_observer(idle_detection::track_client)
+            .add_observer(idle_detection::untrack_client)
             .add_systems(Startup, setup_channels)
+            .add_systems(
+                PreUpdate,
+                (
+                    (
+                        sample_stats,
+                        update_client_entities.run_if(resource_changed::<ConnectedClients>),
+                    )
+                        .chain(),
+                    latency_injection::delay_received
+                        .run_if(server_running)
+                        .run_if(resource_exists::<LatencyInjection>),
+                    session_store::remove_expired.run_if(resource_exists::<SessionStore>),
+                )
+                    .after(ServerSet::ReceivePackets)
+                    .before(ServerSet::Receive),
+            )
+            .add_systems(PreUpdate, send_faults.after(ServerSet::Receive))
+            .add_systems(
+                PostUpdate,
+                (
+                    send_pause_changes.before(ServerSet::Send),
+                    send_tick_policy_changes
+                        .before(ServerSet::Send)
+                        .run_if(server_running)
+                        .run_if(resource_changed::<ServerTickPolicy>),
+                    increment_tick
+                        .before(ServerSet::Send)
+                        .run_if(server_running)
+                        .run_if(not(server_paused))
+                        .run_if(tick_due),
+                    idle_detection::detect_idle
+                        .before(ServerSet::Send)
+                        .run_if(server_running)
+                        .run_if(resource_exists::<IdleDetection>),
+                    warn_mapped_types
+                        .before(ServerSet::Send)
+                        .run_if(resource_added::<EntityObfuscation>),
+                    latency_injection::delay_sent
+                        .after(ServerSet::Send)
+                        .before(ServerSet::SendPackets)
+                        .run_if(server_running)
+                        .run_if(resource_exists::<LatencyInjection>),
+                    isolation_audit::audit_isolation
+                        .after(ServerSet::Send)
+                        .before(ServerSet::SendPackets)
+                        .run_if(server_running)
+                        .run_if(resource_exists::<IsolationAudit>),
+                    trim_sent
+                        .after(ServerSet::Send)
+                        .before(ServerSet::SendPackets)
+                        .run_if(server_running),
+                    reset.run_if(server_just_stopped),
+                ),
+            );
+    }
+}
+
+/// Replication sending, including visibility and entity mappings.
+///
+/// Added by [`ServerPlugin`]. Requires [`ServerSessionPlugin`].
+pub struct ServerReplicationPlugin {
+    /// Visibility configuration.
+    pub visibility_policy: VisibilityPolicy,
+
+    /// See [`ServerPlugin::mutations_timeout`].
+    pub mutations_timeout: Duration,
+
+    /// See [`ServerPlugin::mutations_timeout_ticks`].
+    pub mutations_timeout_ticks: u32,
+
+    /// See [`ServerPlugin::replicate_after_connect`].
+    pub replicate_after_connect: bool,
+
+    /// See [`ServerPlugin::ticks_per_send`].
+    pub ticks_per_send: u32,
+
+    /// See [`ServerPlugin::mappings_timeout`].
+    pub mappings_timeout: u32,
+
+    /// See [`ServerPlugin::bandwidth_cap`].
+    pub bandwidth_cap: Option<BandwidthCap>,
+}
+
+impl Default for ServerReplicationPlugin {
+    fn default() -> Self {
+        Self {
+            visibility_policy: Default::default(),
+            mutations_timeout: Duration::from_secs(10),
+            mutations_timeout_ticks: 30,
+            replicate_after_connect: true,
+            ticks_per_send: 1,
+            mappings_timeout: 300,
+            bandwidth_cap: None,
+        }
+    }
+}
+
+impl Plugin for ServerReplicationPlugin {
+    fn build(&self, app: &mut App) {
+        let mut replicated_clients =
+            ReplicatedClients::new(self.visibility_policy, self.replicate_after_connect);
+        replicated_clients.set_bandwidth_cap(self.bandwidth_cap);
+
+        app.add_plugins((DespawnBufferPlugin, RemovalBufferPlugin))
+            .init_resource::<ClientBuffers>()
+            .init_resource::<ResourceBuffer>()
+            .insert_resource(ClientEntityMap::new(self.mappings_timeout))
+            .insert_resource(replicated_clients)
+            .init_resource::<ReplicationTransactions>()
+            .init_resource::<ClientBundles>()
+            .init_resource::<ServerEpoch>()
+            .add_event::<ReplicationBudgetExceeded>()
+            .add_event::<ClientMappingExpired>()
+            .add_event::<ClientMappingConflict>()
+            .add_event::<ConstraintViolation>()
+            .insert_resource(TickBatch::new(self.ticks_per_send))
+            .add_observer(add_replicated_client)
+            .add_observer(remove_replicated_client)
+            .add_observer(enable_replication)
+            .add_observer(replication_transaction::begin_transaction)
+            .add_observer(replication_transaction::commit_transaction)
+            .add_observer(client_bundles::spawn_bundles)
+            .add_observer(client_bundles::despawn_bundles)
             .add_systems(
                 PreUpdate,
                 (
                     receive_acks,
-                    cleanup_acks(self.mutations_timeout).run_if(on_timer(self.mutations_timeout)),
+                    cleanup_acks(self.mutations_timeout, self.mutations_timeout_ticks)
+                        .run_if(on_timer(self.mutations_timeout)),
                 )
                     .chain()
                     .in_set(ServerSet::Receive)
@@ -134,37 +402,99 @@ impl Plugin for ServerPlugin {
                         .map(Result::unwrap)
                         .in_set(ServerSet::Send)
                         .run_if(server_running)
-                        .run_if(resource_changed::<ServerTick>),
-                    reset.run_if(server_just_stopped),
-                ),
-            );
-
-        match self.tick_policy {
-            TickPolicy::MaxTickRate(max_tick_rate) => {
-                let tick_time = Duration::from_millis(1000 / max_tick_rate as u64);
-                app.add_systems(
-                    PostUpdate,
-                    increment_tick
+                        .run_if(resource_changed::<ServerTick>)
+                        .run_if(tick_batch_ready),
+                    reset_epoch
+                        .in_set(ServerSet::Send)
                         .before(send_replication)
                         .run_if(server_running)
-                        .run_if(on_timer(tick_time)),
-                );
-            }
-            TickPolicy::EveryFrame => {
-                app.add_systems(
-                    PostUpdate,
-                    increment_tick
+                        .run_if(resource_changed::<ServerEpoch>),
+                    client_entity_map::expire_mappings
+                        .in_set(ServerSet::Send)
+                        .before(send_replication)
+                        .run_if(server_running)
+                        .run_if(resource_changed::<ServerTick>),
+                    client_entity_map::send_conflicts
+                        .in_set(ServerSet::Send)
                         .before(send_replication)
                         .run_if(server_running),
-                );
-            }
-            TickPolicy::Manual => (),
-        }
+                    replication_worker::send_assembled
+                        .in_set(ServerSet::Send)
+                        .before(send_replication)
+                        .run_if(server_running)
+                        .run_if(resource_exists::<ReplicationWorker>),
+                    client_bundles::configure_visibility
+                        .before(ServerSet::Send)
+                        .run_if(server_running),
+                    replication_constraints::validate
+                        .before(ServerSet::Send)
+                        .run_if(resource_exists::<ReplicationConstraints>),
+                    change_journal::record_changes
+                        .in_set(ServerSet::Send)
+                        .run_if(server_running)
+                        .run_if(resource_changed::<ServerTick>)
+                        .run_if(resource_exists::<ChangeJournal>),
+                    reset_replication.run_if(server_just_stopped),
+                ),
+            );
+
+        #[cfg(feature = "zstd")]
+        app.add_systems(
+            PostUpdate,
+            message_compression::compress_messages
+                .in_set(ServerSet::Send)
+                .after(send_replication)
+                .run_if(server_running)
+                .run_if(resource_exists::<MessageCompression>),
+        );
     }
 }
 
 fn setup_channels(mut server: ResMut<RepliconServer>, channels: Res<RepliconChannels>) {
-    server.setup_client_channels(channels.client_channels().len());
+    server.setup_client_channels(channels.client_receive_len());
+    server.setup_unreliable_channels(
+        channels
+            .server_channels()
+            .iter()
+            .enumerate()
+            .filter(|(_, channel)| channel.kind == ChannelKind::Unreliable)
+            .map(|(channel_id, _)| channel_id as u8),
+    );
+}
+
+fn trim_sent(mut server: ResMut<RepliconServer>) {
+    server.trim_unreliable_queues();
+}
+
+/// Returns `true` if enough ticks passed since the last send.
+///
+/// See [`ServerPlugin::ticks_per_send`].
+pub(super) fn tick_batch_ready(server_tick: Res<ServerTick>, tick_batch: Res<TickBatch>) -> bool {
+    **server_tick - tick_batch.last_sent >= tick_batch.ticks_per_send
+}
+
+/// Returns `true` if replication was sent for the current tick.
+///
+/// Always `false` without [`ServerReplicationPlugin`].
+pub(crate) fn tick_batch_sent(
+    server_tick: Option<Res<ServerTick>>,
+    tick_batch: Option<Res<TickBatch>>,
+) -> bool {
+    server_tick
+        .zip(tick_batch)
+        .is_some_and(|(server_tick, tick_batch)| tick_batch.last_sent == **server_tick)
+}
+
+/// Forgets mutation ticks for all clients to send replicated entities in full in the new epoch.
+fn reset_epoch(epoch: Res<ServerEpoch>, mut replicated_clients: ResMut<ReplicatedClients>) {
+    if epoch.is_added() {
+        return;
+    }
+
+    debug!("switching to {:?}", **epoch);
+    for client in replicated_clients.iter_mut() {
+        client.clear_mutation_ticks();
+    }
 }
 
 /// Increments current server tick which causes the server to replicate this frame.
@@ -173,34 +503,184 @@ pub fn increment_tick(mut server_tick: ResMut<ServerTick>) {
     trace!("incremented {server_tick:?}");
 }
 
+/// Warns about types whose entities can't be revealed on client with enabled [`EntityObfuscation`].
+fn warn_mapped_types(mapped_types: Res<MappedTypes>) {
+    for type_name in mapped_types.iter() {
+        warn!(
+            "`{type_name}` is registered with entity mapping, \
+            but entities inside it are serialized once for all clients and won't be obfuscated"
+        );
+    }
+}
+
 fn handle_connects(
     trigger: Trigger<ClientConnected>,
+    mut commands: Commands,
+    mut connected_clients: ResMut<ConnectedClients>,
+    mut pause_events: EventWriter<ToClients<ServerPauseChanged>>,
+    mut tick_rate_events: EventWriter<ToClients<TickRateChanged>>,
+    server: Res<RepliconServer>,
+    tick_policy: Res<ServerTickPolicy>,
+) {
+    debug!("`{:?}` connected", trigger.client_id);
+    let entity = commands.spawn(ConnectedClient::new(trigger.client_id)).id();
+    connected_clients.add(trigger.client_id, entity);
+
+    if server.is_paused() {
+        pause_events.send(ToClients {
+            mode: SendMode::Direct(trigger.client_id),
+            event: ServerPauseChanged { paused: true },
+        });
+    }
+
+    if tick_policy.is_renegotiated() {
+        tick_rate_events.send(ToClients {
+            mode: SendMode::Direct(trigger.client_id),
+            event: TickRateChanged {
+                policy: **tick_policy,
+            },
+        });
+    }
+}
+
+fn sample_stats(mut connected_clients: ResMut<ConnectedClients>, time: Res<Time>) {
+    for client in connected_clients.iter_mut() {
+        client.sample_stats(time.elapsed());
+    }
+}
+
+/// Copies client statistics updated by the messaging backend to client entities.
+fn update_client_entities(
+    connected_clients: Res<ConnectedClients>,
+    mut clients: Query<&mut ConnectedClient>,
+) {
+    for client in connected_clients.iter() {
+        let Some(entity) = connected_clients.entity(client.id()) else {
+            continue;
+        };
+        if let Ok(mut component) = clients.get_mut(entity) {
+            component.clone_from(client);
+        }
+    }
+}
+
+/// Handles faults recorded by [`RepliconServer`] according to [`FaultPolicy`].
+fn send_faults(
+    mut server: ResMut<RepliconServer>,
+    policy: Res<FaultPolicy>,
+    mut fault_events: EventWriter<NetworkFault>,
+) {
+    for fault in server.bypass_change_detection().drain_faults() {
+        policy.handle(fault, &mut fault_events);
+    }
+}
+
+/// Notifies clients when the server is paused or resumed.
+///
+/// On resume, forgets mutation ticks for all clients to send replicated entities in full.
+fn send_pause_changes(
+    mut was_paused: Local<bool>,
+    server: Res<RepliconServer>,
+    replicated_clients: Option<ResMut<ReplicatedClients>>,
+    mut pause_events: EventWriter<ToClients<ServerPauseChanged>>,
+) {
+    let paused = server.is_paused();
+    if paused == *was_paused {
+        return;
+    }
+    *was_paused = paused;
+
+    if !server.is_running() {
+        return;
+    }
+
+    if let Some(mut replicated_clients) = replicated_clients.filter(|_| !paused) {
+        for client in replicated_clients.iter_mut() {
+            client.clear_mutation_ticks();
+        }
+    }
+
+    debug!("broadcasting server pause change to `{paused}`");
+    pause_events.send(ToClients {
+        mode: SendMode::Broadcast,
+        event: ServerPauseChanged { paused },
+    });
+}
+
+/// Notifies clients when the tick policy changes.
+fn send_tick_policy_changes(
+    tick_policy: Res<ServerTickPolicy>,
+    mut tick_rate_events: EventWriter<ToClients<TickRateChanged>>,
+) {
+    if tick_policy.is_added() {
+        return;
+    }
+
+    debug!("broadcasting tick policy change to `{:?}`", **tick_policy);
+    tick_rate_events.send(ToClients {
+        mode: SendMode::Broadcast,
+        event: TickRateChanged {
+            policy: **tick_policy,
+        },
+    });
+}
+
+/// Returns `true` when the tick should be incremented according to [`ServerTickPolicy`].
+fn tick_due(mut timer: Local<Timer>, tick_policy: Res<ServerTickPolicy>, time: Res<Time>) -> bool {
+    match **tick_policy {
+        TickPolicy::MaxTickRate(max_tick_rate) => {
+            let tick_time = Duration::from_millis(1000 / max_tick_rate.max(1) as u64);
+            if timer.duration() != tick_time {
+                *timer = Timer::new(tick_time, TimerMode::Repeating);
+            }
+            timer.tick(time.delta()).just_finished()
+        }
+        TickPolicy::EveryFrame => true,
+        TickPolicy::Manual => false,
+    }
+}
+
+fn handle_disconnects(
+    trigger: Trigger<ClientDisconnected>,
+    mut commands: Commands,
     mut connected_clients: ResMut<ConnectedClients>,
+    mut server: ResMut<RepliconServer>,
+    latency_injection: Option<ResMut<LatencyInjection>>,
+) {
+    debug!("`{:?}` disconnected: {}", trigger.client_id, trigger.reason);
+    let Some(entity) = connected_clients.remove(trigger.client_id) else {
+        server.report_fault(NetworkFault::UnknownClient(trigger.client_id));
+        return;
+    };
+    commands.entity(entity).try_despawn();
+    server.remove_client(trigger.client_id);
+    if let Some(mut latency_injection) = latency_injection {
+        latency_injection.remove_client(trigger.client_id);
+    }
+}
+
+fn add_replicated_client(
+    trigger: Trigger<ClientConnected>,
     mut replicated_clients: ResMut<ReplicatedClients>,
-    mut buffered_events: ResMut<BufferedServerEvents>,
     mut client_buffers: ResMut<ClientBuffers>,
 ) {
-    debug!("`{:?}` connected", trigger.client_id);
-    connected_clients.add(trigger.client_id);
     if replicated_clients.replicate_after_connect() {
         replicated_clients.add(&mut client_buffers, trigger.client_id);
     }
-    buffered_events.exclude_client(trigger.client_id);
 }
 
-fn handle_disconnects(
+fn remove_replicated_client(
     trigger: Trigger<ClientDisconnected>,
     mut entity_map: ResMut<ClientEntityMap>,
-    mut connected_clients: ResMut<ConnectedClients>,
     mut replicated_clients: ResMut<ReplicatedClients>,
-    mut server: ResMut<RepliconServer>,
     mut client_buffers: ResMut<ClientBuffers>,
+    heatmap: Option<ResMut<BandwidthHeatmap>>,
 ) {
-    debug!("`{:?}` disconnected: {}", trigger.client_id, trigger.reason);
-    entity_map.0.remove(&trigger.client_id);
-    connected_clients.remove(trigger.client_id);
+    entity_map.remove_client(trigger.client_id);
+    if let Some(mut heatmap) = heatmap {
+        heatmap.remove_client(trigger.client_id);
+    }
     replicated_clients.remove(&mut client_buffers, trigger.client_id);
-    server.remove_client(trigger.client_id);
 }
 
 fn enable_replication(
@@ -213,13 +693,20 @@ fn enable_replication(
 
 fn cleanup_acks(
     mutations_timeout: Duration,
-) -> impl FnMut(ResMut<ReplicatedClients>, ResMut<ClientBuffers>, Res<Time>) {
+    mutations_timeout_ticks: u32,
+) -> impl FnMut(ResMut<ReplicatedClients>, ResMut<ClientBuffers>, Res<ServerTick>, Res<Time>) {
     move |mut replicated_clients: ResMut<ReplicatedClients>,
           mut client_buffers: ResMut<ClientBuffers>,
+          server_tick: Res<ServerTick>,
           time: Res<Time>| {
         let min_timestamp = time.elapsed().saturating_sub(mutations_timeout);
         for client in replicated_clients.iter_mut() {
-            client.cleanup_older_mutations(&mut client_buffers, min_timestamp);
+            client.cleanup_older_mutations(
+                &mut client_buffers,
+                **server_tick,
+                mutations_timeout_ticks,
+                min_timestamp,
+            );
         }
     }
 }
@@ -230,21 +717,88 @@ fn receive_acks(
     mut replicated_clients: ResMut<ReplicatedClients>,
     mut client_buffers: ResMut<ClientBuffers>,
 ) {
+    let mut unknown_clients = Vec::new();
     for (client_id, mut message) in server.receive(ReplicationChannel::Updates) {
+        // Acks could be received before replication is enabled for the client.
+        let Some(client) = replicated_clients.get_client_mut(client_id) else {
+            unknown_clients.push(client_id);
+            continue;
+        };
+
         while message.has_remaining() {
             match postcard_utils::from_buf(&mut message) {
                 Ok(mutate_index) => {
-                    let client = replicated_clients.client_mut(client_id);
                     client.ack_mutate_message(
                         &mut client_buffers,
                         change_tick.this_run(),
                         mutate_index,
                     );
                 }
-                Err(e) => debug!("unable to deserialize mutate index from {client_id:?}: {e}"),
+                Err(e) => {
+                    debug!("unable to deserialize mutate index from {client_id:?}: {e}");
+                    break;
+                }
             }
         }
     }
+
+    for client_id in unknown_clients {
+        server.report_fault(NetworkFault::UnknownClient(client_id));
+    }
+}
+
+/// Buffers filled between sends and drained by [`send_replication`].
+#[derive(SystemParam)]
+pub(super) struct ReplicationBuffers<'w, 's> {
+    removal_buffer: ResMut<'w, RemovalBuffer>,
+    resource_buffer: ResMut<'w, ResourceBuffer>,
+    despawn_buffer: ResMut<'w, DespawnBuffer>,
+
+    /// Entities that lost visibility, reused between clients.
+    hidden_buffer: Local<'s, Vec<Entity>>,
+
+    client_buffers: ResMut<'w, ClientBuffers>,
+    transactions: ResMut<'w, ReplicationTransactions>,
+}
+
+/// Optional replication add-ons and their state used by [`send_replication`].
+#[derive(SystemParam)]
+pub(super) struct ReplicationAddons<'w, 's> {
+    /// Last sent values of components with field groups.
+    field_groups: Local<'s, FieldGroupStates>,
+
+    /// Snapshots of components with delta functions.
+    delta_snapshots: Local<'s, DeltaSnapshots>,
+
+    /// Priority accumulators for per-client bandwidth caps when [`SendBudget`] is not inserted.
+    caps_budget: Local<'s, SendBudget>,
+
+    priorities: Query<'w, 's, &'static ReplicationPriority>,
+    coalescing: Option<Res<'w, MessageCoalescing>>,
+    send_budget: Option<ResMut<'w, SendBudget>>,
+    obfuscation: Option<Res<'w, EntityObfuscation>>,
+    audit: Option<ResMut<'w, ReplicationAudit>>,
+    history: Option<ResMut<'w, ReplicationHistory>>,
+    heatmap: Option<ResMut<'w, BandwidthHeatmap>>,
+    cache: Option<ResMut<'w, SerializationCache>>,
+    budget: Option<ResMut<'w, ReplicationBudget>>,
+    despawn_budget: Option<ResMut<'w, DespawnBudget>>,
+    budget_events: EventWriter<'w, ReplicationBudgetExceeded>,
+    worker: Option<ResMut<'w, ReplicationWorker>>,
+    tick_batch: ResMut<'w, TickBatch>,
+    epoch: Res<'w, ServerEpoch>,
+}
+
+impl ReplicationAddons<'_, '_> {
+    /// Records a replicated change into the audit and the heatmap if they are enabled.
+    fn record_change(&mut self, entry: AuditEntry) {
+        if let Some(heatmap) = &mut self.heatmap {
+            heatmap.record(entry.client_id, entry.entity, entry.bytes);
+        }
+        if let Some(audit) = &mut self.audit {
+            audit.record(entry);
+        }
+    }
 }
 
 /// Collects [`ReplicationMessages`] and sends them.
@@ -255,25 +809,61 @@ pub(super) fn send_replication(
     change_tick: SystemChangeTick,
     world: ReplicationReadWorld,
     mut replicated_clients: ResMut<ReplicatedClients>,
-    mut removal_buffer: ResMut<RemovalBuffer>,
-    mut client_buffers: ResMut<ClientBuffers>,
+    buffers: ReplicationBuffers,
     mut entity_map: ResMut<ClientEntityMap>,
-    mut despawn_buffer: ResMut<DespawnBuffer>,
     mut server: ResMut<RepliconServer>,
     track_mutate_messages: Res<TrackMutateMessages>,
     registry: Res<ReplicationRegistry>,
     rules: Res<ReplicationRules>,
+    prefabs: Res<ReplicationPrefabs>,
     server_tick: Res<ServerTick>,
     time: Res<Time>,
+    mut addons: ReplicationAddons,
 ) -> postcard::Result<()> {
-    replicated_archetypes.update(world.archetypes(), world.components(), &rules);
+    let start = Instant::now();
+    let ReplicationBuffers {
+        mut removal_buffer,
+        mut resource_buffer,
+        mut despawn_buffer,
+        mut hidden_buffer,
+        mut client_buffers,
+        mut transactions,
+    } = buffers;
+    let epoch = **addons.epoch;
+    let epoch_changed = addons.epoch.is_changed() && !addons.epoch.is_added();
+    if rules.is_changed() || prefabs.is_changed() {
+        // Rules could be re-registered at runtime, rebuild the cache with the new ones.
+        replicated_archetypes.clear();
+    }
+    replicated_archetypes.update(
+        world.archetypes(),
+        world.components(),
+        &rules,
+        &registry,
+        &prefabs,
+    );
 
+    if let Some(history) = &mut addons.history {
+        history.start_tick(**server_tick);
+    }
+    if let Some(heatmap) = &mut addons.heatmap {
+        heatmap.start_tick();
+    }
+
+    addons
+        .delta_snapshots
+        .start_run(change_tick.this_run(), **server_tick);
     messages.reset(replicated_clients.len());
+    serialized.set_obfuscation(addons.obfuscation.as_deref().copied());
+
+    finish_visibility_transitions(&mut replicated_clients, &replicated_archetypes, &world);
 
     collect_mappings(
         &mut messages,
         &mut serialized,
         &replicated_clients,
+        &replicated_archetypes,
+        &world,
         &mut entity_map,
     )?;
     collect_despawns(
@@ -281,12 +871,25 @@ pub(super) fn send_replication(
         &mut serialized,
         &mut replicated_clients,
         &mut despawn_buffer,
+        &mut hidden_buffer,
+        &mut addons,
+        **server_tick,
+        epoch_changed,
     )?;
     collect_removals(
         &mut messages,
         &mut serialized,
         &replicated_clients,
+        &registry,
         &removal_buffer,
+        &mut addons,
+        **server_tick,
+    )?;
+    collect_resources(
+        &mut messages,
+        &mut serialized,
+        &replicated_clients,
+        &resource_buffer,
     )?;
     collect_changes(
         &mut messages,
@@ -294,40 +897,116 @@ pub(super) fn send_replication(
         &mut replicated_clients,
         &replicated_archetypes,
         &registry,
+        &prefabs,
         &removal_buffer,
+        &transactions,
         &world,
         &change_tick,
+        &mut addons,
+        start,
         **server_tick,
     )?;
     removal_buffer.clear();
+    resource_buffer.clear_changes();
+    transactions.clear_committed();
+
+    if let Some(budget) = addons
+        .budget
+        .as_ref()
+        .filter(|budget| budget.deferred_entities > 0)
+    {
+        debug!(
+            "replication budget exceeded, deferring mutations for {} entities",
+            budget.deferred_entities
+        );
+        addons.budget_events.send(ReplicationBudgetExceeded {
+            tick: **server_tick,
+            deferred_entities: budget.deferred_entities,
+        });
+    }
+
+    let ticks_covered = **server_tick - addons.tick_batch.last_sent;
+    addons.tick_batch.last_sent = **server_tick;
 
     send_messages(
         &mut messages,
         &mut replicated_clients,
         &mut server,
         **server_tick,
+        ticks_covered,
+        epoch,
+        epoch_changed,
         **track_mutate_messages,
         &mut serialized,
         &mut client_buffers,
+        &transactions,
         change_tick,
         &time,
+        &mut addons,
     )?;
+    if let Some(worker) = &mut addons.worker {
+        worker.spawn(&mut serialized);
+    }
     serialized.clear();
 
+    if let Some(audit) = &mut addons.audit {
+        audit.flush();
+    }
+
     Ok(())
 }
 
 fn reset(
     mut server_tick: ResMut<ServerTick>,
+    latency_injection: Option<ResMut<LatencyInjection>>,
+    session_store: Option<ResMut<SessionStore>>,
+    idle_detection: Option<ResMut<IdleDetection>>,
+) {
+    *server_tick = Default::default();
+    if let Some(mut latency_injection) = latency_injection {
+        latency_injection.clear_held();
+    }
+    if let Some(mut session_store) = session_store {
+        session_store.clear();
+    }
+    if let Some(mut idle_detection) = idle_detection {
+        idle_detection.clear();
+    }
+}
+
+fn reset_replication(
     mut entity_map: ResMut<ClientEntityMap>,
     mut replicated_clients: ResMut<ReplicatedClients>,
     mut client_buffers: ResMut<ClientBuffers>,
-    mut buffered_events: ResMut<BufferedServerEvents>,
+    mut tick_batch: ResMut<TickBatch>,
+    heatmap: Option<ResMut<BandwidthHeatmap>>,
+    journal: Option<ResMut<ChangeJournal>>,
+    cache: Option<ResMut<SerializationCache>>,
+    worker: Option<ResMut<ReplicationWorker>>,
+    mut bundles: ResMut<ClientBundles>,
+    mut despawn_buffer: ResMut<DespawnBuffer>,
+    send_budget: Option<ResMut<SendBudget>>,
 ) {
-    *server_tick = Default::default();
-    entity_map.0.clear();
+    tick_batch.last_sent = Default::default();
+    if let Some(mut heatmap) = heatmap {
+        heatmap.clear();
+    }
+    if let Some(mut journal) = journal {
+        journal.clear();
+    }
+    if let Some(mut cache) = cache {
+        cache.clear();
+    }
+    if let Some(mut worker) = worker {
+        worker.clear();
+    }
+    if let Some(mut send_budget) = send_budget {
+        send_budget.clear();
+    }
+    bundles.clear();
+    despawn_buffer.clear();
+    entity_map.clear();
     replicated_clients.clear(&mut client_buffers);
-    buffered_events.clear();
 }
 
 fn send_messages(
@@ -335,37 +1014,101 @@ fn send_messages(
     replicated_clients: &mut ReplicatedClients,
     server: &mut RepliconServer,
     server_tick: RepliconTick,
+    ticks_covered: u32,
+    epoch: ReplicationEpoch,
+    epoch_changed: bool,
     track_mutate_messages: bool,
     serialized: &mut SerializedData,
     client_buffers: &mut ClientBuffers,
+    transactions: &ReplicationTransactions,
     change_tick: SystemChangeTick,
     time: &Time,
+    addons: &mut ReplicationAddons,
 ) -> postcard::Result<()> {
+    // Per-client caps need priority accumulators even without the global budget.
+    let max_bytes = addons.send_budget.as_ref().map(|budget| budget.max_bytes);
+    let send_budget = addons
+        .send_budget
+        .as_deref_mut()
+        .unwrap_or(&mut addons.caps_budget);
+    send_budget.start_send(|client_id| replicated_clients.get_client(client_id).is_some());
+
     let mut server_tick_range = None;
     for ((update_message, mutate_message), client) in
         messages.iter_mut().zip(replicated_clients.iter_mut())
     {
-        if !update_message.is_empty() {
+        let send_mutations = server_tick
+            .get()
+            .is_multiple_of(client.mutations_interval());
+        if send_mutations && !mutate_message.is_empty() {
+            let bandwidth = client.refill_bandwidth(time.elapsed());
+            if let Some(max_bytes) = max_bytes.into_iter().chain(bandwidth).min() {
+                let written_bytes = send_budget.schedule(
+                    client.id(),
+                    mutate_message,
+                    &addons.priorities,
+                    max_bytes,
+                )?;
+                client.consume_bandwidth(written_bytes);
+            }
+        }
+        if let Some(coalescing) = addons
+            .coalescing
+            .as_deref()
+            .filter(|_| send_mutations && !update_message.is_empty() && !mutate_message.is_empty())
+            .filter(|_| !mutate_message.contains_deltas())
+        {
+            let server_tick = write_tick_cached(&mut server_tick_range, serialized, server_tick)?;
+            let size = update_message.size(epoch, server_tick.len(), ticks_covered)?
+                + mutate_message.changes_size()?;
+            if size <= coalescing.max_size {
+                trace!(
+                    "coalescing mutations into update message for {:?}",
+                    client.id()
+                );
+                // Mutations will be sent reliably, so they don't need acknowledgment.
+                update_message.take_all_mutations(mutate_message, |entity| {
+                    client.set_mutation_tick(entity, change_tick.this_run())
+                });
+            }
+        }
+
+        // Send even an empty message to let the client know about the new epoch.
+        if !update_message.is_empty() || epoch_changed {
             client.set_update_tick(server_tick);
             let server_tick = write_tick_cached(&mut server_tick_range, serialized, server_tick)?;
 
             trace!("sending update message to {:?}", client.id());
-            update_message.send(server, client, serialized, server_tick)?;
+            update_message.send(
+                server,
+                addons.worker.as_deref_mut(),
+                client,
+                serialized,
+                epoch,
+                server_tick,
+                ticks_covered,
+            )?;
         } else {
             trace!("no updates to send for {:?}", client.id());
         }
 
-        if !mutate_message.is_empty() || track_mutate_messages {
+        if !send_mutations {
+            trace!("skipping mutations for {:?} due to interval", client.id());
+        } else if !mutate_message.is_empty() || track_mutate_messages {
+            let sent_tick = server_tick;
             let server_tick = write_tick_cached(&mut server_tick_range, serialized, server_tick)?;
 
             let messages_count = mutate_message.send(
                 server,
+                addons.worker.as_deref_mut(),
                 client,
                 client_buffers,
                 serialized,
                 track_mutate_messages,
+                epoch,
                 server_tick,
                 change_tick.this_run(),
+                sent_tick,
                 time.elapsed(),
             )?;
             trace!(
@@ -376,24 +1119,72 @@ fn send_messages(
             trace!("no mutations to send for {:?}", client.id());
         }
 
-        client.visibility_mut().update();
+        // Entities in open transactions weren't sent, so they keep gained visibility until commit.
+        client
+            .visibility_mut()
+            .update(|entity| transactions.is_active(entity));
+        client.set_baseline_sent();
     }
 
     Ok(())
 }
 
-/// Collects and writes any new entity mappings that happened in this tick.
+/// Computes visibility changes for clients whose visibility policy was changed.
+///
+/// See [`ReplicatedClients::set_visibility_policy`].
+fn finish_visibility_transitions(
+    replicated_clients: &mut ReplicatedClients,
+    replicated_archetypes: &ReplicatedArchetypes,
+    world: &ReplicationReadWorld,
+) {
+    for client in replicated_clients
+        .iter_mut()
+        .filter(|client| client.visibility().in_transition())
+    {
+        let entities = replicated_archetypes
+            .iter()
+            .flat_map(|replicated_archetype| {
+                // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
+                let archetype = unsafe {
+                    world
+                        .archetypes()
+                        .get(replicated_archetype.id)
+                        .unwrap_unchecked()
+                };
+                archetype.entities().iter().map(|entity| entity.id())
+            });
+        client.visibility_mut().finish_transition(entities);
+    }
+}
+
+/// Collects and writes pending entity mappings whose server entities are replicated to the client.
 fn collect_mappings(
     messages: &mut ReplicationMessages,
     serialized: &mut SerializedData,
     replicated_clients: &ReplicatedClients,
+    replicated_archetypes: &ReplicatedArchetypes,
+    world: &ReplicationReadWorld,
     entity_map: &mut ClientEntityMap,
 ) -> postcard::Result<()> {
+    let marker_id = replicated_archetypes.marker_id();
     for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter()) {
-        if let Some(mappings) = entity_map.0.get_mut(&client.id()) {
+        let mappings = entity_map.drain_ready(client.id(), |mapping| {
+            let replicated = world
+                .entities()
+                .get(mapping.server_entity)
+                .and_then(|location| world.archetypes().get(location.archetype_id))
+                .is_some_and(|archetype| archetype.contains(marker_id));
+
+            replicated && client.visibility().is_visible(mapping.server_entity)
+        });
+        if !mappings.is_empty() {
             let len = mappings.len();
-            let mappings = serialized.write_mappings(mappings.drain(..))?;
-            message.set_mappings(mappings, len);
+            let baseline = mappings.iter().all(|pending| pending.baseline);
+            let mappings = serialized.write_mappings(
+                client.id(),
+                mappings.into_iter().map(|pending| pending.mapping),
+            )?;
+            message.set_mappings(mappings, len, baseline);
         }
     }
 
@@ -406,22 +1197,100 @@ fn collect_despawns(
     serialized: &mut SerializedData,
     replicated_clients: &mut ReplicatedClients,
     despawn_buffer: &mut DespawnBuffer,
+    hidden_buffer: &mut Vec<Entity>,
+    addons: &mut ReplicationAddons,
+    server_tick: RepliconTick,
+    epoch_changed: bool,
 ) -> postcard::Result<()> {
-    for entity in despawn_buffer.drain(..) {
-        let entity_range = serialized.write_entity(entity)?;
+    if epoch_changed {
+        // Clients despawn all entities from the previous epoch, no need to send despawns.
+        for entity in despawn_buffer.drain(..) {
+            addons.field_groups.remove_entity(entity);
+            addons.delta_snapshots.remove_entity(entity);
+            for client in replicated_clients.iter_mut() {
+                client.remove_despawned(entity);
+            }
+        }
+    }
+
+    let start = serialized.len();
+    let mut despawned = 0;
+    for &entity in despawn_buffer.iter() {
+        // Always send at least one despawn to guarantee progress.
+        if despawned > 0
+            && addons
+                .despawn_budget
+                .as_ref()
+                .is_some_and(|budget| serialized.len() - start >= budget.max_bytes)
+        {
+            break;
+        }
+
+        despawned += 1;
+        addons.field_groups.remove_entity(entity);
+        addons.delta_snapshots.remove_entity(entity);
+        let mut entity_range = None;
         for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter_mut()) {
             if client.visibility().is_visible(entity) {
+                let entity_range =
+                    write_entity_cached(&mut entity_range, serialized, client.id(), entity)?;
+                addons.record_change(AuditEntry {
+                    tick: server_tick,
+                    client_id: client.id(),
+                    entity,
+                    action: AuditAction::Despawn,
+                    bytes: entity_range.len(),
+                });
                 message.add_despawn(entity_range.clone());
             }
             client.remove_despawned(entity);
         }
     }
 
+    // Deferred despawns stay at the front of the buffer to preserve the order.
+    despawn_buffer.drain(..despawned);
+    if let Some(budget) = &mut addons.despawn_budget {
+        budget.deferred_despawns = despawn_buffer.len();
+        if budget.deferred_despawns > 0 {
+            debug!(
+                "despawn budget exceeded, deferring {} despawns",
+                budget.deferred_despawns
+            );
+        }
+    }
+
     for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter_mut()) {
-        for entity in client.drain_lost_visibility() {
-            let entity_range = serialized.write_entity(entity)?;
-            message.add_despawn(entity_range);
+        let client_id = client.id();
+        hidden_buffer.extend(client.drain_lost_visibility());
+        for group in entity_batch::split(hidden_buffer) {
+            // Obfuscated entities can't be packed into ranges.
+            if group.len() >= entity_batch::MIN_BATCH_LEN && !serialized.is_obfuscated() {
+                let batch_range = serialized.write_entity_batch(group)?;
+                for &entity in group {
+                    addons.record_change(AuditEntry {
+                        tick: server_tick,
+                        client_id,
+                        entity,
+                        action: AuditAction::Hide,
+                        bytes: batch_range.len() / group.len(),
+                    });
+                }
+                message.add_despawn_batch(batch_range);
+            } else {
+                for &entity in group {
+                    let entity_range = serialized.write_entity(client_id, entity)?;
+                    addons.record_change(AuditEntry {
+                        tick: server_tick,
+                        client_id,
+                        entity,
+                        action: AuditAction::Hide,
+                        bytes: entity_range.len(),
+                    });
+                    message.add_despawn(entity_range);
+                }
+            }
         }
+        hidden_buffer.clear();
     }
 
     Ok(())
@@ -432,15 +1301,29 @@ fn collect_removals(
     messages: &mut ReplicationMessages,
     serialized: &mut SerializedData,
     replicated_clients: &ReplicatedClients,
+    registry: &ReplicationRegistry,
     removal_buffer: &RemovalBuffer,
+    addons: &mut ReplicationAddons,
+    server_tick: RepliconTick,
 ) -> postcard::Result<()> {
     for (&entity, remove_ids) in removal_buffer.iter() {
-        let entity_range = serialized.write_entity(entity)?;
+        let mut entity_range = None;
         let ids_len = remove_ids.len();
-        let fn_ids = serialized.write_fn_ids(remove_ids.iter().map(|&(_, fns_id)| fns_id))?;
+        let fn_ids = serialized.write_removals(registry, removal_buffer, entity, remove_ids)?;
         for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter()) {
             if client.visibility().is_visible(entity) {
-                message.add_removals(entity_range.clone(), ids_len, fn_ids.clone());
+                let entity_range =
+                    write_entity_cached(&mut entity_range, serialized, client.id(), entity)?;
+                for &(_, fns_id) in remove_ids {
+                    addons.record_change(AuditEntry {
+                        tick: server_tick,
+                        client_id: client.id(),
+                        entity,
+                        action: AuditAction::Removal(fns_id),
+                        bytes: entity_range.len() + fn_ids.len(),
+                    });
+                }
+                message.add_removals(entity_range, ids_len, fn_ids.clone());
             }
         }
     }
@@ -449,108 +1332,366 @@ fn collect_removals(
 }
 
 /// Collects component changes from this tick into update and mutate messages since the last entity tick.
+/// Collects changed resources for all clients and current resources for clients that
+/// haven't received the initial state yet.
+fn collect_resources(
+    messages: &mut ReplicationMessages,
+    serialized: &mut SerializedData,
+    replicated_clients: &ReplicatedClients,
+    resource_buffer: &ResourceBuffer,
+) -> postcard::Result<()> {
+    // Resources are written lazily once and shared across clients.
+    let mut ranges: Vec<Option<Range<usize>>> = Vec::new();
+    for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter()) {
+        for (id, value, changed) in resource_buffer.iter() {
+            if !changed && (client.is_baseline_sent() || value.is_none()) {
+                continue;
+            }
+
+            if ranges.len() <= id {
+                ranges.resize(id + 1, None);
+            }
+            let range = match &ranges[id] {
+                Some(range) => range.clone(),
+                None => {
+                    let range = serialized.write_resource(id, value)?;
+                    ranges[id] = Some(range.clone());
+                    range
+                }
+            };
+            message.add_resource(range);
+        }
+    }
+
+    Ok(())
+}
+
 fn collect_changes(
     messages: &mut ReplicationMessages,
     serialized: &mut SerializedData,
     replicated_clients: &mut ReplicatedClients,
     replicated_archetypes: &ReplicatedArchetypes,
     registry: &ReplicationRegistry,
+    prefabs: &ReplicationPrefabs,
     removal_buffer: &RemovalBuffer,
+    transactions: &ReplicationTransactions,
     world: &ReplicationReadWorld,
     change_tick: &SystemChangeTick,
+    addons: &mut ReplicationAddons,
+    start: Instant,
     server_tick: RepliconTick,
 ) -> postcard::Result<()> {
-    for replicated_archetype in replicated_archetypes.iter() {
-        // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
-        let archetype = unsafe {
-            world
-                .archetypes()
-                .get(replicated_archetype.id)
-                .unwrap_unchecked()
-        };
+    let entities = replicated_archetypes
+        .iter()
+        .flat_map(|replicated_archetype| {
+            // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
+            let archetype = unsafe {
+                world
+                    .archetypes()
+                    .get(replicated_archetype.id)
+                    .unwrap_unchecked()
+            };
+            archetype
+                .entities()
+                .iter()
+                .map(move |entity| (replicated_archetype, archetype, entity))
+        })
+        .enumerate();
 
-        for entity in archetype.entities() {
-            let mut entity_range = None;
-            for ((update_message, mutate_message), client) in
-                messages.iter_mut().zip(replicated_clients.iter())
-            {
-                let visibility = client.visibility().state(entity.id());
-                update_message.start_entity_changes(visibility);
-                mutate_message.start_entity_mutations();
+    // Rotate entities to start from the first deferred entity.
+    let start_index = match &addons.budget {
+        Some(budget) => {
+            let len = entities.clone().count();
+            if budget.start_index < len {
+                budget.start_index
+            } else {
+                0
             }
+        }
+        None => 0,
+    };
+
+    let mut diff_buffer = Vec::new();
+    let mut budget_exceeded = false;
+    let mut deferred_entities = 0;
+    let mut first_deferred = None;
+    for (index, (replicated_archetype, archetype, entity)) in entities
+        .clone()
+        .skip(start_index)
+        .chain(entities.take(start_index))
+    {
+        if transactions.is_active(entity.id()) {
+            continue;
+        }
+
+        if !budget_exceeded {
+            budget_exceeded = addons
+                .budget
+                .as_ref()
+                .is_some_and(|budget| start.elapsed() > budget.max_duration);
+        }
+        let mut mutations_deferred = false;
+
+        let mut entity_range = None;
+        for ((update_message, mutate_message), client) in
+            messages.iter_mut().zip(replicated_clients.iter())
+        {
+            let visibility = client.visibility().state(entity.id());
+            update_message.start_entity_changes(visibility);
+            mutate_message.start_entity_mutations();
+        }
+
+        // SAFETY: all replicated archetypes have marker component with table storage.
+        let (_, marker_ticks) = unsafe {
+            world.get_component_unchecked(
+                entity,
+                archetype.table_id(),
+                StorageType::Table,
+                replicated_archetypes.marker_id(),
+            )
+        };
+        // If the marker was added in this tick, the entity just started replicating.
+        // It could be a newly spawned entity or an old entity with just-enabled replication,
+        // so we need to include even old components that were registered for replication.
+        let marker_added = marker_ticks.is_added(change_tick.last_run(), change_tick.this_run());
+
+        // All components changed during a committed transaction are sent together inside the update message.
+        let transaction_tick = transactions.committed_tick(entity.id());
 
-            // SAFETY: all replicated archetypes have marker component with table storage.
-            let (_, marker_ticks) = unsafe {
+        // Mutations taken into the update message can't be encoded relative to acknowledged values.
+        // Components with delta functions are placed first, so it's enough to check the first one.
+        let has_update =
+            replicated_archetype
+                .components
+                .first()
+                .is_some_and(|replicated_component| {
+                    let (_, _, rule_fns) = registry.get(replicated_component.fns_id);
+                    rule_fns.delta().is_some()
+                })
+                && (marker_added
+                || transaction_tick.is_some()
+                || removal_buffer.contains_key(&entity.id())
+                // SAFETY: entity and archetype were obtained from replicated archetypes.
+                || unsafe {
+                    has_insertions(
+                        world,
+                        registry,
+                        replicated_archetype,
+                        archetype,
+                        entity,
+                        change_tick,
+                    )
+                });
+
+        for replicated_component in &replicated_archetype.components {
+            let (component_id, component_fns, rule_fns) = registry.get(replicated_component.fns_id);
+
+            // SAFETY: component and storage were obtained from this archetype.
+            let (component, ticks) = unsafe {
                 world.get_component_unchecked(
                     entity,
                     archetype.table_id(),
-                    StorageType::Table,
-                    replicated_archetypes.marker_id(),
+                    replicated_component.storage_type,
+                    component_id,
                 )
             };
-            // If the marker was added in this tick, the entity just started replicating.
-            // It could be a newly spawned entity or an old entity with just-enabled replication,
-            // so we need to include even old components that were registered for replication.
-            let marker_added =
-                marker_ticks.is_added(change_tick.last_run(), change_tick.this_run());
-
-            for replicated_component in &replicated_archetype.components {
-                let (component_id, component_fns, rule_fns) =
-                    registry.get(replicated_component.fns_id);
-
-                // SAFETY: component and storage were obtained from this archetype.
-                let (component, ticks) = unsafe {
-                    world.get_component_unchecked(
-                        entity,
-                        archetype.table_id(),
-                        replicated_component.storage_type,
-                        component_id,
-                    )
-                };
 
-                let ctx = SerializeCtx {
-                    server_tick,
-                    component_id,
+            let ctx = SerializeCtx {
+                server_tick,
+                component_id,
+                diff: false,
+            };
+            let diff_ctx = SerializeCtx { diff: true, ..ctx };
+            if let Some(groups) = rule_fns.field_groups() {
+                if !addons
+                    .field_groups
+                    .contains(entity.id(), replicated_component.fns_id)
+                    || ticks.is_changed(change_tick.last_run(), change_tick.this_run())
+                {
+                    // SAFETY: component and functions were obtained for the same type.
+                    unsafe {
+                        addons.field_groups.update(
+                            (entity.id(), replicated_component.fns_id),
+                            (rule_fns, component_fns),
+                            component,
+                            groups,
+                            change_tick.this_run(),
+                        )?
+                    };
+                }
+            }
+
+            let delta = rule_fns.delta();
+            if delta.is_some()
+                && addons
+                    .delta_snapshots
+                    .checked_tick(entity.id(), replicated_component.fns_id)
+                    .is_none_or(|tick| ticks.is_changed(tick, change_tick.this_run()))
+            {
+                // SAFETY: component and functions were obtained for the same type.
+                unsafe {
+                    addons.delta_snapshots.update(
+                        (entity.id(), replicated_component.fns_id),
+                        (rule_fns, component_fns),
+                        &ctx,
+                        component,
+                        change_tick.this_run(),
+                    )?
                 };
-                let mut component_range = None;
-                for ((update_message, mutate_message), client) in
-                    messages.iter_mut().zip(replicated_clients.iter())
+            }
+
+            let flag_bit = registry.flag_bit(replicated_component.fns_id);
+            let mut component_range = None;
+            let mut diff_range = None;
+            let mut group_ranges = Vec::new();
+            let mut delta_ranges = Vec::new();
+            for ((update_message, mutate_message), client) in
+                messages.iter_mut().zip(replicated_clients.iter())
+            {
+                if update_message.entity_visibility() == Visibility::Hidden {
+                    continue;
+                }
+
+                if let Some(tick) = client
+                    .mutation_tick(entity.id())
+                    .filter(|_| !marker_added)
+                    .filter(|_| update_message.entity_visibility() != Visibility::Gained)
+                    .filter(|_| !ticks.is_added(change_tick.last_run(), change_tick.this_run()))
+                    .filter(|_| {
+                        transaction_tick
+                            .is_none_or(|tick| !ticks.is_changed(tick, change_tick.this_run()))
+                    })
                 {
-                    if update_message.entity_visibility() == Visibility::Hidden {
-                        continue;
-                    }
+                    if ticks.is_changed(tick, change_tick.this_run()) {
+                        if rule_fns.diffs() {
+                            // Diffs are sent reliably, so the client already received all changes before the last run.
+                            if ticks.is_changed(change_tick.last_run(), change_tick.this_run()) {
+                                if !update_message.entity_written() {
+                                    let entity_range = write_entity_cached(
+                                        &mut entity_range,
+                                        serialized,
+                                        client.id(),
+                                        entity.id(),
+                                    )?;
+                                    update_message.add_changed_entity(entity_range);
+                                }
+                                let diff_range = write_diff_cached(
+                                    &mut diff_range,
+                                    serialized,
+                                    rule_fns,
+                                    component_fns,
+                                    &diff_ctx,
+                                    replicated_component,
+                                    component,
+                                )?;
+                                addons.record_change(AuditEntry {
+                                    tick: server_tick,
+                                    client_id: client.id(),
+                                    entity: entity.id(),
+                                    action: AuditAction::Mutation(replicated_component.fns_id),
+                                    bytes: diff_range.len(),
+                                });
+                                update_message.add_inserted_component(diff_range);
+                            }
+                            continue;
+                        }
 
-                    if let Some(tick) = client
-                        .mutation_tick(entity.id())
-                        .filter(|_| !marker_added)
-                        .filter(|_| update_message.entity_visibility() != Visibility::Gained)
-                        .filter(|_| !ticks.is_added(change_tick.last_run(), change_tick.this_run()))
-                    {
-                        if ticks.is_changed(tick, change_tick.this_run()) {
+                        if budget_exceeded {
+                            mutations_deferred = true;
+                            continue;
+                        }
+
+                        if let Some((diff, _)) = delta {
                             if !mutate_message.mutations_written() {
                                 let entity_range = write_entity_cached(
                                     &mut entity_range,
                                     serialized,
+                                    client.id(),
                                     entity.id(),
                                 )?;
                                 mutate_message.add_mutated_entity(entity.id(), entity_range);
                             }
-                            let component_range = write_component_cached(
-                                &mut component_range,
+                            let baseline = addons
+                                .delta_snapshots
+                                .baseline(
+                                    entity.id(),
+                                    replicated_component.fns_id,
+                                    tick,
+                                    change_tick.this_run(),
+                                )
+                                .filter(|_| !has_update);
+                            let has_baseline = baseline.is_some();
+                            let component_range = write_delta_cached(
+                                &mut delta_ranges,
+                                serialized,
+                                &addons.delta_snapshots,
+                                (entity.id(), replicated_component.fns_id),
+                                baseline.map(|(tick, bytes)| (tick, bytes, diff)),
+                                &mut diff_buffer,
+                            )?;
+                            addons.record_change(AuditEntry {
+                                tick: server_tick,
+                                client_id: client.id(),
+                                entity: entity.id(),
+                                action: AuditAction::Mutation(replicated_component.fns_id),
+                                bytes: component_range.len(),
+                            });
+                            if has_baseline {
+                                mutate_message.add_delta_component(component_range);
+                            } else {
+                                mutate_message.add_mutated_component(component_range);
+                            }
+                            continue;
+                        }
+
+                        if rule_fns.field_groups().is_some() {
+                            let (mask, groups) = addons.field_groups.changes(
+                                entity.id(),
+                                replicated_component.fns_id,
+                                tick,
+                                change_tick.this_run(),
+                            );
+                            if mask == 0 {
+                                // Values are equal to the last acknowledged ones.
+                                continue;
+                            }
+
+                            if !mutate_message.mutations_written() {
+                                let entity_range = write_entity_cached(
+                                    &mut entity_range,
+                                    serialized,
+                                    client.id(),
+                                    entity.id(),
+                                )?;
+                                mutate_message.add_mutated_entity(entity.id(), entity_range);
+                            }
+                            let component_range = write_field_groups_cached(
+                                &mut group_ranges,
                                 serialized,
                                 rule_fns,
-                                component_fns,
-                                &ctx,
-                                replicated_component,
-                                component,
+                                replicated_component.fns_id,
+                                mask,
+                                groups,
                             )?;
+                            addons.record_change(AuditEntry {
+                                tick: server_tick,
+                                client_id: client.id(),
+                                entity: entity.id(),
+                                action: AuditAction::Mutation(replicated_component.fns_id),
+                                bytes: component_range.len(),
+                            });
                             mutate_message.add_mutated_component(component_range);
+                            continue;
                         }
-                    } else {
-                        if !update_message.entity_written() {
-                            let entity_range =
-                                write_entity_cached(&mut entity_range, serialized, entity.id())?;
-                            update_message.add_changed_entity(entity_range);
+
+                        if !mutate_message.mutations_written() {
+                            let entity_range = write_entity_cached(
+                                &mut entity_range,
+                                serialized,
+                                client.id(),
+                                entity.id(),
+                            )?;
+                            mutate_message.add_mutated_entity(entity.id(), entity_range);
                         }
                         let component_range = write_component_cached(
                             &mut component_range,
@@ -560,61 +1701,194 @@ fn collect_changes(
                             &ctx,
                             replicated_component,
                             component,
+                            &mut addons.cache,
+                            (entity.id(), ticks.changed),
+                        )?;
+                        addons.record_change(AuditEntry {
+                            tick: server_tick,
+                            client_id: client.id(),
+                            entity: entity.id(),
+                            action: AuditAction::Mutation(replicated_component.fns_id),
+                            bytes: component_range.len(),
+                        });
+                        mutate_message.add_mutated_component(component_range);
+                    }
+                } else {
+                    let new_entity =
+                        marker_added || update_message.entity_visibility() == Visibility::Gained;
+                    if new_entity
+                        // Clients need the full value to use it as a baseline.
+                        && delta.is_none()
+                        && replicated_component
+                            .prefab_index
+                            .zip(replicated_archetype.prefab_index)
+                            .is_some_and(|(component_index, prefab_index)| {
+                                // SAFETY: component index obtained for this component.
+                                unsafe {
+                                    prefabs.is_default(prefab_index, component_index, component)
+                                }
+                            })
+                    {
+                        // Client will insert the default value from the prefab.
+                        continue;
+                    }
+
+                    if !update_message.entity_written() {
+                        let entity_range = write_entity_cached(
+                            &mut entity_range,
+                            serialized,
+                            client.id(),
+                            entity.id(),
                         )?;
-                        update_message.add_inserted_component(component_range);
+                        update_message.add_changed_entity(entity_range);
+                    }
+                    let component_range = if delta.is_some() {
+                        write_delta_cached(
+                            &mut delta_ranges,
+                            serialized,
+                            &addons.delta_snapshots,
+                            (entity.id(), replicated_component.fns_id),
+                            None,
+                            &mut diff_buffer,
+                        )?
+                    } else {
+                        write_component_cached(
+                            &mut component_range,
+                            serialized,
+                            rule_fns,
+                            component_fns,
+                            &ctx,
+                            replicated_component,
+                            component,
+                            &mut addons.cache,
+                            (entity.id(), ticks.changed),
+                        )?
+                    };
+                    addons.record_change(AuditEntry {
+                        tick: server_tick,
+                        client_id: client.id(),
+                        entity: entity.id(),
+                        action: AuditAction::Insertion(replicated_component.fns_id),
+                        bytes: component_range.len(),
+                    });
+                    match flag_bit {
+                        // Pack zero-sized components only if nothing was serialized after the ID.
+                        Some(bit)
+                            if component_range.len()
+                                == serialized_size(&replicated_component.fns_id)? =>
+                        {
+                            update_message.add_inserted_flag(bit)
+                        }
+                        _ => update_message.add_inserted_component(component_range),
                     }
                 }
             }
 
-            for ((update_message, mutate_message), client) in
-                messages.iter_mut().zip(replicated_clients.iter_mut())
-            {
-                let visibility = update_message.entity_visibility();
-                if visibility == Visibility::Hidden {
-                    continue;
+            if let Some(history) = &mut addons.history {
+                if marker_added || ticks.is_changed(change_tick.last_run(), change_tick.this_run())
+                {
+                    // Recorded in the same format as insertions since history is played back as an update message.
+                    let component_range = if delta.is_some() {
+                        write_delta_cached(
+                            &mut delta_ranges,
+                            serialized,
+                            &addons.delta_snapshots,
+                            (entity.id(), replicated_component.fns_id),
+                            None,
+                            &mut diff_buffer,
+                        )?
+                    } else {
+                        write_component_cached(
+                            &mut component_range,
+                            serialized,
+                            rule_fns,
+                            component_fns,
+                            &ctx,
+                            replicated_component,
+                            component,
+                            &mut addons.cache,
+                            (entity.id(), ticks.changed),
+                        )?
+                    };
+                    history.record(
+                        entity.id(),
+                        replicated_component.fns_id,
+                        &serialized[component_range],
+                    );
                 }
+            }
+        }
 
-                let new_entity = marker_added || visibility == Visibility::Gained;
-                if new_entity
-                    || update_message.entity_written()
-                    || removal_buffer.contains_key(&entity.id())
-                {
-                    // If there is any insertion, removal, or it's a new entity for a client, include all mutations
-                    // into update message and bump the last acknowledged tick to keep entity updates atomic.
-                    update_message.take_mutations(mutate_message);
+        for ((update_message, mutate_message), client) in
+            messages.iter_mut().zip(replicated_clients.iter_mut())
+        {
+            let visibility = update_message.entity_visibility();
+            if visibility == Visibility::Hidden {
+                continue;
+            }
+
+            let new_entity = marker_added || visibility == Visibility::Gained;
+            if new_entity
+                || update_message.entity_written()
+                || removal_buffer.contains_key(&entity.id())
+            {
+                // If there is any insertion, removal, or it's a new entity for a client, include all mutations
+                // into update message and bump the last acknowledged tick to keep entity updates atomic.
+                update_message.take_mutations(mutate_message);
+                if new_entity || !mutations_deferred {
                     client.set_mutation_tick(entity.id(), change_tick.this_run());
                 }
+            }
 
-                if new_entity && !update_message.entity_written() {
-                    // Force-write new entity even if it doesn't have any components.
-                    let entity_range =
-                        write_entity_cached(&mut entity_range, serialized, entity.id())?;
-                    update_message.add_changed_entity(entity_range);
-                }
+            if new_entity && !update_message.entity_written() {
+                // Force-write new entity even if it doesn't have any components.
+                let entity_range =
+                    write_entity_cached(&mut entity_range, serialized, client.id(), entity.id())?;
+                update_message.add_changed_entity(entity_range);
             }
         }
+
+        if mutations_deferred {
+            deferred_entities += 1;
+            first_deferred.get_or_insert(index);
+        }
+    }
+
+    if let Some(budget) = &mut addons.budget {
+        budget.deferred_entities = deferred_entities;
+        if let Some(index) = first_deferred {
+            budget.start_index = index;
+        }
     }
 
     Ok(())
 }
 
-/// Writes an entity or re-uses previously written range if exists.
+/// Writes an entity for a client or re-uses previously written range if exists.
+///
+/// Obfuscated entities are never reused since each client has its own permutation.
 fn write_entity_cached(
     entity_range: &mut Option<Range<usize>>,
     serialized: &mut SerializedData,
+    client_id: ClientId,
     entity: Entity,
 ) -> postcard::Result<Range<usize>> {
+    if serialized.is_obfuscated() {
+        return serialized.write_entity(client_id, entity);
+    }
     if let Some(range) = entity_range.clone() {
         return Ok(range);
     }
 
-    let range = serialized.write_entity(entity)?;
+    let range = serialized.write_entity(client_id, entity)?;
     *entity_range = Some(range.clone());
 
     Ok(range)
 }
 
 /// Writes a component or re-uses previously written range if exists.
+///
+/// If [`SerializationCache`] is present, serialized bytes are also reused across ticks.
 fn write_component_cached(
     component_range: &mut Option<Range<usize>>,
     serialized: &mut SerializedData,
@@ -623,11 +1897,49 @@ fn write_component_cached(
     ctx: &SerializeCtx,
     replicated_component: &ReplicatedComponent,
     component: Ptr<'_>,
+    cache: &mut Option<ResMut<SerializationCache>>,
+    (entity, change_tick): (Entity, Tick),
 ) -> postcard::Result<Range<usize>> {
     if let Some(component_range) = component_range.clone() {
         return Ok(component_range);
     }
 
+    let fns_id = replicated_component.fns_id;
+    if let Some(bytes) = cache
+        .as_mut()
+        .and_then(|cache| cache.get(entity, fns_id, change_tick))
+    {
+        let range = serialized.write_cached(bytes);
+        *component_range = Some(range.clone());
+        return Ok(range);
+    }
+
+    let range = serialized.write_component(rule_fns, component_fns, ctx, fns_id, component)?;
+    if let Some(cache) = cache {
+        cache.insert(entity, fns_id, change_tick, &serialized[range.clone()]);
+    }
+    *component_range = Some(range.clone());
+
+    Ok(range)
+}
+
+/// Writes a component diff or re-uses previously written range if exists.
+///
+/// Unlike [`write_component_cached`], doesn't use [`SerializationCache`]
+/// since diffs depend on changes since the last send.
+fn write_diff_cached(
+    diff_range: &mut Option<Range<usize>>,
+    serialized: &mut SerializedData,
+    rule_fns: &UntypedRuleFns,
+    component_fns: &ComponentFns,
+    ctx: &SerializeCtx,
+    replicated_component: &ReplicatedComponent,
+    component: Ptr<'_>,
+) -> postcard::Result<Range<usize>> {
+    if let Some(range) = diff_range.clone() {
+        return Ok(range);
+    }
+
     let range = serialized.write_component(
         rule_fns,
         component_fns,
@@ -635,7 +1947,95 @@ fn write_component_cached(
         replicated_component.fns_id,
         component,
     )?;
-    *component_range = Some(range.clone());
+    *diff_range = Some(range.clone());
+
+    Ok(range)
+}
+
+/// Writes a component with delta functions or re-uses previously written range with the same baseline if exists.
+///
+/// Clients may acknowledge different ticks, so each baseline is written separately.
+/// Without a baseline the full value is written.
+fn write_delta_cached(
+    delta_ranges: &mut Vec<(Option<RepliconTick>, Range<usize>)>,
+    serialized: &mut SerializedData,
+    delta_snapshots: &DeltaSnapshots,
+    (entity, fns_id): (Entity, FnsId),
+    baseline: Option<(RepliconTick, &[u8], DiffFn)>,
+    diff_buffer: &mut Vec<u8>,
+) -> postcard::Result<Range<usize>> {
+    let baseline_tick = baseline.map(|(tick, ..)| tick);
+    if let Some((_, range)) = delta_ranges
+        .iter()
+        .find(|&&(tick, _)| tick == baseline_tick)
+    {
+        return Ok(range.clone());
+    }
+
+    let value = delta_snapshots.current(entity, fns_id);
+    let range = match baseline {
+        Some((tick, bytes, diff)) => {
+            diff_buffer.clear();
+            (diff)(bytes, value, diff_buffer)?;
+            serialized.write_delta(fns_id, Some(tick), diff_buffer)?
+        }
+        None => serialized.write_delta(fns_id, None, value)?,
+    };
+    delta_ranges.push((baseline_tick, range.clone()));
+
+    Ok(range)
+}
+
+/// Returns `true` if any component of the entity was inserted or changed with diffs in this tick.
+///
+/// # Safety
+///
+/// The caller must ensure that `archetype` and `replicated_archetype` were obtained for the entity.
+unsafe fn has_insertions(
+    world: &ReplicationReadWorld,
+    registry: &ReplicationRegistry,
+    replicated_archetype: &ReplicatedArchetype,
+    archetype: &Archetype,
+    entity: &ArchetypeEntity,
+    change_tick: &SystemChangeTick,
+) -> bool {
+    replicated_archetype
+        .components
+        .iter()
+        .any(|replicated_component| {
+            let (component_id, _, rule_fns) = registry.get(replicated_component.fns_id);
+            let (_, ticks) = world.get_component_unchecked(
+                entity,
+                archetype.table_id(),
+                replicated_component.storage_type,
+                component_id,
+            );
+            ticks.is_added(change_tick.last_run(), change_tick.this_run())
+                || (rule_fns.diffs()
+                    && ticks.is_changed(change_tick.last_run(), change_tick.this_run()))
+        })
+}
+
+/// Writes changed field groups or re-uses previously written range with the same mask if exists.
+///
+/// Clients may acknowledge different ticks, so each mask is written separately.
+fn write_field_groups_cached<'a>(
+    group_ranges: &mut Vec<(u64, Range<usize>)>,
+    serialized: &mut SerializedData,
+    rule_fns: &UntypedRuleFns,
+    fns_id: FnsId,
+    mask: u64,
+    groups: impl Iterator<Item = &'a [u8]>,
+) -> postcard::Result<Range<usize>> {
+    if let Some((_, range)) = group_ranges
+        .iter()
+        .find(|&&(written_mask, _)| written_mask == mask)
+    {
+        return Ok(range.clone());
+    }
+
+    let range = serialized.write_field_groups(rule_fns, fns_id, mask, groups)?;
+    group_ranges.push((mask, range.clone()));
 
     Ok(range)
 }
@@ -656,6 +2056,26 @@ fn write_tick_cached(
     Ok(range)
 }
 
+/// Tracks ticks accumulated since the last replication send.
+///
+/// See [`ServerPlugin::ticks_per_send`].
+#[derive(Resource)]
+pub(super) struct TickBatch {
+    ticks_per_send: u32,
+
+    /// Tick on which replication was sent last time.
+    last_sent: RepliconTick,
+}
+
+impl TickBatch {
+    fn new(ticks_per_send: u32) -> Self {
+        Self {
+            ticks_per_send,
+            last_sent: Default::default(),
+        }
+    }
+}
+
 /// Set with replication and event systems related to server.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum ServerSet {
@@ -697,27 +2117,6 @@ pub enum ServerSet {
     SendPackets,
 }
 
-/// Controls how often [`RepliconTick`] is incremented on the server.
-///
-/// When [`RepliconTick`] is mutated, the server's replication
-/// system will run. This means the tick policy controls how often server state is replicated.
-///
-/// Note that component mutations are replicated over the unreliable channel, so if a component mutate message is lost
-/// then component mutations won't be resent until the server's replication system runs again.
-#[derive(Debug, Copy, Clone)]
-pub enum TickPolicy {
-    /// The replicon tick is incremented at most max ticks per second. In practice the tick rate may be lower if the
-    /// app's update cycle duration is too long.
-    ///
-    /// By default it's 30 ticks per second.
-    MaxTickRate(u16),
-    /// The replicon tick is incremented every frame.
-    EveryFrame,
-    /// The user should manually configure [`increment_tick`] or manually increment
-    /// [`RepliconTick`].
-    Manual,
-}
-
 /// Triggered on connection on the server.
 ///
 /// The messaging backend is responsible for triggering.
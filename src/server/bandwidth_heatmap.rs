@@ -0,0 +1,265 @@
+use std::{cmp::Reverse, collections::VecDeque};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::core::ClientId;
+
+/// Opt-in per-entity traffic statistics for each client over a sliding window of ticks.
+///
+/// Insert this resource to enable tracking. When present, the server accumulates
+/// the number of serialized bytes and sends for each entity and client.
+/// Useful for rendering a "bandwidth heatmap" in a debug view to find
+/// entities that dominate traffic and tune replication rules accordingly.
+///
+/// Bytes are counted the same way as in [`AuditEntry::bytes`](super::replication_audit::AuditEntry::bytes).
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::bandwidth_heatmap::BandwidthHeatmap};
+///
+/// # let mut app = App::new();
+/// app.insert_resource(BandwidthHeatmap::new(60))
+///     .add_systems(Update, log_heaviest);
+///
+/// fn log_heaviest(heatmap: Res<BandwidthHeatmap>, connected_clients: Res<ConnectedClients>) {
+///     for client in connected_clients.iter() {
+///         for (entity, traffic) in heatmap.top(client.id(), 5) {
+///             debug!("{entity} sent {} bytes to `{:?}`", traffic.bytes, client.id());
+///         }
+///     }
+/// }
+/// ```
+#[derive(Resource)]
+pub struct BandwidthHeatmap {
+    /// Traffic recorded on each tick, from oldest to newest.
+    ticks: VecDeque<HashMap<(ClientId, Entity), EntityTraffic>>,
+
+    /// Sum of all traffic from [`Self::ticks`].
+    totals: HashMap<(ClientId, Entity), EntityTraffic>,
+
+    /// Maximum number of ticks in [`Self::ticks`].
+    window: usize,
+}
+
+impl BandwidthHeatmap {
+    /// Creates a new instance that accumulates traffic over the last `window` replication ticks.
+    pub fn new(window: usize) -> Self {
+        Self {
+            ticks: VecDeque::with_capacity(window),
+            totals: Default::default(),
+            window,
+        }
+    }
+
+    /// Returns the number of ticks over which the traffic is accumulated.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns accumulated traffic of an entity for a client.
+    pub fn traffic(&self, client_id: ClientId, entity: Entity) -> EntityTraffic {
+        self.totals
+            .get(&(client_id, entity))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns accumulated traffic of an entity for all clients.
+    pub fn entity_traffic(&self, entity: Entity) -> EntityTraffic {
+        self.totals
+            .iter()
+            .filter(|((_, other_entity), _)| *other_entity == entity)
+            .fold(EntityTraffic::default(), |acc, (_, &traffic)| acc + traffic)
+    }
+
+    /// Returns iterator over accumulated traffic of all entities for a client.
+    pub fn iter_client(
+        &self,
+        client_id: ClientId,
+    ) -> impl Iterator<Item = (Entity, EntityTraffic)> + '_ {
+        self.totals
+            .iter()
+            .filter(move |((other_id, _), _)| *other_id == client_id)
+            .map(|(&(_, entity), &traffic)| (entity, traffic))
+    }
+
+    /// Returns iterator over accumulated traffic for all clients and entities.
+    pub fn iter(&self) -> impl Iterator<Item = (ClientId, Entity, EntityTraffic)> + '_ {
+        self.totals
+            .iter()
+            .map(|(&(client_id, entity), &traffic)| (client_id, entity, traffic))
+    }
+
+    /// Returns up to `count` entities with the most bytes sent to a client, sorted in descending order.
+    pub fn top(&self, client_id: ClientId, count: usize) -> Vec<(Entity, EntityTraffic)> {
+        let mut entities: Vec<_> = self.iter_client(client_id).collect();
+        entities.sort_unstable_by_key(|(_, traffic)| Reverse(traffic.bytes));
+        entities.truncate(count);
+        entities
+    }
+
+    /// Removes all accumulated traffic.
+    pub fn clear(&mut self) {
+        self.ticks.clear();
+        self.totals.clear();
+    }
+
+    /// Starts a new tick, evicting the oldest one if the window is exceeded.
+    pub(crate) fn start_tick(&mut self) {
+        if self.window == 0 {
+            return;
+        }
+
+        if self.ticks.len() == self.window {
+            let mut oldest = self.ticks.pop_front().unwrap();
+            for (key, traffic) in oldest.drain() {
+                let total = self
+                    .totals
+                    .get_mut(&key)
+                    .expect("totals should contain all recorded traffic");
+                *total = *total - traffic;
+                if total.sends == 0 {
+                    self.totals.remove(&key);
+                }
+            }
+            self.ticks.push_back(oldest);
+        } else {
+            self.ticks.push_back(Default::default());
+        }
+    }
+
+    /// Records serialized bytes for an entity sent to a client on the current tick.
+    ///
+    /// Multiple records for the same entity and client on a single tick count as one send.
+    pub(crate) fn record(&mut self, client_id: ClientId, entity: Entity, bytes: usize) {
+        let Some(current) = self.ticks.back_mut() else {
+            return;
+        };
+
+        let key = (client_id, entity);
+        let traffic = current.entry(key).or_default();
+        let new_send = traffic.sends == 0;
+        traffic.bytes += bytes;
+        if new_send {
+            traffic.sends += 1;
+        }
+
+        let total = self.totals.entry(key).or_default();
+        total.bytes += bytes;
+        if new_send {
+            total.sends += 1;
+        }
+    }
+
+    /// Removes all traffic of a disconnected client.
+    pub(crate) fn remove_client(&mut self, client_id: ClientId) {
+        for traffic in &mut self.ticks {
+            traffic.retain(|&(other_id, _), _| other_id != client_id);
+        }
+        self.totals
+            .retain(|&(other_id, _), _| other_id != client_id);
+    }
+}
+
+/// Accumulated traffic of an entity.
+///
+/// See [`BandwidthHeatmap`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EntityTraffic {
+    /// Number of serialized bytes.
+    pub bytes: usize,
+
+    /// Number of ticks on which the entity was sent.
+    pub sends: usize,
+}
+
+impl std::ops::Add for EntityTraffic {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            bytes: self.bytes + rhs.bytes,
+            sends: self.sends + rhs.sends,
+        }
+    }
+}
+
+impl std::ops::Sub for EntityTraffic {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            bytes: self.bytes - rhs.bytes,
+            sends: self.sends - rhs.sends,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sliding_window() {
+        let mut heatmap = BandwidthHeatmap::new(2);
+        let client_id = ClientId::new(1);
+        let entity = Entity::from_raw(0);
+
+        heatmap.start_tick();
+        heatmap.record(client_id, entity, 10);
+        heatmap.record(client_id, entity, 5);
+        assert_eq!(
+            heatmap.traffic(client_id, entity),
+            EntityTraffic {
+                bytes: 15,
+                sends: 1
+            }
+        );
+
+        heatmap.start_tick();
+        heatmap.record(client_id, entity, 20);
+        assert_eq!(
+            heatmap.traffic(client_id, entity),
+            EntityTraffic {
+                bytes: 35,
+                sends: 2
+            }
+        );
+
+        heatmap.start_tick();
+        assert_eq!(
+            heatmap.traffic(client_id, entity),
+            EntityTraffic {
+                bytes: 20,
+                sends: 1
+            }
+        );
+
+        heatmap.start_tick();
+        assert_eq!(heatmap.traffic(client_id, entity), Default::default());
+        assert_eq!(heatmap.iter().count(), 0);
+    }
+
+    #[test]
+    fn top() {
+        let mut heatmap = BandwidthHeatmap::new(1);
+        let client_id = ClientId::new(1);
+        let light = Entity::from_raw(0);
+        let heavy = Entity::from_raw(1);
+
+        heatmap.start_tick();
+        heatmap.record(client_id, light, 1);
+        heatmap.record(client_id, heavy, 100);
+        heatmap.record(ClientId::new(2), light, 1000);
+
+        let top = heatmap.top(client_id, 1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].0, heavy);
+        assert_eq!(heatmap.entity_traffic(light).bytes, 1001);
+
+        heatmap.remove_client(client_id);
+        assert!(heatmap.top(client_id, 1).is_empty());
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, Tick},
+        world::CommandQueue,
+    },
+    prelude::*,
+    utils::HashMap,
+};
+use bytes::Bytes;
+
+use super::server_tick::ServerTick;
+use crate::core::{
+    replication::{
+        command_markers::{CommandMarkers, EntityMarkers},
+        deferred_entity::DeferredEntity,
+        replication_registry::{
+            ctx::{RemoveCtx, SerializeCtx, WriteCtx},
+            FnsId, ReplicationRegistry,
+        },
+        replication_rules::ReplicationRules,
+        Replicated,
+    },
+    replicon_tick::RepliconTick,
+    server_entity_map::ServerEntityMap,
+};
+
+/// Opt-in journal of replicated component changes on server.
+///
+/// Insert this resource to enable journaling. When present, the server records serialized
+/// old and new values of all replicated components that changed on each tick.
+/// Use [`ChangeJournalWorldExt::rollback_changes`] to revert the world to a previous tick,
+/// for example, for lag compensation or resimulation.
+///
+/// Values are captured once per tick, so intermediate changes within a tick are not recorded.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     prelude::*,
+///     server::change_journal::{ChangeJournal, ChangeJournalWorldExt},
+/// };
+///
+/// # let mut app = App::new();
+/// # app.add_plugins((MinimalPlugins, RepliconPlugins));
+/// app.insert_resource(ChangeJournal::new(64))
+///     .replicate::<Transform>()
+///     .add_systems(Update, rewind.run_if(server_running));
+///
+/// fn rewind(world: &mut World) {
+///     // Revert transforms to the state from 3 ticks ago.
+///     let transform_id = world.register_component::<Transform>();
+///     world.rollback_changes(3, |component_id| component_id == transform_id);
+/// }
+/// ```
+#[derive(Resource)]
+pub struct ChangeJournal {
+    /// Recorded ticks with changes, from oldest to newest.
+    ticks: VecDeque<JournalTick>,
+
+    /// Last recorded values for entity components.
+    values: HashMap<(Entity, ComponentId), (FnsId, Bytes)>,
+
+    /// Number of ticks after which changes are evicted.
+    max_ticks: u32,
+}
+
+impl ChangeJournal {
+    /// Creates a new instance that keeps changes for the last `max_ticks` server ticks.
+    pub fn new(max_ticks: u32) -> Self {
+        Self {
+            ticks: Default::default(),
+            values: Default::default(),
+            max_ticks,
+        }
+    }
+
+    /// Returns the number of ticks for which changes are kept.
+    pub fn max_ticks(&self) -> u32 {
+        self.max_ticks
+    }
+
+    /// Returns an iterator over recorded ticks with changes, from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &JournalTick> {
+        self.ticks.iter()
+    }
+
+    /// Removes all recorded changes.
+    ///
+    /// Current values will be recorded as insertions on the next tick.
+    pub fn clear(&mut self) {
+        self.ticks.clear();
+        self.values.clear();
+    }
+}
+
+/// Changes recorded on a single tick.
+///
+/// See [`ChangeJournal`].
+pub struct JournalTick {
+    /// Server tick on which the changes were recorded.
+    pub tick: RepliconTick,
+
+    /// Recorded changes.
+    pub changes: Vec<JournalEntry>,
+}
+
+/// A single component change.
+///
+/// See [`ChangeJournal`].
+pub struct JournalEntry {
+    /// Changed entity.
+    pub entity: Entity,
+
+    /// ID of the changed component.
+    pub component_id: ComponentId,
+
+    /// ID of the functions used for serialization.
+    pub fns_id: FnsId,
+
+    /// Serialized value before the change.
+    ///
+    /// [`None`] if the component was inserted.
+    pub old: Option<Bytes>,
+
+    /// Serialized value after the change.
+    ///
+    /// [`None`] if the component was removed or the entity was despawned.
+    pub new: Option<Bytes>,
+}
+
+/// Records changes of all replicated components since the last run.
+pub(super) fn record_changes(world: &mut World) {
+    let last_run = world.last_change_tick();
+    let this_run = world.read_change_tick();
+    let server_tick = **world.resource::<ServerTick>();
+    record(world, server_tick, last_run, this_run);
+}
+
+/// Records changes of all replicated components between the change ticks under the given tick.
+///
+/// The tick can be older than the current server tick, in which case changes
+/// are merged into the already recorded ticks.
+pub(crate) fn record(world: &mut World, tick: RepliconTick, last_run: Tick, this_run: Tick) {
+    world.resource_scope(|world, mut journal: Mut<ChangeJournal>| {
+        let mut query = world.query_filtered::<EntityRef, With<Replicated>>();
+        let server_tick = **world.resource::<ServerTick>();
+        let rules = world.resource::<ReplicationRules>();
+        let mut components: Vec<_> = rules
+            .iter()
+            .flat_map(|rule| rule.components.iter().copied())
+            .collect();
+        components.sort_unstable_by_key(|&(component_id, _)| component_id);
+        components.dedup_by_key(|&mut (component_id, _)| component_id);
+
+        let ChangeJournal {
+            ticks,
+            values,
+            max_ticks,
+        } = &mut *journal;
+
+        let mut changes = Vec::new();
+        values.retain(|&(entity, component_id), (fns_id, value)| {
+            let exists = world.get_entity(entity).is_ok_and(|entity| {
+                entity.contains::<Replicated>() && entity.contains_id(component_id)
+            });
+            if !exists {
+                changes.push(JournalEntry {
+                    entity,
+                    component_id,
+                    fns_id: *fns_id,
+                    old: Some(value.clone()),
+                    new: None,
+                });
+            }
+            exists
+        });
+
+        let registry = world.resource::<ReplicationRegistry>();
+        for entity in query.iter(world) {
+            for &(component_id, fns_id) in &components {
+                let Some(ticks) = entity.get_change_ticks_by_id(component_id) else {
+                    continue;
+                };
+                if !ticks.is_changed(last_run, this_run) {
+                    continue;
+                }
+
+                let (_, component_fns, rule_fns) = registry.get(fns_id);
+                let ctx = SerializeCtx {
+                    component_id,
+                    server_tick: tick,
+                    diff: false,
+                };
+                let ptr = entity
+                    .get_by_id(component_id)
+                    .expect("entity should contain the changed component");
+                let mut message = Vec::new();
+                // SAFETY: `component_fns`, `rule_fns` and `ptr` were created for the same component type.
+                let result = unsafe { component_fns.serialize(&ctx, rule_fns, ptr, &mut message) };
+                if let Err(e) = result {
+                    error!(
+                        "unable to serialize component `{component_id:?}` of `{}` for journal: {e}",
+                        entity.id()
+                    );
+                    continue;
+                }
+
+                let new = Bytes::from(message);
+                let old = values.insert((entity.id(), component_id), (fns_id, new.clone()));
+                let old = old.map(|(_, old)| old);
+                if old.as_ref() == Some(&new) {
+                    continue;
+                }
+
+                changes.push(JournalEntry {
+                    entity: entity.id(),
+                    component_id,
+                    fns_id,
+                    old,
+                    new: Some(new),
+                });
+            }
+        }
+
+        while ticks
+            .front()
+            .is_some_and(|journal_tick| server_tick - journal_tick.tick >= *max_ticks)
+        {
+            ticks.pop_front();
+        }
+
+        if changes.is_empty() {
+            return;
+        }
+
+        trace!("journaling {} changes for {tick:?}", changes.len());
+        match ticks
+            .iter()
+            .rposition(|journal_tick| journal_tick.tick <= tick)
+        {
+            Some(index) if ticks[index].tick == tick => ticks[index].changes.extend(changes),
+            Some(index) => ticks.insert(index + 1, JournalTick { tick, changes }),
+            None => ticks.push_front(JournalTick { tick, changes }),
+        }
+    });
+}
+
+/// Rollback for [`ChangeJournal`].
+pub trait ChangeJournalWorldExt {
+    /// Reverts components that pass the filter to their values from `ticks` ticks ago.
+    ///
+    /// Recorded changes after the target tick are undone from newest to oldest and
+    /// removed from the journal. Inserted components are removed and removed components
+    /// are inserted back. Despawned entities can't be restored and their changes are skipped.
+    ///
+    /// Reverted values are replicated to clients as regular changes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`ChangeJournal`] is missing.
+    fn rollback_changes(&mut self, ticks: u32, filter: impl Fn(ComponentId) -> bool);
+}
+
+impl ChangeJournalWorldExt for World {
+    fn rollback_changes(&mut self, ticks: u32, filter: impl Fn(ComponentId) -> bool) {
+        rollback(self, ticks, |_, change| filter(change.component_id));
+    }
+}
+
+/// Like [`ChangeJournalWorldExt::rollback_changes`], but filters individual changes.
+pub(crate) fn rollback(
+    world: &mut World,
+    ticks: u32,
+    filter: impl Fn(&World, &JournalEntry) -> bool,
+) {
+    world.resource_scope(|world, mut journal: Mut<ChangeJournal>| {
+        let server_tick = **world.resource::<ServerTick>();
+        let target_tick = server_tick - ticks;
+        debug!("rolling back changes to {target_tick:?}");
+
+        world.resource_scope(|world, registry: Mut<ReplicationRegistry>| {
+            let entity_markers = EntityMarkers::new(world.resource::<CommandMarkers>());
+            let mut entity_map = ServerEntityMap::default();
+            let mut queue = CommandQueue::default();
+
+            let ChangeJournal {
+                ticks: journal_ticks,
+                values,
+                ..
+            } = &mut *journal;
+
+            for journal_tick in journal_ticks.iter_mut().rev() {
+                if journal_tick.tick <= target_tick {
+                    break;
+                }
+
+                let (undone, kept) = journal_tick
+                    .changes
+                    .drain(..)
+                    .partition::<Vec<_>, _>(|change| filter(world, change));
+                journal_tick.changes = kept;
+
+                for change in undone.into_iter().rev() {
+                    if world.get_entity(change.entity).is_err() {
+                        continue;
+                    }
+
+                    let mut entity = DeferredEntity::new(world, change.entity);
+                    let mut commands = entity.commands(&mut queue);
+                    let (component_id, component_fns, rule_fns) = registry.get(change.fns_id);
+                    let key = (change.entity, component_id);
+                    match change.old {
+                        Some(old) => {
+                            let mut ctx = WriteCtx::new(
+                                &mut commands,
+                                &mut entity_map,
+                                component_id,
+                                server_tick,
+                            );
+                            ctx.ignore_mapping = true;
+
+                            // SAFETY: `rule_fns` and `component_fns` were created for the same type.
+                            let result = unsafe {
+                                component_fns.write(
+                                    &mut ctx,
+                                    rule_fns,
+                                    &entity_markers,
+                                    &mut entity,
+                                    &mut old.clone(),
+                                )
+                            };
+                            if let Err(e) = result {
+                                error!(
+                                    "unable to roll back component `{component_id:?}` of `{}`: {e}",
+                                    change.entity
+                                );
+                            }
+                            values.insert(key, (change.fns_id, old));
+                        }
+                        None => {
+                            let mut ctx = RemoveCtx {
+                                commands: &mut commands,
+                                component_id,
+                                message_tick: server_tick,
+                                final_value: false,
+                            };
+                            component_fns.remove(&mut ctx, &entity_markers, &mut entity);
+                            values.remove(&key);
+                        }
+                    }
+
+                    queue.apply(world);
+                }
+            }
+
+            journal_ticks.retain(|journal_tick| !journal_tick.changes.is_empty());
+        });
+    });
+}
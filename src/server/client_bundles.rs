@@ -0,0 +1,263 @@
+use bevy::{ecs::entity::Entities, prelude::*, utils::HashSet};
+
+use super::{ClientConnected, ClientDisconnected};
+use crate::core::{
+    replication::replicated_clients::{ReplicatedClients, VisibilityPolicy},
+    ClientId,
+};
+
+/// Spawning of per-client entities on connection.
+pub trait ClientBundleAppExt {
+    /// Registers a bundle that will be spawned on server for each connected client.
+    ///
+    /// Same as [`Self::add_client_bundle_with`], but uses [`ClientBundleConfig::default`].
+    fn add_client_bundle<B: Bundle>(&mut self, bundle: fn(ClientId) -> B) -> &mut Self {
+        self.add_client_bundle_with(ClientBundleConfig::default(), bundle)
+    }
+
+    /**
+    Registers a bundle that will be spawned on server for each connected client with a custom config.
+
+    The bundle is spawned when [`ClientConnected`] is triggered together with [`ClientOwner`]
+    that stores the ID of the client. Visibility is configured according to
+    [`ClientBundleConfig::visibility`] for all replicated clients, including the ones that connect later.
+    On disconnect the entity is handled according to [`ClientBundleConfig::on_leave`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        prelude::*,
+        server::client_bundles::{ClientBundleAppExt, ClientBundleConfig, ClientBundleVisibility},
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate::<Player>()
+        .replicate::<Inventory>()
+        .add_client_bundle(|client_id| (Replicated, Player(client_id)))
+        .add_client_bundle_with(
+            ClientBundleConfig {
+                visibility: ClientBundleVisibility::Owner,
+                ..Default::default()
+            },
+            |_| (Replicated, Inventory::default()),
+        );
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Player(ClientId);
+
+    #[derive(Component, Default, Deserialize, Serialize)]
+    struct Inventory(Vec<u32>);
+    ```
+    */
+    fn add_client_bundle_with<B: Bundle>(
+        &mut self,
+        config: ClientBundleConfig,
+        bundle: fn(ClientId) -> B,
+    ) -> &mut Self;
+}
+
+impl ClientBundleAppExt for App {
+    fn add_client_bundle_with<B: Bundle>(
+        &mut self,
+        config: ClientBundleConfig,
+        bundle: fn(ClientId) -> B,
+    ) -> &mut Self {
+        debug!(
+            "registering client bundle `{}` with {config:?}",
+            std::any::type_name::<B>()
+        );
+
+        let spawn = move |commands: &mut Commands, client_id| {
+            commands
+                .spawn((bundle(client_id), ClientOwner(client_id)))
+                .id()
+        };
+        self.world_mut()
+            .resource_mut::<ClientBundles>()
+            .registrations
+            .push((config, Box::new(spawn)));
+
+        self
+    }
+}
+
+/// Configuration for a bundle registered with [`ClientBundleAppExt::add_client_bundle_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClientBundleConfig {
+    /// Clients that will see the spawned entity.
+    pub visibility: ClientBundleVisibility,
+
+    /// What to do with the spawned entity when its client disconnects.
+    pub on_leave: LeavePolicy,
+}
+
+/// Visibility of an entity spawned by [`ClientBundleAppExt`].
+///
+/// Applied only if [`VisibilityPolicy`] is not [`VisibilityPolicy::All`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClientBundleVisibility {
+    /// Visible to all clients.
+    #[default]
+    All,
+
+    /// Visible only to the owning client.
+    Owner,
+
+    /// Visible to all clients except the owning one.
+    ExceptOwner,
+}
+
+impl ClientBundleVisibility {
+    fn is_visible(self, owner: ClientId, client_id: ClientId) -> bool {
+        match self {
+            ClientBundleVisibility::All => true,
+            ClientBundleVisibility::Owner => client_id == owner,
+            ClientBundleVisibility::ExceptOwner => client_id != owner,
+        }
+    }
+}
+
+/// What to do with an entity spawned by [`ClientBundleAppExt`] when its client disconnects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LeavePolicy {
+    /// Despawn the entity.
+    #[default]
+    Despawn,
+
+    /// Keep the entity, for example to restore it on reconnection.
+    ///
+    /// The entity keeps its [`ClientOwner`] and visibility configuration.
+    Keep,
+}
+
+/// ID of the client for which the entity was spawned by [`ClientBundleAppExt`].
+#[derive(Component, Deref, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientOwner(pub ClientId);
+
+type SpawnFn = Box<dyn Fn(&mut Commands, ClientId) -> Entity + Send + Sync>;
+
+/// Registered bundles and entities spawned from them.
+#[derive(Resource, Default)]
+pub(super) struct ClientBundles {
+    registrations: Vec<(ClientBundleConfig, SpawnFn)>,
+
+    /// Spawned entities with their owners.
+    entities: Vec<ClientBundleEntity>,
+
+    /// Entities spawned since the last visibility configuration.
+    new_entities: Vec<Entity>,
+
+    /// Replicated clients for which visibility of all entities is configured.
+    configured_clients: HashSet<ClientId>,
+}
+
+impl ClientBundles {
+    pub(super) fn clear(&mut self) {
+        self.entities.clear();
+        self.new_entities.clear();
+        self.configured_clients.clear();
+    }
+}
+
+struct ClientBundleEntity {
+    entity: Entity,
+    owner: ClientId,
+    config: ClientBundleConfig,
+}
+
+pub(super) fn spawn_bundles(
+    trigger: Trigger<ClientConnected>,
+    mut commands: Commands,
+    mut bundles: ResMut<ClientBundles>,
+) {
+    let bundles = &mut *bundles;
+    for (config, spawn) in &bundles.registrations {
+        let entity = spawn(&mut commands, trigger.client_id);
+        debug!("spawning `{entity}` for `{:?}`", trigger.client_id);
+        bundles.entities.push(ClientBundleEntity {
+            entity,
+            owner: trigger.client_id,
+            config: *config,
+        });
+        bundles.new_entities.push(entity);
+    }
+}
+
+pub(super) fn despawn_bundles(
+    trigger: Trigger<ClientDisconnected>,
+    mut commands: Commands,
+    mut bundles: ResMut<ClientBundles>,
+) {
+    bundles.configured_clients.remove(&trigger.client_id);
+    bundles.entities.retain(|bundle_entity| {
+        if bundle_entity.owner != trigger.client_id
+            || bundle_entity.config.on_leave == LeavePolicy::Keep
+        {
+            return true;
+        }
+
+        debug!(
+            "despawning `{}` for `{:?}`",
+            bundle_entity.entity, trigger.client_id
+        );
+        commands.entity(bundle_entity.entity).try_despawn();
+        false
+    });
+}
+
+/// Configures visibility of spawned entities for new clients and of new entities for configured clients.
+pub(super) fn configure_visibility(
+    entities: &Entities,
+    mut bundles: ResMut<ClientBundles>,
+    mut replicated_clients: ResMut<ReplicatedClients>,
+) {
+    if let VisibilityPolicy::All = replicated_clients.visibility_policy() {
+        bundles.new_entities.clear();
+        return;
+    }
+
+    let bundles = &mut *bundles;
+    bundles
+        .entities
+        .retain(|bundle_entity| entities.contains(bundle_entity.entity));
+
+    let new_entities: Vec<_> = bundles
+        .new_entities
+        .drain(..)
+        .filter_map(|entity| {
+            bundles
+                .entities
+                .iter()
+                .find(|bundle_entity| bundle_entity.entity == entity)
+        })
+        .collect();
+
+    for client in replicated_clients.iter_mut() {
+        let client_id = client.id();
+        if bundles.configured_clients.insert(client_id) {
+            for bundle_entity in &bundles.entities {
+                let visible = bundle_entity
+                    .config
+                    .visibility
+                    .is_visible(bundle_entity.owner, client_id);
+                client
+                    .visibility_mut()
+                    .set_visibility(bundle_entity.entity, visible);
+            }
+        } else {
+            for bundle_entity in &new_entities {
+                let visible = bundle_entity
+                    .config
+                    .visibility
+                    .is_visible(bundle_entity.owner, client_id);
+                client
+                    .visibility_mut()
+                    .set_visibility(bundle_entity.entity, visible);
+            }
+        }
+    }
+}
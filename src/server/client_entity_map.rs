@@ -1,6 +1,11 @@
 use bevy::{prelude::*, utils::HashMap};
 
-use crate::core::ClientId;
+use super::server_tick::ServerTick;
+use crate::core::{
+    replicon_tick::RepliconTick,
+    server_entity_map::{MappingConflict, MappingConflictPolicy},
+    ClientId,
+};
 
 /**
 A resource that exists on the server for mapping server entities to
@@ -68,26 +73,260 @@ client entity.
 
 If client's original entity is not found, a new entity will be spawned on the client,
 just the same as when no client entity is provided.
+
+Mappings are sent only together with the replication of the server entity, so they stay pending
+until the server entity is replicated and visible to the client. Pending mappings that weren't sent
+within [`ServerPlugin::mappings_timeout`](super::ServerPlugin::mappings_timeout) ticks are discarded
+and reported via [`ClientMappingExpired`].
 **/
-#[derive(Resource, Debug, Default, Deref)]
-pub struct ClientEntityMap(pub(super) HashMap<ClientId, Vec<ClientMapping>>);
+#[derive(Resource, Debug)]
+pub struct ClientEntityMap {
+    mappings: HashMap<ClientId, Vec<PendingMapping>>,
+
+    /// Number of ticks after which pending mappings expire.
+    timeout: u32,
+
+    /// Last server tick seen by [`expire_mappings`].
+    tick: RepliconTick,
+
+    conflict_policy: MappingConflictPolicy,
+
+    /// Rejected conflicts that will be emitted as [`ClientMappingConflict`] events.
+    conflicts: Vec<ClientMappingConflict>,
+}
 
 impl ClientEntityMap {
+    pub(super) fn new(timeout: u32) -> Self {
+        Self {
+            mappings: Default::default(),
+            timeout,
+            tick: Default::default(),
+            conflict_policy: Default::default(),
+            conflicts: Default::default(),
+        }
+    }
+
     /// Registers `mapping` for a client entity pre-spawned by the specified client.
     ///
     /// This will be sent as part of replication data and added to the client's
     /// [`ServerEntityMap`](crate::core::server_entity_map::ServerEntityMap).
-    pub fn insert(&mut self, client_id: ClientId, mapping: ClientMapping) {
+    ///
+    /// If the server entity or the client entity is already present in a pending mapping
+    /// for this client, the configured [`MappingConflictPolicy`] is applied.
+    /// Mappings that were already sent are not checked.
+    ///
+    /// Returns `true` if the mapping is pending after the call.
+    pub fn insert(&mut self, client_id: ClientId, mapping: ClientMapping) -> bool {
+        self.insert_pending(client_id, mapping, false)
+    }
+
+    /// Like [`Self::insert`], but for an entity that the client already has with the same data.
+    ///
+    /// Such mappings are allowed to be sent without entity data.
+    pub(crate) fn insert_baseline(&mut self, client_id: ClientId, mapping: ClientMapping) -> bool {
+        self.insert_pending(client_id, mapping, true)
+    }
+
+    fn insert_pending(
+        &mut self,
+        client_id: ClientId,
+        mapping: ClientMapping,
+        baseline: bool,
+    ) -> bool {
         debug!(
             "mapping `{}` to `{}` for `{client_id:?}`",
             mapping.client_entity, mapping.server_entity
         );
-        self.0.entry(client_id).or_default().push(mapping);
+        let mappings = self.mappings.entry(client_id).or_default();
+        let conflict = MappingConflict {
+            server_entity: mapping.server_entity,
+            client_entity: mapping.client_entity,
+            mapped_client: mappings
+                .iter()
+                .map(|pending| pending.mapping)
+                .find(|other| {
+                    other.server_entity == mapping.server_entity
+                        && other.client_entity != mapping.client_entity
+                })
+                .map(|other| other.client_entity),
+            mapped_server: mappings
+                .iter()
+                .map(|pending| pending.mapping)
+                .find(|other| {
+                    other.client_entity == mapping.client_entity
+                        && other.server_entity != mapping.server_entity
+                })
+                .map(|other| other.server_entity),
+        };
+
+        if conflict.mapped_client.is_some() || conflict.mapped_server.is_some() {
+            if !self.conflict_policy.resolve(&conflict) {
+                self.conflicts.push(ClientMappingConflict {
+                    client_id,
+                    conflict,
+                });
+                return false;
+            }
+
+            mappings.retain(|pending| {
+                pending.mapping.server_entity != mapping.server_entity
+                    && pending.mapping.client_entity != mapping.client_entity
+            });
+        } else if mappings.iter().any(|pending| {
+            pending.mapping.server_entity == mapping.server_entity
+                && pending.mapping.client_entity == mapping.client_entity
+        }) {
+            warn!(
+                "ignoring duplicate mapping `{}` to `{}` for `{client_id:?}`",
+                mapping.client_entity, mapping.server_entity
+            );
+            return true;
+        }
+
+        mappings.push(PendingMapping {
+            mapping,
+            tick: self.tick,
+            baseline,
+        });
+
+        true
+    }
+
+    /// Returns the policy for inserting mappings that conflict with pending ones.
+    pub fn conflict_policy(&self) -> MappingConflictPolicy {
+        self.conflict_policy
+    }
+
+    /// Sets the policy for inserting mappings that conflict with pending ones.
+    pub fn set_conflict_policy(&mut self, policy: MappingConflictPolicy) {
+        self.conflict_policy = policy;
+    }
+
+    /// Returns mappings that weren't sent to the specified client yet.
+    pub fn pending(&self, client_id: ClientId) -> &[PendingMapping] {
+        self.mappings
+            .get(&client_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over all clients with their pending mappings.
+    pub fn iter(&self) -> impl Iterator<Item = (ClientId, &[PendingMapping])> {
+        self.mappings
+            .iter()
+            .filter(|(_, mappings)| !mappings.is_empty())
+            .map(|(&client_id, mappings)| (client_id, mappings.as_slice()))
+    }
+
+    /// Returns the number of ticks after which pending mappings expire.
+    pub fn timeout(&self) -> u32 {
+        self.timeout
     }
+
+    /// Sets the number of ticks after which pending mappings expire.
+    ///
+    /// See also [`ServerPlugin::mappings_timeout`](super::ServerPlugin::mappings_timeout).
+    pub fn set_timeout(&mut self, timeout: u32) {
+        self.timeout = timeout;
+    }
+
+    /// Removes and returns mappings for which `is_ready` returns `true`.
+    pub(super) fn drain_ready(
+        &mut self,
+        client_id: ClientId,
+        mut is_ready: impl FnMut(&ClientMapping) -> bool,
+    ) -> Vec<PendingMapping> {
+        let mut ready = Vec::new();
+        if let Some(mappings) = self.mappings.get_mut(&client_id) {
+            mappings.retain(|pending| {
+                if is_ready(&pending.mapping) {
+                    ready.push(*pending);
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        ready
+    }
+
+    pub(super) fn remove_client(&mut self, client_id: ClientId) {
+        self.mappings.remove(&client_id);
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.mappings.clear();
+    }
+}
+
+/// Removes mappings that stayed pending for longer than the timeout.
+pub(super) fn expire_mappings(
+    mut entity_map: ResMut<ClientEntityMap>,
+    server_tick: Res<ServerTick>,
+    mut expired_events: EventWriter<ClientMappingExpired>,
+) {
+    let entity_map = &mut *entity_map;
+    entity_map.tick = **server_tick;
+    for (&client_id, mappings) in &mut entity_map.mappings {
+        mappings.retain(|pending| {
+            if **server_tick - pending.tick <= entity_map.timeout {
+                return true;
+            }
+
+            debug!(
+                "mapping `{}` to `{}` for `{client_id:?}` expired",
+                pending.mapping.client_entity, pending.mapping.server_entity
+            );
+            expired_events.send(ClientMappingExpired {
+                client_id,
+                mapping: pending.mapping,
+            });
+            false
+        });
+    }
+}
+
+/// Emits conflicts rejected by [`ClientEntityMap`].
+pub(super) fn send_conflicts(
+    mut entity_map: ResMut<ClientEntityMap>,
+    mut conflict_events: EventWriter<ClientMappingConflict>,
+) {
+    conflict_events.send_batch(entity_map.bypass_change_detection().conflicts.drain(..));
+}
+
+/// A mapping from [`ClientEntityMap`] that wasn't sent yet.
+#[derive(Clone, Copy, Debug)]
+pub struct PendingMapping {
+    pub mapping: ClientMapping,
+
+    /// Server tick at which the mapping was registered.
+    pub tick: RepliconTick,
+
+    /// Whether the client already has the entity with the same data.
+    pub(super) baseline: bool,
+}
+
+/// An event that is emitted on server when a pending mapping expires.
+///
+/// It happens when the server entity from the mapping wasn't replicated to the client
+/// within [`ClientEntityMap::timeout`] ticks, e.g. because it was never spawned or
+/// stayed hidden from the client.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ClientMappingExpired {
+    pub client_id: ClientId,
+    pub mapping: ClientMapping,
+}
+
+/// An event that is emitted on server when a mapping was rejected due to [`MappingConflictPolicy`].
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ClientMappingConflict {
+    pub client_id: ClientId,
+    pub conflict: MappingConflict,
 }
 
 /// Stores the server entity corresponding to a client's pre-spawned entity.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct ClientMapping {
     pub server_entity: Entity,
     pub client_entity: Entity,
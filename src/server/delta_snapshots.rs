@@ -0,0 +1,173 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    ecs::{component::Tick, entity::EntityHashMap},
+    prelude::*,
+    ptr::Ptr,
+};
+
+use crate::core::{
+    replication::replication_registry::{
+        component_fns::ComponentFns, ctx::SerializeCtx, rule_fns::UntypedRuleFns, FnsId,
+    },
+    replicon_tick::RepliconTick,
+};
+
+/// Maximum number of stored values for each component.
+///
+/// If a client acknowledged an older value, the full component will be sent.
+const MAX_SNAPSHOTS: usize = 32;
+
+/// Maximum number of stored send ticks.
+const MAX_RUNS: usize = 64;
+
+/// Serialized values of components replicated with
+/// [`RuleFns::with_delta`](crate::core::replication::replication_registry::rule_fns::RuleFns::with_delta)
+/// from previous ticks.
+///
+/// Baselines for each client are derived from their mutation ticks, which
+/// are advanced only on acknowledgment. Since the client received all changes up to its
+/// mutation tick, it has the value that was current on that tick.
+#[derive(Default)]
+pub(crate) struct DeltaSnapshots {
+    /// Stored values for each component with delta functions on an entity.
+    entities: EntityHashMap<Vec<(FnsId, ComponentSnapshots)>>,
+
+    /// Replicon ticks of the recent sends mapped from their system ticks.
+    runs: VecDeque<(Tick, RepliconTick)>,
+
+    /// Buffer for serializing values before comparison.
+    buffer: Vec<u8>,
+}
+
+impl DeltaSnapshots {
+    /// Registers a new send with its system tick and replicon tick.
+    pub(super) fn start_run(&mut self, tick: Tick, server_tick: RepliconTick) {
+        if self.runs.len() >= MAX_RUNS {
+            self.runs.pop_front();
+        }
+        self.runs.push_back((tick, server_tick));
+    }
+
+    /// Serializes a component and stores its value if it differs from the previous one.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr`, `rule_fns` and `component_fns` were created for the same component type.
+    pub(super) unsafe fn update(
+        &mut self,
+        (entity, fns_id): (Entity, FnsId),
+        (rule_fns, component_fns): (&UntypedRuleFns, &ComponentFns),
+        ctx: &SerializeCtx,
+        ptr: Ptr,
+        tick: Tick,
+    ) -> postcard::Result<()> {
+        self.buffer.clear();
+        component_fns.serialize(ctx, rule_fns, ptr, &mut self.buffer)?;
+
+        let components = self.entities.entry(entity).or_default();
+        let index = match components.iter().position(|&(id, _)| id == fns_id) {
+            Some(index) => index,
+            None => {
+                components.push((fns_id, Default::default()));
+                components.len() - 1
+            }
+        };
+        let (_, component) = &mut components[index];
+        component.checked = tick;
+        let snapshots = &mut component.snapshots;
+        if snapshots
+            .back()
+            .is_some_and(|snapshot| snapshot.bytes == self.buffer)
+        {
+            return Ok(());
+        }
+
+        let mut snapshot = if snapshots.len() >= MAX_SNAPSHOTS {
+            snapshots.pop_front().unwrap_or_default()
+        } else {
+            Default::default()
+        };
+        snapshot.tick = tick;
+        snapshot.bytes.clone_from(&self.buffer);
+        snapshots.push_back(snapshot);
+
+        Ok(())
+    }
+
+    /// Returns the tick of the last [`Self::update`] call for a component.
+    pub(super) fn checked_tick(&self, entity: Entity, fns_id: FnsId) -> Option<Tick> {
+        self.get(entity, fns_id).map(|component| component.checked)
+    }
+
+    /// Returns the current serialized value of a component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component wasn't updated with [`Self::update`].
+    pub(super) fn current(&self, entity: Entity, fns_id: FnsId) -> &[u8] {
+        self.get(entity, fns_id)
+            .and_then(|component| component.snapshots.back())
+            .map(|snapshot| &*snapshot.bytes)
+            .expect("component should be updated before reading")
+    }
+
+    /// Returns the replicon tick of the send on `mutation_tick` with the value that was current on it.
+    ///
+    /// Returns [`None`] if the send or the value is too old.
+    pub(super) fn baseline(
+        &self,
+        entity: Entity,
+        fns_id: FnsId,
+        mutation_tick: Tick,
+        this_run: Tick,
+    ) -> Option<(RepliconTick, &[u8])> {
+        let &(_, server_tick) = self
+            .runs
+            .iter()
+            .rev()
+            .find(|&&(tick, _)| tick == mutation_tick)?;
+        let snapshot = self
+            .get(entity, fns_id)?
+            .snapshots
+            .iter()
+            .rev()
+            .find(|snapshot| !snapshot.tick.is_newer_than(mutation_tick, this_run))?;
+
+        Some((server_tick, &snapshot.bytes))
+    }
+
+    /// Removes values of all components for a despawned entity.
+    pub(super) fn remove_entity(&mut self, entity: Entity) {
+        self.entities.remove(&entity);
+    }
+
+    fn get(&self, entity: Entity, fns_id: FnsId) -> Option<&ComponentSnapshots> {
+        self.entities.get(&entity).and_then(|components| {
+            components
+                .iter()
+                .find(|&&(id, _)| id == fns_id)
+                .map(|(_, component)| component)
+        })
+    }
+}
+
+#[derive(Default)]
+struct ComponentSnapshots {
+    /// Tick on which the value was serialized last time.
+    ///
+    /// Used to detect changes from ticks in which the entity wasn't processed.
+    checked: Tick,
+
+    /// Distinct values in the order of change.
+    snapshots: VecDeque<Snapshot>,
+}
+
+#[derive(Default)]
+struct Snapshot {
+    /// Tick on which the value was changed.
+    tick: Tick,
+
+    /// Serialized value.
+    bytes: Vec<u8>,
+}
@@ -3,13 +3,16 @@ use bevy::{
     prelude::*,
 };
 
-use super::{server_tick::ServerTick, ServerSet};
+use super::{server_tick::ServerTick, ClientConnected, ClientDisconnected, ServerSet};
 use crate::core::{
+    channels::RepliconChannels,
     common_conditions::*,
     connected_clients::ConnectedClients,
+    entity_serde::EntityObfuscation,
     event::{
         ctx::{ServerReceiveCtx, ServerSendCtx},
         event_registry::EventRegistry,
+        rate_limit::ClientEventBudgets,
         server_event::BufferedServerEvents,
     },
     replication::replicated_clients::ReplicatedClients,
@@ -18,12 +21,23 @@ use crate::core::{
 
 /// Sending events from the server to clients.
 ///
-/// Requires [`ServerPlugin`](super::ServerPlugin).
+/// Requires [`ServerSessionPlugin`](super::ServerSessionPlugin).
 /// Can be disabled for apps that act only as clients.
+///
+/// Without [`ServerReplicationPlugin`](super::ServerReplicationPlugin) all events are sent
+/// immediately and events sent with [`SendMode::Observers`](crate::core::event::server_event::SendMode::Observers)
+/// are ignored since there is no visibility.
 pub struct ServerEventPlugin;
 
 impl Plugin for ServerEventPlugin {
-    fn build(&self, _app: &mut App) {}
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BufferedServerEvents>()
+            .init_resource::<ClientEventBudgets>()
+            .add_observer(exclude_connected)
+            .add_observer(remove_budgets)
+            .add_observer(remove_held)
+            .add_systems(PostUpdate, reset.run_if(server_just_stopped));
+    }
 
     fn finish(&self, app: &mut App) {
         // Construct systems dynamically after all plugins initialization
@@ -44,6 +58,8 @@ impl Plugin for ServerEventPlugin {
             ParamBuilder,
             ParamBuilder,
             ParamBuilder,
+            ParamBuilder,
+            ParamBuilder,
         )
             .build_state(app.world_mut())
             .build_system(send_or_buffer);
@@ -57,6 +73,10 @@ impl Plugin for ServerEventPlugin {
             ParamBuilder,
             ParamBuilder,
             ParamBuilder,
+            ParamBuilder,
+            ParamBuilder,
+            ParamBuilder,
+            ParamBuilder,
         )
             .build_state(app.world_mut())
             .build_system(receive);
@@ -105,7 +125,8 @@ impl Plugin for ServerEventPlugin {
                     send_or_buffer.run_if(server_running),
                     send_buffered
                         .run_if(server_running)
-                        .run_if(resource_changed::<ServerTick>),
+                        .run_if(resource_changed::<ServerTick>)
+                        .run_if(super::tick_batch_sent),
                     resend_locally.run_if(server_or_singleplayer),
                 )
                     .chain()
@@ -121,11 +142,17 @@ fn send_or_buffer(
     mut buffered_events: ResMut<BufferedServerEvents>,
     registry: Res<AppTypeRegistry>,
     connected_clients: Res<ConnectedClients>,
+    replicated_clients: Option<Res<ReplicatedClients>>,
     event_registry: Res<EventRegistry>,
+    obfuscation: Option<Res<EntityObfuscation>>,
 ) {
-    buffered_events.start_tick();
+    if replicated_clients.is_some() {
+        buffered_events.start_tick();
+    }
     let mut ctx = ServerSendCtx {
         registry: &registry.read(),
+        entity_permutation: None,
+        obfuscation: obfuscation.as_deref().copied(),
     };
 
     for event in event_registry.iter_server_events() {
@@ -140,6 +167,7 @@ fn send_or_buffer(
                 &server_events,
                 &mut server,
                 &connected_clients,
+                replicated_clients.as_deref(),
                 &mut buffered_events,
             );
         }
@@ -149,30 +177,79 @@ fn send_or_buffer(
 fn send_buffered(
     mut server: ResMut<RepliconServer>,
     mut buffered_events: ResMut<BufferedServerEvents>,
+    connected_clients: Res<ConnectedClients>,
     replicated_clients: Res<ReplicatedClients>,
 ) {
     buffered_events
-        .send_all(&mut server, &replicated_clients)
+        .send_all(&mut server, &connected_clients, &replicated_clients)
         .expect("buffered server events should send");
 }
 
+fn exclude_connected(
+    trigger: Trigger<ClientConnected>,
+    mut buffered_events: ResMut<BufferedServerEvents>,
+) {
+    buffered_events.exclude_client(trigger.client_id);
+}
+
+fn remove_budgets(trigger: Trigger<ClientDisconnected>, mut budgets: ResMut<ClientEventBudgets>) {
+    budgets.remove_client(trigger.client_id);
+}
+
+fn remove_held(
+    trigger: Trigger<ClientDisconnected>,
+    mut buffered_events: ResMut<BufferedServerEvents>,
+) {
+    buffered_events.remove_client(trigger.client_id);
+}
+
+fn reset(
+    mut buffered_events: ResMut<BufferedServerEvents>,
+    mut budgets: ResMut<ClientEventBudgets>,
+) {
+    buffered_events.clear();
+    budgets.clear();
+}
+
 fn receive(
     mut client_events: FilteredResourcesMut,
     mut server: ResMut<RepliconServer>,
+    mut budgets: ResMut<ClientEventBudgets>,
     registry: Res<AppTypeRegistry>,
     event_registry: Res<EventRegistry>,
+    channels: Res<RepliconChannels>,
+    time: Res<Time>,
+    obfuscation: Option<Res<EntityObfuscation>>,
 ) {
     let mut ctx = ServerReceiveCtx {
         registry: &registry.read(),
+        entity_permutation: None,
+        obfuscation: obfuscation.as_deref().copied(),
     };
 
+    for &channel_id in event_registry
+        .ordered_client_channels()
+        .iter()
+        .chain(channels.shared_client_channels())
+    {
+        server.split_ordered_channel(channel_id);
+    }
+
     for event in event_registry.iter_client_events() {
         let client_events = client_events
             .get_mut_by_id(event.client_events_id())
             .expect("client events resource should be accessible");
 
         // SAFETY: passed pointer was obtained using this event data.
-        unsafe { event.receive(&mut ctx, client_events.into_inner(), &mut server) };
+        unsafe {
+            event.receive(
+                &mut ctx,
+                client_events.into_inner(),
+                &mut server,
+                &mut budgets,
+                time.elapsed(),
+            )
+        };
     }
 }
 
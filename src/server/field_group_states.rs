@@ -0,0 +1,118 @@
+use bevy::{
+    ecs::{component::Tick, entity::EntityHashMap},
+    prelude::*,
+    ptr::Ptr,
+};
+
+use crate::core::replication::replication_registry::{
+    component_fns::ComponentFns, rule_fns::UntypedRuleFns, FnsId,
+};
+
+/// Last serialized values of field groups for components replicated with
+/// [`RuleFns::field_groups`](crate::core::replication::replication_registry::rule_fns::RuleFns::field_groups).
+///
+/// Used to detect which groups changed since the tick acknowledged by a client.
+#[derive(Default)]
+pub(crate) struct FieldGroupStates {
+    /// Group states for each component with field groups on an entity.
+    entities: EntityHashMap<Vec<(FnsId, Vec<FieldGroupState>)>>,
+
+    /// Buffer for serializing groups before comparison.
+    buffer: Vec<u8>,
+}
+
+impl FieldGroupStates {
+    /// Serializes all groups of a component and marks groups that differ from the previous values as changed on `tick`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr`, `rule_fns` and `component_fns` were created for the same component type.
+    pub(super) unsafe fn update(
+        &mut self,
+        (entity, fns_id): (Entity, FnsId),
+        (rule_fns, component_fns): (&UntypedRuleFns, &ComponentFns),
+        ptr: Ptr,
+        groups: u8,
+        tick: Tick,
+    ) -> postcard::Result<()> {
+        let components = self.entities.entry(entity).or_default();
+        let index = match components.iter().position(|&(id, _)| id == fns_id) {
+            Some(index) => index,
+            None => {
+                components.push((fns_id, Vec::with_capacity(groups.into())));
+                components.len() - 1
+            }
+        };
+        let (_, states) = &mut components[index];
+        states.resize_with(groups.into(), || FieldGroupState {
+            tick,
+            bytes: Vec::new(),
+        });
+
+        for (group, state) in states.iter_mut().enumerate() {
+            self.buffer.clear();
+            component_fns.serialize_group(rule_fns, ptr, group as u8, &mut self.buffer)?;
+            if state.bytes != self.buffer {
+                state.tick = tick;
+                state.bytes.clone_from(&self.buffer);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the component was updated at least once.
+    pub(super) fn contains(&self, entity: Entity, fns_id: FnsId) -> bool {
+        self.get(entity, fns_id).is_some()
+    }
+
+    /// Returns a mask of groups that changed after `last_tick` and their serialized values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component wasn't updated with [`Self::update`].
+    pub(super) fn changes(
+        &self,
+        entity: Entity,
+        fns_id: FnsId,
+        last_tick: Tick,
+        this_run: Tick,
+    ) -> (u64, impl Iterator<Item = &[u8]>) {
+        let states = self
+            .get(entity, fns_id)
+            .expect("field groups should be updated before reading changes");
+        let is_changed =
+            move |state: &&FieldGroupState| state.tick.is_newer_than(last_tick, this_run);
+
+        let mask = states
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| is_changed(state))
+            .fold(0, |mask, (group, _)| mask | (1 << group));
+        let bytes = states.iter().filter(is_changed).map(|state| &*state.bytes);
+
+        (mask, bytes)
+    }
+
+    /// Removes states of all components for a despawned entity.
+    pub(super) fn remove_entity(&mut self, entity: Entity) {
+        self.entities.remove(&entity);
+    }
+
+    fn get(&self, entity: Entity, fns_id: FnsId) -> Option<&[FieldGroupState]> {
+        self.entities.get(&entity).and_then(|components| {
+            components
+                .iter()
+                .find(|&&(id, _)| id == fns_id)
+                .map(|(_, states)| &**states)
+        })
+    }
+}
+
+struct FieldGroupState {
+    /// Tick on which the group was changed.
+    tick: Tick,
+
+    /// Serialized group.
+    bytes: Vec<u8>,
+}
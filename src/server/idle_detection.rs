@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{ClientConnected, ClientDisconnected, ServerSet};
+use crate::core::{
+    common_conditions::server_running, event::client_event::FromClient,
+    replication::replicated_clients::ReplicatedClients, ClientId,
+};
+
+/// Opt-in detection of idle clients on server.
+///
+/// Insert this resource to enable the detection and mark client events that count as activity
+/// using [`ClientActivityAppExt::track_activity`]. If none of them is received from a client
+/// for longer than [`Self::threshold`], the client is considered idle and [`ClientIdle`] is emitted.
+/// When a tracked event is received again, [`ClientActive`] is emitted.
+///
+/// Idle clients receive mutations only every [`Self::idle_mutations_interval`] ticks.
+/// For other downgrades, like a smaller visibility radius, react to the emitted events.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     prelude::*,
+///     server::idle_detection::{ClientActivityAppExt, IdleDetection},
+/// };
+/// use serde::{Deserialize, Serialize};
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(RepliconPlugins);
+/// app.insert_resource(IdleDetection::new(Duration::from_secs(30)))
+///     .add_client_event::<MoveInput>(ChannelKind::Ordered)
+///     .track_activity::<MoveInput>();
+///
+/// #[derive(Event, Deserialize, Serialize)]
+/// struct MoveInput(Vec2);
+/// ```
+#[derive(Resource, Debug)]
+pub struct IdleDetection {
+    /// Time without tracked events after which a client is considered idle.
+    pub threshold: Duration,
+
+    /// Send mutations to idle clients only on every N-th tick.
+    ///
+    /// Skipped mutations are not lost, they will be sent with the next mutate message.
+    /// Set to 1 to disable the downgrade.
+    ///
+    /// By default it's 4.
+    pub idle_mutations_interval: u32,
+
+    clients: HashMap<ClientId, ActivityState>,
+}
+
+impl IdleDetection {
+    /// Creates a new instance with the specified threshold.
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            idle_mutations_interval: 4,
+            clients: Default::default(),
+        }
+    }
+
+    /// Returns `true` if the client didn't send tracked events for longer than [`Self::threshold`].
+    pub fn is_idle(&self, client_id: ClientId) -> bool {
+        self.clients.get(&client_id).is_some_and(|state| state.idle)
+    }
+
+    /// Returns the time when the last tracked event was received from the client.
+    ///
+    /// Initialized with the connection time.
+    pub fn last_active(&self, client_id: ClientId) -> Option<Duration> {
+        self.clients.get(&client_id).map(|state| state.last_active)
+    }
+
+    /// Returns an iterator over idle clients.
+    pub fn iter_idle(&self) -> impl Iterator<Item = ClientId> + '_ {
+        self.clients
+            .iter()
+            .filter(|(_, state)| state.idle)
+            .map(|(&client_id, _)| client_id)
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.clients.clear();
+    }
+}
+
+#[derive(Debug)]
+struct ActivityState {
+    /// Time when the last tracked event was received.
+    last_active: Duration,
+
+    /// Whether [`ClientIdle`] was emitted without the following [`ClientActive`].
+    idle: bool,
+}
+
+/// Marking of client events that count as activity for [`IdleDetection`].
+pub trait ClientActivityAppExt {
+    /// Resets idle time of a client when it sends event `E`.
+    ///
+    /// The event should be registered as a client event.
+    fn track_activity<E: Event>(&mut self) -> &mut Self;
+}
+
+impl ClientActivityAppExt for App {
+    fn track_activity<E: Event>(&mut self) -> &mut Self {
+        debug!(
+            "tracking `{}` as client activity",
+            std::any::type_name::<E>()
+        );
+
+        self.add_systems(
+            PreUpdate,
+            mark_active::<E>
+                .after(ServerSet::Receive)
+                .run_if(server_running)
+                .run_if(resource_exists::<IdleDetection>),
+        )
+    }
+}
+
+pub(super) fn track_client(
+    trigger: Trigger<ClientConnected>,
+    time: Res<Time>,
+    detection: Option<ResMut<IdleDetection>>,
+) {
+    if let Some(mut detection) = detection {
+        detection.clients.insert(
+            trigger.client_id,
+            ActivityState {
+                last_active: time.elapsed(),
+                idle: false,
+            },
+        );
+    }
+}
+
+pub(super) fn untrack_client(
+    trigger: Trigger<ClientDisconnected>,
+    detection: Option<ResMut<IdleDetection>>,
+) {
+    if let Some(mut detection) = detection {
+        detection.clients.remove(&trigger.client_id);
+    }
+}
+
+fn mark_active<E: Event>(
+    mut events: EventReader<FromClient<E>>,
+    time: Res<Time>,
+    mut detection: ResMut<IdleDetection>,
+) {
+    for &FromClient { client_id, .. } in events.read() {
+        if let Some(state) = detection.clients.get_mut(&client_id) {
+            state.last_active = time.elapsed();
+        }
+    }
+}
+
+/// Updates idle status of clients and applies the downgrade.
+pub(super) fn detect_idle(
+    mut idle_events: EventWriter<ClientIdle>,
+    mut active_events: EventWriter<ClientActive>,
+    time: Res<Time>,
+    mut detection: ResMut<IdleDetection>,
+    mut replicated_clients: Option<ResMut<ReplicatedClients>>,
+) {
+    let IdleDetection {
+        threshold,
+        idle_mutations_interval,
+        clients,
+    } = &mut *detection;
+
+    for (&client_id, state) in clients {
+        let idle = time.elapsed() - state.last_active > *threshold;
+        if idle != state.idle {
+            state.idle = idle;
+            if idle {
+                debug!("`{client_id:?}` became idle");
+                idle_events.send(ClientIdle { client_id });
+            } else {
+                debug!("`{client_id:?}` became active");
+                active_events.send(ClientActive { client_id });
+            }
+        }
+
+        // Apply on each run since replication may be started after the client became idle.
+        if let Some(replicated_clients) = &mut replicated_clients {
+            let interval = if idle { *idle_mutations_interval } else { 1 };
+            if replicated_clients
+                .get_client(client_id)
+                .is_some_and(|client| client.mutations_interval() != interval)
+            {
+                replicated_clients
+                    .client_mut(client_id)
+                    .set_mutations_interval(interval);
+            }
+        }
+    }
+}
+
+/// An event that emitted on server when a client stops sending tracked events.
+///
+/// See [`IdleDetection`] for details.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientIdle {
+    /// The idle client.
+    pub client_id: ClientId,
+}
+
+/// An event that emitted on server when an idle client sends a tracked event.
+///
+/// See [`IdleDetection`] for details.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientActive {
+    /// The client that became active.
+    pub client_id: ClientId,
+}
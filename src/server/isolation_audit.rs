@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+
+use crate::core::{
+    connected_clients::ConnectedClients,
+    replication::{replicated_clients::ReplicatedClients, Replicated},
+    ClientId,
+};
+
+/// Opt-in detector of entities that are visible to clients outside of their [`IsolationDomain`].
+///
+/// Useful for multi-tenant servers that host multiple independent sessions in one world.
+/// Insert [`IsolationDomain`] into client entities (see [`ConnectedClients::entity`])
+/// and into replicated entities. An entity with a domain should be visible only to clients
+/// from the same domain. Entities without a domain are not checked.
+///
+/// Insert this resource to enable the detection. Checked on each replication tick
+/// right before packets are sent to the messaging backend, so a leak can be caught
+/// before it reaches the client. Detected leaks are logged as errors and available via [`Self::leaks`].
+///
+/// The check relies on [`ClientVisibility`](crate::core::replication::replicated_clients::client_visibility::ClientVisibility),
+/// so with [`VisibilityPolicy::All`](crate::core::replication::replicated_clients::VisibilityPolicy::All)
+/// any entity with a domain will be reported for clients from other domains.
+///
+/// # Examples
+///
+/// Fail tests on any leak:
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::server::isolation_audit::IsolationAudit;
+///
+/// # let mut app = App::new();
+/// app.insert_resource(IsolationAudit::new(true));
+/// ```
+#[derive(Resource, Default)]
+pub struct IsolationAudit {
+    /// Panic instead of logging an error when a leak is detected.
+    panic_on_leak: bool,
+
+    /// Leaks detected during the last check.
+    leaks: Vec<IsolationLeak>,
+}
+
+impl IsolationAudit {
+    /// Creates a new instance.
+    ///
+    /// If `panic_on_leak` is `true`, panics instead of logging an error on a detected leak.
+    /// Useful in tests and debug builds to catch leaks before they reach clients.
+    pub fn new(panic_on_leak: bool) -> Self {
+        Self {
+            panic_on_leak,
+            leaks: Default::default(),
+        }
+    }
+
+    /// Returns leaks detected during the last replication tick.
+    pub fn leaks(&self) -> &[IsolationLeak] {
+        &self.leaks
+    }
+}
+
+/// Checks that entities from one domain are not visible to clients from another domain.
+///
+/// Runs after sending replication, but before the messaging backend sends packets.
+pub(super) fn audit_isolation(
+    mut audit: ResMut<IsolationAudit>,
+    connected_clients: Res<ConnectedClients>,
+    replicated_clients: Res<ReplicatedClients>,
+    domains: Query<&IsolationDomain>,
+    entities: Query<(Entity, &IsolationDomain), With<Replicated>>,
+) {
+    audit.leaks.clear();
+    for client in replicated_clients.iter() {
+        let client_domain = connected_clients
+            .entity(client.id())
+            .and_then(|entity| domains.get(entity).ok())
+            .copied();
+        for (entity, &entity_domain) in &entities {
+            if client_domain == Some(entity_domain) || !client.visibility().is_visible(entity) {
+                continue;
+            }
+
+            let leak = IsolationLeak {
+                client_id: client.id(),
+                client_domain,
+                entity,
+                entity_domain,
+            };
+            if audit.panic_on_leak {
+                panic!("{leak:?} is visible outside of its domain");
+            }
+            error!("{leak:?} is visible outside of its domain");
+            audit.leaks.push(leak);
+        }
+    }
+}
+
+/// Isolation domain of a client or a replicated entity, such as a room or a match.
+///
+/// See [`IsolationAudit`] for details.
+#[derive(Component, Clone, Copy, Debug, Deref, PartialEq, Eq, Hash)]
+pub struct IsolationDomain(pub u64);
+
+/// An entity from a domain that is visible to a client from another domain.
+///
+/// See [`IsolationAudit`] for details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IsolationLeak {
+    /// Client that sees the entity.
+    pub client_id: ClientId,
+
+    /// Domain of the client, [`None`] if the client isn't in any domain.
+    pub client_domain: Option<IsolationDomain>,
+
+    /// Server entity.
+    pub entity: Entity,
+
+    /// Domain of the entity.
+    pub entity_domain: IsolationDomain,
+}
@@ -0,0 +1,228 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::{prelude::*, utils::HashMap};
+use bytes::Bytes;
+
+use crate::core::{replicon_server::RepliconServer, ClientId};
+
+/// Artificial delay for messages of specific clients.
+///
+/// Insert this resource to enable latency injection. Intended for development
+/// to test how gameplay behaves for high-ping players without external network
+/// shaping tools. Latency can be adjusted at runtime, for example, from an admin command.
+///
+/// Delayed messages are held on the server after receiving and before sending,
+/// so the messaging backend sees them later. The order of messages for a client is preserved
+/// even if its latency is lowered while messages are in flight.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     prelude::*,
+///     server::latency_injection::{InjectedLatency, LatencyInjection},
+/// };
+///
+/// # let mut app = App::new();
+/// app.init_resource::<LatencyInjection>()
+///     .add_observer(add_latency);
+///
+/// fn add_latency(trigger: Trigger<ClientConnected>, mut injection: ResMut<LatencyInjection>) {
+///     injection.set(trigger.client_id, InjectedLatency::symmetric(Duration::from_millis(150)));
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct LatencyInjection {
+    /// Configured latency for each client.
+    latencies: HashMap<ClientId, InjectedLatency>,
+
+    /// Held messages received from clients, in release order.
+    received: HashMap<ClientId, VecDeque<DelayedMessage>>,
+
+    /// Held messages sent to clients, in release order.
+    sent: HashMap<ClientId, VecDeque<DelayedMessage>>,
+}
+
+impl LatencyInjection {
+    /// Sets latency for a client.
+    ///
+    /// Affects only messages that will be received or sent after this call.
+    pub fn set(&mut self, client_id: ClientId, latency: InjectedLatency) {
+        debug!("setting latency for `{client_id:?}` to {latency:?}");
+        self.latencies.insert(client_id, latency);
+    }
+
+    /// Removes latency for a client.
+    ///
+    /// Already held messages will be released on schedule.
+    pub fn remove(&mut self, client_id: ClientId) -> Option<InjectedLatency> {
+        self.latencies.remove(&client_id)
+    }
+
+    /// Returns latency for a client.
+    pub fn get(&self, client_id: ClientId) -> Option<InjectedLatency> {
+        self.latencies.get(&client_id).copied()
+    }
+
+    /// Returns an iterator over clients with configured latency.
+    pub fn iter(&self) -> impl Iterator<Item = (ClientId, InjectedLatency)> + '_ {
+        self.latencies
+            .iter()
+            .map(|(&client_id, &latency)| (client_id, latency))
+    }
+
+    /// Returns the number of held messages for a client in both directions.
+    pub fn held_count(&self, client_id: ClientId) -> usize {
+        let received = self.received.get(&client_id).map_or(0, VecDeque::len);
+        let sent = self.sent.get(&client_id).map_or(0, VecDeque::len);
+        received + sent
+    }
+
+    /// Removes latency and held messages of a disconnected client.
+    pub(crate) fn remove_client(&mut self, client_id: ClientId) {
+        self.latencies.remove(&client_id);
+        self.received.remove(&client_id);
+        self.sent.remove(&client_id);
+    }
+
+    /// Removes all held messages.
+    pub(crate) fn clear_held(&mut self) {
+        self.received.clear();
+        self.sent.clear();
+    }
+}
+
+/// Delay for messages of a client.
+///
+/// See [`LatencyInjection`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InjectedLatency {
+    /// Delay for messages received from the client.
+    pub incoming: Duration,
+
+    /// Delay for messages sent to the client.
+    pub outgoing: Duration,
+}
+
+impl InjectedLatency {
+    /// Creates a new instance with the same delay in both directions.
+    ///
+    /// The resulting round-trip time increases by double the delay.
+    pub fn symmetric(delay: Duration) -> Self {
+        Self {
+            incoming: delay,
+            outgoing: delay,
+        }
+    }
+}
+
+struct DelayedMessage {
+    release_time: Duration,
+    channel_id: u8,
+    message: Bytes,
+}
+
+/// Holds received messages from clients with incoming latency and releases due ones.
+pub(super) fn delay_received(
+    time: Res<Time>,
+    mut server: ResMut<RepliconServer>,
+    mut injection: ResMut<LatencyInjection>,
+) {
+    let now = time.elapsed();
+    let LatencyInjection {
+        latencies,
+        received,
+        ..
+    } = &mut *injection;
+
+    server.retain_received(|channel_id, (client_id, message)| {
+        let Some(latency) = latencies
+            .get(client_id)
+            .filter(|latency| !latency.incoming.is_zero())
+        else {
+            return true;
+        };
+
+        hold(
+            received.entry(*client_id).or_default(),
+            now + latency.incoming,
+            channel_id,
+            message.clone(),
+        );
+        false
+    });
+
+    for (&client_id, messages) in received.iter_mut() {
+        for message in release(messages, now) {
+            server.insert_received(client_id, message.channel_id, message.message);
+        }
+    }
+}
+
+/// Holds sent messages for clients with outgoing latency and releases due ones.
+pub(super) fn delay_sent(
+    time: Res<Time>,
+    mut server: ResMut<RepliconServer>,
+    mut injection: ResMut<LatencyInjection>,
+) {
+    let now = time.elapsed();
+    let LatencyInjection {
+        latencies, sent, ..
+    } = &mut *injection;
+
+    server.retain_sent(|(client_id, channel_id, message)| {
+        let Some(latency) = latencies
+            .get(client_id)
+            .filter(|latency| !latency.outgoing.is_zero())
+        else {
+            return true;
+        };
+
+        hold(
+            sent.entry(*client_id).or_default(),
+            now + latency.outgoing,
+            *channel_id,
+            message.clone(),
+        );
+        false
+    });
+
+    for (&client_id, messages) in sent.iter_mut() {
+        for message in release(messages, now) {
+            server.send(client_id, message.channel_id, message.message);
+        }
+    }
+}
+
+/// Adds a message to the queue without releasing it earlier than previously held messages.
+fn hold(
+    messages: &mut VecDeque<DelayedMessage>,
+    release_time: Duration,
+    channel_id: u8,
+    message: Bytes,
+) {
+    let release_time = messages
+        .back()
+        .map_or(release_time, |last| last.release_time.max(release_time));
+
+    messages.push_back(DelayedMessage {
+        release_time,
+        channel_id,
+        message,
+    });
+}
+
+/// Drains all messages that are due at the given time.
+fn release(
+    messages: &mut VecDeque<DelayedMessage>,
+    now: Duration,
+) -> impl Iterator<Item = DelayedMessage> + '_ {
+    let due = messages
+        .iter()
+        .take_while(|message| message.release_time <= now)
+        .count();
+    messages.drain(..due)
+}
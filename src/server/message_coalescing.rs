@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+/// Opt-in coalescing of mutations into update messages.
+///
+/// Insert this resource to enable it. When a client receives an update message in this tick
+/// and the update message together with all mutations is not larger than [`Self::max_size`],
+/// mutations are sent inside the update message instead of a separate mutate message.
+/// This reduces the number of packets and lets the client apply mutations without waiting
+/// for the matching update message.
+///
+/// Coalesced mutations are sent reliably, so they don't need acknowledgment.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::message_coalescing::MessageCoalescing};
+///
+/// # let mut app = App::new();
+/// app.insert_resource(MessageCoalescing::new(1200));
+/// ```
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct MessageCoalescing {
+    /// Maximum size of the update message with coalesced mutations in bytes.
+    pub max_size: usize,
+}
+
+impl MessageCoalescing {
+    /// Creates a new instance with the specified maximum size.
+    pub fn new(max_size: usize) -> Self {
+        Self { max_size }
+    }
+}
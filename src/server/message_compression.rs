@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use zstd::bulk::Compressor;
+
+use crate::core::{
+    channels::ReplicationChannel,
+    postcard_utils,
+    protocol::{compression_kind::CompressionKind, protocol_version::ProtocolVersion},
+    replicon_server::RepliconServer,
+};
+
+/// Opt-in zstd compression of large replication messages.
+///
+/// Insert this resource to enable compression. Update and mutate messages larger than
+/// [`Self::threshold`] are compressed, while smaller messages are sent as is since
+/// compressing them barely reduces their size. Compressed messages are marked with
+/// [`CompressionKind::Zstd`] in the header and clients decompress them transparently,
+/// so they also need the `zstd` feature.
+///
+/// Most useful for large update messages, such as the initial world sync.
+/// For small mutate messages see [`DictionaryCompressionPlugin`](crate::addons::dictionary_compression::DictionaryCompressionPlugin).
+///
+/// A message is sent uncompressed if compression doesn't reduce its size.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::message_compression::MessageCompression};
+///
+/// # let mut app = App::new();
+/// app.insert_resource(MessageCompression {
+///     threshold: 512,
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct MessageCompression {
+    /// Minimum size of a message in bytes to compress it.
+    ///
+    /// By default set to 256.
+    pub threshold: usize,
+
+    /// Zstd compression level.
+    ///
+    /// By default set to [`zstd::DEFAULT_COMPRESSION_LEVEL`].
+    pub level: i32,
+}
+
+impl Default for MessageCompression {
+    fn default() -> Self {
+        Self {
+            threshold: 256,
+            level: zstd::DEFAULT_COMPRESSION_LEVEL,
+        }
+    }
+}
+
+/// Size of the message header that is left uncompressed.
+const HEADER_SIZE: usize = size_of::<ProtocolVersion>() + size_of::<CompressionKind>();
+
+pub(super) fn compress_messages(
+    mut server: ResMut<RepliconServer>,
+    compression: Res<MessageCompression>,
+) {
+    let mut compressor = None;
+    let updates_id: u8 = ReplicationChannel::Updates.into();
+    let mutations_id: u8 = ReplicationChannel::Mutations.into();
+    for (client_id, channel_id, message) in server.iter_sent_mut() {
+        if (*channel_id != updates_id && *channel_id != mutations_id)
+            || message.len() < compression.threshold
+            || message.len() <= HEADER_SIZE
+        {
+            continue;
+        }
+
+        // Messages that weren't drained by the backend could be already compressed.
+        let (version, data) = message.split_at(size_of::<ProtocolVersion>());
+        let Ok((CompressionKind::None, data)) = postcard::take_from_bytes(data) else {
+            continue;
+        };
+
+        if compressor.is_none() {
+            match Compressor::new(compression.level) {
+                Ok(new_compressor) => compressor = Some(new_compressor),
+                Err(e) => {
+                    error!("unable to create compressor: {e}");
+                    return;
+                }
+            }
+        }
+        let compressor = compressor.as_mut().unwrap();
+
+        let compressed = match compressor.compress(data) {
+            Ok(compressed) => compressed,
+            Err(e) => {
+                error!("unable to compress replication message for `{client_id:?}`: {e}");
+                continue;
+            }
+        };
+
+        if compressed.len() + HEADER_SIZE >= message.len() {
+            trace!(
+                "sending {} bytes uncompressed for `{client_id:?}` since compression doesn't reduce the size",
+                message.len()
+            );
+            continue;
+        }
+
+        trace!(
+            "compressed replication message for `{client_id:?}` from {} to {} bytes",
+            message.len(),
+            compressed.len() + HEADER_SIZE
+        );
+        let mut compressed_message = Vec::with_capacity(compressed.len() + HEADER_SIZE);
+        compressed_message.extend_from_slice(version);
+        postcard_utils::to_extend_mut(&CompressionKind::Zstd, &mut compressed_message)
+            .expect("compression kind should always be serializable");
+        compressed_message.extend_from_slice(&compressed);
+        *message = compressed_message.into();
+    }
+}
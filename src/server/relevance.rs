@@ -0,0 +1,189 @@
+use std::mem;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{server_tick::ServerTick, ServerSet};
+use crate::core::{
+    common_conditions::server_running,
+    replication::{
+        replicated_clients::{ReplicatedClients, VisibilityPolicy},
+        Replicated,
+    },
+    replicon_tick::RepliconTick,
+    ClientId,
+};
+
+/// Distance-based filtering of replicated entities for each client.
+///
+/// A client receives only replicated entities with [`Transform`] that are within [`Self::radius`]
+/// from any of its anchors, marked with [`InterestAnchor`]. An entity becomes hidden only after moving
+/// further than `radius + hysteresis`, so entities moving around the boundary aren't despawned and
+/// spawned again on every tick. To smooth it even more, visibility changes can be delayed
+/// with [`Self::grace_ticks`].
+///
+/// Works by updating [`ClientVisibility`](crate::core::replication::replicated_clients::client_visibility::ClientVisibility)
+/// before sending, so [`ServerPlugin::visibility_policy`](super::ServerPlugin::visibility_policy)
+/// shouldn't be [`VisibilityPolicy::All`]. Clients without anchors and entities without [`Transform`]
+/// are not affected, their visibility can be controlled manually.
+///
+/// Should be added only on server after [`RepliconPlugins`](crate::RepliconPlugins).
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     prelude::*,
+///     server::relevance::{InterestAnchor, RelevancePlugin},
+/// };
+///
+/// # let mut app = App::new();
+/// app.add_plugins((
+///     MinimalPlugins,
+///     RepliconPlugins.set(ServerPlugin {
+///         visibility_policy: VisibilityPolicy::Whitelist,
+///         ..Default::default()
+///     }),
+///     RelevancePlugin {
+///         radius: 50.0,
+///         hysteresis: 5.0,
+///         grace_ticks: 10,
+///     },
+/// ))
+/// .add_observer(spawn_avatar);
+///
+/// fn spawn_avatar(trigger: Trigger<ClientConnected>, mut commands: Commands) {
+///     commands.spawn((
+///         Replicated,
+///         InterestAnchor(trigger.client_id),
+///         Transform::default(),
+///     ));
+/// }
+/// ```
+pub struct RelevancePlugin {
+    /// Maximum distance from an anchor at which an entity becomes visible.
+    pub radius: f32,
+
+    /// Additional distance an entity needs to move beyond [`Self::radius`] to become hidden.
+    pub hysteresis: f32,
+
+    /// Number of server ticks an entity needs to stay inside or outside the boundary
+    /// before its visibility changes.
+    ///
+    /// With 0 visibility changes on the same tick.
+    pub grace_ticks: u32,
+}
+
+impl Default for RelevancePlugin {
+    fn default() -> Self {
+        Self {
+            radius: 100.0,
+            hysteresis: 10.0,
+            grace_ticks: 0,
+        }
+    }
+}
+
+impl Plugin for RelevancePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_relevance(self.radius, self.hysteresis, self.grace_ticks)
+                .before(ServerSet::Send)
+                .run_if(server_running),
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(replicated_clients) = app.world().get_resource::<ReplicatedClients>() {
+            assert!(
+                !matches!(
+                    replicated_clients.visibility_policy(),
+                    VisibilityPolicy::All
+                ),
+                "`RelevancePlugin` requires a visibility policy other than `{:?}`",
+                VisibilityPolicy::All
+            );
+        }
+    }
+}
+
+fn update_relevance(
+    radius: f32,
+    hysteresis: f32,
+    grace_ticks: u32,
+) -> impl FnMut(
+    Local<HashMap<ClientId, Vec<Vec3>>>,
+    Local<HashMap<(ClientId, Entity), RepliconTick>>,
+    Query<(&InterestAnchor, &Transform)>,
+    Query<(Entity, &Transform), With<Replicated>>,
+    ResMut<ReplicatedClients>,
+    Res<ServerTick>,
+) {
+    let show_distance_sq = radius * radius;
+    let hide_distance_sq = (radius + hysteresis).powi(2);
+    move |mut anchor_positions: Local<HashMap<ClientId, Vec<Vec3>>>,
+          mut pending: Local<HashMap<(ClientId, Entity), RepliconTick>>,
+          anchors: Query<(&InterestAnchor, &Transform)>,
+          entities: Query<(Entity, &Transform), With<Replicated>>,
+          mut replicated_clients: ResMut<ReplicatedClients>,
+          server_tick: Res<ServerTick>| {
+        for positions in anchor_positions.values_mut() {
+            positions.clear();
+        }
+        for (anchor, transform) in &anchors {
+            anchor_positions
+                .entry(**anchor)
+                .or_default()
+                .push(transform.translation);
+        }
+
+        // Rebuild pending changes to drop entries for despawned entities and disconnected clients.
+        let mut previous_pending = mem::take(&mut *pending);
+        for client in replicated_clients.iter_mut() {
+            let client_id = client.id();
+            let Some(positions) = anchor_positions
+                .get(&client_id)
+                .filter(|positions| !positions.is_empty())
+            else {
+                continue;
+            };
+
+            let visibility = client.visibility_mut();
+            for (entity, transform) in &entities {
+                let distance_sq = positions
+                    .iter()
+                    .map(|position| position.distance_squared(transform.translation))
+                    .fold(f32::INFINITY, f32::min);
+
+                let visible = visibility.is_visible(entity);
+                let relevant = if visible {
+                    distance_sq <= hide_distance_sq
+                } else {
+                    distance_sq <= show_distance_sq
+                };
+                if relevant == visible {
+                    continue;
+                }
+
+                let key = (client_id, entity);
+                let since_tick = previous_pending.remove(&key).unwrap_or(**server_tick);
+                if **server_tick - since_tick >= grace_ticks {
+                    visibility.set_visibility(entity, relevant);
+                } else {
+                    pending.insert(key, since_tick);
+                }
+            }
+        }
+
+        anchor_positions.retain(|_, positions| !positions.is_empty());
+    }
+}
+
+/// Marks an entity as an interest anchor of a client for [`RelevancePlugin`].
+///
+/// Usually it's a camera or an avatar controlled by the client.
+/// Entities are replicated to the client based on the distance to its anchors.
+/// A client can have multiple anchors.
+#[derive(Component, Clone, Copy, Debug, Deref)]
+pub struct InterestAnchor(pub ClientId);
@@ -11,10 +11,15 @@ use bevy::{
     utils::{HashMap, HashSet},
 };
 
-use super::ServerSet;
+use super::{server_tick::ServerTick, ServerSet};
 use crate::core::{
     common_conditions::server_running,
-    replication::{replication_registry::FnsId, replication_rules::ReplicationRules, Replicated},
+    replication::{
+        replication_registry::{ctx::SerializeCtx, FnsId, ReplicationRegistry},
+        replication_rules::ReplicationRules,
+        Replicated,
+    },
+    replicon_server::RepliconServer,
 };
 
 /// Buffers all replicated component removals in [`RemovalBuffer`] resource.
@@ -32,6 +37,92 @@ impl Plugin for RemovalBufferPlugin {
                 .run_if(server_running),
         );
     }
+
+    fn finish(&self, app: &mut App) {
+        let rules = app.world().resource::<ReplicationRules>();
+        let registry = app.world().resource::<ReplicationRegistry>();
+        let mut components: Vec<_> = rules
+            .iter()
+            .flat_map(|rule| &rule.components)
+            .filter(|&&(_, fns_id)| {
+                let (_, _, rule_fns) = registry.get(fns_id);
+                rule_fns.final_value()
+            })
+            .map(|&(component_id, _)| component_id)
+            .collect();
+        components.sort_unstable();
+        components.dedup();
+
+        if !components.is_empty() {
+            let observer = components
+                .into_iter()
+                .fold(Observer::new(buffer_final_values), Observer::with_component);
+            app.world_mut().spawn(observer);
+        }
+    }
+}
+
+/// Serializes values of components with [`RuleFns::with_final_value`](crate::core::replication::replication_registry::rule_fns::RuleFns::with_final_value)
+/// before their removal.
+fn buffer_final_values(
+    trigger: Trigger<OnRemove>,
+    entities: Query<EntityRef, With<Replicated>>,
+    rules: Res<ReplicationRules>,
+    registry: Option<Res<ReplicationRegistry>>,
+    server: Res<RepliconServer>,
+    server_tick: Res<ServerTick>,
+    mut removal_buffer: ResMut<RemovalBuffer>,
+) {
+    if !server.is_running() {
+        return;
+    }
+    // The registry is temporarily removed while the client applies replication.
+    let Some(registry) = registry else {
+        return;
+    };
+    let Ok(entity) = entities.get(trigger.entity()) else {
+        return;
+    };
+
+    for &component_id in trigger.components() {
+        // Use functions from the rule with the highest priority, like for replication.
+        let Some(fns_id) = rules
+            .iter()
+            .filter(|rule| rule.matches(entity.archetype()))
+            .flat_map(|rule| &rule.components)
+            .find(|&&(id, _)| id == component_id)
+            .map(|&(_, fns_id)| fns_id)
+        else {
+            continue;
+        };
+
+        let (_, component_fns, rule_fns) = registry.get(fns_id);
+        if !rule_fns.final_value() {
+            continue;
+        }
+
+        let component = entity
+            .get_by_id(component_id)
+            .expect("removing component should be present on the entity");
+        let ctx = SerializeCtx {
+            server_tick: **server_tick,
+            component_id,
+            diff: false,
+        };
+        let mut bytes = Vec::new();
+        // SAFETY: `component`, `component_fns` and `rule_fns` were obtained for the same component.
+        if let Err(e) = unsafe { component_fns.serialize(&ctx, rule_fns, component, &mut bytes) } {
+            error!(
+                "unable to serialize final value of `{component_id:?}` for `{}`: {e}",
+                entity.id()
+            );
+            continue;
+        }
+
+        removal_buffer
+            .final_values
+            .insert((entity.id(), fns_id), bytes);
+    }
 }
 
 fn buffer_removals(
@@ -148,6 +239,11 @@ pub(crate) struct RemovalBuffer {
     /// All data is cleared before the insertion.
     /// Stored to reuse allocated capacity.
     ids_buffer: Vec<Vec<(ComponentId, FnsId)>>,
+
+    /// Serialized values of components right before their removal.
+    ///
+    /// Captured only for rules with [`RuleFns::with_final_value`](crate::core::replication::replication_registry::rule_fns::RuleFns::with_final_value).
+    final_values: HashMap<(Entity, FnsId), Vec<u8>>,
 }
 
 impl RemovalBuffer {
@@ -184,6 +280,13 @@ impl RemovalBuffer {
         }
     }
 
+    /// Returns the serialized value of a component right before its removal if it was captured.
+    pub(super) fn final_value(&self, entity: Entity, fns_id: FnsId) -> Option<&[u8]> {
+        self.final_values
+            .get(&(entity, fns_id))
+            .map(|bytes| &**bytes)
+    }
+
     /// Clears all removals.
     ///
     /// Keeps the allocated memory for reuse.
@@ -193,6 +296,7 @@ impl RemovalBuffer {
                 components.clear();
                 components
             }));
+        self.final_values.clear();
     }
 }
 
@@ -11,7 +11,10 @@ use bevy::{
 };
 
 use crate::core::replication::{
-    replication_registry::FnsId, replication_rules::ReplicationRules, Replicated,
+    replication_prefabs::ReplicationPrefabs,
+    replication_registry::{FnsId, ReplicationRegistry},
+    replication_rules::ReplicationRules,
+    Replicated,
 };
 
 /// Cached information about all replicated archetypes.
@@ -29,19 +32,42 @@ pub(crate) struct ReplicatedArchetypes {
 }
 
 impl ReplicatedArchetypes {
+    /// Creates an empty instance with the ID of the [`Replicated`] component.
+    pub(super) fn new(marker_id: ComponentId) -> Self {
+        Self {
+            marker_id,
+            generation: ArchetypeGeneration::initial(),
+            archetypes: Default::default(),
+        }
+    }
+
     /// ID of the [`Replicated`] component.
     pub(crate) fn marker_id(&self) -> ComponentId {
         self.marker_id
     }
 
+    /// Clears all cached archetypes.
+    ///
+    /// They will be collected again on the next [`Self::update`].
+    /// Needed when rules or prefabs change after the initial registration.
+    pub(super) fn clear(&mut self) {
+        self.generation = ArchetypeGeneration::initial();
+        self.archetypes.clear();
+    }
+
     /// Updates the internal view of the [`World`]'s replicated archetypes.
     ///
     /// If this is not called before querying data, the results may not accurately reflect what is in the world.
+    ///
+    /// Components with [`RuleFns::with_delta`](crate::core::replication::replication_registry::rule_fns::RuleFns::with_delta)
+    /// are placed first, so clients can read them without deserializing other components.
     pub(super) fn update(
         &mut self,
         archetypes: &Archetypes,
         components: &Components,
         rules: &ReplicationRules,
+        registry: &ReplicationRegistry,
+        prefabs: &ReplicationPrefabs,
     ) {
         let old_generation = mem::replace(&mut self.generation, archetypes.generation());
 
@@ -51,6 +77,7 @@ impl ReplicatedArchetypes {
             .filter(|archetype| archetype.contains(self.marker_id))
         {
             let mut replicated_archetype = ReplicatedArchetype::new(archetype.id());
+            replicated_archetype.prefab_index = prefabs.find(archetype);
             for rule in rules.iter().filter(|rule| rule.matches(archetype)) {
                 for &(component_id, fns_id) in &rule.components {
                     // Since rules are sorted by priority,
@@ -81,13 +108,22 @@ impl ReplicatedArchetypes {
                     let storage_type =
                         unsafe { archetype.get_storage_type(component_id).unwrap_unchecked() };
 
+                    let prefab_index = replicated_archetype.prefab_index.and_then(|prefab_index| {
+                        prefabs.component_index(prefab_index, component_id)
+                    });
+
                     replicated_archetype.components.push(ReplicatedComponent {
                         component_id,
                         storage_type,
                         fns_id,
+                        prefab_index,
                     });
                 }
             }
+            replicated_archetype.components.sort_by_key(|component| {
+                let (_, _, rule_fns) = registry.get(component.fns_id);
+                rule_fns.delta().is_none()
+            });
             self.archetypes.push(replicated_archetype);
         }
     }
@@ -95,11 +131,7 @@ impl ReplicatedArchetypes {
 
 impl FromWorld for ReplicatedArchetypes {
     fn from_world(world: &mut World) -> Self {
-        Self {
-            marker_id: world.register_component::<Replicated>(),
-            generation: ArchetypeGeneration::initial(),
-            archetypes: Default::default(),
-        }
+        Self::new(world.register_component::<Replicated>())
     }
 }
 
@@ -108,6 +140,9 @@ pub(crate) struct ReplicatedArchetype {
     /// Associated archetype ID.
     pub(super) id: ArchetypeId,
 
+    /// Index of the matching prefab.
+    pub(super) prefab_index: Option<usize>,
+
     /// Components marked as replicated.
     pub(super) components: Vec<ReplicatedComponent>,
 }
@@ -116,6 +151,7 @@ impl ReplicatedArchetype {
     fn new(id: ArchetypeId) -> Self {
         Self {
             id,
+            prefab_index: None,
             components: Default::default(),
         }
     }
@@ -126,6 +162,9 @@ pub(super) struct ReplicatedComponent {
     component_id: ComponentId,
     pub(super) storage_type: StorageType,
     pub(super) fns_id: FnsId,
+
+    /// Index of this component inside the archetype's prefab.
+    pub(super) prefab_index: Option<usize>,
 }
 
 #[cfg(test)]
@@ -133,12 +172,13 @@ mod tests {
     use serde::{Deserialize, Serialize};
 
     use super::*;
-    use crate::{core::replication::replication_registry::ReplicationRegistry, AppRuleExt};
+    use crate::AppRuleExt;
 
     #[test]
     fn empty() {
         let mut app = App::new();
-        app.init_resource::<ReplicationRules>();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationRegistry>();
 
         app.world_mut().spawn_empty();
 
@@ -149,7 +189,8 @@ mod tests {
     #[test]
     fn no_components() {
         let mut app = App::new();
-        app.init_resource::<ReplicationRules>();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationRegistry>();
 
         app.world_mut().spawn(Replicated);
 
@@ -269,6 +310,8 @@ mod tests {
             world.archetypes(),
             world.components(),
             world.resource::<ReplicationRules>(),
+            world.resource::<ReplicationRegistry>(),
+            &Default::default(),
         );
 
         archetypes
@@ -0,0 +1,245 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use bevy::prelude::*;
+
+use crate::core::{
+    replication::replication_registry::FnsId, replicon_tick::RepliconTick, ClientId,
+};
+
+/// Opt-in log of everything the server replicates to each client.
+///
+/// Insert this resource to enable auditing. When present, every replicated change
+/// is recorded as an [`AuditEntry`] into an internal ring buffer and forwarded to all registered sinks.
+/// Useful to answer "why did the client see (or not see) this" after the fact.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::replication_audit::ReplicationAudit};
+///
+/// # let mut app = App::new();
+/// app.insert_resource(ReplicationAudit::new(1024));
+/// ```
+#[derive(Resource)]
+pub struct ReplicationAudit {
+    /// Last recorded entries.
+    entries: VecDeque<AuditEntry>,
+
+    /// Maximum number of entries in [`Self::entries`].
+    capacity: usize,
+
+    /// Additional destinations for recorded entries.
+    sinks: Vec<Box<dyn AuditSink>>,
+}
+
+impl ReplicationAudit {
+    /// Creates a new instance that keeps up to `capacity` last entries in memory.
+    ///
+    /// Use 0 to keep nothing in memory and only forward entries to sinks.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            sinks: Default::default(),
+        }
+    }
+
+    /// Adds a sink that will receive all recorded entries.
+    pub fn with_sink(mut self, sink: impl AuditSink) -> Self {
+        self.add_sink(sink);
+        self
+    }
+
+    /// Like [`Self::with_sink`], but for an existing instance.
+    pub fn add_sink(&mut self, sink: impl AuditSink) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    /// Returns iterator over recorded entries, from oldest to newest.
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &AuditEntry> + ExactSizeIterator {
+        self.entries.iter()
+    }
+
+    /// Returns iterator over recorded entries for a specific client and entity.
+    pub fn entity_history(
+        &self,
+        client_id: ClientId,
+        entity: Entity,
+    ) -> impl Iterator<Item = &AuditEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.client_id == client_id && entry.entity == entity)
+    }
+
+    /// Removes all entries from the ring buffer.
+    ///
+    /// Doesn't affect sinks.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Records an entry into the ring buffer and forwards it to all sinks.
+    pub(crate) fn record(&mut self, entry: AuditEntry) {
+        for sink in &mut self.sinks {
+            sink.record(&entry);
+        }
+
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Flushes all sinks.
+    ///
+    /// Called after each replication tick.
+    pub(crate) fn flush(&mut self) {
+        for sink in &mut self.sinks {
+            sink.flush();
+        }
+    }
+}
+
+/// A single replicated change for a client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// Server tick on which the change was sent.
+    pub tick: RepliconTick,
+
+    /// Receiver of the change.
+    pub client_id: ClientId,
+
+    /// Server entity.
+    pub entity: Entity,
+
+    /// What happened with the entity.
+    pub action: AuditAction,
+
+    /// Number of serialized bytes for this change.
+    ///
+    /// Shared data, such as an entity written once for multiple components, counted for each entry.
+    pub bytes: usize,
+}
+
+/// Kind of a replicated change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditAction {
+    /// Component was inserted or the entity became visible for the client.
+    ///
+    /// Sent via reliable update message.
+    Insertion(FnsId),
+
+    /// Component was mutated.
+    ///
+    /// Sent via unreliable mutate message, unless the entity also had insertions or removals on this tick.
+    Mutation(FnsId),
+
+    /// Component was removed.
+    Removal(FnsId),
+
+    /// Entity was despawned.
+    Despawn,
+
+    /// Entity lost visibility for the client and despawned on its side.
+    Hide,
+}
+
+/// Destination for [`AuditEntry`].
+///
+/// Implement it to forward entries into your own storage.
+pub trait AuditSink: Send + Sync + 'static {
+    /// Called for every recorded entry.
+    fn record(&mut self, entry: &AuditEntry);
+
+    /// Called after each replication tick.
+    fn flush(&mut self) {}
+}
+
+/// Sink that writes entries into a file, one entry per line.
+pub struct FileAuditSink {
+    writer: BufWriter<File>,
+}
+
+impl FileAuditSink {
+    /// Creates a file at the specified path, truncating it if it already exists.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+impl AuditSink for FileAuditSink {
+    fn record(&mut self, entry: &AuditEntry) {
+        if let Err(e) = writeln!(
+            self.writer,
+            "{:?} {:?} {} {:?} {}",
+            entry.tick, entry.client_id, entry.entity, entry.action, entry.bytes
+        ) {
+            error!("unable to write audit entry: {e}");
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Err(e) = self.writer.flush() {
+            error!("unable to flush audit entries: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_buffer() {
+        let mut audit = ReplicationAudit::new(2);
+        for index in 0..3 {
+            audit.record(AuditEntry {
+                tick: RepliconTick::new(index),
+                client_id: ClientId::SERVER,
+                entity: Entity::PLACEHOLDER,
+                action: AuditAction::Despawn,
+                bytes: 1,
+            });
+        }
+
+        let ticks: Vec<_> = audit.entries().map(|entry| entry.tick.get()).collect();
+        assert_eq!(ticks, [1, 2]);
+    }
+
+    #[test]
+    fn sinks() {
+        #[derive(Default, Clone)]
+        struct CountSink(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+        impl AuditSink for CountSink {
+            fn record(&mut self, _entry: &AuditEntry) {
+                self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+
+        let sink = CountSink::default();
+        let mut audit = ReplicationAudit::new(0).with_sink(sink.clone());
+        audit.record(AuditEntry {
+            tick: RepliconTick::new(0),
+            client_id: ClientId::SERVER,
+            entity: Entity::PLACEHOLDER,
+            action: AuditAction::Despawn,
+            bytes: 1,
+        });
+
+        assert_eq!(audit.entries().len(), 0);
+        assert_eq!(sink.0.load(std::sync::atomic::Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::core::replicon_tick::RepliconTick;
+
+/// Opt-in time budget for collecting replicated changes on server.
+///
+/// Insert this resource to enable the budget. When collecting changes takes longer than
+/// [`Self::max_duration`], mutations for the remaining entities are deferred to the next tick
+/// and [`ReplicationBudgetExceeded`] is emitted. Insertions, removals and despawns are always sent
+/// since they are reliable.
+///
+/// On the next tick collection starts from the first deferred entity, so all entities
+/// are eventually replicated.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::replication_budget::ReplicationBudget};
+///
+/// # let mut app = App::new();
+/// app.insert_resource(ReplicationBudget::new(Duration::from_millis(4)));
+/// ```
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct ReplicationBudget {
+    /// Maximum time for collecting changes per tick.
+    pub max_duration: Duration,
+
+    /// Index of the entity from which the next collection starts.
+    pub(super) start_index: usize,
+
+    /// Number of entities with deferred mutations in the last collection.
+    pub(super) deferred_entities: usize,
+}
+
+impl ReplicationBudget {
+    /// Creates a new instance with the specified maximum duration.
+    pub fn new(max_duration: Duration) -> Self {
+        Self {
+            max_duration,
+            start_index: 0,
+            deferred_entities: 0,
+        }
+    }
+}
+
+/// An event that emitted when collecting changes exceeds [`ReplicationBudget::max_duration`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReplicationBudgetExceeded {
+    /// Tick for which the changes were collected.
+    pub tick: RepliconTick,
+
+    /// Number of entities whose mutations were deferred to the next tick.
+    pub deferred_entities: usize,
+}
+
+/// Opt-in byte budget for despawns sent per tick.
+///
+/// Insert this resource to enable the budget. When despawns written during a tick reach
+/// [`Self::max_bytes`], the remaining despawns are deferred to the next ticks. Useful to avoid
+/// bandwidth spikes when many entities are despawned at once, like on level unload.
+///
+/// Deferred despawns are sent before the despawns from later ticks, so all of them are
+/// eventually delivered in the order they happened. At least one despawn is sent per tick
+/// even if it doesn't fit into the budget.
+///
+/// Only affects despawns. Entities that lost visibility are always sent immediately.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::replication_budget::DespawnBudget};
+///
+/// # let mut app = App::new();
+/// app.insert_resource(DespawnBudget::new(1024));
+/// ```
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct DespawnBudget {
+    /// Maximum number of bytes for serialized despawned entities per tick.
+    pub max_bytes: usize,
+
+    /// Number of despawns deferred in the last collection.
+    pub(super) deferred_despawns: usize,
+}
+
+impl DespawnBudget {
+    /// Creates a new instance with the specified maximum number of bytes.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            deferred_despawns: 0,
+        }
+    }
+
+    /// Returns the number of despawns that were deferred to the next tick in the last collection.
+    pub fn deferred_despawns(&self) -> usize {
+        self.deferred_despawns
+    }
+}
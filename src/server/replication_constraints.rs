@@ -0,0 +1,210 @@
+use std::any::TypeId;
+
+use bevy::{
+    ecs::{
+        archetype::ArchetypeId,
+        component::{ComponentId, Components},
+    },
+    prelude::*,
+    reflect::{TypeInfo, TypeRegistry, VariantInfo},
+    utils::HashSet,
+};
+
+use crate::core::replication::{
+    replication_registry::{FnsId, ReplicationRegistry},
+    replication_rules::ReplicationRules,
+    Replicated,
+};
+
+/// Opt-in validation of replication rules for entities that gain [`Replicated`].
+///
+/// Insert this resource to enable the validation. It's intended for debug builds
+/// to catch mistakes that otherwise silently misbehave on clients:
+///
+/// - Replicated components that contain [`Entity`], but registered without mapping
+///   (for example, via [`AppRuleExt::replicate`](crate::core::replication::replication_rules::AppRuleExt::replicate)
+///   instead of [`AppRuleExt::replicate_mapped`](crate::core::replication::replication_rules::AppRuleExt::replicate_mapped)).
+///   Components are inspected via reflection, so only types registered with [`App::register_type`] are checked.
+///   Use [`Self::allow_entities`] for components with entities that don't need mapping or mapped by custom functions.
+/// - Entities that contain only a part of a group rule, so the present components aren't replicated by any rule.
+///
+/// Each violation is logged as a warning with entity and component names and emitted as [`ConstraintViolation`].
+/// Violations of the same kind are reported once for each component or archetype.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::replication_constraints::ReplicationConstraints};
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(RepliconPlugins);
+/// #[cfg(debug_assertions)]
+/// app.insert_resource(ReplicationConstraints::default());
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct ReplicationConstraints {
+    /// Components that are allowed to contain entities without mapping.
+    allowed_entities: HashSet<TypeId>,
+
+    /// Rule functions that were already reported.
+    reported_fns: HashSet<FnsId>,
+
+    /// Archetypes with incomplete groups that were already reported.
+    reported_groups: HashSet<(ArchetypeId, usize)>,
+}
+
+impl ReplicationConstraints {
+    /// Marks a component as allowed to contain [`Entity`] without mapping.
+    ///
+    /// Use it for components with entities that shouldn't be mapped or that are mapped by custom functions.
+    pub fn allow_entities<C: Component>(&mut self) -> &mut Self {
+        self.allowed_entities.insert(TypeId::of::<C>());
+        self
+    }
+}
+
+/// A replication rule violation detected by [`ReplicationConstraints`].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintViolation {
+    /// A replicated component contains [`Entity`], but its rule functions don't map it.
+    UnmappedEntity {
+        /// Entity on which the violation was detected first.
+        entity: Entity,
+        /// Component with entities inside.
+        component_id: ComponentId,
+    },
+    /// An entity contains components from a group rule that aren't replicated because other
+    /// components from the group are missing.
+    IncompleteGroup {
+        /// Entity with the incomplete group.
+        entity: Entity,
+        /// Group components that the entity doesn't have.
+        missing: Vec<ComponentId>,
+    },
+}
+
+pub(super) fn validate(
+    mut violation_events: EventWriter<ConstraintViolation>,
+    mut constraints: ResMut<ReplicationConstraints>,
+    entities: Query<(Entity, EntityRef), Added<Replicated>>,
+    components: &Components,
+    rules: Res<ReplicationRules>,
+    registry: Res<ReplicationRegistry>,
+    type_registry: Res<AppTypeRegistry>,
+) {
+    let type_registry = type_registry.read();
+    for (entity, entity_ref) in &entities {
+        let archetype = entity_ref.archetype();
+        for (rule_index, rule) in rules.iter().enumerate() {
+            if rule.matches(archetype) {
+                for &(component_id, fns_id) in &rule.components {
+                    let (_, _, rule_fns) = registry.get(fns_id);
+                    if rule_fns.mapped()
+                        || constraints.allowed_entities.contains(&rule_fns.type_id())
+                        || constraints.reported_fns.contains(&fns_id)
+                        || !contains_entity(
+                            &type_registry,
+                            rule_fns.type_id(),
+                            &mut Default::default(),
+                        )
+                    {
+                        continue;
+                    }
+
+                    constraints.reported_fns.insert(fns_id);
+                    warn!(
+                        "`{entity}` has replicated `{}` with entities inside, \
+                        but its rule doesn't map them; use `replicate_mapped` or mark it with \
+                        `ReplicationConstraints::allow_entities`",
+                        component_name(components, component_id),
+                    );
+                    violation_events.send(ConstraintViolation::UnmappedEntity {
+                        entity,
+                        component_id,
+                    });
+                }
+            } else if !constraints
+                .reported_groups
+                .contains(&(archetype.id(), rule_index))
+            {
+                let is_uncovered = |component_id| {
+                    archetype.contains(component_id)
+                        && !rules.iter().any(|other| {
+                            other.matches(archetype)
+                                && other.components.iter().any(|&(id, _)| id == component_id)
+                        })
+                };
+                if !rule.components.iter().any(|&(id, _)| is_uncovered(id)) {
+                    continue;
+                }
+
+                constraints
+                    .reported_groups
+                    .insert((archetype.id(), rule_index));
+                let (present, missing): (Vec<_>, Vec<_>) = rule
+                    .components
+                    .iter()
+                    .map(|&(id, _)| id)
+                    .partition(|&id| archetype.contains(id));
+                warn!(
+                    "`{entity}` has {} from a replication group, but is missing {}, \
+                    so they won't be replicated",
+                    component_names(components, &present),
+                    component_names(components, &missing),
+                );
+                violation_events.send(ConstraintViolation::IncompleteGroup { entity, missing });
+            }
+        }
+    }
+}
+
+/// Returns `true` if the type contains [`Entity`] according to its reflection info.
+///
+/// Returns `false` for types that aren't registered.
+fn contains_entity(
+    registry: &TypeRegistry,
+    type_id: TypeId,
+    visited: &mut HashSet<TypeId>,
+) -> bool {
+    if type_id == TypeId::of::<Entity>() {
+        return true;
+    }
+    if !visited.insert(type_id) {
+        return false;
+    }
+    let Some(type_info) = registry.get_type_info(type_id) else {
+        return false;
+    };
+
+    let mut contains = |type_id| contains_entity(registry, type_id, visited);
+    match type_info {
+        TypeInfo::Struct(info) => info.iter().any(|field| contains(field.type_id())),
+        TypeInfo::TupleStruct(info) => info.iter().any(|field| contains(field.type_id())),
+        TypeInfo::Tuple(info) => info.iter().any(|field| contains(field.type_id())),
+        TypeInfo::List(info) => contains(info.item_ty().id()),
+        TypeInfo::Array(info) => contains(info.item_ty().id()),
+        TypeInfo::Map(info) => contains(info.key_ty().id()) || contains(info.value_ty().id()),
+        TypeInfo::Set(info) => contains(info.value_ty().id()),
+        TypeInfo::Enum(info) => info.iter().any(|variant| match variant {
+            VariantInfo::Struct(variant) => variant.iter().any(|field| contains(field.type_id())),
+            VariantInfo::Tuple(variant) => variant.iter().any(|field| contains(field.type_id())),
+            VariantInfo::Unit(_) => false,
+        }),
+        TypeInfo::Opaque(_) => false,
+    }
+}
+
+fn component_name(components: &Components, component_id: ComponentId) -> &str {
+    components
+        .get_name(component_id)
+        .unwrap_or("<unknown component>")
+}
+
+fn component_names(components: &Components, component_ids: &[ComponentId]) -> String {
+    component_ids
+        .iter()
+        .map(|&id| format!("`{}`", component_name(components, id)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
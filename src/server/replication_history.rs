@@ -0,0 +1,229 @@
+use std::{collections::VecDeque, ops::Range, ops::RangeInclusive};
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+use crate::core::{
+    entity_serde, postcard_utils,
+    replication::{
+        history_segment::{HistoryFrame, HistorySegment},
+        replication_registry::FnsId,
+    },
+    replicon_tick::RepliconTick,
+};
+
+/// Opt-in history of serialized component insertions and mutations.
+///
+/// Insert this resource to enable recording. When present, every replicated component change
+/// is stored for the last [`Self::max_ticks`] replication ticks, even if no client can see the entity.
+/// Use [`Self::extract`] to package a part of it into [`HistorySegment`] for killcams or replays.
+///
+/// Removals and despawns are not recorded.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::replication_history::ReplicationHistory};
+///
+/// # let mut app = App::new();
+/// app.insert_resource(ReplicationHistory::new(128));
+/// ```
+#[derive(Resource)]
+pub struct ReplicationHistory {
+    /// Recorded frames, from oldest to newest.
+    frames: VecDeque<RecordedFrame>,
+
+    /// Maximum number of frames in [`Self::frames`].
+    max_ticks: usize,
+}
+
+impl ReplicationHistory {
+    /// Creates a new instance that keeps changes for up to `max_ticks` last replication ticks.
+    pub fn new(max_ticks: usize) -> Self {
+        Self {
+            frames: VecDeque::with_capacity(max_ticks),
+            max_ticks,
+        }
+    }
+
+    /// Returns the maximum number of stored replication ticks.
+    pub fn max_ticks(&self) -> usize {
+        self.max_ticks
+    }
+
+    /// Starts a new frame for the replication tick, evicting the oldest frame if necessary.
+    pub(crate) fn start_tick(&mut self, tick: RepliconTick) {
+        if self.max_ticks == 0 {
+            return;
+        }
+
+        let mut frame = if self.frames.len() >= self.max_ticks {
+            self.frames.pop_front().unwrap_or_default()
+        } else {
+            Default::default()
+        };
+        frame.tick = tick;
+        frame.data.clear();
+        frame.entities.clear();
+
+        self.frames.push_back(frame);
+    }
+
+    /// Records serialized component for the current frame.
+    ///
+    /// `component` is the serialized [`FnsId`] followed by the component data.
+    pub(crate) fn record(&mut self, entity: Entity, fns_id: FnsId, component: &[u8]) {
+        let Some(frame) = self.frames.back_mut() else {
+            return;
+        };
+
+        let start = frame.data.len();
+        frame.data.extend_from_slice(component);
+        frame
+            .entities
+            .entry(entity)
+            .or_default()
+            .push((fns_id, start..frame.data.len()));
+    }
+
+    /**
+    Packages the history of `entities` within `ticks` into a segment.
+
+    The first frame of the segment contains the latest recorded value of each component
+    at the beginning of the range. Components that didn't change within the stored
+    history won't be included.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication::history_segment::HistorySegment, prelude::*,
+        server::{replication_history::ReplicationHistory, server_tick::ServerTick},
+    };
+
+    fn send_killcam(
+        mut segments: EventWriter<ToClients<HistorySegment>>,
+        history: Res<ReplicationHistory>,
+        server_tick: Res<ServerTick>,
+        killer: Single<Entity, With<Killer>>,
+    ) {
+        let ticks = **server_tick - 60..=**server_tick;
+        let segment = history.extract(&[*killer], ticks).unwrap();
+        segments.send(ToClients {
+            mode: SendMode::Broadcast,
+            event: segment,
+        });
+    }
+
+    #[derive(Component)]
+    struct Killer;
+    ```
+    **/
+    pub fn extract(
+        &self,
+        entities: &[Entity],
+        ticks: RangeInclusive<RepliconTick>,
+    ) -> postcard::Result<HistorySegment> {
+        let (start, end) = ticks.into_inner();
+        let mut segment = HistorySegment::default();
+
+        // Merge all frames up to the start of the range into a keyframe.
+        let mut base: Vec<Vec<(FnsId, &[u8])>> = vec![Vec::new(); entities.len()];
+        for frame in self.frames.iter().take_while(|frame| frame.tick <= start) {
+            for (entity, entity_base) in entities.iter().zip(&mut base) {
+                for (fns_id, component) in frame.components(*entity) {
+                    match entity_base.iter_mut().find(|(id, _)| *id == fns_id) {
+                        Some((_, data)) => *data = component,
+                        None => entity_base.push((fns_id, component)),
+                    }
+                }
+            }
+        }
+
+        let mut changes = Vec::new();
+        for (&entity, entity_base) in entities.iter().zip(&base) {
+            if !entity_base.is_empty() {
+                write_changes(
+                    &mut changes,
+                    entity,
+                    entity_base.iter().map(|&(_, component)| component),
+                )?;
+            }
+        }
+        if !changes.is_empty() {
+            segment.frames.push(HistoryFrame {
+                tick: start,
+                changes,
+            });
+        }
+
+        for frame in self
+            .frames
+            .iter()
+            .filter(|frame| frame.tick > start && frame.tick <= end)
+        {
+            let mut changes = Vec::new();
+            for &entity in entities {
+                if frame.entities.contains_key(&entity) {
+                    write_changes(
+                        &mut changes,
+                        entity,
+                        frame.components(entity).map(|(_, component)| component),
+                    )?;
+                }
+            }
+            if !changes.is_empty() {
+                segment.frames.push(HistoryFrame {
+                    tick: frame.tick,
+                    changes,
+                });
+            }
+        }
+
+        Ok(segment)
+    }
+
+    /// Removes all recorded frames.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+/// Serializes entity changes in the same format as inside the update message.
+fn write_changes<'a>(
+    changes: &mut Vec<u8>,
+    entity: Entity,
+    components: impl ExactSizeIterator<Item = &'a [u8]>,
+) -> postcard::Result<()> {
+    entity_serde::serialize_entity(changes, entity)?;
+    // The lowest bit indicates presence of packed zero-sized components, which is never used here.
+    postcard_utils::to_extend_mut(&(components.len() << 1), changes)?;
+    for component in components {
+        changes.extend_from_slice(component);
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct RecordedFrame {
+    tick: RepliconTick,
+
+    /// Serialized components from all entities.
+    data: Vec<u8>,
+
+    /// Recorded components for each entity and their ranges inside [`Self::data`].
+    entities: EntityHashMap<Vec<(FnsId, Range<usize>)>>,
+}
+
+impl RecordedFrame {
+    fn components(&self, entity: Entity) -> impl ExactSizeIterator<Item = (FnsId, &[u8])> + '_ {
+        self.entities
+            .get(&entity)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|(fns_id, range)| (*fns_id, &self.data[range.clone()]))
+    }
+}
@@ -1,4 +1,5 @@
 mod component_changes;
+mod message_builder;
 pub(super) mod mutate_message;
 pub(super) mod serialized_data;
 pub(super) mod update_message;
@@ -11,15 +11,40 @@ pub(super) struct ComponentChanges {
     pub(super) entity: Range<usize>,
     pub(super) components_len: usize,
     pub(super) components: Vec<Range<usize>>,
+
+    /// Bitset of inserted zero-sized components.
+    ///
+    /// Only used inside [`UpdateMessage`](super::update_message::UpdateMessage).
+    /// See [`ReplicationRegistry::flag_bit`](crate::core::replication::replication_registry::ReplicationRegistry::flag_bit).
+    pub(super) flags: u64,
 }
 
 impl ComponentChanges {
+    pub(super) fn new(entity: Range<usize>, components: Vec<Range<usize>>) -> Self {
+        Self {
+            entity,
+            components_len: 0,
+            components,
+            flags: 0,
+        }
+    }
+
     /// Returns serialized size.
+    ///
+    /// Includes [`Self::flags`] if any of them set.
     pub(super) fn size(&self) -> postcard::Result<usize> {
-        let len_size = serialized_size(&self.components_len)?;
+        let mut len_size = serialized_size(&self.header())?;
+        if self.flags != 0 {
+            len_size += serialized_size(&self.flags)?;
+        }
         Ok(self.entity.len() + len_size + self.components_size())
     }
 
+    /// Returns the number of components with the lowest bit indicating presence of [`Self::flags`].
+    pub(super) fn header(&self) -> usize {
+        (self.components_len << 1) | (self.flags != 0) as usize
+    }
+
     /// Like [`Self::size`], but uses components size instead of components count.
     ///
     /// It usually costs more bytes (because the number is bigger),
@@ -51,6 +76,10 @@ impl ComponentChanges {
         self.components.push(component);
     }
 
+    pub(super) fn add_flag(&mut self, bit: u8) {
+        self.flags |= 1 << bit;
+    }
+
     pub(super) fn extend(&mut self, other: &Self) {
         self.components.extend(other.components.iter().cloned());
         self.components_len += other.components_len;
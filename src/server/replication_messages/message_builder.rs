@@ -0,0 +1,121 @@
+use std::ops::Range;
+
+use serde::Serialize;
+
+use super::serialized_data::SerializedData;
+use crate::{
+    core::{postcard_utils, replicon_server::RepliconServer, ClientId},
+    server::replication_worker::{PlannedMessage, ReplicationWorker, Segment},
+};
+
+/// Writes a message from serialized data and inline values.
+///
+/// Copies data immediately or, when [`ReplicationWorker`] is used,
+/// only records ranges to assemble the message later.
+pub(crate) struct MessageBuilder<'a> {
+    serialized: &'a SerializedData,
+
+    /// Message bytes or only inline bytes if planned.
+    bytes: Vec<u8>,
+
+    /// Present if the message is planned instead of written.
+    segments: Option<Vec<Segment>>,
+
+    /// Start of inline bytes that aren't covered by segments yet.
+    inline_start: usize,
+
+    len: usize,
+}
+
+impl<'a> MessageBuilder<'a> {
+    pub(super) fn new(serialized: &'a SerializedData, capacity: usize, planned: bool) -> Self {
+        let (bytes, segments) = if planned {
+            (Vec::new(), Some(Vec::new()))
+        } else {
+            (Vec::with_capacity(capacity), None)
+        };
+
+        Self {
+            serialized,
+            bytes,
+            segments,
+            inline_start: 0,
+            len: 0,
+        }
+    }
+
+    pub(super) fn write<T: Serialize + ?Sized>(&mut self, value: &T) -> postcard::Result<()> {
+        let start = self.bytes.len();
+        postcard_utils::to_extend_mut(value, &mut self.bytes)?;
+        self.len += self.bytes.len() - start;
+
+        Ok(())
+    }
+
+    pub(super) fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+        self.len += bytes.len();
+    }
+
+    pub(super) fn extend_serialized(&mut self, range: Range<usize>) {
+        self.len += range.len();
+        if self.segments.is_none() {
+            self.bytes.extend_from_slice(&self.serialized[range]);
+            return;
+        }
+
+        self.flush_inline();
+        let segments = self.segments.as_mut().unwrap();
+        if let Some(Segment::Serialized(last)) = segments.last_mut() {
+            if last.end == range.start {
+                last.end = range.end;
+                return;
+            }
+        }
+        if !range.is_empty() {
+            segments.push(Segment::Serialized(range));
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Sends the message or plans it for assembly if it was created as planned.
+    pub(super) fn send(
+        mut self,
+        server: &mut RepliconServer,
+        worker: Option<&mut ReplicationWorker>,
+        client_id: ClientId,
+        channel: impl Into<u8>,
+    ) {
+        match worker {
+            Some(worker) if self.segments.is_some() => {
+                self.flush_inline();
+                worker.plan(PlannedMessage {
+                    client_id,
+                    channel: channel.into(),
+                    size: self.len,
+                    inline: self.bytes,
+                    segments: self.segments.unwrap(),
+                });
+            }
+            _ => {
+                debug_assert!(self.segments.is_none(), "planned message requires a worker");
+                server.send(client_id, channel, self.bytes);
+            }
+        }
+    }
+
+    fn flush_inline(&mut self) {
+        let Some(segments) = &mut self.segments else {
+            return;
+        };
+
+        let range = self.inline_start..self.bytes.len();
+        if !range.is_empty() {
+            segments.push(Segment::Inline(range));
+            self.inline_start = self.bytes.len();
+        }
+    }
+}
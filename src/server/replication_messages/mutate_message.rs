@@ -3,16 +3,25 @@ use std::{ops::Range, time::Duration};
 use bevy::{ecs::component::Tick, prelude::*};
 use postcard::experimental::{max_size::MaxSize, serialized_size};
 
-use super::{component_changes::ComponentChanges, serialized_data::SerializedData};
-use crate::core::{
-    channels::ReplicationChannel,
-    postcard_utils,
-    replication::{
-        mutate_index::MutateIndex,
-        replicated_clients::{ClientBuffers, ReplicatedClient},
+use super::{
+    component_changes::ComponentChanges, message_builder::MessageBuilder,
+    serialized_data::SerializedData,
+};
+use crate::{
+    core::{
+        channels::ReplicationChannel,
+        protocol::{
+            compression_kind::CompressionKind, protocol_version::ProtocolVersion,
+            replication_epoch::ReplicationEpoch,
+        },
+        replication::{
+            mutate_index::MutateIndex,
+            replicated_clients::{ClientBuffers, ReplicatedClient},
+        },
+        replicon_server::RepliconServer,
+        replicon_tick::RepliconTick,
     },
-    replicon_server::RepliconServer,
-    replicon_tick::RepliconTick,
+    server::replication_worker::ReplicationWorker,
 };
 
 /// A message with replicated component mutations.
@@ -20,6 +29,8 @@ use crate::core::{
 /// Contains update tick, current tick, mutate index and component mutations since
 /// the last acknowledged tick for each entity.
 ///
+/// Starts with [`ProtocolVersion`], [`CompressionKind`] and [`ReplicationEpoch`].
+///
 /// Cannot be applied on the client until the update message matching this message's update tick
 /// has been applied to the client world.
 /// The message will be manually split into packets up to max size, and each packet will be applied
@@ -57,6 +68,11 @@ pub(crate) struct MutateMessage {
     /// last call of [`Self::start_entity_mutations`].
     mutations_written: bool,
 
+    /// Indicates that mutations contain components encoded relative to acknowledged values.
+    ///
+    /// See [`Self::add_delta_component`].
+    contains_deltas: bool,
+
     /// Intermediate buffer to reuse allocated memory from [`Self::mutations`].
     buffer: Vec<Vec<Range<usize>>>,
 
@@ -84,11 +100,8 @@ impl MutateMessage {
     /// Adds an entity chunk.
     pub(crate) fn add_mutated_entity(&mut self, entity: Entity, entity_range: Range<usize>) {
         let components = self.buffer.pop().unwrap_or_default();
-        self.mutations.push(ComponentChanges {
-            entity: entity_range,
-            components_len: 0,
-            components,
-        });
+        self.mutations
+            .push(ComponentChanges::new(entity_range, components));
         self.entities.push(entity);
         self.mutations_written = true;
     }
@@ -103,6 +116,20 @@ impl MutateMessage {
         mutations.add_component(component);
     }
 
+    /// Like [`Self::add_mutated_component`], but for a component encoded relative to an acknowledged value.
+    ///
+    /// Such mutations can't be sent inside [`UpdateMessage`](super::update_message::UpdateMessage)
+    /// because the client could apply them before the mutate message with the value.
+    pub(crate) fn add_delta_component(&mut self, component: Range<usize>) {
+        self.add_mutated_component(component);
+        self.contains_deltas = true;
+    }
+
+    /// Returns `true` if [`Self::add_delta_component`] was called since the last [`Self::clear`].
+    pub(crate) fn contains_deltas(&self) -> bool {
+        self.contains_deltas
+    }
+
     /// Returns written mutations for the last entity from [`Self::add_mutated_entity`].
     pub(super) fn last_mutations(&mut self) -> Option<&ComponentChanges> {
         self.mutations.last()
@@ -121,15 +148,61 @@ impl MutateMessage {
         self.mutations.is_empty()
     }
 
+    /// Returns all entities with the size of their serialized mutations.
+    pub(crate) fn entity_sizes(
+        &self,
+    ) -> impl Iterator<Item = (Entity, postcard::Result<usize>)> + '_ {
+        self.entities.iter().copied().zip(
+            self.mutations
+                .iter()
+                .map(ComponentChanges::size_with_components_size),
+        )
+    }
+
+    /// Removes entities for which `f` returns `false` with associated components.
+    ///
+    /// Removed entities won't be acknowledged, so their mutations will be sent again on the next tick.
+    pub(crate) fn retain_entities(&mut self, mut f: impl FnMut(Entity) -> bool) {
+        let mut len = 0;
+        for index in 0..self.entities.len() {
+            if f(self.entities[index]) {
+                self.entities.swap(len, index);
+                self.mutations.swap(len, index);
+                len += 1;
+            }
+        }
+
+        self.entities.truncate(len);
+        self.buffer
+            .extend(self.mutations.drain(len..).map(|mut mutations| {
+                mutations.components.clear();
+                mutations.components
+            }));
+    }
+
+    /// Returns the size of all mutations if they are serialized inside
+    /// [`UpdateMessage`](super::update_message::UpdateMessage).
+    pub(crate) fn changes_size(&self) -> postcard::Result<usize> {
+        self.mutations.iter().map(ComponentChanges::size).sum()
+    }
+
+    /// Removes all entities with their mutations.
+    pub(super) fn drain(&mut self) -> impl Iterator<Item = (Entity, ComponentChanges)> + '_ {
+        self.entities.drain(..).zip(self.mutations.drain(..))
+    }
+
     pub(crate) fn send(
         &mut self,
         server: &mut RepliconServer,
+        mut worker: Option<&mut ReplicationWorker>,
         client: &mut ReplicatedClient,
         client_buffers: &mut ClientBuffers,
         serialized: &SerializedData,
         track_mutate_messages: bool,
+        epoch: ReplicationEpoch,
         server_tick: Range<usize>,
         tick: Tick,
+        sent_tick: RepliconTick,
         timestamp: Duration,
     ) -> postcard::Result<usize> {
         debug_assert_eq!(self.entities.len(), self.mutations.len());
@@ -137,13 +210,17 @@ impl MutateMessage {
         const MAX_COUNT_SIZE: usize = usize::POSTCARD_MAX_SIZE;
         let mut tick_buffer = [0; RepliconTick::POSTCARD_MAX_SIZE];
         let update_tick = postcard::to_slice(&client.update_tick(), &mut tick_buffer)?;
-        let mut metadata_size = update_tick.len() + server_tick.len();
+        let mut metadata_size = size_of::<ProtocolVersion>()
+            + size_of::<CompressionKind>()
+            + size_of::<ReplicationEpoch>()
+            + update_tick.len()
+            + server_tick.len();
         if track_mutate_messages {
             metadata_size += MAX_COUNT_SIZE;
         }
 
         let (mut mutate_index, mut entities) =
-            client.register_mutate_message(client_buffers, tick, timestamp);
+            client.register_mutate_message(client_buffers, tick, sent_tick, timestamp);
         let mut header_size = metadata_size + serialized_size(&mutate_index)?;
         let mut body_size = 0;
         let mut mutations_range = Range::<usize>::default();
@@ -163,7 +240,7 @@ impl MutateMessage {
 
                 mutations_range.start = mutations_range.end;
                 (mutate_index, entities) =
-                    client.register_mutate_message(client_buffers, tick, timestamp);
+                    client.register_mutate_message(client_buffers, tick, sent_tick, timestamp);
                 header_size = metadata_size + serialized_size(&mutate_index)?; // Recalculate since the mutate index changed.
                 body_size = 0;
             }
@@ -183,30 +260,45 @@ impl MutateMessage {
         }
 
         let messages_count = self.messages.len();
+        let planned = worker.is_some() && !client.is_virtual();
         for (mutate_index, mut message_size, mutations_range) in self.messages.drain(..) {
             if track_mutate_messages {
                 // Update message counter size based on actual value.
                 message_size -= MAX_COUNT_SIZE - serialized_size(&messages_count)?;
             }
-            let mut message = Vec::with_capacity(message_size);
+            let mut message = MessageBuilder::new(serialized, message_size, planned);
 
+            message.write(&ProtocolVersion::CURRENT)?;
+            message.write(&CompressionKind::None)?;
+            message.write(&epoch)?;
             message.extend_from_slice(update_tick);
-            message.extend_from_slice(&serialized[server_tick.clone()]);
+            message.extend_serialized(server_tick.clone());
             if track_mutate_messages {
-                postcard_utils::to_extend_mut(&messages_count, &mut message)?;
+                message.write(&messages_count)?;
             }
-            postcard_utils::to_extend_mut(&mutate_index, &mut message)?;
+            message.write(&mutate_index)?;
             for mutations in &self.mutations[mutations_range.clone()] {
-                message.extend_from_slice(&serialized[mutations.entity.clone()]);
-                postcard_utils::to_extend_mut(&mutations.components_size(), &mut message)?;
+                message.extend_serialized(mutations.entity.clone());
+                message.write(&mutations.components_size())?;
                 for component in &mutations.components {
-                    message.extend_from_slice(&serialized[component.clone()]);
+                    message.extend_serialized(component.clone());
                 }
             }
 
             debug_assert_eq!(message.len(), message_size);
 
-            server.send(client.id(), ReplicationChannel::Mutations, message);
+            if client.is_virtual() {
+                // Virtual clients receive everything immediately.
+                client.receive_virtual(ReplicationChannel::Mutations, message.len());
+                client.ack_mutate_message(client_buffers, tick, mutate_index);
+            } else {
+                message.send(
+                    server,
+                    worker.as_deref_mut(),
+                    client.id(),
+                    ReplicationChannel::Mutations,
+                );
+            }
         }
 
         Ok(messages_count)
@@ -217,6 +309,7 @@ impl MutateMessage {
     /// Keeps allocated memory for reuse.
     pub(super) fn clear(&mut self) {
         self.entities.clear();
+        self.contains_deltas = false;
         self.buffer
             .extend(self.mutations.drain(..).map(|mut mutations| {
                 mutations.components.clear();
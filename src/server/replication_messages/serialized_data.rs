@@ -1,16 +1,22 @@
 use std::ops::Range;
 
-use bevy::{prelude::*, ptr::Ptr};
+use bevy::{ecs::component::ComponentId, prelude::*, ptr::Ptr};
 
 use crate::{
     core::{
-        entity_serde, postcard_utils,
-        replication::replication_registry::{
-            component_fns::ComponentFns, ctx::SerializeCtx, rule_fns::UntypedRuleFns, FnsId,
+        entity_serde::{self, EntityObfuscation},
+        postcard_utils,
+        replication::{
+            entity_batch,
+            replication_registry::{
+                component_fns::ComponentFns, ctx::SerializeCtx, rule_fns::UntypedRuleFns, FnsId,
+                ReplicationRegistry,
+            },
         },
         replicon_tick::RepliconTick,
+        ClientId,
     },
-    server::client_entity_map::ClientMapping,
+    server::{client_entity_map::ClientMapping, removal_buffer::RemovalBuffer},
 };
 
 /// Single continuous buffer that stores serialized data for messages.
@@ -18,18 +24,78 @@ use crate::{
 /// See [`UpdateMessage`](super::update_message::UpdateMessage) and
 /// [`MutateMessage`](super::mutate_message::MutateMessage).
 #[derive(Default, Deref, DerefMut)]
-pub(crate) struct SerializedData(Vec<u8>);
+pub(crate) struct SerializedData {
+    #[deref]
+    bytes: Vec<u8>,
+
+    /// Obfuscation from which permutations of written server entities are derived for each client.
+    obfuscation: Option<EntityObfuscation>,
+}
 
 impl SerializedData {
+    pub(crate) fn set_obfuscation(&mut self, obfuscation: Option<EntityObfuscation>) {
+        self.obfuscation = obfuscation;
+    }
+
+    /// Returns `true` if written entities differ between clients.
+    pub(crate) fn is_obfuscated(&self) -> bool {
+        self.obfuscation.is_some()
+    }
+
     pub(crate) fn write_mappings(
         &mut self,
+        client_id: ClientId,
         mappings: impl Iterator<Item = ClientMapping>,
     ) -> postcard::Result<Range<usize>> {
         let start = self.len();
 
         for mapping in mappings {
-            self.write_entity(mapping.server_entity)?;
-            self.write_entity(mapping.client_entity)?;
+            self.write_entity(client_id, mapping.server_entity)?;
+            // Client entity is already known to the client.
+            entity_serde::serialize_entity(&mut self.bytes, mapping.client_entity)?;
+        }
+
+        let end = self.len();
+
+        Ok(start..end)
+    }
+
+    pub(crate) fn write_entity_batch(
+        &mut self,
+        group: &[Entity],
+    ) -> postcard::Result<Range<usize>> {
+        let start = self.len();
+
+        entity_batch::serialize(&mut self.bytes, group)?;
+
+        let end = self.len();
+
+        Ok(start..end)
+    }
+
+    /// Writes bytes that were serialized earlier.
+    pub(crate) fn write_cached(&mut self, bytes: &[u8]) -> Range<usize> {
+        let start = self.len();
+        self.extend_from_slice(bytes);
+        let end = self.len();
+
+        start..end
+    }
+
+    /// Writes a resource ID followed by a flag and the resource value if the flag is set.
+    ///
+    /// The flag is not set for removed resources.
+    pub(crate) fn write_resource(
+        &mut self,
+        id: usize,
+        value: Option<&[u8]>,
+    ) -> postcard::Result<Range<usize>> {
+        let start = self.len();
+
+        postcard_utils::to_extend_mut(&id, &mut self.bytes)?;
+        postcard_utils::to_extend_mut(&value.is_some(), &mut self.bytes)?;
+        if let Some(bytes) = value {
+            self.extend_from_slice(bytes);
         }
 
         let end = self.len();
@@ -37,14 +103,29 @@ impl SerializedData {
         Ok(start..end)
     }
 
-    pub(crate) fn write_fn_ids(
+    /// Writes IDs of removed components.
+    ///
+    /// For rules with [`RuleFns::with_final_value`](crate::core::replication::replication_registry::rule_fns::RuleFns::with_final_value),
+    /// each ID is followed by a flag and the captured value if the flag is set.
+    pub(crate) fn write_removals(
         &mut self,
-        fn_ids: impl Iterator<Item = FnsId>,
+        registry: &ReplicationRegistry,
+        removal_buffer: &RemovalBuffer,
+        entity: Entity,
+        remove_ids: &[(ComponentId, FnsId)],
     ) -> postcard::Result<Range<usize>> {
         let start = self.len();
 
-        for fns_id in fn_ids {
-            postcard_utils::to_extend_mut(&fns_id, &mut self.0)?;
+        for &(_, fns_id) in remove_ids {
+            postcard_utils::to_extend_mut(&fns_id, &mut self.bytes)?;
+            let (_, _, rule_fns) = registry.get(fns_id);
+            if rule_fns.final_value() {
+                let final_value = removal_buffer.final_value(entity, fns_id);
+                postcard_utils::to_extend_mut(&final_value.is_some(), &mut self.bytes)?;
+                if let Some(bytes) = final_value {
+                    self.extend_from_slice(bytes);
+                }
+            }
         }
 
         let end = self.len();
@@ -62,9 +143,59 @@ impl SerializedData {
     ) -> postcard::Result<Range<usize>> {
         let start = self.len();
 
-        postcard_utils::to_extend_mut(&fns_id, &mut self.0)?;
+        postcard_utils::to_extend_mut(&fns_id, &mut self.bytes)?;
         // SAFETY: `component_fns`, `ptr` and `rule_fns` were created for the same component type.
-        unsafe { component_fns.serialize(ctx, rule_fns, ptr, &mut self.0)? };
+        unsafe { component_fns.serialize(ctx, rule_fns, ptr, &mut self.bytes)? };
+
+        let end = self.len();
+
+        Ok(start..end)
+    }
+
+    /// Writes a component replicated with [`RuleFns::with_delta`](crate::core::replication::replication_registry::rule_fns::RuleFns::with_delta).
+    ///
+    /// The data is prefixed with the tick of the baseline value and its size.
+    /// Without a baseline `bytes` should contain the full value, otherwise the output of the diff function.
+    pub(crate) fn write_delta(
+        &mut self,
+        fns_id: FnsId,
+        baseline_tick: Option<RepliconTick>,
+        bytes: &[u8],
+    ) -> postcard::Result<Range<usize>> {
+        let start = self.len();
+
+        postcard_utils::to_extend_mut(&fns_id, &mut self.bytes)?;
+        postcard_utils::to_extend_mut(&baseline_tick, &mut self.bytes)?;
+        postcard_utils::to_extend_mut(&bytes.len(), &mut self.bytes)?;
+        self.extend_from_slice(bytes);
+
+        let end = self.len();
+
+        Ok(start..end)
+    }
+
+    /// Writes a component with only changed field groups.
+    ///
+    /// Uses the same format as [`RuleFns::field_groups`](crate::core::replication::replication_registry::rule_fns::RuleFns::field_groups),
+    /// but the mask contains only the passed groups.
+    pub(crate) fn write_field_groups<'a>(
+        &mut self,
+        rule_fns: &UntypedRuleFns,
+        fns_id: FnsId,
+        mask: u64,
+        groups: impl Iterator<Item = &'a [u8]>,
+    ) -> postcard::Result<Range<usize>> {
+        let start = self.len();
+
+        postcard_utils::to_extend_mut(&fns_id, &mut self.bytes)?;
+        if rule_fns.default_elision() {
+            // Only changed groups are written, so the component is never treated as default.
+            postcard_utils::to_extend_mut(&false, &mut self.bytes)?;
+        }
+        postcard_utils::to_extend_mut(&mask, &mut self.bytes)?;
+        for bytes in groups {
+            self.extend_from_slice(bytes);
+        }
 
         let end = self.len();
 
@@ -77,10 +208,20 @@ impl SerializedData {
     /// is serialized or not. It is not serialized if <= 1; note that generations are [`NonZeroU32`](std::num::NonZeroU32)
     /// and a value of zero is used in [`Option<Entity>`] to signify [`None`], so generation 1 is the first
     /// generation.
-    pub(crate) fn write_entity(&mut self, entity: Entity) -> postcard::Result<Range<usize>> {
+    ///
+    /// If obfuscation is enabled, the entity is written as seen by the client.
+    pub(crate) fn write_entity(
+        &mut self,
+        client_id: ClientId,
+        entity: Entity,
+    ) -> postcard::Result<Range<usize>> {
         let start = self.len();
 
-        entity_serde::serialize_entity(&mut self.0, entity)?;
+        let entity = match self.obfuscation {
+            Some(obfuscation) => obfuscation.for_client(client_id).obfuscate(entity),
+            None => entity,
+        };
+        entity_serde::serialize_entity(&mut self.bytes, entity)?;
 
         let end = self.len();
 
@@ -90,7 +231,7 @@ impl SerializedData {
     pub(crate) fn write_tick(&mut self, tick: RepliconTick) -> postcard::Result<Range<usize>> {
         let start = self.len();
 
-        postcard_utils::to_extend_mut(&tick, &mut self.0)?;
+        postcard_utils::to_extend_mut(&tick, &mut self.bytes)?;
 
         let end = self.len();
 
@@ -4,24 +4,31 @@ use bevy::prelude::*;
 use postcard::experimental::serialized_size;
 
 use super::{
-    component_changes::ComponentChanges, mutate_message::MutateMessage,
-    serialized_data::SerializedData,
+    component_changes::ComponentChanges, message_builder::MessageBuilder,
+    mutate_message::MutateMessage, serialized_data::SerializedData,
 };
-use crate::core::{
-    channels::ReplicationChannel,
-    postcard_utils,
-    replication::{
-        replicated_clients::{client_visibility::Visibility, ReplicatedClient},
-        update_message_flags::UpdateMessageFlags,
+use crate::{
+    core::{
+        channels::ReplicationChannel,
+        protocol::{
+            compression_kind::CompressionKind, protocol_version::ProtocolVersion,
+            replication_epoch::ReplicationEpoch, update_message_flags::UpdateMessageFlags,
+        },
+        replication::replicated_clients::{client_visibility::Visibility, ReplicatedClient},
+        replicon_server::RepliconServer,
     },
-    replicon_server::RepliconServer,
+    server::replication_worker::ReplicationWorker,
 };
 
 /// A message with replicated data.
 ///
-/// Contains tick, mappings, insertions, removals, and despawns that
+/// Contains tick, mappings, insertions, removals, despawns and resource changes that
 /// happened in this tick.
 ///
+/// Starts with [`ProtocolVersion`], [`CompressionKind`] and [`UpdateMessageFlags`].
+/// [`ReplicationEpoch`] and the number of ticks covered by the message are written only
+/// if they differ from their defaults, which is indicated by the corresponding flags.
+///
 /// The data is serialized manually and stored in the form of ranges
 /// from [`SerializedData`].
 ///
@@ -53,6 +60,9 @@ pub(crate) struct UpdateMessage {
     /// Number of pairs encoded in [`Self::mappings`].
     mappings_len: usize,
 
+    /// Whether all mappings are for static baseline entities that the client already has.
+    baseline_mappings: bool,
+
     /// Despawns that happened in this tick.
     ///
     /// Since clients may see different entities, it's serialized as multiple chunks of entities.
@@ -64,6 +74,14 @@ pub(crate) struct UpdateMessage {
     /// May not be equal to the length of [`Self::despawns`] since adjacent ranges are merged together.
     despawns_len: usize,
 
+    /// Despawns encoded as batches.
+    ///
+    /// Used for large groups of entities with close indices, such as entities that lost visibility at once.
+    /// Serialized as multiple chunks of batches.
+    ///
+    /// See [`entity_batch`](crate::core::replication::entity_batch).
+    despawn_batches: Vec<Range<usize>>,
+
     /// Component removals that happened in this tick.
     ///
     /// Serialized as a list of pairs of entity chunk and a list of
@@ -71,9 +89,23 @@ pub(crate) struct UpdateMessage {
     /// serialized as a single chunk.
     removals: Vec<ComponentRemovals>,
 
+    /// Resource insertions, mutations or removals that happened in this tick.
+    ///
+    /// Serialized as multiple chunks of resources since clients that haven't received
+    /// the initial state yet need all resources.
+    ///
+    /// See [`AppResourceExt`](crate::core::replication::replicated_resources::AppResourceExt).
+    resources: Vec<Range<usize>>,
+
+    /// Number of resources encoded in [`Self::resources`].
+    ///
+    /// May not be equal to the length of [`Self::resources`] since adjacent ranges are merged together.
+    resources_len: usize,
+
     /// Component insertions or mutations that happened in this tick.
     ///
     /// Serialized as a list of pairs of entity chunk and multiple chunks with changed components.
+    /// Inserted zero-sized components are packed into a bitset after the entity chunk.
     /// Components are stored in multiple chunks because newly connected clients may need to serialize all components,
     /// while previously connected clients only need the components spawned during this tick.
     ///
@@ -95,9 +127,10 @@ pub(crate) struct UpdateMessage {
 }
 
 impl UpdateMessage {
-    pub(crate) fn set_mappings(&mut self, mappings: Range<usize>, len: usize) {
+    pub(crate) fn set_mappings(&mut self, mappings: Range<usize>, len: usize, baseline: bool) {
         self.mappings = mappings;
         self.mappings_len = len;
+        self.baseline_mappings = baseline;
     }
 
     pub(crate) fn add_despawn(&mut self, entity: Range<usize>) {
@@ -112,6 +145,10 @@ impl UpdateMessage {
         self.despawns.push(entity);
     }
 
+    pub(crate) fn add_despawn_batch(&mut self, batch: Range<usize>) {
+        self.despawn_batches.push(batch);
+    }
+
     pub(crate) fn add_removals(
         &mut self,
         entity: Range<usize>,
@@ -125,6 +162,18 @@ impl UpdateMessage {
         });
     }
 
+    pub(crate) fn add_resource(&mut self, resource: Range<usize>) {
+        self.resources_len += 1;
+        if let Some(last) = self.resources.last_mut() {
+            // Append to previous range if possible.
+            if last.end == resource.start {
+                last.end = resource.end;
+                return;
+            }
+        }
+        self.resources.push(resource);
+    }
+
     /// Updates internal state to start writing changed components for an entity with the given visibility.
     ///
     /// Entities and their data are written lazily during the iteration.
@@ -148,11 +197,7 @@ impl UpdateMessage {
     /// Adds an entity chunk.
     pub(crate) fn add_changed_entity(&mut self, entity: Range<usize>) {
         let components = self.buffer.pop().unwrap_or_default();
-        self.changes.push(ComponentChanges {
-            entity,
-            components_len: 0,
-            components,
-        });
+        self.changes.push(ComponentChanges::new(entity, components));
         self.entity_written = true;
     }
 
@@ -166,6 +211,18 @@ impl UpdateMessage {
         changes.add_component(component);
     }
 
+    /// Adds a zero-sized component as a bit to the last added entity from [`Self::add_changed_entity`].
+    ///
+    /// See [`ReplicationRegistry::flag_bit`](crate::core::replication::replication_registry::ReplicationRegistry::flag_bit).
+    pub(crate) fn add_inserted_flag(&mut self, bit: u8) {
+        let changes = self
+            .changes
+            .last_mut()
+            .expect("entity should be written before adding flags");
+
+        changes.add_flag(bit);
+    }
+
     /// Takes last mutated entity with its component chunks from the mutate message.
     pub(crate) fn take_mutations(&mut self, mutate_message: &mut MutateMessage) {
         if !mutate_message.mutations_written() {
@@ -178,11 +235,7 @@ impl UpdateMessage {
 
         if !self.entity_written {
             let components = self.buffer.pop().unwrap_or_default();
-            let changes = ComponentChanges {
-                entity: mutations.entity.clone(),
-                components_len: 0,
-                components,
-            };
+            let changes = ComponentChanges::new(mutations.entity.clone(), components);
             self.changes.push(changes);
         }
         let changes = self.changes.last_mut().unwrap();
@@ -192,26 +245,52 @@ impl UpdateMessage {
         mutate_message.pop_mutations();
     }
 
+    /// Takes all mutated entities with their component chunks from the mutate message.
+    ///
+    /// Unlike [`Self::take_mutations`], called after all entities are written.
+    /// Mutated entities are never written into this message, since [`Self::take_mutations`]
+    /// already took mutations for them. Calls `f` for each taken entity.
+    pub(crate) fn take_all_mutations(
+        &mut self,
+        mutate_message: &mut MutateMessage,
+        mut f: impl FnMut(Entity),
+    ) {
+        for (entity, mutations) in mutate_message.drain() {
+            (f)(entity);
+            self.changes.push(mutations);
+        }
+    }
+
     pub(crate) fn is_empty(&self) -> bool {
         self.changes.is_empty()
             && self.despawns.is_empty()
+            && self.despawn_batches.is_empty()
             && self.removals.is_empty()
+            && self.resources.is_empty()
             && self.mappings.is_empty()
     }
 
-    pub(crate) fn send(
+    /// Returns the serialized size of the message with the given header fields.
+    pub(crate) fn size(
         &self,
-        server: &mut RepliconServer,
-        client: &ReplicatedClient,
-        serialized: &SerializedData,
-        server_tick: Range<usize>,
-    ) -> postcard::Result<()> {
-        let flags = self.flags();
-        let last_flag = flags.last();
-
-        // Precalculate size first to avoid extra allocations.
-        let mut message_size = size_of::<UpdateMessageFlags>() + server_tick.len();
-        for (_, flag) in flags.iter_names() {
+        epoch: ReplicationEpoch,
+        server_tick_size: usize,
+        ticks_covered: u32,
+    ) -> postcard::Result<usize> {
+        let flags = self.flags(epoch, ticks_covered);
+        let last_flag = flags.sections().last();
+
+        let mut message_size = size_of::<ProtocolVersion>()
+            + size_of::<CompressionKind>()
+            + size_of::<UpdateMessageFlags>()
+            + server_tick_size;
+        if flags.contains(UpdateMessageFlags::EPOCH) {
+            message_size += size_of::<ReplicationEpoch>();
+        }
+        if flags.contains(UpdateMessageFlags::TICKS_COVERED) {
+            message_size += serialized_size(&ticks_covered)?;
+        }
+        for (_, flag) in flags.sections().iter_names() {
             match flag {
                 UpdateMessageFlags::MAPPINGS => {
                     if flag != last_flag {
@@ -225,6 +304,12 @@ impl UpdateMessage {
                     }
                     message_size += self.despawns.iter().map(Range::len).sum::<usize>();
                 }
+                UpdateMessageFlags::DESPAWN_BATCHES => {
+                    if flag != last_flag {
+                        message_size += serialized_size(&self.despawn_batches.len())?;
+                    }
+                    message_size += self.despawn_batches.iter().map(Range::len).sum::<usize>();
+                }
                 UpdateMessageFlags::REMOVALS => {
                     if flag != last_flag {
                         message_size += serialized_size(&self.removals.len())?;
@@ -235,6 +320,12 @@ impl UpdateMessage {
                         .map(ComponentRemovals::size)
                         .sum::<postcard::Result<usize>>()?;
                 }
+                UpdateMessageFlags::RESOURCES => {
+                    if flag != last_flag {
+                        message_size += serialized_size(&self.resources_len)?;
+                    }
+                    message_size += self.resources.iter().map(Range::len).sum::<usize>();
+                }
                 UpdateMessageFlags::CHANGES => {
                     debug_assert_eq!(flag, last_flag);
                     message_size += self
@@ -247,50 +338,97 @@ impl UpdateMessage {
             }
         }
 
-        let mut message = Vec::with_capacity(message_size);
-        postcard_utils::to_extend_mut(&flags, &mut message)?;
-        message.extend_from_slice(&serialized[server_tick]);
-        for (_, flag) in flags.iter_names() {
+        Ok(message_size)
+    }
+
+    pub(crate) fn send(
+        &self,
+        server: &mut RepliconServer,
+        worker: Option<&mut ReplicationWorker>,
+        client: &mut ReplicatedClient,
+        serialized: &SerializedData,
+        epoch: ReplicationEpoch,
+        server_tick: Range<usize>,
+        ticks_covered: u32,
+    ) -> postcard::Result<()> {
+        let flags = self.flags(epoch, ticks_covered);
+        let last_flag = flags.sections().last();
+
+        // Precalculate size first to avoid extra allocations.
+        let message_size = self.size(epoch, server_tick.len(), ticks_covered)?;
+
+        let planned = worker.is_some() && !client.is_virtual();
+        let mut message = MessageBuilder::new(serialized, message_size, planned);
+        message.write(&ProtocolVersion::CURRENT)?;
+        message.write(&CompressionKind::None)?;
+        message.write(&flags)?;
+        if flags.contains(UpdateMessageFlags::EPOCH) {
+            message.write(&epoch)?;
+        }
+        message.extend_serialized(server_tick);
+        if flags.contains(UpdateMessageFlags::TICKS_COVERED) {
+            message.write(&ticks_covered)?;
+        }
+        for (_, flag) in flags.sections().iter_names() {
             match flag {
                 UpdateMessageFlags::MAPPINGS => {
-                    // Always write size since the message can't have only mappings.
-                    // Otherwise this would mean that the client already received the mapped
-                    // entity and it's already mapped or server sends an invisible entity which
-                    // is an error.
-                    if flag == last_flag {
+                    // Only static baseline entities can be mapped without data because
+                    // the client already has them. Otherwise a message with only mappings would mean that
+                    // the client already received the mapped entity and it's already mapped
+                    // or server sends an invisible entity which is an error.
+                    if flag != last_flag {
+                        message.write(&self.mappings_len)?;
+                    } else if !self.baseline_mappings {
                         error!("skipping the sending of a message with mappings but without any entity data,
                                 which could be caused by mapping invisible or non-replicatable entities for `{:?}", client.id());
                         return Ok(());
                     }
-
-                    postcard_utils::to_extend_mut(&self.mappings_len, &mut message)?;
-                    message.extend_from_slice(&serialized[self.mappings.clone()]);
+                    message.extend_serialized(self.mappings.clone());
                 }
                 UpdateMessageFlags::DESPAWNS => {
                     if flag != last_flag {
-                        postcard_utils::to_extend_mut(&self.despawns_len, &mut message)?;
+                        message.write(&self.despawns_len)?;
                     }
                     for range in &self.despawns {
-                        message.extend_from_slice(&serialized[range.clone()]);
+                        message.extend_serialized(range.clone());
+                    }
+                }
+                UpdateMessageFlags::DESPAWN_BATCHES => {
+                    if flag != last_flag {
+                        message.write(&self.despawn_batches.len())?;
+                    }
+                    for range in &self.despawn_batches {
+                        message.extend_serialized(range.clone());
                     }
                 }
                 UpdateMessageFlags::REMOVALS => {
                     if flag != last_flag {
-                        postcard_utils::to_extend_mut(&self.removals.len(), &mut message)?;
+                        message.write(&self.removals.len())?;
                     }
                     for removals in &self.removals {
-                        message.extend_from_slice(&serialized[removals.entity.clone()]);
-                        postcard_utils::to_extend_mut(&removals.ids_len, &mut message)?;
-                        message.extend_from_slice(&serialized[removals.fn_ids.clone()]);
+                        message.extend_serialized(removals.entity.clone());
+                        message.write(&removals.ids_len)?;
+                        message.extend_serialized(removals.fn_ids.clone());
+                    }
+                }
+                UpdateMessageFlags::RESOURCES => {
+                    if flag != last_flag {
+                        message.write(&self.resources_len)?;
+                    }
+                    for range in &self.resources {
+                        message.extend_serialized(range.clone());
                     }
                 }
                 UpdateMessageFlags::CHANGES => {
                     // Changes are always last, don't write len for it.
                     for changes in &self.changes {
-                        message.extend_from_slice(&serialized[changes.entity.clone()]);
-                        postcard_utils::to_extend_mut(&changes.components_len, &mut message)?;
+                        message.extend_serialized(changes.entity.clone());
+                        message.write(&changes.header())?;
+                        if changes.flags != 0 {
+                            message.write(&changes.flags)?;
+                        }
                         for component in &changes.components {
-                            message.extend_from_slice(&serialized[component.clone()]);
+                            message.extend_serialized(component.clone());
                         }
                     }
                 }
@@ -300,23 +438,40 @@ impl UpdateMessage {
 
         debug_assert_eq!(message.len(), message_size);
 
-        server.send(client.id(), ReplicationChannel::Updates, message);
+        if client.is_virtual() {
+            client.receive_virtual(ReplicationChannel::Updates, message.len());
+        } else {
+            message.send(server, worker, client.id(), ReplicationChannel::Updates);
+        }
 
         Ok(())
     }
 
-    fn flags(&self) -> UpdateMessageFlags {
+    fn flags(&self, epoch: ReplicationEpoch, ticks_covered: u32) -> UpdateMessageFlags {
         let mut flags = UpdateMessageFlags::default();
 
+        if epoch != ReplicationEpoch::default() {
+            flags |= UpdateMessageFlags::EPOCH;
+        }
+        if ticks_covered != 1 {
+            flags |= UpdateMessageFlags::TICKS_COVERED;
+        }
+
         if !self.mappings.is_empty() {
             flags |= UpdateMessageFlags::MAPPINGS;
         }
         if !self.despawns.is_empty() {
             flags |= UpdateMessageFlags::DESPAWNS;
         }
+        if !self.despawn_batches.is_empty() {
+            flags |= UpdateMessageFlags::DESPAWN_BATCHES;
+        }
         if !self.removals.is_empty() {
             flags |= UpdateMessageFlags::REMOVALS;
         }
+        if !self.resources.is_empty() {
+            flags |= UpdateMessageFlags::RESOURCES;
+        }
         if !self.changes.is_empty() {
             flags |= UpdateMessageFlags::CHANGES;
         }
@@ -330,9 +485,13 @@ impl UpdateMessage {
     pub(super) fn clear(&mut self) {
         self.mappings = Default::default();
         self.mappings_len = 0;
+        self.baseline_mappings = false;
         self.despawns.clear();
         self.despawns_len = 0;
+        self.despawn_batches.clear();
         self.removals.clear();
+        self.resources.clear();
+        self.resources_len = 0;
         self.buffer
             .extend(self.changes.drain(..).map(|mut changes| {
                 changes.components.clear();
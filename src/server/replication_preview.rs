@@ -0,0 +1,187 @@
+use bevy::{ecs::component::ComponentId, prelude::*};
+
+use super::{
+    replicated_archetypes::ReplicatedArchetypes,
+    replication_messages::serialized_data::SerializedData, server_tick::ServerTick,
+};
+use crate::core::{
+    entity_serde::EntityObfuscation,
+    replication::{
+        replicated_clients::ReplicatedClients,
+        replication_prefabs::ReplicationPrefabs,
+        replication_registry::{ctx::SerializeCtx, ReplicationRegistry},
+        replication_rules::ReplicationRules,
+        Replicated,
+    },
+    ClientId,
+};
+
+/// Computation of what would be replicated to a client without sending anything.
+///
+/// Useful for tooling that tunes visibility settings or for editor previews.
+pub trait ReplicationPreviewExt {
+    /**
+    Returns everything that would be sent to a new client that sees only entities for which `is_visible` returns `true`.
+
+    Components are serialized with the registered functions, but nothing is sent
+    and no replication state is changed.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{prelude::*, server::replication_preview::ReplicationPreviewExt};
+
+    fn preview_radius(world: &World, origin: Vec3, radius: f32) -> usize {
+        let preview = world.preview_replication(|entity| {
+            world
+                .get::<Transform>(entity)
+                .is_some_and(|transform| transform.translation.distance(origin) <= radius)
+        });
+
+        preview.bytes()
+    }
+    ```
+    **/
+    fn preview_replication(&self, is_visible: impl Fn(Entity) -> bool) -> ReplicationPreview;
+
+    /// Like [`Self::preview_replication`], but uses the visibility of an already replicated client.
+    ///
+    /// Shows the full state of visible entities, as if the client just started replication.
+    /// Returns [`None`] if the client doesn't have replication enabled.
+    fn preview_client_replication(&self, client_id: ClientId) -> Option<ReplicationPreview>;
+}
+
+impl ReplicationPreviewExt for World {
+    fn preview_replication(&self, is_visible: impl Fn(Entity) -> bool) -> ReplicationPreview {
+        let mut preview = ReplicationPreview::default();
+        let Some(marker_id) = self.components().component_id::<Replicated>() else {
+            return preview;
+        };
+
+        let registry = self.resource::<ReplicationRegistry>();
+        let mut replicated_archetypes = ReplicatedArchetypes::new(marker_id);
+        replicated_archetypes.update(
+            self.archetypes(),
+            self.components(),
+            self.resource::<ReplicationRules>(),
+            registry,
+            self.resource::<ReplicationPrefabs>(),
+        );
+
+        let server_tick = self
+            .get_resource::<ServerTick>()
+            .map(|server_tick| **server_tick)
+            .unwrap_or_default();
+        let mut serialized = SerializedData::default();
+        serialized.set_obfuscation(self.get_resource::<EntityObfuscation>().copied());
+
+        for replicated_archetype in replicated_archetypes.iter() {
+            let archetype = self
+                .archetypes()
+                .get(replicated_archetype.id)
+                .expect("replicated archetypes should be obtained from the world");
+
+            for entity in archetype
+                .entities()
+                .iter()
+                .map(|entity| entity.id())
+                .filter(|&entity| is_visible(entity))
+            {
+                let entity_ref = self.entity(entity);
+                let start = serialized.len();
+                let mut components = Vec::with_capacity(replicated_archetype.components.len());
+
+                // Obfuscated entities take the same space for any client on average.
+                serialized
+                    .write_entity(ClientId::SERVER, entity)
+                    .expect("entity should always be serializable");
+                for replicated_component in &replicated_archetype.components {
+                    let (component_id, component_fns, rule_fns) =
+                        registry.get(replicated_component.fns_id);
+                    let component = entity_ref
+                        .get_by_id(component_id)
+                        .expect("component should be present in the archetype");
+
+                    let ctx = SerializeCtx {
+                        server_tick,
+                        component_id,
+                        diff: false,
+                    };
+                    if let Err(e) = serialized.write_component(
+                        rule_fns,
+                        component_fns,
+                        &ctx,
+                        replicated_component.fns_id,
+                        component,
+                    ) {
+                        error!("unable to serialize component for preview of `{entity}`: {e}");
+                        continue;
+                    }
+                    components.push(component_id);
+                }
+
+                preview.entities.push(PreviewEntity {
+                    entity,
+                    components,
+                    bytes: serialized.len() - start,
+                });
+            }
+        }
+
+        trace!(
+            "previewed replication of {} entities with {} bytes",
+            preview.entities.len(),
+            preview.bytes()
+        );
+
+        preview
+    }
+
+    fn preview_client_replication(&self, client_id: ClientId) -> Option<ReplicationPreview> {
+        let client = self
+            .get_resource::<ReplicatedClients>()?
+            .get_client(client_id)?;
+
+        Some(self.preview_replication(|entity| client.visibility().is_visible(entity)))
+    }
+}
+
+/// Result of [`ReplicationPreviewExt::preview_replication`].
+#[derive(Clone, Debug, Default)]
+pub struct ReplicationPreview {
+    /// Entities that would be replicated.
+    pub entities: Vec<PreviewEntity>,
+}
+
+impl ReplicationPreview {
+    /// Returns the preview of a specific entity if it would be replicated.
+    pub fn get(&self, entity: Entity) -> Option<&PreviewEntity> {
+        self.entities
+            .iter()
+            .find(|preview_entity| preview_entity.entity == entity)
+    }
+
+    /// Returns the estimated total size of all entities in bytes.
+    pub fn bytes(&self) -> usize {
+        self.entities
+            .iter()
+            .map(|preview_entity| preview_entity.bytes)
+            .sum()
+    }
+}
+
+/// An entity from [`ReplicationPreview`].
+#[derive(Clone, Debug)]
+pub struct PreviewEntity {
+    /// Server entity.
+    pub entity: Entity,
+
+    /// Replicated components of the entity.
+    pub components: Vec<ComponentId>,
+
+    /// Estimated size of the entity with its components in bytes.
+    ///
+    /// Message headers and prefab optimizations are not taken into account.
+    pub bytes: usize,
+}
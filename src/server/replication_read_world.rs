@@ -2,6 +2,7 @@ use bevy::{
     ecs::{
         archetype::{ArchetypeEntity, Archetypes},
         component::{ComponentId, ComponentTicks, Components, StorageType, Tick},
+        entity::Entities,
         query::{Access, FilteredAccess},
         storage::TableId,
         system::{ReadOnlySystemParam, SystemMeta, SystemParam},
@@ -68,6 +69,10 @@ impl<'w> ReplicationReadWorld<'w, '_> {
     pub(super) fn components(&self) -> &Components {
         self.world.components()
     }
+
+    pub(super) fn entities(&self) -> &Entities {
+        self.world.entities()
+    }
 }
 
 unsafe impl SystemParam for ReplicationReadWorld<'_, '_> {
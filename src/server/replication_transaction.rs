@@ -0,0 +1,93 @@
+use bevy::{
+    ecs::{component::Tick, entity::EntityHashMap, system::SystemChangeTick},
+    prelude::*,
+};
+
+/// Holds back replication of changes for an entity while present.
+///
+/// Insert it before a multi-component operation and remove it to commit.
+/// While the transaction is open, insertions and mutations for the entity are not sent.
+/// On commit, all components changed since the beginning of the transaction will be sent
+/// together with pending mutations in a single reliable update message,
+/// so clients never observe intermediate states.
+///
+/// Removals and despawns are not held back.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::replication_transaction::ReplicationTransaction};
+///
+/// fn begin(mut commands: Commands, player: Single<Entity, With<Health>>) {
+///     commands.entity(*player).insert(ReplicationTransaction);
+/// }
+///
+/// fn commit(mut commands: Commands, player: Single<Entity, With<Health>>) {
+///     commands.entity(*player).remove::<ReplicationTransaction>();
+/// }
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ReplicationTransaction;
+
+/// Open and committed replication transactions.
+#[derive(Resource, Default)]
+pub(crate) struct ReplicationTransactions {
+    /// Entities with open transactions and ticks on which they started.
+    active: EntityHashMap<Tick>,
+
+    /// Entities with transactions committed since the last replication and ticks on which they started.
+    committed: EntityHashMap<Tick>,
+}
+
+impl ReplicationTransactions {
+    /// Returns `true` if the entity has an open transaction.
+    pub(crate) fn is_active(&self, entity: Entity) -> bool {
+        !self.active.is_empty() && self.active.contains_key(&entity)
+    }
+
+    /// Returns the start tick if the entity committed its transaction since the last replication.
+    pub(crate) fn committed_tick(&self, entity: Entity) -> Option<Tick> {
+        if self.committed.is_empty() {
+            return None;
+        }
+        self.committed.get(&entity).copied()
+    }
+
+    /// Clears all committed transactions.
+    ///
+    /// Should be called after each replication.
+    pub(crate) fn clear_committed(&mut self) {
+        self.committed.clear();
+    }
+}
+
+pub(super) fn begin_transaction(
+    trigger: Trigger<OnAdd, ReplicationTransaction>,
+    change_tick: SystemChangeTick,
+    mut transactions: ResMut<ReplicationTransactions>,
+) {
+    trace!(
+        "beginning replication transaction for `{}`",
+        trigger.entity()
+    );
+    transactions
+        .active
+        .insert(trigger.entity(), change_tick.this_run());
+}
+
+pub(super) fn commit_transaction(
+    trigger: Trigger<OnRemove, ReplicationTransaction>,
+    mut transactions: ResMut<ReplicationTransactions>,
+) {
+    if let Some(tick) = transactions.active.remove(&trigger.entity()) {
+        trace!(
+            "committing replication transaction for `{}`",
+            trigger.entity()
+        );
+        transactions.committed.insert(trigger.entity(), tick);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{mem, ops::Range};
+
+use bevy::{
+    prelude::*,
+    tasks::{block_on, AsyncComputeTaskPool, Task},
+};
+
+use crate::core::{
+    replication::replicated_clients::ReplicatedClients, replicon_server::RepliconServer, ClientId,
+};
+
+/// Opt-in background assembly of replication messages on server.
+///
+/// Insert this resource to enable the worker. Collecting changes and serializing components
+/// still happens in [`ServerSet::Send`](super::ServerSet::Send) since it requires world access.
+/// But instead of copying serialized data into messages for each client, the server only plans
+/// messages and assembles them in a task on [`AsyncComputeTaskPool`].
+///
+/// Assembled messages are passed to [`RepliconServer`] on the next frame before
+/// [`ServerSet::SendPackets`](super::ServerSet::SendPackets). If the task hasn't finished by then,
+/// the server waits for it. This adds one frame of latency, so it's only worth it for servers with
+/// many clients where assembly takes noticeable time.
+///
+/// Messages for clients that disconnected in the meantime are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::replication_worker::ReplicationWorker};
+///
+/// # let mut app = App::new();
+/// app.init_resource::<ReplicationWorker>();
+/// ```
+#[derive(Resource, Default)]
+pub struct ReplicationWorker {
+    /// Messages planned for the current tick.
+    planned: Vec<PlannedMessage>,
+
+    /// Tasks that assemble messages, in the order they were spawned.
+    tasks: Vec<Task<Vec<(ClientId, u8, Vec<u8>)>>>,
+}
+
+impl ReplicationWorker {
+    /// Returns the number of ticks whose messages are still being assembled.
+    pub fn pending_ticks(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub(super) fn plan(&mut self, message: PlannedMessage) {
+        self.planned.push(message);
+    }
+
+    /// Spawns a task that assembles all planned messages.
+    ///
+    /// Takes serialized data, so it will be empty after the call.
+    pub(super) fn spawn(&mut self, serialized: &mut Vec<u8>) {
+        if self.planned.is_empty() {
+            return;
+        }
+
+        let planned = mem::take(&mut self.planned);
+        let serialized = mem::take(serialized);
+        trace!("assembling {} message(s) in background", planned.len());
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            planned
+                .into_iter()
+                .map(|message| message.assemble(&serialized))
+                .collect()
+        });
+
+        self.tasks.push(task);
+    }
+
+    /// Drops all planned and pending messages.
+    pub(super) fn clear(&mut self) {
+        self.planned.clear();
+        self.tasks.clear();
+    }
+}
+
+/// A message that references serialized data instead of containing a copy.
+pub(super) struct PlannedMessage {
+    pub(super) client_id: ClientId,
+    pub(super) channel: u8,
+    pub(super) size: usize,
+
+    /// Bytes written directly into the message, such as counts and headers.
+    pub(super) inline: Vec<u8>,
+
+    pub(super) segments: Vec<Segment>,
+}
+
+impl PlannedMessage {
+    fn assemble(self, serialized: &[u8]) -> (ClientId, u8, Vec<u8>) {
+        let mut message = Vec::with_capacity(self.size);
+        for segment in self.segments {
+            match segment {
+                Segment::Inline(range) => message.extend_from_slice(&self.inline[range]),
+                Segment::Serialized(range) => message.extend_from_slice(&serialized[range]),
+            }
+        }
+
+        debug_assert_eq!(message.len(), self.size);
+
+        (self.client_id, self.channel, message)
+    }
+}
+
+/// Part of a [`PlannedMessage`].
+pub(super) enum Segment {
+    /// Range in [`PlannedMessage::inline`].
+    Inline(Range<usize>),
+
+    /// Range in serialized data.
+    Serialized(Range<usize>),
+}
+
+/// Passes messages assembled on previous frames to the server.
+pub(super) fn send_assembled(
+    mut server: ResMut<RepliconServer>,
+    mut worker: ResMut<ReplicationWorker>,
+    replicated_clients: Res<ReplicatedClients>,
+) {
+    for task in worker.tasks.drain(..) {
+        for (client_id, channel, message) in block_on(task) {
+            if replicated_clients.get_client(client_id).is_some() {
+                server.send(client_id, channel, message);
+            } else {
+                trace!("dropping assembled message for disconnected `{client_id:?}`");
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::core::{postcard_utils, replication::replicated_resources::ReplicatedResources};
+
+/// Serializes `R` into [`ResourceBuffer`] on insertion or change and tracks its removal.
+///
+/// Registered by [`AppResourceExt::replicate_resource`](crate::core::replication::replicated_resources::AppResourceExt::replicate_resource).
+pub(crate) fn buffer_resource<R: Resource + Serialize>(
+    resource: Option<Res<R>>,
+    resources: Res<ReplicatedResources>,
+    mut resource_buffer: ResMut<ResourceBuffer>,
+) -> postcard::Result<()> {
+    let id = resources
+        .id::<R>()
+        .expect("resource should be registered for replication");
+
+    match resource {
+        Some(resource) if resource.is_changed() => {
+            let mut bytes = resource_buffer.take_value(id).unwrap_or_default();
+            bytes.clear();
+            postcard_utils::to_extend_mut(&*resource, &mut bytes)?;
+            resource_buffer.set_value(id, Some(bytes));
+        }
+        None if resource_buffer.contains(id) => {
+            resource_buffer.set_value(id, None);
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Serialized values of replicated resources.
+///
+/// Values are kept across ticks to send them to newly replicated clients.
+/// Changes should be cleaned up manually.
+#[derive(Default, Resource)]
+pub(crate) struct ResourceBuffer {
+    /// Serialized values, indexed by resource IDs.
+    ///
+    /// [`None`] if the resource doesn't exist.
+    values: Vec<Option<Vec<u8>>>,
+
+    /// Resources that were inserted, changed or removed since the last [`Self::clear_changes`].
+    changed: Vec<bool>,
+}
+
+impl ResourceBuffer {
+    /// Returns `true` if the resource with the given ID exists.
+    fn contains(&self, id: usize) -> bool {
+        self.values.get(id).is_some_and(Option::is_some)
+    }
+
+    fn take_value(&mut self, id: usize) -> Option<Vec<u8>> {
+        self.values.get_mut(id).and_then(Option::take)
+    }
+
+    fn set_value(&mut self, id: usize, value: Option<Vec<u8>>) {
+        if self.values.len() <= id {
+            self.values.resize(id + 1, None);
+            self.changed.resize(id + 1, false);
+        }
+        self.values[id] = value;
+        self.changed[id] = true;
+    }
+
+    /// Iterates over resource IDs with their serialized values and change status.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (usize, Option<&[u8]>, bool)> {
+        self.values
+            .iter()
+            .zip(&self.changed)
+            .enumerate()
+            .map(|(id, (value, &changed))| (id, value.as_deref(), changed))
+    }
+
+    pub(crate) fn clear_changes(&mut self) {
+        self.changed.fill(false);
+    }
+}
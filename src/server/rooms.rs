@@ -0,0 +1,193 @@
+use std::ops::Deref;
+
+use bevy::{
+    ecs::{component::ComponentId, world::DeferredWorld},
+    prelude::*,
+    utils::HashMap,
+};
+
+use super::{isolation_audit::IsolationDomain, ServerSet};
+use crate::core::{
+    common_conditions::server_running,
+    connected_clients::ConnectedClient,
+    replication::{
+        replicated_clients::{ReplicatedClients, VisibilityPolicy},
+        Replicated,
+    },
+    ClientId,
+};
+
+/// Scoping of replication into rooms.
+///
+/// Insert [`RoomMember`] into a client entity (see [`ConnectedClients::entity`](crate::core::connected_clients::ConnectedClients::entity))
+/// and [`InRoom`] into replicated entities. Entities in a room are replicated only to clients from the same room.
+/// Entities without [`InRoom`] are not affected, their visibility can be controlled manually.
+/// Clients without [`RoomMember`] don't receive any entities in rooms.
+///
+/// Both components can be changed at runtime to move clients and entities between rooms.
+/// Clients will receive despawns for entities from the previous room and spawns for entities
+/// from the new room.
+///
+/// Works by updating [`ClientVisibility`](crate::core::replication::replicated_clients::client_visibility::ClientVisibility)
+/// before sending, so [`ServerPlugin::visibility_policy`](super::ServerPlugin::visibility_policy)
+/// shouldn't be [`VisibilityPolicy::All`].
+///
+/// Both components also insert [`IsolationDomain`] with the room ID, so
+/// [`IsolationAudit`](super::isolation_audit::IsolationAudit) can be used to verify
+/// that entities don't leak between rooms.
+///
+/// Should be added only on server after [`RepliconPlugins`](crate::RepliconPlugins).
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     prelude::*,
+///     server::rooms::{InRoom, RoomId, RoomMember, RoomsPlugin},
+/// };
+///
+/// # let mut app = App::new();
+/// app.add_plugins((
+///     MinimalPlugins,
+///     RepliconPlugins.set(ServerPlugin {
+///         visibility_policy: VisibilityPolicy::Whitelist,
+///         ..Default::default()
+///     }),
+///     RoomsPlugin,
+/// ))
+/// .add_observer(join_lobby);
+///
+/// const LOBBY: RoomId = RoomId::new(0);
+///
+/// fn join_lobby(
+///     trigger: Trigger<ClientConnected>,
+///     mut commands: Commands,
+///     connected_clients: Res<ConnectedClients>,
+/// ) {
+///     let entity = connected_clients.entity(trigger.client_id).unwrap();
+///     commands.entity(entity).insert(RoomMember(LOBBY));
+///     commands.spawn((Replicated, InRoom(LOBBY)));
+/// }
+/// ```
+pub struct RoomsPlugin;
+
+impl Plugin for RoomsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Rooms>().add_systems(
+            PostUpdate,
+            update_rooms.before(ServerSet::Send).run_if(server_running),
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(replicated_clients) = app.world().get_resource::<ReplicatedClients>() {
+            assert!(
+                !matches!(
+                    replicated_clients.visibility_policy(),
+                    VisibilityPolicy::All
+                ),
+                "`RoomsPlugin` requires a visibility policy other than `{:?}`",
+                VisibilityPolicy::All
+            );
+        }
+    }
+}
+
+fn update_rooms(
+    mut rooms: ResMut<Rooms>,
+    mut replicated_clients: ResMut<ReplicatedClients>,
+    members: Query<(&ConnectedClient, &RoomMember)>,
+    entities: Query<(Entity, &InRoom), With<Replicated>>,
+) {
+    rooms.clients.clear();
+    rooms.clients.extend(
+        members
+            .iter()
+            .map(|(client, &RoomMember(room_id))| (client.id(), room_id)),
+    );
+
+    for client in replicated_clients.iter_mut() {
+        let client_room = rooms.client_room(client.id());
+        let visibility = client.visibility_mut();
+        for (entity, &InRoom(room_id)) in &entities {
+            let visible = client_room == Some(room_id);
+            if visibility.is_visible(entity) != visible {
+                visibility.set_visibility(entity, visible);
+            }
+        }
+    }
+}
+
+fn insert_domain<C: Component + Deref<Target = RoomId>>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _component_id: ComponentId,
+) {
+    let room_id = **world.get::<C>(entity).unwrap();
+    world
+        .commands()
+        .entity(entity)
+        .try_insert(IsolationDomain(room_id.get()));
+}
+
+fn remove_domain(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
+    world.commands().entity(entity).remove::<IsolationDomain>();
+}
+
+/// Rooms of connected clients.
+///
+/// Updated from [`RoomMember`] before sending replication.
+///
+/// See [`RoomsPlugin`] for details.
+#[derive(Resource, Default)]
+pub struct Rooms {
+    clients: HashMap<ClientId, RoomId>,
+}
+
+impl Rooms {
+    /// Returns the room of a client.
+    pub fn client_room(&self, client_id: ClientId) -> Option<RoomId> {
+        self.clients.get(&client_id).copied()
+    }
+
+    /// Returns an iterator over clients in a room.
+    pub fn clients(&self, room_id: RoomId) -> impl Iterator<Item = ClientId> + '_ {
+        self.clients
+            .iter()
+            .filter(move |(_, &client_room)| client_room == room_id)
+            .map(|(&client_id, _)| client_id)
+    }
+}
+
+/// Unique room ID.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Ord, PartialOrd, Reflect)]
+pub struct RoomId(u64);
+
+impl RoomId {
+    /// Creates a new ID wrapping the given value.
+    pub const fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Gets the value of this ID.
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Room of a client.
+///
+/// Should be inserted into a client entity.
+///
+/// See [`RoomsPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Deref, PartialEq, Eq)]
+#[component(on_insert = insert_domain::<Self>, on_remove = remove_domain)]
+pub struct RoomMember(pub RoomId);
+
+/// Room of a replicated entity.
+///
+/// See [`RoomsPlugin`] for details.
+#[derive(Component, Clone, Copy, Debug, Deref, PartialEq, Eq)]
+#[component(on_insert = insert_domain::<Self>, on_remove = remove_domain)]
+pub struct InRoom(pub RoomId);
@@ -0,0 +1,153 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*, utils::HashMap};
+
+use super::replication_messages::mutate_message::MutateMessage;
+use crate::core::ClientId;
+
+/// Opt-in byte budget for mutations sent to each client per tick.
+///
+/// Insert this resource to enable the budget. Entities with mutations for a client
+/// accumulate their [`ReplicationPriority`] on every tick. Mutations of entities with
+/// the highest accumulated priority are packed into the mutate message until
+/// [`Self::max_bytes`] is reached, and the rest are deferred to the next ticks.
+/// The accumulated priority of an entity resets when its mutations are sent,
+/// so entities with low priority are eventually replicated too.
+///
+/// At least one entity is sent per tick even if it doesn't fit into the budget.
+/// For per-client limits, see [`ReplicatedClient::set_bandwidth_cap`](crate::core::replication::replicated_clients::ReplicatedClient::set_bandwidth_cap).
+/// Only affects mutations. Insertions, removals and despawns are always sent since they are reliable.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     prelude::*,
+///     server::send_budget::{ReplicationPriority, SendBudget},
+/// };
+///
+/// # let mut app = App::new();
+/// app.insert_resource(SendBudget::new(1200));
+///
+/// // Replicate the player more often than other entities.
+/// app.world_mut().spawn((Replicated, ReplicationPriority(10.0)));
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct SendBudget {
+    /// Maximum number of bytes for serialized mutations per client per tick.
+    pub max_bytes: usize,
+
+    /// Accumulated priorities of entities with deferred mutations for each client.
+    accumulators: HashMap<ClientId, EntityHashMap<f32>>,
+
+    /// Number of entities with deferred mutations in the last send for all clients.
+    deferred_entities: usize,
+
+    /// Intermediate buffer with entities, their accumulated priorities and sizes for sorting.
+    buffer: Vec<(Entity, f32, usize)>,
+}
+
+impl Default for SendBudget {
+    /// Creates an unlimited budget, useful to track deferrals caused only by per-client caps.
+    fn default() -> Self {
+        Self::new(usize::MAX)
+    }
+}
+
+impl SendBudget {
+    /// Creates a new instance with the specified maximum number of bytes.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            accumulators: Default::default(),
+            deferred_entities: 0,
+            buffer: Default::default(),
+        }
+    }
+
+    /// Returns the accumulated priority of an entity whose mutations were deferred for a client.
+    ///
+    /// Returns [`None`] if the entity doesn't have deferred mutations.
+    pub fn accumulated_priority(&self, client_id: ClientId, entity: Entity) -> Option<f32> {
+        self.accumulators
+            .get(&client_id)
+            .and_then(|accumulators| accumulators.get(&entity))
+            .copied()
+    }
+
+    /// Returns the number of entities whose mutations were deferred in the last send for all clients.
+    pub fn deferred_entities(&self) -> usize {
+        self.deferred_entities
+    }
+
+    /// Prepares for a new send.
+    ///
+    /// Removes accumulators of clients for which `is_replicated` returns `false`.
+    pub(super) fn start_send(&mut self, mut is_replicated: impl FnMut(ClientId) -> bool) {
+        self.deferred_entities = 0;
+        self.accumulators
+            .retain(|&client_id, _| is_replicated(client_id));
+    }
+
+    /// Removes mutations of entities that don't fit into `max_bytes` from the message.
+    ///
+    /// Entities that stop having mutations are removed from the accumulators.
+    /// Returns the number of bytes of the remaining mutations.
+    pub(super) fn schedule(
+        &mut self,
+        client_id: ClientId,
+        mutate_message: &mut MutateMessage,
+        priorities: &Query<&ReplicationPriority>,
+        max_bytes: usize,
+    ) -> postcard::Result<usize> {
+        let accumulators = self.accumulators.entry(client_id).or_default();
+        self.buffer.clear();
+        for (entity, size) in mutate_message.entity_sizes() {
+            let priority = priorities.get(entity).copied().unwrap_or_default();
+            let accumulated = accumulators.get(&entity).copied().unwrap_or_default() + *priority;
+            self.buffer.push((entity, accumulated, size?));
+        }
+        accumulators.clear();
+
+        self.buffer.sort_by(|(_, a, _), (_, b, _)| b.total_cmp(a));
+        let mut written_bytes = 0;
+        for &(entity, accumulated, size) in &self.buffer {
+            if (written_bytes == 0 && max_bytes != 0) || written_bytes + size <= max_bytes {
+                written_bytes += size;
+            } else {
+                accumulators.insert(entity, accumulated);
+            }
+        }
+
+        if !accumulators.is_empty() {
+            trace!(
+                "send budget exceeded, deferring mutations for {} entities for `{client_id:?}`",
+                accumulators.len()
+            );
+            self.deferred_entities += accumulators.len();
+            mutate_message.retain_entities(|entity| !accumulators.contains_key(&entity));
+        }
+
+        Ok(written_bytes)
+    }
+
+    /// Removes all accumulated priorities.
+    pub(super) fn clear(&mut self) {
+        self.accumulators.clear();
+        self.deferred_entities = 0;
+    }
+}
+
+/// Priority of an entity for [`SendBudget`].
+///
+/// Added to the accumulated priority of the entity for each client on every tick in which
+/// it has mutations. Entities without this component have the default priority of 1.
+///
+/// Can be changed at any time, for example, based on the distance to players.
+#[derive(Component, Clone, Copy, Debug, Deref, DerefMut, PartialEq)]
+pub struct ReplicationPriority(pub f32);
+
+impl Default for ReplicationPriority {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
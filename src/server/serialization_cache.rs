@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+
+use bevy::{ecs::component::Tick, prelude::*, utils::HashMap};
+
+use crate::core::replication::replication_registry::FnsId;
+
+/// Opt-in cache of serialized component values across ticks.
+///
+/// Insert this resource to enable caching. Serialized bytes are stored per entity and component
+/// together with the component change tick. When an unchanged value needs to be sent again,
+/// for example because a mutation wasn't acknowledged yet or a client just gained visibility,
+/// the cached bytes are copied instead of serializing the component again.
+///
+/// When the total size of cached values exceeds [`Self::max_bytes`], the oldest values are evicted.
+///
+/// Serialization functions for cached components should depend only on the component value.
+/// If a function uses [`SerializeCtx::server_tick`](crate::core::replication::replication_registry::ctx::SerializeCtx::server_tick),
+/// the cache will return bytes serialized on the tick the component was changed.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::serialization_cache::SerializationCache};
+///
+/// # let mut app = App::new();
+/// app.insert_resource(SerializationCache::new(1024 * 1024));
+/// ```
+#[derive(Resource)]
+pub struct SerializationCache {
+    /// Serialized values with their change ticks.
+    entries: HashMap<(Entity, FnsId), CachedValue>,
+
+    /// Keys in insertion order for eviction.
+    ///
+    /// May contain outdated keys that were replaced by newer values.
+    order: VecDeque<(Entity, FnsId, Tick)>,
+
+    /// Maximum total size of cached values in bytes.
+    max_bytes: usize,
+
+    /// Current total size of cached values in bytes.
+    bytes: usize,
+
+    hits: usize,
+    misses: usize,
+}
+
+impl SerializationCache {
+    /// Creates a new instance that keeps up to `max_bytes` of serialized values.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            entries: Default::default(),
+            order: Default::default(),
+            max_bytes,
+            bytes: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the maximum total size of cached values in bytes.
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Returns the current total size of cached values in bytes.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Returns the number of cached values.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no cached values.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of times serialization was skipped.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns the number of times a value was serialized and cached.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Removes all cached values.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.bytes = 0;
+    }
+
+    /// Returns cached bytes if the value wasn't changed since it was cached.
+    pub(super) fn get(&mut self, entity: Entity, fns_id: FnsId, tick: Tick) -> Option<&[u8]> {
+        let value = self
+            .entries
+            .get(&(entity, fns_id))
+            .filter(|value| value.tick == tick)?;
+
+        self.hits += 1;
+        Some(&value.bytes)
+    }
+
+    /// Caches serialized bytes for a value changed on the given tick.
+    pub(super) fn insert(&mut self, entity: Entity, fns_id: FnsId, tick: Tick, bytes: &[u8]) {
+        self.misses += 1;
+        if bytes.len() > self.max_bytes {
+            return;
+        }
+
+        let value = CachedValue {
+            tick,
+            bytes: bytes.into(),
+        };
+        self.bytes += bytes.len();
+        if let Some(old_value) = self.entries.insert((entity, fns_id), value) {
+            self.bytes -= old_value.bytes.len();
+        }
+        self.order.push_back((entity, fns_id, tick));
+
+        while self.bytes > self.max_bytes {
+            let Some((entity, fns_id, tick)) = self.order.pop_front() else {
+                break;
+            };
+
+            let key = (entity, fns_id);
+            if self
+                .entries
+                .get(&key)
+                .is_some_and(|value| value.tick == tick)
+            {
+                trace!("evicting cached value for `{entity}`");
+                let value = self.entries.remove(&key).unwrap();
+                self.bytes -= value.bytes.len();
+            }
+        }
+
+        // Drop outdated keys if they accumulate faster than eviction.
+        if self.order.len() > self.entries.len() * 2 {
+            let entries = &self.entries;
+            self.order.retain(|(entity, fns_id, tick)| {
+                entries
+                    .get(&(*entity, *fns_id))
+                    .is_some_and(|value| value.tick == *tick)
+            });
+        }
+    }
+}
+
+struct CachedValue {
+    tick: Tick,
+    bytes: Box<[u8]>,
+}
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use crate::core::{protocol::replication_epoch::ReplicationEpoch, replication::Replicated};
+
+/// Stores current [`ReplicationEpoch`].
+///
+/// Used only on the server. Call [`Self::advance`] when you perform a full world reset,
+/// such as starting a new match in the same process. On change, all visible entities
+/// will be sent in full and clients will discard everything replicated in the previous epoch
+/// emitting [`WorldReset`](crate::client::world_reset::WorldReset).
+/// Despawns from the tick in which the epoch changed aren't sent, since clients
+/// despawn all entities from the previous epoch anyway.
+///
+/// Not reset when the server stops, clients adopt the current epoch on connection.
+///
+/// See also [`ServerResetExt::despawn_all_replicated`].
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::server_epoch::ServerEpoch};
+///
+/// fn start_new_match(
+///     mut commands: Commands,
+///     mut epoch: ResMut<ServerEpoch>,
+///     entities: Query<Entity, (With<Replicated>, Without<Persistent>)>,
+/// ) {
+///     for entity in &entities {
+///         commands.entity(entity).despawn();
+///     }
+///     epoch.advance();
+/// }
+///
+/// #[derive(Component)]
+/// struct Persistent;
+/// ```
+#[derive(Clone, Copy, Deref, Debug, Default, Resource)]
+pub struct ServerEpoch(ReplicationEpoch);
+
+impl ServerEpoch {
+    /// Switches to the next epoch.
+    #[inline]
+    pub fn advance(&mut self) {
+        self.0 = self.0.next();
+    }
+}
+
+/// Extension trait for resetting the replicated world.
+pub trait ServerResetExt {
+    /// Recursively despawns all entities with [`Replicated`] and advances [`ServerEpoch`].
+    ///
+    /// Instead of a despawn for each entity, clients receive only the new epoch
+    /// and despawn all mapped entities. Useful for match restarts within the same session.
+    fn despawn_all_replicated(&mut self);
+}
+
+impl ServerResetExt for Commands<'_, '_> {
+    fn despawn_all_replicated(&mut self) {
+        self.queue(|world: &mut World| world.despawn_all_replicated());
+    }
+}
+
+impl ServerResetExt for World {
+    fn despawn_all_replicated(&mut self) {
+        let mut replicated = self.query_filtered::<Entity, With<Replicated>>();
+        let entities: Vec<_> = replicated.iter(self).collect();
+        debug!("despawning {} replicated entities", entities.len());
+        for entity in entities {
+            // Could be despawned together with its parent.
+            if let Ok(entity) = self.get_entity_mut(entity) {
+                entity.despawn_recursive();
+            }
+        }
+
+        self.resource_mut::<ServerEpoch>().advance();
+    }
+}
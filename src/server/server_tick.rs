@@ -1,15 +1,15 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::core::replicon_tick::RepliconTick;
+use crate::core::{replicon_tick::RepliconTick, tick_policy::TickPolicy};
 
 /// Stores current [`RepliconTick`].
 ///
 /// Used only on the server. The [`ServerPlugin`](super::ServerPlugin) sends replication data
 /// in [`PostUpdate`] any time this resource changes.
-/// By default, its incremented in [`PostUpdate`] per the [`TickPolicy`](super::TickPolicy).
+/// By default, its incremented in [`PostUpdate`] per the [`ServerTickPolicy`].
 ///
-/// If you set [`TickPolicy::Manual`](super::TickPolicy::Manual), you can increment this resource
+/// If you set [`TickPolicy::Manual`], you can increment this resource
 /// at the start of your game loop (e.g. inside [`FixedMain`](bevy::app::FixedMain)).
 /// This value can be used to represent your simulation step, and is made available to the client in
 /// the custom deserialization, despawn, and component removal functions.
@@ -32,3 +32,36 @@ impl ServerTick {
         self.increment_by(1)
     }
 }
+
+/// Stores current [`TickPolicy`].
+///
+/// Initialized from [`ServerSessionPlugin::tick_policy`](super::ServerSessionPlugin::tick_policy).
+/// Can be changed at runtime: connected clients will be notified via [`TickRateChanged`](crate::core::tick_policy::TickRateChanged).
+/// Clients that connect later receive it only if the policy differs from the initial one.
+#[derive(Resource, Clone, Copy, Debug, Deref)]
+pub struct ServerTickPolicy {
+    #[deref]
+    policy: TickPolicy,
+
+    /// Policy from the plugin that clients are expected to know.
+    initial: TickPolicy,
+}
+
+impl ServerTickPolicy {
+    pub(super) fn new(policy: TickPolicy) -> Self {
+        Self {
+            policy,
+            initial: policy,
+        }
+    }
+
+    /// Changes the policy.
+    pub fn set(&mut self, policy: TickPolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns `true` if the policy differs from the one the server was started with.
+    pub fn is_renegotiated(&self) -> bool {
+        self.policy != self.initial
+    }
+}
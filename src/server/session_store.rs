@@ -0,0 +1,252 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{client_bundles::ClientOwner, ClientDisconnected};
+use crate::core::ClientId;
+
+/// Opt-in storage for per-client server state that survives reconnects.
+///
+/// Insert this resource to enable it. The store works with persistent client identities
+/// since [`ClientId`] may change on reconnect. Trigger [`ClientIdentified`] after the identity
+/// of a connected client is verified, for example by the messaging backend authentication
+/// or your own handshake event.
+///
+/// On disconnect of an identified client, entities with [`PersistSession`] and [`ClientOwner`]
+/// of this client are despawned and their registered components are stored. If the same identity
+/// is identified again within [`Self::timeout`], the entities are spawned back with the new
+/// [`ClientOwner`] and [`SessionRestored`] is triggered.
+///
+/// If you spawn such entities with [`ClientBundleAppExt`](super::client_bundles::ClientBundleAppExt),
+/// use [`LeavePolicy::Keep`](super::client_bundles::LeavePolicy::Keep) for them to be stored.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// use bevy_replicon::{prelude::*, server::session_store::SessionStore};
+///
+/// # let mut app = App::new();
+/// let mut store = SessionStore::new(Duration::from_secs(60));
+/// store.register_component::<Health>();
+/// app.insert_resource(store);
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+/// ```
+#[derive(Resource)]
+pub struct SessionStore {
+    /// How long a session is kept after disconnect.
+    pub timeout: Duration,
+
+    /// Functions that take registered components from an entity.
+    components: Vec<TakeFn>,
+
+    identities: HashMap<ClientId, ClientIdentity>,
+    sessions: HashMap<ClientIdentity, StoredSession>,
+}
+
+impl SessionStore {
+    /// Creates a new instance with the specified timeout.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            components: Default::default(),
+            identities: Default::default(),
+            sessions: Default::default(),
+        }
+    }
+
+    /// Registers component `C` to be stored on disconnect.
+    ///
+    /// Other components of stored entities are dropped.
+    pub fn register_component<C: Component>(&mut self) -> &mut Self {
+        self.components.push(take_component::<C>);
+        self
+    }
+
+    /// Returns the identity of a connected client if it was identified.
+    pub fn identity(&self, client_id: ClientId) -> Option<ClientIdentity> {
+        self.identities.get(&client_id).copied()
+    }
+
+    /// Returns `true` if there is a stored session for the identity.
+    pub fn contains(&self, identity: ClientIdentity) -> bool {
+        self.sessions.contains_key(&identity)
+    }
+
+    /// Returns the number of stored sessions.
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Returns `true` if there are no stored sessions.
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Removes the stored session for the identity.
+    ///
+    /// Returns `true` if it was present.
+    pub fn forget(&mut self, identity: ClientIdentity) -> bool {
+        self.sessions.remove(&identity).is_some()
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.identities.clear();
+        self.sessions.clear();
+    }
+}
+
+type TakeFn = fn(&mut EntityWorldMut) -> Option<InsertFn>;
+type InsertFn = Box<dyn FnOnce(&mut EntityWorldMut) + Send + Sync>;
+
+fn take_component<C: Component>(entity: &mut EntityWorldMut) -> Option<InsertFn> {
+    let component = entity.take::<C>()?;
+    Some(Box::new(move |entity| {
+        entity.insert(component);
+    }))
+}
+
+/// Registered components of entities owned by a disconnected client.
+struct StoredSession {
+    /// Time from [`Time::elapsed`] after which the session is removed.
+    expires_at: Duration,
+    entities: Vec<Vec<InsertFn>>,
+}
+
+/// Persistent identity of a client that doesn't change between reconnects.
+///
+/// For example, an account ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ClientIdentity(pub u64);
+
+/// Marks an entity owned by a client for storing in [`SessionStore`].
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct PersistSession;
+
+/// A trigger that associates a connected client with its persistent identity.
+///
+/// Restores the stored session if there is one. See [`SessionStore`].
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ClientIdentified {
+    pub client_id: ClientId,
+    pub identity: ClientIdentity,
+}
+
+/// A trigger emitted after restoring a stored session for a client.
+///
+/// Useful to configure visibility or other state for the spawned entities.
+#[derive(Event, Clone, Debug)]
+pub struct SessionRestored {
+    pub client_id: ClientId,
+    pub identity: ClientIdentity,
+
+    /// Spawned entities with restored components.
+    pub entities: Vec<Entity>,
+}
+
+pub(super) fn restore_session(trigger: Trigger<ClientIdentified>, mut commands: Commands) {
+    let ClientIdentified {
+        client_id,
+        identity,
+    } = *trigger;
+
+    commands.queue(move |world: &mut World| {
+        if !world.contains_resource::<SessionStore>() {
+            return;
+        }
+
+        let elapsed = world.resource::<Time>().elapsed();
+        let mut store = world.resource_mut::<SessionStore>();
+        debug!("identifying `{client_id:?}` as `{identity:?}`");
+        store.identities.insert(client_id, identity);
+        let Some(session) = store.sessions.remove(&identity) else {
+            return;
+        };
+        if session.expires_at < elapsed {
+            debug!("ignoring expired session for `{identity:?}`");
+            return;
+        }
+
+        debug!(
+            "restoring {} entities for `{client_id:?}`",
+            session.entities.len()
+        );
+        let mut entities = Vec::with_capacity(session.entities.len());
+        for components in session.entities {
+            let mut entity = world.spawn((PersistSession, ClientOwner(client_id)));
+            for insert in components {
+                (insert)(&mut entity);
+            }
+            entities.push(entity.id());
+        }
+
+        world.trigger(SessionRestored {
+            client_id,
+            identity,
+            entities,
+        });
+    });
+}
+
+pub(super) fn store_session(trigger: Trigger<ClientDisconnected>, mut commands: Commands) {
+    let client_id = trigger.client_id;
+    commands.queue(move |world: &mut World| {
+        if !world.contains_resource::<SessionStore>() {
+            return;
+        }
+
+        let elapsed = world.resource::<Time>().elapsed();
+        world.resource_scope(|world, mut store: Mut<SessionStore>| {
+            let Some(identity) = store.identities.remove(&client_id) else {
+                return;
+            };
+
+            let owned: Vec<_> = world
+                .query_filtered::<(Entity, &ClientOwner), With<PersistSession>>()
+                .iter(world)
+                .filter(|(_, owner)| ***owner == client_id)
+                .map(|(entity, _)| entity)
+                .collect();
+
+            debug!(
+                "storing {} entities for `{client_id:?}` as `{identity:?}`",
+                owned.len()
+            );
+            let mut entities = Vec::with_capacity(owned.len());
+            for entity in owned {
+                let mut entity = world.entity_mut(entity);
+                let components = store
+                    .components
+                    .iter()
+                    .filter_map(|take| take(&mut entity))
+                    .collect();
+                entity.despawn();
+                entities.push(components);
+            }
+
+            let expires_at = elapsed + store.timeout;
+            store.sessions.insert(
+                identity,
+                StoredSession {
+                    expires_at,
+                    entities,
+                },
+            );
+        });
+    });
+}
+
+pub(super) fn remove_expired(time: Res<Time>, mut store: ResMut<SessionStore>) {
+    let elapsed = time.elapsed();
+    store.sessions.retain(|identity, session| {
+        let expired = session.expires_at < elapsed;
+        if expired {
+            debug!("removing expired session for `{identity:?}`");
+        }
+        !expired
+    });
+}
@@ -0,0 +1,520 @@
+use bevy::{
+    ecs::{component::ComponentId, world::CommandQueue},
+    prelude::*,
+    reflect::TypeRegistry,
+};
+use bytes::Bytes;
+
+use super::{replication_messages::serialized_data::SerializedData, server_tick::ServerTick};
+use crate::core::{
+    channels::{ChannelKind, ReplicationChannel, RepliconChannel, RepliconChannels},
+    event::event_registry::EventRegistry,
+    replication::{
+        command_markers::{CommandMarkers, EntityMarkers},
+        deferred_entity::DeferredEntity,
+        replication_registry::{
+            ctx::{SerializeCtx, WriteCtx},
+            FnsId, ReplicationRegistry,
+        },
+        replication_rules::ReplicationRules,
+    },
+    replicon_tick::RepliconTick,
+    server_entity_map::ServerEntityMap,
+    ClientId,
+};
+
+/// Self-check of the replication setup.
+///
+/// Useful for dedicated servers to catch registration mistakes before players connect.
+pub trait SmokeTestExt {
+    /**
+    Checks registered replication rules and channels and returns a report.
+
+    - Each component from [`ReplicationRules`] is constructed via [`ReflectDefault`], serialized
+      and deserialized into a temporary entity using the registered functions. The result is compared
+      with the original value if the type supports [`PartialReflect::reflect_partial_eq`].
+      Components that aren't registered for reflection with `#[reflect(Component, Default)]`
+      are reported as [`ComponentStatus::NotReflected`].
+    - Channels of registered events and replication are checked against [`RepliconChannels`].
+    - A replication message is built for a synthetic entity with all successfully checked components.
+
+    Temporary entities are spawned without [`Replicated`](crate::core::replication::Replicated)
+    and despawned before returning, but hooks and observers for the checked components will be triggered.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{prelude::*, server::smoke_test::SmokeTestExt};
+
+    # let mut app = App::new();
+    app.add_systems(Startup, |world: &mut World| {
+        let report = world.run_smoke_test();
+        assert!(report.is_ok(), "replication setup is invalid: {report:?}");
+    });
+    ```
+    **/
+    fn run_smoke_test(&mut self) -> SmokeTestReport;
+}
+
+impl SmokeTestExt for World {
+    fn run_smoke_test(&mut self) -> SmokeTestReport {
+        let mut report = SmokeTestReport {
+            channel_issues: check_channels(self),
+            ..Default::default()
+        };
+
+        let mut fns_ids = Vec::new();
+        for rule in self.resource::<ReplicationRules>().iter() {
+            for &(_, fns_id) in &rule.components {
+                if !fns_ids.contains(&fns_id) {
+                    fns_ids.push(fns_id);
+                }
+            }
+        }
+
+        let server_tick = self
+            .get_resource::<ServerTick>()
+            .map(|server_tick| **server_tick)
+            .unwrap_or_default();
+        let type_registry = self.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+
+        self.resource_scope(|world, registry: Mut<ReplicationRegistry>| {
+            let message_entity = world.spawn_empty().id();
+            for fns_id in fns_ids {
+                let (component_id, ..) = registry.get(fns_id);
+                let status = check_component(
+                    world,
+                    &registry,
+                    &type_registry,
+                    message_entity,
+                    fns_id,
+                    server_tick,
+                );
+                let name = world
+                    .components()
+                    .get_name(component_id)
+                    .expect("rules should be registered with valid component IDs")
+                    .to_string();
+
+                if let ComponentStatus::Failed(e) = &status {
+                    error!("smoke test failed for `{name}`: {e}");
+                }
+                report.components.push(ComponentReport {
+                    fns_id,
+                    component_id,
+                    name,
+                    status,
+                });
+            }
+
+            report.message = build_message(
+                world,
+                &registry,
+                message_entity,
+                server_tick,
+                report
+                    .components
+                    .iter()
+                    .filter(|component| component.status == ComponentStatus::Passed)
+                    .map(|component| component.fns_id),
+            );
+            world.despawn(message_entity);
+        });
+
+        debug!(
+            "finished smoke test for {} components with {} channel issues",
+            report.components.len(),
+            report.channel_issues.len()
+        );
+
+        report
+    }
+}
+
+/// Inserts a default value into `message_entity` and checks its serialization round-trip.
+fn check_component(
+    world: &mut World,
+    registry: &ReplicationRegistry,
+    type_registry: &TypeRegistry,
+    message_entity: Entity,
+    fns_id: FnsId,
+    server_tick: RepliconTick,
+) -> ComponentStatus {
+    let (component_id, component_fns, rule_fns) = registry.get(fns_id);
+    let Some(type_id) = world
+        .components()
+        .get_info(component_id)
+        .and_then(|info| info.type_id())
+    else {
+        return ComponentStatus::NotReflected;
+    };
+    let Some(registration) = type_registry.get(type_id) else {
+        return ComponentStatus::NotReflected;
+    };
+    let (Some(reflect_default), Some(reflect_component)) = (
+        registration.data::<ReflectDefault>(),
+        registration.data::<ReflectComponent>(),
+    ) else {
+        return ComponentStatus::NotReflected;
+    };
+
+    let value = reflect_default.default();
+    if !world.entity(message_entity).contains_id(component_id) {
+        reflect_component.insert(
+            &mut world.entity_mut(message_entity),
+            value.as_partial_reflect(),
+            type_registry,
+        );
+    }
+
+    let ctx = SerializeCtx {
+        server_tick,
+        component_id,
+        diff: false,
+    };
+    let Ok(ptr) = world.entity(message_entity).get_by_id(component_id) else {
+        return ComponentStatus::Failed("component was removed on insertion".into());
+    };
+    let mut bytes = Vec::new();
+    // SAFETY: `component_fns`, `ptr` and `rule_fns` were created for the same component type.
+    if let Err(e) = unsafe { component_fns.serialize(&ctx, rule_fns, ptr, &mut bytes) } {
+        return ComponentStatus::Failed(format!("unable to serialize: {e}"));
+    }
+
+    let target = world.spawn_empty().id();
+    let entity_markers = EntityMarkers::new(world.resource::<CommandMarkers>());
+    let mut entity_map = ServerEntityMap::default();
+    let mut queue = CommandQueue::default();
+    let mut message = Bytes::from(bytes);
+    let result = {
+        let mut entity = DeferredEntity::new(world, target);
+        let mut commands = entity.commands(&mut queue);
+        let mut ctx = WriteCtx::new(&mut commands, &mut entity_map, component_id, server_tick);
+        ctx.ignore_mapping = true;
+
+        // SAFETY: `rule_fns` and `component_fns` were created for the same type.
+        unsafe {
+            component_fns.write(
+                &mut ctx,
+                rule_fns,
+                &entity_markers,
+                &mut entity,
+                &mut message,
+            )
+        }
+    };
+    queue.apply(world);
+
+    let status = if let Err(e) = result {
+        ComponentStatus::Failed(format!("unable to deserialize: {e}"))
+    } else if !message.is_empty() {
+        ComponentStatus::Failed(format!("{} unread bytes left", message.len()))
+    } else {
+        match reflect_component.reflect(world.entity(target)) {
+            Some(component) => {
+                if component.reflect_partial_eq(value.as_partial_reflect()) == Some(false) {
+                    ComponentStatus::Failed("deserialized value differs from the original".into())
+                } else {
+                    ComponentStatus::Passed
+                }
+            }
+            None => ComponentStatus::Failed("component wasn't inserted on write".into()),
+        }
+    };
+
+    world.despawn(target);
+
+    status
+}
+
+/// Serializes components of `message_entity` the same way as for an update message.
+fn build_message(
+    world: &World,
+    registry: &ReplicationRegistry,
+    message_entity: Entity,
+    server_tick: RepliconTick,
+    fns_ids: impl Iterator<Item = FnsId>,
+) -> MessageStatus {
+    let mut message = SerializedData::default();
+    if let Err(e) = message
+        .write_tick(server_tick)
+        .and_then(|_| message.write_entity(ClientId::SERVER, message_entity))
+    {
+        return MessageStatus::Failed(e.to_string());
+    }
+
+    let entity = world.entity(message_entity);
+    let mut components = 0;
+    for fns_id in fns_ids {
+        let (component_id, component_fns, rule_fns) = registry.get(fns_id);
+        let ptr = entity
+            .get_by_id(component_id)
+            .expect("checked components should be inserted into the message entity");
+        let ctx = SerializeCtx {
+            server_tick,
+            component_id,
+            diff: false,
+        };
+        if let Err(e) = message.write_component(rule_fns, component_fns, &ctx, fns_id, ptr) {
+            return MessageStatus::Failed(e.to_string());
+        }
+        components += 1;
+    }
+
+    MessageStatus::Built {
+        components,
+        bytes: message.len(),
+    }
+}
+
+fn check_channels(world: &World) -> Vec<ChannelIssue> {
+    let channels = world.resource::<RepliconChannels>();
+    let mut issues = Vec::new();
+
+    for (side, side_channels) in [
+        (ChannelSide::Server, channels.server_channels()),
+        (ChannelSide::Client, channels.client_channels()),
+    ] {
+        for (channel_id, channel) in side_channels.iter().enumerate() {
+            if channel.max_bytes.unwrap_or(channels.default_max_bytes) == 0 {
+                issues.push(ChannelIssue {
+                    side,
+                    channel_id: channel_id as u8,
+                    kind: ChannelIssueKind::ZeroMaxBytes,
+                });
+            }
+        }
+    }
+
+    check_kind(
+        &mut issues,
+        ChannelSide::Server,
+        channels.server_channels(),
+        ReplicationChannel::Updates.into(),
+        ChannelKind::Ordered,
+        "replication",
+    );
+
+    let Some(event_registry) = world.get_resource::<EventRegistry>() else {
+        return issues;
+    };
+
+    for event in event_registry.iter_server_events() {
+        check_exists(
+            &mut issues,
+            ChannelSide::Server,
+            channels.server_channels(),
+            event.auto_channel().unwrap_or(event.channel_id()),
+            event.type_name(),
+        );
+    }
+    for &channel_id in event_registry.ordered_server_channels() {
+        check_kind(
+            &mut issues,
+            ChannelSide::Server,
+            channels.server_channels(),
+            channel_id,
+            ChannelKind::Ordered,
+            "ordered server events",
+        );
+    }
+
+    for event in event_registry.iter_client_events() {
+        check_exists(
+            &mut issues,
+            ChannelSide::Client,
+            channels.client_channels(),
+            event.auto_channel().unwrap_or(event.channel_id()),
+            event.type_name(),
+        );
+    }
+    for &channel_id in event_registry.ordered_client_channels() {
+        check_kind(
+            &mut issues,
+            ChannelSide::Client,
+            channels.client_channels(),
+            channel_id,
+            ChannelKind::Ordered,
+            "ordered client events",
+        );
+    }
+
+    issues
+}
+
+fn check_exists(
+    issues: &mut Vec<ChannelIssue>,
+    side: ChannelSide,
+    channels: &[RepliconChannel],
+    channel_id: u8,
+    usage: &'static str,
+) -> bool {
+    if channels.len() <= channel_id as usize {
+        issues.push(ChannelIssue {
+            side,
+            channel_id,
+            kind: ChannelIssueKind::Missing { usage },
+        });
+        return false;
+    }
+
+    true
+}
+
+fn check_kind(
+    issues: &mut Vec<ChannelIssue>,
+    side: ChannelSide,
+    channels: &[RepliconChannel],
+    channel_id: u8,
+    expected: ChannelKind,
+    usage: &'static str,
+) {
+    if !check_exists(issues, side, channels, channel_id, usage) {
+        return;
+    }
+
+    let actual = channels[channel_id as usize].kind;
+    if actual != expected {
+        issues.push(ChannelIssue {
+            side,
+            channel_id,
+            kind: ChannelIssueKind::WrongKind {
+                usage,
+                expected,
+                actual,
+            },
+        });
+    }
+}
+
+/// Result of [`SmokeTestExt::run_smoke_test`].
+#[derive(Clone, Debug, Default)]
+pub struct SmokeTestReport {
+    /// Results for each component from [`ReplicationRules`].
+    pub components: Vec<ComponentReport>,
+
+    /// Found channel misconfigurations.
+    pub channel_issues: Vec<ChannelIssue>,
+
+    /// Result of building a message for a synthetic entity.
+    pub message: MessageStatus,
+}
+
+impl SmokeTestReport {
+    /// Returns `true` if no failures or channel issues were found.
+    ///
+    /// Components reported as [`ComponentStatus::NotReflected`] are not considered failures.
+    pub fn is_ok(&self) -> bool {
+        self.channel_issues.is_empty()
+            && !matches!(self.message, MessageStatus::Failed(_))
+            && !self
+                .components
+                .iter()
+                .any(|component| matches!(component.status, ComponentStatus::Failed(_)))
+    }
+
+    /// Returns iterator over components that failed the check.
+    pub fn failed_components(&self) -> impl Iterator<Item = &ComponentReport> {
+        self.components
+            .iter()
+            .filter(|component| matches!(component.status, ComponentStatus::Failed(_)))
+    }
+}
+
+/// Check result for a single registered rule function.
+#[derive(Clone, Debug)]
+pub struct ComponentReport {
+    /// ID of the checked functions.
+    pub fns_id: FnsId,
+
+    /// ID of the checked component.
+    pub component_id: ComponentId,
+
+    /// Type name of the checked component.
+    pub name: String,
+
+    /// Check result.
+    pub status: ComponentStatus,
+}
+
+/// Result of a component round-trip.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ComponentStatus {
+    /// The component was serialized and deserialized into the same value.
+    Passed,
+    /// The component can't be constructed without `#[reflect(Component, Default)]`.
+    NotReflected,
+    /// The round-trip failed with the specified reason.
+    Failed(String),
+}
+
+/// Result of building a message for a synthetic entity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MessageStatus {
+    /// The message was successfully built.
+    Built {
+        /// Number of serialized components.
+        components: usize,
+
+        /// Message size in bytes.
+        bytes: usize,
+    },
+    /// Building failed with the specified reason.
+    Failed(String),
+}
+
+impl Default for MessageStatus {
+    fn default() -> Self {
+        Self::Built {
+            components: 0,
+            bytes: 0,
+        }
+    }
+}
+
+/// A channel misconfiguration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChannelIssue {
+    /// Whether the channel is a server or a client channel.
+    pub side: ChannelSide,
+
+    /// ID of the channel from [`RepliconChannels`].
+    pub channel_id: u8,
+
+    /// What's wrong with the channel.
+    pub kind: ChannelIssueKind,
+}
+
+/// Side of a channel in [`RepliconChannels`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelSide {
+    /// Channel for messages from server to clients.
+    Server,
+    /// Channel for messages from clients to server.
+    Client,
+}
+
+/// Type of [`ChannelIssue`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelIssueKind {
+    /// The channel is used, but not present in [`RepliconChannels`].
+    Missing {
+        /// Type name of the event or a description of the channel usage.
+        usage: &'static str,
+    },
+    /// The channel has a delivery guarantee that doesn't match its usage.
+    WrongKind {
+        /// Description of the channel usage.
+        usage: &'static str,
+
+        /// Required delivery guarantee.
+        expected: ChannelKind,
+
+        /// Configured delivery guarantee.
+        actual: ChannelKind,
+    },
+    /// The channel has zero max bytes, so no messages can be sent over it.
+    ZeroMaxBytes,
+}
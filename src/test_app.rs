@@ -1,7 +1,13 @@
+pub mod assertions;
+pub mod loopback;
+pub mod message_sink;
+pub mod scripted_link;
+
 use bevy::prelude::*;
 
 use crate::{
     core::{
+        connected_clients::ConnectedClients,
         replication::replicated_clients::ReplicatedClients,
         replicon_client::{RepliconClient, RepliconClientStatus},
         replicon_server::RepliconServer,
@@ -84,8 +90,24 @@ pub trait ServerTestAppExt {
     ///
     /// Panics if a client app hasn't been connected before.
     fn exchange_with_client(&mut self, client_app: &mut App);
+
+    /// Updates [`self`] and the client app and exchanges messages between them
+    /// until the server has nothing more to send to the client.
+    ///
+    /// Returns the number of performed rounds.
+    /// Requires [`TickPolicy::EveryFrame`](crate::server::TickPolicy::EveryFrame)
+    /// to replicate on each update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a client app hasn't been connected before
+    /// or if the apps didn't sync after [`MAX_SYNC_ROUNDS`].
+    fn advance_until_synced(&mut self, client_app: &mut App) -> usize;
 }
 
+/// Maximum number of rounds for [`ServerTestAppExt::advance_until_synced`].
+pub const MAX_SYNC_ROUNDS: usize = 100;
+
 impl ServerTestAppExt for App {
     fn connect_client(&mut self, client_app: &mut App) {
         let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
@@ -94,15 +116,7 @@ impl ServerTestAppExt for App {
             "client can't be connected multiple times"
         );
 
-        // Use client number as ID.
-        // Server ID (0) will always be skipped.
-        let max_id = self
-            .world_mut()
-            .resource_mut::<ReplicatedClients>()
-            .iter_client_ids()
-            .max()
-            .unwrap_or(ClientId::SERVER);
-        let client_id = ClientId::new(max_id.get() + 1);
+        let client_id = next_client_id(self.world());
         client.set_status(RepliconClientStatus::Connected {
             client_id: Some(client_id),
         });
@@ -153,4 +167,51 @@ impl ServerTestAppExt for App {
             }
         })
     }
+
+    fn advance_until_synced(&mut self, client_app: &mut App) -> usize {
+        let client = client_app.world().resource::<RepliconClient>();
+        let client_id = client
+            .id()
+            .expect("client should have an assigned ID for syncing");
+
+        for round in 1..=MAX_SYNC_ROUNDS {
+            self.update();
+
+            let mut has_messages = false;
+            let mut server = self.world_mut().resource_mut::<RepliconServer>();
+            server.retain_sent(|&(sender_id, ..)| {
+                has_messages |= sender_id == client_id;
+                true
+            });
+
+            self.exchange_with_client(client_app);
+            client_app.update();
+            self.exchange_with_client(client_app);
+
+            if !has_messages {
+                return round;
+            }
+        }
+
+        panic!("`{client_id:?}` should sync within {MAX_SYNC_ROUNDS} rounds");
+    }
+}
+
+/// Returns an unused client ID for a simulated connection.
+///
+/// Uses client number as ID. Server ID (0) will always be skipped.
+fn next_client_id(world: &World) -> ClientId {
+    let connected_max = world
+        .resource::<ConnectedClients>()
+        .iter()
+        .map(|client| client.id())
+        .max();
+    let replicated_max = world
+        .get_resource::<ReplicatedClients>()
+        .and_then(|clients| clients.iter_client_ids().max());
+    let max_id = connected_max
+        .max(replicated_max)
+        .unwrap_or(ClientId::SERVER);
+
+    ClientId::new(max_id.get() + 1)
 }
@@ -0,0 +1,146 @@
+use std::{any, fmt::Debug};
+
+use bevy::prelude::*;
+
+use crate::core::{replication::Replicated, server_entity_map::ServerEntityMap};
+
+/**
+Asserts that the component of a server entity is replicated to the client with the same value.
+
+# Panics
+
+Panics if the entity is not replicated, doesn't have the component on either side,
+or the values differ.
+
+# Example
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    test_app::{assertions, ServerTestAppExt},
+};
+use serde::{Deserialize, Serialize};
+
+let mut server_app = App::new();
+let mut client_app = App::new();
+for app in [&mut server_app, &mut client_app] {
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .replicate::<Health>();
+}
+
+server_app.connect_client(&mut client_app);
+
+let server_entity = server_app.world_mut().spawn((Replicated, Health(100))).id();
+
+server_app.advance_until_synced(&mut client_app);
+assertions::assert_replicated::<Health>(&server_app, &client_app, server_entity);
+assertions::assert_synced::<Health>(&mut server_app, &mut client_app);
+
+#[derive(Component, Clone, Debug, PartialEq, Deserialize, Serialize)]
+struct Health(u32);
+```
+**/
+pub fn assert_replicated<C: Component + PartialEq + Debug>(
+    server_app: &App,
+    client_app: &App,
+    server_entity: Entity,
+) {
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity = *entity_map
+        .to_client()
+        .get(&server_entity)
+        .unwrap_or_else(|| panic!("server entity {server_entity} should be replicated"));
+
+    let type_name = any::type_name::<C>();
+    let server_component = server_app
+        .world()
+        .get::<C>(server_entity)
+        .unwrap_or_else(|| panic!("server entity {server_entity} should have `{type_name}`"));
+    let client_component = client_app
+        .world()
+        .get::<C>(client_entity)
+        .unwrap_or_else(|| panic!("client entity {client_entity} should have `{type_name}`"));
+
+    assert_eq!(
+        server_component, client_component,
+        "`{type_name}` of server entity {server_entity} should be equal to client entity {client_entity}"
+    );
+}
+
+/// Asserts that all replicated entities with the component have the same values on server and client.
+///
+/// See [`ReplicationSnapshot`] for details.
+pub fn assert_synced<C: Component + Clone + PartialEq + Debug>(
+    server_app: &mut App,
+    client_app: &mut App,
+) {
+    let server_snapshot = ReplicationSnapshot::<C>::server(server_app);
+    let client_snapshot = ReplicationSnapshot::<C>::client(client_app);
+    assert_eq!(
+        server_snapshot,
+        client_snapshot,
+        "client state of `{}` should match the server",
+        any::type_name::<C>()
+    );
+}
+
+/// Values of a component on all replicated entities, keyed by server entities.
+///
+/// Snapshots taken on server and client can be compared directly to check
+/// if the client state matches the server. Can also be stored to compare
+/// the state at different points of a test.
+#[derive(Debug, PartialEq)]
+pub struct ReplicationSnapshot<C>(Vec<(Entity, C)>);
+
+impl<C: Component + Clone> ReplicationSnapshot<C> {
+    /// Takes a snapshot of all entities with [`Replicated`] and `C` on server.
+    pub fn server(server_app: &mut App) -> Self {
+        let mut query = server_app
+            .world_mut()
+            .query_filtered::<(Entity, &C), With<Replicated>>();
+        let mut components: Vec<_> = query
+            .iter(server_app.world())
+            .map(|(entity, component)| (entity, component.clone()))
+            .collect();
+        components.sort_unstable_by_key(|&(entity, _)| entity);
+
+        Self(components)
+    }
+
+    /// Takes a snapshot of all entities with [`Replicated`] and `C` on client.
+    ///
+    /// Entities are mapped to their server counterparts. Entities without a mapping
+    /// are kept as is, so they won't match the server snapshot.
+    pub fn client(client_app: &mut App) -> Self {
+        let mut query = client_app
+            .world_mut()
+            .query_filtered::<(Entity, &C), With<Replicated>>();
+        let entity_map = client_app.world().resource::<ServerEntityMap>();
+        let mut components: Vec<_> = query
+            .iter(client_app.world())
+            .map(|(entity, component)| {
+                let server_entity = entity_map
+                    .to_server()
+                    .get(&entity)
+                    .copied()
+                    .unwrap_or(entity);
+                (server_entity, component.clone())
+            })
+            .collect();
+        components.sort_unstable_by_key(|&(entity, _)| entity);
+
+        Self(components)
+    }
+
+    /// Returns entities and component values, sorted by entity.
+    pub fn components(&self) -> &[(Entity, C)] {
+        &self.0
+    }
+}
@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+
+use super::ServerTestAppExt;
+
+/**
+In-process clients attached to a server app with scripted behaviors.
+
+Uses [`ServerTestAppExt`] to exchange messages without any messaging backend.
+Useful for soak tests of replication setups.
+
+# Example
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    test_app::loopback::{ClientScript, LoopbackClients},
+};
+use serde::{Deserialize, Serialize};
+
+let mut server_app = App::new();
+server_app
+    .add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .add_client_event::<Jump>(ChannelKind::Ordered)
+    .finish();
+
+let mut clients = LoopbackClients::default();
+for _ in 0..4 {
+    let mut client_app = App::new();
+    client_app
+        .add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_client_event::<Jump>(ChannelKind::Ordered)
+        .finish();
+
+    let index = clients.connect(&mut server_app, client_app);
+    clients.add_script(index, ClientScript::send_event(2, Jump));
+    clients.add_script(index, ClientScript::reconnect(10));
+}
+
+clients.run(&mut server_app, 20);
+
+#[derive(Event, Clone, Deserialize, Serialize)]
+struct Jump;
+```
+**/
+#[derive(Default)]
+pub struct LoopbackClients {
+    clients: Vec<LoopbackClient>,
+    step: usize,
+}
+
+impl LoopbackClients {
+    /// Connects a client app to the server app and returns its index.
+    ///
+    /// Internally updates both apps one time.
+    pub fn connect(&mut self, server_app: &mut App, mut client_app: App) -> usize {
+        server_app.connect_client(&mut client_app);
+        self.clients.push(LoopbackClient {
+            app: client_app,
+            scripts: Default::default(),
+        });
+
+        self.clients.len() - 1
+    }
+
+    /// Adds a script for a client by its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no client with this index.
+    pub fn add_script(&mut self, index: usize, script: ClientScript) {
+        self.clients[index].scripts.push(script);
+    }
+
+    /// Runs [`Self::step`] the specified number of times.
+    pub fn run(&mut self, server_app: &mut App, steps: usize) {
+        for _ in 0..steps {
+            self.step(server_app);
+        }
+    }
+
+    /// Runs all client scripts and then updates all apps, exchanging messages in both directions.
+    pub fn step(&mut self, server_app: &mut App) {
+        for client in &mut self.clients {
+            let mut ctx = ScriptCtx {
+                app: &mut client.app,
+                step: self.step,
+                reconnect: false,
+            };
+            for script in &mut client.scripts {
+                (script.0)(&mut ctx);
+            }
+
+            if ctx.reconnect {
+                server_app.disconnect_client(&mut client.app);
+                server_app.connect_client(&mut client.app);
+            }
+        }
+
+        for client in &mut self.clients {
+            client.app.update();
+            server_app.exchange_with_client(&mut client.app);
+        }
+        server_app.update();
+        for client in &mut self.clients {
+            server_app.exchange_with_client(&mut client.app);
+        }
+
+        self.step += 1;
+    }
+
+    /// Returns the number of completed steps.
+    pub fn steps(&self) -> usize {
+        self.step
+    }
+
+    /// Returns a client app by its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no client with this index.
+    pub fn app(&self, index: usize) -> &App {
+        &self.clients[index].app
+    }
+
+    /// Returns a mutable client app by its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no client with this index.
+    pub fn app_mut(&mut self, index: usize) -> &mut App {
+        &mut self.clients[index].app
+    }
+
+    /// Returns an iterator over client apps.
+    pub fn iter(&self) -> impl Iterator<Item = &App> {
+        self.clients.iter().map(|client| &client.app)
+    }
+
+    /// Returns a mutable iterator over client apps.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut App> {
+        self.clients.iter_mut().map(|client| &mut client.app)
+    }
+
+    /// Returns the number of clients.
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Returns `true` if there are no clients.
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+}
+
+struct LoopbackClient {
+    app: App,
+    scripts: Vec<ClientScript>,
+}
+
+/// Scripted behavior for a client from [`LoopbackClients`].
+///
+/// Runs before each step.
+pub struct ClientScript(Box<dyn FnMut(&mut ScriptCtx)>);
+
+impl ClientScript {
+    /// Creates a script that runs on each step.
+    pub fn new(script: impl FnMut(&mut ScriptCtx) + 'static) -> Self {
+        Self(Box::new(script))
+    }
+
+    /// Creates a script that runs every `period` steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is 0.
+    pub fn every(period: usize, mut script: impl FnMut(&mut ScriptCtx) + 'static) -> Self {
+        assert_ne!(period, 0, "period should be greater than 0");
+        Self::new(move |ctx| {
+            if ctx.step % period == 0 {
+                (script)(ctx);
+            }
+        })
+    }
+
+    /// Creates a script that sends a client event every `period` steps.
+    pub fn send_event<E: Event + Clone>(period: usize, event: E) -> Self {
+        Self::every(period, move |ctx| {
+            ctx.app.world_mut().send_event(event.clone());
+        })
+    }
+
+    /// Creates a script that reconnects the client every `period` steps.
+    pub fn reconnect(period: usize) -> Self {
+        Self::every(period, |ctx| {
+            if ctx.step != 0 {
+                ctx.reconnect();
+            }
+        })
+    }
+}
+
+/// Context for [`ClientScript`].
+pub struct ScriptCtx<'a> {
+    /// Client app.
+    ///
+    /// Can be used to mutate the client world, for example, to move a player.
+    pub app: &'a mut App,
+
+    /// Current step of [`LoopbackClients`].
+    pub step: usize,
+
+    reconnect: bool,
+}
+
+impl ScriptCtx<'_> {
+    /// Requests reconnection of the client after all its scripts for the step.
+    pub fn reconnect(&mut self) {
+        self.reconnect = true;
+    }
+}
@@ -0,0 +1,164 @@
+use bevy::{prelude::*, utils::HashMap};
+use bytes::Bytes;
+
+use super::next_client_id;
+use crate::{
+    core::{replicon_server::RepliconServer, ClientId, DisconnectReason},
+    server::{ClientConnected, ClientDisconnected},
+};
+
+/**
+In-memory clients that only record messages sent to them by a server app.
+
+Allows running all server logic without any messaging backend or client apps.
+Useful for simulation services and golden tests that validate the exact bytes
+produced by the protocol.
+
+Sinks never reply, so mutations will not be acknowledged and will be resent on each tick.
+
+# Example
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::channels::ReplicationChannel, prelude::*, test_app::message_sink::MessageSinks};
+
+let mut server_app = App::new();
+server_app
+    .add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .finish();
+
+let mut sinks = MessageSinks::default();
+let client_id = sinks.connect(&mut server_app);
+
+server_app.world_mut().spawn(Replicated);
+server_app.update();
+sinks.collect(&mut server_app);
+
+let messages = sinks.messages(client_id);
+assert_eq!(messages.len(), 1);
+assert_eq!(messages[0].0, ReplicationChannel::Updates as u8);
+```
+**/
+#[derive(Default)]
+pub struct MessageSinks {
+    sinks: HashMap<ClientId, Vec<(u8, Bytes)>>,
+}
+
+impl MessageSinks {
+    /// Starts the server in the app and connects a new sink to it.
+    ///
+    /// Returns the ID assigned to the sink.
+    /// Internally updates the app once.
+    pub fn connect(&mut self, server_app: &mut App) -> ClientId {
+        let client_id = next_client_id(server_app.world());
+
+        let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+        server.set_running(true);
+
+        server_app
+            .world_mut()
+            .trigger(ClientConnected { client_id });
+        server_app.update();
+
+        self.sinks.insert(client_id, Default::default());
+
+        client_id
+    }
+
+    /// Disconnects a sink from the server app and returns its recorded messages.
+    ///
+    /// Internally updates the app once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no sink with this ID.
+    pub fn disconnect(&mut self, server_app: &mut App, client_id: ClientId) -> Vec<(u8, Bytes)> {
+        let messages = self
+            .sinks
+            .remove(&client_id)
+            .unwrap_or_else(|| panic!("`{client_id:?}` should be a connected sink"));
+
+        server_app.world_mut().trigger(ClientDisconnected {
+            client_id,
+            reason: DisconnectReason::DisconnectedByServer,
+        });
+        server_app.update();
+
+        messages
+    }
+
+    /// Connects a temporary sink and returns messages with the initial state of the server world.
+    ///
+    /// Useful to pin the wire format by comparing bytes produced from a known world with stored ones.
+    /// See also [`ProtocolVersion`](crate::core::protocol_version::ProtocolVersion).
+    ///
+    /// Requires [`TickPolicy::EveryFrame`](crate::server::TickPolicy::EveryFrame)
+    /// to replicate on each update. Internally updates the app twice.
+    pub fn capture(server_app: &mut App) -> Vec<(u8, Bytes)> {
+        let mut sinks = Self::default();
+        let client_id = sinks.connect(server_app);
+        sinks.collect(server_app);
+        sinks.disconnect(server_app, client_id)
+    }
+
+    /// Moves messages sent to sinks from the server app into sinks.
+    ///
+    /// Messages for other clients are kept in the server.
+    pub fn collect(&mut self, server_app: &mut App) {
+        let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+        server.retain_sent(|(client_id, channel_id, message)| {
+            if let Some(messages) = self.sinks.get_mut(client_id) {
+                messages.push((*channel_id, message.clone()));
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Returns recorded messages for a sink as channel IDs with message bytes, in the order they were sent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no sink with this ID.
+    pub fn messages(&self, client_id: ClientId) -> &[(u8, Bytes)] {
+        self.sinks
+            .get(&client_id)
+            .unwrap_or_else(|| panic!("`{client_id:?}` should be a connected sink"))
+    }
+
+    /// Takes recorded messages for a sink, leaving it empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no sink with this ID.
+    pub fn take(&mut self, client_id: ClientId) -> Vec<(u8, Bytes)> {
+        let messages = self
+            .sinks
+            .get_mut(&client_id)
+            .unwrap_or_else(|| panic!("`{client_id:?}` should be a connected sink"));
+
+        std::mem::take(messages)
+    }
+
+    /// Returns an iterator over sink IDs.
+    pub fn iter_client_ids(&self) -> impl Iterator<Item = ClientId> + '_ {
+        self.sinks.keys().copied()
+    }
+
+    /// Returns the number of sinks.
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// Returns `true` if there are no sinks.
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+}
@@ -0,0 +1,225 @@
+use bevy::prelude::*;
+use bytes::Bytes;
+
+use crate::core::{replicon_client::RepliconClient, replicon_server::RepliconServer};
+
+/**
+Deterministic connection between a server app and a client app with scripted delivery.
+
+Each message sent by either side is passed to a script that decides its [`Delivery`].
+Messages can be delivered immediately, delayed by a number of exchanges, held until
+[`Self::release_held`], dropped or duplicated. Useful for regression tests of
+out-of-order and lost messages.
+
+Unlike [`ServerTestAppExt::exchange_with_client`](super::ServerTestAppExt::exchange_with_client),
+time is measured in virtual ticks, which are advanced by each [`Self::exchange`].
+
+# Example
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::channels::ReplicationChannel,
+    prelude::*,
+    test_app::{
+        scripted_link::{Delivery, ScriptedLink},
+        ServerTestAppExt,
+    },
+};
+
+let mut server_app = App::new();
+let mut client_app = App::new();
+for app in [&mut server_app, &mut client_app] {
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ));
+}
+
+server_app.connect_client(&mut client_app);
+
+// Drop all mutate messages.
+let mut link = ScriptedLink::new(|packet| {
+    if packet.channel_id == ReplicationChannel::Mutations as u8 {
+        Delivery::Drop
+    } else {
+        Delivery::Deliver
+    }
+});
+
+server_app.update();
+link.exchange(&mut server_app, &mut client_app);
+client_app.update();
+```
+**/
+pub struct ScriptedLink {
+    /// Decides what to do with each sent message.
+    script: Box<dyn FnMut(&Packet) -> Delivery + Send + Sync>,
+
+    /// Number of performed exchanges.
+    tick: u32,
+
+    /// Number of messages passed to the script.
+    sequence: usize,
+
+    /// Messages scheduled for delivery.
+    in_flight: Vec<(u32, Packet)>,
+
+    /// Messages waiting for [`Self::release_held`].
+    held: Vec<Packet>,
+}
+
+impl ScriptedLink {
+    /// Creates a new link with the given script.
+    pub fn new(script: impl FnMut(&Packet) -> Delivery + Send + Sync + 'static) -> Self {
+        Self {
+            script: Box::new(script),
+            tick: 0,
+            sequence: 0,
+            in_flight: Default::default(),
+            held: Default::default(),
+        }
+    }
+
+    /// Replaces the script for messages sent after this call.
+    pub fn set_script(&mut self, script: impl FnMut(&Packet) -> Delivery + Send + Sync + 'static) {
+        self.script = Box::new(script);
+    }
+
+    /// Returns the number of performed exchanges.
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Returns the number of scheduled messages that weren't delivered yet.
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Returns the number of held messages.
+    pub fn held_count(&self) -> usize {
+        self.held.len()
+    }
+
+    /// Schedules all held messages for delivery on the next exchange.
+    pub fn release_held(&mut self) {
+        let tick = self.tick + 1;
+        self.in_flight
+            .extend(self.held.drain(..).map(|packet| (tick, packet)));
+    }
+
+    /// Collects sent messages from both apps, passes them to the script,
+    /// advances the virtual tick and delivers all due messages.
+    ///
+    /// Messages that are due on the same tick are delivered in the order they were sent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client app isn't connected.
+    pub fn exchange(&mut self, server_app: &mut App, client_app: &mut App) {
+        let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+        let client_id = client
+            .id()
+            .expect("client should have an assigned ID for exchanging messages");
+
+        self.tick += 1;
+        let mut sent = Vec::new();
+        for (channel_id, message) in client.drain_sent() {
+            sent.push((Direction::ToServer, channel_id, message));
+        }
+
+        let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+        server.retain_sent(|(sender_id, channel_id, message)| {
+            if *sender_id == client_id {
+                sent.push((Direction::ToClient, *channel_id, message.clone()));
+                false
+            } else {
+                true
+            }
+        });
+
+        for (direction, channel_id, message) in sent {
+            let packet = Packet {
+                direction,
+                channel_id,
+                message,
+                sequence: self.sequence,
+            };
+            self.sequence += 1;
+
+            match (self.script)(&packet) {
+                Delivery::Deliver => self.in_flight.push((self.tick, packet)),
+                Delivery::Delay(ticks) => self.in_flight.push((self.tick + ticks, packet)),
+                Delivery::Hold => self.held.push(packet),
+                Delivery::Drop => trace!("dropping {packet:?}"),
+                Delivery::Duplicate => {
+                    self.in_flight.push((self.tick, packet.clone()));
+                    self.in_flight.push((self.tick, packet));
+                }
+            }
+        }
+
+        self.in_flight
+            .sort_by_key(|(tick, packet)| (*tick, packet.sequence));
+        let due = self
+            .in_flight
+            .iter()
+            .take_while(|&&(tick, _)| tick <= self.tick)
+            .count();
+        for (_, packet) in self.in_flight.drain(..due) {
+            match packet.direction {
+                Direction::ToClient => {
+                    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+                    client.insert_received(packet.channel_id, packet.message);
+                }
+                Direction::ToServer => {
+                    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+                    server.insert_received(client_id, packet.channel_id, packet.message);
+                }
+            }
+        }
+    }
+}
+
+/// A message passed to the script of [`ScriptedLink`].
+#[derive(Clone, Debug)]
+pub struct Packet {
+    /// Receiver side of the message.
+    pub direction: Direction,
+
+    /// Channel over which the message was sent.
+    pub channel_id: u8,
+
+    /// Message content.
+    pub message: Bytes,
+
+    /// Index of the message among all messages passed through the link.
+    pub sequence: usize,
+}
+
+/// Direction of a [`Packet`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Sent from server to client.
+    ToClient,
+    /// Sent from client to server.
+    ToServer,
+}
+
+/// What [`ScriptedLink`] should do with a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Delivery {
+    /// Deliver on the current exchange.
+    Deliver,
+    /// Deliver after the specified number of exchanges.
+    Delay(u32),
+    /// Keep until [`ScriptedLink::release_held`].
+    Hold,
+    /// Never deliver.
+    Drop,
+    /// Deliver two copies on the current exchange.
+    Duplicate,
+}
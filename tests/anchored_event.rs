@@ -0,0 +1,122 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    addons::anchored_event::{AnchorReached, AnchorSettings, Anchored, AnchoredEventAppExt},
+    core::replicon_tick::RepliconTick,
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn interpolation_delay() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_anchored_event::<DummyEvent>(ChannelKind::Ordered)
+        .insert_resource(AnchorSettings {
+            interpolation_delay: 1,
+            ..Default::default()
+        })
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    send_anchored(&mut server_app, 1, DummyEvent(0));
+    exchange(&mut server_app, &mut client_app);
+    assert!(read_reached(&mut client_app).is_empty());
+
+    send_anchored(&mut server_app, 2, DummyEvent(1));
+    exchange(&mut server_app, &mut client_app);
+    assert_eq!(read_reached(&mut client_app), [(1, DummyEvent(0), 0)]);
+}
+
+#[test]
+fn late_and_duplicate() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_anchored_event::<DummyEvent>(ChannelKind::Ordered)
+        .insert_resource(AnchorSettings {
+            max_late_ticks: 2,
+            ..Default::default()
+        })
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    send_anchored(&mut server_app, 5, DummyEvent(0));
+    exchange(&mut server_app, &mut client_app);
+    assert_eq!(read_reached(&mut client_app), [(5, DummyEvent(0), 0)]);
+
+    send_anchored(&mut server_app, 5, DummyEvent(0));
+    send_anchored(&mut server_app, 3, DummyEvent(1));
+    send_anchored(&mut server_app, 2, DummyEvent(2));
+    exchange(&mut server_app, &mut client_app);
+    assert_eq!(
+        read_reached(&mut client_app),
+        [(3, DummyEvent(1), 2)],
+        "duplicate and too late events should be ignored"
+    );
+}
+
+#[test]
+fn local() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_anchored_event::<DummyEvent>(ChannelKind::Ordered)
+        .insert_resource(AnchorSettings {
+            interpolation_delay: 10,
+            ..Default::default()
+        })
+        .finish();
+
+    app.world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    send_anchored(&mut app, 1, DummyEvent(0));
+    app.update();
+    app.update();
+
+    assert_eq!(read_reached(&mut app), [(1, DummyEvent(0), 0)]);
+}
+
+fn send_anchored(server_app: &mut App, tick: u32, event: DummyEvent) {
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: Anchored::new(RepliconTick::new(tick), event),
+    });
+}
+
+fn exchange(server_app: &mut App, client_app: &mut App) {
+    server_app.update();
+    server_app.exchange_with_client(client_app);
+    client_app.update();
+}
+
+fn read_reached(app: &mut App) -> Vec<(u32, DummyEvent, u32)> {
+    app.world_mut()
+        .resource_mut::<Events<AnchorReached<DummyEvent>>>()
+        .drain()
+        .map(|reached| (reached.tick.get(), reached.event, reached.late_ticks))
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+struct DummyEvent(u8);
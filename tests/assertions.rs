@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    test_app::{
+        assertions::{self, ReplicationSnapshot},
+        ServerTestAppExt,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn synced_after_mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.advance_until_synced(&mut client_app);
+    assertions::assert_replicated::<TestComponent>(&server_app, &client_app, server_entity);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_entity)
+        .unwrap();
+    component.0 = 1;
+
+    server_app.advance_until_synced(&mut client_app);
+    assertions::assert_synced::<TestComponent>(&mut server_app, &mut client_app);
+
+    let rounds = server_app.advance_until_synced(&mut client_app);
+    assert_eq!(rounds, 1, "nothing should be sent without changes");
+}
+
+#[test]
+fn snapshot_mismatch() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((Replicated, TestComponent(0)));
+
+    let server_snapshot = ReplicationSnapshot::<TestComponent>::server(&mut server_app);
+    let client_snapshot = ReplicationSnapshot::<TestComponent>::client(&mut client_app);
+    assert_eq!(server_snapshot.components().len(), 1);
+    assert_ne!(server_snapshot, client_snapshot);
+
+    server_app.advance_until_synced(&mut client_app);
+
+    let client_snapshot = ReplicationSnapshot::<TestComponent>::client(&mut client_app);
+    assert_eq!(server_snapshot, client_snapshot);
+}
+
+#[derive(Component, Clone, Debug, PartialEq, Deserialize, Serialize)]
+struct TestComponent(u32);
@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    server::replication_audit::{AuditAction, ReplicationAudit},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn lifecycle() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app.insert_resource(ReplicationAudit::new(16));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let audit = server_app.world().resource::<ReplicationAudit>();
+    let actions: Vec<_> = audit
+        .entity_history(client_id, server_entity)
+        .map(|entry| entry.action)
+        .collect();
+
+    assert!(matches!(
+        actions[..],
+        [
+            AuditAction::Insertion(_),
+            AuditAction::Mutation(_),
+            AuditAction::Despawn
+        ]
+    ));
+    assert!(audit.entries().all(|entry| entry.bytes > 0));
+}
+
+#[test]
+fn hidden() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app.insert_resource(ReplicationAudit::new(16));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(false)))
+        .id();
+
+    server_app.update();
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let audit = server_app.world().resource::<ReplicationAudit>();
+    assert_eq!(audit.entity_history(client_id, server_entity).count(), 0);
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    let visibility = replicated_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_entity, true);
+
+    server_app.update();
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    let visibility = replicated_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_entity, false);
+
+    server_app.update();
+
+    let audit = server_app.world().resource::<ReplicationAudit>();
+    let actions: Vec<_> = audit
+        .entity_history(client_id, server_entity)
+        .map(|entry| entry.action)
+        .collect();
+    assert!(matches!(
+        actions[..],
+        [AuditAction::Insertion(_), AuditAction::Hide]
+    ));
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(bool);
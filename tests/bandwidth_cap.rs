@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn per_tick() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                bandwidth_cap: Some(BandwidthCap::PerTick(1)),
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)));
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    assert_eq!(
+        components.iter(client_app.world()).count(),
+        2,
+        "insertions should be sent regardless of the cap"
+    );
+
+    let mut server_components = server_app.world_mut().query::<&mut DummyComponent>();
+    for mut component in server_components.iter_mut(server_app.world_mut()) {
+        component.0 = 1;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(
+        components
+            .iter(client_app.world())
+            .filter(|component| component.0 == 1)
+            .count(),
+        1,
+        "only one entity should be sent due to the cap"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(
+        components
+            .iter(client_app.world())
+            .all(|component| component.0 == 1),
+        "deferred entity should be sent on the next tick"
+    );
+}
+
+#[test]
+fn per_second() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)));
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .set_bandwidth_cap(Some(BandwidthCap::PerSecond(1)));
+
+    let mut server_components = server_app.world_mut().query::<&mut DummyComponent>();
+    for mut component in server_components.iter_mut(server_app.world_mut()) {
+        component.0 = 1;
+    }
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        assert_eq!(
+            components
+                .iter(client_app.world())
+                .filter(|component| component.0 == 1)
+                .count(),
+            1,
+            "exceeded bytes should be subtracted from the next ticks"
+        );
+    }
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .set_bandwidth_cap(None);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(components
+        .iter(client_app.world())
+        .all(|component| component.0 == 1));
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*, server::bandwidth_heatmap::BandwidthHeatmap, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn tracking() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>();
+    }
+
+    server_app.insert_resource(BandwidthHeatmap::new(10));
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+    let idle_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for value in 1..=3 {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<TestComponent>(server_entity)
+            .unwrap();
+        component.0 = value;
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let heatmap = server_app.world().resource::<BandwidthHeatmap>();
+    let traffic = heatmap.traffic(client_id, server_entity);
+    assert_eq!(
+        traffic.sends, 4,
+        "insertion and 3 mutations should be counted"
+    );
+    assert_ne!(traffic.bytes, 0);
+
+    let top = heatmap.top(client_id, 1);
+    assert_eq!(top[0].0, server_entity);
+    assert_eq!(heatmap.traffic(client_id, idle_entity).sends, 0);
+
+    server_app.disconnect_client(&mut client_app);
+
+    let heatmap = server_app.world().resource::<BandwidthHeatmap>();
+    assert_eq!(heatmap.iter_client(client_id).count(), 0);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u32);
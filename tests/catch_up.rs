@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::catch_up::{CatchUpPerformed, ClientCatchUp},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn catch_up() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<IntComponent>();
+    }
+    client_app.insert_resource(ClientCatchUp::new(2));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, IntComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Simulate a stall on client.
+    for value in 1..=5 {
+        server_app
+            .world_mut()
+            .get_mut::<IntComponent>(server_entity)
+            .unwrap()
+            .0 = value;
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&IntComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, 5);
+
+    let mut catch_up_events = client_app
+        .world_mut()
+        .resource_mut::<Events<CatchUpPerformed>>();
+    let event = catch_up_events
+        .drain()
+        .next()
+        .expect("client should catch up after the stall");
+    assert_eq!(event.to - event.from, 5);
+}
+
+#[test]
+fn no_catch_up() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<IntComponent>();
+    }
+    client_app.insert_resource(ClientCatchUp::new(2));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, IntComponent(0)))
+        .id();
+
+    for value in 1..=5 {
+        server_app
+            .world_mut()
+            .get_mut::<IntComponent>(server_entity)
+            .unwrap()
+            .0 = value;
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let catch_up_events = client_app.world().resource::<Events<CatchUpPerformed>>();
+    assert!(catch_up_events.is_empty());
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct IntComponent(u32);
@@ -0,0 +1,224 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    server::change_journal::{ChangeJournal, ChangeJournalWorldExt},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn recording() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .insert_resource(ChangeJournal::new(10))
+    .replicate::<DummyComponent>();
+
+    app.world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let entity = app.world_mut().spawn((Replicated, DummyComponent(1))).id();
+
+    app.update();
+
+    app.world_mut().get_mut::<DummyComponent>(entity).unwrap().0 = 2;
+
+    app.update();
+
+    // Should ignore changes without a different value.
+    app.world_mut()
+        .get_mut::<DummyComponent>(entity)
+        .unwrap()
+        .set_changed();
+
+    app.update();
+
+    app.world_mut().entity_mut(entity).despawn();
+
+    app.update();
+
+    let journal = app.world().resource::<ChangeJournal>();
+    let changes: Vec<_> = journal
+        .iter()
+        .flat_map(|journal_tick| &journal_tick.changes)
+        .map(|change| (change.entity, change.old.is_some(), change.new.is_some()))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            (entity, false, true),
+            (entity, true, true),
+            (entity, true, false)
+        ]
+    );
+}
+
+#[test]
+fn rollback() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app.insert_resource(ChangeJournal::new(10));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(1)))
+        .id();
+
+    server_app.update();
+
+    for value in 2..=3 {
+        server_app
+            .world_mut()
+            .get_mut::<DummyComponent>(server_entity)
+            .unwrap()
+            .0 = value;
+        server_app.update();
+    }
+
+    server_app.world_mut().rollback_changes(2, |_| true);
+
+    let component = server_app
+        .world()
+        .get::<DummyComponent>(server_entity)
+        .unwrap();
+    assert_eq!(component.0, 1);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let journal = server_app.world().resource::<ChangeJournal>();
+    assert_eq!(journal.iter().count(), 1, "only insertion should be kept");
+
+    let component = client_app
+        .world_mut()
+        .query::<&DummyComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, 1);
+}
+
+#[test]
+fn insertion_and_removal_rollback() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .insert_resource(ChangeJournal::new(10))
+    .replicate::<DummyComponent>()
+    .replicate::<OtherComponent>();
+
+    app.world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let entity = app.world_mut().spawn((Replicated, DummyComponent(1))).id();
+
+    app.update();
+
+    app.world_mut()
+        .entity_mut(entity)
+        .remove::<DummyComponent>()
+        .insert(OtherComponent);
+
+    app.update();
+
+    app.world_mut().rollback_changes(1, |_| true);
+
+    let entity = app.world().entity(entity);
+    assert_eq!(entity.get::<DummyComponent>().unwrap().0, 1);
+    assert!(!entity.contains::<OtherComponent>());
+}
+
+#[test]
+fn filtered_rollback() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .insert_resource(ChangeJournal::new(10))
+    .replicate::<DummyComponent>()
+    .replicate::<OtherComponent>();
+
+    app.world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let entity = app.world_mut().spawn((Replicated, DummyComponent(1))).id();
+
+    app.update();
+
+    app.world_mut()
+        .entity_mut(entity)
+        .insert((DummyComponent(2), OtherComponent));
+
+    app.update();
+
+    let dummy_id = app.world_mut().register_component::<DummyComponent>();
+    app.world_mut()
+        .rollback_changes(1, |component_id| component_id == dummy_id);
+
+    let entity = app.world().entity(entity);
+    assert_eq!(entity.get::<DummyComponent>().unwrap().0, 1);
+    assert!(entity.contains::<OtherComponent>());
+}
+
+#[test]
+fn eviction() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .insert_resource(ChangeJournal::new(2))
+    .replicate::<DummyComponent>();
+
+    app.world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let entity = app.world_mut().spawn((Replicated, DummyComponent(0))).id();
+
+    for value in 1..=3 {
+        app.update();
+        app.world_mut().get_mut::<DummyComponent>(entity).unwrap().0 = value;
+    }
+    app.update();
+
+    let journal = app.world().resource::<ChangeJournal>();
+    assert_eq!(journal.iter().count(), 2);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
+
+#[derive(Component, Deserialize, Serialize)]
+struct OtherComponent;
@@ -0,0 +1,139 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn automatic_server() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+
+        let mut channels = app.world_mut().resource_mut::<RepliconChannels>();
+        channels.set_channel_selection(ChannelSelection::Automatic);
+        let channels_count = channels.server_channels().len();
+
+        app.add_server_event::<OrderedEvent>(ChannelKind::Ordered)
+            .add_server_event::<UnorderedEvent>(ChannelKind::Unordered)
+            .add_server_event::<UnreliableEvent>(ChannelKind::Unreliable)
+            .finish();
+
+        let channels = app.world().resource::<RepliconChannels>();
+        assert_eq!(
+            channels.server_channels().len(),
+            channels_count + 2,
+            "unordered event should reuse the ordered channel"
+        );
+        assert_eq!(channels.shared_server_channels().len(), 2);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: OrderedEvent,
+    });
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: UnorderedEvent,
+    });
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: UnreliableEvent,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let ordered_events = client_app.world().resource::<Events<OrderedEvent>>();
+    assert_eq!(ordered_events.len(), 1);
+    let unordered_events = client_app.world().resource::<Events<UnorderedEvent>>();
+    assert_eq!(unordered_events.len(), 1);
+    let unreliable_events = client_app.world().resource::<Events<UnreliableEvent>>();
+    assert_eq!(unreliable_events.len(), 1);
+}
+
+#[test]
+fn automatic_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+
+        let mut channels = app.world_mut().resource_mut::<RepliconChannels>();
+        channels.set_channel_selection(ChannelSelection::Automatic);
+        let channels_count = channels.client_channels().len();
+
+        app.add_client_event::<OrderedEvent>(ChannelKind::Ordered)
+            .add_client_event::<UnorderedEvent>(ChannelKind::Unordered)
+            .finish();
+
+        let channels = app.world().resource::<RepliconChannels>();
+        assert_eq!(channels.client_channels().len(), channels_count + 1);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world_mut().send_event(OrderedEvent);
+    client_app.world_mut().send_event(UnorderedEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let ordered_events = server_app
+        .world()
+        .resource::<Events<FromClient<OrderedEvent>>>();
+    assert_eq!(ordered_events.len(), 1);
+    let unordered_events = server_app
+        .world()
+        .resource::<Events<FromClient<UnorderedEvent>>>();
+    assert_eq!(unordered_events.len(), 1);
+}
+
+#[test]
+fn priority() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let mut channels = app.world_mut().resource_mut::<RepliconChannels>();
+    channels.set_channel_selection(ChannelSelection::Automatic);
+    let channels_count = channels.server_channels().len();
+
+    let mut channel = RepliconChannel::from(ChannelKind::Ordered);
+    channel.priority = 1;
+    app.add_server_event::<OrderedEvent>(ChannelKind::Ordered)
+        .add_server_event::<UnorderedEvent>(channel);
+
+    let channels = app.world().resource::<RepliconChannels>();
+    assert_eq!(
+        channels.server_channels().len(),
+        channels_count + 2,
+        "channels with different priorities shouldn't be shared"
+    );
+    let channel_id = *channels.shared_server_channels().last().unwrap();
+    let channel = channels.server_channel(channel_id).unwrap();
+    assert_eq!(channel.priority, 1);
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct OrderedEvent;
+
+#[derive(Deserialize, Event, Serialize)]
+struct UnorderedEvent;
+
+#[derive(Deserialize, Event, Serialize)]
+struct UnreliableEvent;
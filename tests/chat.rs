@@ -0,0 +1,216 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    addons::chat::{ChatHistory, ChatMessage, ChatPlugin, ChatRoom, ChatRooms, SendChat},
+    core::event::rate_limit::ClientEventRateLimit,
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn global() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, ChatPlugin::default()))
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    send_chat(&mut client_app1, ChatRoom::GLOBAL, "hello");
+
+    client_app1.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app1.update();
+    client_app2.update();
+
+    assert_eq!(drain_messages(&mut server_app), ["hello"]);
+    assert_eq!(drain_messages(&mut client_app1), ["hello"]);
+
+    let client_id = client_app1
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let messages: Vec<_> = client_app2
+        .world_mut()
+        .resource_mut::<Events<ChatMessage>>()
+        .drain()
+        .collect();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].sender, client_id);
+    assert_eq!(messages[0].room, ChatRoom::GLOBAL);
+}
+
+#[test]
+fn rooms() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins, ChatPlugin::default()))
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    const ROOM: ChatRoom = ChatRoom::new(1);
+    let client_id = client_app1
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    server_app
+        .world_mut()
+        .resource_mut::<ChatRooms>()
+        .join(client_id, ROOM);
+
+    send_chat(&mut client_app1, ROOM, "member");
+    send_chat(&mut client_app2, ROOM, "outsider");
+
+    client_app1.update();
+    client_app2.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.exchange_with_client(&mut client_app2);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app1.update();
+    client_app2.update();
+
+    assert_eq!(drain_messages(&mut client_app1), ["member"]);
+    assert!(drain_messages(&mut client_app2).is_empty());
+}
+
+#[test]
+fn moderation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            ChatPlugin {
+                max_len: 10,
+                rate_limit: ClientEventRateLimit::per_second(10),
+                filter: Some(|message| {
+                    message.text = message.text.replace("bad", "***");
+                    message.text != "spam"
+                }),
+                ..Default::default()
+            },
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    send_chat(&mut client_app, ChatRoom::GLOBAL, "bad word");
+    send_chat(&mut client_app, ChatRoom::GLOBAL, "spam");
+    send_chat(&mut client_app, ChatRoom::GLOBAL, "too long message");
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(drain_messages(&mut client_app), ["*** word"]);
+}
+
+#[test]
+fn rate_limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            ChatPlugin {
+                rate_limit: ClientEventRateLimit::per_second(1),
+                ..Default::default()
+            },
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    send_chat(&mut client_app, ChatRoom::GLOBAL, "first");
+    send_chat(&mut client_app, ChatRoom::GLOBAL, "second");
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(drain_messages(&mut client_app), ["first"]);
+}
+
+#[test]
+fn history() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins,
+            ChatPlugin {
+                history_len: 1,
+                ..Default::default()
+            },
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app1);
+
+    send_chat(&mut client_app1, ChatRoom::GLOBAL, "old");
+    send_chat(&mut client_app1, ChatRoom::GLOBAL, "new");
+
+    client_app1.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.update();
+
+    let history = server_app.world().resource::<ChatHistory>();
+    let texts: Vec<_> = history
+        .messages(ChatRoom::GLOBAL)
+        .map(|message| &message.text)
+        .collect();
+    assert_eq!(texts, ["new"]);
+
+    server_app.connect_client(&mut client_app2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app2);
+    client_app2.update();
+
+    assert_eq!(
+        drain_messages(&mut client_app2),
+        ["new"],
+        "late joiner should receive the history"
+    );
+}
+
+fn send_chat(app: &mut App, room: ChatRoom, text: &str) {
+    app.world_mut().send_event(SendChat {
+        room,
+        text: text.into(),
+    });
+}
+
+fn drain_messages(app: &mut App) -> Vec<String> {
+    app.world_mut()
+        .resource_mut::<Events<ChatMessage>>()
+        .drain()
+        .map(|message| message.text)
+        .collect()
+}
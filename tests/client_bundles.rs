@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    server::client_bundles::{
+        ClientBundleAppExt, ClientBundleConfig, ClientBundleVisibility, ClientOwner, LeavePolicy,
+    },
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn spawn_despawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app.add_client_bundle(|_| (Replicated, DummyComponent));
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let owner = *server_app
+        .world_mut()
+        .query_filtered::<&ClientOwner, With<DummyComponent>>()
+        .single(server_app.world());
+    assert_eq!(*owner, client_id);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world_mut()
+        .query::<(&Replicated, &DummyComponent)>()
+        .single(client_app.world());
+
+    server_app.disconnect_client(&mut client_app);
+
+    let mut components = server_app.world_mut().query::<&DummyComponent>();
+    assert_eq!(components.iter(server_app.world()).count(), 0);
+}
+
+#[test]
+fn keep_on_leave() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    server_app.add_client_bundle_with(
+        ClientBundleConfig {
+            on_leave: LeavePolicy::Keep,
+            ..Default::default()
+        },
+        |_| (Replicated, DummyComponent),
+    );
+
+    server_app.connect_client(&mut client_app);
+    server_app.disconnect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .query_filtered::<&ClientOwner, With<DummyComponent>>()
+        .single(server_app.world());
+}
+
+#[test]
+fn owner_visibility() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<OtherComponent>();
+    }
+    server_app
+        .add_client_bundle_with(
+            ClientBundleConfig {
+                visibility: ClientBundleVisibility::Owner,
+                ..Default::default()
+            },
+            |_| (Replicated, DummyComponent),
+        )
+        .add_client_bundle(|_| (Replicated, OtherComponent));
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    for client_app in [&mut client_app1, &mut client_app2] {
+        server_app.update();
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+
+        let mut components = client_app.world_mut().query::<&DummyComponent>();
+        assert_eq!(
+            components.iter(client_app.world()).count(),
+            1,
+            "client should see only its own entity"
+        );
+
+        let mut components = client_app.world_mut().query::<&OtherComponent>();
+        assert_eq!(
+            components.iter(client_app.world()).count(),
+            2,
+            "client should see entities of all clients"
+        );
+    }
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct OtherComponent;
@@ -0,0 +1,148 @@
+use bevy::{diagnostic::DiagnosticsStore, ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    client::diagnostics::{
+        ClientApplyCosts, MutationBacklogExceeded, MutationBacklogThresholds, BUFFERED_MUTATIONS,
+    },
+    core::channels::ReplicationChannel,
+    prelude::*,
+    test_app::{
+        scripted_link::{Delivery, Direction, ScriptedLink},
+        ServerTestAppExt,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn backlog_diagnostics() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+    client_app.insert_resource(MutationBacklogThresholds {
+        max_messages: Some(0),
+        ..Default::default()
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    let mut link = ScriptedLink::new(|_| Delivery::Deliver);
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+
+    link.set_script(|packet| {
+        if packet.direction == Direction::ToClient
+            && packet.channel_id == ReplicationChannel::Updates as u8
+        {
+            Delivery::Hold
+        } else {
+            Delivery::Deliver
+        }
+    });
+
+    // Spawn goes to the held update message, so the mutation will be buffered.
+    server_app.world_mut().spawn(Replicated);
+    server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+
+    let store = client_app.world().resource::<DiagnosticsStore>();
+    let buffered = store.get(&BUFFERED_MUTATIONS).unwrap().value().unwrap();
+    assert_eq!(buffered, 1.0);
+
+    let mut exceeded_events = client_app
+        .world_mut()
+        .resource_mut::<Events<MutationBacklogExceeded>>();
+    let events: Vec<_> = exceeded_events.drain().collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].messages, 1);
+
+    client_app.update();
+
+    let exceeded_events = client_app
+        .world()
+        .resource::<Events<MutationBacklogExceeded>>();
+    assert!(
+        exceeded_events.is_empty(),
+        "event should be emitted only once"
+    );
+}
+
+#[test]
+fn apply_costs() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>()
+        .replicate::<OtherComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0), OtherComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let apply_costs = client_app.world().resource::<ClientApplyCosts>();
+    assert_eq!(apply_costs.iter().count(), 2);
+    let (fns_id, cost) = apply_costs
+        .iter()
+        .find(|(_, cost)| cost.type_name.ends_with("TestComponent"))
+        .expect("cost should be recorded with the type name");
+    assert_eq!(cost.calls, 1);
+
+    server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let apply_costs = client_app.world().resource::<ClientApplyCosts>();
+    let cost = apply_costs.get(fns_id).unwrap();
+    assert_eq!(cost.calls, 2);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u8);
+
+#[derive(Component, Deserialize, Serialize)]
+struct OtherComponent;
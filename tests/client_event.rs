@@ -1,10 +1,12 @@
 use bevy::{
-    ecs::{entity::MapEntities, event::Events},
+    ecs::{entity::MapEntities, event::Events, system::RunSystemOnce},
     prelude::*,
     time::TimePlugin,
 };
 use bevy_replicon::{
-    core::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
+    core::{event::rate_limit::ClientEventRateLimit, server_entity_map::ServerEntityMap},
+    prelude::*,
+    test_app::ServerTestAppExt,
 };
 use serde::{Deserialize, Serialize};
 
@@ -32,6 +34,36 @@ fn sending_receiving() {
     assert_eq!(client_events.len(), 1);
 }
 
+#[test]
+fn sending_with_commands() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app
+        .world_mut()
+        .run_system_once(|mut commands: Commands| {
+            commands.client_send(DummyEvent);
+        })
+        .unwrap();
+    client_app.world_mut().client_send(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 2);
+}
+
 #[test]
 fn mapping_and_sending_receiving() {
     let mut server_app = App::new();
@@ -68,6 +100,36 @@ fn mapping_and_sending_receiving() {
     assert_eq!(mapped_entities, [server_entity]);
 }
 
+#[test]
+fn mapping_unknown_entity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_mapped_client_event::<EntityEvent>(ChannelKind::Ordered)
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn_empty().id();
+    client_app
+        .world_mut()
+        .send_event(EntityEvent(client_entity));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<EntityEvent>>>();
+    assert!(
+        client_events.is_empty(),
+        "event with unmapped entity should be discarded"
+    );
+}
+
 #[test]
 fn sending_receiving_without_plugins() {
     let mut server_app = App::new();
@@ -125,7 +187,86 @@ fn local_resending() {
     assert_eq!(client_events.len(), 1);
 }
 
-#[derive(Deserialize, Event, Serialize)]
+#[test]
+fn delivery_receipts() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<DummyEvent>(ChannelKind::Unreliable)
+            .enable_delivery_receipts::<DummyEvent>()
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world_mut().send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 1);
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let delivered_events = client_app
+        .world()
+        .resource::<Events<EventDelivered<DummyEvent>>>();
+    assert_eq!(delivered_events.len(), 1);
+}
+
+#[test]
+fn local_delivery_receipts() {
+    let mut app = App::new();
+    app.add_plugins((TimePlugin, RepliconPlugins))
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+        .enable_delivery_receipts::<DummyEvent>()
+        .finish();
+
+    app.world_mut().send_event(DummyEvent);
+
+    app.update();
+
+    let client_events = app.world().resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 1);
+
+    let delivered_events = app.world().resource::<Events<EventDelivered<DummyEvent>>>();
+    assert_eq!(delivered_events.len(), 1);
+}
+
+#[test]
+fn rate_limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+            .set_client_event_rate_limit::<DummyEvent>(ClientEventRateLimit::per_second(2))
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    for _ in 0..3 {
+        client_app.world_mut().send_event(DummyEvent);
+    }
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 2);
+}
+
+#[derive(Deserialize, Event, Serialize, Clone)]
 struct DummyEvent;
 
 #[derive(Deserialize, Event, Serialize, Clone)]
@@ -0,0 +1,108 @@
+use bevy::{app::AppLabel, prelude::*};
+use bevy_replicon::{
+    client::sessions::ClientSessionsAppExt, core::server_entity_map::ServerEntityMap, prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn split_screen() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+
+    let mut client_app = App::new();
+    for player in Player::ALL {
+        let mut session = App::new();
+        session
+            .add_plugins((MinimalPlugins, RepliconPlugins))
+            .replicate::<TestComponent>();
+        client_app.add_client_session(player, session);
+    }
+
+    for player in Player::ALL {
+        connect(&mut server_app, &mut client_app, player);
+    }
+    server_app.update();
+    client_app.update();
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent))
+        .id();
+
+    server_app.update();
+    exchange(&mut server_app, &mut client_app);
+    client_app.update();
+
+    for player in Player::ALL {
+        let world = client_app.client_session_mut(player);
+        let entity_map = world.resource::<ServerEntityMap>();
+        let client_entity = *entity_map
+            .to_client()
+            .get(&server_entity)
+            .expect("entity should be replicated to each session");
+        assert!(world.get::<TestComponent>(client_entity).is_some());
+    }
+
+    assert_eq!(
+        client_app
+            .world_mut()
+            .query::<&TestComponent>()
+            .iter(client_app.world())
+            .count(),
+        0,
+        "main world shouldn't be affected"
+    );
+}
+
+fn client_id(player: Player) -> ClientId {
+    ClientId::new(player as u64 + 1)
+}
+
+fn connect(server_app: &mut App, client_app: &mut App, player: Player) {
+    let client_id = client_id(player);
+    let mut client = client_app
+        .client_session_mut(player)
+        .resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connected {
+        client_id: Some(client_id),
+    });
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    server.set_running(true);
+    server_app
+        .world_mut()
+        .trigger(ClientConnected { client_id });
+}
+
+fn exchange(server_app: &mut App, client_app: &mut App) {
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    for (client_id, channel_id, message) in server.drain_sent() {
+        let player = Player::ALL[client_id.get() as usize - 1];
+        let mut client = client_app
+            .client_session_mut(player)
+            .resource_mut::<RepliconClient>();
+        client.insert_received(channel_id, message);
+    }
+}
+
+#[derive(AppLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Player {
+    First,
+    Second,
+}
+
+impl Player {
+    const ALL: [Self; 2] = [Self::First, Self::Second];
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent;
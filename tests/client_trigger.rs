@@ -59,6 +59,35 @@ fn sending_receiving_with_target() {
     assert_eq!(reader.entities, [server_entity]);
 }
 
+#[test]
+fn sending_receiving_with_unknown_target() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_trigger::<DummyEvent>(ChannelKind::Ordered)
+            .finish();
+    }
+    server_app.init_resource::<TriggerReader<DummyEvent>>();
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn_empty().id();
+    client_app
+        .world_mut()
+        .client_trigger_targets(DummyEvent, client_entity);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let reader = server_app.world().resource::<TriggerReader<DummyEvent>>();
+    assert!(
+        reader.events.is_empty(),
+        "trigger with unmapped target should be discarded"
+    );
+}
+
 #[test]
 fn mapping_and_sending_receiving() {
     let mut server_app = App::new();
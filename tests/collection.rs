@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::collection::{AppCollectionExt, CollectionElement, ReplicatedCollection},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn insertion() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_collection::<DummyCollection>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((
+        Replicated,
+        DummyCollection(
+            [DummyElement::new(0, 0), DummyElement::new(1, 1)]
+                .into_iter()
+                .collect(),
+        ),
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let collection = client_app
+        .world_mut()
+        .query::<&DummyCollection>()
+        .single(client_app.world());
+    assert_eq!(collection.len(), 2);
+    assert_eq!(collection.get(&0).unwrap().value, 0);
+    assert_eq!(collection.get(&1).unwrap().value, 1);
+}
+
+#[test]
+fn diffs() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_collection::<DummyCollection>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((
+            Replicated,
+            DummyCollection(
+                [
+                    DummyElement::new(0, 0),
+                    DummyElement::new(1, 0),
+                    DummyElement::new(2, 0),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        ))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut collection = server_app
+        .world_mut()
+        .get_mut::<DummyCollection>(server_entity)
+        .unwrap();
+    collection.get_mut(&0).unwrap().value = 1;
+    collection.remove(&1);
+    collection.insert(DummyElement::new(3, 3));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut collections = client_app.world_mut().query::<&DummyCollection>();
+    let collection = collections.single(client_app.world());
+    assert_eq!(collection.len(), 3);
+    assert_eq!(collection.get(&0).unwrap().value, 1);
+    assert!(!collection.contains_key(&1));
+    assert_eq!(
+        collection.get(&2).unwrap().value,
+        0,
+        "untouched elements should be preserved"
+    );
+    assert_eq!(collection.get(&3).unwrap().value, 3);
+
+    server_app
+        .world_mut()
+        .get_mut::<DummyCollection>(server_entity)
+        .unwrap()
+        .get_mut(&2)
+        .unwrap()
+        .value = 2;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let collection = collections.single(client_app.world());
+    assert_eq!(collection.len(), 3);
+    assert_eq!(collection.get(&0).unwrap().value, 1);
+    assert_eq!(collection.get(&2).unwrap().value, 2);
+    assert_eq!(collection.get(&3).unwrap().value, 3);
+}
+
+#[test]
+fn late_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_collection::<DummyCollection>()
+        .finish();
+    }
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((
+            Replicated,
+            DummyCollection(
+                [DummyElement::new(0, 0), DummyElement::new(1, 0)]
+                    .into_iter()
+                    .collect(),
+            ),
+        ))
+        .id();
+
+    server_app.update();
+
+    let mut collection = server_app
+        .world_mut()
+        .get_mut::<DummyCollection>(server_entity)
+        .unwrap();
+    collection.get_mut(&1).unwrap().value = 1;
+
+    server_app.connect_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let collection = client_app
+        .world_mut()
+        .query::<&DummyCollection>()
+        .single(client_app.world());
+    assert_eq!(
+        collection.len(),
+        2,
+        "new clients should receive the full collection"
+    );
+    assert_eq!(collection.get(&0).unwrap().value, 0);
+    assert_eq!(collection.get(&1).unwrap().value, 1);
+}
+
+#[derive(Component, Default, Deref, DerefMut)]
+struct DummyCollection(ReplicatedCollection<DummyElement>);
+
+#[derive(Deserialize, Serialize)]
+struct DummyElement {
+    id: u8,
+    value: u8,
+}
+
+impl DummyElement {
+    fn new(id: u8, value: u8) -> Self {
+        Self { id, value }
+    }
+}
+
+impl CollectionElement for DummyElement {
+    type Key = u8;
+
+    fn key(&self) -> Self::Key {
+        self.id
+    }
+}
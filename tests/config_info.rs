@@ -0,0 +1,70 @@
+use std::any;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{config_info::RepliconConfigInfo, server_pause::ServerPauseChanged},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn collection() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<DummyComponent>()
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+        .register_marker::<DummyMarker>();
+
+    app.finish();
+
+    let config_info = app.world().resource::<RepliconConfigInfo>();
+    assert!(config_info
+        .rules
+        .iter()
+        .any(|rule| rule.components == [any::type_name::<DummyComponent>()]));
+
+    let pause_event = config_info
+        .server_events
+        .iter()
+        .find(|event| event.type_name == any::type_name::<ServerPauseChanged>())
+        .expect("pause event should be registered by core plugin");
+    assert!(pause_event.independent);
+
+    let [client_event] = config_info.client_events.as_slice() else {
+        panic!("client should have only a single event");
+    };
+    assert_eq!(client_event.type_name, any::type_name::<DummyEvent>());
+
+    let [marker] = config_info.markers.as_slice() else {
+        panic!("only a single marker should be registered");
+    };
+    assert_eq!(marker.component, any::type_name::<DummyMarker>());
+}
+
+#[test]
+fn reflection() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    app.finish();
+
+    let registry = app.world().resource::<AppTypeRegistry>().read();
+    for type_id in [
+        any::TypeId::of::<RepliconConfigInfo>(),
+        any::TypeId::of::<RepliconChannels>(),
+    ] {
+        let registration = registry
+            .get(type_id)
+            .expect("type should be registered for reflection");
+        assert!(registration.data::<ReflectResource>().is_some());
+    }
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Component)]
+struct DummyMarker;
+
+#[derive(Event, Deserialize, Serialize)]
+struct DummyEvent;
@@ -1,6 +1,8 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, time::TimeUpdateStrategy};
 use bevy_replicon::{
-    core::channels::ReplicationChannel, prelude::*, server::server_tick::ServerTick,
+    core::{channels::ReplicationChannel, connection_stats::StatsHistory},
+    prelude::*,
+    server::server_tick::ServerTick,
     test_app::ServerTestAppExt,
 };
 
@@ -94,6 +96,108 @@ fn connect_disconnect() {
     assert!(replicated_clients.is_empty());
 }
 
+#[test]
+fn client_entity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let connected_clients = server_app.world().resource::<ConnectedClients>();
+    let client_entity = connected_clients
+        .entity(client_id)
+        .expect("connected client should have an entity");
+
+    let mut connected_clients = server_app.world_mut().resource_mut::<ConnectedClients>();
+    let client = connected_clients.iter_mut().next().unwrap();
+    client.set_rtt(0.5);
+
+    server_app.update();
+
+    let client = server_app
+        .world()
+        .get::<ConnectedClient>(client_entity)
+        .unwrap();
+    assert_eq!(client.id(), client_id);
+    assert_eq!(
+        client.rtt(),
+        0.5,
+        "stats should be copied from the resource"
+    );
+
+    server_app.disconnect_client(&mut client_app);
+
+    let connected_clients = server_app.world().resource::<ConnectedClients>();
+    assert!(connected_clients.entity(client_id).is_none());
+    assert!(server_app.world().get_entity(client_entity).is_err());
+}
+
+#[test]
+fn stats_history() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(
+            StatsHistory::SAMPLE_INTERVAL,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    for rtt in [0.1, 0.3, 0.2] {
+        let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+        client.set_rtt(rtt);
+
+        let mut connected_clients = server_app.world_mut().resource_mut::<ConnectedClients>();
+        let client = connected_clients.iter_mut().next().unwrap();
+        client.set_rtt(rtt);
+
+        client_app.update();
+        server_app.update();
+    }
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let history = client.stats_history();
+    assert!(history.len() >= 3);
+    assert_eq!(history.rtt_percentile(1.0), 0.3);
+    assert_eq!(history.iter().last().unwrap().rtt, 0.2);
+
+    let connected_clients = server_app.world().resource::<ConnectedClients>();
+    let client_entity = connected_clients.iter_entities().next().unwrap().1;
+    let client = server_app
+        .world()
+        .get::<ConnectedClient>(client_entity)
+        .unwrap();
+    let history = client.stats_history();
+    assert!(history.len() >= 3);
+    assert_eq!(history.rtt_percentile(1.0), 0.3);
+    assert_eq!(history.iter().last().unwrap().rtt, 0.2);
+
+    server_app.disconnect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    assert!(client.stats_history().is_empty());
+}
+
 #[test]
 fn client_cleanup_on_disconnect() {
     let mut app = App::new();
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{
+    addons::dead_reckoning::DeadReckoningPlugin, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn advance() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            DeadReckoningPlugin::new(move_position),
+        ))
+        .replicate::<Position>()
+        .replicate::<Velocity>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, Position(0.0), Velocity(10.0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    assert_eq!(client_position(&mut client_app), 0.0);
+
+    client_app.update();
+    client_app.update();
+    assert!((client_position(&mut client_app) - 2.0).abs() < 0.001);
+}
+
+#[test]
+fn suppression() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for (app, step) in [(&mut server_app, 100), (&mut client_app, 0)] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            DeadReckoningPlugin::new(move_position).with_error_threshold(position_error, 0.5),
+        ))
+        .replicate::<Position>()
+        .replicate::<Velocity>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            step,
+        )))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Position(0.0), Velocity(10.0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    assert_eq!(client_position(&mut client_app), 0.0);
+
+    for _ in 0..3 {
+        // Move according to the model.
+        server_app
+            .world_mut()
+            .get_mut::<Position>(server_entity)
+            .unwrap()
+            .0 += 1.0;
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    assert_eq!(
+        client_position(&mut client_app),
+        0.0,
+        "predicted mutations shouldn't be sent"
+    );
+
+    // Teleport.
+    server_app
+        .world_mut()
+        .get_mut::<Position>(server_entity)
+        .unwrap()
+        .0 = 100.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(client_position(&mut client_app), 100.0);
+}
+
+fn client_position(client_app: &mut App) -> f32 {
+    client_app
+        .world_mut()
+        .query::<&Position>()
+        .single(client_app.world())
+        .0
+}
+
+fn move_position(position: &Position, velocity: &Velocity, secs: f32) -> Position {
+    Position(position.0 + velocity.0 * secs)
+}
+
+fn position_error(actual: &Position, predicted: &Position) -> f32 {
+    (actual.0 - predicted.0).abs()
+}
+
+#[derive(Component, Clone, Deserialize, Serialize)]
+struct Position(f32);
+
+#[derive(Component, Deserialize, Serialize)]
+struct Velocity(f32);
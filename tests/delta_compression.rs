@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::ClientReplicationStats,
+    core::replication::replication_registry::rule_fns::{self, RuleFns},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn mutations() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with(
+            RuleFns::<LargeComponent>::default()
+                .with_delta(rule_fns::byte_diff, rule_fns::apply_byte_diff),
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, LargeComponent(vec![0; 256])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    client_app.insert_resource(ClientReplicationStats::default());
+    for index in [10, 200, 10] {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<LargeComponent>(server_entity)
+            .unwrap();
+        component.0[index] += 1;
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let component = client_app
+            .world_mut()
+            .query::<&LargeComponent>()
+            .single(client_app.world());
+        let expected = server_app
+            .world()
+            .get::<LargeComponent>(server_entity)
+            .unwrap();
+        assert_eq!(component, expected);
+    }
+
+    let stats = client_app.world().resource::<ClientReplicationStats>();
+    assert_eq!(stats.messages, 3);
+    assert!(
+        stats.bytes < 256,
+        "only changed bytes should be sent, but got {}",
+        stats.bytes
+    );
+}
+
+#[test]
+fn unacknowledged() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with(
+            RuleFns::<LargeComponent>::default()
+                .with_delta(rule_fns::byte_diff, rule_fns::apply_byte_diff),
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, LargeComponent(vec![0; 64])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<LargeComponent>(server_entity)
+        .unwrap();
+    component.0[0] = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    // Take and drop ack message.
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    assert_eq!(client.drain_sent().count(), 1);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<LargeComponent>(server_entity)
+        .unwrap();
+    component.0[1] = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world_mut()
+        .query::<&LargeComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0[..2], [1, 1]);
+}
+
+#[test]
+fn insertion_after_delta() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate_with(
+            RuleFns::<LargeComponent>::default()
+                .with_delta(rule_fns::byte_diff, rule_fns::apply_byte_diff),
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, LargeComponent(vec![0; 64])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut entity = server_app.world_mut().entity_mut(server_entity);
+    entity.get_mut::<LargeComponent>().unwrap().0[0] = 1;
+    entity.insert(BoolComponent(true));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<LargeComponent>(server_entity)
+        .unwrap()
+        .0[1] = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let (component, bool_component) = client_app
+        .world_mut()
+        .query::<(&LargeComponent, &BoolComponent)>()
+        .single(client_app.world());
+    assert_eq!(component.0[..2], [1, 1]);
+    assert!(bool_component.0);
+}
+
+#[derive(Component, Deserialize, Serialize, Debug, PartialEq)]
+struct LargeComponent(Vec<u8>);
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
@@ -0,0 +1,211 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::dictionary_compression::{
+        CompressionDictionary, DictionaryClients, DictionaryCompressionPlugin, DictionarySamples,
+    },
+    core::{
+        channels::ReplicationChannel,
+        protocol::{
+            compression_kind::CompressionKind, postcard_utils, protocol_version::ProtocolVersion,
+        },
+    },
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn compressed() {
+    let dictionary = CompressionDictionary::train(&samples(), 1024).unwrap();
+
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            DictionaryCompressionPlugin,
+        ))
+        .insert_resource(CompressionDictionary::new(dictionary.as_bytes().to_vec()).unwrap())
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let dictionary_clients = server_app.world().resource::<DictionaryClients>();
+    assert!(dictionary_clients.contains(client_id));
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_entity)
+        .unwrap();
+    component.0 = 42;
+
+    server_app.update();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    let messages: Vec<_> = server.drain_sent().collect();
+    for (client_id, channel_id, message) in messages {
+        if channel_id == ReplicationChannel::Mutations as u8 {
+            let mut header = message.clone();
+            let version: ProtocolVersion = postcard_utils::from_buf(&mut header).unwrap();
+            assert_eq!(
+                version,
+                ProtocolVersion::CURRENT,
+                "version should stay uncompressed"
+            );
+            let compression: CompressionKind = postcard_utils::from_buf(&mut header).unwrap();
+            assert_eq!(compression, CompressionKind::Dictionary);
+        }
+        server.send(client_id, channel_id, message);
+    }
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&TestComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, 42);
+}
+
+#[test]
+fn unknown_dictionary() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            DictionaryCompressionPlugin,
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    let dictionary = CompressionDictionary::train(&samples(), 1024).unwrap();
+    server_app.insert_resource(dictionary);
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let dictionary_clients = server_app.world().resource::<DictionaryClients>();
+    assert!(!dictionary_clients.contains(client_id));
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_entity)
+        .unwrap();
+    component.0 = 42;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&TestComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, 42, "mutations should be sent uncompressed");
+}
+
+#[test]
+fn sampling() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            DictionaryCompressionPlugin,
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.insert_resource(DictionarySamples::new(2));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for value in 1..=3 {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<TestComponent>(server_entity)
+            .unwrap();
+        component.0 = value;
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let samples = server_app.world().resource::<DictionarySamples>();
+    assert!(samples.is_full());
+    assert_eq!(samples.messages().len(), 2);
+}
+
+fn samples() -> Vec<Vec<u8>> {
+    (0..1000)
+        .map(|index| format!("entity {index} value {}", index % 17).into_bytes())
+        .collect()
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u32);
@@ -0,0 +1,201 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        event::Events,
+    },
+    prelude::*,
+};
+use bevy_replicon::{
+    core::{
+        entity_serde::{EntityObfuscation, EntityPermutation},
+        server_entity_map::ServerEntityMap,
+    },
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn permutation() {
+    let permutation = EntityPermutation::new(42);
+    for bits in [
+        1 << 32,
+        2 << 32 | 1,
+        1 << 32 | 100,
+        1 << 32 | u32::MAX as u64,
+    ] {
+        let entity = Entity::from_bits(bits);
+        let obfuscated = permutation.obfuscate(entity);
+        assert_ne!(obfuscated, entity);
+        assert_eq!(obfuscated.generation(), entity.generation());
+        assert_eq!(permutation.reveal(obfuscated), entity);
+    }
+
+    assert_ne!(
+        EntityPermutation::new(1).obfuscate(Entity::from_raw(1)),
+        EntityPermutation::new(2).obfuscate(Entity::from_raw(1)),
+        "different keys should produce different permutations"
+    );
+
+    let obfuscation = EntityObfuscation::new(42);
+    assert_ne!(
+        obfuscation
+            .for_client(ClientId::new(1))
+            .obfuscate(Entity::from_raw(1)),
+        obfuscation
+            .for_client(ClientId::new(2))
+            .obfuscate(Entity::from_raw(1)),
+        "different clients should have different permutations"
+    );
+}
+
+#[test]
+fn replication() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .add_mapped_client_event::<EntityEvent>(ChannelKind::Ordered)
+        .finish();
+    }
+    let obfuscation = EntityObfuscation::new(42);
+    server_app.insert_resource(obfuscation);
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(!entity_map.to_client().contains_key(&server_entity));
+    let client_entity = *entity_map
+        .to_client()
+        .get(&obfuscation.for_client(client_id).obfuscate(server_entity))
+        .expect("client should receive obfuscated entity");
+
+    client_app
+        .world_mut()
+        .send_event(EntityEvent(client_entity));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let mapped_entities: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Events<FromClient<EntityEvent>>>()
+        .drain()
+        .map(|event| event.event.0)
+        .collect();
+    assert_eq!(mapped_entities, [server_entity]);
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world().get_entity(client_entity).is_err());
+}
+
+#[test]
+fn per_client() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_trigger::<DummyEvent>(ChannelKind::Ordered)
+        .finish();
+    }
+    for client_app in [&mut client_app1, &mut client_app2] {
+        client_app.init_resource::<TriggerTargets>().add_observer(
+            |trigger: Trigger<DummyEvent>, mut targets: ResMut<TriggerTargets>| {
+                targets.push(trigger.entity());
+            },
+        );
+    }
+    server_app.insert_resource(EntityObfuscation::new(42));
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+    server_app.world_mut().server_trigger_targets(
+        ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent,
+        },
+        server_entity,
+    );
+
+    server_app.update();
+    let mut received_entities = Vec::new();
+    for client_app in [&mut client_app1, &mut client_app2] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+
+        let (&received_entity, &client_entity) = client_app
+            .world()
+            .resource::<ServerEntityMap>()
+            .to_client()
+            .iter()
+            .next()
+            .expect("client should receive the entity");
+        let targets = client_app.world().resource::<TriggerTargets>();
+        assert_eq!(
+            **targets,
+            [client_entity],
+            "trigger target should be obfuscated for the client"
+        );
+        received_entities.push(received_entity);
+    }
+
+    assert_ne!(
+        received_entities[0], received_entities[1],
+        "clients should see different entities"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Deserialize, Event, Serialize)]
+struct DummyEvent;
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct TriggerTargets(Vec<Entity>);
+
+#[derive(Deserialize, Event, Serialize, Clone)]
+struct EntityEvent(Entity);
+
+impl MapEntities for EntityEvent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
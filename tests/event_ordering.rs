@@ -0,0 +1,167 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn server_events() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<BeforeEvent>(ChannelKind::Ordered)
+        .add_server_event::<AfterEvent>(ChannelKind::Ordered)
+        .order_server_events::<BeforeEvent, AfterEvent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: BeforeEvent(0),
+    });
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: AfterEvent(1),
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut before_events = client_app.world_mut().resource_mut::<Events<BeforeEvent>>();
+    assert_eq!(before_events.drain().collect::<Vec<_>>(), [BeforeEvent(0)]);
+
+    let mut after_events = client_app.world_mut().resource_mut::<Events<AfterEvent>>();
+    assert_eq!(after_events.drain().collect::<Vec<_>>(), [AfterEvent(1)]);
+}
+
+#[test]
+fn independent_server_events() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<BeforeEvent>(ChannelKind::Ordered)
+        .add_server_event::<AfterEvent>(ChannelKind::Ordered)
+        .make_independent::<BeforeEvent>()
+        .make_independent::<AfterEvent>()
+        .order_server_events::<BeforeEvent, AfterEvent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: BeforeEvent(0),
+    });
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: AfterEvent(1),
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut before_events = client_app.world_mut().resource_mut::<Events<BeforeEvent>>();
+    assert_eq!(before_events.drain().collect::<Vec<_>>(), [BeforeEvent(0)]);
+
+    let mut after_events = client_app.world_mut().resource_mut::<Events<AfterEvent>>();
+    assert_eq!(after_events.drain().collect::<Vec<_>>(), [AfterEvent(1)]);
+}
+
+#[test]
+fn client_events() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_client_event::<BeforeEvent>(ChannelKind::Ordered)
+        .add_client_event::<AfterEvent>(ChannelKind::Ordered)
+        .order_client_events::<BeforeEvent, AfterEvent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world_mut().send_event(BeforeEvent(0));
+    client_app.world_mut().send_event(AfterEvent(1));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let mut before_events = server_app
+        .world_mut()
+        .resource_mut::<Events<FromClient<BeforeEvent>>>();
+    let before_events: Vec<_> = before_events.drain().map(|event| event.event).collect();
+    assert_eq!(before_events, [BeforeEvent(0)]);
+
+    let mut after_events = server_app
+        .world_mut()
+        .resource_mut::<Events<FromClient<AfterEvent>>>();
+    let after_events: Vec<_> = after_events.drain().map(|event| event.event).collect();
+    assert_eq!(after_events, [AfterEvent(1)]);
+}
+
+#[test]
+fn joining_groups() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_server_event::<BeforeEvent>(ChannelKind::Ordered)
+        .add_server_event::<AfterEvent>(ChannelKind::Ordered)
+        .add_server_event::<LastEvent>(ChannelKind::Ordered)
+        .order_server_events::<BeforeEvent, AfterEvent>()
+        .order_server_events::<AfterEvent, LastEvent>()
+        .order_server_events::<BeforeEvent, LastEvent>();
+}
+
+#[test]
+#[should_panic]
+fn wrong_order() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_server_event::<BeforeEvent>(ChannelKind::Ordered)
+        .add_server_event::<AfterEvent>(ChannelKind::Ordered)
+        .order_server_events::<AfterEvent, BeforeEvent>();
+}
+
+#[test]
+#[should_panic]
+fn different_independence() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_server_event::<BeforeEvent>(ChannelKind::Ordered)
+        .add_server_event::<AfterEvent>(ChannelKind::Ordered)
+        .make_independent::<AfterEvent>()
+        .order_server_events::<BeforeEvent, AfterEvent>();
+}
+
+#[derive(Event, Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct BeforeEvent(usize);
+
+#[derive(Event, Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct AfterEvent(usize);
+
+#[derive(Event, Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct LastEvent(usize);
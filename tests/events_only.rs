@@ -0,0 +1,50 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    client::ServerUpdateTick, prelude::*, server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, EventsOnlyPlugins))
+            .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+            .add_client_event::<ClientDummyEvent>(ChannelKind::Ordered)
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    assert!(server_app.world().contains_resource::<ServerTick>());
+    assert!(!server_app.world().contains_resource::<ReplicatedClients>());
+    assert!(!client_app.world().contains_resource::<ServerUpdateTick>());
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+    });
+    client_app.world_mut().send_event(ClientDummyEvent);
+
+    server_app.update();
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    client_app.update();
+
+    let mut server_events = client_app.world_mut().resource_mut::<Events<DummyEvent>>();
+    assert_eq!(server_events.drain().count(), 1);
+
+    let mut client_events = server_app
+        .world_mut()
+        .resource_mut::<Events<FromClient<ClientDummyEvent>>>();
+    assert_eq!(client_events.drain().count(), 1);
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct DummyEvent;
+
+#[derive(Deserialize, Event, Serialize)]
+struct ClientDummyEvent;
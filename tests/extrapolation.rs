@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::extrapolation::{AppExtrapolationExt, Extrapolated, ExtrapolationSettings},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn stall_and_resume() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<TickerComponent>()
+        .set_extrapolation_fns(extrapolate, blend)
+        .insert_resource(ExtrapolationSettings {
+            stale_ticks: 3,
+            blend_ticks: 2,
+        })
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0.0)))
+        .id();
+    server_app
+        .world_mut()
+        .spawn((Replicated, TickerComponent(0)));
+
+    for _ in 0..3 {
+        update(&mut server_app, &mut client_app);
+    }
+
+    let (value, extrapolated) = client_state(&mut client_app);
+    assert_eq!(value, 0.0);
+    assert!(!extrapolated);
+
+    update(&mut server_app, &mut client_app);
+
+    let (value, extrapolated) = client_state(&mut client_app);
+    assert_eq!(value, 3.0, "should be extrapolated for 3 stale ticks");
+    assert!(extrapolated);
+
+    server_app
+        .world_mut()
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 10.0;
+
+    update(&mut server_app, &mut client_app);
+
+    let (value, extrapolated) = client_state(&mut client_app);
+    assert_eq!(
+        value, 3.0,
+        "should start blending from the extrapolated value"
+    );
+    assert!(!extrapolated);
+
+    update(&mut server_app, &mut client_app);
+
+    let (value, _) = client_state(&mut client_app);
+    assert_eq!(value, 6.5);
+
+    update(&mut server_app, &mut client_app);
+
+    let (value, extrapolated) = client_state(&mut client_app);
+    assert_eq!(value, 10.0, "should end up with the received value");
+    assert!(!extrapolated);
+}
+
+/// Updates apps while mutating [`TickerComponent`] to keep the server sending updates.
+fn update(server_app: &mut App, client_app: &mut App) {
+    let mut ticker = server_app
+        .world_mut()
+        .query::<&mut TickerComponent>()
+        .single_mut(server_app.world_mut());
+    ticker.0 += 1;
+
+    server_app.update();
+    server_app.exchange_with_client(client_app);
+    client_app.update();
+    server_app.exchange_with_client(client_app);
+}
+
+fn client_state(client_app: &mut App) -> (f32, bool) {
+    let (component, extrapolated) = client_app
+        .world_mut()
+        .query::<(&DummyComponent, Has<Extrapolated>)>()
+        .single(client_app.world());
+    (component.0, extrapolated)
+}
+
+fn extrapolate(component: &DummyComponent, ticks: u32) -> DummyComponent {
+    DummyComponent(component.0 + ticks as f32)
+}
+
+fn blend(from: &DummyComponent, to: &DummyComponent, factor: f32) -> DummyComponent {
+    DummyComponent(from.0.lerp(to.0, factor))
+}
+
+#[derive(Component, Clone, Deserialize, Serialize)]
+struct DummyComponent(f32);
+
+#[derive(Component, Deserialize, Serialize)]
+struct TickerComponent(u32);
@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replication::{field_groups::FieldGroups, replication_registry::rule_fns::RuleFns},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn insertion() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with(RuleFns::<GroupedComponent>::field_groups())
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((
+        Replicated,
+        GroupedComponent {
+            first: 1,
+            second: 2,
+            separate: 3,
+        },
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world_mut()
+        .query::<&GroupedComponent>()
+        .single(client_app.world());
+    assert_eq!(
+        *component,
+        GroupedComponent {
+            first: 1,
+            second: 2,
+            separate: 3,
+        }
+    );
+}
+
+#[test]
+fn changed_groups() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with(RuleFns::<GroupedComponent>::field_groups())
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, GroupedComponent::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<GroupedComponent>>()
+        .single(client_app.world());
+
+    // Modify locally to check that the group is not overwritten.
+    client_app
+        .world_mut()
+        .get_mut::<GroupedComponent>(client_entity)
+        .unwrap()
+        .separate = 1;
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<GroupedComponent>(server_entity)
+        .unwrap();
+    component.first = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world()
+        .get::<GroupedComponent>(client_entity)
+        .unwrap();
+    assert_eq!(
+        *component,
+        GroupedComponent {
+            first: 1,
+            second: 0,
+            separate: 1,
+        },
+        "only the changed group should be sent"
+    );
+}
+
+#[test]
+fn unacknowledged_groups() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with(RuleFns::<GroupedComponent>::field_groups())
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, GroupedComponent::default()))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<GroupedComponent>(server_entity)
+        .unwrap()
+        .first = 1;
+
+    server_app.update();
+
+    // Change another group before the client acknowledges the previous mutation.
+    server_app
+        .world_mut()
+        .get_mut::<GroupedComponent>(server_entity)
+        .unwrap()
+        .separate = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world_mut()
+        .query::<&GroupedComponent>()
+        .single(client_app.world());
+    assert_eq!(
+        *component,
+        GroupedComponent {
+            first: 1,
+            second: 0,
+            separate: 1,
+        }
+    );
+}
+
+#[derive(Component, Default, Debug, FieldGroups, PartialEq)]
+struct GroupedComponent {
+    #[field_group(pair)]
+    first: u8,
+    #[field_group(pair)]
+    second: u8,
+    separate: u8,
+}
@@ -55,6 +55,43 @@ fn write() {
     assert!(entity.contains::<OriginalComponent>());
 }
 
+#[test]
+fn write_default_elision() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(
+                    world,
+                    RuleFns::<DefaultComponent>::default().with_default_elision(),
+                )
+            });
+
+    let mut entity = app.world_mut().spawn(DefaultComponent::default());
+    let default_data = entity.serialize(fns_id, tick);
+    assert_eq!(default_data.len(), 1, "only the flag should be written");
+
+    entity.insert(DefaultComponent(1));
+    let data = entity.serialize(fns_id, tick);
+    assert!(data.len() > 1);
+
+    entity.apply_write(default_data, fns_id, tick);
+    assert_eq!(
+        *entity.get::<DefaultComponent>().unwrap(),
+        DefaultComponent(0)
+    );
+
+    entity.remove::<DefaultComponent>();
+    entity.apply_write(data, fns_id, tick);
+    assert_eq!(
+        *entity.get::<DefaultComponent>().unwrap(),
+        DefaultComponent(1)
+    );
+}
+
 #[test]
 fn remove() {
     let mut app = App::new();
@@ -351,6 +388,9 @@ struct OriginalComponent;
 #[derive(Component, Deserialize, Serialize)]
 struct ReplacedComponent;
 
+#[derive(Component, Deserialize, Serialize, Default, Debug, PartialEq)]
+struct DefaultComponent(u32);
+
 #[derive(Component)]
 struct Despawned;
 
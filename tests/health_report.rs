@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::health_report::{ClientHealth, ClientHealthReports, HealthReportPlugin},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn reports() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            HealthReportPlugin {
+                interval: Duration::ZERO,
+            },
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let reports = server_app.world().resource::<ClientHealthReports>();
+    assert!(reports.get(client_id).is_none());
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let reports = server_app.world().resource::<ClientHealthReports>();
+    let report = reports.get(client_id).unwrap();
+    assert_eq!(report.health, ClientHealth::default());
+
+    let channels = client_app.world().resource::<RepliconChannels>();
+    let event_channel = channels.server_channels().len() - 1;
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    // Tick with a varint that doesn't terminate.
+    client.insert_received(event_channel as u8, vec![u8::MAX; 6]);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    assert_eq!(client.decode_errors(), 1);
+
+    let reports = server_app.world().resource::<ClientHealthReports>();
+    let report = reports.get(client_id).unwrap();
+    assert_eq!(report.health.decode_errors, 1);
+    assert_eq!(reports.iter().count(), 1);
+
+    server_app.disconnect_client(&mut client_app);
+
+    let reports = server_app.world().resource::<ClientHealthReports>();
+    assert!(reports.get(client_id).is_none());
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct DummyEvent;
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::Events, prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{
+    addons::heartbeat::{ClientHeartbeats, ClientResponsive, ClientUnresponsive, HeartbeatPlugin},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn unresponsive() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            HeartbeatPlugin {
+                interval: Duration::ZERO,
+                timeout: Duration::from_millis(50),
+            },
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            20,
+        )))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let heartbeats = server_app.world().resource::<ClientHeartbeats>();
+    assert!(!heartbeats.is_unresponsive(client_id));
+
+    // Stop exchanging messages.
+    for _ in 0..3 {
+        server_app.update();
+    }
+
+    let heartbeats = server_app.world().resource::<ClientHeartbeats>();
+    assert!(heartbeats.is_unresponsive(client_id));
+    assert_eq!(
+        heartbeats.iter_unresponsive().collect::<Vec<_>>(),
+        [client_id]
+    );
+
+    let mut unresponsive_events = server_app
+        .world_mut()
+        .resource_mut::<Events<ClientUnresponsive>>();
+    assert_eq!(
+        unresponsive_events.drain().collect::<Vec<_>>(),
+        [ClientUnresponsive { client_id }]
+    );
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let heartbeats = server_app.world().resource::<ClientHeartbeats>();
+    assert!(!heartbeats.is_unresponsive(client_id));
+
+    let mut responsive_events = server_app
+        .world_mut()
+        .resource_mut::<Events<ClientResponsive>>();
+    assert_eq!(
+        responsive_events.drain().collect::<Vec<_>>(),
+        [ClientResponsive { client_id }]
+    );
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            HeartbeatPlugin::default(),
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let heartbeats = server_app.world().resource::<ClientHeartbeats>();
+    assert!(heartbeats.last_received(client_id).is_some());
+
+    server_app.disconnect_client(&mut client_app);
+
+    let heartbeats = server_app.world().resource::<ClientHeartbeats>();
+    assert!(heartbeats.last_received(client_id).is_none());
+}
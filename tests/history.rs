@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::segment_playback::SegmentPlayback,
+    core::replication::history_segment::HistorySegment,
+    prelude::*,
+    server::{replication_history::ReplicationHistory, server_tick::ServerTick},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn playback() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate::<IntComponent>()
+        .add_server_event::<HistorySegment>(ChannelKind::Ordered)
+        .finish();
+    }
+    server_app.insert_resource(ReplicationHistory::new(10));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false), IntComponent(0)))
+        .id();
+    server_app.update();
+    let start = **server_app.world().resource::<ServerTick>();
+
+    for value in 1..=3 {
+        server_app
+            .world_mut()
+            .get_mut::<IntComponent>(server_entity)
+            .unwrap()
+            .0 = value;
+        server_app.update();
+    }
+    let end = **server_app.world().resource::<ServerTick>();
+
+    let history = server_app.world().resource::<ReplicationHistory>();
+    let segment = history.extract(&[server_entity], start..=end).unwrap();
+    assert_eq!(segment.len(), 4);
+    assert_eq!(segment.ticks().next(), Some(start));
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: segment,
+    });
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut segments = client_app
+        .world_mut()
+        .resource_mut::<Events<HistorySegment>>();
+    let segment = segments.drain().next().expect("segment should be received");
+
+    let mut playback = SegmentPlayback::new(segment);
+    let mut playback_world = World::new();
+    let mut values = Vec::new();
+    while let Some(tick) = playback
+        .advance(client_app.world(), &mut playback_world)
+        .unwrap()
+    {
+        let entity = playback
+            .entity_map()
+            .to_client()
+            .get(&server_entity)
+            .copied()
+            .expect("entity should be mapped");
+        let entity = playback_world.entity(entity);
+        assert!(!entity.get::<BoolComponent>().unwrap().0);
+        values.push((tick, entity.get::<IntComponent>().unwrap().0));
+    }
+
+    assert!(playback.is_finished());
+    assert_eq!(
+        values,
+        [(start, 0), (start + 1, 1), (start + 2, 2), (end, 3)]
+    );
+}
+
+#[test]
+fn eviction() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<IntComponent>()
+        .insert_resource(ReplicationHistory::new(2))
+        .finish();
+
+    let mut client_app = App::new();
+    client_app
+        .add_plugins((MinimalPlugins, RepliconPlugins))
+        .finish();
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, IntComponent(0)))
+        .id();
+    server_app.update();
+    let start = **server_app.world().resource::<ServerTick>();
+
+    for value in 1..=3 {
+        server_app
+            .world_mut()
+            .get_mut::<IntComponent>(server_entity)
+            .unwrap()
+            .0 = value;
+        server_app.update();
+    }
+    let end = **server_app.world().resource::<ServerTick>();
+
+    let history = server_app.world().resource::<ReplicationHistory>();
+    let segment = history.extract(&[server_entity], start..=end).unwrap();
+    assert_eq!(
+        segment.ticks().collect::<Vec<_>>(),
+        [end - 1, end],
+        "only the last frames should be kept"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Component, Deserialize, Serialize)]
+struct IntComponent(u32);
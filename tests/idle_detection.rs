@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::Events, prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{
+    prelude::*,
+    server::idle_detection::{ClientActive, ClientActivityAppExt, ClientIdle, IdleDetection},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn idle_and_active() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+        .track_activity::<DummyEvent>()
+        .finish();
+    }
+    server_app
+        .insert_resource(IdleDetection::new(Duration::ZERO))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )));
+
+    server_app.connect_client(&mut client_app);
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    server_app.update();
+
+    let detection = server_app.world().resource::<IdleDetection>();
+    assert!(detection.is_idle(client_id));
+    let mut idle_events = server_app.world_mut().resource_mut::<Events<ClientIdle>>();
+    assert_eq!(
+        idle_events.drain().collect::<Vec<_>>(),
+        [ClientIdle { client_id }]
+    );
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    assert_eq!(replicated_clients.client(client_id).mutations_interval(), 4);
+
+    client_app.world_mut().send_event(DummyEvent);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let detection = server_app.world().resource::<IdleDetection>();
+    assert!(!detection.is_idle(client_id));
+    let mut active_events = server_app
+        .world_mut()
+        .resource_mut::<Events<ClientActive>>();
+    assert_eq!(
+        active_events.drain().collect::<Vec<_>>(),
+        [ClientActive { client_id }]
+    );
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    assert_eq!(replicated_clients.client(client_id).mutations_interval(), 1);
+}
+
+#[test]
+fn mutations_interval() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .set_mutations_interval(u32::MAX);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(!component.0, "mutations should be skipped");
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .set_mutations_interval(1);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(component.0, "skipped mutations should be sent later");
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct DummyEvent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
@@ -93,6 +93,49 @@ fn sparse_set_storage() {
         .single(client_app.world());
 }
 
+#[test]
+fn zero_sized() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<SparseSetComponent>()
+        .replicate::<BoolComponent>()
+        .replicate_with::<UnitComponent>(RuleFns::default().with_default_elision());
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn((
+        Replicated,
+        DummyComponent,
+        SparseSetComponent,
+        BoolComponent(true),
+        UnitComponent,
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query_filtered::<&BoolComponent, (
+            With<DummyComponent>,
+            With<SparseSetComponent>,
+            With<UnitComponent>,
+        )>()
+        .single(client_app.world());
+    assert!(component.0);
+}
+
 #[test]
 fn mapped_existing_entity() {
     let mut server_app = App::new();
@@ -575,6 +618,13 @@ struct DummyComponent;
 #[component(storage = "SparseSet")]
 struct SparseSetComponent;
 
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+/// Zero-sized, but serialized with a flag.
+#[derive(Component, Deserialize, Serialize, Default, PartialEq)]
+struct UnitComponent;
+
 #[derive(Component, Deserialize, Serialize)]
 struct GroupComponentA;
 
@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    server::isolation_audit::{IsolationAudit, IsolationDomain, IsolationLeak},
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn leaks() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .finish();
+    }
+    server_app.insert_resource(IsolationAudit::new(false));
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let client_entity = server_app
+        .world()
+        .resource::<ConnectedClients>()
+        .entity(client_id)
+        .unwrap();
+    server_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(DOMAIN1);
+
+    let same_entity = server_app.world_mut().spawn((Replicated, DOMAIN1)).id();
+    let other_entity = server_app.world_mut().spawn((Replicated, DOMAIN2)).id();
+    let free_entity = server_app.world_mut().spawn(Replicated).id();
+    set_visibility(&mut server_app, client_id, same_entity);
+    set_visibility(&mut server_app, client_id, free_entity);
+
+    server_app.update();
+
+    let audit = server_app.world().resource::<IsolationAudit>();
+    assert!(audit.leaks().is_empty());
+
+    set_visibility(&mut server_app, client_id, other_entity);
+
+    server_app.update();
+
+    let audit = server_app.world().resource::<IsolationAudit>();
+    assert_eq!(
+        audit.leaks(),
+        [IsolationLeak {
+            client_id,
+            client_domain: Some(DOMAIN1),
+            entity: other_entity,
+            entity_domain: DOMAIN2,
+        }]
+    );
+}
+
+#[test]
+#[should_panic]
+fn panic_on_leak() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .finish();
+    }
+    server_app.insert_resource(IsolationAudit::new(true));
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let server_entity = server_app.world_mut().spawn((Replicated, DOMAIN1)).id();
+    set_visibility(&mut server_app, client_id, server_entity);
+
+    server_app.update();
+}
+
+fn set_visibility(server_app: &mut App, client_id: ClientId, entity: Entity) {
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(entity, true);
+}
+
+const DOMAIN1: IsolationDomain = IsolationDomain(1);
+const DOMAIN2: IsolationDomain = IsolationDomain(2);
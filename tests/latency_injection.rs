@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::Events, prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{
+    prelude::*,
+    server::latency_injection::{InjectedLatency, LatencyInjection},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn outgoing() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            20,
+        )))
+        .finish();
+    }
+    server_app.init_resource::<LatencyInjection>();
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    server_app
+        .world_mut()
+        .resource_mut::<LatencyInjection>()
+        .set(
+            client_id,
+            InjectedLatency {
+                outgoing: Duration::from_millis(50),
+                ..Default::default()
+            },
+        );
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let latency_injection = server_app.world().resource::<LatencyInjection>();
+    assert_ne!(latency_injection.held_count(client_id), 0);
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 0);
+
+    for _ in 0..3 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+    }
+
+    let latency_injection = server_app.world().resource::<LatencyInjection>();
+    assert_eq!(latency_injection.held_count(client_id), 0);
+    assert_eq!(replicated.iter(client_app.world()).count(), 1);
+}
+
+#[test]
+fn incoming() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                20,
+            )))
+            .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+            .finish();
+    }
+    server_app.init_resource::<LatencyInjection>();
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    server_app
+        .world_mut()
+        .resource_mut::<LatencyInjection>()
+        .set(
+            client_id,
+            InjectedLatency::symmetric(Duration::from_millis(50)),
+        );
+
+    client_app.world_mut().send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert!(client_events.is_empty());
+
+    for _ in 0..3 {
+        server_app.update();
+    }
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 1);
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                20,
+            )))
+            .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+            .finish();
+    }
+    server_app.init_resource::<LatencyInjection>();
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    server_app
+        .world_mut()
+        .resource_mut::<LatencyInjection>()
+        .set(
+            client_id,
+            InjectedLatency::symmetric(Duration::from_secs(1)),
+        );
+
+    client_app.world_mut().send_event(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let latency_injection = server_app.world().resource::<LatencyInjection>();
+    assert_eq!(latency_injection.held_count(client_id), 1);
+
+    server_app.disconnect_client(&mut client_app);
+
+    let latency_injection = server_app.world().resource::<LatencyInjection>();
+    assert_eq!(latency_injection.held_count(client_id), 0);
+    assert!(latency_injection.get(client_id).is_none());
+}
+
+#[derive(Deserialize, Event, Serialize)]
+struct DummyEvent;
@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*,
+    test_app::loopback::{ClientScript, LoopbackClients},
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn events() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+        .init_resource::<ReceivedEvents>()
+        .add_systems(Update, count_events)
+        .finish();
+
+    let mut clients = LoopbackClients::default();
+    for _ in 0..4 {
+        let index = clients.connect(&mut server_app, client_app());
+        clients.add_script(index, ClientScript::send_event(2, DummyEvent));
+    }
+
+    clients.run(&mut server_app, 20);
+    // Receive events sent on the last step.
+    server_app.update();
+
+    assert_eq!(clients.steps(), 20);
+    assert_eq!(server_app.world().resource::<ReceivedEvents>().0, 40);
+}
+
+#[test]
+fn replication_with_reconnects() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<IntComponent>()
+        .finish();
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, IntComponent(0)))
+        .id();
+
+    let mut clients = LoopbackClients::default();
+    for period in 3..7 {
+        let index = clients.connect(&mut server_app, client_app());
+        clients.add_script(index, ClientScript::reconnect(period));
+        clients.add_script(
+            index,
+            ClientScript::new(move |ctx| {
+                let mut components = ctx.app.world_mut().query::<&IntComponent>();
+                assert!(
+                    components.iter(ctx.app.world()).count() <= 1,
+                    "client {index} shouldn't have duplicate entities"
+                );
+            }),
+        );
+    }
+
+    for value in 1..=20 {
+        server_app
+            .world_mut()
+            .get_mut::<IntComponent>(server_entity)
+            .unwrap()
+            .0 = value;
+        clients.step(&mut server_app);
+    }
+
+    // Apply messages from the last step.
+    for client_app in clients.iter_mut() {
+        client_app.update();
+    }
+
+    for client_app in clients.iter() {
+        let components = client_app
+            .world()
+            .iter_entities()
+            .filter_map(|entity| entity.get::<IntComponent>())
+            .map(|component| component.0)
+            .collect::<Vec<_>>();
+        assert_eq!(components, [20]);
+    }
+}
+
+fn client_app() -> App {
+    let mut client_app = App::new();
+    client_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+        .replicate::<IntComponent>()
+        .add_systems(
+            PreUpdate,
+            despawn_replicated
+                .after(ClientSet::Reset)
+                .run_if(client_just_disconnected),
+        )
+        .finish();
+
+    client_app
+}
+
+fn despawn_replicated(mut commands: Commands, entities: Query<Entity, With<Replicated>>) {
+    for entity in &entities {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn count_events(
+    mut events: EventReader<FromClient<DummyEvent>>,
+    mut received: ResMut<ReceivedEvents>,
+) {
+    received.0 += events.read().count();
+}
+
+#[derive(Resource, Default)]
+struct ReceivedEvents(usize);
+
+#[derive(Event, Clone, Deserialize, Serialize)]
+struct DummyEvent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct IntComponent(u32);
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::channels::ReplicationChannel, prelude::*, server::message_coalescing::MessageCoalescing,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn coalesced() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+    server_app.insert_resource(MessageCoalescing::new(1200));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Mutate in the same tick as a new entity spawns.
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    let channels = forward_messages(&mut server_app, &mut client_app);
+    assert_eq!(
+        channels,
+        [ReplicationChannel::Updates as u8],
+        "mutations should be sent inside the update message"
+    );
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    let component = components.single(client_app.world());
+    assert!(component.0, "mutated value should be updated on client");
+}
+
+#[test]
+fn too_large() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+    server_app.insert_resource(MessageCoalescing::new(1));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    let channels = forward_messages(&mut server_app, &mut client_app);
+    assert_eq!(
+        channels,
+        [
+            ReplicationChannel::Updates as u8,
+            ReplicationChannel::Mutations as u8
+        ],
+        "mutations should be sent separately when exceeding the limit"
+    );
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    let component = components.single(client_app.world());
+    assert!(component.0);
+}
+
+/// Forwards all sent messages from server to client and returns their channels.
+fn forward_messages(server_app: &mut App, client_app: &mut App) -> Vec<u8> {
+    let messages: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .collect();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    let mut channels = Vec::new();
+    for (_, channel_id, message) in messages {
+        channels.push(channel_id);
+        client.insert_received(channel_id, message);
+    }
+
+    channels
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::ClientReplicationStats, prelude::*, server::message_compression::MessageCompression,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn update() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<LargeComponent>()
+        .finish();
+    }
+    server_app.init_resource::<MessageCompression>();
+    client_app.init_resource::<ClientReplicationStats>();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, LargeComponent(vec![1; 1024])));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&LargeComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, [1; 1024]);
+
+    let stats = client_app.world().resource::<ClientReplicationStats>();
+    assert_eq!(stats.messages, 1);
+    assert!(
+        stats.bytes < 1024,
+        "update message should be compressed, but got {} bytes",
+        stats.bytes
+    );
+}
+
+#[test]
+fn mutations() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<LargeComponent>()
+        .finish();
+    }
+    server_app.init_resource::<MessageCompression>();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, LargeComponent(vec![0; 1024])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    client_app.insert_resource(ClientReplicationStats::default());
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<LargeComponent>(server_entity)
+        .unwrap();
+    component.0.fill(2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&LargeComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, [2; 1024]);
+
+    let stats = client_app.world().resource::<ClientReplicationStats>();
+    assert_eq!(stats.messages, 1);
+    assert!(
+        stats.bytes < 1024,
+        "mutate message should be compressed, but got {} bytes",
+        stats.bytes
+    );
+}
+
+#[test]
+fn below_threshold() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<LargeComponent>()
+        .finish();
+    }
+    server_app.insert_resource(MessageCompression {
+        threshold: 2048,
+        ..Default::default()
+    });
+    client_app.init_resource::<ClientReplicationStats>();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, LargeComponent(vec![1; 1024])));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&LargeComponent>();
+    assert_eq!(components.iter(client_app.world()).count(), 1);
+
+    let stats = client_app.world().resource::<ClientReplicationStats>();
+    assert!(
+        stats.bytes > 1024,
+        "update message shouldn't be compressed, but got {} bytes",
+        stats.bytes
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct LargeComponent(Vec<u8>);
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::channels::ReplicationChannel,
+    prelude::*,
+    test_app::{message_sink::MessageSinks, ServerTestAppExt},
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn deterministic() {
+    let mut outputs = Vec::new();
+    for _ in 0..2 {
+        let mut server_app = App::new();
+        server_app
+            .add_plugins((
+                MinimalPlugins,
+                RepliconPlugins.set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                }),
+            ))
+            .replicate::<DummyComponent>()
+            .finish();
+
+        let mut sinks = MessageSinks::default();
+        let client_id = sinks.connect(&mut server_app);
+
+        let entity = server_app
+            .world_mut()
+            .spawn((Replicated, DummyComponent(0)))
+            .id();
+
+        server_app.update();
+        sinks.collect(&mut server_app);
+
+        server_app
+            .world_mut()
+            .get_mut::<DummyComponent>(entity)
+            .unwrap()
+            .0 = 1;
+
+        server_app.update();
+        sinks.collect(&mut server_app);
+
+        let messages = sinks.disconnect(&mut server_app, client_id);
+        let channels: Vec<_> = messages.iter().map(|&(channel_id, _)| channel_id).collect();
+        assert_eq!(
+            channels,
+            [
+                ReplicationChannel::Updates as u8,
+                ReplicationChannel::Mutations as u8
+            ]
+        );
+
+        outputs.push(messages);
+    }
+
+    assert_eq!(outputs[0], outputs[1]);
+}
+
+#[test]
+fn with_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+    let mut sinks = MessageSinks::default();
+    let client_id = sinks.connect(&mut server_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)));
+
+    server_app.update();
+    sinks.collect(&mut server_app);
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(sinks.take(client_id).len(), 1);
+    assert!(sinks.messages(client_id).is_empty());
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    assert_eq!(
+        components.iter(client_app.world()).count(),
+        1,
+        "messages for other clients should be kept"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::{mutation_buffer::MutationBufferPolicy, BufferedMutations},
+    core::channels::ReplicationChannel,
+    prelude::*,
+    test_app::{
+        scripted_link::{Delivery, Direction, ScriptedLink},
+        ServerTestAppExt,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn buffered() {
+    let (mut server_app, mut client_app, mut link) = setup(MutationBufferPolicy::default());
+
+    assert_eq!(client_value(&mut client_app), 0);
+    assert_eq!(client_app.world().resource::<BufferedMutations>().len(), 1);
+
+    link.release_held();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+
+    assert_eq!(client_value(&mut client_app), 1);
+    assert!(client_app
+        .world()
+        .resource::<BufferedMutations>()
+        .is_empty());
+}
+
+#[test]
+fn optimistic() {
+    let (_, mut client_app, _) = setup(MutationBufferPolicy {
+        optimistic: true,
+        ..Default::default()
+    });
+
+    assert_eq!(client_value(&mut client_app), 1);
+    assert!(client_app
+        .world()
+        .resource::<BufferedMutations>()
+        .is_empty());
+}
+
+#[test]
+fn max_messages() {
+    let (mut server_app, mut client_app, mut link) = setup(MutationBufferPolicy {
+        max_messages: Some(0),
+        ..Default::default()
+    });
+
+    assert!(client_app
+        .world()
+        .resource::<BufferedMutations>()
+        .is_empty());
+
+    link.release_held();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        client_value(&mut client_app),
+        0,
+        "dropped mutation shouldn't be applied"
+    );
+}
+
+#[test]
+fn max_age() {
+    let (mut server_app, mut client_app, mut link) = setup(MutationBufferPolicy {
+        max_age: Some(0),
+        ..Default::default()
+    });
+
+    assert_eq!(client_app.world().resource::<BufferedMutations>().len(), 1);
+
+    let mut components = server_app.world_mut().query::<&mut TestComponent>();
+    components.single_mut(server_app.world_mut()).0 = 2;
+
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        client_app.world().resource::<BufferedMutations>().len(),
+        1,
+        "older message should be dropped"
+    );
+
+    link.release_held();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+
+    assert_eq!(client_value(&mut client_app), 2);
+}
+
+/// Replicates entities and sends a mutation whose update message is held by the link.
+fn setup(policy: MutationBufferPolicy) -> (App, App, ScriptedLink) {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>()
+        .replicate::<OtherComponent>()
+        .finish();
+    }
+    client_app.insert_resource(policy);
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+    let other_entity = server_app.world_mut().spawn(Replicated).id();
+
+    let mut link = ScriptedLink::new(|_| Delivery::Deliver);
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+
+    link.set_script(|packet| {
+        if packet.direction == Direction::ToClient
+            && packet.channel_id == ReplicationChannel::Updates as u8
+        {
+            Delivery::Hold
+        } else {
+            Delivery::Deliver
+        }
+    });
+
+    // Insertion goes to an update message, so the mutation depends on it.
+    server_app
+        .world_mut()
+        .entity_mut(other_entity)
+        .insert(OtherComponent);
+    server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+
+    (server_app, client_app, link)
+}
+
+fn client_value(client_app: &mut App) -> u8 {
+    let mut components = client_app.world_mut().query::<&TestComponent>();
+    components.single(client_app.world()).0
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u8);
+
+#[derive(Component, Deserialize, Serialize)]
+struct OtherComponent;
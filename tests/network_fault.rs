@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{
+        channels::ReplicationChannel,
+        network_fault::{FaultPolicy, NetworkFault},
+    },
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn unknown_channel() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .insert_resource(FaultPolicy::Emit);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    server.insert_received(client_id, u8::MAX, Vec::new());
+
+    server_app.update();
+
+    let fault_events = server_app.world().resource::<Events<NetworkFault>>();
+    let faults: Vec<_> = fault_events.iter_current_update_events().cloned().collect();
+    assert_eq!(
+        faults,
+        [NetworkFault::UnknownChannel {
+            client_id: Some(client_id),
+            channel_id: u8::MAX,
+        }]
+    );
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(u8::MAX, Vec::new());
+    assert_eq!(client.receive(u8::MAX).count(), 0);
+
+    client_app.update();
+
+    let fault_events = client_app.world().resource::<Events<NetworkFault>>();
+    assert_eq!(fault_events.len(), 2);
+}
+
+#[test]
+fn unknown_client() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((MinimalPlugins, RepliconPlugins))
+        .insert_resource(FaultPolicy::Emit);
+
+    let client_id = ClientId::new(1);
+    server_app.world_mut().trigger(ClientDisconnected {
+        client_id,
+        reason: DisconnectReason::DisconnectedByClient,
+    });
+
+    server_app.update();
+
+    let fault_events = server_app.world().resource::<Events<NetworkFault>>();
+    let faults: Vec<_> = fault_events.iter_current_update_events().cloned().collect();
+    assert_eq!(faults, [NetworkFault::UnknownClient(client_id)]);
+}
+
+#[test]
+fn ack_without_replication() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                replicate_after_connect: false,
+                ..Default::default()
+            }),
+        ))
+        .insert_resource(FaultPolicy::Emit);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    server.insert_received(client_id, ReplicationChannel::Updates, vec![0]);
+
+    server_app.update();
+
+    let fault_events = server_app.world().resource::<Events<NetworkFault>>();
+    let faults: Vec<_> = fault_events.iter_current_update_events().cloned().collect();
+    assert_eq!(faults, [NetworkFault::UnknownClient(client_id)]);
+}
+
+#[test]
+fn malformed_replication() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .insert_resource(FaultPolicy::Emit);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, Vec::new());
+
+    client_app.update();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    assert_eq!(client.decode_errors(), 1);
+
+    let fault_events = client_app.world().resource::<Events<NetworkFault>>();
+    let faults: Vec<_> = fault_events.iter_current_update_events().cloned().collect();
+    assert!(matches!(faults.as_slice(), [NetworkFault::Replication(_)]));
+}
+
+#[test]
+#[should_panic]
+fn panic_policy() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .insert_resource(FaultPolicy::Panic);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    server.insert_received(client_id, u8::MAX, Vec::new());
+
+    server_app.update();
+}
@@ -0,0 +1,171 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    addons::orphan_detection::{OrphanDetectionPlugin, PossiblyOrphaned},
+    client::confirm_history::ConfirmHistory,
+    core::{replicon_tick::RepliconTick, server_entity_map::ServerEntityMap},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+const MAX_TICKS: u32 = 3;
+
+#[test]
+fn orphaned() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            OrphanDetectionPlugin {
+                max_ticks: MAX_TICKS,
+                resync: true,
+            },
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Simulate an entity which despawn was lost.
+    let orphan_entity = client_app
+        .world_mut()
+        .spawn((Replicated, ConfirmHistory::new(RepliconTick::new(0))))
+        .id();
+    let mut entity_map = client_app.world_mut().resource_mut::<ServerEntityMap>();
+    entity_map.insert(Entity::from_raw(1000), orphan_entity);
+
+    for _ in 0..MAX_TICKS {
+        mutate(&mut server_app, &mut client_app, server_entity);
+    }
+
+    let mut orphaned_events = client_app
+        .world_mut()
+        .resource_mut::<Events<PossiblyOrphaned>>();
+    assert_eq!(
+        orphaned_events.drain().collect::<Vec<_>>(),
+        [PossiblyOrphaned {
+            entity: orphan_entity
+        }]
+    );
+
+    mutate(&mut server_app, &mut client_app, server_entity);
+    mutate(&mut server_app, &mut client_app, server_entity);
+
+    assert!(
+        client_app.world().get_entity(orphan_entity).is_err(),
+        "server should report the entity as orphaned"
+    );
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(entity_map.len(), 1);
+
+    let orphaned_events = client_app.world().resource::<Events<PossiblyOrphaned>>();
+    assert!(orphaned_events.is_empty(), "entity should be reported once");
+}
+
+#[test]
+fn resync() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            OrphanDetectionPlugin {
+                max_ticks: MAX_TICKS,
+                resync: true,
+            },
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+    let static_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for _ in 0..MAX_TICKS + 2 {
+        mutate(&mut server_app, &mut client_app, server_entity);
+    }
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity = entity_map
+        .get_by_server(static_entity)
+        .expect("entity should be replicated");
+
+    let mut orphaned_events = client_app
+        .world_mut()
+        .resource_mut::<Events<PossiblyOrphaned>>();
+    assert_eq!(
+        orphaned_events.drain().collect::<Vec<_>>(),
+        [PossiblyOrphaned {
+            entity: client_entity
+        }],
+        "static entity should be reported"
+    );
+
+    let history = client_app
+        .world()
+        .get::<ConfirmHistory>(client_entity)
+        .unwrap();
+    let latest_history = client_app
+        .world()
+        .get::<ConfirmHistory>(
+            client_app
+                .world()
+                .resource::<ServerEntityMap>()
+                .get_by_server(server_entity)
+                .unwrap(),
+        )
+        .unwrap();
+    assert!(
+        latest_history.last_tick() - history.last_tick() <= MAX_TICKS,
+        "server should resend the entity"
+    );
+}
+
+fn mutate(server_app: &mut App, client_app: &mut App, entity: Entity) {
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(entity)
+        .unwrap();
+    component.0 = !component.0;
+
+    server_app.update();
+    server_app.exchange_with_client(client_app);
+    client_app.update();
+    server_app.exchange_with_client(client_app);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
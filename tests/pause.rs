@@ -0,0 +1,256 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::{ClientReplicationStats, ServerPaused},
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn pause_and_resume() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .pause();
+    let tick = **server_app.world().resource::<ServerTick>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(**server_app.world().resource::<ServerTick>(), tick);
+    assert!(**client_app.world().resource::<ServerPaused>());
+
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    let component = components.single(client_app.world());
+    assert!(!component.0, "changes shouldn't be replicated while paused");
+
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .resume();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(!**client_app.world().resource::<ServerPaused>());
+    let component = components.single(client_app.world());
+    assert!(component.0);
+}
+
+#[test]
+fn keyframe() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+    client_app.init_resource::<ClientReplicationStats>();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let stats = client_app.world().resource::<ClientReplicationStats>();
+    assert_eq!(stats.components_changed, 1);
+
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .pause();
+    server_app.update();
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .resume();
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let stats = client_app.world().resource::<ClientReplicationStats>();
+    assert_eq!(
+        stats.components_changed, 2,
+        "all components should be resent after resume"
+    );
+}
+
+#[test]
+fn connect_while_paused() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .finish();
+    }
+
+    server_app.update();
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .pause();
+    server_app.update();
+
+    server_app.connect_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(**client_app.world().resource::<ServerPaused>());
+}
+
+#[test]
+fn late_ack_after_pause() {
+    assert!(
+        delayed_ack_applied(true),
+        "mutations shouldn't expire while paused"
+    );
+}
+
+#[test]
+fn late_ack_after_ticks() {
+    assert!(
+        !delayed_ack_applied(false),
+        "mutations should expire after the configured number of ticks"
+    );
+}
+
+/// Holds a mutation ack for several server updates and returns `true` if the server still applied it.
+fn delayed_ack_applied(pause: bool) -> bool {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                mutations_timeout: Duration::ZERO,
+                mutations_timeout_ticks: 1,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    let acks: Vec<_> = client.drain_sent().collect();
+    assert_eq!(acks.len(), 1);
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let tick = replicated_clients
+        .client(client_id)
+        .mutation_tick(server_entity);
+
+    if pause {
+        server_app
+            .world_mut()
+            .resource_mut::<RepliconServer>()
+            .pause();
+    }
+    for _ in 0..5 {
+        server_app.update();
+    }
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    for (channel_id, message) in acks {
+        server.insert_received(client_id, channel_id, message);
+    }
+
+    server_app.update();
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    replicated_clients
+        .client(client_id)
+        .mutation_tick(server_entity)
+        != tick
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::pending_despawn::{ConfirmDespawn, PendingDespawn, PendingDespawnPlugin},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn confirmation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            PendingDespawnPlugin::default(),
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(PendingDespawn);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(server_app.world().get_entity(server_entity).is_ok());
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<PendingDespawn>>()
+        .single(client_app.world());
+
+    client_app.world_mut().send_event(ConfirmDespawn {
+        entity: client_entity,
+    });
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(server_app.world().get_entity(server_entity).is_err());
+    assert!(client_app.world().get_entity(client_entity).is_err());
+}
+
+#[test]
+fn timeout() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            PendingDespawnPlugin {
+                timeout: Duration::ZERO,
+            },
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(PendingDespawn);
+
+    server_app.update();
+    server_app.update();
+
+    assert!(server_app.world().get_entity(server_entity).is_err());
+}
@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::physics::{PhysicsAuthority, PhysicsBackend, PhysicsInterpolated, PhysicsPlugin},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn targets() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            PhysicsPlugin::<DummyPhysics>::default(),
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DynamicBody, Transform::from_xyz(1.0, 0.0, 0.0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(server_app
+        .world()
+        .entity(server_entity)
+        .contains::<PhysicsAuthority>());
+
+    let (transform, target) = client_app
+        .world_mut()
+        .query_filtered::<(&Transform, &BodyTarget), With<PhysicsInterpolated>>()
+        .single(client_app.world());
+    assert_eq!(transform.translation.x, 1.0);
+    assert_eq!(target.0.x, 1.0);
+
+    server_app
+        .world_mut()
+        .get_mut::<Transform>(server_entity)
+        .unwrap()
+        .translation
+        .x = 2.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let transform = client_app
+        .world_mut()
+        .query_filtered::<&Transform, With<PhysicsInterpolated>>()
+        .single(client_app.world());
+    assert_eq!(transform.translation.x, 2.0);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<DynamicBody>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(!server_app
+        .world()
+        .entity(server_entity)
+        .contains::<PhysicsAuthority>());
+
+    let mut targets = client_app.world_mut().query::<&BodyTarget>();
+    assert_eq!(targets.iter(client_app.world()).count(), 0);
+}
+
+struct DummyPhysics;
+
+impl PhysicsBackend for DummyPhysics {
+    type Target = BodyTarget;
+
+    fn is_authoritative(entity: &EntityRef) -> bool {
+        entity.contains::<DynamicBody>()
+    }
+
+    fn read_target(entity: &EntityRef) -> Self::Target {
+        BodyTarget(entity.get::<Transform>().unwrap().translation)
+    }
+
+    fn apply_target(target: &Self::Target, entity: &mut EntityMut) {
+        if let Some(mut transform) = entity.get_mut::<Transform>() {
+            transform.translation = target.0;
+        }
+    }
+
+    fn prepare_client_entity(entity: &mut EntityCommands) {
+        entity.insert(Transform::default());
+    }
+}
+
+#[derive(Component)]
+struct DynamicBody;
+
+#[derive(Component, Clone, PartialEq, Deserialize, Serialize)]
+struct BodyTarget(Vec3);
@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replication::replication_prefabs::{AppPrefabExt, ReplicationPrefab},
+    prelude::*,
+    server::replication_audit::{AuditAction, ReplicationAudit},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn defaults() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<PrefabMarker>()
+        .replicate::<ComponentA>()
+        .replicate::<ComponentB>()
+        .register_prefab::<PrefabMarker>(
+            ReplicationPrefab::default()
+                .with(ComponentA(1))
+                .with(ComponentB(2)),
+        );
+    }
+    server_app.insert_resource(ReplicationAudit::new(16));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, PrefabMarker, ComponentB(3)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (component_a, component_b) = client_app
+        .world_mut()
+        .query_filtered::<(&ComponentA, &ComponentB), With<PrefabMarker>>()
+        .single(client_app.world());
+    assert_eq!(*component_a, ComponentA(1));
+    assert_eq!(*component_b, ComponentB(3));
+
+    let audit = server_app.world().resource::<ReplicationAudit>();
+    let insertions = audit
+        .entries()
+        .filter(|entry| matches!(entry.action, AuditAction::Insertion(_)))
+        .count();
+    assert_eq!(insertions, 2, "only marker and override should be sent");
+}
+
+#[test]
+fn mutation_after_spawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<PrefabMarker>()
+        .replicate::<ComponentA>()
+        .register_prefab::<PrefabMarker>(ReplicationPrefab::default().with(ComponentA(1)));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, PrefabMarker))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<ComponentA>(server_entity)
+        .unwrap();
+    component.0 = 5;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&ComponentA>()
+        .single(client_app.world());
+    assert_eq!(*component, ComponentA(5));
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct PrefabMarker;
+
+#[derive(Component, Deserialize, Serialize, Clone, Debug, PartialEq)]
+struct ComponentA(u8);
+
+#[derive(Component, Deserialize, Serialize, Clone, Debug, PartialEq)]
+struct ComponentB(u8);
@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::preload_hints::{AppPreloadExt, PreloadArea, PreloadHint},
+    core::event::server_event::{SendMode, ToClients},
+    prelude::*,
+    server::relevance::InterestAnchor,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn manual() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_preload_hints::<TestKey>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: PreloadHint {
+            key: TestKey(1),
+            priority: 2,
+        },
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(drain_hints(&mut client_app), [(TestKey(1), 2)]);
+}
+
+#[test]
+fn area() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_preload_hints::<TestKey>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    server_app.world_mut().spawn((
+        PreloadArea {
+            key: TestKey(0),
+            priority: 0,
+            radius: 10.0,
+        },
+        Transform::default(),
+    ));
+    server_app.world_mut().spawn((
+        PreloadArea {
+            key: TestKey(1),
+            priority: 1,
+            radius: 10.0,
+        },
+        Transform::from_xyz(5.0, 0.0, 0.0),
+    ));
+    let anchor = server_app
+        .world_mut()
+        .spawn((
+            InterestAnchor(client_id),
+            Transform::from_xyz(100.0, 0.0, 0.0),
+        ))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    assert!(drain_hints(&mut client_app).is_empty());
+
+    server_app
+        .world_mut()
+        .get_mut::<Transform>(anchor)
+        .unwrap()
+        .translation
+        .x = 2.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    assert_eq!(
+        drain_hints(&mut client_app),
+        [(TestKey(1), 1), (TestKey(0), 0)],
+        "hints should be sorted by priority"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    assert!(
+        drain_hints(&mut client_app).is_empty(),
+        "hints should be sent once"
+    );
+
+    server_app
+        .world_mut()
+        .get_mut::<Transform>(anchor)
+        .unwrap()
+        .translation
+        .x = 14.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    assert!(drain_hints(&mut client_app).is_empty());
+
+    server_app
+        .world_mut()
+        .get_mut::<Transform>(anchor)
+        .unwrap()
+        .translation
+        .x = 2.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    assert_eq!(
+        drain_hints(&mut client_app),
+        [(TestKey(0), 0)],
+        "hint should be sent again after re-entering"
+    );
+}
+
+fn drain_hints(app: &mut App) -> Vec<(TestKey, u8)> {
+    app.world_mut()
+        .resource_mut::<Events<PreloadHint<TestKey>>>()
+        .drain()
+        .map(|hint| (hint.key, hint.priority))
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+struct TestKey(u8);
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{channels::ReplicationChannel, entity_serde, protocol_version::ProtocolVersion},
+    prelude::*,
+    test_app::{message_sink::MessageSinks, ServerTestAppExt},
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn golden() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+
+    let entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(42)))
+        .id();
+
+    let messages = MessageSinks::capture(&mut server_app);
+    let [(channel_id, message)] = messages.as_slice() else {
+        panic!("world should be sent in a single message");
+    };
+    assert_eq!(*channel_id, ReplicationChannel::Updates as u8);
+
+    // Changing the wire format requires incrementing the protocol version.
+    assert_eq!(ProtocolVersion::CURRENT, ProtocolVersion::new(5));
+    // Entity index depends on the number of entities spawned by plugins, so it's serialized separately.
+    let mut golden = GOLDEN_HEADER.to_vec();
+    entity_serde::serialize_entity(&mut golden, entity).unwrap();
+    golden.extend_from_slice(GOLDEN_DATA);
+    assert_eq!(&message[..], golden);
+}
+
+#[test]
+fn version_mismatch() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)));
+
+    server_app.update();
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in server.drain_sent() {
+        let mut message = message.to_vec();
+        message[0] = ProtocolVersion::CURRENT.get() + 1;
+        client.insert_received(channel_id, message);
+    }
+
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    assert_eq!(components.iter(client_app.world()).count(), 0);
+}
+
+/// Update message with a single entity, before the entity.
+const GOLDEN_HEADER: &[u8] = &[
+    5,  // Protocol version.
+    0,  // Compression.
+    32, // Flags with only changes.
+    1,  // Server tick.
+];
+
+/// Update message with a single entity, after the entity.
+const GOLDEN_DATA: &[u8] = &[
+    2,  // Number of components without flags.
+    1,  // Serialization functions ID.
+    42, // Component value.
+];
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
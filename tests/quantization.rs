@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{
+        replication::replication_registry::rule_fns::RuleFns, server_entity_map::ServerEntityMap,
+    },
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn presets() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with(RuleFns::<Position>::fixed_point::<100>())
+        .replicate_with(RuleFns::<Rotation>::smallest_three())
+        .replicate_with(RuleFns::<Health>::half_float())
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let position = Vec3::new(1.234, -5.678, 90.0);
+    let rotation = Quat::from_euler(EulerRot::XYZ, 0.3, -2.0, 1.5);
+    let server_entity = server_app
+        .world_mut()
+        .spawn((
+            Replicated,
+            Position(position),
+            Rotation(rotation),
+            Health(75.5),
+        ))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity = *entity_map.to_client().get(&server_entity).unwrap();
+    let client_entity = client_app.world().entity(client_entity);
+
+    let client_position = client_entity.get::<Position>().unwrap();
+    assert!(client_position.abs_diff_eq(position, 0.005));
+
+    let client_rotation = client_entity.get::<Rotation>().unwrap();
+    assert!(client_rotation.angle_between(rotation) < 0.01);
+
+    let client_health = client_entity.get::<Health>().unwrap();
+    assert_eq!(**client_health, 75.5, "should be representable exactly");
+
+    // Mutate to ensure that mutations use the same functions.
+    let mut position = server_app
+        .world_mut()
+        .get_mut::<Position>(server_entity)
+        .unwrap();
+    position.0 = Vec3::new(-0.011, 0.0, 1.0);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_position = client_app
+        .world_mut()
+        .query::<&Position>()
+        .single(client_app.world());
+    assert_eq!(**client_position, Vec3::new(-0.01, 0.0, 1.0));
+}
+
+#[derive(Component, Deref, Deserialize, Serialize)]
+struct Position(Vec3);
+
+impl From<Vec3> for Position {
+    fn from(value: Vec3) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(Component, Deref, Deserialize, Serialize)]
+struct Rotation(Quat);
+
+impl From<Quat> for Rotation {
+    fn from(value: Quat) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(Component, Deref, Deserialize, Serialize)]
+struct Health(f32);
+
+impl From<f32> for Health {
+    fn from(value: f32) -> Self {
+        Self(value)
+    }
+}
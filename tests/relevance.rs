@@ -0,0 +1,217 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::server_entity_map::ServerEntityMap,
+    prelude::*,
+    server::relevance::{InterestAnchor, RelevancePlugin},
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn radius() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ));
+    }
+    server_app.add_plugins(RelevancePlugin {
+        radius: 10.0,
+        hysteresis: 2.0,
+        grace_ticks: 0,
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let anchor = server_app
+        .world_mut()
+        .spawn((Replicated, InterestAnchor(client_id), Transform::default()))
+        .id();
+    let near_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Transform::from_xyz(5.0, 0.0, 0.0)))
+        .id();
+    let far_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Transform::from_xyz(20.0, 0.0, 0.0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().contains_key(&anchor));
+    assert!(entity_map.to_client().contains_key(&near_entity));
+    assert!(!entity_map.to_client().contains_key(&far_entity));
+
+    // Within hysteresis.
+    move_to(&mut server_app, near_entity, 11.0);
+    move_to(&mut server_app, far_entity, 11.0);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(
+        entity_map.to_client().contains_key(&near_entity),
+        "entity should stay visible within hysteresis"
+    );
+    assert!(
+        !entity_map.to_client().contains_key(&far_entity),
+        "entity should stay hidden outside of radius"
+    );
+
+    move_to(&mut server_app, near_entity, 13.0);
+    move_to(&mut server_app, far_entity, 9.0);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(!entity_map.to_client().contains_key(&near_entity));
+    assert!(entity_map.to_client().contains_key(&far_entity));
+    assert_eq!(entity_map.len(), 2);
+}
+
+#[test]
+fn without_anchor() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Blacklist,
+                ..Default::default()
+            }),
+        ));
+    }
+    server_app.add_plugins(RelevancePlugin::default());
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, Transform::from_xyz(1000.0, 0.0, 0.0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.len(),
+        1,
+        "clients without anchors shouldn't be affected"
+    );
+}
+
+#[test]
+fn grace_ticks() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ));
+    }
+    server_app.add_plugins(RelevancePlugin {
+        radius: 10.0,
+        hysteresis: 0.0,
+        grace_ticks: 2,
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, InterestAnchor(client_id), Transform::default()));
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Transform::from_xyz(5.0, 0.0, 0.0)))
+        .id();
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let entity_map = client_app.world().resource::<ServerEntityMap>();
+        assert!(
+            !entity_map.to_client().contains_key(&server_entity),
+            "entity should become visible only after grace ticks"
+        );
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().contains_key(&server_entity));
+
+    // Leave and return before the grace period expires.
+    move_to(&mut server_app, server_entity, 20.0);
+    server_app.update();
+    move_to(&mut server_app, server_entity, 5.0);
+    for _ in 0..3 {
+        server_app.update();
+    }
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(
+        entity_map.to_client().contains_key(&server_entity),
+        "short trips outside of the radius shouldn't hide the entity"
+    );
+
+    move_to(&mut server_app, server_entity, 20.0);
+    for _ in 0..3 {
+        server_app.update();
+    }
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(!entity_map.to_client().contains_key(&server_entity));
+}
+
+#[test]
+#[should_panic]
+fn all_policy() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins, RelevancePlugin::default()))
+        .finish();
+}
+
+fn move_to(app: &mut App, entity: Entity, x: f32) {
+    let mut transform = app.world_mut().get_mut::<Transform>(entity).unwrap();
+    transform.translation.x = x;
+}
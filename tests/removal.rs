@@ -3,7 +3,11 @@ use bevy_replicon::{
     client::confirm_history::{ConfirmHistory, EntityReplicated},
     core::replication::{
         deferred_entity::DeferredEntity,
-        replication_registry::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+        replication_registry::{
+            command_fns,
+            ctx::{RemoveCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
     },
     prelude::*,
     server::server_tick::ServerTick,
@@ -162,6 +166,62 @@ fn marker() {
     assert!(!client_entity.contains::<ReplacedComponent>());
 }
 
+#[test]
+fn final_value() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .register_marker::<FadeMarker>()
+        .replicate_with(RuleFns::<StackComponent>::default().with_final_value())
+        .set_marker_fns::<FadeMarker, _>(command_fns::default_write::<StackComponent>, fade_out)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, StackComponent(1)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<StackComponent>>()
+        .single(client_app.world());
+    client_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(FadeMarker);
+
+    // Change and remove in the same tick, so the change is sent only with the removal.
+    let mut server_entity = server_app.world_mut().entity_mut(server_entity);
+    server_entity.get_mut::<StackComponent>().unwrap().0 = 3;
+    server_entity.remove::<StackComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(!client_entity.contains::<StackComponent>());
+    let fading = client_entity
+        .get::<FadingStacks>()
+        .expect("remove function should receive the final value");
+    assert_eq!(fading.0, 3);
+}
+
 #[test]
 fn group() {
     let mut server_app = App::new();
@@ -515,6 +575,26 @@ struct OriginalComponent;
 #[derive(Component, Deserialize, Serialize)]
 struct ReplacedComponent;
 
+#[derive(Component)]
+struct FadeMarker;
+
+#[derive(Component, Deserialize, Serialize)]
+struct StackComponent(usize);
+
+#[derive(Component)]
+struct FadingStacks(usize);
+
+/// Replaces [`StackComponent`] with [`FadingStacks`] that holds its final value.
+fn fade_out(ctx: &mut RemoveCtx, entity: &mut DeferredEntity) {
+    if ctx.final_value {
+        let stacks = entity.get::<StackComponent>().unwrap().0;
+        ctx.commands
+            .entity(entity.id())
+            .insert(FadingStacks(stacks));
+    }
+    command_fns::default_remove::<StackComponent>(ctx, entity);
+}
+
 /// Deserializes [`OriginalComponent`], but ignores it and inserts [`ReplacedComponent`].
 fn replace(
     ctx: &mut WriteCtx,
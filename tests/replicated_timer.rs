@@ -0,0 +1,245 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::Events, prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{
+    addons::replicated_timer::{ReplicatedTimer, ReplicatedTimerPlugin, TickClock, TimerFinished},
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn finishing() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ReplicatedTimerPlugin::default(),
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let end_tick = server_tick + 2;
+    let server_entity = server_app
+        .world_mut()
+        .spawn(ReplicatedTimer::new(end_tick))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(drain_finished(&mut server_app).is_empty());
+    assert!(drain_finished(&mut client_app).is_empty());
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<ReplicatedTimer>>()
+        .single(client_app.world());
+
+    server_app.update();
+    assert_eq!(
+        drain_finished(&mut server_app),
+        [TimerFinished {
+            entity: server_entity,
+            tick: end_tick,
+        }]
+    );
+
+    // Client shouldn't finish the timer before receiving the end tick.
+    client_app.update();
+    assert!(drain_finished(&mut client_app).is_empty());
+
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    assert_eq!(
+        drain_finished(&mut client_app),
+        [TimerFinished {
+            entity: client_entity,
+            tick: end_tick,
+        }]
+    );
+
+    let timer = client_app
+        .world()
+        .get::<ReplicatedTimer>(client_entity)
+        .unwrap();
+    assert!(timer.is_finished());
+}
+
+#[test]
+fn restart() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ReplicatedTimerPlugin::default(),
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let server_entity = server_app
+        .world_mut()
+        .spawn(ReplicatedTimer::new(server_tick + 1))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(drain_finished(&mut server_app).len(), 1);
+    assert_eq!(drain_finished(&mut client_app).len(), 1);
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let end_tick = server_tick + 2;
+    let mut timer = server_app
+        .world_mut()
+        .get_mut::<ReplicatedTimer>(server_entity)
+        .unwrap();
+    timer.set_end_tick(end_tick);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(drain_finished(&mut server_app).is_empty());
+    assert!(drain_finished(&mut client_app).is_empty());
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_events = drain_finished(&mut server_app);
+    let client_events = drain_finished(&mut client_app);
+    assert_eq!(server_events.len(), 1);
+    assert_eq!(client_events.len(), 1);
+    assert_eq!(server_events[0].tick, end_tick);
+    assert_eq!(client_events[0].tick, end_tick);
+}
+
+#[test]
+fn finished_before_connection() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ReplicatedTimerPlugin::default(),
+        ))
+        .finish();
+    }
+
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    server_app
+        .world_mut()
+        .spawn(ReplicatedTimer::new(server_tick + 1));
+
+    server_app.update();
+    server_app.update();
+    assert_eq!(drain_finished(&mut server_app).len(), 1);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let timer = client_app
+        .world_mut()
+        .query::<&ReplicatedTimer>()
+        .single(client_app.world());
+    assert!(timer.is_finished());
+    assert!(
+        drain_finished(&mut client_app).is_empty(),
+        "timers that were already finished shouldn't emit events"
+    );
+}
+
+#[test]
+fn remaining() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ReplicatedTimerPlugin::default(),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            50,
+        )))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let end_tick = server_tick + 100;
+    server_app.world_mut().spawn(ReplicatedTimer::new(end_tick));
+
+    for _ in 0..50 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    for app in [&mut server_app, &mut client_app] {
+        let clock = *app.world().resource::<TickClock>();
+        let tick_duration = clock.tick_duration().as_secs_f64();
+        assert!(
+            (tick_duration - 0.05).abs() < 0.005,
+            "tick duration should be measured, got {tick_duration}"
+        );
+
+        let timer = *app
+            .world_mut()
+            .query::<&ReplicatedTimer>()
+            .single(app.world());
+        let remaining = timer.remaining(&clock).as_secs_f64();
+        let last_tick = clock.last_tick().unwrap();
+        let expected = (end_tick - last_tick) as f64 * 0.05;
+        assert!(
+            (remaining - expected).abs() < 0.1,
+            "remaining time should be close to {expected}, got {remaining}"
+        );
+    }
+}
+
+fn drain_finished(app: &mut App) -> Vec<TimerFinished> {
+    app.world_mut()
+        .resource_mut::<Events<TimerFinished>>()
+        .drain()
+        .collect()
+}
@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    prelude::*,
+    server::replication_budget::{DespawnBudget, ReplicationBudget, ReplicationBudgetExceeded},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn deferred_mutations() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .insert_resource(ReplicationBudget::new(Duration::ZERO));
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)));
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    assert_eq!(
+        components.iter(client_app.world()).count(),
+        2,
+        "insertions should be sent regardless of the budget"
+    );
+
+    let mut server_components = server_app.world_mut().query::<&mut DummyComponent>();
+    for mut component in server_components.iter_mut(server_app.world_mut()) {
+        component.0 = 1;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(components
+        .iter(client_app.world())
+        .all(|component| component.0 == 0));
+
+    let mut exceeded_events = server_app
+        .world_mut()
+        .resource_mut::<Events<ReplicationBudgetExceeded>>();
+    let event = exceeded_events.drain().last().unwrap();
+    assert_eq!(event.deferred_entities, 2);
+
+    server_app
+        .world_mut()
+        .remove_resource::<ReplicationBudget>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        components
+            .iter(client_app.world())
+            .all(|component| component.0 == 1),
+        "deferred mutations should be sent on the next tick"
+    );
+}
+
+#[test]
+fn deferred_despawns() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entities: Vec<_> = (0..3)
+        .map(|_| server_app.world_mut().spawn(Replicated).id())
+        .collect();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 3);
+
+    // Budget is too small to fit even a single entity.
+    server_app
+        .world_mut()
+        .insert_resource(DespawnBudget::new(1));
+    for entity in server_entities {
+        server_app.world_mut().despawn(entity);
+    }
+
+    for deferred in [2, 1, 0] {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let budget = server_app.world().resource::<DespawnBudget>();
+        assert_eq!(budget.deferred_despawns(), deferred);
+        assert_eq!(
+            replicated.iter(client_app.world()).count(),
+            deferred,
+            "only a single despawn should be sent per tick"
+        );
+    }
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
@@ -0,0 +1,139 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::{
+    prelude::*,
+    server::replication_constraints::{ConstraintViolation, ReplicationConstraints},
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn unmapped_entity() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .init_resource::<ReplicationConstraints>()
+        .register_type::<EntityComponent>()
+        .replicate::<EntityComponent>();
+
+    let entity = app
+        .world_mut()
+        .spawn((Replicated, EntityComponent(Entity::PLACEHOLDER)))
+        .id();
+
+    app.update();
+
+    let component_id = app.world().component_id::<EntityComponent>().unwrap();
+    assert_eq!(
+        drain_violations(&mut app),
+        [ConstraintViolation::UnmappedEntity {
+            entity,
+            component_id
+        }]
+    );
+
+    app.world_mut()
+        .spawn((Replicated, EntityComponent(Entity::PLACEHOLDER)));
+
+    app.update();
+
+    assert!(
+        drain_violations(&mut app).is_empty(),
+        "each component should be reported once"
+    );
+}
+
+#[test]
+fn mapped_entity() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .init_resource::<ReplicationConstraints>()
+        .register_type::<EntityComponent>()
+        .replicate_mapped::<EntityComponent>();
+
+    app.world_mut()
+        .spawn((Replicated, EntityComponent(Entity::PLACEHOLDER)));
+
+    app.update();
+
+    assert!(drain_violations(&mut app).is_empty());
+}
+
+#[test]
+fn allowed_entity() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .init_resource::<ReplicationConstraints>()
+        .register_type::<EntityComponent>()
+        .replicate::<EntityComponent>();
+
+    app.world_mut()
+        .resource_mut::<ReplicationConstraints>()
+        .allow_entities::<EntityComponent>();
+
+    app.world_mut()
+        .spawn((Replicated, EntityComponent(Entity::PLACEHOLDER)));
+
+    app.update();
+
+    assert!(drain_violations(&mut app).is_empty());
+}
+
+#[test]
+fn incomplete_group() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .init_resource::<ReplicationConstraints>()
+        .replicate_group::<(ComponentA, ComponentB)>();
+
+    let entity = app.world_mut().spawn((Replicated, ComponentA)).id();
+    app.world_mut().spawn((Replicated, ComponentA, ComponentB));
+
+    app.update();
+
+    let component_id = app.world().component_id::<ComponentB>().unwrap();
+    assert_eq!(
+        drain_violations(&mut app),
+        [ConstraintViolation::IncompleteGroup {
+            entity,
+            missing: vec![component_id]
+        }]
+    );
+}
+
+#[test]
+fn covered_group() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .init_resource::<ReplicationConstraints>()
+        .replicate::<ComponentA>()
+        .replicate_group::<(ComponentA, ComponentB)>();
+
+    app.world_mut().spawn((Replicated, ComponentA));
+
+    app.update();
+
+    assert!(
+        drain_violations(&mut app).is_empty(),
+        "component should be replicated by its own rule"
+    );
+}
+
+fn drain_violations(app: &mut App) -> Vec<ConstraintViolation> {
+    app.world_mut()
+        .resource_mut::<Events<ConstraintViolation>>()
+        .drain()
+        .collect()
+}
+
+#[derive(Component, Deserialize, Reflect, Serialize)]
+struct EntityComponent(Entity);
+
+impl MapEntities for EntityComponent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct ComponentA;
+
+#[derive(Component, Deserialize, Serialize)]
+struct ComponentB;
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::server_entity_map::ServerEntityMap, prelude::*,
+    server::replication_preview::ReplicationPreviewExt, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn predicate() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate::<DummyComponent>();
+
+    let visible_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(1)))
+        .id();
+    let hidden_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(2)))
+        .id();
+    let not_replicated = server_app.world_mut().spawn(DummyComponent(3)).id();
+
+    let world = server_app.world();
+    let preview = world.preview_replication(|entity| entity != hidden_entity);
+    assert_eq!(preview.entities.len(), 1);
+    assert!(preview.get(hidden_entity).is_none());
+    assert!(preview.get(not_replicated).is_none());
+
+    let preview_entity = preview
+        .get(visible_entity)
+        .expect("visible entity should be previewed");
+    let component_id = world.component_id::<DummyComponent>().unwrap();
+    assert_eq!(preview_entity.components, [component_id]);
+    assert!(preview_entity.bytes > 0);
+    assert_eq!(preview.bytes(), preview_entity.bytes);
+}
+
+#[test]
+fn client_visibility() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let visible_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(1)))
+        .id();
+    let hidden_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(2)))
+        .id();
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    server_app
+        .world_mut()
+        .resource_mut::<ReplicatedClients>()
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(visible_entity, true);
+
+    let preview = server_app
+        .world()
+        .preview_client_replication(client_id)
+        .expect("client should have replication enabled");
+    assert!(preview.get(visible_entity).is_some());
+    assert!(preview.get(hidden_entity).is_none());
+
+    assert!(server_app
+        .world()
+        .preview_client_replication(ClientId::new(u64::MAX))
+        .is_none());
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(
+        entity_map.to_client().is_empty(),
+        "preview shouldn't send anything"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u32);
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*, server::replication_worker::ReplicationWorker, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn next_frame() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.init_resource::<ReplicationWorker>();
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    assert_eq!(
+        components.iter(client_app.world()).count(),
+        0,
+        "messages should be sent on the next frame"
+    );
+    assert_eq!(
+        server_app
+            .world()
+            .resource::<ReplicationWorker>()
+            .pending_ticks(),
+        1
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(components.iter(client_app.world()).count(), 1);
+
+    let mut server_components = server_app.world_mut().query::<&mut DummyComponent>();
+    server_components.single_mut(server_app.world_mut()).0 = 1;
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let component = components.single(client_app.world());
+    assert_eq!(component.0, 1);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
@@ -0,0 +1,157 @@
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+use bevy_replicon::{
+    addons::resimulation::{Resimulated, ResimulationPlugin, TickInputs, TickedInput},
+    core::replicon_tick::RepliconTick,
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn late_input() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+        ResimulationPlugin::<DummyInput>::new(Simulation, 2),
+    ))
+    .replicate::<DummyComponent>()
+    .add_systems(Simulation, simulate)
+    .finish();
+
+    app.world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let entity = app
+        .world_mut()
+        .spawn((Replicated, Resimulated, DummyComponent(1)))
+        .id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(**app.world().resource::<ServerTick>(), RepliconTick::new(3));
+    assert_eq!(app.world().get::<DummyComponent>(entity).unwrap().0, 8);
+
+    app.world_mut().send_event(FromClient {
+        client_id: ClientId::SERVER,
+        event: TickedInput {
+            tick: RepliconTick::new(2),
+            input: DummyInput(1),
+        },
+    });
+
+    app.update();
+
+    // Tick 2 is resimulated from 2 into 5, tick 3 into 10 and tick 4 is simulated as usual.
+    assert_eq!(app.world().get::<DummyComponent>(entity).unwrap().0, 20);
+}
+
+#[test]
+fn too_late_input() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+        ResimulationPlugin::<DummyInput>::new(Simulation, 1),
+    ))
+    .replicate::<DummyComponent>()
+    .add_systems(Simulation, simulate)
+    .finish();
+
+    app.world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let entity = app
+        .world_mut()
+        .spawn((Replicated, Resimulated, DummyComponent(1)))
+        .id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    app.world_mut().send_event(FromClient {
+        client_id: ClientId::SERVER,
+        event: TickedInput {
+            tick: RepliconTick::new(2),
+            input: DummyInput(1),
+        },
+    });
+
+    app.update();
+
+    assert_eq!(app.world().get::<DummyComponent>(entity).unwrap().0, 16);
+}
+
+#[test]
+fn client_input() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ResimulationPlugin::<DummyInput>::new(Simulation, 2),
+        ))
+        .replicate::<DummyComponent>()
+        .add_systems(Simulation, simulate)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, Resimulated, DummyComponent(1)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let tick = **server_app.world().resource::<ServerTick>();
+    client_app.world_mut().send_event(TickedInput {
+        tick: tick + 1,
+        input: DummyInput(1),
+    });
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&DummyComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, 5);
+}
+
+fn simulate(inputs: Res<TickInputs<DummyInput>>, mut components: Query<&mut DummyComponent>) {
+    let input: u32 = inputs.iter().map(|(_, input)| input.0).sum();
+    for mut component in &mut components {
+        component.0 = component.0 * 2 + input;
+    }
+}
+
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct Simulation;
+
+#[derive(Clone, Deserialize, Serialize)]
+struct DummyInput(u32);
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u32);
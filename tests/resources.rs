@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, server::server_tick::ServerTick, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn insertion() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<TestResource>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.insert_resource(TestResource(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<TestResource>();
+    assert_eq!(resource.0, 1);
+}
+
+#[test]
+fn mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<TestResource>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.insert_resource(TestResource(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    // Modify locally to check that unchanged resources aren't sent.
+    client_app.world_mut().resource_mut::<TestResource>().0 = 0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<TestResource>();
+    assert_eq!(resource.0, 0, "unchanged resource shouldn't be sent");
+
+    server_app.world_mut().resource_mut::<TestResource>().0 = 2;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<TestResource>();
+    assert_eq!(resource.0, 2);
+}
+
+#[test]
+fn removal() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<TestResource>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.insert_resource(TestResource(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    assert!(client_app.world().contains_resource::<TestResource>());
+
+    server_app.world_mut().remove_resource::<TestResource>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(!client_app.world().contains_resource::<TestResource>());
+}
+
+#[test]
+fn initial_sync() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<TestResource>()
+        .replicate_resource::<OtherResource>();
+    }
+
+    server_app
+        .insert_resource(TestResource(1))
+        .insert_resource(OtherResource);
+
+    server_app.update();
+    server_app.update();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<TestResource>();
+    assert_eq!(resource.0, 1);
+    assert!(client_app.world().contains_resource::<OtherResource>());
+}
+
+#[test]
+fn after_tick() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::Manual,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<TestResource>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.insert_resource(TestResource(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    assert!(!client_app.world().contains_resource::<TestResource>());
+
+    // Trigger replication.
+    server_app
+        .world_mut()
+        .resource_mut::<ServerTick>()
+        .increment();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<TestResource>();
+    assert_eq!(resource.0, 1, "change should be sent on the next tick");
+}
+
+#[derive(Resource, Deserialize, Serialize)]
+struct TestResource(u8);
+
+#[derive(Resource, Deserialize, Serialize)]
+struct OtherResource;
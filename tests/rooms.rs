@@ -0,0 +1,239 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::server_entity_map::ServerEntityMap,
+    prelude::*,
+    server::{
+        isolation_audit::{IsolationAudit, IsolationDomain, IsolationLeak},
+        rooms::{InRoom, RoomId, RoomMember, Rooms, RoomsPlugin},
+    },
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn rooms() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ));
+    }
+    server_app.add_plugins(RoomsPlugin);
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    let client_id1 = client_app1
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let client_id2 = client_app2
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    join(&mut server_app, client_id1, ROOM1);
+    join(&mut server_app, client_id2, ROOM2);
+
+    let entity1 = server_app
+        .world_mut()
+        .spawn((Replicated, InRoom(ROOM1)))
+        .id();
+    let entity2 = server_app
+        .world_mut()
+        .spawn((Replicated, InRoom(ROOM2)))
+        .id();
+    let global_entity = server_app.world_mut().spawn(Replicated).id();
+
+    let client_entity = server_app
+        .world()
+        .resource::<ConnectedClients>()
+        .entity(client_id1)
+        .unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    for client_id in [client_id1, client_id2] {
+        let visibility = replicated_clients.client_mut(client_id).visibility_mut();
+        visibility.set_visibility(global_entity, true);
+    }
+
+    server_app.update();
+    for client_app in [&mut client_app1, &mut client_app2] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+        server_app.exchange_with_client(client_app);
+    }
+
+    let rooms = server_app.world().resource::<Rooms>();
+    assert_eq!(rooms.client_room(client_id1), Some(ROOM1));
+    assert_eq!(rooms.clients(ROOM2).collect::<Vec<_>>(), [client_id2]);
+
+    let entity_map = client_app1.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().contains_key(&entity1));
+    assert!(!entity_map.to_client().contains_key(&entity2));
+    assert!(entity_map.to_client().contains_key(&global_entity));
+
+    let entity_map = client_app2.world().resource::<ServerEntityMap>();
+    assert!(!entity_map.to_client().contains_key(&entity1));
+    assert!(entity_map.to_client().contains_key(&entity2));
+    assert!(entity_map.to_client().contains_key(&global_entity));
+
+    // Move the first client into the second room.
+    server_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(RoomMember(ROOM2));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    client_app1.update();
+
+    let entity_map = client_app1.world().resource::<ServerEntityMap>();
+    assert!(
+        !entity_map.to_client().contains_key(&entity1),
+        "entity from the previous room should be despawned"
+    );
+    assert!(entity_map.to_client().contains_key(&entity2));
+    assert!(entity_map.to_client().contains_key(&global_entity));
+    assert_eq!(entity_map.len(), 2);
+}
+
+#[test]
+fn entity_room_change() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Blacklist,
+                ..Default::default()
+            }),
+        ));
+    }
+    server_app.add_plugins(RoomsPlugin);
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    join(&mut server_app, client_id, ROOM1);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, InRoom(ROOM2)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.is_empty());
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(InRoom(ROOM1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().contains_key(&server_entity));
+}
+
+#[test]
+fn isolation_audit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ));
+    }
+    server_app
+        .add_plugins(RoomsPlugin)
+        .insert_resource(IsolationAudit::new(false))
+        .init_resource::<LeakedEntity>()
+        .add_systems(PostUpdate, leak_entity.in_set(ServerSet::Send));
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    join(&mut server_app, client_id, ROOM1);
+
+    server_app.world_mut().spawn((Replicated, InRoom(ROOM1)));
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, InRoom(ROOM2)))
+        .id();
+
+    server_app.update();
+
+    let audit = server_app.world().resource::<IsolationAudit>();
+    assert!(audit.leaks().is_empty());
+
+    server_app.world_mut().resource_mut::<LeakedEntity>().0 = Some(server_entity);
+
+    server_app.update();
+
+    let audit = server_app.world().resource::<IsolationAudit>();
+    assert_eq!(
+        audit.leaks(),
+        [IsolationLeak {
+            client_id,
+            client_domain: Some(IsolationDomain(ROOM1.get())),
+            entity: server_entity,
+            entity_domain: IsolationDomain(ROOM2.get()),
+        }]
+    );
+}
+
+fn join(server_app: &mut App, client_id: ClientId, room_id: RoomId) {
+    let entity = server_app
+        .world()
+        .resource::<ConnectedClients>()
+        .entity(client_id)
+        .unwrap();
+    server_app
+        .world_mut()
+        .entity_mut(entity)
+        .insert(RoomMember(room_id));
+}
+
+const ROOM1: RoomId = RoomId::new(1);
+const ROOM2: RoomId = RoomId::new(2);
+
+/// Makes the entity visible to all clients after rooms are updated.
+fn leak_entity(leaked: Res<LeakedEntity>, mut replicated_clients: ResMut<ReplicatedClients>) {
+    if let Some(entity) = leaked.0 {
+        for client in replicated_clients.iter_mut() {
+            client.visibility_mut().set_visibility(entity, true);
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+struct LeakedEntity(Option<Entity>);
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::roster::{ClientRosterPlugin, RosterEntry},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn connect_and_disconnect() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ClientRosterPlugin,
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app1.update();
+    client_app2.update();
+
+    let client_id1 = client_app1
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let client_id2 = client_app2
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    for client_app in [&mut client_app1, &mut client_app2] {
+        let mut clients: Vec<_> = client_app
+            .world_mut()
+            .query::<&RosterEntry>()
+            .iter(client_app.world())
+            .map(|entry| entry.client_id)
+            .collect();
+        clients.sort_unstable();
+        assert_eq!(clients, [client_id1, client_id2]);
+    }
+
+    server_app.disconnect_client(&mut client_app2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    client_app1.update();
+
+    let entry = client_app1
+        .world_mut()
+        .query::<&RosterEntry>()
+        .single(client_app1.world());
+    assert_eq!(entry.client_id, client_id1);
+}
+
+#[test]
+fn payload() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ClientRosterPlugin,
+        ))
+        .replicate::<Nickname>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let connected_clients = server_app.world().resource::<ConnectedClients>();
+    let client_entity = connected_clients.entity(client_id).unwrap();
+    server_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(Nickname("Alice".into()));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (entry, nickname) = client_app
+        .world_mut()
+        .query::<(&RosterEntry, &Nickname)>()
+        .single(client_app.world());
+    assert_eq!(entry.client_id, client_id);
+    assert_eq!(nickname.0, "Alice");
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct Nickname(String);
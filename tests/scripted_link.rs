@@ -0,0 +1,199 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::channels::ReplicationChannel,
+    prelude::*,
+    test_app::{
+        scripted_link::{Delivery, Direction, Packet, ScriptedLink},
+        ServerTestAppExt,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn delayed_mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mut link = ScriptedLink::new(|_| Delivery::Deliver);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+
+    // Delay only the first mutate message.
+    let mut delayed = false;
+    link.set_script(move |packet| {
+        if is_mutation(packet) && !delayed {
+            delayed = true;
+            Delivery::Delay(2)
+        } else {
+            Delivery::Deliver
+        }
+    });
+
+    for value in 1..=2 {
+        set_value(&mut server_app, server_entity, value);
+        server_app.update();
+        link.exchange(&mut server_app, &mut client_app);
+        client_app.update();
+    }
+    assert_eq!(client_value(&mut client_app), 2);
+    assert_eq!(link.in_flight_count(), 1);
+
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+    assert_eq!(link.in_flight_count(), 0);
+    assert_eq!(
+        client_value(&mut client_app),
+        2,
+        "outdated mutation shouldn't be applied"
+    );
+}
+
+#[test]
+fn held_mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mut link = ScriptedLink::new(|packet| {
+        if is_mutation(packet) {
+            Delivery::Hold
+        } else {
+            Delivery::Deliver
+        }
+    });
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+
+    set_value(&mut server_app, server_entity, 1);
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+    assert_eq!(link.held_count(), 1);
+    assert_eq!(client_value(&mut client_app), 0);
+
+    link.release_held();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+    assert_eq!(link.held_count(), 0);
+    assert_eq!(client_value(&mut client_app), 1);
+}
+
+#[test]
+fn duplicated_and_dropped() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mut link = ScriptedLink::new(|packet| {
+        if is_mutation(packet) {
+            Delivery::Duplicate
+        } else {
+            Delivery::Deliver
+        }
+    });
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+
+    set_value(&mut server_app, server_entity, 1);
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+    assert_eq!(client_value(&mut client_app), 1);
+
+    link.set_script(|packet| {
+        if is_mutation(packet) {
+            Delivery::Drop
+        } else {
+            Delivery::Deliver
+        }
+    });
+
+    set_value(&mut server_app, server_entity, 2);
+    server_app.update();
+    link.exchange(&mut server_app, &mut client_app);
+    client_app.update();
+    assert_eq!(link.in_flight_count(), 0);
+    assert_eq!(client_value(&mut client_app), 1);
+}
+
+fn is_mutation(packet: &Packet) -> bool {
+    packet.direction == Direction::ToClient
+        && packet.channel_id == ReplicationChannel::Mutations as u8
+}
+
+fn set_value(server_app: &mut App, server_entity: Entity, value: u32) {
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_entity)
+        .unwrap();
+    component.0 = value;
+}
+
+fn client_value(client_app: &mut App) -> u32 {
+    client_app
+        .world_mut()
+        .query::<&TestComponent>()
+        .single(client_app.world())
+        .0
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u32);
@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::server_entity_map::ServerEntityMap,
+    prelude::*,
+    server::send_budget::{ReplicationPriority, SendBudget},
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn priority() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let low_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)))
+        .id();
+    let high_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0), ReplicationPriority(10.0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().insert_resource(SendBudget::new(1));
+
+    for entity in [low_entity, high_entity] {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<DummyComponent>(entity)
+            .unwrap();
+        component.0 = 1;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_low = *entity_map.to_client().get(&low_entity).unwrap();
+    let client_high = *entity_map.to_client().get(&high_entity).unwrap();
+    assert_eq!(
+        client_app.world().get::<DummyComponent>(client_high),
+        Some(&DummyComponent(1)),
+        "entity with higher priority should be sent first"
+    );
+    assert_eq!(
+        client_app.world().get::<DummyComponent>(client_low),
+        Some(&DummyComponent(0))
+    );
+
+    let send_budget = server_app.world().resource::<SendBudget>();
+    assert_eq!(send_budget.deferred_entities(), 1);
+    assert_eq!(
+        send_budget.accumulated_priority(client_id, low_entity),
+        Some(1.0)
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(
+        client_app.world().get::<DummyComponent>(client_low),
+        Some(&DummyComponent(1)),
+        "deferred entity should be sent on the next tick"
+    );
+
+    let send_budget = server_app.world().resource::<SendBudget>();
+    assert_eq!(send_budget.deferred_entities(), 0);
+    assert_eq!(
+        send_budget.accumulated_priority(client_id, low_entity),
+        None
+    );
+}
+
+#[test]
+fn accumulation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let low_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)))
+        .id();
+    let high_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0), ReplicationPriority(2.5)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().insert_resource(SendBudget::new(1));
+
+    // Mutate the high-priority entity every tick, the low-priority entity should still be sent eventually.
+    for value in 1..=3 {
+        for entity in [low_entity, high_entity] {
+            let mut component = server_app
+                .world_mut()
+                .get_mut::<DummyComponent>(entity)
+                .unwrap();
+            component.0 = value;
+        }
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_low = *entity_map.to_client().get(&low_entity).unwrap();
+    assert_eq!(
+        client_app.world().get::<DummyComponent>(client_low),
+        Some(&DummyComponent(3)),
+        "accumulated priority should exceed the priority of the other entity"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize, Debug, PartialEq)]
+struct DummyComponent(u8);
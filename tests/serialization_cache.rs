@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    prelude::*, server::serialization_cache::SerializationCache, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn unacked_resend() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .insert_resource(SerializationCache::new(1024));
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let cache = server_app.world().resource::<SerializationCache>();
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.hits(), 0);
+    assert_eq!(cache.misses(), 1);
+
+    server_app
+        .world_mut()
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    // Send the mutation twice without acknowledgment.
+    server_app.update();
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let cache = server_app.world().resource::<SerializationCache>();
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.hits(), 1, "resent value should be taken from cache");
+    assert_eq!(cache.misses(), 2);
+
+    let component = client_app
+        .world_mut()
+        .query::<&DummyComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, 1);
+}
+
+#[test]
+fn eviction() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Enough for two values.
+    server_app
+        .world_mut()
+        .insert_resource(SerializationCache::new(4));
+
+    for _ in 0..3 {
+        server_app
+            .world_mut()
+            .spawn((Replicated, DummyComponent(0)));
+    }
+
+    server_app.update();
+
+    let cache = server_app.world().resource::<SerializationCache>();
+    assert_eq!(cache.misses(), 3);
+    assert_eq!(cache.len(), 2, "the oldest value should be evicted");
+    assert!(cache.bytes() <= cache.max_bytes());
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);
@@ -1,11 +1,17 @@
 use bevy::{
-    ecs::{entity::MapEntities, event::Events},
+    ecs::{entity::MapEntities, event::Events, system::RunSystemOnce},
     prelude::*,
     time::TimePlugin,
 };
 use bevy_replicon::{
-    client::ServerUpdateTick, core::server_entity_map::ServerEntityMap, prelude::*,
-    server::server_tick::ServerTick, test_app::ServerTestAppExt,
+    client::ServerUpdateTick,
+    core::{
+        channels::ReplicationChannel, event::server_event::SyncingPolicy,
+        server_entity_map::ServerEntityMap,
+    },
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
 };
 use serde::{Deserialize, Serialize};
 
@@ -36,6 +42,7 @@ fn sending_receiving() {
         (SendMode::Direct(client_id), 1),
         (SendMode::BroadcastExcept(ClientId::SERVER), 1),
         (SendMode::BroadcastExcept(client_id), 0),
+        (SendMode::Observers(Entity::PLACEHOLDER), 1),
     ] {
         server_app.world_mut().send_event(ToClients {
             mode,
@@ -56,6 +63,140 @@ fn sending_receiving() {
     }
 }
 
+#[test]
+fn sending_with_commands() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .run_system_once(|mut commands: Commands| {
+            commands.server_send(ToClients {
+                mode: SendMode::Broadcast,
+                event: DummyEvent,
+            });
+        })
+        .unwrap();
+    server_app.world_mut().server_send(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let dummy_events = client_app.world().resource::<Events<DummyEvent>>();
+    assert_eq!(dummy_events.len(), 2);
+}
+
+#[test]
+fn without_replication() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    server_app.add_plugins((
+        MinimalPlugins,
+        RepliconCorePlugin,
+        ServerSessionPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+        },
+        ServerEventPlugin,
+    ));
+    client_app.add_plugins((MinimalPlugins, RepliconPlugins));
+    for app in [&mut server_app, &mut client_app] {
+        app.add_server_event::<DummyEvent>(ChannelKind::Ordered)
+            .add_server_event::<IndependentEvent>(ChannelKind::Ordered)
+            .make_independent::<IndependentEvent>()
+            .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+    });
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: IndependentEvent,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut dummy_events = client_app.world_mut().resource_mut::<Events<DummyEvent>>();
+    assert_eq!(dummy_events.drain().count(), 1);
+
+    let mut independent_events = client_app
+        .world_mut()
+        .resource_mut::<Events<IndependentEvent>>();
+    assert_eq!(independent_events.drain().count(), 1);
+}
+
+#[test]
+fn sending_to_observers() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let visible_entity = server_app.world_mut().spawn(Replicated).id();
+    let hidden_entity = server_app.world_mut().spawn(Replicated).id();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(visible_entity, true);
+
+    for (entity, events_count) in [(visible_entity, 1), (hidden_entity, 0)] {
+        server_app.world_mut().send_event(ToClients {
+            mode: SendMode::Observers(entity),
+            event: DummyEvent,
+        });
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let mut events = client_app.world_mut().resource_mut::<Events<DummyEvent>>();
+        assert_eq!(
+            events.drain().count(),
+            events_count,
+            "event should be emitted {events_count} times for {entity}"
+        );
+    }
+}
+
 #[test]
 fn sending_receiving_and_mapping() {
     let mut server_app = App::new();
@@ -139,6 +280,7 @@ fn sending_receiving_without_plugins() {
         (SendMode::Direct(client_id), 1),
         (SendMode::BroadcastExcept(ClientId::SERVER), 1),
         (SendMode::BroadcastExcept(client_id), 0),
+        (SendMode::Observers(Entity::PLACEHOLDER), 1),
     ] {
         server_app.world_mut().send_event(ToClients {
             mode,
@@ -179,6 +321,7 @@ fn local_resending() {
         (SendMode::Direct(DUMMY_CLIENT_ID), 0),
         (SendMode::BroadcastExcept(ClientId::SERVER), 0),
         (SendMode::BroadcastExcept(DUMMY_CLIENT_ID), 1),
+        (SendMode::Observers(Entity::PLACEHOLDER), 1),
     ] {
         app.world_mut().send_event(ToClients {
             mode,
@@ -307,7 +450,7 @@ fn event_queue_and_mapping() {
                 ..Default::default()
             }),
         ))
-        .add_server_event::<EntityEvent>(ChannelKind::Ordered)
+        .add_mapped_server_event::<EntityEvent>(ChannelKind::Ordered)
         .finish();
     }
 
@@ -356,6 +499,153 @@ fn event_queue_and_mapping() {
     assert_eq!(mapped_entities, [client_entity]);
 }
 
+#[test]
+fn mapping_spawned_entity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_mapped_server_event::<EntityEvent>(ChannelKind::Ordered)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Send the event on the same tick as the spawn.
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: EntityEvent(server_entity),
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .get_by_server(server_entity)
+        .expect("entity should be replicated");
+
+    let mapped_entities: Vec<_> = client_app
+        .world_mut()
+        .resource_mut::<Events<EntityEvent>>()
+        .drain()
+        .map(|event| event.0)
+        .collect();
+    assert_eq!(mapped_entities, [client_entity]);
+}
+
+#[test]
+fn mapping_delayed_spawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_mapped_server_event::<EntityEvent>(ChannelKind::Ordered)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: EntityEvent(server_entity),
+    });
+
+    server_app.update();
+
+    // Deliver the event before the update message with the spawn.
+    let (updates, events): (Vec<_>, Vec<_>) = server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .partition(|&(_, channel_id, _)| channel_id == ReplicationChannel::Updates as u8);
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in events {
+        client.insert_received(channel_id, message);
+    }
+    client_app.update();
+
+    assert!(
+        client_app
+            .world()
+            .resource::<Events<EntityEvent>>()
+            .is_empty(),
+        "event should wait for the spawn"
+    );
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in updates {
+        client.insert_received(channel_id, message);
+    }
+    client_app.update();
+
+    let client_entity = client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .get_by_server(server_entity)
+        .expect("entity should be replicated");
+
+    let mapped_entities: Vec<_> = client_app
+        .world_mut()
+        .resource_mut::<Events<EntityEvent>>()
+        .drain()
+        .map(|event| event.0)
+        .collect();
+    assert_eq!(mapped_entities, [client_entity]);
+}
+
+#[test]
+fn mapping_unknown_entity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_mapped_server_event::<EntityEvent>(ChannelKind::Ordered)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn_empty().id();
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: EntityEvent(server_entity),
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let events = client_app.world().resource::<Events<EntityEvent>>();
+    assert!(
+        events.is_empty(),
+        "event with entities unknown to the client should be discarded"
+    );
+}
+
 #[test]
 fn multiple_event_queues() {
     let mut server_app = App::new();
@@ -459,6 +749,7 @@ fn independent() {
         (SendMode::Direct(client_id), 1),
         (SendMode::BroadcastExcept(ClientId::SERVER), 1),
         (SendMode::BroadcastExcept(client_id), 0),
+        (SendMode::Observers(Entity::PLACEHOLDER), 1),
     ] {
         server_app.world_mut().send_event(ToClients {
             mode,
@@ -553,6 +844,155 @@ fn independent_before_started_replication() {
     assert_eq!(client_app.world().resource::<Events<DummyEvent>>().len(), 1);
 }
 
+#[test]
+fn buffered_before_started_replication() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                replicate_after_connect: false,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+        .set_syncing_policy::<DummyEvent>(SyncingPolicy::Buffer)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Spawn entity to trigger world change.
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let events = client_app.world().resource::<Events<DummyEvent>>();
+    assert!(events.is_empty());
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    server_app.world_mut().trigger(StartReplication(client_id));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(client_app.world().resource::<Events<DummyEvent>>().len(), 1);
+}
+
+#[test]
+fn independent_syncing_policies() {
+    for (policy, events_count) in [
+        (SyncingPolicy::Send, 2),
+        (SyncingPolicy::Buffer, 2),
+        (SyncingPolicy::Drop, 1),
+    ] {
+        let mut server_app = App::new();
+        let mut client_app = App::new();
+        for app in [&mut server_app, &mut client_app] {
+            app.add_plugins((
+                MinimalPlugins,
+                RepliconPlugins.set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    replicate_after_connect: false,
+                    ..Default::default()
+                }),
+            ))
+            .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+            .make_independent::<DummyEvent>()
+            .set_syncing_policy::<DummyEvent>(policy)
+            .finish();
+        }
+
+        server_app.connect_client(&mut client_app);
+
+        server_app.world_mut().send_event(ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent,
+        });
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let client = client_app.world().resource::<RepliconClient>();
+        let client_id = client.id().unwrap();
+        server_app.world_mut().trigger(StartReplication(client_id));
+
+        server_app.world_mut().send_event(ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent,
+        });
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let events = client_app.world().resource::<Events<DummyEvent>>();
+        assert_eq!(
+            events.len(),
+            events_count,
+            "event should be emitted {events_count} times for {policy:?}"
+        );
+    }
+}
+
+#[test]
+fn held_cleared_on_disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                replicate_after_connect: false,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+        .set_syncing_policy::<DummyEvent>(SyncingPolicy::Buffer)
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+    });
+
+    server_app.update();
+    server_app.disconnect_client(&mut client_app);
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    server_app.world_mut().trigger(StartReplication(client_id));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let events = client_app.world().resource::<Events<DummyEvent>>();
+    assert!(events.is_empty());
+}
+
 #[test]
 fn different_ticks() {
     let mut server_app = App::new();
@@ -609,6 +1049,49 @@ fn different_ticks() {
     );
 }
 
+#[test]
+fn reregistration() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+
+        let channels_count = app
+            .world()
+            .resource::<RepliconChannels>()
+            .server_channels()
+            .len();
+        app.add_server_event::<DummyEvent>(ChannelKind::Unordered)
+            .finish();
+
+        let channels = app.world().resource::<RepliconChannels>();
+        assert_eq!(channels.server_channels().len(), channels_count);
+        let channel = channels.server_channels().last().unwrap();
+        assert_eq!(channel.kind, ChannelKind::Unordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut events = client_app.world_mut().resource_mut::<Events<DummyEvent>>();
+    assert_eq!(events.drain().count(), 1);
+}
+
 #[derive(Deserialize, Event, Serialize)]
 struct DummyEvent;
 
@@ -620,3 +1103,6 @@ impl MapEntities for EntityEvent {
         self.0 = entity_mapper.map_entity(self.0);
     }
 }
+
+#[derive(Deserialize, Event, Serialize)]
+struct IndependentEvent;
@@ -78,6 +78,49 @@ fn sending_receiving_with_target() {
     assert_eq!(reader.entities, [client_entity]);
 }
 
+#[test]
+fn sending_receiving_with_spawned_target() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_trigger::<DummyEvent>(ChannelKind::Ordered)
+        .finish();
+    }
+    client_app.init_resource::<TriggerReader<DummyEvent>>();
+
+    server_app.connect_client(&mut client_app);
+
+    // Trigger on the same tick as the spawn.
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+    server_app.world_mut().server_trigger_targets(
+        ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent,
+        },
+        server_entity,
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .get_by_server(server_entity)
+        .expect("entity should be replicated");
+
+    let reader = client_app.world().resource::<TriggerReader<DummyEvent>>();
+    assert_eq!(reader.entities, [client_entity]);
+}
+
 #[test]
 fn sending_receiving_and_mapping() {
     let mut server_app = App::new();
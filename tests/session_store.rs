@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{
+    prelude::*,
+    server::{
+        client_bundles::ClientOwner,
+        session_store::{
+            ClientIdentified, ClientIdentity, PersistSession, SessionRestored, SessionStore,
+        },
+    },
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn restore() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    let mut store = SessionStore::new(Duration::from_secs(60));
+    store.register_component::<Health>();
+    server_app
+        .insert_resource(store)
+        .init_resource::<RestoredClients>()
+        .add_observer(
+            |trigger: Trigger<SessionRestored>, mut restored: ResMut<RestoredClients>| {
+                restored.0.push(trigger.client_id);
+            },
+        );
+
+    const IDENTITY: ClientIdentity = ClientIdentity(1);
+
+    server_app.connect_client(&mut client_app);
+    let client_id = get_client_id(&client_app);
+    server_app.world_mut().trigger(ClientIdentified {
+        client_id,
+        identity: IDENTITY,
+    });
+    server_app
+        .world_mut()
+        .spawn((PersistSession, ClientOwner(client_id), Health(5), NotStored));
+
+    server_app.disconnect_client(&mut client_app);
+
+    let mut entities = server_app.world_mut().query::<&Health>();
+    assert_eq!(entities.iter(server_app.world()).count(), 0);
+    let store = server_app.world().resource::<SessionStore>();
+    assert!(store.contains(IDENTITY));
+
+    server_app.connect_client(&mut client_app);
+    let client_id = get_client_id(&client_app);
+    server_app.world_mut().trigger(ClientIdentified {
+        client_id,
+        identity: IDENTITY,
+    });
+    server_app.update();
+
+    let (owner, health, not_stored) = server_app
+        .world_mut()
+        .query::<(&ClientOwner, &Health, Has<NotStored>)>()
+        .single(server_app.world());
+    assert_eq!(**owner, client_id);
+    assert_eq!(health.0, 5);
+    assert!(!not_stored, "only registered components should be restored");
+
+    let store = server_app.world().resource::<SessionStore>();
+    assert!(store.is_empty());
+    assert_eq!(store.identity(client_id), Some(IDENTITY));
+
+    let restored = server_app.world().resource::<RestoredClients>();
+    assert_eq!(restored.0, [client_id]);
+}
+
+#[test]
+fn expired() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    let mut store = SessionStore::new(Duration::ZERO);
+    store.register_component::<Health>();
+    server_app
+        .insert_resource(store)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)));
+
+    server_app.connect_client(&mut client_app);
+    let client_id = get_client_id(&client_app);
+    server_app.world_mut().trigger(ClientIdentified {
+        client_id,
+        identity: ClientIdentity(1),
+    });
+    server_app
+        .world_mut()
+        .spawn((PersistSession, ClientOwner(client_id), Health(5)));
+
+    server_app.disconnect_client(&mut client_app);
+    server_app.update();
+
+    let store = server_app.world().resource::<SessionStore>();
+    assert!(store.is_empty());
+}
+
+fn get_client_id(client_app: &App) -> ClientId {
+    let client = client_app.world().resource::<RepliconClient>();
+    client.id().unwrap()
+}
+
+#[derive(Resource, Default)]
+struct RestoredClients(Vec<ClientId>);
+
+#[derive(Component)]
+struct Health(u32);
+
+#[derive(Component)]
+struct NotStored;
@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{
+        replication::replicated_singletons::{AppSingletonExt, ReplicatedSingleton},
+        server_entity_map::ServerEntityMap,
+    },
+    prelude::*,
+    server::server_epoch::ServerResetExt,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn spawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<MatchState>()
+        .replicate_singleton::<MatchState>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let server_singleton = server_app
+        .world()
+        .resource::<ReplicatedSingleton<MatchState>>();
+    let server_entity = server_singleton.entity();
+    assert!(server_app
+        .world()
+        .get::<Replicated>(server_entity)
+        .is_some());
+
+    let client_singleton = client_app
+        .world()
+        .resource::<ReplicatedSingleton<MatchState>>();
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.get_by_server(server_entity),
+        Some(client_singleton.entity())
+    );
+    assert_eq!(
+        client_app
+            .world()
+            .get::<MatchState>(client_singleton.entity()),
+        Some(&MatchState(0))
+    );
+}
+
+#[test]
+fn reset() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<MatchState>()
+        .replicate_singleton::<MatchState>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let singleton = server_app
+        .world()
+        .resource::<ReplicatedSingleton<MatchState>>();
+    let server_entity = singleton.entity();
+    server_app
+        .world_mut()
+        .get_mut::<MatchState>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_singleton = client_app
+        .world()
+        .resource::<ReplicatedSingleton<MatchState>>();
+    let client_entity = client_singleton.entity();
+    assert_eq!(
+        client_app.world().get::<MatchState>(client_entity),
+        Some(&MatchState(1))
+    );
+
+    server_app.world_mut().despawn_all_replicated();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let singleton = server_app
+        .world()
+        .resource::<ReplicatedSingleton<MatchState>>();
+    assert_ne!(singleton.entity(), server_entity);
+
+    let client_singleton = client_app
+        .world()
+        .resource::<ReplicatedSingleton<MatchState>>();
+    assert_ne!(client_singleton.entity(), client_entity);
+    assert!(client_app.world().get_entity(client_entity).is_err());
+    assert_eq!(
+        client_app
+            .world()
+            .get::<MatchState>(client_singleton.entity()),
+        Some(&MatchState(0)),
+        "singleton should be recreated with default values"
+    );
+}
+
+#[derive(Component, Clone, Default, Deserialize, Serialize, Debug, PartialEq)]
+struct MatchState(u32);
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{
+        channels::{ChannelKind, ReplicationChannel},
+        postcard_utils,
+        replication::replication_registry::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
+    },
+    prelude::*,
+    server::smoke_test::{
+        ChannelIssueKind, ChannelSide, ComponentStatus, MessageStatus, SmokeTestExt,
+    },
+};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn components() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .register_type::<ReflectedComponent>()
+        .register_type::<BrokenComponent>()
+        .replicate::<ReflectedComponent>()
+        .replicate::<NotReflectedComponent>()
+        .replicate_with(RuleFns::new(serialize_broken, deserialize_broken));
+
+    let report = app.world_mut().run_smoke_test();
+    assert!(report.channel_issues.is_empty());
+    assert!(!report.is_ok(), "broken component should fail the check");
+
+    let status = |name: &str| {
+        &report
+            .components
+            .iter()
+            .find(|component| component.name.ends_with(name))
+            .unwrap_or_else(|| panic!("`{name}` should be checked"))
+            .status
+    };
+    assert_eq!(*status("::ReflectedComponent"), ComponentStatus::Passed);
+    assert_eq!(
+        *status("::NotReflectedComponent"),
+        ComponentStatus::NotReflected
+    );
+    assert!(matches!(
+        status("::BrokenComponent"),
+        ComponentStatus::Failed(_)
+    ));
+    assert_eq!(report.failed_components().count(), 1);
+
+    let MessageStatus::Built { components, bytes } = report.message else {
+        panic!("message should be built");
+    };
+    assert_eq!(components, 1);
+    assert!(bytes > 0);
+
+    let mut query = app.world_mut().query::<&ReflectedComponent>();
+    assert_eq!(
+        query.iter(app.world()).count(),
+        0,
+        "temporary entities should be despawned"
+    );
+}
+
+#[test]
+fn channels() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+        .finish();
+
+    let report = app.world_mut().run_smoke_test();
+    assert!(report.is_ok());
+
+    let mut channels = app.world_mut().resource_mut::<RepliconChannels>();
+    channels
+        .server_channel_mut(ReplicationChannel::Updates)
+        .kind = ChannelKind::Unreliable;
+    channels
+        .client_channel_mut(ReplicationChannel::Mutations)
+        .max_bytes = Some(0);
+
+    let report = app.world_mut().run_smoke_test();
+    assert!(!report.is_ok());
+    assert!(report
+        .channel_issues
+        .iter()
+        .any(|issue| issue.side == ChannelSide::Server
+            && issue.channel_id == ReplicationChannel::Updates as u8
+            && matches!(
+                issue.kind,
+                ChannelIssueKind::WrongKind {
+                    expected: ChannelKind::Ordered,
+                    actual: ChannelKind::Unreliable,
+                    ..
+                }
+            )));
+    assert!(report
+        .channel_issues
+        .iter()
+        .any(|issue| issue.side == ChannelSide::Client
+            && issue.channel_id == ReplicationChannel::Mutations as u8
+            && issue.kind == ChannelIssueKind::ZeroMaxBytes));
+
+    // Emulate channels overwritten after registration of events.
+    app.insert_resource(RepliconChannels::default());
+
+    let report = app.world_mut().run_smoke_test();
+    assert!(report.channel_issues.iter().any(|issue| issue.side
+        == ChannelSide::Server
+        && matches!(issue.kind, ChannelIssueKind::Missing { usage } if usage.ends_with("::DummyEvent"))));
+}
+
+/// Writes an extra byte that won't be consumed by [`deserialize_broken`].
+fn serialize_broken(
+    _ctx: &SerializeCtx,
+    component: &BrokenComponent,
+    message: &mut Vec<u8>,
+) -> postcard::Result<()> {
+    postcard_utils::to_extend_mut(&component.0, message)?;
+    message.push(0);
+    Ok(())
+}
+
+fn deserialize_broken(
+    _ctx: &mut WriteCtx,
+    message: &mut Bytes,
+) -> postcard::Result<BrokenComponent> {
+    let value = postcard_utils::from_buf(message)?;
+    Ok(BrokenComponent(value))
+}
+
+#[derive(Component, Default, Deserialize, Reflect, Serialize)]
+#[reflect(Component, Default)]
+struct ReflectedComponent(u32);
+
+#[derive(Component, Deserialize, Serialize)]
+struct NotReflectedComponent;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+struct BrokenComponent(u32);
+
+#[derive(Deserialize, Event, Serialize)]
+struct DummyEvent;
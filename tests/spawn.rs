@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use bevy_replicon::{
-    client::confirm_history::ConfirmHistory, core::server_entity_map::ServerEntityMap, prelude::*,
+    client::confirm_history::ConfirmHistory,
+    core::server_entity_map::{MappingConflictPolicy, ServerEntityMap},
+    prelude::*,
     test_app::ServerTestAppExt,
 };
 use serde::{Deserialize, Serialize};
@@ -223,6 +225,193 @@ fn pre_spawn() {
     );
 }
 
+#[test]
+fn pre_spawn_pending() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn_empty().id();
+    let server_entity = server_app.world_mut().spawn(DummyComponent).id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = server_app.world().resource::<ClientEntityMap>();
+    assert_eq!(
+        entity_map.pending(client_id).len(),
+        1,
+        "mapping should wait for the server entity to be replicated"
+    );
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = server_app.world().resource::<ClientEntityMap>();
+    assert!(entity_map.pending(client_id).is_empty());
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity),
+    );
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(client_entity.contains::<DummyComponent>());
+}
+
+#[test]
+fn pre_spawn_expiry() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                mappings_timeout: 1,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn_empty().id();
+    let server_entity = server_app.world_mut().spawn_empty().id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+
+    let expired_events = server_app
+        .world()
+        .resource::<Events<ClientMappingExpired>>();
+    assert!(expired_events.is_empty(), "mapping shouldn't expire yet");
+
+    server_app.update();
+
+    let mut expired_events = server_app
+        .world_mut()
+        .resource_mut::<Events<ClientMappingExpired>>();
+    let events: Vec<_> = expired_events.drain().collect();
+    assert_eq!(events.len(), 1, "mapping should expire after the timeout");
+    let event = events[0];
+    assert_eq!(event.client_id, client_id);
+    assert_eq!(event.mapping.server_entity, server_entity);
+    assert_eq!(event.mapping.client_entity, client_entity);
+
+    let entity_map = server_app.world().resource::<ClientEntityMap>();
+    assert!(entity_map.pending(client_id).is_empty());
+}
+
+#[test]
+fn pre_spawn_conflict() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn_empty().id();
+    let other_entity = client_app.world_mut().spawn_empty().id();
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.set_conflict_policy(MappingConflictPolicy::Reject);
+    assert!(entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    ));
+    assert!(!entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity: other_entity,
+        },
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut conflict_events = server_app
+        .world_mut()
+        .resource_mut::<Events<ClientMappingConflict>>();
+    let events: Vec<_> = conflict_events.drain().collect();
+    assert_eq!(events.len(), 1, "conflicting mapping should be rejected");
+    let event = events[0];
+    assert_eq!(event.client_id, client_id);
+    assert_eq!(event.conflict.client_entity, other_entity);
+    assert_eq!(event.conflict.mapped_client, Some(client_entity));
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity),
+        "first mapping should be kept"
+    );
+}
+
 #[test]
 fn after_despawn() {
     let mut server_app = App::new();
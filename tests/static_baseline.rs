@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::static_baseline::{
+        BaselineEntity, BaselineResponse, StaticBaseline, StaticBaselinePlugin,
+    },
+    client::ClientReplicationStats,
+    core::server_entity_map::ServerEntityMap,
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn accepted() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                replicate_after_connect: false,
+                ..Default::default()
+            }),
+            StaticBaselinePlugin,
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+    client_app.init_resource::<ClientReplicationStats>();
+
+    let [server_unchanged, server_overridden, server_despawned] = load_baseline(&mut server_app, 1);
+    let [client_unchanged, client_overridden, client_despawned] = load_baseline(&mut client_app, 1);
+
+    server_app.update();
+    client_app.update();
+
+    server_app
+        .world_mut()
+        .get_mut::<TestComponent>(server_overridden)
+        .unwrap()
+        .0 = 1;
+    server_app.world_mut().despawn(server_despawned);
+    server_app.world_mut().spawn((Replicated, TestComponent(2)));
+
+    server_app.connect_client(&mut client_app);
+    for _ in 0..2 {
+        server_app.exchange_with_client(&mut client_app);
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+    }
+
+    let mut responses = client_app
+        .world_mut()
+        .resource_mut::<Events<BaselineResponse>>();
+    assert_eq!(
+        responses.drain().collect::<Vec<_>>(),
+        [BaselineResponse::Accepted(vec![BaselineEntity(2)])]
+    );
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.get_by_server(server_unchanged),
+        Some(client_unchanged)
+    );
+    assert_eq!(
+        entity_map.get_by_server(server_overridden),
+        Some(client_overridden)
+    );
+    assert!(client_app.world().get_entity(client_despawned).is_err());
+
+    let component = client_app
+        .world()
+        .get::<TestComponent>(client_overridden)
+        .unwrap();
+    assert_eq!(component.0, 1, "override should be replicated");
+
+    let mut components = client_app.world_mut().query::<&TestComponent>();
+    assert_eq!(components.iter(client_app.world()).count(), 3);
+
+    let stats = client_app.world().resource::<ClientReplicationStats>();
+    assert_eq!(stats.mappings, 2);
+    assert_eq!(
+        stats.components_changed, 2,
+        "only the override and the new entity should be sent"
+    );
+}
+
+#[test]
+fn accepted_without_changes() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                replicate_after_connect: false,
+                ..Default::default()
+            }),
+            StaticBaselinePlugin,
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    let server_entities = load_baseline(&mut server_app, 1);
+    let client_entities = load_baseline(&mut client_app, 1);
+
+    server_app.update();
+    client_app.update();
+
+    server_app.connect_client(&mut client_app);
+    for _ in 0..2 {
+        server_app.exchange_with_client(&mut client_app);
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+    }
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    for (server_entity, client_entity) in server_entities.into_iter().zip(client_entities) {
+        assert_eq!(entity_map.get_by_server(server_entity), Some(client_entity));
+    }
+}
+
+#[test]
+fn rejected() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                replicate_after_connect: false,
+                ..Default::default()
+            }),
+            StaticBaselinePlugin,
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    let server_entities = load_baseline(&mut server_app, 1);
+    let client_entities = load_baseline(&mut client_app, 2);
+
+    server_app.update();
+    client_app.update();
+
+    server_app.connect_client(&mut client_app);
+    for _ in 0..2 {
+        server_app.exchange_with_client(&mut client_app);
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+    }
+
+    let mut responses = client_app
+        .world_mut()
+        .resource_mut::<Events<BaselineResponse>>();
+    assert_eq!(
+        responses.drain().collect::<Vec<_>>(),
+        [BaselineResponse::Rejected]
+    );
+
+    for entity in client_entities {
+        assert!(client_app.world().get_entity(entity).is_err());
+    }
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    for entity in server_entities {
+        assert!(entity_map.get_by_server(entity).is_some());
+    }
+
+    let mut components = client_app.world_mut().query::<&TestComponent>();
+    assert_eq!(components.iter(client_app.world()).count(), 3);
+}
+
+fn load_baseline(app: &mut App, hash: u64) -> [Entity; 3] {
+    let entities = [0, 1, 2].map(|index| {
+        app.world_mut()
+            .spawn((Replicated, BaselineEntity(index), TestComponent(0)))
+            .id()
+    });
+    app.insert_resource(StaticBaseline::new(hash));
+
+    entities
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u8);
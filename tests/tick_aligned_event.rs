@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    addons::tick_aligned_event::{TickAligned, TickAlignedEventAppExt, TickAlignmentSettings},
+    prelude::*,
+    server::server_tick::ServerTick,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn holding() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .add_tick_aligned_client_event::<DummyEvent>(ChannelKind::Ordered)
+    .finish();
+
+    app.world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    app.update();
+
+    let tick = **app.world().resource::<ServerTick>();
+    for aligned in [
+        TickAligned::new(tick + 2, DummyEvent(0)),
+        TickAligned::new(tick + 1, DummyEvent(1)),
+        TickAligned::new(tick, DummyEvent(2)),
+    ] {
+        app.world_mut().send_event(FromClient {
+            client_id: ClientId::SERVER,
+            event: aligned,
+        });
+    }
+
+    app.update();
+    assert_eq!(
+        drain_events(&mut app),
+        [2, 1],
+        "late events shouldn't be held and should be sorted by tick"
+    );
+
+    app.update();
+    assert_eq!(drain_events(&mut app), [0]);
+
+    app.update();
+    assert!(drain_events(&mut app).is_empty());
+}
+
+#[test]
+fn too_early() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .add_tick_aligned_client_event::<DummyEvent>(ChannelKind::Ordered)
+    .insert_resource(TickAlignmentSettings { max_early_ticks: 1 })
+    .finish();
+
+    app.world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    app.update();
+
+    let tick = **app.world().resource::<ServerTick>();
+    app.world_mut().send_event(FromClient {
+        client_id: ClientId::SERVER,
+        event: TickAligned::new(tick + 3, DummyEvent(0)),
+    });
+
+    for _ in 0..3 {
+        app.update();
+        assert!(drain_events(&mut app).is_empty());
+    }
+}
+
+#[test]
+fn singleplayer() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_tick_aligned_client_event::<DummyEvent>(ChannelKind::Ordered)
+        .finish();
+
+    let tick = **app.world().resource::<ServerTick>();
+    app.world_mut()
+        .send_event(TickAligned::new(tick + 10, DummyEvent(0)));
+
+    app.update();
+    app.update();
+
+    assert_eq!(drain_events(&mut app), [0]);
+}
+
+fn drain_events(app: &mut App) -> Vec<u8> {
+    app.world_mut()
+        .resource_mut::<Events<FromClient<DummyEvent>>>()
+        .drain()
+        .map(|event| event.event.0)
+        .collect()
+}
+
+#[derive(Deserialize, Serialize)]
+struct DummyEvent(u8);
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use bevy_replicon::{client::ServerTickRange, prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn batching() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ticks_per_send: 3,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Connection already ran the first tick, complete the first batch.
+    for _ in 0..2 {
+        server_app.update();
+    }
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    assert_eq!(
+        components.iter(client_app.world()).len(),
+        0,
+        "changes should be held until the batch is complete"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = components.single(client_app.world());
+    assert!(
+        component.0,
+        "mutations should be coalesced to the latest value"
+    );
+
+    let tick_range = *client_app.world().resource::<ServerTickRange>();
+    assert_eq!(tick_range.ticks_count(), 3);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
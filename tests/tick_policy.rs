@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use bevy::{ecs::event::Events, prelude::*, time::TimeUpdateStrategy};
+use bevy_replicon::{
+    core::tick_policy::TickRateChanged,
+    prelude::*,
+    server::server_tick::{ServerTick, ServerTickPolicy},
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn change() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let events = client_app.world().resource::<Events<TickRateChanged>>();
+    assert!(events.is_empty(), "policy wasn't changed yet");
+
+    server_app
+        .world_mut()
+        .resource_mut::<ServerTickPolicy>()
+        .set(TickPolicy::Manual);
+    let tick = **server_app.world().resource::<ServerTick>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        **server_app.world().resource::<ServerTick>(),
+        tick,
+        "tick shouldn't be incremented with manual policy"
+    );
+
+    let expected = TickRateChanged {
+        policy: TickPolicy::Manual,
+    };
+    let mut events = client_app
+        .world_mut()
+        .resource_mut::<Events<TickRateChanged>>();
+    assert_eq!(events.drain().collect::<Vec<_>>(), [expected]);
+
+    let mut events = server_app
+        .world_mut()
+        .resource_mut::<Events<TickRateChanged>>();
+    assert_eq!(
+        events.drain().collect::<Vec<_>>(),
+        [expected],
+        "event should also be emitted on server"
+    );
+
+    server_app
+        .world_mut()
+        .resource_mut::<ServerTickPolicy>()
+        .set(TickPolicy::EveryFrame);
+
+    server_app.update();
+
+    assert_eq!(**server_app.world().resource::<ServerTick>(), tick + 1);
+}
+
+#[test]
+fn late_client() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .finish();
+    }
+
+    server_app
+        .world_mut()
+        .resource_mut::<ServerTickPolicy>()
+        .set(TickPolicy::MaxTickRate(20));
+
+    server_app.connect_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut events = client_app
+        .world_mut()
+        .resource_mut::<Events<TickRateChanged>>();
+    assert_eq!(
+        events.drain().collect::<Vec<_>>(),
+        [TickRateChanged {
+            policy: TickPolicy::MaxTickRate(20)
+        }],
+        "client should receive the policy on connect"
+    );
+}
+
+#[test]
+fn zero_max_tick_rate() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            200,
+        )))
+        .finish();
+
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    server_app
+        .world_mut()
+        .resource_mut::<ServerTickPolicy>()
+        .set(TickPolicy::MaxTickRate(0));
+    let tick = **server_app.world().resource::<ServerTick>();
+
+    // First update initializes the time.
+    for _ in 0..6 {
+        server_app.update();
+    }
+
+    assert_eq!(
+        **server_app.world().resource::<ServerTick>(),
+        tick + 1,
+        "zero rate should be treated as one tick per second"
+    );
+}
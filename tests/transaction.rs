@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::server_entity_map::ServerEntityMap, prelude::*,
+    server::replication_transaction::ReplicationTransaction, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn commit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(ReplicationTransaction);
+
+    let mut server_entity_mut = server_app.world_mut().entity_mut(server_entity);
+    server_entity_mut.get_mut::<BoolComponent>().unwrap().0 = true;
+    server_entity_mut.insert(DummyComponent);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let (component, dummy) = client_app
+        .world_mut()
+        .query::<(&BoolComponent, Option<&DummyComponent>)>()
+        .single(client_app.world());
+    assert!(!component.0, "mutation should be held back");
+    assert!(dummy.is_none(), "insertion should be held back");
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<ReplicationTransaction>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (component, dummy) = client_app
+        .world_mut()
+        .query::<(&BoolComponent, Option<&DummyComponent>)>()
+        .single(client_app.world());
+    assert!(component.0);
+    assert!(dummy.is_some());
+}
+
+#[test]
+fn visibility_gained() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, ReplicationTransaction))
+        .id();
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(server_entity, true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(
+        entity_map.get_by_server(server_entity).is_none(),
+        "entity should be held back"
+    );
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<ReplicationTransaction>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(
+        entity_map.get_by_server(server_entity).is_some(),
+        "entity should be sent after commit"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{channels::ReplicationChannel, server_entity_map::ServerEntityMap},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn drop_oldest() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_max_unreliable_queue(Some(2));
+
+    // Simulate a stalled backend that doesn't drain sent messages.
+    const UPDATES: u32 = 5;
+    for value in 1..=UPDATES {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<TestComponent>(server_entity)
+            .unwrap();
+        component.0 = value;
+        server_app.world_mut().spawn((Replicated, TestComponent(0)));
+
+        server_app.update();
+    }
+
+    let messages: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .collect();
+    let updates_count = messages
+        .iter()
+        .filter(|&&(_, channel_id, _)| channel_id == ReplicationChannel::Updates as u8)
+        .count();
+    let mutations_count = messages
+        .iter()
+        .filter(|&&(_, channel_id, _)| channel_id == ReplicationChannel::Mutations as u8)
+        .count();
+    assert_eq!(
+        updates_count, UPDATES as usize,
+        "reliable messages shouldn't be dropped"
+    );
+    assert_eq!(mutations_count, 2);
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in messages {
+        client.insert_received(channel_id, message);
+    }
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&TestComponent>();
+    assert_eq!(
+        components.iter(client_app.world()).count(),
+        1 + UPDATES as usize
+    );
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity = *entity_map.to_client().get(&server_entity).unwrap();
+    let component = client_app
+        .world()
+        .get::<TestComponent>(client_entity)
+        .unwrap();
+    assert_eq!(component.0, UPDATES, "the newest mutations should be kept");
+}
+
+#[test]
+fn unlimited() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<TestComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    const UPDATES: u32 = 5;
+    for value in 1..=UPDATES {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<TestComponent>(server_entity)
+            .unwrap();
+        component.0 = value;
+
+        server_app.update();
+    }
+
+    let mutations_count = server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .filter(|&(_, channel_id, _)| channel_id == ReplicationChannel::Mutations as u8)
+        .count();
+    assert_eq!(mutations_count, UPDATES as usize);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct TestComponent(u32);
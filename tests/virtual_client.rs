@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn replication() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let client_id = ClientId::new(u64::MAX);
+    server_app
+        .world_mut()
+        .resource_mut::<ReplicatedClients>()
+        .add_virtual_client(client_id);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    assert_eq!(
+        server.drain_sent().count(),
+        0,
+        "messages for virtual clients shouldn't be passed to backend"
+    );
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let client = replicated_clients.client(client_id);
+    assert!(client.is_virtual());
+    assert!(client.mutation_tick(server_entity).is_some());
+    let stats = *client.virtual_stats().unwrap();
+    assert_eq!(stats.update_messages, 1);
+    assert_eq!(stats.mutate_messages, 0);
+
+    for _ in 0..2 {
+        server_app
+            .world_mut()
+            .get_mut::<BoolComponent>(server_entity)
+            .unwrap()
+            .0 ^= true;
+        server_app.update();
+    }
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let stats = replicated_clients
+        .client(client_id)
+        .virtual_stats()
+        .copied()
+        .unwrap();
+    assert_eq!(stats.update_messages, 1);
+    assert_eq!(
+        stats.mutate_messages, 2,
+        "mutations should be acknowledged immediately"
+    );
+
+    server_app.update();
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    let stats = replicated_clients
+        .client(client_id)
+        .virtual_stats()
+        .copied()
+        .unwrap();
+    assert_eq!(stats.mutate_messages, 2);
+
+    assert!(replicated_clients.remove_virtual_client(client_id));
+    assert!(replicated_clients.is_empty());
+}
+
+#[test]
+fn visibility() {
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .finish();
+
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let client_id = ClientId::new(u64::MAX);
+    server_app
+        .world_mut()
+        .resource_mut::<ReplicatedClients>()
+        .add_virtual_client(client_id);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let client = replicated_clients.client(client_id);
+    assert!(client.mutation_tick(server_entity).is_none());
+    assert_eq!(client.virtual_stats().unwrap().update_messages, 0);
+
+    server_app
+        .world_mut()
+        .resource_mut::<ReplicatedClients>()
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(server_entity, true);
+
+    server_app.update();
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let client = replicated_clients.client(client_id);
+    assert!(client.mutation_tick(server_entity).is_some());
+    assert_eq!(client.virtual_stats().unwrap().update_messages, 1);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
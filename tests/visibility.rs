@@ -1,5 +1,9 @@
 use bevy::prelude::*;
-use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use bevy_replicon::{
+    core::{channels::ReplicationChannel, server_entity_map::ServerEntityMap},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -176,6 +180,72 @@ fn blacklist_with_despawn() {
     assert!(visibility.is_visible(server_entity)); // The missing entity must be removed from the list, so this should return `true`.
 }
 
+#[test]
+fn blacklist_many() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Blacklist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entities: Vec<_> = (0..20)
+        .map(|_| {
+            server_app
+                .world_mut()
+                .spawn((Replicated, DummyComponent))
+                .id()
+        })
+        .collect();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 20);
+
+    // Hide enough entities at once to be sent as a batch.
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    let visibility = replicated_clients.client_mut(client_id).visibility_mut();
+    for &server_entity in server_entities
+        .iter()
+        .step_by(2)
+        .chain(&server_entities[..3])
+    {
+        visibility.set_visibility(server_entity, false);
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(replicated.iter(client_app.world()).count(), 9);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    for (index, server_entity) in server_entities.into_iter().enumerate() {
+        let hidden = index % 2 == 0 || index < 3;
+        assert_eq!(
+            entity_map.to_client().contains_key(&server_entity),
+            !hidden,
+            "entity {index} should be {}",
+            if hidden { "hidden" } else { "visible" }
+        );
+    }
+}
+
 #[test]
 fn empty_whitelist() {
     let mut server_app = App::new();
@@ -300,5 +370,130 @@ fn whitelist_with_despawn() {
     assert!(!visibility.is_visible(server_entity));
 }
 
+#[test]
+fn hidden_mutations() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let visible_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+    let hidden_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    let visibility = replicated_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(visible_entity, true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(entity_map.len(), 1);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(hidden_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    assert!(
+        server
+            .drain_sent()
+            .all(|(_, channel_id, _)| channel_id != ReplicationChannel::Mutations as u8),
+        "mutations of hidden entities shouldn't be sent"
+    );
+}
+
+#[test]
+fn policy_change() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let visible_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+    server_app.world_mut().spawn((Replicated, DummyComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut replicated_query = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated_query.iter(client_app.world()).count(), 2);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients.set_visibility_policy(VisibilityPolicy::Whitelist);
+    replicated_clients
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(visible_entity, true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().keys().copied().collect::<Vec<_>>(),
+        [visible_entity],
+        "only whitelisted entity should remain"
+    );
+    assert_eq!(replicated_query.iter(client_app.world()).count(), 1);
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients.set_visibility_policy(VisibilityPolicy::All);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(replicated_query.iter(client_app.world()).count(), 2);
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
@@ -0,0 +1,276 @@
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_replicon::{
+    addons::vote::{AppVoteExt, Vote, VoteBallots, VoteCast, VoteResult},
+    core::server_entity_map::ServerEntityMap,
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn all_voted() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_vote::<TestOption>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let server_entity = server_app
+        .world_mut()
+        .spawn(Vote::new(
+            vec![TestOption(0), TestOption(1)],
+            server_tick + 100,
+        ))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .get_by_server(server_entity)
+        .expect("vote should be replicated");
+
+    client_app
+        .world_mut()
+        .send_event(VoteCast::<TestOption>::new(client_entity, 1));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let ballots = server_app
+        .world()
+        .get::<VoteBallots>(server_entity)
+        .unwrap();
+    assert!(
+        ballots.is_resolved(),
+        "vote should end when all clients voted"
+    );
+
+    let server_results = drain_results(&mut server_app);
+    assert_eq!(
+        server_results,
+        [(server_entity, Some(TestOption(1)), vec![0, 1])]
+    );
+
+    let client_results = drain_results(&mut client_app);
+    assert_eq!(
+        client_results,
+        [(client_entity, Some(TestOption(1)), vec![0, 1])]
+    );
+}
+
+#[test]
+fn deadline() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_vote::<TestOption>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let server_entity = server_app
+        .world_mut()
+        .spawn(Vote::new(
+            vec![TestOption(0), TestOption(1)],
+            server_tick + 2,
+        ))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    assert!(drain_results(&mut server_app).is_empty());
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_results = drain_results(&mut server_app);
+    assert_eq!(server_results, [(server_entity, None, vec![0, 0])]);
+
+    let client_results = drain_results(&mut client_app);
+    assert_eq!(client_results.len(), 1);
+}
+
+#[test]
+fn invalid_option() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_vote::<TestOption>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let server_entity = server_app
+        .world_mut()
+        .spawn(Vote::new(vec![TestOption(0)], server_tick + 100))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .get_by_server(server_entity)
+        .expect("vote should be replicated");
+
+    client_app
+        .world_mut()
+        .send_event(VoteCast::<TestOption>::new(client_entity, 1));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let ballots = server_app
+        .world()
+        .get::<VoteBallots>(server_entity)
+        .unwrap();
+    assert!(ballots.is_empty());
+    assert!(!ballots.is_resolved());
+    assert!(drain_results(&mut server_app).is_empty());
+}
+
+#[test]
+fn disconnected_voter() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_vote::<TestOption>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let server_entity = server_app
+        .world_mut()
+        .spawn(Vote::new(
+            vec![TestOption(0), TestOption(1)],
+            server_tick + 100,
+        ))
+        .id();
+
+    server_app.update();
+    for client_app in [&mut client_app1, &mut client_app2] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+        server_app.exchange_with_client(client_app);
+    }
+
+    let client_entity1 = client_app1
+        .world()
+        .resource::<ServerEntityMap>()
+        .get_by_server(server_entity)
+        .expect("vote should be replicated");
+
+    client_app1
+        .world_mut()
+        .send_event(VoteCast::<TestOption>::new(client_entity1, 1));
+
+    client_app1.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.update();
+
+    let ballots = server_app
+        .world()
+        .get::<VoteBallots>(server_entity)
+        .unwrap();
+    assert_eq!(ballots.len(), 1);
+
+    server_app.disconnect_client(&mut client_app1);
+    server_app.update();
+
+    let ballots = server_app
+        .world()
+        .get::<VoteBallots>(server_entity)
+        .unwrap();
+    assert!(
+        ballots.is_empty(),
+        "ballots of disconnected clients should be dropped"
+    );
+    assert!(!ballots.is_resolved());
+
+    let client_entity2 = client_app2
+        .world()
+        .resource::<ServerEntityMap>()
+        .get_by_server(server_entity)
+        .expect("vote should be replicated");
+
+    client_app2
+        .world_mut()
+        .send_event(VoteCast::<TestOption>::new(client_entity2, 0));
+
+    client_app2.update();
+    server_app.exchange_with_client(&mut client_app2);
+    server_app.update();
+
+    let server_results = drain_results(&mut server_app);
+    assert_eq!(
+        server_results,
+        [(server_entity, Some(TestOption(0)), vec![1, 0])]
+    );
+}
+
+fn drain_results(app: &mut App) -> Vec<(Entity, Option<TestOption>, Vec<u32>)> {
+    app.world_mut()
+        .resource_mut::<Events<VoteResult<TestOption>>>()
+        .drain()
+        .map(|result| (result.vote, result.winner, result.counts))
+        .collect()
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct TestOption(u8);
@@ -0,0 +1,262 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    client::world_reset::{ReceivedEpoch, WorldReset},
+    core::{
+        channels::ReplicationChannel,
+        protocol::{
+            compression_kind::CompressionKind, postcard_utils, protocol_version::ProtocolVersion,
+            replication_epoch::ReplicationEpoch, update_message_flags::UpdateMessageFlags,
+        },
+        server_entity_map::ServerEntityMap,
+    },
+    prelude::*,
+    server::server_epoch::ServerEpoch,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn reset() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity = entity_map.get_by_server(server_entity).unwrap();
+    let received_epoch = client_app.world().resource::<ReceivedEpoch>();
+    assert_eq!(**received_epoch, Some(ReplicationEpoch::default()));
+
+    server_app.world_mut().despawn(server_entity);
+    server_app
+        .world_mut()
+        .resource_mut::<ServerEpoch>()
+        .advance();
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(1)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(client_app.world().get_entity(client_entity).is_err());
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    let component = components.single(client_app.world());
+    assert_eq!(component.0, 1);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(entity_map.len(), 1);
+
+    let reset_events = client_app.world().resource::<Events<WorldReset>>();
+    let events: Vec<_> = reset_events.iter_current_update_events().copied().collect();
+    assert_eq!(
+        events,
+        [WorldReset {
+            epoch: ReplicationEpoch::new(1)
+        }]
+    );
+}
+
+#[test]
+fn kept_entity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity = entity_map.get_by_server(server_entity).unwrap();
+
+    server_app
+        .world_mut()
+        .resource_mut::<ServerEpoch>()
+        .advance();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert!(
+        client_app.world().get_entity(client_entity).is_err(),
+        "entity from the previous epoch should be despawned"
+    );
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let new_entity = entity_map
+        .get_by_server(server_entity)
+        .expect("entity should be sent again in the new epoch");
+    assert!(client_app
+        .world()
+        .get::<DummyComponent>(new_entity)
+        .is_some());
+
+    let reset_events = client_app.world().resource::<Events<WorldReset>>();
+    assert_eq!(reset_events.len(), 1);
+}
+
+#[test]
+fn first_epoch() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app
+        .world_mut()
+        .resource_mut::<ServerEpoch>()
+        .advance();
+    server_app.update();
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent(0)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let received_epoch = client_app.world().resource::<ReceivedEpoch>();
+    assert_eq!(**received_epoch, Some(ReplicationEpoch::new(1)));
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    assert_eq!(components.iter(client_app.world()).count(), 1);
+
+    let reset_events = client_app.world().resource::<Events<WorldReset>>();
+    assert!(
+        reset_events.is_empty(),
+        "client should adopt the epoch on connection"
+    );
+}
+
+#[test]
+fn despawn_all_replicated() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    for value in 0..10 {
+        server_app
+            .world_mut()
+            .spawn((Replicated, DummyComponent(value)));
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(entity_map.len(), 10);
+
+    server_app.world_mut().despawn_all_replicated();
+
+    server_app.update();
+
+    let messages: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .collect();
+    let [(_, channel_id, message)] = messages.as_slice() else {
+        panic!("only a single message should be sent");
+    };
+    assert_eq!(*channel_id, ReplicationChannel::Updates as u8);
+
+    let mut header = message.clone();
+    let version: ProtocolVersion = postcard_utils::from_buf(&mut header).unwrap();
+    assert_eq!(version, ProtocolVersion::CURRENT);
+    let compression: CompressionKind = postcard_utils::from_buf(&mut header).unwrap();
+    assert_eq!(compression, CompressionKind::None);
+    let flags: UpdateMessageFlags = postcard_utils::from_buf(&mut header).unwrap();
+    assert!(
+        flags.sections().is_empty(),
+        "message shouldn't contain despawns"
+    );
+    assert!(flags.contains(UpdateMessageFlags::EPOCH));
+    let epoch: ReplicationEpoch = postcard_utils::from_buf(&mut header).unwrap();
+    assert_eq!(epoch, ReplicationEpoch::new(1));
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in messages {
+        client.insert_received(channel_id, message);
+    }
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    assert_eq!(components.iter(client_app.world()).count(), 0);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.is_empty());
+
+    let reset_events = client_app.world().resource::<Events<WorldReset>>();
+    assert_eq!(reset_events.len(), 1);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent(u8);